@@ -118,6 +118,10 @@ pub mod rpc {
     #[doc(inline)]
     pub use alloy_json_rpc as json_rpc;
 
+    #[cfg(feature = "rpc-proxy")]
+    #[doc(inline)]
+    pub use alloy_rpc_proxy as proxy;
+
     /// Ethereum JSON-RPC type definitions.
     #[cfg(feature = "rpc-types")]
     #[doc(inline)]
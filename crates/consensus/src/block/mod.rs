@@ -0,0 +1,187 @@
+//! Genesic Block Type
+
+use crate::{
+    proofs::{calculate_ommers_root, calculate_transaction_root, calculate_withdrawals_root},
+    Header, Requests, Transaction,
+};
+use alloc::vec::Vec;
+use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawal};
+use alloy_primitives::B256;
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+
+mod sealed;
+pub use sealed::SealedBlock;
+
+mod validation;
+pub use validation::{BlockBodyValidationError, BodyFork};
+
+/// Ethereum full block.
+///
+/// Withdrawals can be optionally included at the end of the RLP encoded message.
+///
+/// Taken from [reth-primitives](https://github.com/paradigmxyz/reth)
+///
+/// See p2p block encoding reference: <https://github.com/ethereum/devp2p/blob/master/caps/eth.md#block-encoding-and-validity>
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Block<T> {
+    /// Block header.
+    pub header: Header,
+    /// Block body.
+    pub body: BlockBody<T>,
+}
+
+/// A response to `GetBlockBodies`, containing bodies if any bodies were found.
+///
+/// Withdrawals can be optionally included at the end of the RLP encoded message.
+#[derive(Debug, Clone, PartialEq, Eq, Default, RlpEncodable, RlpDecodable)]
+#[rlp(trailing)]
+pub struct BlockBody<T> {
+    /// Transactions in this block.
+    pub transactions: Vec<T>,
+    /// Ommers/uncles header.
+    pub ommers: Vec<Header>,
+    /// Block withdrawals.
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Block requests
+    pub requests: Option<Requests>,
+}
+
+impl<T> Block<T> {
+    /// Creates a block from its header and body.
+    pub const fn new(header: Header, body: BlockBody<T>) -> Self {
+        Self { header, body }
+    }
+
+    /// Seals the block with the hash of its header.
+    pub fn seal_slow(self) -> SealedBlock<T> {
+        let hash = self.header.hash_slow();
+        self.seal(hash)
+    }
+
+    /// Seals the block with a known hash of its header.
+    ///
+    /// WARNING: This method does not perform validation whether the hash is correct.
+    pub fn seal(self, hash: B256) -> SealedBlock<T> {
+        SealedBlock::new(self.header.seal(hash), self.body)
+    }
+}
+
+impl<T: Transaction + Encodable2718> Block<T> {
+    /// Validates the body against the commitments of the header, see
+    /// [`BlockBody::validate_against_header`].
+    pub fn validate_against_header(&self, fork: BodyFork) -> Result<(), BlockBodyValidationError> {
+        self.body.validate_against_header(&self.header, fork)
+    }
+}
+
+impl<T> BlockBody<T> {
+    /// Calculates the ommers hash of the body.
+    pub fn calculate_ommers_hash(&self) -> B256 {
+        calculate_ommers_root(&self.ommers)
+    }
+
+    /// Calculates the withdrawals root of the body, if it has withdrawals.
+    pub fn calculate_withdrawals_root(&self) -> Option<B256> {
+        self.withdrawals.as_deref().map(calculate_withdrawals_root)
+    }
+
+    /// Calculates the requests root of the body, if it has requests.
+    pub fn calculate_requests_root(&self) -> Option<B256> {
+        self.requests.as_ref().map(Requests::root)
+    }
+}
+
+impl<T: Encodable2718> BlockBody<T> {
+    /// Calculates the transactions root of the body.
+    pub fn calculate_tx_root(&self) -> B256 {
+        calculate_transaction_root(&self.transactions)
+    }
+}
+
+/// We need to implement RLP traits manually because we currently don't have a way to flatten
+/// [`BlockBody`] into [`Block`].
+mod block_rlp {
+    use super::*;
+
+    #[derive(RlpDecodable)]
+    #[rlp(trailing)]
+    struct Helper<T> {
+        header: Header,
+        transactions: Vec<T>,
+        ommers: Vec<Header>,
+        withdrawals: Option<Vec<Withdrawal>>,
+        requests: Option<Requests>,
+    }
+
+    #[derive(RlpEncodable)]
+    #[rlp(trailing)]
+    struct HelperRef<'a, T> {
+        header: &'a Header,
+        transactions: &'a Vec<T>,
+        ommers: &'a Vec<Header>,
+        withdrawals: Option<&'a Vec<Withdrawal>>,
+        requests: Option<&'a Requests>,
+    }
+
+    impl<'a, T> HelperRef<'a, T> {
+        const fn new(header: &'a Header, body: &'a BlockBody<T>) -> Self {
+            let BlockBody { transactions, ommers, withdrawals, requests } = body;
+            Self {
+                header,
+                transactions,
+                ommers,
+                withdrawals: withdrawals.as_ref(),
+                requests: requests.as_ref(),
+            }
+        }
+    }
+
+    impl<'a, T> From<&'a Block<T>> for HelperRef<'a, T> {
+        fn from(block: &'a Block<T>) -> Self {
+            Self::new(&block.header, &block.body)
+        }
+    }
+
+    impl<'a, T> From<&'a SealedBlock<T>> for HelperRef<'a, T> {
+        fn from(block: &'a SealedBlock<T>) -> Self {
+            Self::new(&block.header, &block.body)
+        }
+    }
+
+    impl<T: Encodable> Encodable for Block<T> {
+        fn length(&self) -> usize {
+            let helper: HelperRef<'_, T> = self.into();
+            helper.length()
+        }
+
+        fn encode(&self, out: &mut dyn alloy_rlp::bytes::BufMut) {
+            let helper: HelperRef<'_, T> = self.into();
+            helper.encode(out)
+        }
+    }
+
+    impl<T: Decodable> Decodable for Block<T> {
+        fn decode(b: &mut &[u8]) -> alloy_rlp::Result<Self> {
+            let Helper { header, transactions, ommers, withdrawals, requests } = Helper::decode(b)?;
+            Ok(Self { header, body: BlockBody { transactions, ommers, withdrawals, requests } })
+        }
+    }
+
+    impl<T: Encodable> Encodable for SealedBlock<T> {
+        fn length(&self) -> usize {
+            let helper: HelperRef<'_, T> = self.into();
+            helper.length()
+        }
+
+        fn encode(&self, out: &mut dyn alloy_rlp::bytes::BufMut) {
+            let helper: HelperRef<'_, T> = self.into();
+            helper.encode(out)
+        }
+    }
+
+    impl<T: Decodable> Decodable for SealedBlock<T> {
+        fn decode(b: &mut &[u8]) -> alloy_rlp::Result<Self> {
+            Block::decode(b).map(Block::seal_slow)
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! A [`Block`] with the hash of its header.
+
+use super::{Block, BlockBody, BlockBodyValidationError, BodyFork};
+use crate::{Header, Sealed, Transaction};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{BlockHash, B256};
+
+/// A block with the hash of its header, see [`Block::seal_slow`].
+///
+/// It is RLP encoded like a [`Block`], and hashes the header when decoded, so that blocks
+/// received from peers can be identified and checked with
+/// [`validate_against_header`](Self::validate_against_header).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SealedBlock<T> {
+    /// Sealed block header.
+    pub header: Sealed<Header>,
+    /// Block body.
+    pub body: BlockBody<T>,
+}
+
+impl<T> SealedBlock<T> {
+    /// Creates a block from its sealed header and body.
+    pub const fn new(header: Sealed<Header>, body: BlockBody<T>) -> Self {
+        Self { header, body }
+    }
+
+    /// Returns the hash of the block.
+    pub const fn hash(&self) -> BlockHash {
+        self.header.hash()
+    }
+
+    /// Returns the header of the block.
+    pub const fn header(&self) -> &Header {
+        self.header.inner()
+    }
+
+    /// Returns the number of the block.
+    pub const fn number(&self) -> u64 {
+        self.header.inner().number
+    }
+
+    /// Returns the hash of the parent block.
+    pub const fn parent_hash(&self) -> B256 {
+        self.header.inner().parent_hash
+    }
+
+    /// Splits the block into its sealed header and body.
+    pub fn split(self) -> (Sealed<Header>, BlockBody<T>) {
+        (self.header, self.body)
+    }
+
+    /// Drops the hash of the block.
+    pub fn unseal(self) -> Block<T> {
+        Block { header: self.header.unseal(), body: self.body }
+    }
+}
+
+impl<T: Transaction + Encodable2718> SealedBlock<T> {
+    /// Validates the body against the commitments of the header, see
+    /// [`BlockBody::validate_against_header`].
+    pub fn validate_against_header(&self, fork: BodyFork) -> Result<(), BlockBodyValidationError> {
+        self.body.validate_against_header(self.header(), fork)
+    }
+}
+
+impl<T> From<SealedBlock<T>> for Block<T> {
+    fn from(block: SealedBlock<T>) -> Self {
+        block.unseal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SignableTransaction, TxEip1559, TxEnvelope};
+    use alloy_eips::eip4895::Withdrawal;
+    use alloy_primitives::{Address, Signature};
+    use alloy_rlp::{Decodable, Encodable};
+
+    #[test]
+    fn seals_and_validates_decoded_block() {
+        let tx = TxEip1559 { chain_id: 1, gas_limit: 21000, ..Default::default() };
+        let tx: TxEnvelope = tx.into_signed(Signature::test_signature()).into();
+        let withdrawal =
+            Withdrawal { address: Address::with_last_byte(1), amount: 1, ..Default::default() };
+        let body = BlockBody {
+            transactions: vec![tx],
+            ommers: vec![],
+            withdrawals: Some(vec![withdrawal]),
+            requests: None,
+        };
+        let header = Header {
+            number: 1,
+            ommers_hash: body.calculate_ommers_hash(),
+            transactions_root: body.calculate_tx_root(),
+            withdrawals_root: body.calculate_withdrawals_root(),
+            ..Default::default()
+        };
+        let block = Block::new(header, body);
+        assert_eq!(block.validate_against_header(BodyFork::Shanghai), Ok(()));
+
+        let mut encoded = Vec::new();
+        block.encode(&mut encoded);
+        let sealed = SealedBlock::<TxEnvelope>::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(sealed.hash(), block.header.hash_slow());
+        assert_eq!(sealed.length(), encoded.len());
+        assert_eq!(sealed.validate_against_header(BodyFork::Shanghai), Ok(()));
+        assert_eq!(sealed.clone().unseal(), block);
+
+        let (header, mut body) = sealed.split();
+        body.withdrawals = Some(vec![]);
+        assert!(matches!(
+            SealedBlock::new(header, body).validate_against_header(BodyFork::Shanghai),
+            Err(BlockBodyValidationError::WithdrawalsRootMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,502 @@
+//! Structural validation of a [`BlockBody`] against its [`Header`].
+
+use super::BlockBody;
+use crate::{Header, RequestsError, Transaction};
+use alloy_eips::{
+    eip2718::Encodable2718,
+    eip4844::{BlobParams, DATA_GAS_PER_BLOB},
+};
+use alloy_primitives::B256;
+use core::fmt;
+
+/// The hardforks that changed the structure of a block body or its header commitments.
+///
+/// Later forks include the rules of all earlier forks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum BodyFork {
+    /// Proof of work blocks, which may contain ommers.
+    Frontier,
+    /// The merge, after which blocks must not contain ommers.
+    Paris,
+    /// Blocks contain withdrawals, see [EIP-4895](https://eips.ethereum.org/EIPS/eip-4895).
+    Shanghai,
+    /// Headers account for blob gas, see [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+    Cancun,
+    /// Blocks contain requests, see [EIP-7685](https://eips.ethereum.org/EIPS/eip-7685).
+    Prague,
+}
+
+impl BodyFork {
+    /// Returns the blob parameters of the fork, or `None` before Cancun.
+    pub const fn blob_params(&self) -> Option<BlobParams> {
+        match self {
+            Self::Frontier | Self::Paris | Self::Shanghai => None,
+            Self::Cancun => Some(BlobParams::cancun()),
+            Self::Prague => Some(BlobParams::prague()),
+        }
+    }
+}
+
+/// An error returned by [`BlockBody::validate_against_header`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockBodyValidationError {
+    /// The ommers hash of the header does not match the ommers of the body.
+    OmmersHashMismatch {
+        /// The hash of the ommers of the body.
+        have: B256,
+        /// The ommers hash of the header.
+        expected: B256,
+    },
+    /// The body contains ommers after the merge.
+    UnexpectedOmmers,
+    /// The transactions root of the header does not match the transactions of the body.
+    TransactionsRootMismatch {
+        /// The root of the transactions of the body.
+        have: B256,
+        /// The transactions root of the header.
+        expected: B256,
+    },
+    /// The body has no withdrawals after Shanghai.
+    MissingWithdrawals,
+    /// The body has withdrawals before Shanghai.
+    UnexpectedWithdrawals,
+    /// The header has no withdrawals root after Shanghai.
+    MissingWithdrawalsRoot,
+    /// The header has a withdrawals root before Shanghai.
+    UnexpectedWithdrawalsRoot,
+    /// The withdrawals root of the header does not match the withdrawals of the body.
+    WithdrawalsRootMismatch {
+        /// The root of the withdrawals of the body.
+        have: B256,
+        /// The withdrawals root of the header.
+        expected: B256,
+    },
+    /// The header has no blob gas used after Cancun.
+    MissingBlobGasUsed,
+    /// The header has a blob gas used before Cancun.
+    UnexpectedBlobGasUsed,
+    /// The body contains blob transactions before Cancun.
+    UnexpectedBlobTransactions,
+    /// The blob gas used of the header does not match the blobs of the transactions.
+    BlobGasUsedMismatch {
+        /// The blob gas used by the transactions of the body.
+        have: u64,
+        /// The blob gas used of the header.
+        expected: u64,
+    },
+    /// The transactions of the body use more blob gas than allowed per block.
+    BlobGasExceedsMax {
+        /// The blob gas used by the transactions of the body.
+        blob_gas_used: u64,
+        /// The maximum blob gas per block.
+        max: u64,
+    },
+    /// The body has no requests after Prague.
+    MissingRequests,
+    /// The body has requests before Prague.
+    UnexpectedRequests,
+    /// The requests of the body are not sorted by request type.
+    InvalidRequests(RequestsError),
+    /// The header has no requests root after Prague.
+    MissingRequestsRoot,
+    /// The header has a requests root before Prague.
+    UnexpectedRequestsRoot,
+    /// The requests root of the header does not match the requests of the body.
+    RequestsRootMismatch {
+        /// The root of the requests of the body.
+        have: B256,
+        /// The requests root of the header.
+        expected: B256,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlockBodyValidationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidRequests(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<RequestsError> for BlockBodyValidationError {
+    fn from(err: RequestsError) -> Self {
+        Self::InvalidRequests(err)
+    }
+}
+
+impl fmt::Display for BlockBodyValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OmmersHashMismatch { have, expected } => {
+                write!(f, "ommers hash mismatch: have {have}, expected {expected}")
+            }
+            Self::UnexpectedOmmers => f.write_str("ommers are not allowed after the merge"),
+            Self::TransactionsRootMismatch { have, expected } => {
+                write!(f, "transactions root mismatch: have {have}, expected {expected}")
+            }
+            Self::MissingWithdrawals => f.write_str("missing withdrawals"),
+            Self::UnexpectedWithdrawals => {
+                f.write_str("withdrawals are not allowed before Shanghai")
+            }
+            Self::MissingWithdrawalsRoot => f.write_str("missing withdrawals root"),
+            Self::UnexpectedWithdrawalsRoot => {
+                f.write_str("withdrawals root is not allowed before Shanghai")
+            }
+            Self::WithdrawalsRootMismatch { have, expected } => {
+                write!(f, "withdrawals root mismatch: have {have}, expected {expected}")
+            }
+            Self::MissingBlobGasUsed => f.write_str("missing blob gas used"),
+            Self::UnexpectedBlobGasUsed => {
+                f.write_str("blob gas used is not allowed before Cancun")
+            }
+            Self::UnexpectedBlobTransactions => {
+                f.write_str("blob transactions are not allowed before Cancun")
+            }
+            Self::BlobGasUsedMismatch { have, expected } => {
+                write!(f, "blob gas used mismatch: have {have}, expected {expected}")
+            }
+            Self::BlobGasExceedsMax { blob_gas_used, max } => {
+                write!(f, "blob gas used {blob_gas_used} exceeds maximum {max}")
+            }
+            Self::MissingRequests => f.write_str("missing requests"),
+            Self::UnexpectedRequests => f.write_str("requests are not allowed before Prague"),
+            Self::InvalidRequests(err) => write!(f, "invalid requests: {err}"),
+            Self::MissingRequestsRoot => f.write_str("missing requests root"),
+            Self::UnexpectedRequestsRoot => {
+                f.write_str("requests root is not allowed before Prague")
+            }
+            Self::RequestsRootMismatch { have, expected } => {
+                write!(f, "requests root mismatch: have {have}, expected {expected}")
+            }
+        }
+    }
+}
+
+impl<T: Transaction + Encodable2718> BlockBody<T> {
+    /// Validates the structure of the body against the commitments of its header, under the rules
+    /// of the given fork.
+    ///
+    /// This checks the ommers hash, the transactions root, the withdrawals root, the requests root
+    /// and the blob gas used, and that fields are present exactly when the fork requires them. It
+    /// does not execute the transactions, so the state root, receipts root and gas used are not
+    /// checked.
+    pub fn validate_against_header(
+        &self,
+        header: &Header,
+        fork: BodyFork,
+    ) -> Result<(), BlockBodyValidationError> {
+        self.validate_ommers(header, fork)?;
+        self.validate_transactions_root(header)?;
+        self.validate_withdrawals(header, fork)?;
+        self.validate_blob_gas(header, fork)?;
+        self.validate_requests(header, fork)
+    }
+
+    /// Returns the total blob gas used by the transactions of the body.
+    pub fn blob_gas_used(&self) -> u64 {
+        let blobs: usize = self
+            .transactions
+            .iter()
+            .filter_map(|tx| tx.blob_versioned_hashes())
+            .map(|hashes| hashes.len())
+            .sum();
+        blobs as u64 * DATA_GAS_PER_BLOB
+    }
+
+    fn validate_ommers(
+        &self,
+        header: &Header,
+        fork: BodyFork,
+    ) -> Result<(), BlockBodyValidationError> {
+        if fork >= BodyFork::Paris && !self.ommers.is_empty() {
+            return Err(BlockBodyValidationError::UnexpectedOmmers);
+        }
+        let have = self.calculate_ommers_hash();
+        if have != header.ommers_hash {
+            return Err(BlockBodyValidationError::OmmersHashMismatch {
+                have,
+                expected: header.ommers_hash,
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_transactions_root(&self, header: &Header) -> Result<(), BlockBodyValidationError> {
+        let have = self.calculate_tx_root();
+        if have != header.transactions_root {
+            return Err(BlockBodyValidationError::TransactionsRootMismatch {
+                have,
+                expected: header.transactions_root,
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_withdrawals(
+        &self,
+        header: &Header,
+        fork: BodyFork,
+    ) -> Result<(), BlockBodyValidationError> {
+        if fork < BodyFork::Shanghai {
+            if self.withdrawals.is_some() {
+                return Err(BlockBodyValidationError::UnexpectedWithdrawals);
+            }
+            if header.withdrawals_root.is_some() {
+                return Err(BlockBodyValidationError::UnexpectedWithdrawalsRoot);
+            }
+            return Ok(());
+        }
+
+        let have = self
+            .calculate_withdrawals_root()
+            .ok_or(BlockBodyValidationError::MissingWithdrawals)?;
+        let expected =
+            header.withdrawals_root.ok_or(BlockBodyValidationError::MissingWithdrawalsRoot)?;
+        if have != expected {
+            return Err(BlockBodyValidationError::WithdrawalsRootMismatch { have, expected });
+        }
+        Ok(())
+    }
+
+    fn validate_blob_gas(
+        &self,
+        header: &Header,
+        fork: BodyFork,
+    ) -> Result<(), BlockBodyValidationError> {
+        let have = self.blob_gas_used();
+        let Some(blob_params) = fork.blob_params() else {
+            if header.blob_gas_used.is_some() {
+                return Err(BlockBodyValidationError::UnexpectedBlobGasUsed);
+            }
+            if have != 0 {
+                return Err(BlockBodyValidationError::UnexpectedBlobTransactions);
+            }
+            return Ok(());
+        };
+
+        let expected = header.blob_gas_used.ok_or(BlockBodyValidationError::MissingBlobGasUsed)?;
+        let max = blob_params.max_blob_gas_per_block();
+        if have > max {
+            return Err(BlockBodyValidationError::BlobGasExceedsMax { blob_gas_used: have, max });
+        }
+        if have != expected {
+            return Err(BlockBodyValidationError::BlobGasUsedMismatch { have, expected });
+        }
+        Ok(())
+    }
+
+    fn validate_requests(
+        &self,
+        header: &Header,
+        fork: BodyFork,
+    ) -> Result<(), BlockBodyValidationError> {
+        if fork < BodyFork::Prague {
+            if self.requests.is_some() {
+                return Err(BlockBodyValidationError::UnexpectedRequests);
+            }
+            if header.requests_root.is_some() {
+                return Err(BlockBodyValidationError::UnexpectedRequestsRoot);
+            }
+            return Ok(());
+        }
+
+        let requests = self.requests.as_ref().ok_or(BlockBodyValidationError::MissingRequests)?;
+        requests.validate()?;
+        let expected = header.requests_root.ok_or(BlockBodyValidationError::MissingRequestsRoot)?;
+        let have = requests.root();
+        if have != expected {
+            return Err(BlockBodyValidationError::RequestsRootMismatch { have, expected });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        proofs::{calculate_ommers_root, calculate_transaction_root, calculate_withdrawals_root},
+        Signed, TxEip4844, TxEnvelope, TxLegacy, EMPTY_ROOT_HASH,
+    };
+    use alloy_eips::eip4895::Withdrawal;
+    use alloy_primitives::{Address, Signature};
+
+    fn legacy() -> TxEnvelope {
+        let tx = TxLegacy { gas_limit: 21000, ..Default::default() };
+        Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO).into()
+    }
+
+    fn blob(blobs: usize) -> TxEnvelope {
+        let tx = TxEip4844 { blob_versioned_hashes: vec![B256::ZERO; blobs], ..Default::default() };
+        Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO).into()
+    }
+
+    fn empty_body() -> BlockBody<TxEnvelope> {
+        BlockBody { transactions: vec![], ommers: vec![], withdrawals: None, requests: None }
+    }
+
+    fn cancun_block(transactions: Vec<TxEnvelope>) -> (BlockBody<TxEnvelope>, Header) {
+        let withdrawals = vec![Withdrawal {
+            address: Address::with_last_byte(1),
+            amount: 1,
+            ..Default::default()
+        }];
+        let body = BlockBody {
+            transactions,
+            ommers: vec![],
+            withdrawals: Some(withdrawals),
+            requests: None,
+        };
+        let header = Header {
+            transactions_root: calculate_transaction_root(&body.transactions),
+            withdrawals_root: Some(calculate_withdrawals_root(body.withdrawals.as_ref().unwrap())),
+            blob_gas_used: Some(body.blob_gas_used()),
+            ..Default::default()
+        };
+        (body, header)
+    }
+
+    #[test]
+    fn valid_body() {
+        let (body, header) = cancun_block(vec![legacy(), blob(2)]);
+        assert_eq!(body.blob_gas_used(), 2 * DATA_GAS_PER_BLOB);
+        assert_eq!(body.validate_against_header(&header, BodyFork::Cancun), Ok(()));
+
+        let empty = empty_body();
+        assert_eq!(empty.validate_against_header(&Header::default(), BodyFork::Frontier), Ok(()));
+    }
+
+    #[test]
+    fn fork_presence() {
+        let (body, header) = cancun_block(vec![legacy()]);
+        assert_eq!(
+            body.validate_against_header(&header, BodyFork::Paris),
+            Err(BlockBodyValidationError::UnexpectedWithdrawals)
+        );
+        assert_eq!(
+            body.validate_against_header(&header, BodyFork::Prague),
+            Err(BlockBodyValidationError::MissingRequests)
+        );
+
+        let mut without_blob_gas = header;
+        without_blob_gas.blob_gas_used = None;
+        assert_eq!(
+            body.validate_against_header(&without_blob_gas, BodyFork::Cancun),
+            Err(BlockBodyValidationError::MissingBlobGasUsed)
+        );
+        assert_eq!(body.validate_against_header(&without_blob_gas, BodyFork::Shanghai), Ok(()));
+
+        let mut with_ommers = empty_body();
+        with_ommers.ommers.push(Header::default());
+        let header = Header {
+            ommers_hash: calculate_ommers_root(&with_ommers.ommers),
+            ..Default::default()
+        };
+        assert_eq!(with_ommers.validate_against_header(&header, BodyFork::Frontier), Ok(()));
+        assert_eq!(
+            with_ommers.validate_against_header(&header, BodyFork::Paris),
+            Err(BlockBodyValidationError::UnexpectedOmmers)
+        );
+    }
+
+    #[test]
+    fn commitment_mismatches() {
+        let (body, header) = cancun_block(vec![legacy(), blob(1)]);
+
+        let mut wrong = header.clone();
+        wrong.transactions_root = EMPTY_ROOT_HASH;
+        assert!(matches!(
+            body.validate_against_header(&wrong, BodyFork::Cancun),
+            Err(BlockBodyValidationError::TransactionsRootMismatch { .. })
+        ));
+
+        let mut wrong = header.clone();
+        wrong.withdrawals_root = Some(EMPTY_ROOT_HASH);
+        assert!(matches!(
+            body.validate_against_header(&wrong, BodyFork::Cancun),
+            Err(BlockBodyValidationError::WithdrawalsRootMismatch { .. })
+        ));
+
+        let mut wrong = header.clone();
+        wrong.ommers_hash = B256::ZERO;
+        assert!(matches!(
+            body.validate_against_header(&wrong, BodyFork::Cancun),
+            Err(BlockBodyValidationError::OmmersHashMismatch { .. })
+        ));
+
+        let mut wrong = header;
+        wrong.blob_gas_used = Some(0);
+        assert_eq!(
+            body.validate_against_header(&wrong, BodyFork::Cancun),
+            Err(BlockBodyValidationError::BlobGasUsedMismatch {
+                have: DATA_GAS_PER_BLOB,
+                expected: 0
+            })
+        );
+    }
+
+    #[test]
+    fn prague_requests() {
+        use crate::{Request, Requests};
+        use alloy_eips::{eip6110::DepositRequest, eip7002::WithdrawalRequest};
+
+        let (mut body, mut header) = cancun_block(vec![legacy()]);
+        let requests = Requests(vec![
+            Request::from(WithdrawalRequest::default()),
+            Request::from(DepositRequest::default()),
+        ]);
+        header.requests_root = Some(requests.root());
+        body.requests = Some(requests);
+        assert_eq!(
+            body.validate_against_header(&header, BodyFork::Prague),
+            Err(BlockBodyValidationError::InvalidRequests(RequestsError::Unsorted {
+                index: 1,
+                request_type: 0,
+                previous_type: 1
+            }))
+        );
+
+        let requests = body.requests.as_mut().unwrap();
+        requests.sort_by_type();
+        header.requests_root = Some(requests.root());
+        assert_eq!(body.validate_against_header(&header, BodyFork::Prague), Ok(()));
+    }
+
+    #[test]
+    fn blob_gas_limits() {
+        let (body, header) = cancun_block(vec![blob(7)]);
+        assert_eq!(
+            body.validate_against_header(&header, BodyFork::Cancun),
+            Err(BlockBodyValidationError::BlobGasExceedsMax {
+                blob_gas_used: 7 * DATA_GAS_PER_BLOB,
+                max: BlobParams::cancun().max_blob_gas_per_block()
+            })
+        );
+
+        // Prague raises the maximum to 9 blobs
+        let (mut body, mut header) = cancun_block(vec![blob(9)]);
+        let requests = crate::Requests(vec![]);
+        header.requests_root = Some(requests.root());
+        body.requests = Some(requests);
+        assert_eq!(body.validate_against_header(&header, BodyFork::Prague), Ok(()));
+        let (mut body, header) = cancun_block(vec![blob(10)]);
+        body.requests = Some(crate::Requests(vec![]));
+        assert_eq!(
+            body.validate_against_header(&header, BodyFork::Prague),
+            Err(BlockBodyValidationError::BlobGasExceedsMax {
+                blob_gas_used: 10 * DATA_GAS_PER_BLOB,
+                max: BlobParams::prague().max_blob_gas_per_block()
+            })
+        );
+
+        let (body, mut header) = cancun_block(vec![blob(1)]);
+        header.blob_gas_used = None;
+        assert_eq!(
+            body.validate_against_header(&header, BodyFork::Shanghai),
+            Err(BlockBodyValidationError::UnexpectedBlobTransactions)
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Helpers for headers of chains using the Clique proof-of-authority consensus, see
+//! [EIP-225](https://eips.ethereum.org/EIPS/eip-225).
+//!
+//! Clique repurposes the `extraData` field of the header: it starts with [`EXTRA_VANITY`] bytes
+//! of signer vanity, followed on epoch blocks by the list of authorized signers, and ends with the
+//! [`EXTRA_SEAL`] bytes of the signer's signature over the [signing
+//! hash](Header::clique_signing_hash) of the header.
+
+use crate::Header;
+use alloc::vec::Vec;
+use alloy_primitives::{b64, Address, FixedBytes, B256, B64};
+use core::fmt;
+
+/// The number of bytes of signer vanity at the start of the extra data.
+pub const EXTRA_VANITY: usize = 32;
+
+/// The number of bytes of the signer's seal at the end of the extra data.
+pub const EXTRA_SEAL: usize = 65;
+
+/// The default number of blocks after which votes are reset and the signers are checkpointed.
+pub const DEFAULT_EPOCH: u64 = 30_000;
+
+/// The difficulty of a block sealed by the in-turn signer.
+pub const DIFF_IN_TURN: u64 = 2;
+
+/// The difficulty of a block sealed by an out-of-turn signer.
+pub const DIFF_NO_TURN: u64 = 1;
+
+/// The nonce of a block voting to authorize the signer in the beneficiary field.
+pub const NONCE_AUTH_VOTE: B64 = b64!("ffffffffffffffff");
+
+/// The nonce of a block voting to deauthorize the signer in the beneficiary field.
+pub const NONCE_DROP_VOTE: B64 = B64::ZERO;
+
+/// Returns `true` if the block with the number is an epoch block, whose extra data lists the
+/// authorized signers.
+pub const fn is_epoch_block(number: u64, epoch: u64) -> bool {
+    epoch != 0 && number % epoch == 0
+}
+
+/// An error returned when the extra data of a header is not valid Clique extra data.
+#[derive(Debug)]
+pub enum CliqueError {
+    /// The extra data is too short to contain the vanity and the seal.
+    ExtraDataTooShort {
+        /// The length of the extra data.
+        len: usize,
+    },
+    /// The signer list is not a whole number of addresses.
+    InvalidSignerList {
+        /// The length of the signer list.
+        len: usize,
+    },
+    /// A block that is not an epoch block lists signers.
+    UnexpectedSigners,
+    /// The seal is not a valid signature.
+    InvalidSeal(alloy_primitives::SignatureError),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CliqueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidSeal(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CliqueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExtraDataTooShort { len } => write!(
+                f,
+                "extra data of {len} bytes is shorter than the {} bytes of vanity and seal",
+                EXTRA_VANITY + EXTRA_SEAL
+            ),
+            Self::InvalidSignerList { len } => {
+                write!(f, "signer list of {len} bytes is not a multiple of the address length")
+            }
+            Self::UnexpectedSigners => f.write_str("signers are only allowed in epoch blocks"),
+            Self::InvalidSeal(err) => write!(f, "invalid seal: {err}"),
+        }
+    }
+}
+
+impl From<alloy_primitives::SignatureError> for CliqueError {
+    fn from(err: alloy_primitives::SignatureError) -> Self {
+        Self::InvalidSeal(err)
+    }
+}
+
+/// The parsed extra data of a Clique header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CliqueExtraData {
+    /// The signer vanity.
+    pub vanity: B256,
+    /// The authorized signers, which are only listed on epoch blocks.
+    pub signers: Vec<Address>,
+    /// The signature of the signer over the signing hash of the header.
+    pub seal: FixedBytes<EXTRA_SEAL>,
+}
+
+impl CliqueExtraData {
+    /// Parses Clique extra data.
+    pub fn decode(extra_data: &[u8]) -> Result<Self, CliqueError> {
+        let len = extra_data.len();
+        if len < EXTRA_VANITY + EXTRA_SEAL {
+            return Err(CliqueError::ExtraDataTooShort { len });
+        }
+        let (vanity, rest) = extra_data.split_at(EXTRA_VANITY);
+        let (signers, seal) = rest.split_at(rest.len() - EXTRA_SEAL);
+        if signers.len() % Address::len_bytes() != 0 {
+            return Err(CliqueError::InvalidSignerList { len: signers.len() });
+        }
+
+        Ok(Self {
+            vanity: B256::from_slice(vanity),
+            signers: signers.chunks_exact(Address::len_bytes()).map(Address::from_slice).collect(),
+            seal: FixedBytes::from_slice(seal),
+        })
+    }
+
+    /// Encodes the extra data.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            EXTRA_VANITY + self.signers.len() * Address::len_bytes() + EXTRA_SEAL,
+        );
+        out.extend_from_slice(self.vanity.as_slice());
+        for signer in &self.signers {
+            out.extend_from_slice(signer.as_slice());
+        }
+        out.extend_from_slice(self.seal.as_slice());
+        out
+    }
+
+    /// Returns the seal as a signature.
+    pub fn signature(&self) -> Result<alloy_primitives::Signature, CliqueError> {
+        Ok(alloy_primitives::Signature::try_from(self.seal.as_slice())?)
+    }
+}
+
+impl Header {
+    /// Parses the extra data of the header as Clique extra data.
+    ///
+    /// Signers are only allowed in epoch blocks of the given epoch length, see
+    /// [`DEFAULT_EPOCH`].
+    pub fn clique_extra_data(&self, epoch: u64) -> Result<CliqueExtraData, CliqueError> {
+        let extra_data = CliqueExtraData::decode(&self.extra_data)?;
+        if !extra_data.signers.is_empty() && !is_epoch_block(self.number, epoch) {
+            return Err(CliqueError::UnexpectedSigners);
+        }
+        Ok(extra_data)
+    }
+
+    /// Returns the hash the signer of the header signs, which is the hash of the header without
+    /// the seal in the extra data.
+    pub fn clique_signing_hash(&self) -> Result<B256, CliqueError> {
+        let len = self.extra_data.len();
+        if len < EXTRA_VANITY + EXTRA_SEAL {
+            return Err(CliqueError::ExtraDataTooShort { len });
+        }
+        let header = Self { extra_data: self.extra_data.slice(..len - EXTRA_SEAL), ..self.clone() };
+        Ok(header.hash_slow())
+    }
+
+    /// Recovers the address of the signer that sealed the header.
+    #[cfg(feature = "k256")]
+    pub fn clique_signer(&self) -> Result<Address, CliqueError> {
+        let signing_hash = self.clique_signing_hash()?;
+        let signature = CliqueExtraData::decode(&self.extra_data)?.signature()?;
+        Ok(signature.recover_address_from_prehash(&signing_hash)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extra_data(signers: &[Address]) -> CliqueExtraData {
+        CliqueExtraData {
+            vanity: B256::repeat_byte(0xaa),
+            signers: signers.to_vec(),
+            seal: FixedBytes::repeat_byte(0x01),
+        }
+    }
+
+    #[test]
+    fn decode_extra_data() {
+        let signers = [Address::repeat_byte(1), Address::repeat_byte(2)];
+        let extra = extra_data(&signers);
+        let encoded = extra.encode();
+        assert_eq!(encoded.len(), 32 + 40 + 65);
+        assert_eq!(CliqueExtraData::decode(&encoded).unwrap(), extra);
+
+        let mut header =
+            Header { number: 60_000, extra_data: encoded.into(), ..Default::default() };
+        assert_eq!(header.clique_extra_data(DEFAULT_EPOCH).unwrap().signers, signers);
+        header.number += 1;
+        assert!(matches!(
+            header.clique_extra_data(DEFAULT_EPOCH),
+            Err(CliqueError::UnexpectedSigners)
+        ));
+
+        assert!(matches!(
+            CliqueExtraData::decode(&[0; 96]),
+            Err(CliqueError::ExtraDataTooShort { len: 96 })
+        ));
+        assert!(matches!(
+            CliqueExtraData::decode(&[0; 97 + 19]),
+            Err(CliqueError::InvalidSignerList { len: 19 })
+        ));
+    }
+
+    #[test]
+    fn signing_hash_excludes_seal() {
+        let header = Header { extra_data: extra_data(&[]).encode().into(), ..Default::default() };
+        let unsealed = Header { extra_data: header.extra_data.slice(..32), ..header };
+        assert_eq!(header.clique_signing_hash().unwrap(), unsealed.hash_slow());
+    }
+
+    #[cfg(feature = "k256")]
+    #[test]
+    fn recover_signer() {
+        let key = k256::ecdsa::SigningKey::from_slice(&[0x11; 32]).unwrap();
+        let mut header = Header {
+            number: 1,
+            difficulty: alloy_primitives::U256::from(DIFF_IN_TURN),
+            extra_data: extra_data(&[]).encode().into(),
+            ..Default::default()
+        };
+
+        let (signature, recovery_id) =
+            key.sign_prehash_recoverable(header.clique_signing_hash().unwrap().as_slice()).unwrap();
+        let mut extra = CliqueExtraData::decode(&header.extra_data).unwrap();
+        extra.seal[..64].copy_from_slice(&signature.to_bytes());
+        extra.seal[64] = recovery_id.to_byte();
+        header.extra_data = extra.encode().into();
+
+        assert_eq!(header.clique_signer().unwrap(), Address::from_private_key(&key));
+    }
+}
@@ -1,7 +1,7 @@
 use alloc::vec::Vec;
 use alloy_eips::{
     eip1559::{calc_next_block_base_fee, BaseFeeParams},
-    eip4844::{calc_blob_gasprice, calc_excess_blob_gas},
+    eip4844::{calc_blob_gasprice, calc_excess_blob_gas, BlobParams},
     merge::ALLOWED_FUTURE_BLOCK_TIME_SECONDS,
     BlockNumHash,
 };
@@ -208,6 +208,13 @@ impl Header {
         self.excess_blob_gas.map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for _this_ block, computed with the blob parameters of its fork.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None
+    pub fn blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        Some(blob_params.calc_blob_fee(self.excess_blob_gas?))
+    }
+
     /// Returns the blob fee for the next block according to the EIP-4844 spec.
     ///
     /// Returns `None` if `excess_blob_gas` is None.
@@ -217,6 +224,16 @@ impl Header {
         self.next_block_excess_blob_gas().map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for the next block, computed with the blob parameters of the fork of
+    /// the next block.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None.
+    ///
+    /// See also [Self::next_block_excess_blob_gas_with_params]
+    pub fn next_block_blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        Some(blob_params.calc_blob_fee(self.next_block_excess_blob_gas_with_params(blob_params)?))
+    }
+
     /// Calculate base fee for next block according to the EIP-1559 spec.
     ///
     /// Returns a `None` if no base fee is set, no EIP-1559 support
@@ -237,6 +254,14 @@ impl Header {
         Some(calc_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 
+    /// Calculate excess blob gas for the next block, with the blob parameters of the fork of the
+    /// next block.
+    ///
+    /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
+    pub fn next_block_excess_blob_gas_with_params(&self, blob_params: BlobParams) -> Option<u64> {
+        Some(blob_params.next_block_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+    }
+
     /// Calculate a heuristic for the in-memory size of the [Header].
     #[inline]
     pub fn size(&self) -> usize {
@@ -819,4 +844,37 @@ mod tests {
         // Check that the decoded RLP data matches the original header data
         assert_eq!(decoded_rlp, decoded);
     }
+
+    #[test]
+    fn blob_gas_with_params() {
+        use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
+
+        let header = Header {
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(7 * DATA_GAS_PER_BLOB),
+            ..Default::default()
+        };
+        assert_eq!(
+            header.next_block_excess_blob_gas_with_params(BlobParams::cancun()),
+            Some(4 * DATA_GAS_PER_BLOB)
+        );
+        assert_eq!(
+            header.next_block_excess_blob_gas_with_params(BlobParams::cancun()),
+            header.next_block_excess_blob_gas()
+        );
+        assert_eq!(
+            header.next_block_excess_blob_gas_with_params(BlobParams::prague()),
+            Some(DATA_GAS_PER_BLOB)
+        );
+        assert_eq!(header.blob_fee_with_params(BlobParams::prague()), Some(1));
+        assert!(
+            header.next_block_blob_fee_with_params(BlobParams::cancun())
+                >= header.next_block_blob_fee_with_params(BlobParams::prague())
+        );
+        assert_eq!(
+            header.next_block_blob_fee_with_params(BlobParams::cancun()),
+            header.next_block_blob_fee()
+        );
+        assert_eq!(Header::default().next_block_blob_fee_with_params(BlobParams::cancun()), None);
+    }
 }
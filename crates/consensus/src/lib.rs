@@ -13,7 +13,9 @@ mod account;
 pub use account::Account;
 
 mod block;
-pub use block::{Block, BlockBody};
+pub use block::{Block, BlockBody, BlockBodyValidationError, BodyFork, SealedBlock};
+
+pub mod clique;
 
 pub mod constants;
 
@@ -23,6 +25,8 @@ pub use encodable_signature::EncodableSignature;
 mod header;
 pub use header::{BlockHeader, Header, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
 
+pub mod proofs;
+
 mod receipt;
 pub use receipt::{
     AnyReceiptEnvelope, Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom, Receipts,
@@ -30,14 +34,15 @@ pub use receipt::{
 };
 
 mod request;
-pub use request::{Request, Requests};
+pub use request::{Request, Requests, RequestsByType, RequestsError};
 
 pub mod transaction;
 #[cfg(feature = "kzg")]
 pub use transaction::BlobTransactionValidationError;
 pub use transaction::{
-    SignableTransaction, Transaction, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant,
-    TxEip4844WithSidecar, TxEip7702, TxEnvelope, TxLegacy, TxType, TypedTransaction,
+    SignableTransaction, Transaction, TxEip1559, TxEip1559Builder, TxEip2930, TxEip4844,
+    TxEip4844Variant, TxEip4844WithSidecar, TxEip7702, TxEnvelope, TxLegacy, TxLegacyBuilder,
+    TxType, TypedTransaction,
 };
 
 pub use alloy_eips::eip4844::{
@@ -0,0 +1,368 @@
+//! Merkle Patricia Trie root and inclusion proof helpers for ordered lists, such as the
+//! transactions and receipts of a block, and for the state and storage tries.
+
+// `ProofVerificationError` is defined in `alloy-trie`.
+#![allow(clippy::result_large_err)]
+
+use crate::{Account, Header, Request};
+use alloc::{vec, vec::Vec};
+use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawal, eip7685::Encodable7685};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy_rlp::Encodable;
+use alloy_trie::{
+    proof::{verify_proof, ProofRetainer},
+    HashBuilder, Nibbles, EMPTY_ROOT_HASH,
+};
+
+pub use alloy_trie::proof::ProofVerificationError;
+
+/// Adjusts the index of an item for rlp encoding.
+///
+/// The keys of an ordered trie are the rlp encoded indices, which do not sort in the same order
+/// as the indices themselves. Iterating `0..len` through this function yields the indices in
+/// key order.
+pub const fn adjust_index_for_rlp(i: usize, len: usize) -> usize {
+    if i > 0x7f {
+        i
+    } else if i == 0x7f || i + 1 == len {
+        0
+    } else {
+        i + 1
+    }
+}
+
+/// Returns the trie key of the item at the given index of an ordered list.
+fn ordered_trie_key(index: usize) -> Nibbles {
+    let mut key = Vec::new();
+    index.encode(&mut key);
+    Nibbles::unpack(&key)
+}
+
+/// Builds the ordered trie of the given items, optionally retaining the proof of one of them.
+fn build_ordered_trie<T, F>(items: &[T], target: Option<usize>, mut encode: F) -> HashBuilder
+where
+    F: FnMut(&T, &mut Vec<u8>),
+{
+    let mut hb = HashBuilder::default();
+    if let Some(target) = target {
+        hb = hb.with_proof_retainer(ProofRetainer::new(vec![ordered_trie_key(target)]));
+    }
+
+    let items_len = items.len();
+    let mut value = Vec::new();
+    for i in 0..items_len {
+        let index = adjust_index_for_rlp(i, items_len);
+        value.clear();
+        encode(&items[index], &mut value);
+        hb.add_leaf(ordered_trie_key(index), &value);
+    }
+
+    hb
+}
+
+/// Computes the root of the ordered trie of the given items, using a custom encoder for the
+/// leaf values.
+pub fn ordered_trie_root_with_encoder<T, F>(items: &[T], encode: F) -> B256
+where
+    F: FnMut(&T, &mut Vec<u8>),
+{
+    if items.is_empty() {
+        return EMPTY_ROOT_HASH;
+    }
+    build_ordered_trie(items, None, encode).root()
+}
+
+/// Computes the root of the ordered trie of the given [EIP-2718] encodable items.
+///
+/// This is the `transactionsRoot` of a list of transactions, or the `receiptsRoot` of a list of
+/// receipts.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub fn ordered_trie_root_2718<T: Encodable2718>(items: &[T]) -> B256 {
+    ordered_trie_root_with_encoder(items, |item, buf| item.encode_2718(buf))
+}
+
+/// Calculates the transaction root of the given transactions.
+pub fn calculate_transaction_root<T: Encodable2718>(transactions: &[T]) -> B256 {
+    ordered_trie_root_2718(transactions)
+}
+
+/// Calculates the receipt root of the given receipts.
+pub fn calculate_receipt_root<T: Encodable2718>(receipts: &[T]) -> B256 {
+    ordered_trie_root_2718(receipts)
+}
+
+/// Calculates the withdrawals root of the given withdrawals.
+pub fn calculate_withdrawals_root(withdrawals: &[Withdrawal]) -> B256 {
+    ordered_trie_root_with_encoder(withdrawals, |withdrawal, buf| withdrawal.encode(buf))
+}
+
+/// Calculates the [EIP-7685] requests root of the given requests.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+pub fn calculate_requests_root(requests: &[Request]) -> B256 {
+    ordered_trie_root_with_encoder(requests, |request, buf| request.encode_7685(buf))
+}
+
+/// Calculates the ommers hash of the given ommer headers.
+pub fn calculate_ommers_root(ommers: &[Header]) -> B256 {
+    let mut buf = Vec::new();
+    alloy_rlp::encode_list(ommers, &mut buf);
+    keccak256(buf)
+}
+
+/// A Merkle Patricia Trie inclusion proof for an item of an ordered list.
+///
+/// The proof can be verified against the trusted root of the list, for example the
+/// `transactionsRoot` or `receiptsRoot` of a block header, with [`InclusionProof::verify`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct InclusionProof {
+    /// The root of the trie the item is included in.
+    pub root: B256,
+    /// The index of the item in the list.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub index: u64,
+    /// The encoded item.
+    pub value: Bytes,
+    /// The trie nodes on the path from the root to the item, in root-to-leaf order.
+    pub proof: Vec<Bytes>,
+}
+
+impl InclusionProof {
+    /// Creates the inclusion proof of the item at `index`, using a custom encoder for the leaf
+    /// values.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn new_with_encoder<T, F>(items: &[T], index: usize, mut encode: F) -> Option<Self>
+    where
+        F: FnMut(&T, &mut Vec<u8>),
+    {
+        let item = items.get(index)?;
+        let mut value = Vec::new();
+        encode(item, &mut value);
+
+        let mut hb = build_ordered_trie(items, Some(index), encode);
+        let root = hb.root();
+        let proof = hb
+            .take_proof_nodes()
+            .matching_nodes_sorted(&ordered_trie_key(index))
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+
+        Some(Self { root, index: index as u64, value: value.into(), proof })
+    }
+
+    /// Creates the inclusion proof of the [EIP-2718] encodable item at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    pub fn new_2718<T: Encodable2718>(items: &[T], index: usize) -> Option<Self> {
+        Self::new_with_encoder(items, index, |item, buf| item.encode_2718(buf))
+    }
+
+    /// Verifies the proof against its own [`root`](Self::root).
+    ///
+    /// Note that this only proves that the item is part of a trie with the given root, the root
+    /// itself must be checked against a trusted source, see [`verify`](Self::verify).
+    pub fn verify_self(&self) -> Result<(), ProofVerificationError> {
+        self.verify(self.root)
+    }
+
+    /// Verifies the proof against the given trusted root.
+    pub fn verify(&self, root: B256) -> Result<(), ProofVerificationError> {
+        verify_inclusion_proof(root, self.index as usize, &self.value, &self.proof)
+    }
+}
+
+/// Verifies that `value` is the item at `index` of the ordered trie with the given `root`.
+pub fn verify_inclusion_proof(
+    root: B256,
+    index: usize,
+    value: &[u8],
+    proof: &[Bytes],
+) -> Result<(), ProofVerificationError> {
+    verify_proof(root, ordered_trie_key(index), Some(value.to_vec()), proof)
+}
+
+/// Verifies that `account` is the account of `address` in the state trie with the given `root`,
+/// or that there is no account if `account` is `None`, e.g. with a proof of `eth_getProof`.
+pub fn verify_account_proof(
+    root: B256,
+    address: Address,
+    account: Option<Account>,
+    proof: &[Bytes],
+) -> Result<(), ProofVerificationError> {
+    let value = account.map(alloy_rlp::encode);
+    verify_proof(root, Nibbles::unpack(keccak256(address)), value, proof)
+}
+
+/// Verifies that the storage slot `key` holds `value` in the storage trie with the given `root`,
+/// e.g. with a storage proof of `eth_getProof`.
+///
+/// Slots holding zero are not part of the trie, so their proofs are exclusion proofs.
+pub fn verify_storage_proof(
+    root: B256,
+    key: B256,
+    value: U256,
+    proof: &[Bytes],
+) -> Result<(), ProofVerificationError> {
+    let value = (!value.is_zero()).then(|| alloy_rlp::encode(value));
+    verify_proof(root, Nibbles::unpack(keccak256(key)), value, proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Receipt, ReceiptEnvelope, ReceiptWithBloom};
+    use alloy_primitives::{hex, Log, LogData};
+
+    fn receipts(n: u64) -> Vec<ReceiptEnvelope> {
+        (0..n)
+            .map(|i| {
+                ReceiptEnvelope::Eip1559(ReceiptWithBloom::from(Receipt {
+                    status: true.into(),
+                    cumulative_gas_used: 21_000 * (i as u128 + 1),
+                    logs: vec![Log {
+                        address: Default::default(),
+                        data: LogData::new_unchecked(
+                            vec![B256::with_last_byte(i as u8)],
+                            Bytes::new(),
+                        ),
+                    }],
+                }))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_root() {
+        assert_eq!(calculate_receipt_root::<ReceiptEnvelope>(&[]), EMPTY_ROOT_HASH);
+        assert_eq!(InclusionProof::new_2718::<ReceiptEnvelope>(&[], 0), None);
+        assert_eq!(calculate_withdrawals_root(&[]), EMPTY_ROOT_HASH);
+        assert_eq!(calculate_requests_root(&[]), EMPTY_ROOT_HASH);
+        assert_eq!(calculate_ommers_root(&[]), crate::EMPTY_OMMER_ROOT_HASH);
+    }
+
+    #[test]
+    fn adjusted_indices_are_sorted_by_key() {
+        for len in [1, 2, 10, 127, 128, 129, 300] {
+            let keys: Vec<_> =
+                (0..len).map(|i| ordered_trie_key(adjust_index_for_rlp(i, len))).collect();
+            assert!(keys.windows(2).all(|w| w[0] < w[1]), "unsorted keys for len {len}");
+        }
+    }
+
+    #[test]
+    fn single_leaf_root() {
+        let receipts = receipts(1);
+        let value = Bytes::from(receipts[0].encoded_2718());
+
+        // a single leaf node at key `rlp(0) = 0x80`, with the hex-prefix encoded path `0x2080`
+        let mut node = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: hex!("2080").length() + value.length() }
+            .encode(&mut node);
+        hex!("2080").encode(&mut node);
+        value.encode(&mut node);
+
+        assert_eq!(calculate_receipt_root(&receipts), keccak256(&node));
+        assert_eq!(InclusionProof::new_2718(&receipts, 0).unwrap().proof, vec![Bytes::from(node)]);
+    }
+
+    #[test]
+    fn inclusion_proofs() {
+        for n in [1, 2, 3, 16, 127, 128, 129, 200] {
+            let receipts = receipts(n);
+            let root = calculate_receipt_root(&receipts);
+
+            for index in [0, n as usize / 2, n as usize - 1] {
+                let proof = InclusionProof::new_2718(&receipts, index).unwrap();
+                assert_eq!(proof.root, root);
+                assert_eq!(proof.value, Bytes::from(receipts[index].encoded_2718()));
+                proof.verify_self().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_inclusion_proofs() {
+        let receipts = receipts(20);
+        let root = calculate_receipt_root(&receipts);
+        let proof = InclusionProof::new_2718(&receipts, 7).unwrap();
+
+        assert!(proof.verify(B256::ZERO).is_err());
+
+        let mut wrong_index = proof.clone();
+        wrong_index.index = 8;
+        assert!(wrong_index.verify(root).is_err());
+
+        let mut wrong_value = proof;
+        wrong_value.value = receipts[8].encoded_2718().into();
+        assert!(wrong_value.verify(root).is_err());
+    }
+
+    /// Builds a trie of the values at the hashed keys, returning its root and the proof of `key`.
+    fn secure_trie_proof<K: AsRef<[u8]>>(values: &[(K, Vec<u8>)], key: K) -> (B256, Vec<Bytes>) {
+        let mut leaves = values
+            .iter()
+            .map(|(key, value)| (Nibbles::unpack(keccak256(key)), value))
+            .collect::<Vec<_>>();
+        leaves.sort();
+
+        let target = Nibbles::unpack(keccak256(key));
+        let mut hb =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![target.clone()]));
+        for (key, value) in leaves {
+            hb.add_leaf(key, value);
+        }
+        let root = hb.root();
+        let proof = hb
+            .take_proof_nodes()
+            .matching_nodes_sorted(&target)
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+        (root, proof)
+    }
+
+    #[test]
+    fn storage_proofs() {
+        let slots = (1..=20u8)
+            .map(|i| (B256::with_last_byte(i), alloy_rlp::encode(U256::from(i))))
+            .collect::<Vec<_>>();
+
+        let (root, proof) = secure_trie_proof(&slots, B256::with_last_byte(7));
+        verify_storage_proof(root, B256::with_last_byte(7), U256::from(7), &proof).unwrap();
+        assert!(verify_storage_proof(root, B256::with_last_byte(7), U256::from(8), &proof).is_err());
+        assert!(verify_storage_proof(root, B256::with_last_byte(7), U256::ZERO, &proof).is_err());
+
+        // empty slots are proven by exclusion
+        let (root, proof) = secure_trie_proof(&slots, B256::with_last_byte(21));
+        verify_storage_proof(root, B256::with_last_byte(21), U256::ZERO, &proof).unwrap();
+        assert!(
+            verify_storage_proof(root, B256::with_last_byte(21), U256::from(1), &proof).is_err()
+        );
+    }
+
+    #[test]
+    fn account_proofs() {
+        let account = Account { nonce: 3, balance: U256::from(5), ..Default::default() };
+        let address = Address::with_last_byte(1);
+        let accounts = [
+            (address, alloy_rlp::encode(account)),
+            (Address::with_last_byte(2), alloy_rlp::encode(Account::default())),
+        ];
+
+        let (root, proof) = secure_trie_proof(&accounts, address);
+        verify_account_proof(root, address, Some(account), &proof).unwrap();
+        let wrong = Account { balance: U256::from(6), ..account };
+        assert!(verify_account_proof(root, address, Some(wrong), &proof).is_err());
+        assert!(verify_account_proof(root, address, None, &proof).is_err());
+
+        let (root, proof) = secure_trie_proof(&accounts, Address::with_last_byte(3));
+        verify_account_proof(root, Address::with_last_byte(3), None, &proof).unwrap();
+    }
+}
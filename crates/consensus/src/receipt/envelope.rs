@@ -105,6 +105,17 @@ impl<T> ReceiptEnvelope<T> {
             | Self::Eip7702(t) => Some(&t.receipt),
         }
     }
+
+    /// Converts the receipt's logs with the given function, keeping the receipt type.
+    pub fn map_logs<U>(self, f: impl FnMut(T) -> U) -> ReceiptEnvelope<U> {
+        match self {
+            Self::Legacy(r) => ReceiptEnvelope::Legacy(r.map_logs(f)),
+            Self::Eip2930(r) => ReceiptEnvelope::Eip2930(r.map_logs(f)),
+            Self::Eip1559(r) => ReceiptEnvelope::Eip1559(r.map_logs(f)),
+            Self::Eip4844(r) => ReceiptEnvelope::Eip4844(r.map_logs(f)),
+            Self::Eip7702(r) => ReceiptEnvelope::Eip7702(r.map_logs(f)),
+        }
+    }
 }
 
 impl<T> TxReceipt<T> for ReceiptEnvelope<T> {
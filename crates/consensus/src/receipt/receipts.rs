@@ -52,6 +52,14 @@ where
     }
 }
 
+impl<T> Receipt<T> {
+    /// Converts the receipt's logs with the given function.
+    pub fn map_logs<U>(self, f: impl FnMut(T) -> U) -> Receipt<U> {
+        let Self { status, cumulative_gas_used, logs } = self;
+        Receipt { status, cumulative_gas_used, logs: logs.into_iter().map(f).collect() }
+    }
+}
+
 impl<T> Receipt<T>
 where
     T: Borrow<Log>,
@@ -228,6 +236,11 @@ impl<T> ReceiptWithBloom<T> {
         (self.receipt, self.logs_bloom)
     }
 
+    /// Converts the receipt's logs with the given function, keeping the bloom filter.
+    pub fn map_logs<U>(self, f: impl FnMut(T) -> U) -> ReceiptWithBloom<U> {
+        ReceiptWithBloom { receipt: self.receipt.map_logs(f), logs_bloom: self.logs_bloom }
+    }
+
     /// Decodes the receipt payload
     fn decode_receipt(buf: &mut &[u8]) -> alloy_rlp::Result<Self>
     where
@@ -5,8 +5,9 @@ use alloy_eips::{
     eip7251::ConsolidationRequest,
     eip7685::{Decodable7685, Eip7685Error, Encodable7685},
 };
-use alloy_primitives::{bytes, Bytes};
+use alloy_primitives::{bytes, Bytes, B256};
 use alloy_rlp::{Decodable, Encodable};
+use core::fmt;
 use derive_more::{Deref, DerefMut, From, IntoIterator};
 
 /// Ethereum execution layer requests.
@@ -153,3 +154,249 @@ impl Decodable for Requests {
             .map(Self)?)
     }
 }
+
+impl Requests {
+    /// Returns `true` if the requests are sorted by [request type](Encodable7685::request_type),
+    /// as required in a block.
+    pub fn is_sorted_by_type(&self) -> bool {
+        self.0.windows(2).all(|w| w[0].request_type() <= w[1].request_type())
+    }
+
+    /// Validates that the requests are sorted by [request type](Encodable7685::request_type).
+    ///
+    /// Requests are never empty once decoded, see [`Requests::validate_encoded`] for the encoded
+    /// form.
+    pub fn validate(&self) -> Result<(), RequestsError> {
+        self.0.windows(2).position(|w| w[0].request_type() > w[1].request_type()).map_or(
+            Ok(()),
+            |index| {
+                Err(RequestsError::Unsorted {
+                    index: index + 1,
+                    request_type: self.0[index + 1].request_type(),
+                    previous_type: self.0[index].request_type(),
+                })
+            },
+        )
+    }
+
+    /// Validates encoded requests, each a request type followed by its data, as in
+    /// [`Encodable7685::encoded_7685`]: no entry may be empty or lack data, and the entries must be
+    /// sorted by request type.
+    pub fn validate_encoded(encoded: &[Bytes]) -> Result<(), RequestsError> {
+        if let Some(index) = encoded.iter().position(|req| req.len() < 2) {
+            return Err(RequestsError::Empty { index });
+        }
+        encoded.windows(2).position(|w| w[0][0] > w[1][0]).map_or(Ok(()), |index| {
+            Err(RequestsError::Unsorted {
+                index: index + 1,
+                request_type: encoded[index + 1][0],
+                previous_type: encoded[index][0],
+            })
+        })
+    }
+
+    /// Sorts the requests by [request type](Encodable7685::request_type), keeping the order of
+    /// requests of the same type.
+    pub fn sort_by_type(&mut self) {
+        self.0.sort_by_key(|req| req.request_type());
+    }
+
+    /// Splits the requests into per-type lists, keeping the order of requests of the same type.
+    pub fn by_type(&self) -> RequestsByType {
+        let mut by_type = RequestsByType::default();
+        for req in &self.0 {
+            match req {
+                Request::DepositRequest(req) => by_type.deposits.push(*req),
+                Request::WithdrawalRequest(req) => by_type.withdrawals.push(*req),
+                Request::ConsolidationRequest(req) => by_type.consolidations.push(*req),
+            }
+        }
+        by_type
+    }
+
+    /// Returns the commitment to the requests, which is the `requests_root` of the header of the
+    /// block containing them.
+    ///
+    /// See [`calculate_requests_root`](crate::proofs::calculate_requests_root).
+    pub fn root(&self) -> B256 {
+        crate::proofs::calculate_requests_root(&self.0)
+    }
+}
+
+/// The requests of a block, split by request type.
+///
+/// Merging the lists with [`RequestsByType::into_requests`] always produces requests that are
+/// sorted by type. Types without requests do not contribute any entries.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ssz", derive(ssz_derive::Encode, ssz_derive::Decode))]
+pub struct RequestsByType {
+    /// The [`DepositRequest`]s, of type `0`.
+    pub deposits: Vec<DepositRequest>,
+    /// The [`WithdrawalRequest`]s, of type `1`.
+    pub withdrawals: Vec<WithdrawalRequest>,
+    /// The [`ConsolidationRequest`]s, of type `2`.
+    pub consolidations: Vec<ConsolidationRequest>,
+}
+
+impl RequestsByType {
+    /// Returns `true` if there are no requests of any type.
+    pub fn is_empty(&self) -> bool {
+        self.deposits.is_empty() && self.withdrawals.is_empty() && self.consolidations.is_empty()
+    }
+
+    /// Merges the per-type lists into requests sorted by type.
+    pub fn into_requests(self) -> Requests {
+        let Self { deposits, withdrawals, consolidations } = self;
+        Requests(
+            deposits
+                .into_iter()
+                .map(Request::from)
+                .chain(withdrawals.into_iter().map(Request::from))
+                .chain(consolidations.into_iter().map(Request::from))
+                .collect(),
+        )
+    }
+}
+
+/// Requests are SSZ encoded like the request lists of an `ExecutionPayloadV4`, see
+/// [`RequestsByType`]. Decoded requests are sorted by type.
+#[cfg(feature = "ssz")]
+impl ssz::Encode for Requests {
+    fn is_ssz_fixed_len() -> bool {
+        <RequestsByType as ssz::Encode>::is_ssz_fixed_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        self.by_type().ssz_append(buf)
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        self.by_type().ssz_bytes_len()
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl ssz::Decode for Requests {
+    fn is_ssz_fixed_len() -> bool {
+        <RequestsByType as ssz::Decode>::is_ssz_fixed_len()
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        RequestsByType::from_ssz_bytes(bytes).map(RequestsByType::into_requests)
+    }
+}
+
+impl From<RequestsByType> for Requests {
+    fn from(by_type: RequestsByType) -> Self {
+        by_type.into_requests()
+    }
+}
+
+/// An error returned by [`Requests::validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestsError {
+    /// A request is of a lower type than the request before it.
+    Unsorted {
+        /// The index of the out-of-order request.
+        index: usize,
+        /// The type of the out-of-order request.
+        request_type: u8,
+        /// The type of the request before it.
+        previous_type: u8,
+    },
+    /// An encoded request is empty, or has a request type but no data.
+    Empty {
+        /// The index of the empty request.
+        index: usize,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RequestsError {}
+
+impl fmt::Display for RequestsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsorted { index, request_type, previous_type } => write!(
+                f,
+                "request {index} of type {request_type} follows a request of type {previous_type}"
+            ),
+            Self::Empty { index } => write!(f, "request {index} is empty"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requests() -> RequestsByType {
+        RequestsByType {
+            deposits: vec![
+                DepositRequest { index: 0, ..Default::default() },
+                DepositRequest { index: 1, ..Default::default() },
+            ],
+            withdrawals: vec![],
+            consolidations: vec![ConsolidationRequest::default()],
+        }
+    }
+
+    #[test]
+    fn merge_and_split() {
+        let by_type = requests();
+        let merged = by_type.clone().into_requests();
+        assert_eq!(merged.len(), 3);
+        assert!(merged.is_sorted_by_type());
+        assert_eq!(merged.validate(), Ok(()));
+        assert_eq!(merged.by_type(), by_type);
+        assert!(RequestsByType::default().into_requests().is_empty());
+    }
+
+    #[test]
+    fn unsorted_requests() {
+        let mut requests = requests().into_requests();
+        requests.0.rotate_right(1);
+        assert!(!requests.is_sorted_by_type());
+        assert_eq!(
+            requests.validate(),
+            Err(RequestsError::Unsorted { index: 1, request_type: 0, previous_type: 2 })
+        );
+
+        let root = requests.root();
+        requests.sort_by_type();
+        assert_eq!(requests, self::requests().into_requests());
+        assert_ne!(requests.root(), root);
+    }
+
+    #[test]
+    fn encoded_requests() {
+        let encoded: Vec<Bytes> =
+            requests().into_requests().iter().map(|req| req.encoded_7685().into()).collect();
+        assert_eq!(Requests::validate_encoded(&encoded), Ok(()));
+        assert_eq!(Requests::validate_encoded(&[]), Ok(()));
+
+        let mut empty = encoded.clone();
+        empty.insert(1, Bytes::new());
+        assert_eq!(Requests::validate_encoded(&empty), Err(RequestsError::Empty { index: 1 }));
+        empty[1] = Bytes::from_static(&[1]);
+        assert_eq!(Requests::validate_encoded(&empty), Err(RequestsError::Empty { index: 1 }));
+
+        let mut unsorted = encoded;
+        unsorted.rotate_right(1);
+        assert_eq!(
+            Requests::validate_encoded(&unsorted),
+            Err(RequestsError::Unsorted { index: 1, request_type: 0, previous_type: 2 })
+        );
+    }
+
+    #[cfg(feature = "ssz")]
+    #[test]
+    fn requests_ssz_roundtrip() {
+        use ssz::{Decode, Encode};
+
+        let by_type = requests();
+        let encoded = by_type.clone().into_requests().as_ssz_bytes();
+        assert_eq!(encoded, by_type.as_ssz_bytes());
+        assert_eq!(Requests::from_ssz_bytes(&encoded).unwrap(), by_type.into_requests());
+    }
+}
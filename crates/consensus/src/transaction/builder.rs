@@ -0,0 +1,287 @@
+//! Typestate builders for consensus transactions.
+//!
+//! The builders track which required fields were set in their type parameters, so that `build`
+//! is only available once all of them were set, and a missing field is a compile-time error
+//! rather than a runtime one.
+//!
+//! ```
+//! use alloy_consensus::TxEip1559;
+//! use alloy_primitives::{address, U256};
+//!
+//! let tx = TxEip1559::builder()
+//!     .chain_id(1)
+//!     .nonce(0)
+//!     .gas_limit(21_000)
+//!     .fees(20_000_000_000, 1_000_000_000)
+//!     .to(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+//!     .value(U256::from(1))
+//!     .build();
+//! assert_eq!(tx.gas_limit, 21_000);
+//! ```
+//!
+//! A builder with missing fields does not compile:
+//!
+//! ```compile_fail
+//! use alloy_consensus::TxEip1559;
+//!
+//! // the nonce is missing
+//! let tx = TxEip1559::builder().chain_id(1).gas_limit(21_000).fees(20, 1).build();
+//! ```
+
+use crate::{SignableTransaction, TxEip1559, TxLegacy};
+use alloy_eips::eip2930::AccessList;
+use alloy_primitives::{Bytes, ChainId, TxKind, B256, U256};
+use core::marker::PhantomData;
+
+/// Marks a required field of a builder as not yet set.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Missing;
+
+/// Marks a required field of a builder as set.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Set;
+
+/// A typestate builder for a [`TxEip1559`].
+///
+/// The chain ID, nonce, gas limit and fees are required, see the [module docs](self).
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct TxEip1559Builder<ChainId = Missing, Nonce = Missing, Gas = Missing, Fees = Missing> {
+    tx: TxEip1559,
+    _state: PhantomData<(ChainId, Nonce, Gas, Fees)>,
+}
+
+impl Default for TxEip1559Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxEip1559Builder {
+    /// Creates a new builder with no fields set.
+    pub fn new() -> Self {
+        Self { tx: TxEip1559::default(), _state: PhantomData }
+    }
+}
+
+impl<C, N, G, F> TxEip1559Builder<C, N, G, F> {
+    fn transition<C2, N2, G2, F2>(self) -> TxEip1559Builder<C2, N2, G2, F2> {
+        TxEip1559Builder { tx: self.tx, _state: PhantomData }
+    }
+
+    /// Sets the chain ID.
+    pub fn chain_id(mut self, chain_id: ChainId) -> TxEip1559Builder<Set, N, G, F> {
+        self.tx.chain_id = chain_id;
+        self.transition()
+    }
+
+    /// Sets the nonce.
+    pub fn nonce(mut self, nonce: u64) -> TxEip1559Builder<C, Set, G, F> {
+        self.tx.nonce = nonce;
+        self.transition()
+    }
+
+    /// Sets the gas limit.
+    pub fn gas_limit(mut self, gas_limit: u64) -> TxEip1559Builder<C, N, Set, F> {
+        self.tx.gas_limit = gas_limit;
+        self.transition()
+    }
+
+    /// Sets the max fee per gas and the max priority fee per gas.
+    pub fn fees(
+        mut self,
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    ) -> TxEip1559Builder<C, N, G, Set> {
+        self.tx.max_fee_per_gas = max_fee_per_gas;
+        self.tx.max_priority_fee_per_gas = max_priority_fee_per_gas;
+        self.transition()
+    }
+
+    /// Sets the recipient, or [`TxKind::Create`] for a contract creation.
+    ///
+    /// Defaults to a contract creation.
+    pub fn to(mut self, to: impl Into<TxKind>) -> Self {
+        self.tx.to = to.into();
+        self
+    }
+
+    /// Sets the value. Defaults to zero.
+    pub const fn value(mut self, value: U256) -> Self {
+        self.tx.value = value;
+        self
+    }
+
+    /// Sets the input data. Defaults to empty.
+    pub fn input(mut self, input: impl Into<Bytes>) -> Self {
+        self.tx.input = input.into();
+        self
+    }
+
+    /// Sets the access list. Defaults to empty.
+    pub fn access_list(mut self, access_list: AccessList) -> Self {
+        self.tx.access_list = access_list;
+        self
+    }
+}
+
+impl TxEip1559Builder<Set, Set, Set, Set> {
+    /// Returns the transaction.
+    pub fn build(self) -> TxEip1559 {
+        self.tx
+    }
+
+    /// Returns the hash of the transaction that must be signed.
+    pub fn signature_hash(&self) -> B256 {
+        self.tx.signature_hash()
+    }
+}
+
+impl TxEip1559 {
+    /// Returns a typestate builder for the transaction.
+    pub fn builder() -> TxEip1559Builder {
+        TxEip1559Builder::new()
+    }
+}
+
+/// A typestate builder for a [`TxLegacy`].
+///
+/// The nonce, gas limit and gas price are required. The chain ID is optional, legacy transactions
+/// without a chain ID are not replay protected by [EIP-155].
+///
+/// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct TxLegacyBuilder<Nonce = Missing, Gas = Missing, GasPrice = Missing> {
+    tx: TxLegacy,
+    _state: PhantomData<(Nonce, Gas, GasPrice)>,
+}
+
+impl Default for TxLegacyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxLegacyBuilder {
+    /// Creates a new builder with no fields set.
+    pub fn new() -> Self {
+        Self { tx: TxLegacy::default(), _state: PhantomData }
+    }
+}
+
+impl<N, G, P> TxLegacyBuilder<N, G, P> {
+    fn transition<N2, G2, P2>(self) -> TxLegacyBuilder<N2, G2, P2> {
+        TxLegacyBuilder { tx: self.tx, _state: PhantomData }
+    }
+
+    /// Sets the [EIP-155] chain ID.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub const fn chain_id(mut self, chain_id: ChainId) -> Self {
+        self.tx.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the nonce.
+    pub fn nonce(mut self, nonce: u64) -> TxLegacyBuilder<Set, G, P> {
+        self.tx.nonce = nonce;
+        self.transition()
+    }
+
+    /// Sets the gas limit.
+    pub fn gas_limit(mut self, gas_limit: u64) -> TxLegacyBuilder<N, Set, P> {
+        self.tx.gas_limit = gas_limit;
+        self.transition()
+    }
+
+    /// Sets the gas price.
+    pub fn gas_price(mut self, gas_price: u128) -> TxLegacyBuilder<N, G, Set> {
+        self.tx.gas_price = gas_price;
+        self.transition()
+    }
+
+    /// Sets the recipient, or [`TxKind::Create`] for a contract creation.
+    ///
+    /// Defaults to a contract creation.
+    pub fn to(mut self, to: impl Into<TxKind>) -> Self {
+        self.tx.to = to.into();
+        self
+    }
+
+    /// Sets the value. Defaults to zero.
+    pub const fn value(mut self, value: U256) -> Self {
+        self.tx.value = value;
+        self
+    }
+
+    /// Sets the input data. Defaults to empty.
+    pub fn input(mut self, input: impl Into<Bytes>) -> Self {
+        self.tx.input = input.into();
+        self
+    }
+}
+
+impl TxLegacyBuilder<Set, Set, Set> {
+    /// Returns the transaction.
+    pub fn build(self) -> TxLegacy {
+        self.tx
+    }
+
+    /// Returns the hash of the transaction that must be signed.
+    pub fn signature_hash(&self) -> B256 {
+        self.tx.signature_hash()
+    }
+}
+
+impl TxLegacy {
+    /// Returns a typestate builder for the transaction.
+    pub fn builder() -> TxLegacyBuilder {
+        TxLegacyBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+
+    #[test]
+    fn build_eip1559() {
+        let to = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        // required fields can be set in any order
+        let builder = TxEip1559::builder()
+            .fees(20, 1)
+            .to(to)
+            .gas_limit(21_000)
+            .nonce(7)
+            .chain_id(1)
+            .input(bytes!("01"));
+        let hash = builder.signature_hash();
+        let tx = builder.build();
+
+        let expected = TxEip1559 {
+            chain_id: 1,
+            nonce: 7,
+            gas_limit: 21_000,
+            max_fee_per_gas: 20,
+            max_priority_fee_per_gas: 1,
+            to: to.into(),
+            input: bytes!("01"),
+            ..Default::default()
+        };
+        assert_eq!(tx, expected);
+        assert_eq!(hash, expected.signature_hash());
+    }
+
+    #[test]
+    fn build_legacy() {
+        let tx = TxLegacy::builder().nonce(1).gas_price(10).gas_limit(21_000).build();
+        assert_eq!(tx.chain_id, None);
+        assert!(tx.to.is_create());
+
+        let builder = TxLegacy::builder().chain_id(5).nonce(1).gas_price(10).gas_limit(21_000);
+        assert_eq!(builder.signature_hash(), builder.clone().build().signature_hash());
+        assert_eq!(builder.build().chain_id, Some(5));
+    }
+}
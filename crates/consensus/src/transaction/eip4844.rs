@@ -350,7 +350,7 @@ pub struct TxEip4844 {
     /// this transaction. This is paid up-front, before any
     /// computation is done and may not be increased
     /// later; formally Tg.
-    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity", alias = "gas"))]
     pub gas_limit: u64,
     /// A scalar value equal to the maximum
     /// amount of gas that should be used in executing
@@ -820,6 +820,15 @@ impl TxEip4844WithSidecar {
         self.tx.validate_blob(&self.sidecar, proof_settings)
     }
 
+    /// Verifies the sidecar against the versioned hashes of the transaction, using the default
+    /// mainnet KZG trusted setup.
+    ///
+    /// See also [BlobTransactionSidecar::verify]
+    #[cfg(feature = "kzg")]
+    pub fn verify(&self) -> Result<(), BlobTransactionValidationError> {
+        self.sidecar.verify(&self.tx.blob_versioned_hashes)
+    }
+
     /// Get the transaction type.
     #[doc(alias = "transaction_type")]
     pub const fn tx_type(&self) -> TxType {
@@ -84,10 +84,14 @@ impl TryFrom<u8> for TxType {
 /// was decoded, preserving the presence or absence of the `TransactionType`
 /// flag.
 ///
+/// The transaction object of the JSON-RPC API can be deserialized directly: its `gas` field is
+/// accepted for the `gasLimit`, and the `type` of legacy transactions may be omitted, as some nodes
+/// do for pre-[EIP-2718] transactions.
+///
 /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", from = "serde_from::MaybeTaggedTxEnvelope"))]
 #[doc(alias = "TransactionEnvelope")]
 #[non_exhaustive]
 pub enum TxEnvelope {
@@ -523,6 +527,68 @@ impl Transaction for TxEnvelope {
     }
 }
 
+#[cfg(feature = "serde")]
+pub(crate) mod serde_from {
+    //! Deserializes a [`TxEnvelope`] from the JSON-RPC transaction object, in which the `type` of
+    //! legacy transactions may be omitted.
+
+    use super::*;
+    use serde::{de::IgnoredAny, Deserialize, Deserializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(crate) enum MaybeTaggedTxEnvelope {
+        Tagged(TaggedTxEnvelope),
+        Untagged {
+            #[serde(default, rename = "type", deserialize_with = "reject_type")]
+            _ty: Option<IgnoredAny>,
+            #[serde(flatten)]
+            tx: Signed<TxLegacy>,
+        },
+    }
+
+    #[derive(Deserialize)]
+    #[serde(tag = "type")]
+    pub(crate) enum TaggedTxEnvelope {
+        #[serde(rename = "0x0", alias = "0x00")]
+        Legacy(Signed<TxLegacy>),
+        #[serde(rename = "0x1", alias = "0x01")]
+        Eip2930(Signed<TxEip2930>),
+        #[serde(rename = "0x2", alias = "0x02")]
+        Eip1559(Signed<TxEip1559>),
+        #[serde(rename = "0x3", alias = "0x03")]
+        Eip4844(Signed<TxEip4844Variant>),
+        #[serde(rename = "0x4", alias = "0x04")]
+        Eip7702(Signed<TxEip7702>),
+    }
+
+    impl From<MaybeTaggedTxEnvelope> for TxEnvelope {
+        fn from(value: MaybeTaggedTxEnvelope) -> Self {
+            match value {
+                MaybeTaggedTxEnvelope::Tagged(tagged) => match tagged {
+                    TaggedTxEnvelope::Legacy(tx) => Self::Legacy(tx),
+                    TaggedTxEnvelope::Eip2930(tx) => Self::Eip2930(tx),
+                    TaggedTxEnvelope::Eip1559(tx) => Self::Eip1559(tx),
+                    TaggedTxEnvelope::Eip4844(tx) => Self::Eip4844(tx),
+                    TaggedTxEnvelope::Eip7702(tx) => Self::Eip7702(tx),
+                },
+                MaybeTaggedTxEnvelope::Untagged { tx, .. } => Self::Legacy(tx),
+            }
+        }
+    }
+
+    /// Rejects a present `type`, so that typed transactions which fail to deserialize are not
+    /// deserialized as untagged legacy transactions instead.
+    pub(crate) fn reject_type<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<IgnoredAny>, D::Error> {
+        match Option::<IgnoredAny>::deserialize(deserializer)? {
+            Some(_) => Err(serde::de::Error::custom("unknown or invalid transaction type")),
+            None => Ok(None),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -995,4 +1061,50 @@ mod tests {
         };
         test_serde_roundtrip(tx);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_rpc_transactions() {
+        // cast rpc eth_getTransactionByHash
+        // 0xe9e91f1ee4b56c0df2e9f06c2b8c27c6076195a88a7b8537ba8313d80e6f124e --rpc-url mainnet
+        let legacy = r#"{"blockHash":"0x8e38b4dbf6b11fcc3b9dee84fb7986e29ca0a02cecd8977c161ff7333329681e","blockNumber":"0xf4240","hash":"0xe9e91f1ee4b56c0df2e9f06c2b8c27c6076195a88a7b8537ba8313d80e6f124e","transactionIndex":"0x1","type":"0x0","nonce":"0x43eb","input":"0x","r":"0x3b08715b4403c792b8c7567edea634088bedcd7f60d9352b1f16c69830f3afd5","s":"0x10b9afb67d2ec8b956f0e1dbc07eb79152904f3a7bf789fc869db56320adfe09","chainId":"0x0","v":"0x1c","gas":"0xc350","from":"0x32be343b94f860124dc4fee278fdcbd38c102d88","to":"0xdf190dc7190dfba737d7777a163445b7fff16133","value":"0x6113a84987be800","gasPrice":"0xdf8475800"}"#;
+        // cast rpc eth_getTransactionByHash
+        // 0x0e07d8b53ed3d91314c80e53cf25bcde02084939395845cbb625b029d568135c --rpc-url mainnet
+        let eip1559 = r#"{"blockHash":"0x883f974b17ca7b28cb970798d1c80f4d4bb427473dc6d39b2a7fe24edc02902d","blockNumber":"0xe26e6d","hash":"0x0e07d8b53ed3d91314c80e53cf25bcde02084939395845cbb625b029d568135c","accessList":[],"transactionIndex":"0xad","type":"0x2","nonce":"0x16d","input":"0x5ae401dc00000000000000000000000000000000000000000000000000000000628ced5b000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000016000000000000000000000000000000000000000000000000000000000000000e442712a6700000000000000000000000000000000000000000000b3ff1489674e11c40000000000000000000000000000000000000000000000000000004a6ed55bbcc18000000000000000000000000000000000000000000000000000000000000000800000000000000000000000003cf412d970474804623bb4e3a42de13f9bca54360000000000000000000000000000000000000000000000000000000000000002000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000003a75941763f31c930b19c041b709742b0b31ebb600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000412210e8a00000000000000000000000000000000000000000000000000000000","r":"0x7f2153019a74025d83a73effdd91503ceecefac7e35dd933adc1901c875539aa","s":"0x334ab2f714796d13c825fddf12aad01438db3a8152b2fe3ef7827707c25ecab3","chainId":"0x1","v":"0x0","gas":"0x46a02","maxPriorityFeePerGas":"0x59682f00","from":"0x3cf412d970474804623bb4e3a42de13f9bca5436","to":"0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45","maxFeePerGas":"0x7fc1a20a8","value":"0x4a6ed55bbcc180","gasPrice":"0x50101df3a"}"#;
+
+        for rpc_tx in [legacy, eip1559] {
+            let tx: TxEnvelope = serde_json::from_str(rpc_tx).unwrap();
+            assert_eq!(*tx.tx_hash(), alloy_primitives::keccak256(tx.encoded_2718()));
+
+            let serialized = serde_json::to_string(&tx).unwrap();
+            assert_eq!(serde_json::from_str::<TxEnvelope>(&serialized).unwrap(), tx);
+        }
+
+        let tx: TxEnvelope = serde_json::from_str(legacy).unwrap();
+        assert!(tx.is_legacy());
+        assert_eq!(tx.gas_limit(), 0xc350);
+
+        // the `type` of legacy transactions may be omitted
+        let mut untagged: serde_json::Value = serde_json::from_str(legacy).unwrap();
+        untagged.as_object_mut().unwrap().remove("type");
+        assert_eq!(serde_json::from_value::<TxEnvelope>(untagged.clone()).unwrap(), tx);
+
+        // but a typed transaction that fails to deserialize is not taken for a legacy one
+        untagged["type"] = "0x2".into();
+        assert!(serde_json::from_value::<TxEnvelope>(untagged).is_err());
+
+        let typed: crate::TypedTransaction = serde_json::from_str(eip1559).unwrap();
+        assert_eq!(typed.tx_type(), TxType::Eip1559);
+
+        // the serialized form is unchanged
+        let serialized = serde_json::to_value(&tx).unwrap();
+        assert_eq!(serialized["gasLimit"], "0xc350");
+        assert!(serialized.get("gas").is_none());
+        let serialized = serde_json::to_value(crate::TypedTransaction::from(tx)).unwrap();
+        assert_eq!(serialized["type"], "0x00");
+        assert_eq!(
+            serde_json::from_value::<crate::TypedTransaction>(serialized).unwrap().tx_type(),
+            TxType::Legacy
+        );
+    }
 }
@@ -6,6 +6,9 @@ use alloy_eips::{eip2930::AccessList, eip7702::SignedAuthorization};
 use alloy_primitives::{keccak256, ChainId, TxKind, B256, U256};
 use core::any;
 
+pub mod builder;
+pub use builder::{TxEip1559Builder, TxLegacyBuilder};
+
 mod eip1559;
 pub use eip1559::TxEip1559;
 
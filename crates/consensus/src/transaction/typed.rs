@@ -13,9 +13,15 @@ use crate::{
 /// 2. EIP2930 (state access lists) [`TxEip2930`]
 /// 3. EIP1559 [`TxEip1559`]
 /// 4. EIP4844 [`TxEip4844Variant`]
+///
+/// Like [`TxEnvelope`], the JSON-RPC transaction object can be deserialized, including the `gas`
+/// field and legacy transactions without a `type`. The serialized form is unchanged.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", from = "serde_from::MaybeTaggedTypedTransaction")
+)]
 #[doc(alias = "TypedTx", alias = "TxTyped", alias = "TransactionTyped")]
 pub enum TypedTransaction {
     /// Legacy transaction
@@ -300,3 +306,55 @@ impl<T: From<TxEnvelope>> From<TxEnvelope> for alloy_serde::WithOtherFields<T> {
         Self::new(value.into())
     }
 }
+
+#[cfg(feature = "serde")]
+mod serde_from {
+    //! Deserializes a [`TypedTransaction`] from the JSON-RPC transaction object, in which the
+    //! `type` of legacy transactions may be omitted.
+
+    use super::*;
+    use crate::transaction::envelope::serde_from::reject_type;
+    use serde::{de::IgnoredAny, Deserialize};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum MaybeTaggedTypedTransaction {
+        Tagged(TaggedTypedTransaction),
+        Untagged {
+            #[serde(default, rename = "type", deserialize_with = "reject_type")]
+            _ty: Option<IgnoredAny>,
+            #[serde(flatten)]
+            tx: TxLegacy,
+        },
+    }
+
+    #[derive(Deserialize)]
+    #[serde(tag = "type")]
+    pub(super) enum TaggedTypedTransaction {
+        #[serde(rename = "0x0", alias = "0x00")]
+        Legacy(TxLegacy),
+        #[serde(rename = "0x1", alias = "0x01")]
+        Eip2930(TxEip2930),
+        #[serde(rename = "0x2", alias = "0x02")]
+        Eip1559(TxEip1559),
+        #[serde(rename = "0x3", alias = "0x03")]
+        Eip4844(TxEip4844Variant),
+        #[serde(rename = "0x4", alias = "0x04")]
+        Eip7702(TxEip7702),
+    }
+
+    impl From<MaybeTaggedTypedTransaction> for TypedTransaction {
+        fn from(value: MaybeTaggedTypedTransaction) -> Self {
+            match value {
+                MaybeTaggedTypedTransaction::Tagged(tagged) => match tagged {
+                    TaggedTypedTransaction::Legacy(tx) => Self::Legacy(tx),
+                    TaggedTypedTransaction::Eip2930(tx) => Self::Eip2930(tx),
+                    TaggedTypedTransaction::Eip1559(tx) => Self::Eip1559(tx),
+                    TaggedTypedTransaction::Eip4844(tx) => Self::Eip4844(tx),
+                    TaggedTypedTransaction::Eip7702(tx) => Self::Eip7702(tx),
+                },
+                MaybeTaggedTypedTransaction::Untagged { tx, .. } => Self::Legacy(tx),
+            }
+        }
+    }
+}
@@ -22,6 +22,15 @@ pub enum Error {
     /// `contractAddress` was not found in the deployment transaction’s receipt.
     #[error("missing `contractAddress` from deployment transaction receipt")]
     ContractNotDeployed,
+    /// A human-readable ABI fragment is malformed or repeated.
+    #[error("invalid ABI fragment: {0}")]
+    InvalidFragment(String),
+    /// A storage layout does not describe the requested value.
+    #[error("storage layout: {0}")]
+    StorageLayout(String),
+    /// A value cannot be ABI-encoded in packed mode.
+    #[error("packed encoding: {0}")]
+    PackedEncoding(String),
     /// An error occurred ABI encoding or decoding.
     #[error(transparent)]
     AbiError(#[from] AbiError),
@@ -1,14 +1,17 @@
-use crate::{CallBuilder, Event, Interface, Result};
+use crate::{CallBuilder, Event, Interface, Result, StorageLayout};
 use alloy_dyn_abi::DynSolValue;
 use alloy_json_abi::{Function, JsonAbi};
 use alloy_network::{Ethereum, Network};
 use alloy_primitives::{Address, Selector};
 use alloy_provider::Provider;
-use alloy_rpc_types_eth::Filter;
-use alloy_sol_types::SolEvent;
+use alloy_rpc_types_eth::{BlockId, Filter};
+use alloy_sol_types::{SolEvent, SolType};
 use alloy_transport::Transport;
 use std::marker::PhantomData;
 
+/// The maximum number of nested proxies followed by [`ContractInstance::proxy_implementation`].
+const MAX_PROXY_DEPTH: usize = 8;
+
 /// A handle to an Ethereum contract at a specific address.
 ///
 /// A contract is an abstraction of an executable program on Ethereum. Every deployed contract has
@@ -18,6 +21,7 @@ pub struct ContractInstance<T, P, N = Ethereum> {
     address: Address,
     provider: P,
     interface: Interface,
+    implementation: Option<Address>,
     transport: PhantomData<T>,
     network: PhantomData<N>,
 }
@@ -26,7 +30,14 @@ impl<T, P, N> ContractInstance<T, P, N> {
     /// Creates a new contract from the provided address, provider, and interface.
     #[inline]
     pub const fn new(address: Address, provider: P, interface: Interface) -> Self {
-        Self { address, provider, interface, transport: PhantomData, network: PhantomData }
+        Self {
+            address,
+            provider,
+            interface,
+            implementation: None,
+            transport: PhantomData,
+            network: PhantomData,
+        }
     }
 
     /// Returns a reference to the contract's address.
@@ -59,6 +70,13 @@ impl<T, P, N> ContractInstance<T, P, N> {
     pub const fn provider(&self) -> &P {
         &self.provider
     }
+
+    /// Returns the implementation whose ABI the contract uses, if it was resolved with
+    /// [`ContractInstance::resolve_proxy`].
+    #[inline]
+    pub const fn implementation(&self) -> Option<&Address> {
+        self.implementation.as_ref()
+    }
 }
 
 impl<T, P: Clone, N> ContractInstance<T, &P, N> {
@@ -69,6 +87,7 @@ impl<T, P: Clone, N> ContractInstance<T, &P, N> {
             address: self.address,
             provider: self.provider.clone(),
             interface: self.interface,
+            implementation: self.implementation,
             transport: PhantomData,
             network: PhantomData,
         }
@@ -100,6 +119,49 @@ impl<T: Transport + Clone, P: Provider<T, N>, N: Network> ContractInstance<T, P,
         CallBuilder::new_dyn(&self.provider, &self.address, function, args)
     }
 
+    /// Returns the implementation of the contract if it is a proxy, following nested proxies.
+    ///
+    /// EIP-1967 proxies and beacon proxies, EIP-1822 proxies, EIP-1167 minimal proxies and EIP-7702
+    /// delegations are detected, see [`Provider::get_code_with_metadata`].
+    pub async fn proxy_implementation(&self) -> Result<Option<Address>> {
+        let metadata = self
+            .provider
+            .get_code_with_metadata(self.address, BlockId::latest(), MAX_PROXY_DEPTH)
+            .await?;
+        let resolved = metadata.resolved().address;
+        Ok((resolved != self.address).then_some(resolved))
+    }
+
+    /// Resolves the implementation of the contract if it is a proxy, and rebinds the interface to
+    /// the ABI returned by `abi` for it.
+    ///
+    /// Calls are still addressed to the proxy. The interface is kept if the contract is not a
+    /// proxy, or if `abi` returns `None`.
+    pub async fn resolve_proxy(
+        mut self,
+        abi: impl FnOnce(Address) -> Option<JsonAbi>,
+    ) -> Result<Self> {
+        if let Some(implementation) = self.proxy_implementation().await? {
+            if let Some(abi) = abi(implementation) {
+                self.interface = Interface::new(abi);
+            }
+            self.implementation = Some(implementation);
+        }
+        Ok(self)
+    }
+
+    /// Reads the state variable at the path from the storage of the contract, converted to the
+    /// Solidity type, see [`StorageLayout`].
+    ///
+    /// For proxies, pass the layout of the implementation, since its state lives in the proxy.
+    pub async fn read_storage<S: SolType>(
+        &self,
+        layout: &StorageLayout,
+        path: &str,
+    ) -> Result<S::RustType> {
+        layout.read_as::<S, P, T, N>(&self.provider, self.address, path).await
+    }
+
     /// Returns an [`Event`] builder with the provided filter.
     pub const fn event<E: SolEvent>(&self, filter: Filter) -> Event<T, &P, E, N> {
         Event::new(&self.provider, filter)
@@ -116,7 +178,10 @@ impl<T, P, N> std::ops::Deref for ContractInstance<T, P, N> {
 
 impl<T, P, N> std::fmt::Debug for ContractInstance<T, P, N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ContractInstance").field("address", &self.address).finish()
+        f.debug_struct("ContractInstance")
+            .field("address", &self.address)
+            .field("implementation", &self.implementation)
+            .finish()
     }
 }
 
@@ -157,4 +222,47 @@ mod tests {
         let result = contract.function("counter", &[]).unwrap().call().await.unwrap();
         assert_eq!(result[0].as_uint().unwrap().0, U256::from(1));
     }
+
+    const COUNTER_ABI: &str = r#"[{"inputs":[],"name":"counter","outputs":[{"internalType":"uint256","name":"","type":"uint256"}],"stateMutability":"view","type":"function"},{"inputs":[],"name":"increment","outputs":[],"stateMutability":"nonpayable","type":"function"}]"#;
+    const COUNTER_BYTECODE: &str = "6080806040523460135760b2908160188239f35b5f80fdfe60808060405260043610156011575f80fd5b5f3560e01c90816361bc221a146065575063d09de08a14602f575f80fd5b346061575f3660031901126061575f5460018101809111604d575f55005b634e487b7160e01b5f52601160045260245ffd5b5f80fd5b346061575f3660031901126061576020905f548152f3fea2646970667358221220d802267a5f574e54a87a63d0ff8d733fdb275e6e6c502831d9e14f957bbcd7a264736f6c634300081a0033";
+
+    async fn deploy<P: Provider<T>, T: Transport + Clone>(provider: &P, code: Vec<u8>) -> Address {
+        let deploy_tx = TransactionRequest::default().with_deploy_code(code);
+        provider
+            .send_transaction(deploy_tx)
+            .await
+            .unwrap()
+            .get_receipt()
+            .await
+            .unwrap()
+            .contract_address
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn resolves_minimal_proxy() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let counter = deploy(&provider, hex::decode(COUNTER_BYTECODE).unwrap()).await;
+        let proxy_code = [
+            &hex!("3d602d80600a3d3981f3363d3d373d3d3d363d73")[..],
+            &counter[..],
+            &hex!("5af43d82803e903d91602b57fd5bf3")[..],
+        ]
+        .concat();
+        let proxy = deploy(&provider, proxy_code).await;
+
+        let contract = ContractInstance::new(proxy, provider, Interface::new(JsonAbi::new()))
+            .resolve_proxy(|implementation| {
+                assert_eq!(implementation, counter);
+                serde_json::from_str(COUNTER_ABI).ok()
+            })
+            .await
+            .unwrap();
+        assert_eq!(contract.address(), &proxy);
+        assert_eq!(contract.implementation(), Some(&counter));
+
+        contract.function("increment", &[]).unwrap().send().await.unwrap().watch().await.unwrap();
+        let result = contract.function("counter", &[]).unwrap().call().await.unwrap();
+        assert_eq!(result[0].as_uint().unwrap().0, U256::from(1));
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{ContractInstance, Error, Result};
 use alloy_dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt};
-use alloy_json_abi::{Function, JsonAbi};
+use alloy_json_abi::{Fallback, Function, JsonAbi, Receive, StateMutability};
 use alloy_primitives::{
     map::{FbHashMap, SelectorHashMap},
     Address, FixedBytes, Selector,
@@ -22,6 +22,55 @@ impl Interface {
         Self { abi, functions }
     }
 
+    /// Creates a new contract interface from human-readable ABI fragments, like the ones accepted
+    /// by ethers.js.
+    ///
+    /// Each fragment is a function, event, error, constructor, fallback or receive signature.
+    /// Parameter names are optional, and functions may declare their outputs with `returns`.
+    /// Empty fragments are skipped.
+    ///
+    /// Returns [`Error::AbiError`] if a fragment cannot be parsed, and [`Error::InvalidFragment`]
+    /// for a malformed or repeated `fallback` or `receive` fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alloy_contract::Interface;
+    ///
+    /// let interface = Interface::parse([
+    ///     "function transfer(address to, uint256 amount) external returns (bool)",
+    ///     "function balanceOf(address) view returns (uint256)",
+    ///     "event Transfer(address indexed from, address indexed to, uint256 value)",
+    ///     "error InsufficientBalance(uint256 available, uint256 required)",
+    /// ])?;
+    /// assert_eq!(interface.abi().functions().count(), 2);
+    /// # Ok::<(), alloy_contract::Error>(())
+    /// ```
+    pub fn parse<'a>(fragments: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut fallback = None;
+        let mut receive = None;
+        let mut items = Vec::new();
+        for fragment in fragments.into_iter().map(str::trim).filter(|f| !f.is_empty()) {
+            // `fallback` and `receive` are not supported by the item parser
+            let (slot, state_mutability) = if let Some(rest) = fragment.strip_prefix("fallback") {
+                (&mut fallback, parse_special(fragment, rest)?)
+            } else if let Some(rest) = fragment.strip_prefix("receive") {
+                (&mut receive, parse_special(fragment, rest)?)
+            } else {
+                items.push(fragment);
+                continue;
+            };
+            if slot.replace(state_mutability).is_some() {
+                return Err(Error::InvalidFragment(fragment.to_string()));
+            }
+        }
+
+        let mut abi = JsonAbi::parse(items).map_err(|e| Error::AbiError(e.into()))?;
+        abi.fallback = fallback.map(|state_mutability| Fallback { state_mutability });
+        abi.receive = receive.map(|state_mutability| Receive { state_mutability });
+        Ok(Self::new(abi))
+    }
+
     /// Returns the ABI encoded data (including the selector) for the provided function and
     /// arguments.
     ///
@@ -128,6 +177,21 @@ impl Interface {
     }
 }
 
+/// Parses the state mutability of a `fallback` or `receive` fragment, like
+/// `receive() external payable`, from what follows the keyword.
+fn parse_special(fragment: &str, rest: &str) -> Result<StateMutability> {
+    let invalid = || Error::InvalidFragment(fragment.to_string());
+    let modifiers = rest.trim_start().strip_prefix("()").ok_or_else(invalid)?;
+    let mut state_mutability = StateMutability::NonPayable;
+    for modifier in modifiers.split_whitespace() {
+        match modifier {
+            "external" | "virtual" | "override" => {}
+            modifier => state_mutability = StateMutability::parse(modifier).ok_or_else(invalid)?,
+        }
+    }
+    Ok(state_mutability)
+}
+
 /// Utility function for creating a mapping between a unique signature and a
 /// name-index pair for accessing contract ABI items.
 fn create_mapping<const N: usize, T, F>(
@@ -147,3 +211,47 @@ where
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{hex, U256};
+
+    #[test]
+    fn parses_human_readable_abi() {
+        let interface = Interface::parse([
+            "constructor(address owner)",
+            "function transfer(address,uint) returns (bool)",
+            "function transfer(address to, uint256 amount, bytes data) returns (bool)",
+            "",
+            "event Transfer(address indexed, address indexed, uint256)",
+            "error Unauthorized()",
+            "receive() external payable",
+        ])
+        .unwrap();
+        let abi = interface.abi();
+        assert!(abi.constructor.is_some() && abi.fallback.is_none());
+        assert_eq!(abi.receive.unwrap().state_mutability, StateMutability::Payable);
+        assert_eq!(abi.function("transfer").unwrap().len(), 2);
+        assert!(abi.event("Transfer").unwrap()[0]
+            .inputs
+            .iter()
+            .all(|input| input.indexed || input.ty == "uint256"));
+        assert_eq!(abi.error("Unauthorized").unwrap().len(), 1);
+
+        let input = interface
+            .encode_input_with_selector(
+                &hex!("a9059cbb").into(),
+                &[DynSolValue::Address(Address::ZERO), DynSolValue::Uint(U256::from(1), 256)],
+            )
+            .unwrap();
+        assert_eq!(input.len(), 4 + 64);
+
+        assert!(matches!(Interface::parse(["function transfer(address"]), Err(Error::AbiError(_))));
+        assert!(matches!(
+            Interface::parse(["receive() payable", "receive() payable"]),
+            Err(Error::InvalidFragment(_))
+        ));
+        assert!(matches!(Interface::parse(["fallback(bytes)"]), Err(Error::InvalidFragment(_))));
+    }
+}
@@ -24,6 +24,15 @@ pub use event::subscription::EventSubscription;
 mod interface;
 pub use interface::*;
 
+mod packed;
+pub use packed::{encode_packed, keccak256_packed};
+
+mod signatures;
+pub use signatures::SignatureRegistry;
+
+mod storage;
+pub use storage::{StorageEncoding, StorageLayout, StorageLocation, StorageType, StorageVariable};
+
 mod instance;
 pub use instance::*;
 
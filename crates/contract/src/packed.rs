@@ -0,0 +1,146 @@
+use crate::{Error, Result};
+use alloy_dyn_abi::DynSolValue;
+use alloy_primitives::{keccak256, B256};
+
+/// ABI-encodes the values in packed mode, like Solidity's `abi.encodePacked`.
+///
+/// Values are encoded with their minimal size: `uintN` and `intN` take `N / 8` bytes, `bytesN`
+/// takes `N` bytes, `address` 20 bytes, `bool` 1 byte, a `function` 24 bytes, and `string` and
+/// `bytes` are not padded. The elements of arrays are padded to 32 bytes as in the standard
+/// encoding.
+///
+/// Returns [`Error::PackedEncoding`] for values Solidity cannot encode in packed mode: tuples and
+/// structs, and arrays of dynamic types or of arrays.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_contract::encode_packed;
+/// use alloy_dyn_abi::DynSolValue;
+/// use alloy_primitives::{hex, U256};
+///
+/// let packed = encode_packed(&[
+///     DynSolValue::Uint(U256::from(1), 16),
+///     DynSolValue::String("ab".into()),
+///     DynSolValue::Bool(true),
+/// ])
+/// .unwrap();
+/// assert_eq!(packed, hex!("0001616201"));
+/// ```
+pub fn encode_packed(values: &[DynSolValue]) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    for value in values {
+        encode_packed_to(value, &mut buf)?;
+    }
+    Ok(buf)
+}
+
+/// Returns the keccak-256 hash of the values encoded in packed mode, like Solidity's
+/// `keccak256(abi.encodePacked(...))`, e.g. to compute commitments off-chain.
+///
+/// See [`encode_packed`] for the encoding.
+pub fn keccak256_packed(values: &[DynSolValue]) -> Result<B256> {
+    encode_packed(values).map(keccak256)
+}
+
+fn encode_packed_to(value: &DynSolValue, buf: &mut Vec<u8>) -> Result<()> {
+    match value {
+        DynSolValue::Address(address) => buf.extend_from_slice(address.as_slice()),
+        DynSolValue::Function(function) => buf.extend_from_slice(function.as_slice()),
+        DynSolValue::Bool(b) => buf.push(*b as u8),
+        DynSolValue::String(s) => buf.extend_from_slice(s.as_bytes()),
+        DynSolValue::Bytes(bytes) => buf.extend_from_slice(bytes),
+        DynSolValue::FixedBytes(word, size) => buf.extend_from_slice(&word[..(*size).min(32)]),
+        DynSolValue::Int(_, size) | DynSolValue::Uint(_, size) => {
+            let word = value.as_word().expect("integers are words");
+            buf.extend_from_slice(&word[32 - (*size / 8).min(32)..]);
+        }
+        DynSolValue::Array(elements) | DynSolValue::FixedArray(elements) => {
+            for element in elements {
+                // elements are encoded as in the standard encoding, so only static words are
+                // allowed, and `bytesN` is padded on the right
+                let word = match element {
+                    DynSolValue::Function(_) => None,
+                    element => element.as_word(),
+                };
+                let word = word.ok_or_else(|| {
+                    Error::PackedEncoding(format!(
+                        "arrays of type {} cannot be encoded in packed mode",
+                        type_name(element)
+                    ))
+                })?;
+                buf.extend_from_slice(word.as_slice());
+            }
+        }
+        value => {
+            return Err(Error::PackedEncoding(format!(
+                "values of type {} cannot be encoded in packed mode",
+                type_name(value)
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn type_name(value: &DynSolValue) -> String {
+    value.sol_type_name().map_or_else(|| "unknown".to_string(), |name| name.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, hex, I256, U256};
+
+    #[test]
+    fn encodes_like_solidity() {
+        // abi.encodePacked(int8(-1), bytes1(0x42), address(0x...01), "hi", uint256[](1, 2))
+        let values = [
+            DynSolValue::Int(I256::MINUS_ONE, 8),
+            DynSolValue::FixedBytes(B256::right_padding_from(&[0x42]), 1),
+            DynSolValue::Address(address!("0000000000000000000000000000000000000001")),
+            DynSolValue::String("hi".into()),
+            DynSolValue::Array(vec![
+                DynSolValue::Uint(U256::from(1), 256),
+                DynSolValue::Uint(U256::from(2), 256),
+            ]),
+        ];
+        let packed = encode_packed(&values).unwrap();
+        assert_eq!(
+            packed,
+            [
+                &hex!("ff42")[..],
+                &hex!("0000000000000000000000000000000000000001"),
+                b"hi",
+                &U256::from(1).to_be_bytes::<32>(),
+                &U256::from(2).to_be_bytes::<32>(),
+            ]
+            .concat()
+        );
+
+        // array elements are padded like in the standard encoding
+        let packed = encode_packed(&[DynSolValue::Array(vec![
+            DynSolValue::FixedBytes(B256::right_padding_from(&[0x42]), 1),
+            DynSolValue::Int(I256::MINUS_ONE, 8),
+        ])])
+        .unwrap();
+        assert_eq!(packed, [B256::right_padding_from(&[0x42]), B256::repeat_byte(0xff)].concat());
+
+        // keccak256(abi.encodePacked("hello"))
+        assert_eq!(
+            keccak256_packed(&[DynSolValue::String("hello".into())]).unwrap(),
+            b256!("1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8")
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_types() {
+        let tuple = DynSolValue::Tuple(vec![DynSolValue::Bool(true)]);
+        assert!(matches!(encode_packed(&[tuple]), Err(Error::PackedEncoding(_))));
+
+        let strings = DynSolValue::Array(vec![DynSolValue::String("a".into())]);
+        assert!(matches!(encode_packed(&[strings]), Err(Error::PackedEncoding(_))));
+
+        let nested = DynSolValue::Array(vec![DynSolValue::Array(vec![])]);
+        assert!(matches!(encode_packed(&[nested]), Err(Error::PackedEncoding(_))));
+    }
+}
@@ -0,0 +1,159 @@
+use alloy_primitives::{Selector, B256};
+use alloy_sol_types::{SolCall, SolError, SolEvent};
+
+/// A lookup table from selectors and event topics to the signatures of the functions, errors and
+/// events of one or more contracts.
+///
+/// Entries are registered from the constants emitted by [`sol!`](alloy_sol_types::sol), so
+/// building the table does not hash any signature, and lookups are binary searches. This makes it
+/// suitable for log processors and transaction classifiers matching many logs and calls.
+///
+/// When two entries of the same kind share a selector, the first one registered is kept.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_contract::SignatureRegistry;
+/// use alloy_sol_types::{sol, SolCall, SolEvent};
+///
+/// sol! {
+///     interface IERC20 {
+///         event Transfer(address indexed from, address indexed to, uint256 value);
+///         function transfer(address to, uint256 amount) external returns (bool);
+///     }
+/// }
+///
+/// let registry = SignatureRegistry::new()
+///     .with_event::<IERC20::Transfer>()
+///     .with_function::<IERC20::transferCall>();
+///
+/// assert_eq!(
+///     registry.event(&IERC20::Transfer::SIGNATURE_HASH),
+///     Some("Transfer(address,address,uint256)")
+/// );
+/// assert_eq!(
+///     registry.function(IERC20::transferCall::SELECTOR.into()),
+///     Some("transfer(address,uint256)")
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SignatureRegistry {
+    functions: Vec<(Selector, &'static str)>,
+    errors: Vec<(Selector, &'static str)>,
+    events: Vec<(B256, &'static str)>,
+}
+
+impl SignatureRegistry {
+    /// Creates an empty registry.
+    pub const fn new() -> Self {
+        Self { functions: Vec::new(), errors: Vec::new(), events: Vec::new() }
+    }
+
+    /// Registers the function.
+    pub fn with_function<C: SolCall>(mut self) -> Self {
+        insert(&mut self.functions, C::SELECTOR.into(), C::SIGNATURE);
+        self
+    }
+
+    /// Registers the custom error.
+    pub fn with_error<E: SolError>(mut self) -> Self {
+        insert(&mut self.errors, E::SELECTOR.into(), E::SIGNATURE);
+        self
+    }
+
+    /// Registers the event.
+    ///
+    /// Anonymous events are ignored, since their logs do not carry the signature hash.
+    pub fn with_event<E: SolEvent>(mut self) -> Self {
+        if !E::ANONYMOUS {
+            insert(&mut self.events, E::SIGNATURE_HASH, E::SIGNATURE);
+        }
+        self
+    }
+
+    /// Returns the signature of the function with the selector.
+    pub fn function(&self, selector: Selector) -> Option<&'static str> {
+        lookup(&self.functions, &selector)
+    }
+
+    /// Returns the signature of the function called with the calldata, from its first 4 bytes.
+    pub fn function_of(&self, input: &[u8]) -> Option<&'static str> {
+        self.function(Selector::try_from(input.get(..4)?).ok()?)
+    }
+
+    /// Returns the signature of the custom error with the selector.
+    pub fn error(&self, selector: Selector) -> Option<&'static str> {
+        lookup(&self.errors, &selector)
+    }
+
+    /// Returns the signature of the event with the signature hash, the first topic of its logs.
+    pub fn event(&self, topic0: &B256) -> Option<&'static str> {
+        lookup(&self.events, topic0)
+    }
+
+    /// Returns the signature of the event emitting the log with the topics.
+    pub fn event_of(&self, topics: &[B256]) -> Option<&'static str> {
+        self.event(topics.first()?)
+    }
+
+    /// Returns the number of registered functions, errors and events.
+    pub fn len(&self) -> usize {
+        self.functions.len() + self.errors.len() + self.events.len()
+    }
+
+    /// Returns `true` if nothing is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn insert<K: Ord>(entries: &mut Vec<(K, &'static str)>, key: K, signature: &'static str) {
+    if let Err(i) = entries.binary_search_by(|(k, _)| k.cmp(&key)) {
+        entries.insert(i, (key, signature));
+    }
+}
+
+fn lookup<K: Ord>(entries: &[(K, &'static str)], key: &K) -> Option<&'static str> {
+    entries.binary_search_by(|(k, _)| k.cmp(key)).ok().map(|i| entries[i].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+    use alloy_sol_types::sol;
+
+    sol! {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+        event Anonymous(uint256 value) anonymous;
+        error InsufficientBalance(uint256 available, uint256 required);
+        function approve(address spender, uint256 amount) returns (bool);
+        function transfer(address to, uint256 amount) returns (bool);
+    }
+
+    #[test]
+    fn looks_up_signatures() {
+        let registry = SignatureRegistry::new()
+            .with_function::<transferCall>()
+            .with_function::<approveCall>()
+            .with_function::<transferCall>()
+            .with_error::<InsufficientBalance>()
+            .with_event::<Transfer>()
+            .with_event::<Anonymous>();
+        assert_eq!(registry.len(), 4);
+
+        assert_eq!(registry.function(hex!("a9059cbb").into()), Some("transfer(address,uint256)"));
+        assert_eq!(registry.function_of(&approveCall::SELECTOR), Some("approve(address,uint256)"));
+        assert_eq!(registry.function_of(&[0xa9]), None);
+        assert_eq!(
+            registry.error(InsufficientBalance::SELECTOR.into()),
+            Some("InsufficientBalance(uint256,uint256)")
+        );
+        assert_eq!(registry.function(InsufficientBalance::SELECTOR.into()), None);
+        assert_eq!(
+            registry.event_of(&[Transfer::SIGNATURE_HASH, B256::ZERO]),
+            Some("Transfer(address,address,uint256)")
+        );
+        assert_eq!(registry.event_of(&[]), None);
+    }
+}
@@ -0,0 +1,600 @@
+use crate::{Error, Result};
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_network::Network;
+use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::BlockId;
+use alloy_transport::Transport;
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The storage layout of a contract, as emitted by solc with the `storageLayout` output
+/// selection.
+///
+/// The layout locates state variables in storage, including mapping values, array elements and
+/// struct members, and decodes their raw storage words. This allows inspecting the state of a
+/// contract without view functions.
+///
+/// Variables are addressed by paths such as `owner`, `balances[0x…]`, `values[3]`,
+/// `config.fee` or `allowances[0x…][0x…]`. Mapping keys are parsed according to the key type.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy_contract::StorageLayout;
+/// use alloy_primitives::{Address, U256};
+/// use alloy_provider::Provider;
+/// use alloy_sol_types::sol_data;
+///
+/// # async fn test(provider: impl Provider, contract: Address, layout_json: &str) -> Result<(), Box<dyn std::error::Error>> {
+/// let layout: StorageLayout = serde_json::from_str(layout_json)?;
+/// let owner: Address = layout.read_as::<sol_data::Address, _, _, _>(&provider, contract, "owner").await?;
+/// let balance: U256 = layout
+///     .read_as::<sol_data::Uint<256>, _, _, _>(&provider, contract, &format!("balances[{owner}]"))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayout {
+    /// The state variables, in declaration order.
+    pub storage: Vec<StorageVariable>,
+    /// The types of the variables, by identifier.
+    #[serde(default)]
+    pub types: BTreeMap<String, StorageType>,
+}
+
+/// A state variable or struct member of a [`StorageLayout`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageVariable {
+    /// The name of the variable.
+    pub label: String,
+    /// The slot of the variable, relative to the enclosing struct for members.
+    pub slot: U256,
+    /// The offset in bytes of the variable within the slot, from the lowest-order byte.
+    pub offset: usize,
+    /// The identifier of the type of the variable in [`StorageLayout::types`].
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The identifier of the declaration in the AST.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ast_id: Option<u64>,
+    /// The name of the contract declaring the variable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+}
+
+/// A type of a [`StorageLayout`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageType {
+    /// How values of the type are laid out in storage.
+    pub encoding: StorageEncoding,
+    /// The name of the type, e.g. `uint256` or `mapping(address => uint256)`.
+    pub label: String,
+    /// The size in bytes of the type in storage.
+    pub number_of_bytes: U256,
+    /// The key type of a mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// The value type of a mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The element type of an array.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// The members of a struct.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<StorageVariable>>,
+}
+
+impl StorageType {
+    fn size(&self) -> usize {
+        self.number_of_bytes.saturating_to()
+    }
+}
+
+/// How values of a [`StorageType`] are laid out in storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageEncoding {
+    /// The value is stored in place, packed with neighbouring values if it fits.
+    Inplace,
+    /// The value of a key is stored at the hash of the key and the slot of the mapping.
+    Mapping,
+    /// The length is stored at the slot, and the elements from the hash of the slot.
+    DynamicArray,
+    /// `bytes` and `string`, stored in place if shorter than 32 bytes.
+    Bytes,
+}
+
+/// The location of a value in storage, returned by [`StorageLayout::locate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageLocation {
+    /// The first slot of the value.
+    pub slot: U256,
+    /// The offset in bytes of the value within the slot, from the lowest-order byte.
+    pub offset: usize,
+    /// The identifier of the type of the value in [`StorageLayout::types`].
+    pub ty: String,
+}
+
+impl StorageLayout {
+    /// Returns the type with the identifier.
+    pub fn ty(&self, id: &str) -> Result<&StorageType> {
+        self.types.get(id).ok_or_else(|| Error::StorageLayout(format!("unknown type {id}")))
+    }
+
+    /// Locates the value at the path in storage.
+    pub fn locate(&self, path: &str) -> Result<StorageLocation> {
+        let (name, accessors) = parse_path(path)?;
+        let variable = self
+            .storage
+            .iter()
+            .find(|variable| variable.label == name)
+            .ok_or_else(|| Error::StorageLayout(format!("unknown variable {name}")))?;
+        let mut location = StorageLocation {
+            slot: variable.slot,
+            offset: variable.offset,
+            ty: variable.ty.clone(),
+        };
+
+        for accessor in accessors {
+            let ty = self.ty(&location.ty)?;
+            location = match (accessor, ty.encoding, &ty.members) {
+                (Accessor::Member(name), StorageEncoding::Inplace, Some(members)) => {
+                    let member = members.iter().find(|m| m.label == name).ok_or_else(|| {
+                        Error::StorageLayout(format!("{} has no member {name}", ty.label))
+                    })?;
+                    StorageLocation {
+                        slot: location.slot.wrapping_add(member.slot),
+                        offset: member.offset,
+                        ty: member.ty.clone(),
+                    }
+                }
+                (Accessor::Index(key), StorageEncoding::Mapping, _) => {
+                    let (key_ty, value_ty) =
+                        ty.key.as_ref().zip(ty.value.as_ref()).ok_or_else(|| {
+                            Error::StorageLayout(format!("{} has no key or value type", ty.label))
+                        })?;
+                    let mut preimage = self.encode_key(key_ty, key)?;
+                    preimage.extend_from_slice(&location.slot.to_be_bytes::<32>());
+                    StorageLocation {
+                        slot: keccak256(preimage).into(),
+                        offset: 0,
+                        ty: value_ty.clone(),
+                    }
+                }
+                (Accessor::Index(index), StorageEncoding::DynamicArray, _)
+                | (Accessor::Index(index), StorageEncoding::Inplace, None) => {
+                    let base = ty.base.as_ref().ok_or_else(|| {
+                        Error::StorageLayout(format!("{} cannot be indexed", ty.label))
+                    })?;
+                    let index: usize = index.parse().map_err(|_| {
+                        Error::StorageLayout(format!("invalid array index {index}"))
+                    })?;
+                    let start = if ty.encoding == StorageEncoding::DynamicArray {
+                        keccak256(location.slot.to_be_bytes::<32>()).into()
+                    } else {
+                        match fixed_array_len(&ty.label) {
+                            Some(len) if index < len => location.slot,
+                            _ => {
+                                return Err(Error::StorageLayout(format!(
+                                    "index {index} out of bounds"
+                                )))
+                            }
+                        }
+                    };
+                    element_location(start, index, base, self.ty(base)?.size())
+                }
+                (accessor, _, _) => {
+                    return Err(Error::StorageLayout(format!(
+                        "{} cannot be accessed with {accessor}",
+                        ty.label
+                    )))
+                }
+            };
+        }
+        Ok(location)
+    }
+
+    /// Reads and decodes the value at the path from the storage of the contract, at the latest
+    /// block.
+    ///
+    /// Value types, `bytes` and `string`, structs and arrays can be read, whereas mappings
+    /// cannot be read as a whole.
+    pub async fn read<P, T, N>(
+        &self,
+        provider: &P,
+        address: Address,
+        path: &str,
+    ) -> Result<DynSolValue>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        self.read_at(provider, address, path, BlockId::latest()).await
+    }
+
+    /// Reads and decodes the value at the path from the storage of the contract, at the block.
+    pub async fn read_at<P, T, N>(
+        &self,
+        provider: &P,
+        address: Address,
+        path: &str,
+        block: BlockId,
+    ) -> Result<DynSolValue>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let location = self.locate(path)?;
+        StorageReader { layout: self, provider, address, block, _pd: Default::default() }
+            .read(location)
+            .await
+    }
+
+    /// Reads the value at the path like [`read`](Self::read), converted to the Solidity type.
+    pub async fn read_as<S, P, T, N>(
+        &self,
+        provider: &P,
+        address: Address,
+        path: &str,
+    ) -> Result<S::RustType>
+    where
+        S: alloy_sol_types::SolType,
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let value = self.read(provider, address, path).await?;
+        S::abi_decode(&value.abi_encode(), true).map_err(Into::into)
+    }
+
+    fn encode_key(&self, key_ty: &str, key: &str) -> Result<Vec<u8>> {
+        let ty = self.ty(key_ty)?;
+        if ty.encoding == StorageEncoding::Bytes {
+            let key = key.strip_prefix('"').and_then(|k| k.strip_suffix('"')).unwrap_or(key);
+            return if ty.label == "string" {
+                Ok(key.as_bytes().to_vec())
+            } else {
+                let value = DynSolType::Bytes.coerce_str(key)?;
+                Ok(value.as_bytes().unwrap_or_default().to_vec())
+            };
+        }
+        let value = value_type(ty)?.coerce_str(key)?;
+        value
+            .as_word()
+            .map(|word| word.to_vec())
+            .ok_or_else(|| Error::StorageLayout(format!("invalid mapping key type {}", ty.label)))
+    }
+}
+
+struct StorageReader<'a, P, T, N> {
+    layout: &'a StorageLayout,
+    provider: &'a P,
+    address: Address,
+    block: BlockId,
+    _pd: std::marker::PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> StorageReader<'_, P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn word(&self, slot: U256) -> Result<B256> {
+        let word = self.provider.get_storage_at(self.address, slot).block_id(self.block).await?;
+        Ok(word.into())
+    }
+
+    fn read(&self, location: StorageLocation) -> BoxFuture<'_, Result<DynSolValue>> {
+        Box::pin(async move {
+            let ty = self.layout.ty(&location.ty)?;
+            match (ty.encoding, &ty.members, &ty.base) {
+                (StorageEncoding::Inplace, Some(members), _) => {
+                    let mut values = Vec::with_capacity(members.len());
+                    for member in members {
+                        values.push(
+                            self.read(StorageLocation {
+                                slot: location.slot.wrapping_add(member.slot),
+                                offset: member.offset,
+                                ty: member.ty.clone(),
+                            })
+                            .await?,
+                        );
+                    }
+                    Ok(DynSolValue::Tuple(values))
+                }
+                (StorageEncoding::Inplace, None, Some(base)) => {
+                    let len = fixed_array_len(&ty.label).ok_or_else(|| {
+                        Error::StorageLayout(format!("invalid array type {}", ty.label))
+                    })?;
+                    let values = self.read_elements(location.slot, len, base).await?;
+                    Ok(DynSolValue::FixedArray(values))
+                }
+                (StorageEncoding::Inplace, None, None) => {
+                    let word = self.word(location.slot).await?;
+                    decode_value(ty, &word, location.offset)
+                }
+                (StorageEncoding::DynamicArray, _, Some(base)) => {
+                    let len: usize = U256::from_be_bytes(self.word(location.slot).await?.0)
+                        .try_into()
+                        .map_err(|_| Error::StorageLayout("array length overflows".to_string()))?;
+                    let start = keccak256(location.slot.to_be_bytes::<32>()).into();
+                    Ok(DynSolValue::Array(self.read_elements(start, len, base).await?))
+                }
+                (StorageEncoding::Bytes, _, _) => {
+                    let bytes = self.read_bytes(location.slot).await?;
+                    if ty.label == "string" {
+                        let s = String::from_utf8(bytes).map_err(|_| {
+                            Error::StorageLayout("invalid UTF-8 string".to_string())
+                        })?;
+                        Ok(DynSolValue::String(s))
+                    } else {
+                        Ok(DynSolValue::Bytes(bytes))
+                    }
+                }
+                (StorageEncoding::Mapping, _, _) => Err(Error::StorageLayout(format!(
+                    "{} cannot be read as a whole, index it with a key",
+                    ty.label
+                ))),
+                _ => Err(Error::StorageLayout(format!("{} cannot be read", ty.label))),
+            }
+        })
+    }
+
+    async fn read_elements(&self, start: U256, len: usize, base: &str) -> Result<Vec<DynSolValue>> {
+        let size = self.layout.ty(base)?.size();
+        let mut values = Vec::with_capacity(len);
+        for index in 0..len {
+            values.push(self.read(element_location(start, index, base, size)).await?);
+        }
+        Ok(values)
+    }
+
+    async fn read_bytes(&self, slot: U256) -> Result<Vec<u8>> {
+        let word = self.word(slot).await?;
+        // short values are stored in place with twice their length in the lowest-order byte,
+        // long values store twice their length plus one, with the data from the hash of the slot
+        if word[31] & 1 == 0 {
+            let len = (word[31] / 2) as usize;
+            return Ok(word[..len.min(31)].to_vec());
+        }
+        let len: usize = (U256::from_be_bytes(word.0) / U256::from(2))
+            .try_into()
+            .map_err(|_| Error::StorageLayout("bytes length overflows".to_string()))?;
+        let start: U256 = keccak256(slot.to_be_bytes::<32>()).into();
+        let mut bytes = Vec::with_capacity(len);
+        for i in 0..len.div_ceil(32) {
+            bytes.extend_from_slice(self.word(start.wrapping_add(U256::from(i))).await?.as_slice());
+        }
+        bytes.truncate(len);
+        Ok(bytes)
+    }
+}
+
+enum Accessor<'a> {
+    Member(&'a str),
+    Index(&'a str),
+}
+
+impl std::fmt::Display for Accessor<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Member(name) => write!(f, ".{name}"),
+            Self::Index(key) => write!(f, "[{key}]"),
+        }
+    }
+}
+
+/// Splits a path like `a.b[c]` into the variable name and its accessors.
+fn parse_path(path: &str) -> Result<(&str, Vec<Accessor<'_>>)> {
+    let invalid = || Error::StorageLayout(format!("invalid path {path}"));
+    let end = path.find(['.', '[']).unwrap_or(path.len());
+    let (name, mut rest) = path.split_at(end);
+    if name.is_empty() {
+        return Err(invalid());
+    }
+    let mut accessors = Vec::new();
+    while !rest.is_empty() {
+        if let Some(member) = rest.strip_prefix('.') {
+            let end = member.find(['.', '[']).unwrap_or(member.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            accessors.push(Accessor::Member(&member[..end]));
+            rest = &member[end..];
+        } else if let Some(index) = rest.strip_prefix('[') {
+            let end = index.find(']').ok_or_else(invalid)?;
+            accessors.push(Accessor::Index(index[..end].trim()));
+            rest = &index[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok((name, accessors))
+}
+
+/// Returns the location of the element of an array whose elements start at the slot, packing
+/// elements smaller than a word.
+fn element_location(start: U256, index: usize, base: &str, size: usize) -> StorageLocation {
+    let (slot, offset) = if size == 0 || size > 32 {
+        (index * size.div_ceil(32).max(1), 0)
+    } else {
+        let per_slot = 32 / size;
+        (index / per_slot, index % per_slot * size)
+    };
+    StorageLocation { slot: start.wrapping_add(U256::from(slot)), offset, ty: base.to_string() }
+}
+
+/// Returns the length of a fixed-size array from its label, like `uint256[3]`.
+fn fixed_array_len(label: &str) -> Option<usize> {
+    label.strip_suffix(']')?.rsplit_once('[')?.1.parse().ok()
+}
+
+/// Returns the Solidity type of a value type of the layout.
+fn value_type(ty: &StorageType) -> Result<DynSolType> {
+    let label = ty.label.as_str();
+    if label.starts_with("contract ") || label.starts_with("address") {
+        return Ok(DynSolType::Address);
+    }
+    if label.starts_with("enum ") {
+        return Ok(DynSolType::Uint(ty.size() * 8));
+    }
+    DynSolType::parse(label)
+        .ok()
+        .filter(|ty| {
+            matches!(
+                ty,
+                DynSolType::Address
+                    | DynSolType::Bool
+                    | DynSolType::Int(_)
+                    | DynSolType::Uint(_)
+                    | DynSolType::FixedBytes(_)
+            )
+        })
+        .ok_or_else(|| Error::StorageLayout(format!("unsupported value type {label}")))
+}
+
+/// Decodes the value of the type stored at the offset of the word.
+fn decode_value(ty: &StorageType, word: &B256, offset: usize) -> Result<DynSolValue> {
+    let size = ty.size();
+    let solidity_ty = value_type(ty)?;
+    let end = 32usize
+        .checked_sub(offset)
+        .filter(|end| *end >= size && size > 0)
+        .ok_or_else(|| Error::StorageLayout(format!("invalid offset {offset} of {}", ty.label)))?;
+    let bytes = &word[end - size..end];
+
+    // values are stored as their lowest-order bytes, convert them back to a padded ABI word
+    let mut abi_word = B256::ZERO;
+    match solidity_ty {
+        DynSolType::FixedBytes(_) => abi_word[..size].copy_from_slice(bytes),
+        DynSolType::Int(_) if bytes[0] & 0x80 != 0 => {
+            abi_word = B256::repeat_byte(0xff);
+            abi_word[32 - size..].copy_from_slice(bytes);
+        }
+        _ => abi_word[32 - size..].copy_from_slice(bytes),
+    }
+    solidity_ty.abi_decode(abi_word.as_slice()).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, I256};
+
+    // solc --storage-layout for:
+    // contract C {
+    //     struct S { uint128 a; uint128 b; address c; }
+    //     address owner;
+    //     uint8 small;
+    //     int16 negative;
+    //     mapping(address => uint256) balances;
+    //     uint64[] values;
+    //     S config;
+    //     string name;
+    //     mapping(string => S) named;
+    //     uint256[3] fixedValues;
+    // }
+    const LAYOUT: &str = r#"{
+        "storage": [
+            {"astId": 1, "contract": "C.sol:C", "label": "owner", "offset": 0, "slot": "0", "type": "t_address"},
+            {"astId": 2, "contract": "C.sol:C", "label": "small", "offset": 20, "slot": "0", "type": "t_uint8"},
+            {"astId": 3, "contract": "C.sol:C", "label": "negative", "offset": 21, "slot": "0", "type": "t_int16"},
+            {"astId": 4, "contract": "C.sol:C", "label": "balances", "offset": 0, "slot": "1", "type": "t_mapping(t_address,t_uint256)"},
+            {"astId": 5, "contract": "C.sol:C", "label": "values", "offset": 0, "slot": "2", "type": "t_array(t_uint64)dyn_storage"},
+            {"astId": 6, "contract": "C.sol:C", "label": "config", "offset": 0, "slot": "3", "type": "t_struct(S)10_storage"},
+            {"astId": 7, "contract": "C.sol:C", "label": "name", "offset": 0, "slot": "5", "type": "t_string_storage"},
+            {"astId": 8, "contract": "C.sol:C", "label": "named", "offset": 0, "slot": "6", "type": "t_mapping(t_string_memory_ptr,t_struct(S)10_storage)"},
+            {"astId": 9, "contract": "C.sol:C", "label": "fixedValues", "offset": 0, "slot": "7", "type": "t_array(t_uint256)3_storage"}
+        ],
+        "types": {
+            "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+            "t_uint8": {"encoding": "inplace", "label": "uint8", "numberOfBytes": "1"},
+            "t_int16": {"encoding": "inplace", "label": "int16", "numberOfBytes": "2"},
+            "t_uint64": {"encoding": "inplace", "label": "uint64", "numberOfBytes": "8"},
+            "t_uint128": {"encoding": "inplace", "label": "uint128", "numberOfBytes": "16"},
+            "t_uint256": {"encoding": "inplace", "label": "uint256", "numberOfBytes": "32"},
+            "t_string_storage": {"encoding": "bytes", "label": "string", "numberOfBytes": "32"},
+            "t_string_memory_ptr": {"encoding": "bytes", "label": "string", "numberOfBytes": "32"},
+            "t_mapping(t_address,t_uint256)": {"encoding": "mapping", "key": "t_address", "label": "mapping(address => uint256)", "numberOfBytes": "32", "value": "t_uint256"},
+            "t_mapping(t_string_memory_ptr,t_struct(S)10_storage)": {"encoding": "mapping", "key": "t_string_memory_ptr", "label": "mapping(string => struct C.S)", "numberOfBytes": "32", "value": "t_struct(S)10_storage"},
+            "t_array(t_uint64)dyn_storage": {"base": "t_uint64", "encoding": "dynamic_array", "label": "uint64[]", "numberOfBytes": "32"},
+            "t_array(t_uint256)3_storage": {"base": "t_uint256", "encoding": "inplace", "label": "uint256[3]", "numberOfBytes": "96"},
+            "t_struct(S)10_storage": {"encoding": "inplace", "label": "struct C.S", "numberOfBytes": "64", "members": [
+                {"astId": 11, "contract": "C.sol:C", "label": "a", "offset": 0, "slot": "0", "type": "t_uint128"},
+                {"astId": 12, "contract": "C.sol:C", "label": "b", "offset": 16, "slot": "0", "type": "t_uint128"},
+                {"astId": 13, "contract": "C.sol:C", "label": "c", "offset": 0, "slot": "1", "type": "t_address"}
+            ]}
+        }
+    }"#;
+
+    #[test]
+    fn locates_values() {
+        let layout: StorageLayout = serde_json::from_str(LAYOUT).unwrap();
+        let location = |path: &str| layout.locate(path).unwrap();
+
+        assert_eq!(
+            location("small"),
+            StorageLocation { slot: U256::ZERO, offset: 20, ty: "t_uint8".into() }
+        );
+        assert_eq!(location("config.b").offset, 16);
+        assert_eq!(location("config.c").slot, U256::from(4));
+
+        // keccak256(abi.encode(key, 1))
+        let key = address!("00000000000000000000000000000000000000aa");
+        assert_eq!(
+            B256::from(location(&format!("balances[{key}]")).slot),
+            keccak256([key.into_word().as_slice(), &U256::from(1).to_be_bytes::<32>()].concat())
+        );
+        // keccak256(abi.encodePacked("fee", 6)) + 1
+        let named = location("named[\"fee\"].c");
+        assert_eq!(
+            named.slot,
+            U256::from_be_bytes(
+                keccak256([&b"fee"[..], &U256::from(6).to_be_bytes::<32>()].concat()).0
+            ) + U256::from(1)
+        );
+
+        // four uint64 per slot from keccak256(2)
+        let values = U256::from_be_bytes(
+            b256!("405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ace").0,
+        );
+        assert_eq!(
+            location("values[5]"),
+            StorageLocation { slot: values + U256::from(1), offset: 8, ty: "t_uint64".into() }
+        );
+        assert_eq!(location("fixedValues[2]").slot, U256::from(9));
+
+        assert!(layout.locate("fixedValues[3]").is_err());
+        assert!(layout.locate("owner.field").is_err());
+        assert!(layout.locate("unknown").is_err());
+        assert!(layout.locate("balances[0x01").is_err());
+    }
+
+    #[test]
+    fn decodes_packed_values() {
+        let layout: StorageLayout = serde_json::from_str(LAYOUT).unwrap();
+        let mut word = B256::ZERO;
+        word[12..].copy_from_slice(address!("00000000000000000000000000000000000000aa").as_slice());
+        word[11] = 7;
+        word[9..11].copy_from_slice(&(-2i16).to_be_bytes());
+
+        let decode =
+            |id: &str, offset| decode_value(layout.ty(id).unwrap(), &word, offset).unwrap();
+        assert_eq!(
+            decode("t_address", 0),
+            DynSolValue::Address(address!("00000000000000000000000000000000000000aa"))
+        );
+        assert_eq!(decode("t_uint8", 20), DynSolValue::Uint(U256::from(7), 8));
+        assert_eq!(decode("t_int16", 21), DynSolValue::Int(I256::try_from(-2).unwrap(), 16));
+        assert!(decode_value(layout.ty("t_uint256").unwrap(), &word, 1).is_err());
+    }
+}
@@ -21,12 +21,13 @@ use serde::{
 /// If true, an RPC call should additionally raise if the block is not in the canonical chain.
 /// <https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1898.md#specification>
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
-#[cfg_attr(feature = "serde", serde(rename = "camelCase"))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct RpcBlockHash {
     /// A block hash
     pub block_hash: BlockHash,
     /// Whether the block must be a canonical block
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub require_canonical: Option<bool>,
 }
 
@@ -36,6 +37,16 @@ impl RpcBlockHash {
     pub const fn from_hash(block_hash: B256, require_canonical: Option<bool>) -> Self {
         Self { block_hash, require_canonical }
     }
+
+    /// Returns an [RpcBlockHash] that requires the block to be canonical.
+    pub const fn canonical(block_hash: B256) -> Self {
+        Self::from_hash(block_hash, Some(true))
+    }
+
+    /// Returns `true` if the block must be a canonical block.
+    pub const fn requires_canonical(&self) -> bool {
+        matches!(self.require_canonical, Some(true))
+    }
 }
 
 impl From<B256> for RpcBlockHash {
@@ -367,6 +378,26 @@ impl BlockId {
     pub const fn hash_canonical(block_hash: BlockHash) -> Self {
         Self::Hash(RpcBlockHash { block_hash, require_canonical: Some(true) })
     }
+
+    /// Sets whether the block must be canonical, if this is [`BlockId::Hash`].
+    ///
+    /// Blocks identified by number or tag are always canonical, so this has no effect on them.
+    pub const fn with_require_canonical(self, require_canonical: bool) -> Self {
+        match self {
+            Self::Hash(RpcBlockHash { block_hash, .. }) => {
+                Self::Hash(RpcBlockHash { block_hash, require_canonical: Some(require_canonical) })
+            }
+            Self::Number(_) => self,
+        }
+    }
+
+    /// Returns `true` if this is [`BlockId::Hash`] and the block must be canonical.
+    pub const fn requires_canonical(&self) -> bool {
+        match self {
+            Self::Hash(hash) => hash.requires_canonical(),
+            Self::Number(_) => false,
+        }
+    }
 }
 
 impl Default for BlockId {
@@ -914,6 +945,41 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_require_canonical() {
+        let id = BlockId::hash(HASH).with_require_canonical(true);
+        assert!(id.requires_canonical());
+        let json = serde_json::to_value(id).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "blockHash": "0x1a15e3c30cf094a99826869517b16d185d45831d3a494f01030b0001a9d3ebb9",
+                "requireCanonical": true
+            })
+        );
+        assert_eq!(serde_json::from_value::<BlockId>(json).unwrap(), id);
+
+        let id = BlockId::hash(HASH).with_require_canonical(false);
+        assert!(!id.requires_canonical());
+        assert_eq!(
+            serde_json::from_value::<BlockId>(serde_json::to_value(id).unwrap()).unwrap(),
+            id
+        );
+
+        let hash = RpcBlockHash::canonical(HASH);
+        let json = serde_json::to_value(hash).unwrap();
+        assert_eq!(json["requireCanonical"], true);
+        assert_eq!(serde_json::from_value::<RpcBlockHash>(json).unwrap(), hash);
+
+        let hash = RpcBlockHash::from(HASH);
+        let json = serde_json::to_value(hash).unwrap();
+        assert!(json.get("requireCanonical").is_none());
+        assert_eq!(serde_json::from_value::<RpcBlockHash>(json).unwrap(), hash);
+
+        assert!(!BlockId::latest().with_require_canonical(true).requires_canonical());
+    }
+
     #[test]
     fn display_rpc_block_hash() {
         let hash = RpcBlockHash::from_hash(HASH, Some(true));
@@ -0,0 +1,378 @@
+//! Decoding of data packed into blobs, the inverse of the [`SidecarBuilder`].
+//!
+//! A [`BlobDecoder`] strips the field element padding of a packing format and returns the payloads
+//! that were packed into the blobs. A [`BlobFraming`] then splits the payloads into the frames of
+//! a higher level protocol, such as the channel frames of the OP Stack with [`OpFraming`].
+//!
+//! [`SidecarBuilder`]: crate::eip4844::builder::SidecarBuilder
+
+use crate::eip4844::{
+    builder::{SidecarCoder, SimpleCoder},
+    Blob, FIELD_ELEMENTS_PER_BLOB,
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Bytes, FixedBytes};
+use core::fmt;
+
+/// An error returned when blobs do not contain data in the expected format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlobDecodingError {
+    /// The data is not packed in the format of the decoder.
+    InvalidEncoding,
+    /// The field element at the index has bits set that the format does not use.
+    InvalidFieldElement(usize),
+    /// The encoding version of a blob is not supported.
+    UnsupportedVersion(u8),
+    /// The length prefix of a blob exceeds the capacity of the blob.
+    InvalidLength(usize),
+    /// The padding after the data of a blob is not zero.
+    NonZeroPadding,
+    /// A frame is truncated or malformed.
+    InvalidFrame,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlobDecodingError {}
+
+impl fmt::Display for BlobDecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidEncoding => f.write_str("blobs are not packed in the expected format"),
+            Self::InvalidFieldElement(index) => {
+                write!(f, "field element {index} has invalid high-order bits")
+            }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported blob encoding version {version}")
+            }
+            Self::InvalidLength(len) => write!(f, "blob data length {len} exceeds blob capacity"),
+            Self::NonZeroPadding => f.write_str("blob padding is not zero"),
+            Self::InvalidFrame => f.write_str("invalid frame"),
+        }
+    }
+}
+
+/// A packing format of data in blobs, which can be decoded back into the original payloads.
+pub trait BlobDecoder {
+    /// Decodes the payloads packed into the blobs, in order.
+    fn decode(&self, blobs: &[Blob]) -> Result<Vec<Vec<u8>>, BlobDecodingError>;
+}
+
+impl BlobDecoder for SimpleCoder {
+    fn decode(&self, blobs: &[Blob]) -> Result<Vec<Vec<u8>>, BlobDecodingError> {
+        let mut coder = *self;
+        coder.decode_all(blobs).ok_or(BlobDecodingError::InvalidEncoding)
+    }
+}
+
+/// The version 0 blob encoding of the OP Stack, which packs a single payload into each blob and
+/// uses all but the two high-order bits of every field element.
+///
+/// The first field element holds the encoding version and a 3-byte big-endian length of the
+/// payload. The payload is then packed in rounds of 4 field elements, holding 31 bytes each in
+/// their low-order bytes and 3 more bytes spread over the 6 low-order bits of their high-order
+/// bytes.
+///
+/// See the [OP Stack specs](https://specs.optimism.io/protocol/derivation.html#blob-encoding).
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct RollupBlobDecoder;
+
+impl RollupBlobDecoder {
+    /// The supported encoding version.
+    pub const VERSION: u8 = 0;
+
+    /// The maximum number of bytes of a payload in a single blob.
+    pub const MAX_DATA_SIZE: usize = (4 * 31 + 3) * (FIELD_ELEMENTS_PER_BLOB as usize / 4) - 4;
+
+    /// Decodes the payload of a single blob.
+    pub fn decode_blob(blob: &Blob) -> Result<Vec<u8>, BlobDecodingError> {
+        if blob[1] != Self::VERSION {
+            return Err(BlobDecodingError::UnsupportedVersion(blob[1]));
+        }
+        let len = u32::from_be_bytes([0, blob[2], blob[3], blob[4]]) as usize;
+        if len > Self::MAX_DATA_SIZE {
+            return Err(BlobDecodingError::InvalidLength(len));
+        }
+
+        let mut out = alloc::vec![0u8; Self::MAX_DATA_SIZE];
+        let mut encoded = [0u8; 4];
+        // the first field element holds the version and length, followed by 27 bytes of data
+        check_field_element(blob, 0)?;
+        encoded[0] = blob[0];
+        out[..27].copy_from_slice(&blob[5..32]);
+        let mut opos = 28;
+        let mut fe = 1;
+        for byte in &mut encoded[1..] {
+            *byte = decode_field_element(blob, fe, &mut out, &mut opos)?;
+            fe += 1;
+        }
+        opos = reassemble_bytes(opos, encoded, &mut out);
+
+        while opos < len && fe < FIELD_ELEMENTS_PER_BLOB as usize {
+            for byte in &mut encoded {
+                *byte = decode_field_element(blob, fe, &mut out, &mut opos)?;
+                fe += 1;
+            }
+            opos = reassemble_bytes(opos, encoded, &mut out);
+        }
+
+        if out[len..].iter().any(|b| *b != 0) || blob[fe * 32..].iter().any(|b| *b != 0) {
+            return Err(BlobDecodingError::NonZeroPadding);
+        }
+        out.truncate(len);
+        Ok(out)
+    }
+}
+
+/// Checks that the two high-order bits of the field element are not set.
+fn check_field_element(blob: &Blob, fe: usize) -> Result<(), BlobDecodingError> {
+    if blob[fe * 32] & 0b1100_0000 != 0 {
+        return Err(BlobDecodingError::InvalidFieldElement(fe));
+    }
+    Ok(())
+}
+
+/// Copies the 31 low-order bytes of the field element into the output, returning its high-order
+/// byte. Leaves a byte of the output free for the reassembled bytes of the round.
+fn decode_field_element(
+    blob: &Blob,
+    fe: usize,
+    out: &mut [u8],
+    opos: &mut usize,
+) -> Result<u8, BlobDecodingError> {
+    check_field_element(blob, fe)?;
+    let start = fe * 32;
+    out[*opos..*opos + 31].copy_from_slice(&blob[start + 1..start + 32]);
+    *opos += 32;
+    Ok(blob[start])
+}
+
+/// Reassembles the 3 bytes spread over the high-order bytes of the 4 field elements of a round.
+fn reassemble_bytes(opos: usize, encoded: [u8; 4], out: &mut [u8]) -> usize {
+    // the round has room for 128 bytes, but only outputs 127
+    let opos = opos - 1;
+    let x = (encoded[0] & 0b0011_1111) | ((encoded[1] & 0b0011_0000) << 2);
+    let y = (encoded[1] & 0b0000_1111) | ((encoded[3] & 0b0000_1111) << 4);
+    let z = (encoded[2] & 0b0011_1111) | ((encoded[3] & 0b0011_0000) << 2);
+    out[opos - 32] = z;
+    out[opos - 32 * 2] = y;
+    out[opos - 32 * 3] = x;
+    opos
+}
+
+impl BlobDecoder for RollupBlobDecoder {
+    fn decode(&self, blobs: &[Blob]) -> Result<Vec<Vec<u8>>, BlobDecodingError> {
+        blobs.iter().map(Self::decode_blob).collect()
+    }
+}
+
+/// A framing format of the payloads packed into blobs.
+pub trait BlobFraming {
+    /// The frames of the format.
+    type Frame;
+
+    /// Splits a decoded payload into its frames.
+    fn parse_frames(&self, payload: &[u8]) -> Result<Vec<Self::Frame>, BlobDecodingError>;
+
+    /// Decodes the blobs with the decoder and splits all payloads into their frames, in order.
+    fn decode_frames<D: BlobDecoder>(
+        &self,
+        decoder: &D,
+        blobs: &[Blob],
+    ) -> Result<Vec<Self::Frame>, BlobDecodingError> {
+        let mut frames = Vec::new();
+        for payload in decoder.decode(blobs)? {
+            frames.extend(self.parse_frames(&payload)?);
+        }
+        Ok(frames)
+    }
+}
+
+/// A frame of an OP Stack channel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpChannelFrame {
+    /// The ID of the channel.
+    pub channel_id: FixedBytes<16>,
+    /// The index of the frame in the channel.
+    pub frame_number: u16,
+    /// The data of the frame.
+    pub data: Bytes,
+    /// Whether this is the last frame of the channel.
+    pub is_last: bool,
+}
+
+/// The framing of OP Stack batcher transactions, which hold a derivation version byte followed by
+/// channel frames.
+///
+/// See the [OP Stack specs](https://specs.optimism.io/protocol/derivation.html#frame-format).
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct OpFraming;
+
+impl OpFraming {
+    /// The supported derivation version.
+    pub const DERIVATION_VERSION: u8 = 0;
+
+    /// The maximum length of the data of a frame.
+    pub const MAX_FRAME_LEN: usize = 1_000_000;
+
+    fn parse_frame(data: &mut &[u8]) -> Option<OpChannelFrame> {
+        let channel_id = FixedBytes::from_slice(take(data, 16)?);
+        let frame_number = u16::from_be_bytes(take(data, 2)?.try_into().ok()?);
+        let len = u32::from_be_bytes(take(data, 4)?.try_into().ok()?) as usize;
+        if len > Self::MAX_FRAME_LEN {
+            return None;
+        }
+        let frame_data = Bytes::copy_from_slice(take(data, len)?);
+        let is_last = match take(data, 1)? {
+            [0] => false,
+            [1] => true,
+            _ => return None,
+        };
+        Some(OpChannelFrame { channel_id, frame_number, data: frame_data, is_last })
+    }
+}
+
+impl BlobFraming for OpFraming {
+    type Frame = OpChannelFrame;
+
+    fn parse_frames(&self, payload: &[u8]) -> Result<Vec<Self::Frame>, BlobDecodingError> {
+        let (&version, mut data) = payload.split_first().ok_or(BlobDecodingError::InvalidFrame)?;
+        if version != Self::DERIVATION_VERSION {
+            return Err(BlobDecodingError::UnsupportedVersion(version));
+        }
+        let mut frames = Vec::new();
+        while !data.is_empty() {
+            frames.push(Self::parse_frame(&mut data).ok_or(BlobDecodingError::InvalidFrame)?);
+        }
+        if frames.is_empty() {
+            return Err(BlobDecodingError::InvalidFrame);
+        }
+        Ok(frames)
+    }
+}
+
+/// Splits `len` bytes off the front of the data.
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if data.len() < len {
+        return None;
+    }
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Some(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip4844::{builder::SidecarBuilder, BYTES_PER_BLOB};
+
+    /// Reads bytes of the data into the buffer, padding with zeros past its end.
+    fn read(data: &[u8], ipos: &mut usize, buf: &mut [u8]) {
+        for byte in buf {
+            *byte = data.get(*ipos).copied().unwrap_or_default();
+            *ipos += 1;
+        }
+    }
+
+    /// Encodes data with the OP Stack version 0 blob encoding.
+    fn encode_rollup_blob(data: &[u8]) -> Blob {
+        let mut blob = Blob::new([0; BYTES_PER_BLOB]);
+        let (mut ipos, mut opos) = (0, 0);
+        let mut write = |high: u8, buf: &[u8; 31]| {
+            blob[opos] = high;
+            blob[opos + 1..opos + 32].copy_from_slice(buf);
+            opos += 32;
+        };
+
+        let mut round = 0;
+        while round < FIELD_ELEMENTS_PER_BLOB as usize / 4 && ipos < data.len() {
+            let (mut buf, mut byte) = ([0u8; 31], [0u8; 1]);
+            if round == 0 {
+                buf[0] = RollupBlobDecoder::VERSION;
+                buf[1..4].copy_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+                read(data, &mut ipos, &mut buf[4..]);
+            } else {
+                read(data, &mut ipos, &mut buf);
+            }
+            read(data, &mut ipos, &mut byte);
+            let x = byte[0];
+            write(x & 0b0011_1111, &buf);
+
+            read(data, &mut ipos, &mut buf);
+            read(data, &mut ipos, &mut byte);
+            let y = byte[0];
+            write((y & 0b0000_1111) | ((x & 0b1100_0000) >> 2), &buf);
+
+            read(data, &mut ipos, &mut buf);
+            read(data, &mut ipos, &mut byte);
+            let z = byte[0];
+            write(z & 0b0011_1111, &buf);
+
+            read(data, &mut ipos, &mut buf);
+            write(((z & 0b1100_0000) >> 2) | ((y & 0b1111_0000) >> 4), &buf);
+            round += 1;
+        }
+        blob
+    }
+
+    #[test]
+    fn decode_rollup_blobs() {
+        for len in [0, 1, 27, 123, 124, 1000, RollupBlobDecoder::MAX_DATA_SIZE] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 7 + 3) as u8).collect();
+            let blob = encode_rollup_blob(&data);
+            assert_eq!(RollupBlobDecoder::decode_blob(&blob).unwrap(), data, "length {len}");
+        }
+
+        let mut blob = encode_rollup_blob(b"hello");
+        blob[1] = 1;
+        assert_eq!(
+            RollupBlobDecoder::decode_blob(&blob),
+            Err(BlobDecodingError::UnsupportedVersion(1))
+        );
+
+        let mut blob = encode_rollup_blob(b"hello");
+        blob[BYTES_PER_BLOB - 1] = 1;
+        assert_eq!(RollupBlobDecoder::decode_blob(&blob), Err(BlobDecodingError::NonZeroPadding));
+
+        let mut blob = encode_rollup_blob(b"hello");
+        blob[32] = 0x80;
+        assert_eq!(
+            RollupBlobDecoder::decode_blob(&blob),
+            Err(BlobDecodingError::InvalidFieldElement(1))
+        );
+    }
+
+    #[test]
+    fn decode_simple_coder_blobs() {
+        let data = [b"hello".to_vec(), alloc::vec![0xab; 100]];
+        let mut builder = SidecarBuilder::<SimpleCoder>::new();
+        data.iter().for_each(|data| builder.ingest(data));
+        assert_eq!(SimpleCoder.decode(&builder.take()).unwrap(), data);
+    }
+
+    #[test]
+    fn decode_op_frames() {
+        let mut payload = alloc::vec![OpFraming::DERIVATION_VERSION];
+        for (frame_number, data, is_last) in [(0u16, &b"abc"[..], false), (1, &b"de"[..], true)] {
+            payload.extend_from_slice(&[0x11; 16]);
+            payload.extend_from_slice(&frame_number.to_be_bytes());
+            payload.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            payload.extend_from_slice(data);
+            payload.push(is_last as u8);
+        }
+        let blobs = [encode_rollup_blob(&payload), encode_rollup_blob(&payload)];
+
+        let frames = OpFraming.decode_frames(&RollupBlobDecoder, &blobs).unwrap();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[1].channel_id, FixedBytes::repeat_byte(0x11));
+        assert_eq!(frames[1].frame_number, 1);
+        assert_eq!(frames[1].data, Bytes::from_static(b"de"));
+        assert!(frames[1].is_last);
+
+        assert_eq!(
+            OpFraming.parse_frames(&payload[..payload.len() - 1]),
+            Err(BlobDecodingError::InvalidFrame)
+        );
+    }
+}
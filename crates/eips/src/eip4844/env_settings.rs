@@ -4,6 +4,10 @@ use c_kzg::KzgSettings;
 use core::hash::{Hash, Hasher};
 
 /// KZG settings.
+///
+/// The default mainnet trusted setup is embedded in the binary, and only loaded into memory once,
+/// on first use. A custom trusted setup can be supplied instead, e.g. from a file with
+/// [`EnvKzgSettings::from_trusted_setup_file`] in air-gapped environments.
 #[derive(Clone, Debug, Default, Eq)]
 pub enum EnvKzgSettings {
     /// Default mainnet trusted setup.
@@ -35,6 +39,40 @@ impl Hash for EnvKzgSettings {
 }
 
 impl EnvKzgSettings {
+    /// Parses a custom trusted setup from the contents of a trusted setup file.
+    pub fn from_trusted_setup(trusted_setup: &str) -> Result<Self, c_kzg::Error> {
+        Ok(Self::Custom(Arc::new(KzgSettings::parse_kzg_trusted_setup(trusted_setup)?)))
+    }
+
+    /// Loads a custom trusted setup from a trusted setup file.
+    ///
+    /// Loaded setups are cached by path while they are in use, so loading the same file again
+    /// returns the same settings instead of loading another copy into memory.
+    #[cfg(feature = "std")]
+    pub fn from_trusted_setup_file(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, c_kzg::Error> {
+        use std::{
+            collections::HashMap,
+            path::PathBuf,
+            sync::{Mutex, OnceLock, Weak},
+        };
+        static CACHE: OnceLock<Mutex<HashMap<PathBuf, Weak<KzgSettings>>>> = OnceLock::new();
+
+        let path = path.as_ref();
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mut cache =
+            CACHE.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(settings) = cache.get(&key).and_then(Weak::upgrade) {
+            return Ok(Self::Custom(settings));
+        }
+
+        let settings = Arc::new(KzgSettings::load_trusted_setup_file(path)?);
+        cache.retain(|_, settings| settings.strong_count() > 0);
+        cache.insert(key, Arc::downgrade(&settings));
+        Ok(Self::Custom(settings))
+    }
+
     /// Returns the KZG settings.
     ///
     /// This will initialize the default settings if it is not already loaded.
@@ -64,3 +102,39 @@ impl EnvKzgSettings {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip4844::builder::{SidecarBuilder, SimpleCoder};
+
+    const TRUSTED_SETUP: &str = include_str!("trusted_setup.txt");
+
+    #[test]
+    fn custom_trusted_setup() {
+        let settings = EnvKzgSettings::from_trusted_setup(TRUSTED_SETUP).unwrap();
+        let sidecar = SidecarBuilder::<SimpleCoder>::from_slice(b"custom setup")
+            .build_with_settings(settings.get())
+            .unwrap();
+        let hashes: Vec<_> = sidecar.versioned_hashes().collect();
+        sidecar.verify(&hashes).unwrap();
+        sidecar.verify_with_settings(&hashes, &settings).unwrap();
+
+        assert!(EnvKzgSettings::from_trusted_setup("4096\n65\n").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn trusted_setup_file_is_cached() {
+        let path =
+            std::env::temp_dir().join(format!("alloy-trusted-setup-{}.txt", std::process::id()));
+        std::fs::write(&path, TRUSTED_SETUP).unwrap();
+
+        let first = EnvKzgSettings::from_trusted_setup_file(&path).unwrap();
+        let second = EnvKzgSettings::from_trusted_setup_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // the same settings are returned for the same file
+        assert_eq!(first, second);
+        assert_ne!(first, EnvKzgSettings::Default);
+    }
+}
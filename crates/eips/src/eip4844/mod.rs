@@ -11,11 +11,15 @@ pub mod trusted_setup_points;
 
 /// Builder and utils for the [EIP-4844 Blob Transaction](https://eips.ethereum.org/EIPS/eip-4844#blob-transaction)
 pub mod builder;
+pub mod decode;
 pub mod utils;
 
 mod engine;
 pub use engine::*;
 
+mod params;
+pub use params::BlobParams;
+
 /// Contains sidecar related types
 #[cfg(feature = "kzg-sidecar")]
 mod sidecar;
@@ -111,12 +115,45 @@ pub type Bytes48 = FixedBytes<48>;
 /// If the given commitment is not 48 bytes long.
 #[cfg(feature = "sha2")]
 pub fn kzg_to_versioned_hash(commitment: &[u8]) -> B256 {
-    use sha2::Digest;
-
     debug_assert_eq!(commitment.len(), 48, "commitment length is not 48");
-    let mut res = sha2::Sha256::digest(commitment);
+    let mut res = sha256(commitment);
     res[0] = VERSIONED_HASH_VERSION_KZG;
-    B256::new(res.into())
+    res
+}
+
+/// Calculates the SHA-256 hash of the given data.
+///
+/// With the `native-sha256` feature, this calls the `native_sha256` function of the host
+/// environment instead of [`sha2`] when compiling for a zkVM (`target_os = "zkvm"`), e.g. the
+/// SHA-256 precompile of SP1 or RISC Zero. Other targets keep using [`sha2`], so that the feature
+/// can be enabled together with any other, e.g. for `--all-features` builds.
+#[cfg(feature = "sha2")]
+pub fn sha256(data: &[u8]) -> B256 {
+    #[cfg(all(feature = "native-sha256", target_os = "zkvm"))]
+    {
+        extern "C" {
+            /// When targeting VMs with native SHA-256 hooks, the `native-sha256` feature can be
+            /// enabled to import and use the host environment's implementation of SHA-256.
+            ///
+            /// # Safety
+            ///
+            /// The VM accepts the preimage by pointer and length, and writes the 32-byte hash.
+            /// - `bytes` must point to an input buffer at least `len` long.
+            /// - `output` must point to a buffer that is at least 32-bytes long.
+            fn native_sha256(bytes: *const u8, len: usize, output: *mut u8);
+        }
+
+        let mut output = B256::ZERO;
+        // SAFETY: The output is 32-bytes, and the input comes from a slice.
+        unsafe { native_sha256(data.as_ptr(), data.len(), output.as_mut_ptr()) };
+        output
+    }
+    #[cfg(not(all(feature = "native-sha256", target_os = "zkvm")))]
+    {
+        use sha2::Digest;
+
+        B256::new(sha2::Sha256::digest(data).into())
+    }
 }
 
 /// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used` and `excess_blob_gas`.
@@ -172,6 +209,18 @@ fn fake_exponential(factor: u128, numerator: u128, denominator: u128) -> u128 {
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn test_kzg_to_versioned_hash() {
+        assert_eq!(
+            sha256(b""),
+            b256!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+        let versioned_hash = kzg_to_versioned_hash(&[0; 48]);
+        assert_eq!(versioned_hash[0], VERSIONED_HASH_VERSION_KZG);
+        assert_eq!(versioned_hash[1..], sha256(&[0; 48])[1..]);
+    }
+
     // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L27
     #[test]
     fn test_calc_excess_blob_gas() {
@@ -0,0 +1,125 @@
+use super::{
+    fake_exponential, BLOB_GASPRICE_UPDATE_FRACTION, BLOB_TX_MIN_BLOB_GASPRICE, DATA_GAS_PER_BLOB,
+    MAX_BLOBS_PER_BLOCK, TARGET_BLOBS_PER_BLOCK,
+};
+use crate::eip7691::{
+    BLOB_GASPRICE_UPDATE_FRACTION_PECTRA, MAX_BLOBS_PER_BLOCK_ELECTRA,
+    TARGET_BLOBS_PER_BLOCK_ELECTRA,
+};
+
+/// BlobParams contains the config parameters that control the blob gas accounting of a block.
+///
+/// The parameters were introduced by [EIP-4844] in Cancun and raised by [EIP-7691] in Prague.
+///
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+/// [EIP-7691]: https://eips.ethereum.org/EIPS/eip-7691
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BlobParams {
+    /// The target number of blobs per block.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub target_blob_count: u64,
+    /// The maximum number of blobs per block.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub max_blob_count: u64,
+    /// The update fraction of the blob base fee.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub update_fraction: u128,
+    /// The minimum blob base fee.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub min_blob_fee: u128,
+}
+
+impl BlobParams {
+    /// Get the blob parameters for Cancun, as defined by EIP-4844.
+    pub const fn cancun() -> Self {
+        Self {
+            target_blob_count: TARGET_BLOBS_PER_BLOCK,
+            max_blob_count: MAX_BLOBS_PER_BLOCK as u64,
+            update_fraction: BLOB_GASPRICE_UPDATE_FRACTION,
+            min_blob_fee: BLOB_TX_MIN_BLOB_GASPRICE,
+        }
+    }
+
+    /// Get the blob parameters for Prague, as defined by EIP-7691.
+    pub const fn prague() -> Self {
+        Self {
+            target_blob_count: TARGET_BLOBS_PER_BLOCK_ELECTRA,
+            max_blob_count: MAX_BLOBS_PER_BLOCK_ELECTRA,
+            update_fraction: BLOB_GASPRICE_UPDATE_FRACTION_PECTRA,
+            min_blob_fee: BLOB_TX_MIN_BLOB_GASPRICE,
+        }
+    }
+
+    /// Returns the target blob gas per block.
+    pub const fn target_blob_gas_per_block(&self) -> u64 {
+        self.target_blob_count * DATA_GAS_PER_BLOB
+    }
+
+    /// Returns the maximum blob gas per block.
+    pub const fn max_blob_gas_per_block(&self) -> u64 {
+        self.max_blob_count * DATA_GAS_PER_BLOB
+    }
+
+    /// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used` and
+    /// `excess_blob_gas`.
+    ///
+    /// See also [`calc_excess_blob_gas`](super::calc_excess_blob_gas).
+    #[inline]
+    pub const fn next_block_excess_blob_gas(
+        &self,
+        parent_excess_blob_gas: u64,
+        parent_blob_gas_used: u64,
+    ) -> u64 {
+        parent_excess_blob_gas
+            .saturating_add(parent_blob_gas_used)
+            .saturating_sub(self.target_blob_gas_per_block())
+    }
+
+    /// Calculates the blob gas price from the header's excess blob gas field.
+    ///
+    /// See also [`calc_blob_gasprice`](super::calc_blob_gasprice).
+    #[inline]
+    pub fn calc_blob_fee(&self, excess_blob_gas: u64) -> u128 {
+        fake_exponential(self.min_blob_fee, excess_blob_gas as u128, self.update_fraction)
+    }
+}
+
+impl Default for BlobParams {
+    fn default() -> Self {
+        Self::cancun()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip4844::{calc_blob_gasprice, calc_excess_blob_gas};
+
+    #[test]
+    fn cancun_matches_eip4844_helpers() {
+        let params = BlobParams::cancun();
+        for (excess, used) in [(0, 0), (0, 4 * DATA_GAS_PER_BLOB), (1_000_000, DATA_GAS_PER_BLOB)] {
+            assert_eq!(
+                params.next_block_excess_blob_gas(excess, used),
+                calc_excess_blob_gas(excess, used)
+            );
+            assert_eq!(params.calc_blob_fee(excess), calc_blob_gasprice(excess));
+        }
+    }
+
+    #[test]
+    fn prague_params() {
+        let params = BlobParams::prague();
+        assert_eq!(params.target_blob_gas_per_block(), 786_432);
+        assert_eq!(params.max_blob_gas_per_block(), 1_179_648);
+        // 7 blobs exceed the target of 6 by one blob
+        assert_eq!(params.next_block_excess_blob_gas(0, 7 * DATA_GAS_PER_BLOB), DATA_GAS_PER_BLOB);
+        assert_eq!(params.next_block_excess_blob_gas(0, 6 * DATA_GAS_PER_BLOB), 0);
+        // the higher update fraction makes the fee grow more slowly
+        let excess = 10_000_000;
+        assert!(params.calc_blob_fee(excess) < BlobParams::cancun().calc_blob_fee(excess));
+        assert_eq!(params.calc_blob_fee(0), 1);
+    }
+}
@@ -13,10 +13,6 @@ use crate::eip4844::MAX_BLOBS_PER_BLOCK;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-/// The versioned hash version for KZG.
-#[cfg(feature = "kzg")]
-pub(crate) const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
-
 /// This represents a set of blobs, and its corresponding commitments and proofs.
 ///
 /// This type encodes and decodes the fields without an rlp header.
@@ -78,11 +74,7 @@ pub struct BlobTransactionSidecarItem {
 impl BlobTransactionSidecarItem {
     /// `VERSIONED_HASH_VERSION_KZG ++ sha256(commitment)[1..]`
     pub fn to_kzg_versioned_hash(&self) -> [u8; 32] {
-        use sha2::Digest;
-        let commitment = self.kzg_commitment.as_slice();
-        let mut hash: [u8; 32] = sha2::Sha256::digest(commitment).into();
-        hash[0] = VERSIONED_HASH_VERSION_KZG;
-        hash
+        kzg_to_versioned_hash(self.kzg_commitment.as_slice()).0
     }
 
     /// Verifies the KZG proof of a blob to ensure its integrity and correctness.
@@ -237,6 +229,44 @@ impl BlobTransactionSidecar {
         res.then_some(()).ok_or(BlobTransactionValidationError::InvalidProof)
     }
 
+    /// Verifies the sidecar against the versioned hashes of its transaction, using the default
+    /// mainnet KZG trusted setup.
+    ///
+    /// See [`BlobTransactionSidecar::verify_with_settings`].
+    #[cfg(feature = "kzg")]
+    pub fn verify(
+        &self,
+        blob_versioned_hashes: &[B256],
+    ) -> Result<(), BlobTransactionValidationError> {
+        self.verify_with_settings(
+            blob_versioned_hashes,
+            &crate::eip4844::env_settings::EnvKzgSettings::Default,
+        )
+    }
+
+    /// Verifies the sidecar against the versioned hashes of its transaction, using the given KZG
+    /// settings.
+    ///
+    /// This checks that the sidecar has one commitment and one proof per blob, that the versioned
+    /// hashes match the commitments, and that every proof is valid for its blob and commitment.
+    #[cfg(feature = "kzg")]
+    pub fn verify_with_settings(
+        &self,
+        blob_versioned_hashes: &[B256],
+        settings: &crate::eip4844::env_settings::EnvKzgSettings,
+    ) -> Result<(), BlobTransactionValidationError> {
+        if self.blobs.len() != self.commitments.len() || self.blobs.len() != self.proofs.len() {
+            return Err(c_kzg::Error::MismatchLength(format!(
+                "There are {} blobs, {} commitments and {} proofs",
+                self.blobs.len(),
+                self.commitments.len(),
+                self.proofs.len()
+            ))
+            .into());
+        }
+        self.validate(blob_versioned_hashes, settings.get())
+    }
+
     /// Returns an iterator over the versioned hashes of the commitments.
     pub fn versioned_hashes(&self) -> impl Iterator<Item = B256> + '_ {
         self.commitments.iter().map(|c| kzg_to_versioned_hash(c.as_slice()))
@@ -248,6 +278,14 @@ impl BlobTransactionSidecar {
         self.commitments.get(blob_index).map(|c| kzg_to_versioned_hash(c.as_slice()))
     }
 
+    /// Decodes the payloads packed into the blobs with the given decoder.
+    pub fn decode_data<D: crate::eip4844::decode::BlobDecoder>(
+        &self,
+        decoder: &D,
+    ) -> Result<Vec<Vec<u8>>, crate::eip4844::decode::BlobDecodingError> {
+        decoder.decode(&self.blobs)
+    }
+
     /// Encodes the inner [BlobTransactionSidecar] fields as RLP bytes, __without__ a RLP header.
     ///
     /// This encodes the fields in the following order:
@@ -405,6 +443,37 @@ mod tests {
         assert_eq!(blob, deserialized);
     }
 
+    #[test]
+    #[cfg(feature = "kzg")]
+    fn verify_sidecar() {
+        use crate::eip4844::builder::{SidecarBuilder, SimpleCoder};
+
+        let sidecar =
+            SidecarBuilder::<SimpleCoder>::from_slice(b"blobs are verified").build().unwrap();
+        let hashes: Vec<_> = sidecar.versioned_hashes().collect();
+        sidecar.verify(&hashes).unwrap();
+
+        assert!(matches!(
+            sidecar.verify(&[B256::ZERO]),
+            Err(BlobTransactionValidationError::WrongVersionedHash { .. })
+        ));
+        assert!(matches!(sidecar.verify(&[]), Err(BlobTransactionValidationError::KZGError(_))));
+
+        let mut tampered = sidecar.clone();
+        tampered.blobs[0][1] ^= 1;
+        assert!(matches!(
+            tampered.verify(&hashes),
+            Err(BlobTransactionValidationError::InvalidProof)
+        ));
+
+        let mut missing_proof = sidecar;
+        missing_proof.proofs.clear();
+        assert!(matches!(
+            missing_proof.verify(&hashes),
+            Err(BlobTransactionValidationError::KZGError(_))
+        ));
+    }
+
     #[test]
     fn test_arbitrary_blob() {
         let mut unstructured = arbitrary::Unstructured::new(b"unstructured blob");
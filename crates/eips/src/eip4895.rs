@@ -33,16 +33,111 @@ pub struct Withdrawal {
 impl Withdrawal {
     /// Return the withdrawal amount in wei.
     pub fn amount_wei(&self) -> U256 {
-        U256::from(self.amount) * U256::from(GWEI_TO_WEI)
+        self.amount_gwei().to_wei()
     }
+
+    /// Return the withdrawal amount as a typed gwei amount.
+    pub const fn amount_gwei(&self) -> GweiAmount {
+        GweiAmount(self.amount)
+    }
+}
+
+/// Returns the total amount withdrawn by the given withdrawals, in wei.
+///
+/// The sum is computed as [`U256`], so it cannot overflow.
+pub fn total_withdrawn_wei(withdrawals: &[Withdrawal]) -> U256 {
+    withdrawals.iter().map(Withdrawal::amount_wei).sum()
 }
 
-#[cfg(all(test, feature = "serde"))]
+/// An amount of ether denominated in gwei, as used by the consensus layer.
+///
+/// Consensus layer amounts, such as the [`Withdrawal::amount`], are in gwei, while execution
+/// layer balances are in wei. Wrapping gwei amounts in this type makes the unit explicit, so
+/// they must be converted with [`GweiAmount::to_wei`] before they are compared with balances.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GweiAmount(pub u64);
+
+impl GweiAmount {
+    /// Creates a new amount from gwei.
+    pub const fn from_gwei(gwei: u64) -> Self {
+        Self(gwei)
+    }
+
+    /// Converts an amount in wei to gwei.
+    ///
+    /// Returns `None` if the amount is not a whole number of gwei, or does not fit in a `u64` of
+    /// gwei. See [`GweiAmount::from_wei_floor`] to round down instead.
+    pub fn from_wei(wei: U256) -> Option<Self> {
+        let (gwei, rem) = wei.div_rem(U256::from(GWEI_TO_WEI));
+        if rem.is_zero() {
+            u64::try_from(gwei).ok().map(Self)
+        } else {
+            None
+        }
+    }
+
+    /// Converts an amount in wei to gwei, rounding down to a whole number of gwei.
+    ///
+    /// Returns `None` if the amount does not fit in a `u64` of gwei.
+    pub fn from_wei_floor(wei: U256) -> Option<Self> {
+        u64::try_from(wei / U256::from(GWEI_TO_WEI)).ok().map(Self)
+    }
+
+    /// Returns the amount in gwei.
+    pub const fn gwei(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the amount in wei.
+    pub fn to_wei(self) -> U256 {
+        U256::from(self.0) * U256::from(GWEI_TO_WEI)
+    }
+
+    /// Adds two amounts, returning `None` on overflow.
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.0.checked_add(other.0) {
+            Some(gwei) => Some(Self(gwei)),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn gwei_amounts() {
+        let withdrawal = Withdrawal { amount: 32_000_000_000, ..Default::default() };
+        assert_eq!(withdrawal.amount_gwei(), GweiAmount::from_gwei(32_000_000_000));
+        assert_eq!(withdrawal.amount_wei(), U256::from(32u128 * 10u128.pow(18)));
+        assert_eq!(
+            total_withdrawn_wei(&[withdrawal, Withdrawal { amount: 1, ..Default::default() }]),
+            U256::from(32u128 * 10u128.pow(18) + 10u128.pow(9))
+        );
+        assert_eq!(total_withdrawn_wei(&[]), U256::ZERO);
+
+        let max = Withdrawal { amount: u64::MAX, ..Default::default() };
+        assert_eq!(
+            total_withdrawn_wei(&[max, max]),
+            U256::from(u64::MAX) * U256::from(2 * GWEI_TO_WEI)
+        );
+        assert_eq!(max.amount_gwei().checked_add(GweiAmount(1)), None);
+    }
+
+    #[test]
+    fn gwei_from_wei() {
+        let one_gwei = U256::from(GWEI_TO_WEI);
+        assert_eq!(GweiAmount::from_wei(one_gwei * U256::from(3)), Some(GweiAmount(3)));
+        assert_eq!(GweiAmount::from_wei(one_gwei + U256::from(1)), None);
+        assert_eq!(GweiAmount::from_wei_floor(one_gwei + U256::from(1)), Some(GweiAmount(1)));
+        assert_eq!(GweiAmount::from_wei(U256::MAX), None);
+        assert_eq!(GweiAmount(2).to_wei(), one_gwei * U256::from(2));
+    }
+
     // <https://github.com/paradigmxyz/reth/issues/1614>
     #[test]
+    #[cfg(feature = "serde")]
     fn test_withdrawal_serde_roundtrip() {
         let input = r#"[{"index":"0x0","validatorIndex":"0x0","address":"0x0000000000000000000000000000000000001000","amount":"0x1"},{"index":"0x1","validatorIndex":"0x1","address":"0x0000000000000000000000000000000000001001","amount":"0x1"},{"index":"0x2","validatorIndex":"0x2","address":"0x0000000000000000000000000000000000001002","amount":"0x1"},{"index":"0x3","validatorIndex":"0x3","address":"0x0000000000000000000000000000000000001003","amount":"0x1"},{"index":"0x4","validatorIndex":"0x4","address":"0x0000000000000000000000000000000000001004","amount":"0x1"},{"index":"0x5","validatorIndex":"0x5","address":"0x0000000000000000000000000000000000001005","amount":"0x1"},{"index":"0x6","validatorIndex":"0x6","address":"0x0000000000000000000000000000000000001006","amount":"0x1"},{"index":"0x7","validatorIndex":"0x7","address":"0x0000000000000000000000000000000000001007","amount":"0x1"},{"index":"0x8","validatorIndex":"0x8","address":"0x0000000000000000000000000000000000001008","amount":"0x1"},{"index":"0x9","validatorIndex":"0x9","address":"0x0000000000000000000000000000000000001009","amount":"0x1"},{"index":"0xa","validatorIndex":"0xa","address":"0x000000000000000000000000000000000000100A","amount":"0x1"},{"index":"0xb","validatorIndex":"0xb","address":"0x000000000000000000000000000000000000100b","amount":"0x1"},{"index":"0xc","validatorIndex":"0xc","address":"0x000000000000000000000000000000000000100C","amount":"0x1"},{"index":"0xd","validatorIndex":"0xd","address":"0x000000000000000000000000000000000000100D","amount":"0x1"},{"index":"0xe","validatorIndex":"0xe","address":"0x000000000000000000000000000000000000100e","amount":"0x1"},{"index":"0xf","validatorIndex":"0xf","address":"0x000000000000000000000000000000000000100f","amount":"0x1"}]"#;
 
@@ -0,0 +1,13 @@
+//! Contains constants of [EIP-7691]: Blob throughput increase, first introduced in the Prague
+//! hardfork.
+//!
+//! [EIP-7691]: https://eips.ethereum.org/EIPS/eip-7691
+
+/// The target number of blobs per block after Prague.
+pub const TARGET_BLOBS_PER_BLOCK_ELECTRA: u64 = 6;
+
+/// The maximum number of blobs per block after Prague.
+pub const MAX_BLOBS_PER_BLOCK_ELECTRA: u64 = 9;
+
+/// The blob base fee update fraction after Prague.
+pub const BLOB_GASPRICE_UPDATE_FRACTION_PECTRA: u128 = 5_007_716;
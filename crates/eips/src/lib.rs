@@ -11,6 +11,10 @@
 #[macro_use]
 extern crate alloc;
 
+// `native-sha256` replaces the `sha2` implementation in zkVMs.
+#[cfg(all(feature = "native-sha256", target_os = "zkvm"))]
+use sha2 as _;
+
 pub mod eip1559;
 pub use eip1559::calc_next_block_base_fee;
 
@@ -36,10 +40,14 @@ pub mod eip4895;
 pub mod eip6110;
 pub mod merge;
 
+pub mod precompiles;
+
 pub mod eip7002;
 
 pub mod eip7251;
 
 pub mod eip7685;
 
+pub mod eip7691;
+
 pub mod eip7702;
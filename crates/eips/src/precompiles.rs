@@ -0,0 +1,380 @@
+//! Addresses, input encoding and gas costs of the standard precompiled contracts.
+//!
+//! Gas costs are those of the Cancun hardfork, see the [yellow paper], [EIP-1108], [EIP-2565] and
+//! [EIP-4844].
+//!
+//! [yellow paper]: https://ethereum.github.io/yellowpaper/paper.pdf
+//! [EIP-1108]: https://eips.ethereum.org/EIPS/eip-1108
+//! [EIP-2565]: https://eips.ethereum.org/EIPS/eip-2565
+//! [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+
+use crate::eip4844::Bytes48;
+use alloc::vec::Vec;
+use alloy_primitives::{address, Address, B256, U256};
+
+/// The address of the `ecrecover` precompile.
+pub const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+
+/// The address of the `sha256` precompile.
+pub const SHA256: Address = address!("0000000000000000000000000000000000000002");
+
+/// The address of the `ripemd160` precompile.
+pub const RIPEMD160: Address = address!("0000000000000000000000000000000000000003");
+
+/// The address of the `identity` precompile, which returns its input.
+pub const IDENTITY: Address = address!("0000000000000000000000000000000000000004");
+
+/// The address of the `modexp` precompile ([EIP-198]).
+///
+/// [EIP-198]: https://eips.ethereum.org/EIPS/eip-198
+pub const MODEXP: Address = address!("0000000000000000000000000000000000000005");
+
+/// The address of the `ecAdd` precompile on the alt_bn128 curve ([EIP-196]).
+///
+/// [EIP-196]: https://eips.ethereum.org/EIPS/eip-196
+pub const BN254_ADD: Address = address!("0000000000000000000000000000000000000006");
+
+/// The address of the `ecMul` precompile on the alt_bn128 curve ([EIP-196]).
+///
+/// [EIP-196]: https://eips.ethereum.org/EIPS/eip-196
+pub const BN254_MUL: Address = address!("0000000000000000000000000000000000000007");
+
+/// The address of the `ecPairing` precompile on the alt_bn128 curve ([EIP-197]).
+///
+/// [EIP-197]: https://eips.ethereum.org/EIPS/eip-197
+pub const BN254_PAIRING: Address = address!("0000000000000000000000000000000000000008");
+
+/// The address of the `blake2f` compression function precompile ([EIP-152]).
+///
+/// [EIP-152]: https://eips.ethereum.org/EIPS/eip-152
+pub const BLAKE2F: Address = address!("0000000000000000000000000000000000000009");
+
+/// The address of the KZG point evaluation precompile ([EIP-4844]).
+///
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+pub const POINT_EVALUATION: Address = address!("000000000000000000000000000000000000000a");
+
+/// The addresses of all standard precompiles, in order.
+pub const PRECOMPILES: [Address; 10] = [
+    ECRECOVER,
+    SHA256,
+    RIPEMD160,
+    IDENTITY,
+    MODEXP,
+    BN254_ADD,
+    BN254_MUL,
+    BN254_PAIRING,
+    BLAKE2F,
+    POINT_EVALUATION,
+];
+
+/// The gas cost of `ecrecover`.
+pub const ECRECOVER_GAS: u64 = 3_000;
+
+/// The gas cost of `ecAdd`.
+pub const BN254_ADD_GAS: u64 = 150;
+
+/// The gas cost of `ecMul`.
+pub const BN254_MUL_GAS: u64 = 6_000;
+
+/// The base gas cost of `ecPairing`.
+pub const BN254_PAIRING_BASE_GAS: u64 = 45_000;
+
+/// The gas cost of `ecPairing` per pair of points.
+pub const BN254_PAIRING_PER_PAIR_GAS: u64 = 34_000;
+
+/// The gas cost of the point evaluation precompile.
+pub const POINT_EVALUATION_GAS: u64 = 50_000;
+
+/// The minimum gas cost of `modexp`.
+pub const MODEXP_MIN_GAS: u64 = 200;
+
+/// Returns `true` if the address is one of the standard [`PRECOMPILES`].
+pub fn is_precompile(address: Address) -> bool {
+    PRECOMPILES.contains(&address)
+}
+
+/// Returns the gas cost of calling the precompile at the address with the input, or `None` if the
+/// address is not a standard precompile.
+///
+/// Invalid inputs are priced as if they were valid, calls with them fail and consume all gas.
+pub fn gas_cost(address: Address, input: &[u8]) -> Option<u64> {
+    let words = (input.len() as u64).div_ceil(32);
+    let gas = match address {
+        ECRECOVER => ECRECOVER_GAS,
+        SHA256 => 60 + 12 * words,
+        RIPEMD160 => 600 + 120 * words,
+        IDENTITY => 15 + 3 * words,
+        MODEXP => modexp_gas(input),
+        BN254_ADD => BN254_ADD_GAS,
+        BN254_MUL => BN254_MUL_GAS,
+        BN254_PAIRING => {
+            BN254_PAIRING_BASE_GAS + BN254_PAIRING_PER_PAIR_GAS * (input.len() as u64 / 192)
+        }
+        BLAKE2F => {
+            input.get(..4).map_or(0, |rounds| u32::from_be_bytes(rounds.try_into().unwrap())) as u64
+        }
+        POINT_EVALUATION => POINT_EVALUATION_GAS,
+        _ => return None,
+    };
+    Some(gas)
+}
+
+/// Returns the gas cost of `modexp` with the input, saturating at `u64::MAX` ([EIP-2565]).
+///
+/// [EIP-2565]: https://eips.ethereum.org/EIPS/eip-2565
+pub fn modexp_gas(input: &[u8]) -> u64 {
+    let length = |index: usize| {
+        let mut word = [0; 32];
+        read_padded(input, index * 32, &mut word);
+        u64::try_from(U256::from_be_bytes(word)).unwrap_or(u64::MAX)
+    };
+    let (base_len, exp_len, mod_len) = (length(0), length(1), length(2));
+
+    // the iteration count follows from the first 32 bytes of the exponent
+    let mut exp_head = [0; 32];
+    let head_len = exp_len.min(32) as usize;
+    let offset = usize::try_from(base_len).unwrap_or(usize::MAX).saturating_add(96);
+    read_padded(input, offset, &mut exp_head[32 - head_len..]);
+    let head_bits = (U256::from_be_bytes(exp_head).bit_len() as u64).saturating_sub(1);
+    let iterations = if exp_len <= 32 {
+        head_bits
+    } else {
+        (exp_len - 32).saturating_mul(8).saturating_add(head_bits)
+    }
+    .max(1);
+
+    let words = U256::from(base_len.max(mod_len).div_ceil(8));
+    let gas = words * words * U256::from(iterations) / U256::from(3);
+    u64::try_from(gas).unwrap_or(u64::MAX).max(MODEXP_MIN_GAS)
+}
+
+/// Encodes the input of `ecrecover`, with the recovery id `v` as 27 or 28.
+pub fn encode_ecrecover(hash: B256, v: u8, r: U256, s: U256) -> [u8; 128] {
+    let mut input = [0; 128];
+    input[..32].copy_from_slice(hash.as_slice());
+    input[63] = v;
+    input[64..96].copy_from_slice(&r.to_be_bytes::<32>());
+    input[96..].copy_from_slice(&s.to_be_bytes::<32>());
+    input
+}
+
+/// Decodes the output of `ecrecover`, or returns `None` if the signature was invalid.
+pub fn decode_ecrecover(output: &[u8]) -> Option<Address> {
+    (output.len() == 32).then(|| Address::from_slice(&output[12..]))
+}
+
+/// Decodes the output of `ripemd160`, which is left-padded to 32 bytes.
+pub fn decode_ripemd160(output: &[u8]) -> Option<[u8; 20]> {
+    (output.len() == 32).then(|| output[12..].try_into().unwrap())
+}
+
+/// Encodes the input of `modexp`, computing `base ** exponent % modulus` of the big-endian
+/// numbers.
+pub fn encode_modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let mut input = Vec::with_capacity(96 + base.len() + exponent.len() + modulus.len());
+    for len in [base.len(), exponent.len(), modulus.len()] {
+        input.extend_from_slice(&U256::from(len).to_be_bytes::<32>());
+    }
+    input.extend_from_slice(base);
+    input.extend_from_slice(exponent);
+    input.extend_from_slice(modulus);
+    input
+}
+
+/// A point on the G1 curve of alt_bn128, with the point at infinity as `(0, 0)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct G1Point {
+    /// The x coordinate.
+    pub x: U256,
+    /// The y coordinate.
+    pub y: U256,
+}
+
+impl G1Point {
+    /// Encodes the point as the big-endian coordinates.
+    pub fn encode(&self) -> [u8; 64] {
+        let mut encoded = [0; 64];
+        encoded[..32].copy_from_slice(&self.x.to_be_bytes::<32>());
+        encoded[32..].copy_from_slice(&self.y.to_be_bytes::<32>());
+        encoded
+    }
+
+    /// Decodes the output of `ecAdd` or `ecMul`.
+    pub fn decode(output: &[u8]) -> Option<Self> {
+        (output.len() == 64).then(|| Self {
+            x: U256::from_be_slice(&output[..32]),
+            y: U256::from_be_slice(&output[32..]),
+        })
+    }
+}
+
+/// A point on the G2 curve of alt_bn128, whose coordinates are elements of `Fp2`.
+///
+/// The coefficients of each coordinate are in the order of the precompile input: the imaginary
+/// part first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct G2Point {
+    /// The x coordinate, imaginary part first.
+    pub x: [U256; 2],
+    /// The y coordinate, imaginary part first.
+    pub y: [U256; 2],
+}
+
+impl G2Point {
+    /// Encodes the point as the big-endian coefficients.
+    pub fn encode(&self) -> [u8; 128] {
+        let mut encoded = [0; 128];
+        for (chunk, coefficient) in encoded.chunks_exact_mut(32).zip(self.x.iter().chain(&self.y)) {
+            chunk.copy_from_slice(&coefficient.to_be_bytes::<32>());
+        }
+        encoded
+    }
+}
+
+/// Encodes the input of `ecAdd`, adding the points.
+pub fn encode_bn254_add(a: G1Point, b: G1Point) -> [u8; 128] {
+    let mut input = [0; 128];
+    input[..64].copy_from_slice(&a.encode());
+    input[64..].copy_from_slice(&b.encode());
+    input
+}
+
+/// Encodes the input of `ecMul`, multiplying the point by the scalar.
+pub fn encode_bn254_mul(point: G1Point, scalar: U256) -> [u8; 96] {
+    let mut input = [0; 96];
+    input[..64].copy_from_slice(&point.encode());
+    input[64..].copy_from_slice(&scalar.to_be_bytes::<32>());
+    input
+}
+
+/// Encodes the input of `ecPairing`, checking if the product of the pairings of the points is one.
+pub fn encode_bn254_pairing(pairs: &[(G1Point, G2Point)]) -> Vec<u8> {
+    let mut input = Vec::with_capacity(pairs.len() * 192);
+    for (g1, g2) in pairs {
+        input.extend_from_slice(&g1.encode());
+        input.extend_from_slice(&g2.encode());
+    }
+    input
+}
+
+/// Decodes the output of `ecPairing`.
+pub fn decode_bn254_pairing(output: &[u8]) -> Option<bool> {
+    (output.len() == 32).then(|| output[31] == 1)
+}
+
+/// Encodes the input of `blake2f`, compressing the message block `m` into the state `h` with the
+/// offset counters `t`, `f` marking the final block.
+pub fn encode_blake2f(rounds: u32, h: [u64; 8], m: [u64; 16], t: [u64; 2], f: bool) -> [u8; 213] {
+    let mut input = [0; 213];
+    input[..4].copy_from_slice(&rounds.to_be_bytes());
+    let words = h.iter().chain(&m).chain(&t);
+    for (chunk, word) in input[4..212].chunks_exact_mut(8).zip(words) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    input[212] = f as u8;
+    input
+}
+
+/// Decodes the output of `blake2f`, the compressed state.
+pub fn decode_blake2f(output: &[u8]) -> Option<[u64; 8]> {
+    if output.len() != 64 {
+        return None;
+    }
+    let mut h = [0; 8];
+    for (word, chunk) in h.iter_mut().zip(output.chunks_exact(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    Some(h)
+}
+
+/// Encodes the input of the point evaluation precompile, verifying the KZG proof that the blob of
+/// the versioned hash evaluates to `y` at `z`.
+pub fn encode_point_evaluation(
+    versioned_hash: B256,
+    z: B256,
+    y: B256,
+    commitment: &Bytes48,
+    proof: &Bytes48,
+) -> [u8; 192] {
+    let mut input = [0; 192];
+    input[..32].copy_from_slice(versioned_hash.as_slice());
+    input[32..64].copy_from_slice(z.as_slice());
+    input[64..96].copy_from_slice(y.as_slice());
+    input[96..144].copy_from_slice(commitment.as_slice());
+    input[144..].copy_from_slice(proof.as_slice());
+    input
+}
+
+/// Decodes the output of the point evaluation precompile, the number of field elements per blob
+/// and the modulus of the BLS field.
+pub fn decode_point_evaluation(output: &[u8]) -> Option<(U256, U256)> {
+    (output.len() == 64)
+        .then(|| (U256::from_be_slice(&output[..32]), U256::from_be_slice(&output[32..])))
+}
+
+/// Copies the input at the offset to the buffer, padding it with zeros.
+fn read_padded(input: &[u8], offset: usize, buf: &mut [u8]) {
+    let available = input.get(offset..).unwrap_or_default();
+    let len = available.len().min(buf.len());
+    buf[..len].copy_from_slice(&available[..len]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip4844::{BLS_MODULUS, FIELD_ELEMENTS_PER_BLOB};
+    use alloy_primitives::hex;
+
+    #[test]
+    fn prices_precompiles() {
+        assert_eq!(gas_cost(SHA256, &[0; 33]), Some(60 + 2 * 12));
+        assert_eq!(gas_cost(IDENTITY, &[]), Some(15));
+        assert_eq!(gas_cost(BN254_PAIRING, &[0; 384]), Some(45_000 + 2 * 34_000));
+        assert_eq!(gas_cost(BLAKE2F, &encode_blake2f(12, [0; 8], [0; 16], [0; 2], true)), Some(12));
+        assert_eq!(gas_cost(Address::ZERO, &[]), None);
+        assert!(PRECOMPILES.iter().all(|address| is_precompile(*address)));
+
+        // EIP-198 example: 3 ** (2 ** 256 - 2 ** 32 - 978) % (2 ** 256 - 2 ** 32 - 977)
+        let input = encode_modexp(
+            &[3],
+            &hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e"),
+            &hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"),
+        );
+        assert_eq!(modexp_gas(&input), 1360);
+        assert_eq!(modexp_gas(&encode_modexp(&[2], &[0; 64], &[5])), 200);
+        assert_eq!(modexp_gas(&hex!("ff")), u64::MAX);
+    }
+
+    #[test]
+    fn encodes_inputs() {
+        let input = encode_ecrecover(B256::repeat_byte(1), 28, U256::from(2), U256::from(3));
+        assert_eq!(input[63], 28);
+        assert_eq!(input[95], 2);
+        assert_eq!(input[127], 3);
+
+        let mut output = [0; 32];
+        output[12..].copy_from_slice(ECRECOVER.as_slice());
+        assert_eq!(decode_ecrecover(&output), Some(ECRECOVER));
+        assert_eq!(decode_ecrecover(&[]), None);
+
+        let g1 = G1Point { x: U256::from(1), y: U256::from(2) };
+        assert_eq!(G1Point::decode(&g1.encode()), Some(g1));
+        let pairing = encode_bn254_pairing(&[(g1, G2Point::default())]);
+        assert_eq!(pairing.len(), 192);
+        assert_eq!(decode_bn254_pairing(&U256::from(1).to_be_bytes::<32>()), Some(true));
+
+        let blake = encode_blake2f(1, [1; 8], [2; 16], [3; 2], true);
+        assert_eq!(&blake[..5], &[0, 0, 0, 1, 1]);
+        assert_eq!(blake[212], 1);
+        assert_eq!(decode_blake2f(&blake[4..68]), Some([1; 8]));
+
+        let mut output = [0; 64];
+        output[..32].copy_from_slice(&U256::from(FIELD_ELEMENTS_PER_BLOB).to_be_bytes::<32>());
+        output[32..].copy_from_slice(&BLS_MODULUS.to_be_bytes::<32>());
+        assert_eq!(
+            decode_point_evaluation(&output),
+            Some((U256::from(FIELD_ELEMENTS_PER_BLOB), BLS_MODULUS))
+        );
+    }
+}
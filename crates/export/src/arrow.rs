@@ -0,0 +1,126 @@
+//! Conversions into [Arrow](arrow_array) arrays and record batches.
+
+use crate::{Batch, Column, DataType, Field};
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, FixedSizeBinaryArray, RecordBatch, RecordBatchOptions,
+    UInt64Array, UInt8Array,
+};
+use arrow_schema::{ArrowError, Schema};
+use std::sync::Arc;
+
+impl From<DataType> for arrow_schema::DataType {
+    fn from(data_type: DataType) -> Self {
+        match data_type {
+            DataType::Boolean => Self::Boolean,
+            DataType::UInt8 => Self::UInt8,
+            DataType::UInt64 => Self::UInt64,
+            DataType::Binary => Self::Binary,
+            DataType::FixedSizeBinary(size) => {
+                Self::FixedSizeBinary(size.try_into().expect("size of fixed size binary too large"))
+            }
+        }
+    }
+}
+
+impl From<&Field> for arrow_schema::Field {
+    fn from(field: &Field) -> Self {
+        Self::new(field.name, field.data_type.into(), field.nullable)
+    }
+}
+
+/// Returns the Arrow schema of the fields.
+pub fn arrow_schema(fields: &[Field]) -> Schema {
+    Schema::new(fields.iter().map(arrow_schema::Field::from).collect::<Vec<_>>())
+}
+
+impl Column {
+    /// Converts the column into an Arrow array.
+    pub fn to_arrow(&self) -> Result<ArrayRef, ArrowError> {
+        Ok(match self {
+            Self::Boolean(values) => Arc::new(BooleanArray::from(values.clone())),
+            Self::UInt8(values) => Arc::new(UInt8Array::from(values.clone())),
+            Self::UInt64(values) => Arc::new(UInt64Array::from(values.clone())),
+            Self::Binary(values) => {
+                Arc::new(values.iter().map(Option::as_deref).collect::<BinaryArray>())
+            }
+            Self::FixedSizeBinary(size, values) => {
+                let size = (*size).try_into().map_err(|_| {
+                    ArrowError::InvalidArgumentError(format!("fixed size binary of {size} bytes"))
+                })?;
+                Arc::new(FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                    values.iter().map(Option::as_deref),
+                    size,
+                )?)
+            }
+        })
+    }
+}
+
+impl Batch {
+    /// Returns the Arrow schema of the batch.
+    pub fn arrow_schema(&self) -> Schema {
+        arrow_schema(self.schema())
+    }
+
+    /// Converts the batch into an Arrow record batch, with one array per column.
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let columns = self.columns().iter().map(Column::to_arrow).collect::<Result<_, _>>()?;
+        RecordBatch::try_new_with_options(
+            Arc::new(self.arrow_schema()),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(self.num_rows())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Row, RowWriter};
+    use alloy_primitives::U256;
+    use arrow_array::Array;
+
+    struct Pair(bool, Option<U256>, &'static [u8]);
+
+    impl Row for Pair {
+        const SCHEMA: &'static [Field] = &[
+            Field::required("a", DataType::Boolean),
+            Field::nullable("b", DataType::FixedSizeBinary(32)),
+            Field::required("c", DataType::Binary),
+        ];
+
+        fn write(&self, row: &mut RowWriter<'_>) {
+            row.bool(self.0);
+            row.u256(self.1);
+            row.binary(self.2);
+        }
+    }
+
+    #[test]
+    fn converts_batches() {
+        let batch =
+            Batch::from_rows(&[Pair(true, Some(U256::from(2)), b"ab"), Pair(false, None, b"")]);
+        let record_batch = batch.to_record_batch().unwrap();
+        assert_eq!(record_batch.num_rows(), 2);
+
+        let schema = record_batch.schema();
+        assert_eq!(schema.field(1).data_type(), &arrow_schema::DataType::FixedSizeBinary(32));
+        assert!(schema.field(1).is_nullable());
+        assert!(!schema.field(2).is_nullable());
+
+        let b = record_batch.column(1).as_any().downcast_ref::<FixedSizeBinaryArray>().unwrap();
+        assert_eq!(b.value(0), U256::from(2).to_be_bytes::<32>().as_slice());
+        assert!(b.is_null(1));
+
+        let c = record_batch.column(2).as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(c.value(0), b"ab");
+        assert_eq!(c.value(1), b"");
+    }
+
+    #[test]
+    fn converts_empty_batches() {
+        let record_batch = Batch::new(Pair::SCHEMA).to_record_batch().unwrap();
+        assert_eq!(record_batch.num_rows(), 0);
+        assert_eq!(record_batch.num_columns(), 3);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{DataType, Field};
+use alloy_primitives::U256;
+
+/// A type that is exported as a row of a table.
+pub trait Row {
+    /// The schema of the table.
+    const SCHEMA: &'static [Field];
+
+    /// Writes the values of the row, in the order of the columns of the schema.
+    fn write(&self, row: &mut RowWriter<'_>);
+}
+
+/// The values of a column, `None` for nulls.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// A [`DataType::Boolean`] column.
+    Boolean(Vec<Option<bool>>),
+    /// A [`DataType::UInt8`] column.
+    UInt8(Vec<Option<u8>>),
+    /// A [`DataType::UInt64`] column.
+    UInt64(Vec<Option<u64>>),
+    /// A [`DataType::Binary`] column.
+    Binary(Vec<Option<Vec<u8>>>),
+    /// A [`DataType::FixedSizeBinary`] column, whose values all have the size of the data type.
+    FixedSizeBinary(usize, Vec<Option<Vec<u8>>>),
+}
+
+impl Column {
+    /// Creates an empty column of the data type.
+    pub const fn new(data_type: DataType) -> Self {
+        match data_type {
+            DataType::Boolean => Self::Boolean(Vec::new()),
+            DataType::UInt8 => Self::UInt8(Vec::new()),
+            DataType::UInt64 => Self::UInt64(Vec::new()),
+            DataType::Binary => Self::Binary(Vec::new()),
+            DataType::FixedSizeBinary(size) => Self::FixedSizeBinary(size, Vec::new()),
+        }
+    }
+
+    /// Returns the data type of the column.
+    pub const fn data_type(&self) -> DataType {
+        match self {
+            Self::Boolean(_) => DataType::Boolean,
+            Self::UInt8(_) => DataType::UInt8,
+            Self::UInt64(_) => DataType::UInt64,
+            Self::Binary(_) => DataType::Binary,
+            Self::FixedSizeBinary(size, _) => DataType::FixedSizeBinary(*size),
+        }
+    }
+
+    /// Returns the number of values of the column.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Boolean(values) => values.len(),
+            Self::UInt8(values) => values.len(),
+            Self::UInt64(values) => values.len(),
+            Self::Binary(values) | Self::FixedSizeBinary(_, values) => values.len(),
+        }
+    }
+
+    /// Returns `true` if the column has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of nulls of the column.
+    pub fn null_count(&self) -> usize {
+        match self {
+            Self::Boolean(values) => values.iter().filter(|value| value.is_none()).count(),
+            Self::UInt8(values) => values.iter().filter(|value| value.is_none()).count(),
+            Self::UInt64(values) => values.iter().filter(|value| value.is_none()).count(),
+            Self::Binary(values) | Self::FixedSizeBinary(_, values) => {
+                values.iter().filter(|value| value.is_none()).count()
+            }
+        }
+    }
+}
+
+/// Writes the values of a [`Row`] into the columns of a [`Batch`].
+///
+/// Each method writes the value of the next column.
+///
+/// # Panics
+///
+/// The methods panic if the data type of the next column does not match, or if a null is written
+/// to a required column, as this is a bug of the [`Row`] implementation.
+#[derive(Debug)]
+pub struct RowWriter<'a> {
+    fields: &'static [Field],
+    columns: &'a mut [Column],
+    index: usize,
+}
+
+impl RowWriter<'_> {
+    fn next(&mut self, is_null: bool) -> (&Field, &mut Column) {
+        let field = &self.fields[self.index];
+        assert!(!is_null || field.nullable, "null written to required column {}", field.name);
+        let column = &mut self.columns[self.index];
+        self.index += 1;
+        (field, column)
+    }
+
+    /// Writes a [`DataType::Boolean`] value.
+    pub fn bool(&mut self, value: impl Into<Option<bool>>) {
+        let value = value.into();
+        match self.next(value.is_none()) {
+            (_, Column::Boolean(values)) => values.push(value),
+            (field, _) => mismatch(field, "Boolean"),
+        }
+    }
+
+    /// Writes a [`DataType::UInt8`] value.
+    pub fn u8(&mut self, value: impl Into<Option<u8>>) {
+        let value = value.into();
+        match self.next(value.is_none()) {
+            (_, Column::UInt8(values)) => values.push(value),
+            (field, _) => mismatch(field, "UInt8"),
+        }
+    }
+
+    /// Writes a [`DataType::UInt64`] value.
+    pub fn u64(&mut self, value: impl Into<Option<u64>>) {
+        let value = value.into();
+        match self.next(value.is_none()) {
+            (_, Column::UInt64(values)) => values.push(value),
+            (field, _) => mismatch(field, "UInt64"),
+        }
+    }
+
+    /// Writes a [`DataType::Binary`] value.
+    pub fn binary<'b>(&mut self, value: impl Into<Option<&'b [u8]>>) {
+        let value = value.into();
+        match self.next(value.is_none()) {
+            (_, Column::Binary(values)) => values.push(value.map(<[u8]>::to_vec)),
+            (field, _) => mismatch(field, "Binary"),
+        }
+    }
+
+    /// Writes a [`DataType::FixedSizeBinary`] value, e.g. an address or a hash.
+    pub fn fixed<'b>(&mut self, value: impl Into<Option<&'b [u8]>>) {
+        let value = value.into();
+        match self.next(value.is_none()) {
+            (field, Column::FixedSizeBinary(size, values)) => {
+                if let Some(value) = value {
+                    assert_eq!(value.len(), *size, "invalid size of column {}", field.name);
+                }
+                values.push(value.map(<[u8]>::to_vec));
+            }
+            (field, _) => mismatch(field, "FixedSizeBinary"),
+        }
+    }
+
+    /// Writes a `u128` as a big-endian [`DataType::FixedSizeBinary`] value of 16 bytes.
+    pub fn u128(&mut self, value: impl Into<Option<u128>>) {
+        let value = value.into().map(u128::to_be_bytes);
+        self.fixed(value.as_ref().map(<[u8; 16]>::as_slice));
+    }
+
+    /// Writes a [`U256`] as a big-endian [`DataType::FixedSizeBinary`] value of 32 bytes.
+    pub fn u256(&mut self, value: impl Into<Option<U256>>) {
+        let value = value.into().map(|value| value.to_be_bytes::<32>());
+        self.fixed(value.as_ref().map(<[u8; 32]>::as_slice));
+    }
+}
+
+#[track_caller]
+fn mismatch(field: &Field, written: &str) -> ! {
+    panic!("column {} is {}, not {written}", field.name, field.data_type)
+}
+
+/// A table of rows, stored as one [`Column`] per field of the schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Batch {
+    schema: &'static [Field],
+    columns: Vec<Column>,
+    num_rows: usize,
+}
+
+impl Batch {
+    /// Creates an empty batch with the schema.
+    pub fn new(schema: &'static [Field]) -> Self {
+        let columns = schema.iter().map(|field| Column::new(field.data_type)).collect();
+        Self { schema, columns, num_rows: 0 }
+    }
+
+    /// Creates a batch of the rows.
+    pub fn from_rows<'a, T: Row + 'a>(rows: impl IntoIterator<Item = &'a T>) -> Self {
+        let mut batch = Self::new(T::SCHEMA);
+        for row in rows {
+            batch.push(row);
+        }
+        batch
+    }
+
+    /// Appends the row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schema of the row is not the schema of the batch.
+    pub fn push<T: Row>(&mut self, row: &T) {
+        assert_eq!(T::SCHEMA, self.schema, "schema mismatch");
+        let mut writer = RowWriter { fields: self.schema, columns: &mut self.columns, index: 0 };
+        row.write(&mut writer);
+        assert_eq!(writer.index, self.schema.len(), "incomplete row");
+        self.num_rows += 1;
+    }
+
+    /// Returns the schema of the batch.
+    pub const fn schema(&self) -> &'static [Field] {
+        self.schema
+    }
+
+    /// Returns the columns, in the order of the schema.
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Returns the column of the field with the name.
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.schema.iter().position(|field| field.name == name).map(|index| &self.columns[index])
+    }
+
+    /// Consumes the batch, returning its columns.
+    pub fn into_columns(self) -> Vec<Column> {
+        self.columns
+    }
+
+    /// Returns the number of rows.
+    pub const fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns `true` if the batch has no rows.
+    pub const fn is_empty(&self) -> bool {
+        self.num_rows == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Pair(u64, Option<U256>);
+
+    impl Row for Pair {
+        const SCHEMA: &'static [Field] = &[
+            Field::required("a", DataType::UInt64),
+            Field::nullable("b", DataType::FixedSizeBinary(32)),
+        ];
+
+        fn write(&self, row: &mut RowWriter<'_>) {
+            row.u64(self.0);
+            row.u256(self.1);
+        }
+    }
+
+    #[test]
+    fn builds_batches() {
+        let batch = Batch::from_rows(&[Pair(1, Some(U256::from(2))), Pair(3, None)]);
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column("a"), Some(&Column::UInt64(vec![Some(1), Some(3)])));
+
+        let b = batch.column("b").unwrap();
+        assert_eq!(b.null_count(), 1);
+        let Column::FixedSizeBinary(32, values) = b else { panic!("{b:?}") };
+        assert_eq!(values[0].as_deref(), Some(U256::from(2).to_be_bytes::<32>().as_slice()));
+    }
+
+    #[test]
+    #[should_panic = "column a is Boolean, not UInt64"]
+    fn panics_on_mismatch() {
+        struct Invalid;
+
+        impl Row for Invalid {
+            const SCHEMA: &'static [Field] = &[Field::required("a", DataType::Boolean)];
+
+            fn write(&self, row: &mut RowWriter<'_>) {
+                row.u64(1);
+            }
+        }
+
+        Batch::new(Invalid::SCHEMA).push(&Invalid);
+    }
+}
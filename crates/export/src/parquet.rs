@@ -0,0 +1,107 @@
+//! Writing batches to [Parquet](parquet) files.
+
+use crate::{Batch, Field};
+use parquet::{arrow::ArrowWriter, errors::ParquetError, file::properties::WriterProperties};
+use std::{io::Write, sync::Arc};
+
+/// Writes [`Batch`]es of the same schema into a Parquet file.
+///
+/// Rows are buffered until a row group reaches the maximum size of the [`WriterProperties`]. The
+/// file is only complete once the writer is [closed](Self::close).
+pub struct ParquetWriter<W: Write + Send> {
+    schema: &'static [Field],
+    inner: ArrowWriter<W>,
+}
+
+impl<W: Write + Send> core::fmt::Debug for ParquetWriter<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ParquetWriter").field("schema", &self.schema).finish_non_exhaustive()
+    }
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    /// Creates a writer of batches with the schema, using the default [`WriterProperties`].
+    pub fn new(writer: W, schema: &'static [Field]) -> Result<Self, ParquetError> {
+        Self::with_properties(writer, schema, WriterProperties::default())
+    }
+
+    /// Creates a writer of batches with the schema, using the properties, e.g. to compress the
+    /// columns.
+    pub fn with_properties(
+        writer: W,
+        schema: &'static [Field],
+        properties: WriterProperties,
+    ) -> Result<Self, ParquetError> {
+        let arrow_schema = Arc::new(crate::arrow::arrow_schema(schema));
+        let inner = ArrowWriter::try_new(writer, arrow_schema, Some(properties))?;
+        Ok(Self { schema, inner })
+    }
+
+    /// Writes the batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schema of the batch is not the schema of the writer.
+    pub fn write(&mut self, batch: &Batch) -> Result<(), ParquetError> {
+        assert_eq!(batch.schema(), self.schema, "schema mismatch");
+        self.inner.write(&batch.to_record_batch()?)
+    }
+
+    /// Flushes the buffered rows and writes the footer of the file, returning the underlying
+    /// writer.
+    pub fn close(self) -> Result<W, ParquetError> {
+        self.inner.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Row;
+    use alloy_primitives::{address, b256, bytes, LogData};
+    use alloy_rpc_types_eth::Log;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::io::{Seek, SeekFrom};
+
+    fn log(block_number: u64) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: address!("4200000000000000000000000000000000000006"),
+                data: LogData::new_unchecked(
+                    vec![b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")],
+                    bytes!("01"),
+                ),
+            },
+            block_number: Some(block_number),
+            log_index: Some(2),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn writes_readable_files() {
+        let mut writer = ParquetWriter::new(tempfile::tempfile().unwrap(), Log::SCHEMA).unwrap();
+        writer.write(&Batch::from_rows(&[log(1), log(2)])).unwrap();
+        writer.write(&Batch::from_rows(&[log(3)])).unwrap();
+        writer.write(&Batch::new(Log::SCHEMA)).unwrap();
+        let mut file = writer.close().unwrap();
+        file.seek(SeekFrom::Start(0)).unwrap();
+
+        let read = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let expected = Batch::from_rows(&[log(1), log(2), log(3)]).to_record_batch().unwrap();
+        assert_eq!(read, [expected]);
+    }
+
+    #[test]
+    #[should_panic = "schema mismatch"]
+    fn panics_on_schema_mismatch() {
+        let mut writer =
+            ParquetWriter::new(Vec::new(), alloy_rpc_types_eth::Header::SCHEMA).unwrap();
+        writer.write(&Batch::from_rows(&[log(1)])).unwrap();
+    }
+}
@@ -0,0 +1,265 @@
+//! The [`Row`] implementations of the RPC types.
+
+use crate::{DataType, Field, Row, RowWriter};
+use alloy_consensus::ReceiptEnvelope;
+use alloy_rpc_types_eth::{Header, Log, Transaction, TransactionReceipt};
+
+const HASH: DataType = DataType::FixedSizeBinary(32);
+const ADDRESS: DataType = DataType::FixedSizeBinary(20);
+const BLOOM: DataType = DataType::FixedSizeBinary(256);
+const U128: DataType = DataType::FixedSizeBinary(16);
+const U256: DataType = DataType::FixedSizeBinary(32);
+
+/// Block headers, one row per block.
+impl Row for Header {
+    const SCHEMA: &'static [Field] = &[
+        Field::required("number", DataType::UInt64),
+        Field::required("hash", HASH),
+        Field::required("parent_hash", HASH),
+        Field::required("uncles_hash", HASH),
+        Field::required("miner", ADDRESS),
+        Field::required("state_root", HASH),
+        Field::required("transactions_root", HASH),
+        Field::required("receipts_root", HASH),
+        Field::required("logs_bloom", BLOOM),
+        Field::required("difficulty", U256),
+        Field::nullable("total_difficulty", U256),
+        Field::required("gas_limit", DataType::UInt64),
+        Field::required("gas_used", DataType::UInt64),
+        Field::required("timestamp", DataType::UInt64),
+        Field::required("extra_data", DataType::Binary),
+        Field::nullable("mix_hash", HASH),
+        Field::nullable("nonce", DataType::FixedSizeBinary(8)),
+        Field::nullable("base_fee_per_gas", DataType::UInt64),
+        Field::nullable("withdrawals_root", HASH),
+        Field::nullable("blob_gas_used", DataType::UInt64),
+        Field::nullable("excess_blob_gas", DataType::UInt64),
+        Field::nullable("parent_beacon_block_root", HASH),
+        Field::nullable("requests_root", HASH),
+    ];
+
+    fn write(&self, row: &mut RowWriter<'_>) {
+        row.u64(self.number);
+        row.fixed(self.hash.as_slice());
+        row.fixed(self.parent_hash.as_slice());
+        row.fixed(self.uncles_hash.as_slice());
+        row.fixed(self.miner.as_slice());
+        row.fixed(self.state_root.as_slice());
+        row.fixed(self.transactions_root.as_slice());
+        row.fixed(self.receipts_root.as_slice());
+        row.fixed(self.logs_bloom.as_slice());
+        row.u256(self.difficulty);
+        row.u256(self.total_difficulty);
+        row.u64(self.gas_limit);
+        row.u64(self.gas_used);
+        row.u64(self.timestamp);
+        row.binary(self.extra_data.as_ref());
+        row.fixed(self.mix_hash.as_ref().map(|hash| hash.as_slice()));
+        row.fixed(self.nonce.as_ref().map(|nonce| nonce.as_slice()));
+        row.u64(self.base_fee_per_gas);
+        row.fixed(self.withdrawals_root.as_ref().map(|root| root.as_slice()));
+        row.u64(self.blob_gas_used);
+        row.u64(self.excess_blob_gas);
+        row.fixed(self.parent_beacon_block_root.as_ref().map(|root| root.as_slice()));
+        row.fixed(self.requests_root.as_ref().map(|root| root.as_slice()));
+    }
+}
+
+/// Transactions, one row per transaction. Access, blob and authorization lists are not exported.
+impl Row for Transaction {
+    const SCHEMA: &'static [Field] = &[
+        Field::nullable("block_number", DataType::UInt64),
+        Field::nullable("block_hash", HASH),
+        Field::nullable("transaction_index", DataType::UInt64),
+        Field::required("hash", HASH),
+        Field::nullable("transaction_type", DataType::UInt8),
+        Field::nullable("chain_id", DataType::UInt64),
+        Field::required("nonce", DataType::UInt64),
+        Field::required("from", ADDRESS),
+        Field::nullable("to", ADDRESS),
+        Field::required("value", U256),
+        Field::required("input", DataType::Binary),
+        Field::required("gas", DataType::UInt64),
+        Field::nullable("gas_price", U128),
+        Field::nullable("max_fee_per_gas", U128),
+        Field::nullable("max_priority_fee_per_gas", U128),
+        Field::nullable("max_fee_per_blob_gas", U128),
+        Field::nullable("r", U256),
+        Field::nullable("s", U256),
+        Field::nullable("v", U256),
+        Field::nullable("y_parity", DataType::Boolean),
+    ];
+
+    fn write(&self, row: &mut RowWriter<'_>) {
+        row.u64(self.block_number);
+        row.fixed(self.block_hash.as_ref().map(|hash| hash.as_slice()));
+        row.u64(self.transaction_index);
+        row.fixed(self.hash.as_slice());
+        row.u8(self.transaction_type);
+        row.u64(self.chain_id);
+        row.u64(self.nonce);
+        row.fixed(self.from.as_slice());
+        row.fixed(self.to.as_ref().map(|to| to.as_slice()));
+        row.u256(self.value);
+        row.binary(self.input.as_ref());
+        row.u64(self.gas);
+        row.u128(self.gas_price);
+        row.u128(self.max_fee_per_gas);
+        row.u128(self.max_priority_fee_per_gas);
+        row.u128(self.max_fee_per_blob_gas);
+        row.u256(self.signature.map(|signature| signature.r));
+        row.u256(self.signature.map(|signature| signature.s));
+        row.u256(self.signature.map(|signature| signature.v));
+        row.bool(self.signature.and_then(|signature| signature.y_parity).map(|parity| parity.0));
+    }
+}
+
+/// Receipts, one row per transaction. The logs are exported separately, see the [`Log`] rows.
+impl Row for TransactionReceipt<ReceiptEnvelope<Log>> {
+    const SCHEMA: &'static [Field] = &[
+        Field::nullable("block_number", DataType::UInt64),
+        Field::nullable("block_hash", HASH),
+        Field::nullable("transaction_index", DataType::UInt64),
+        Field::required("transaction_hash", HASH),
+        Field::required("transaction_type", DataType::UInt8),
+        Field::required("status", DataType::Boolean),
+        Field::required("from", ADDRESS),
+        Field::nullable("to", ADDRESS),
+        Field::nullable("contract_address", ADDRESS),
+        Field::required("cumulative_gas_used", U128),
+        Field::required("gas_used", U128),
+        Field::required("effective_gas_price", U128),
+        Field::nullable("blob_gas_used", U128),
+        Field::nullable("blob_gas_price", U128),
+        Field::required("logs_bloom", BLOOM),
+        Field::required("log_count", DataType::UInt64),
+        Field::nullable("state_root", HASH),
+    ];
+
+    fn write(&self, row: &mut RowWriter<'_>) {
+        row.u64(self.block_number);
+        row.fixed(self.block_hash.as_ref().map(|hash| hash.as_slice()));
+        row.u64(self.transaction_index);
+        row.fixed(self.transaction_hash.as_slice());
+        row.u8(self.inner.tx_type() as u8);
+        row.bool(self.inner.status());
+        row.fixed(self.from.as_slice());
+        row.fixed(self.to.as_ref().map(|to| to.as_slice()));
+        row.fixed(self.contract_address.as_ref().map(|address| address.as_slice()));
+        row.u128(self.inner.cumulative_gas_used());
+        row.u128(self.gas_used);
+        row.u128(self.effective_gas_price);
+        row.u128(self.blob_gas_used);
+        row.u128(self.blob_gas_price);
+        row.fixed(self.inner.logs_bloom().as_slice());
+        row.u64(self.inner.logs().len() as u64);
+        row.fixed(self.state_root.as_ref().map(|root| root.as_slice()));
+    }
+}
+
+/// Logs, one row per log, with one column per topic.
+impl Row for Log {
+    const SCHEMA: &'static [Field] = &[
+        Field::nullable("block_number", DataType::UInt64),
+        Field::nullable("block_hash", HASH),
+        Field::nullable("block_timestamp", DataType::UInt64),
+        Field::nullable("transaction_index", DataType::UInt64),
+        Field::nullable("transaction_hash", HASH),
+        Field::nullable("log_index", DataType::UInt64),
+        Field::required("removed", DataType::Boolean),
+        Field::required("address", ADDRESS),
+        Field::nullable("topic0", HASH),
+        Field::nullable("topic1", HASH),
+        Field::nullable("topic2", HASH),
+        Field::nullable("topic3", HASH),
+        Field::required("data", DataType::Binary),
+    ];
+
+    fn write(&self, row: &mut RowWriter<'_>) {
+        row.u64(self.block_number);
+        row.fixed(self.block_hash.as_ref().map(|hash| hash.as_slice()));
+        row.u64(self.block_timestamp);
+        row.u64(self.transaction_index);
+        row.fixed(self.transaction_hash.as_ref().map(|hash| hash.as_slice()));
+        row.u64(self.log_index);
+        row.bool(self.removed);
+        row.fixed(self.address().as_slice());
+        let topics = self.topics();
+        for index in 0..4 {
+            row.fixed(topics.get(index).map(|topic| topic.as_slice()));
+        }
+        row.binary(self.data().data.as_ref());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Batch, Column, Row};
+    use alloy_consensus::{Receipt, ReceiptEnvelope, ReceiptWithBloom};
+    use alloy_primitives::{address, b256, bytes, Bloom, LogData};
+    use alloy_rpc_types_eth::{Header, Log, Transaction, TransactionReceipt};
+
+    fn assert_complete<T: Row>(rows: &[T]) {
+        let batch = Batch::from_rows(rows);
+        assert_eq!(batch.num_rows(), rows.len());
+        for (field, column) in batch.schema().iter().zip(batch.columns()) {
+            assert_eq!(column.data_type(), field.data_type, "{}", field.name);
+            assert_eq!(column.len(), rows.len(), "{}", field.name);
+        }
+    }
+
+    fn log() -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: address!("4200000000000000000000000000000000000006"),
+                data: LogData::new_unchecked(
+                    vec![b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")],
+                    bytes!("01"),
+                ),
+            },
+            block_number: Some(7),
+            log_index: Some(2),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn exports_logs() {
+        let batch = Batch::from_rows(&[log()]);
+        assert_eq!(batch.column("block_number"), Some(&Column::UInt64(vec![Some(7)])));
+        assert_eq!(batch.column("topic0").unwrap().null_count(), 0);
+        assert_eq!(batch.column("topic1").unwrap().null_count(), 1);
+        assert_eq!(batch.column("data"), Some(&Column::Binary(vec![Some(vec![1])])));
+    }
+
+    #[test]
+    fn exports_all_columns() {
+        assert_complete(&[Header::default(), Header { number: 1, ..Default::default() }]);
+        assert_complete(&[Transaction::default()]);
+        assert_complete(&[log()]);
+
+        let receipt = TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom::new(
+                Receipt { status: true.into(), cumulative_gas_used: 21_000, logs: vec![log()] },
+                Bloom::default(),
+            )),
+            transaction_hash: Default::default(),
+            transaction_index: Some(0),
+            block_hash: None,
+            block_number: Some(7),
+            gas_used: 21_000,
+            effective_gas_price: 1,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Default::default(),
+            to: None,
+            contract_address: None,
+            state_root: None,
+            authorization_list: None,
+        };
+        assert_complete(std::slice::from_ref(&receipt));
+        let batch = Batch::from_rows([&receipt]);
+        assert_eq!(batch.column("transaction_type"), Some(&Column::UInt8(vec![Some(2)])));
+        assert_eq!(batch.column("log_count"), Some(&Column::UInt64(vec![Some(1)])));
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+/// The data type of a column, see the crate documentation for the corresponding Arrow types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DataType {
+    /// A boolean.
+    Boolean,
+    /// An unsigned 8-bit integer.
+    UInt8,
+    /// An unsigned 64-bit integer.
+    UInt64,
+    /// Variable-length bytes.
+    Binary,
+    /// Bytes of the given length.
+    FixedSizeBinary(usize),
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Boolean => f.write_str("Boolean"),
+            Self::UInt8 => f.write_str("UInt8"),
+            Self::UInt64 => f.write_str("UInt64"),
+            Self::Binary => f.write_str("Binary"),
+            Self::FixedSizeBinary(size) => write!(f, "FixedSizeBinary({size})"),
+        }
+    }
+}
+
+/// A column of a schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Field {
+    /// The name of the column, in `snake_case`.
+    pub name: &'static str,
+    /// The data type of the column.
+    pub data_type: DataType,
+    /// Whether the column may contain nulls.
+    pub nullable: bool,
+}
+
+impl Field {
+    /// Creates a column without nulls.
+    pub const fn required(name: &'static str, data_type: DataType) -> Self {
+        Self { name, data_type, nullable: false }
+    }
+
+    /// Creates a column that may contain nulls.
+    pub const fn nullable(name: &'static str, data_type: DataType) -> Self {
+        Self { name, data_type, nullable: true }
+    }
+}
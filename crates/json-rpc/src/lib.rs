@@ -92,12 +92,14 @@ mod packet;
 pub use packet::{BorrowedResponsePacket, RequestPacket, ResponsePacket};
 
 mod request;
-pub use request::{PartiallySerializedRequest, Request, RequestMeta, SerializedRequest};
+pub use request::{
+    PartiallySerializedRequest, Request, RequestMeta, RequestPriority, SerializedRequest,
+};
 
 mod response;
 pub use response::{
-    BorrowedErrorPayload, BorrowedResponse, BorrowedResponsePayload, ErrorPayload, Response,
-    ResponsePayload,
+    extract_revert_data, is_revert_message, BorrowedErrorPayload, BorrowedResponse,
+    BorrowedResponsePayload, ErrorPayload, Response, ResponsePayload,
 };
 
 mod result;
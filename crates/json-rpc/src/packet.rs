@@ -1,11 +1,11 @@
-use crate::{ErrorPayload, Id, Response, SerializedRequest};
+use crate::{ErrorPayload, Id, RequestPriority, Response, SerializedRequest};
 use alloy_primitives::map::HashSet;
 use serde::{
     de::{self, Deserializer, MapAccess, SeqAccess, Visitor},
     Deserialize, Serialize,
 };
 use serde_json::value::RawValue;
-use std::{fmt, marker::PhantomData};
+use std::{fmt, marker::PhantomData, time::Instant};
 
 /// A [`RequestPacket`] is a [`SerializedRequest`] or a batch of serialized
 /// request.
@@ -70,6 +70,35 @@ impl RequestPacket {
         }
     }
 
+    /// Get the highest priority of the requests in the packet.
+    pub fn priority(&self) -> RequestPriority {
+        self.requests().iter().map(|req| req.meta().priority()).max().unwrap_or_default()
+    }
+
+    /// Get the earliest deadline of the requests in the packet, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.requests().iter().filter_map(|req| req.meta().deadline()).min()
+    }
+
+    /// Returns `true` if all requests in the packet may be sent more than once.
+    pub fn is_idempotent(&self) -> bool {
+        self.requests().iter().all(|req| req.meta().is_idempotent())
+    }
+
+    /// Get the correlation ID of the packet, which is the first correlation ID of its requests, if
+    /// any.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.requests().iter().find_map(|req| req.meta().correlation_id())
+    }
+
+    /// Get the requests in the packet.
+    pub fn requests(&self) -> &[SerializedRequest] {
+        match self {
+            Self::Single(single) => std::slice::from_ref(single),
+            Self::Batch(batch) => batch,
+        }
+    }
+
     /// Get the number of requests in the packet.
     pub fn len(&self) -> usize {
         match self {
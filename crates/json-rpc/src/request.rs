@@ -6,9 +6,25 @@ use serde::{
     Deserialize, Serialize,
 };
 use serde_json::value::RawValue;
-use std::{borrow::Cow, marker::PhantomData, mem::MaybeUninit};
+use std::{borrow::Cow, marker::PhantomData, mem::MaybeUninit, time::Instant};
 
-/// `RequestMeta` contains the [`Id`] and method name of a request.
+/// The scheduling priority of a request.
+///
+/// Transports ignore the priority. It is a hint for layers that queue or drop requests, e.g. rate
+/// limiters, which should serve higher priority requests first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestPriority {
+    /// Bulk traffic that may be delayed, e.g. historical backfills.
+    Low,
+    /// The default priority.
+    #[default]
+    Normal,
+    /// Latency-critical requests, e.g. transaction submission.
+    High,
+}
+
+/// `RequestMeta` contains the [`Id`] and method name of a request, and hints
+/// for the layers that handle it.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RequestMeta {
     /// The method name.
@@ -17,12 +33,89 @@ pub struct RequestMeta {
     pub id: Id,
     /// Whether the request is a subscription, other than `eth_subscribe`.
     is_subscription: bool,
+    /// The scheduling priority of the request.
+    priority: RequestPriority,
+    /// The time after which the response is no longer useful.
+    deadline: Option<Instant>,
+    /// Whether the request has been marked as safe or unsafe to send more than once.
+    idempotent: Option<bool>,
+    /// The ID correlating the request with the flow it is part of, if any.
+    correlation_id: Option<Cow<'static, str>>,
 }
 
 impl RequestMeta {
     /// Create a new `RequestMeta`.
     pub const fn new(method: Cow<'static, str>, id: Id) -> Self {
-        Self { method, id, is_subscription: false }
+        Self {
+            method,
+            id,
+            is_subscription: false,
+            priority: RequestPriority::Normal,
+            deadline: None,
+            idempotent: None,
+            correlation_id: None,
+        }
+    }
+
+    /// Returns the scheduling priority of the request.
+    pub const fn priority(&self) -> RequestPriority {
+        self.priority
+    }
+
+    /// Sets the scheduling priority of the request.
+    pub fn set_priority(&mut self, priority: RequestPriority) {
+        self.priority = priority;
+    }
+
+    /// Returns the time after which the response is no longer useful, if any.
+    ///
+    /// Layers should not delay or retry the request past the deadline.
+    pub const fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Sets the time after which the response is no longer useful.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Returns `true` if the deadline of the request has passed.
+    pub fn is_expired(&self) -> bool {
+        self.deadline.is_some_and(|deadline| deadline <= Instant::now())
+    }
+
+    /// Returns `true` if sending the request more than once has the same
+    /// effect as sending it once, so that layers may retry it or send it to
+    /// several backends.
+    ///
+    /// Unless set with [`set_idempotent`], this is `true` only for known
+    /// read-only methods, such as `eth_call`, `eth_estimateGas`, `eth_chainId`
+    /// and the `eth_get*` methods. Unknown methods, transaction submissions,
+    /// subscriptions, filters, whose IDs are local to a node, and the
+    /// `evm_*`/`anvil_*` test node methods are not idempotent.
+    ///
+    /// [`set_idempotent`]: Self::set_idempotent
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent.unwrap_or_else(|| !self.is_subscription() && is_read_only(&self.method))
+    }
+
+    /// Marks the request as safe or unsafe to send more than once.
+    pub fn set_idempotent(&mut self, idempotent: bool) {
+        self.idempotent = Some(idempotent);
+    }
+
+    /// Returns the ID correlating the request with the flow it is part of, if any.
+    ///
+    /// The correlation ID is not part of the JSON-RPC request. Transports that support it send it
+    /// out of band, e.g. as an HTTP header, and it is recorded in the tracing spans of the
+    /// request, to follow a request flow across services.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
+    /// Sets the ID correlating the request with the flow it is part of.
+    pub fn set_correlation_id(&mut self, correlation_id: Option<Cow<'static, str>>) {
+        self.correlation_id = correlation_id;
     }
 
     /// Returns `true` if the request is a subscription.
@@ -43,6 +136,38 @@ impl RequestMeta {
     }
 }
 
+/// Returns `true` if the method only reads the state of the node, without
+/// creating any node-local state such as filters.
+fn is_read_only(method: &str) -> bool {
+    if let Some(method) = method.strip_prefix("eth_get") {
+        // filter IDs are local to the node, and polling a filter consumes its changes
+        return !method.starts_with("Filter");
+    }
+    matches!(
+        method,
+        "eth_call"
+            | "eth_estimateGas"
+            | "eth_createAccessList"
+            | "eth_simulateV1"
+            | "eth_chainId"
+            | "eth_blockNumber"
+            | "eth_gasPrice"
+            | "eth_maxPriorityFeePerGas"
+            | "eth_blobBaseFee"
+            | "eth_feeHistory"
+            | "eth_syncing"
+            | "eth_protocolVersion"
+            | "net_version"
+            | "net_listening"
+            | "net_peerCount"
+            | "web3_clientVersion"
+            | "web3_sha3"
+            | "rpc_modules"
+    ) || ["debug_trace", "trace_", "ots_", "txpool_"]
+        .iter()
+        .any(|prefix| method.starts_with(prefix))
+}
+
 /// A JSON-RPC 2.0 request object.
 ///
 /// This is a generic type that can be used to represent any JSON-RPC request.
@@ -312,6 +437,11 @@ impl SerializedRequest {
         &self.meta
     }
 
+    /// Returns a mutable reference to the request metadata.
+    pub fn meta_mut(&mut self) -> &mut RequestMeta {
+        &mut self.meta
+    }
+
     /// Returns the request ID.
     pub const fn id(&self) -> &Id {
         &self.meta.id
@@ -406,4 +536,48 @@ mod test {
         test_inner(Request::<String>::new("test", Id::None, "test".to_string()));
         test_inner(Request::<Vec<u64>>::new("test", u64::MAX.into(), vec![1, 2, 3]));
     }
+
+    #[test]
+    fn request_meta_hints() {
+        let mut meta = RequestMeta::new("eth_call".into(), Id::Number(1));
+        assert_eq!(meta.priority(), RequestPriority::Normal);
+        assert!(meta.is_idempotent());
+        assert!(!meta.is_expired());
+
+        meta.set_priority(RequestPriority::High);
+        meta.set_deadline(Some(Instant::now()));
+        meta.set_idempotent(false);
+        assert_eq!(meta.priority(), RequestPriority::High);
+        assert!(meta.is_expired());
+        assert!(!meta.is_idempotent());
+
+        let meta = RequestMeta::new("eth_sendRawTransaction".into(), Id::Number(2));
+        assert!(!meta.is_idempotent());
+        assert!(RequestPriority::Low < RequestPriority::Normal);
+    }
+
+    #[test]
+    fn idempotent_methods() {
+        let idempotent =
+            |method: &'static str| RequestMeta::new(method.into(), Id::None).is_idempotent();
+        for method in
+            ["eth_call", "eth_getBalance", "eth_getLogs", "eth_chainId", "debug_traceTransaction"]
+        {
+            assert!(idempotent(method), "{method}");
+        }
+        for method in [
+            "eth_sendRawTransaction",
+            "eth_subscribe",
+            "eth_newFilter",
+            "eth_newBlockFilter",
+            "eth_newPendingTransactionFilter",
+            "eth_getFilterChanges",
+            "eth_uninstallFilter",
+            "evm_mine",
+            "anvil_setBalance",
+            "unknown_method",
+        ] {
+            assert!(!idempotent(method), "{method}");
+        }
+    }
 }
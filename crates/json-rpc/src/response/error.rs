@@ -4,17 +4,14 @@ use serde::{
     de::{DeserializeOwned, MapAccess, Visitor},
     Deserialize, Deserializer, Serialize,
 };
-use serde_json::{
-    value::{to_raw_value, RawValue},
-    Value,
-};
+use serde_json::value::{to_raw_value, RawValue};
 use std::{
     borrow::{Borrow, Cow},
     fmt,
     marker::PhantomData,
 };
 
-use crate::RpcObject;
+use crate::{extract_revert_data, RpcObject};
 
 const INTERNAL_ERROR: Cow<'static, str> = Cow::Borrowed("Internal error");
 
@@ -84,6 +81,13 @@ impl<E> ErrorPayload<E> {
     /// Analyzes the [ErrorPayload] and decides if the request should be
     /// retried based on the error code or the message.
     pub fn is_retry_err(&self) -> bool {
+        // this is commonly thrown by infura and is apparently a load balancer issue, see also <https://github.com/MetaMask/metamask-extension/issues/7234>
+        self.is_rate_limit_err() || self.message == "header not found"
+    }
+
+    /// Analyzes the [ErrorPayload] and decides if the request was rejected by the rate limit of
+    /// the endpoint, based on the error code or the message.
+    pub fn is_rate_limit_err(&self) -> bool {
         // alchemy throws it this way
         if self.code == 429 {
             return true;
@@ -112,8 +116,6 @@ impl<E> ErrorPayload<E> {
         }
 
         match self.message.as_ref() {
-            // this is commonly thrown by infura and is apparently a load balancer issue, see also <https://github.com/MetaMask/metamask-extension/issues/7234>
-            "header not found" => true,
             // also thrown by infura if out of budget for the day and ratelimited
             "daily request count exceeded, request rate limited" => true,
             msg => {
@@ -125,6 +127,12 @@ impl<E> ErrorPayload<E> {
             }
         }
     }
+
+    /// Analyzes the [ErrorPayload] and decides if the node has not yet seen the requested block,
+    /// e.g. because it is behind the other nodes of a load balanced endpoint.
+    pub fn is_node_behind_err(&self) -> bool {
+        matches!(self.message.as_ref(), "header not found" | "unknown block" | "block not found")
+    }
 }
 
 impl<T> From<T> for ErrorPayload<T>
@@ -153,18 +161,6 @@ where
     }
 }
 
-/// Recursively traverses the value, looking for hex data that it can extract.
-///
-/// Inspired by ethers-js logic:
-/// <https://github.com/ethers-io/ethers.js/blob/9f990c57f0486728902d4b8e049536f2bb3487ee/packages/providers/src.ts/json-rpc-provider.ts#L25-L53>
-fn spelunk_revert(value: &Value) -> Option<Bytes> {
-    match value {
-        Value::String(s) => s.parse().ok(),
-        Value::Object(o) => o.values().find_map(spelunk_revert),
-        _ => None,
-    }
-}
-
 impl<ErrData: fmt::Display> fmt::Display for ErrorPayload<ErrData> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -330,23 +326,16 @@ where
     }
 
     /// Attempt to extract revert data from the JsonRpcError be recursively
-    /// traversing the error's data field
+    /// traversing the error's data field, falling back to hex data in the
+    /// error message.
     ///
     /// This returns the first hex it finds in the data object, and its
     /// behavior may change with `serde_json` internal changes.
     ///
-    /// If no hex object is found, it will return an empty bytes IFF the error
-    /// is a revert
-    ///
-    /// Inspired by ethers-js logic:
-    /// <https://github.com/ethers-io/ethers.js/blob/9f990c57f0486728902d4b8e049536f2bb3487ee/packages/providers/src.ts/json-rpc-provider.ts#L25-L53>
+    /// The formats of the common node implementations are supported, see
+    /// [`extract_revert_data`] for details.
     pub fn as_revert_data(&self) -> Option<Bytes> {
-        if self.message.contains("revert") {
-            let value = Value::deserialize(self.data.as_ref()?.borrow()).ok()?;
-            spelunk_revert(&value)
-        } else {
-            None
-        }
+        extract_revert_data(&self.message, self.data.as_ref().map(Borrow::borrow))
     }
 
     /// Extracts revert data and tries decoding it into given custom errors set.
@@ -413,5 +402,13 @@ mod test {
             payload.as_decoded_error::<Errors::ErrorsErrors>(false).unwrap();
 
         assert_eq!(value.a, U256::from(1));
+
+        let json = r#"{"code":-32015,"message":"VM execution error.","data":"Reverted 0x810f00230000000000000000000000000000000000000000000000000000000000000001"}"#;
+        let payload: ErrorPayload = serde_json::from_str(json).unwrap();
+
+        let Errors::ErrorsErrors::SomeCustomError(value) =
+            payload.as_decoded_error::<Errors::ErrorsErrors>(false).unwrap();
+
+        assert_eq!(value.a, U256::from(1));
     }
 }
@@ -17,6 +17,9 @@ pub use error::{BorrowedErrorPayload, ErrorPayload};
 mod payload;
 pub use payload::{BorrowedResponsePayload, ResponsePayload};
 
+mod revert;
+pub use revert::{extract_revert_data, is_revert_message};
+
 /// A JSON-RPC 2.0 response object containing a [`ResponsePayload`].
 ///
 /// This object is used to represent a JSON-RPC 2.0 response. It may contain
@@ -0,0 +1,147 @@
+use alloy_primitives::Bytes;
+use serde::Deserialize;
+use serde_json::{value::RawValue, Value};
+
+/// The prefix nethermind puts in front of the revert data in the error's
+/// `data` field, e.g. `Reverted 0x08c379a0...`.
+const NETHERMIND_REVERTED_PREFIX: &str = "reverted ";
+
+/// Lowercase fragments of error messages that report a reverted call, for all
+/// node implementations.
+///
+/// Other crates match revert messages with [`is_revert_message`] instead of
+/// keeping their own lists, so messages of new implementations are added here.
+const REVERT_MESSAGES: &[&str] = &["revert", "vm execution error"];
+
+/// Returns `true` if the error message reports a reverted call.
+///
+/// This matches the messages of the common node implementations, e.g.
+/// `execution reverted` (geth, reth, erigon, besu) and `VM execution error.`
+/// (nethermind).
+pub fn is_revert_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    REVERT_MESSAGES.iter().any(|fragment| message.contains(fragment))
+}
+
+/// Extracts the revert data from the `message` and `data` of an error,
+/// normalizing the formats used by different node implementations:
+///
+/// - hex data in the `data` field, e.g. `0x08c379a0...` (geth, reth, erigon, besu).
+/// - hex data following a prefix in the `data` field, e.g. `Reverted 0x08c379a0...` (nethermind).
+/// - hex data nested in an object in the `data` field, e.g. `{ "data": "0x08c379a0..." }` (hardhat,
+///   some gateways).
+/// - ABI-encoded hex data in the error message, e.g. `execution reverted: 0x08c379a0...` (some
+///   gateways).
+///
+/// Returns `None` if the error is not a revert, or if no revert data is
+/// found.
+pub fn extract_revert_data(message: &str, data: Option<&RawValue>) -> Option<Bytes> {
+    let data = data.and_then(|data| Value::deserialize(data).ok());
+
+    // the nethermind prefix identifies a revert, regardless of the message
+    if let Some(Value::String(s)) = &data {
+        if let Some(bytes) = strip_reverted_prefix(s) {
+            return Some(bytes);
+        }
+    }
+
+    if !is_revert_message(message) {
+        return None;
+    }
+    data.as_ref().and_then(spelunk_revert).or_else(|| revert_data_in_message(message))
+}
+
+/// Recursively traverses the value, looking for hex data that it can extract.
+///
+/// Inspired by ethers-js logic:
+/// <https://github.com/ethers-io/ethers.js/blob/9f990c57f0486728902d4b8e049536f2bb3487ee/packages/providers/src.ts/json-rpc-provider.ts#L25-L53>
+fn spelunk_revert(value: &Value) -> Option<Bytes> {
+    match value {
+        Value::String(s) => s.parse().ok().or_else(|| strip_reverted_prefix(s)),
+        Value::Object(o) => o.values().find_map(spelunk_revert),
+        _ => None,
+    }
+}
+
+/// Parses hex data following the nethermind `Reverted` prefix.
+fn strip_reverted_prefix(s: &str) -> Option<Bytes> {
+    let s = s.trim_start();
+    let prefix = s.get(..NETHERMIND_REVERTED_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(NETHERMIND_REVERTED_PREFIX) {
+        return None;
+    }
+    s[prefix.len()..].trim().parse().ok()
+}
+
+/// Finds ABI-encoded revert data in an error message.
+///
+/// Only hex words made of a 4-byte selector followed by 32-byte words are
+/// considered, so that addresses and hashes in the message are skipped.
+fn revert_data_in_message(message: &str) -> Option<Bytes> {
+    message
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| word.starts_with("0x"))
+        .filter_map(|word| word.parse::<Bytes>().ok())
+        .find(|bytes| bytes.len() >= 4 && (bytes.len() - 4) % 32 == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::bytes;
+    use serde_json::value::to_raw_value;
+
+    const REVERT: &str =
+        "0x810f00230000000000000000000000000000000000000000000000000000000000000001";
+
+    fn extract(message: &str, data: Option<Value>) -> Option<Bytes> {
+        let data = data.map(|data| to_raw_value(&data).unwrap());
+        extract_revert_data(message, data.as_deref())
+    }
+
+    #[test]
+    fn vendor_formats() {
+        let expected: Bytes = REVERT.parse().unwrap();
+
+        // geth
+        let data = Value::String(REVERT.into());
+        assert_eq!(extract("execution reverted", Some(data)).as_ref(), Some(&expected));
+
+        // nethermind
+        let data = Value::String(format!("Reverted {REVERT}"));
+        assert_eq!(extract("VM execution error.", Some(data)).as_ref(), Some(&expected));
+
+        // nested object
+        let data = serde_json::json!({ "message": "reverted", "data": REVERT });
+        assert_eq!(extract("Execution reverted", Some(data)).as_ref(), Some(&expected));
+
+        // gateway message
+        let message = format!("execution reverted: {REVERT}");
+        assert_eq!(extract(&message, None).as_ref(), Some(&expected));
+    }
+
+    #[test]
+    fn revert_messages() {
+        assert!(is_revert_message("execution reverted: not owner"));
+        assert!(is_revert_message("Execution reverted"));
+        assert!(is_revert_message("VM execution error."));
+        assert!(!is_revert_message("out of gas"));
+        assert!(!is_revert_message("invalid opcode: INVALID"));
+    }
+
+    #[test]
+    fn non_revert_errors() {
+        let data = Value::String(REVERT.into());
+        assert_eq!(extract("insufficient funds for gas * price + value", Some(data)), None);
+
+        // addresses in messages are not revert data
+        let message = "execution reverted: 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+        assert_eq!(extract(message, None), None);
+
+        assert_eq!(
+            extract("execution reverted", Some(Value::String("0x".into()))),
+            Some(bytes!(""))
+        );
+        assert_eq!(extract("execution reverted", None), None);
+    }
+}
@@ -2,7 +2,7 @@ use alloy_primitives::B256;
 use serde::{Deserialize, Serialize};
 
 use alloc::{vec, vec::Vec};
-use core::slice;
+use core::{fmt, slice};
 
 use crate::TransactionResponse;
 
@@ -100,13 +100,45 @@ impl<T> BlockTransactions<T> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns the number of transactions, or `None` for an uncle response, whose transactions
+    /// are not included.
+    #[inline]
+    pub fn len_hint(&self) -> Option<usize> {
+        match self {
+            Self::Uncle => None,
+            _ => Some(self.len()),
+        }
+    }
+
+    /// Maps the full transactions with the given function, keeping hashes and uncle responses.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> BlockTransactions<U> {
+        match self {
+            Self::Full(txs) => BlockTransactions::Full(txs.into_iter().map(f).collect()),
+            Self::Hashes(hashes) => BlockTransactions::Hashes(hashes),
+            Self::Uncle => BlockTransactions::Uncle,
+        }
+    }
+
+    /// Converts `self` into the full transactions.
+    ///
+    /// Returns an error if the block only contains hashes or is an uncle response.
+    pub fn try_into_full(self) -> Result<Vec<T>, BlockTransactionsError> {
+        match self {
+            Self::Full(txs) => Ok(txs),
+            Self::Hashes(_) => Err(BlockTransactionsError::Hashes),
+            Self::Uncle => Err(BlockTransactionsError::Uncle),
+        }
+    }
 }
 
 impl<T: TransactionResponse> BlockTransactions<T> {
     /// Converts `self` into `Hashes`.
+    ///
+    /// Uncle responses are left as they are.
     #[inline]
     pub fn convert_to_hashes(&mut self) {
-        if !self.is_hashes() {
+        if self.is_full() {
             *self = Self::Hashes(self.hashes().collect());
         }
     }
@@ -126,12 +158,57 @@ impl<T: TransactionResponse> BlockTransactions<T> {
     }
 
     /// Returns an iterator over references to the transaction hashes.
+    #[doc(alias = "iter_hashes")]
     #[inline]
     pub fn hashes(&self) -> BlockTransactionHashes<'_, T> {
         BlockTransactionHashes::new(self)
     }
+
+    /// Returns the full transaction with the given hash.
+    ///
+    /// Returns `None` if the block does not contain it, or only contains hashes.
+    pub fn get_by_hash(&self, hash: B256) -> Option<&T> {
+        self.txns().find(|tx| tx.tx_hash() == hash)
+    }
+
+    /// Returns the index of the transaction with the given hash in the block.
+    pub fn position(&self, hash: B256) -> Option<usize> {
+        self.hashes().position(|tx_hash| tx_hash == hash)
+    }
+
+    /// Converts `self` into the transaction hashes.
+    ///
+    /// Returns an error if the block is an uncle response, whose transactions are not included.
+    pub fn try_into_hashes(self) -> Result<Vec<B256>, BlockTransactionsError> {
+        match self {
+            Self::Full(txs) => Ok(txs.iter().map(|tx| tx.tx_hash()).collect()),
+            Self::Hashes(hashes) => Ok(hashes),
+            Self::Uncle => Err(BlockTransactionsError::Uncle),
+        }
+    }
 }
 
+/// Error returned when [`BlockTransactions`] do not contain the requested transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockTransactionsError {
+    /// The block only contains the transaction hashes.
+    Hashes,
+    /// The block is an uncle response, without transactions.
+    Uncle,
+}
+
+impl fmt::Display for BlockTransactionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hashes => f.write_str("block contains only transaction hashes"),
+            Self::Uncle => f.write_str("uncle block does not contain transactions"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlockTransactionsError {}
+
 impl<T> From<Vec<B256>> for BlockTransactions<T> {
     fn from(hashes: Vec<B256>) -> Self {
         Self::Hashes(hashes)
@@ -259,4 +336,20 @@ mod tests {
         let full = false;
         assert_eq!(BlockTransactionsKind::Hashes, full.into());
     }
+
+    #[test]
+    fn test_uncle_conversions() {
+        let uncle = BlockTransactions::<()>::Uncle;
+        assert_eq!(uncle.len_hint(), None);
+        assert_eq!(uncle.clone().map(|_| 1u8), BlockTransactions::Uncle);
+        assert_eq!(uncle.try_into_full(), Err(BlockTransactionsError::Uncle));
+
+        let hashes = BlockTransactions::<()>::Hashes(vec![B256::ZERO]);
+        assert_eq!(hashes.len_hint(), Some(1));
+        assert_eq!(hashes.try_into_full(), Err(BlockTransactionsError::Hashes));
+
+        let full = BlockTransactions::Full(vec![1, 2]).map(|tx| tx * 2);
+        assert_eq!(full.len_hint(), Some(2));
+        assert_eq!(full.try_into_full(), Ok(vec![2, 4]));
+    }
 }
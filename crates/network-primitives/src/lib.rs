@@ -12,5 +12,13 @@ extern crate alloc;
 mod traits;
 pub use traits::{BlockResponse, HeaderResponse, ReceiptResponse, TransactionResponse};
 
+mod receipt;
+pub use receipt::ReceiptStatus;
+
+mod units;
+pub use units::{GasUnits, Gwei, Wei, WeiPerGas};
+
 mod block;
-pub use block::{BlockTransactionHashes, BlockTransactions, BlockTransactionsKind};
+pub use block::{
+    BlockTransactionHashes, BlockTransactions, BlockTransactionsError, BlockTransactionsKind,
+};
@@ -0,0 +1,66 @@
+use alloy_primitives::B256;
+
+/// The status of a transaction, as reported by its receipt.
+///
+/// Receipts of transactions before [EIP-658] (Byzantium) do not include a status code, but the
+/// post-transaction state root, so their status is not knowable from the receipt alone.
+///
+/// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReceiptStatus {
+    /// The transaction succeeded.
+    Success,
+    /// The transaction failed.
+    Failure,
+    /// The status of a pre-[EIP-658] transaction is unknown, the receipt only contains the
+    /// post-transaction state root.
+    ///
+    /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+    Unknown(B256),
+}
+
+impl ReceiptStatus {
+    /// Returns `true` if the transaction is known to have succeeded.
+    pub const fn is_success(&self) -> bool {
+        matches!(self, Self::Success)
+    }
+
+    /// Returns `true` if the transaction is known to have failed.
+    pub const fn is_failure(&self) -> bool {
+        matches!(self, Self::Failure)
+    }
+
+    /// Returns `true` if the status of the transaction is unknown.
+    pub const fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown(_))
+    }
+
+    /// Returns the status of the transaction, or `None` if it is unknown.
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Success => Some(true),
+            Self::Failure => Some(false),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// Returns the post-transaction state root of a pre-[EIP-658] receipt.
+    ///
+    /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+    pub const fn state_root(&self) -> Option<B256> {
+        match self {
+            Self::Unknown(root) => Some(*root),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for ReceiptStatus {
+    fn from(status: bool) -> Self {
+        if status {
+            Self::Success
+        } else {
+            Self::Failure
+        }
+    }
+}
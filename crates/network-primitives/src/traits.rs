@@ -1,9 +1,14 @@
 use alloc::vec::Vec;
-use alloy_eips::{eip2930::AccessList, eip7702::SignedAuthorization};
-use alloy_primitives::{Address, BlockHash, Bytes, ChainId, TxHash, B256, U256};
+use alloy_eips::{
+    eip2930::AccessList,
+    eip4844::{BlobParams, DATA_GAS_PER_BLOB},
+    eip4895::Withdrawal,
+    eip7702::SignedAuthorization,
+};
+use alloy_primitives::{Address, BlockHash, Bloom, Bytes, ChainId, TxHash, B256, U256};
 use alloy_serde::WithOtherFields;
 
-use crate::BlockTransactions;
+use crate::{BlockTransactions, GasUnits, ReceiptStatus, Wei, WeiPerGas};
 
 /// Receipt JSON-RPC response.
 pub trait ReceiptResponse {
@@ -17,9 +22,22 @@ pub trait ReceiptResponse {
     /// Caution must be taken when using this method for deep-historical
     /// receipts, as it may not accurately reflect the status of the
     /// transaction. The transaction status is not knowable from the receipt
-    /// for transactions before [EIP-658].
+    /// for transactions before [EIP-658]. Use [`ReceiptResponse::receipt_status`] to distinguish
+    /// these receipts.
     fn status(&self) -> bool;
 
+    /// Status of the transaction, distinguishing pre-[EIP-658] receipts, which carry the
+    /// post-transaction state root instead of a status.
+    ///
+    /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+    fn receipt_status(&self) -> ReceiptStatus {
+        match self.state_root() {
+            Some(root) => ReceiptStatus::Unknown(root),
+            None if self.status() => ReceiptStatus::Success,
+            None => ReceiptStatus::Failure,
+        }
+    }
+
     /// Hash of the block this transaction was included within.
     fn block_hash(&self) -> Option<BlockHash>;
 
@@ -56,6 +74,64 @@ pub trait ReceiptResponse {
     /// Returns the cumulative gas used at this receipt.
     fn cumulative_gas_used(&self) -> u128;
 
+    /// Returns the gas used by this transaction alone as a `u64`, or `None` if it does not fit.
+    fn gas_used_u64(&self) -> Option<u64> {
+        u64::try_from(self.gas_used()).ok()
+    }
+
+    /// Returns the cumulative gas used at this receipt as a `u64`, or `None` if it does not fit.
+    fn cumulative_gas_used_u64(&self) -> Option<u64> {
+        u64::try_from(self.cumulative_gas_used()).ok()
+    }
+
+    /// Returns the blob gas used by the eip-4844 transaction as a `u64`.
+    ///
+    /// Returns `None` if the transaction is not an eip-4844 transaction, or if the blob gas used
+    /// does not fit.
+    fn blob_gas_used_u64(&self) -> Option<u64> {
+        self.blob_gas_used().and_then(|gas| u64::try_from(gas).ok())
+    }
+
+    /// Returns the total fee paid by this transaction, including the blob fee.
+    ///
+    /// The fee is computed as [`U256`], so it cannot overflow even for gas values above `u64`.
+    fn gas_cost(&self) -> U256 {
+        let fee = U256::from(self.gas_used()) * U256::from(self.effective_gas_price());
+        let blob_fee = U256::from(self.blob_gas_used().unwrap_or_default())
+            * U256::from(self.blob_gas_price().unwrap_or_default());
+        fee.saturating_add(blob_fee)
+    }
+
+    /// Returns the [`gas_used`](Self::gas_used) as [`GasUnits`].
+    fn gas_used_units(&self) -> GasUnits {
+        GasUnits(self.gas_used())
+    }
+
+    /// Returns the [`cumulative_gas_used`](Self::cumulative_gas_used) as [`GasUnits`].
+    fn cumulative_gas_used_units(&self) -> GasUnits {
+        GasUnits(self.cumulative_gas_used())
+    }
+
+    /// Returns the [`blob_gas_used`](Self::blob_gas_used) as [`GasUnits`].
+    fn blob_gas_used_units(&self) -> Option<GasUnits> {
+        self.blob_gas_used().map(GasUnits)
+    }
+
+    /// Returns the [`effective_gas_price`](Self::effective_gas_price) as [`WeiPerGas`].
+    fn effective_gas_price_wei(&self) -> WeiPerGas {
+        WeiPerGas(self.effective_gas_price())
+    }
+
+    /// Returns the [`blob_gas_price`](Self::blob_gas_price) as [`WeiPerGas`].
+    fn blob_gas_price_wei(&self) -> Option<WeiPerGas> {
+        self.blob_gas_price().map(WeiPerGas)
+    }
+
+    /// Returns the [`gas_cost`](Self::gas_cost) as [`Wei`].
+    fn gas_cost_wei(&self) -> Wei {
+        Wei(self.gas_cost())
+    }
+
     /// The post-transaction state root (pre Byzantium)
     ///
     /// EIP98 makes this field optional.
@@ -97,6 +173,11 @@ pub trait TransactionResponse {
     /// Gas limit
     fn gas(&self) -> u64;
 
+    /// Gas limit, widened to `u128` for comparisons against receipt gas values.
+    fn gas_u128(&self) -> u128 {
+        self.gas() as u128
+    }
+
     /// Max BaseFeePerGas the user is willing to pay
     fn max_fee_per_gas(&self) -> Option<u128>;
 
@@ -127,6 +208,57 @@ pub trait TransactionResponse {
 
     /// The signed authorization list
     fn authorization_list(&self) -> Option<Vec<SignedAuthorization>>;
+
+    /// Returns the maximum fee this transaction can pay, including the blob fee.
+    ///
+    /// This is the gas limit times the max fee per gas, or the gas price for legacy transactions,
+    /// plus the blob gas of all blobs times the max fee per blob gas. The fee is computed as
+    /// [`U256`], so it cannot overflow.
+    fn max_gas_cost(&self) -> U256 {
+        let fee_per_gas = self.max_fee_per_gas().or_else(|| self.gas_price()).unwrap_or_default();
+        let fee = U256::from(self.gas()) * U256::from(fee_per_gas);
+        let blob_gas = self.blob_versioned_hashes().map_or(0, |hashes| hashes.len() as u64)
+            * DATA_GAS_PER_BLOB;
+        let blob_fee =
+            U256::from(blob_gas) * U256::from(self.max_fee_per_blob_gas().unwrap_or_default());
+        fee.saturating_add(blob_fee)
+    }
+
+    /// Returns the [`gas`](Self::gas) limit as [`GasUnits`].
+    fn gas_units(&self) -> GasUnits {
+        GasUnits::from(self.gas())
+    }
+
+    /// Returns the transferred [`value`](Self::value) as [`Wei`].
+    fn value_wei(&self) -> Wei {
+        Wei(self.value())
+    }
+
+    /// Returns the [`gas_price`](Self::gas_price) as [`WeiPerGas`].
+    fn gas_price_wei(&self) -> Option<WeiPerGas> {
+        self.gas_price().map(WeiPerGas)
+    }
+
+    /// Returns the [`max_fee_per_gas`](Self::max_fee_per_gas) as [`WeiPerGas`].
+    fn max_fee_per_gas_wei(&self) -> Option<WeiPerGas> {
+        self.max_fee_per_gas().map(WeiPerGas)
+    }
+
+    /// Returns the [`max_priority_fee_per_gas`](Self::max_priority_fee_per_gas) as
+    /// [`WeiPerGas`].
+    fn max_priority_fee_per_gas_wei(&self) -> Option<WeiPerGas> {
+        self.max_priority_fee_per_gas().map(WeiPerGas)
+    }
+
+    /// Returns the [`max_fee_per_blob_gas`](Self::max_fee_per_blob_gas) as [`WeiPerGas`].
+    fn max_fee_per_blob_gas_wei(&self) -> Option<WeiPerGas> {
+        self.max_fee_per_blob_gas().map(WeiPerGas)
+    }
+
+    /// Returns the [`max_gas_cost`](Self::max_gas_cost) as [`Wei`].
+    fn max_gas_cost_wei(&self) -> Wei {
+        Wei(self.max_gas_cost())
+    }
 }
 
 /// Header JSON-RPC response.
@@ -134,6 +266,14 @@ pub trait HeaderResponse {
     /// Block hash
     fn hash(&self) -> BlockHash;
 
+    /// Hash of the parent block
+    ///
+    /// Defaults to [`BlockHash::ZERO`] for headers that don't expose it. Such headers can't be
+    /// linked to their parent, e.g. to detect reorgs.
+    fn parent_hash(&self) -> BlockHash {
+        BlockHash::ZERO
+    }
+
     /// Block number
     fn number(&self) -> u64;
 
@@ -143,12 +283,68 @@ pub trait HeaderResponse {
     /// Extra data
     fn extra_data(&self) -> &Bytes;
 
+    /// Root hash of the transactions trie
+    ///
+    /// Defaults to [`B256::ZERO`] for headers that don't expose it, which fails proof
+    /// verification.
+    fn transactions_root(&self) -> B256 {
+        B256::ZERO
+    }
+
+    /// Root hash of the receipts trie
+    ///
+    /// Defaults to [`B256::ZERO`] for headers that don't expose it, which fails proof
+    /// verification.
+    fn receipts_root(&self) -> B256 {
+        B256::ZERO
+    }
+
+    /// Bloom filter of the logs of the block
+    ///
+    /// Defaults to [`Bloom::ZERO`] for headers that don't expose it.
+    fn logs_bloom(&self) -> Bloom {
+        Bloom::ZERO
+    }
+
+    /// Gas used by the transactions of the block
+    ///
+    /// Defaults to zero for headers that don't expose it.
+    fn gas_used(&self) -> u64 {
+        0
+    }
+
+    /// Root hash of the withdrawals trie (If EIP-4895 is supported)
+    fn withdrawals_root(&self) -> Option<B256> {
+        None
+    }
+
+    /// Blob gas used by the transactions of the block (If EIP-4844 is supported)
+    fn blob_gas_used(&self) -> Option<u64> {
+        None
+    }
+
+    /// Excess blob gas of the block (If EIP-4844 is supported)
+    fn excess_blob_gas(&self) -> Option<u64> {
+        None
+    }
+
     /// Base fee per unit of gas (If EIP-1559 is supported)
     fn base_fee_per_gas(&self) -> Option<u64>;
 
     /// Blob fee for the next block (if EIP-4844 is supported)
     fn next_block_blob_fee(&self) -> Option<u128>;
 
+    /// Blob fee for the next block (if EIP-4844 is supported), computed with the blob parameters
+    /// of the fork of the next block.
+    ///
+    /// Defaults to computing the fee from the [`excess_blob_gas`](Self::excess_blob_gas) and
+    /// [`blob_gas_used`](Self::blob_gas_used) of the header.
+    fn next_block_blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        let excess_blob_gas =
+            blob_params.next_block_excess_blob_gas(self.excess_blob_gas()?, self.blob_gas_used()?);
+        Some(blob_params.calc_blob_fee(excess_blob_gas))
+    }
+
     /// Coinbase/Miner of the block
     fn coinbase(&self) -> Address;
 
@@ -172,6 +368,21 @@ pub trait HeaderResponse {
     ///
     /// Unused after the Paris (AKA the merge) upgrade, and replaced by `prevrandao`.
     fn difficulty(&self) -> U256;
+
+    /// Returns the [`gas_used`](Self::gas_used) as [`GasUnits`].
+    fn gas_used_units(&self) -> GasUnits {
+        GasUnits::from(self.gas_used())
+    }
+
+    /// Returns the [`gas_limit`](Self::gas_limit) as [`GasUnits`].
+    fn gas_limit_units(&self) -> GasUnits {
+        GasUnits::from(self.gas_limit())
+    }
+
+    /// Returns the [`base_fee_per_gas`](Self::base_fee_per_gas) as [`WeiPerGas`].
+    fn base_fee_per_gas_wei(&self) -> Option<WeiPerGas> {
+        self.base_fee_per_gas().map(|fee| WeiPerGas(fee as u128))
+    }
 }
 
 /// Block JSON-RPC response.
@@ -190,6 +401,11 @@ pub trait BlockResponse {
     /// Mutable reference to block transactions
     fn transactions_mut(&mut self) -> &mut BlockTransactions<Self::Transaction>;
 
+    /// Block withdrawals (If EIP-4895 is supported)
+    fn withdrawals(&self) -> Option<&[Withdrawal]> {
+        None
+    }
+
     /// Returns the `other` field from `WithOtherFields` type.
     fn other_fields(&self) -> Option<&alloy_serde::OtherFields> {
         None
@@ -289,6 +505,10 @@ impl<T: ReceiptResponse> ReceiptResponse for WithOtherFields<T> {
         self.inner.status()
     }
 
+    fn receipt_status(&self) -> ReceiptStatus {
+        self.inner.receipt_status()
+    }
+
     fn block_hash(&self) -> Option<BlockHash> {
         self.inner.block_hash()
     }
@@ -358,6 +578,10 @@ impl<T: BlockResponse> BlockResponse for WithOtherFields<T> {
         self.inner.transactions_mut()
     }
 
+    fn withdrawals(&self) -> Option<&[Withdrawal]> {
+        self.inner.withdrawals()
+    }
+
     fn other_fields(&self) -> Option<&alloy_serde::OtherFields> {
         Some(&self.other)
     }
@@ -368,6 +592,10 @@ impl<T: HeaderResponse> HeaderResponse for WithOtherFields<T> {
         self.inner.hash()
     }
 
+    fn parent_hash(&self) -> BlockHash {
+        self.inner.parent_hash()
+    }
+
     fn number(&self) -> u64 {
         self.inner.number()
     }
@@ -380,6 +608,34 @@ impl<T: HeaderResponse> HeaderResponse for WithOtherFields<T> {
         self.inner.extra_data()
     }
 
+    fn transactions_root(&self) -> B256 {
+        self.inner.transactions_root()
+    }
+
+    fn receipts_root(&self) -> B256 {
+        self.inner.receipts_root()
+    }
+
+    fn logs_bloom(&self) -> Bloom {
+        self.inner.logs_bloom()
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.inner.gas_used()
+    }
+
+    fn withdrawals_root(&self) -> Option<B256> {
+        self.inner.withdrawals_root()
+    }
+
+    fn blob_gas_used(&self) -> Option<u64> {
+        self.inner.blob_gas_used()
+    }
+
+    fn excess_blob_gas(&self) -> Option<u64> {
+        self.inner.excess_blob_gas()
+    }
+
     fn base_fee_per_gas(&self) -> Option<u64> {
         self.inner.base_fee_per_gas()
     }
@@ -388,6 +644,10 @@ impl<T: HeaderResponse> HeaderResponse for WithOtherFields<T> {
         self.inner.next_block_blob_fee()
     }
 
+    fn next_block_blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        self.inner.next_block_blob_fee_with_params(blob_params)
+    }
+
     fn coinbase(&self) -> Address {
         self.inner.coinbase()
     }
@@ -0,0 +1,188 @@
+//! Typed amounts of gas and wei, to keep the units of fee math apart.
+//!
+//! The response traits return plain integers, and have parallel accessors returning these types,
+//! e.g. [`ReceiptResponse::gas_cost_wei`](crate::ReceiptResponse::gas_cost_wei). Values can only
+//! be combined in ways that preserve their units: multiplying [`GasUnits`] by a [`WeiPerGas`]
+//! yields [`Wei`], and [`Gwei`] are converted explicitly.
+
+use alloy_primitives::U256;
+use core::{fmt, ops::Mul};
+use serde::{Deserialize, Serialize};
+
+/// The number of wei in one gwei.
+const WEI_PER_GWEI: u128 = 1_000_000_000;
+
+macro_rules! unit {
+    ($(#[$attr:meta])* $name:ident($(#[$field:meta])* $inner:ty), $unit:literal) => {
+        #[derive(
+            Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        $(#[$attr])*
+        pub struct $name($(#[$field])* pub $inner);
+
+        impl $name {
+            /// Zero.
+            pub const ZERO: Self = Self(<$inner>::MIN);
+
+            /// Returns the inner value.
+            pub const fn get(self) -> $inner {
+                self.0
+            }
+
+            /// Adds `rhs`, returning `None` on overflow.
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.0.checked_add(rhs.0).map(Self)
+            }
+
+            /// Subtracts `rhs`, returning `None` on underflow.
+            pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.0.checked_sub(rhs.0).map(Self)
+            }
+
+            /// Adds `rhs`, saturating at the maximum.
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                Self(self.0.saturating_add(rhs.0))
+            }
+
+            /// Subtracts `rhs`, saturating at zero.
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                Self(self.0.saturating_sub(rhs.0))
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!("{} ", $unit), self.0)
+            }
+        }
+    };
+}
+
+unit! {
+    /// An amount of wei.
+    #[serde(transparent)]
+    Wei(U256), "wei"
+}
+
+unit! {
+    /// An amount of gwei, a billion wei.
+    #[serde(transparent)]
+    Gwei(#[serde(with = "alloy_serde::quantity")] u128), "gwei"
+}
+
+unit! {
+    /// An amount of gas.
+    #[serde(transparent)]
+    GasUnits(#[serde(with = "alloy_serde::quantity")] u128), "gas"
+}
+
+unit! {
+    /// A price of gas, in wei per unit of gas.
+    #[serde(transparent)]
+    WeiPerGas(#[serde(with = "alloy_serde::quantity")] u128), "wei/gas"
+}
+
+impl Wei {
+    /// Multiplies by `rhs`, returning `None` on overflow.
+    pub fn checked_mul(self, rhs: u128) -> Option<Self> {
+        self.0.checked_mul(U256::from(rhs)).map(Self)
+    }
+
+    /// Converts to gwei, rounding down, or returns `None` if the amount does not fit.
+    pub fn to_gwei_floor(self) -> Option<Gwei> {
+        u128::try_from(self.0 / U256::from(WEI_PER_GWEI)).ok().map(Gwei)
+    }
+}
+
+impl From<Gwei> for Wei {
+    fn from(value: Gwei) -> Self {
+        Self(U256::from(value.0) * U256::from(WEI_PER_GWEI))
+    }
+}
+
+impl GasUnits {
+    /// Returns the amount of gas as a `u64`, or `None` if it does not fit.
+    pub fn to_u64(self) -> Option<u64> {
+        u64::try_from(self.0).ok()
+    }
+}
+
+impl From<u64> for GasUnits {
+    fn from(value: u64) -> Self {
+        Self(value as u128)
+    }
+}
+
+impl WeiPerGas {
+    /// Converts a price in gwei per unit of gas, returning `None` on overflow.
+    pub fn from_gwei(price: Gwei) -> Option<Self> {
+        price.0.checked_mul(WEI_PER_GWEI).map(Self)
+    }
+
+    /// Returns the cost of the gas, which cannot overflow.
+    pub fn cost(self, gas: GasUnits) -> Wei {
+        Wei(U256::from(self.0) * U256::from(gas.0))
+    }
+}
+
+impl Mul<WeiPerGas> for GasUnits {
+    type Output = Wei;
+
+    fn mul(self, rhs: WeiPerGas) -> Wei {
+        rhs.cost(self)
+    }
+}
+
+impl Mul<GasUnits> for WeiPerGas {
+    type Output = Wei;
+
+    fn mul(self, rhs: GasUnits) -> Wei {
+        self.cost(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn combines_units() {
+        let price = WeiPerGas::from_gwei(Gwei(30)).unwrap();
+        assert_eq!(price, WeiPerGas(30_000_000_000));
+        let cost = GasUnits::from(21_000u64) * price;
+        assert_eq!(cost, Wei(U256::from(630_000_000_000_000u128)));
+        assert_eq!(cost.to_gwei_floor(), Some(Gwei(630_000)));
+        assert_eq!(Wei::from(Gwei(630_000)), cost);
+
+        assert_eq!(
+            GasUnits(u128::MAX) * WeiPerGas(u128::MAX),
+            Wei(U256::from(u128::MAX).pow(U256::from(2)))
+        );
+        assert_eq!(WeiPerGas::from_gwei(Gwei(u128::MAX)), None);
+        assert_eq!(GasUnits(1).checked_sub(GasUnits(2)), None);
+        assert_eq!(GasUnits(1).saturating_sub(GasUnits(2)), GasUnits::ZERO);
+        assert_eq!(Wei(U256::MAX).checked_add(Wei(U256::from(1))), None);
+        assert_eq!(cost.to_string(), "630000000000000 wei");
+        assert_eq!(price.to_string(), "30000000000 wei/gas");
+    }
+
+    #[test]
+    fn serde_quantities() {
+        assert_eq!(serde_json::to_string(&GasUnits(21_000)).unwrap(), "\"0x5208\"");
+        assert_eq!(serde_json::from_str::<WeiPerGas>("\"0x1\"").unwrap(), WeiPerGas(1));
+        assert_eq!(serde_json::to_string(&Wei(U256::from(16))).unwrap(), "\"0x10\"");
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    any::AnyNetwork, BuildResult, Network, NetworkWallet, TransactionBuilder,
+    any::AnyNetwork, BuildResult, FieldError, Network, NetworkWallet, TransactionBuilder,
     TransactionBuilder4844, TransactionBuilder7702, TransactionBuilderError,
 };
 use alloy_consensus::BlobTransactionSidecar;
@@ -108,6 +108,13 @@ impl TransactionBuilder<AnyNetwork> for WithOtherFields<TransactionRequest> {
         self.deref().complete_type(ty.try_into().map_err(|_| vec!["supported tx type"])?)
     }
 
+    fn validate_type(&self, ty: <AnyNetwork as Network>::TxType) -> Result<(), Vec<FieldError>> {
+        let ty = ty.try_into().map_err(|_| {
+            vec![FieldError::Invalid { field: "transaction_type", reason: "is not supported" }]
+        })?;
+        self.deref().validate_type(ty)
+    }
+
     fn can_submit(&self) -> bool {
         self.deref().can_submit()
     }
@@ -130,15 +137,20 @@ impl TransactionBuilder<AnyNetwork> for WithOtherFields<TransactionRequest> {
         self.deref_mut().prep_for_submission()
     }
 
-    fn build_unsigned(self) -> BuildResult<<AnyNetwork as Network>::UnsignedTx, AnyNetwork> {
-        if let Err((tx_type, missing)) = self.missing_keys() {
-            return Err(TransactionBuilderError::InvalidTransactionRequest(
-                tx_type.into(),
-                missing,
-            )
-            .into_unbuilt(self));
+    fn build_unsigned(mut self) -> BuildResult<<AnyNetwork as Network>::UnsignedTx, AnyNetwork> {
+        let tx_type = match crate::ethereum::apply_tx_type_policy(&mut self) {
+            Ok(tx_type) => tx_type,
+            Err((tx_type, errors)) => {
+                return Err(TransactionBuilderError::InvalidFields(tx_type.into(), errors)
+                    .into_unbuilt(self))
+            }
+        };
+        if let Err(errors) = self.deref().validate_type(tx_type) {
+            return Err(
+                TransactionBuilderError::InvalidFields(tx_type.into(), errors).into_unbuilt(self)
+            );
         }
-        Ok(self.inner.build_typed_tx().expect("checked by missing_keys"))
+        Ok(self.inner.build_typed_tx().expect("checked by validate_type"))
     }
 
     async fn build<W: NetworkWallet<AnyNetwork>>(
@@ -0,0 +1,430 @@
+//! Type-erased responses, to handle the responses of many networks with one type.
+
+use alloy_eips::{eip2930::AccessList, eip4844::BlobParams, eip7702::SignedAuthorization};
+use alloy_network_primitives::{
+    BlockResponse, HeaderResponse, ReceiptResponse, ReceiptStatus, TransactionResponse,
+};
+use alloy_primitives::{Address, BlockHash, Bloom, Bytes, ChainId, TxHash, B256, U256};
+use alloy_rpc_types_eth::{AnyTransactionReceipt, Header, Signature, Transaction};
+use alloy_serde::{OtherFields, WithOtherFields};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::{any::Any, fmt, sync::Arc};
+
+macro_rules! erased {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $erased:ident: $response:path, $default:ty
+    ) => {
+        trait $erased: $response + fmt::Debug + Send + Sync + 'static {
+            fn as_any(&self) -> &dyn Any;
+
+            fn to_json(&self) -> serde_json::Result<Value>;
+        }
+
+        impl<T> $erased for T
+        where
+            T: $response + Serialize + fmt::Debug + Send + Sync + 'static,
+        {
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn to_json(&self) -> serde_json::Result<Value> {
+                serde_json::to_value(self)
+            }
+        }
+
+        $(#[$attr])*
+        ///
+        /// The wrapped response is shared, so clones are cheap. Fields that are unknown to it are
+        /// kept in the [`OtherFields`], and serialized along with it.
+        #[derive(Clone, Debug)]
+        pub struct $name {
+            inner: Arc<dyn $erased>,
+            other: OtherFields,
+        }
+
+        impl $name {
+            /// Wraps the response, without other fields.
+            pub fn new<T>(inner: T) -> Self
+            where
+                T: $response + Serialize + fmt::Debug + Send + Sync + 'static,
+            {
+                Self::with_other_fields(inner, OtherFields::default())
+            }
+
+            /// Wraps the response, with the fields that are unknown to it.
+            pub fn with_other_fields<T>(inner: T, other: OtherFields) -> Self
+            where
+                T: $response + Serialize + fmt::Debug + Send + Sync + 'static,
+            {
+                Self { inner: Arc::new(inner), other }
+            }
+
+            /// Returns the fields that are unknown to the wrapped response.
+            pub const fn other_fields(&self) -> &OtherFields {
+                &self.other
+            }
+
+            /// Returns a mutable reference to the fields that are unknown to the wrapped
+            /// response.
+            pub fn other_fields_mut(&mut self) -> &mut OtherFields {
+                &mut self.other
+            }
+
+            /// Returns the wrapped response if it is a `T`.
+            ///
+            /// Responses wrapped from a [`WithOtherFields<T>`] are a `T`.
+            pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+                self.inner.as_any().downcast_ref()
+            }
+
+            /// Returns `true` if the wrapped response is a `T`.
+            pub fn is<T: Any>(&self) -> bool {
+                self.inner.as_any().is::<T>()
+            }
+        }
+
+        impl<T> From<WithOtherFields<T>> for $name
+        where
+            T: $response + Serialize + fmt::Debug + Send + Sync + 'static,
+        {
+            fn from(value: WithOtherFields<T>) -> Self {
+                Self::with_other_fields(value.inner, value.other)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut value = self.inner.to_json().map_err(serde::ser::Error::custom)?;
+                if let Value::Object(map) = &mut value {
+                    for (key, field) in self.other.iter() {
+                        map.entry(key.clone()).or_insert_with(|| field.clone());
+                    }
+                }
+                value.serialize(serializer)
+            }
+        }
+
+        /// Deserializes the Ethereum response, keeping all unknown fields.
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <$default>::deserialize(deserializer).map(<Self as From<_>>::from)
+            }
+        }
+    };
+}
+
+erased! {
+    /// A receipt of any network.
+    AnyReceipt, ErasedReceipt: ReceiptResponse, AnyTransactionReceipt
+}
+
+erased! {
+    /// A transaction of any network whose signatures are Ethereum [`Signature`]s.
+    AnyTransaction, ErasedTransaction: TransactionResponse<Signature = Signature>, WithOtherFields<Transaction>
+}
+
+erased! {
+    /// A header of any network.
+    AnyHeader, ErasedHeader: HeaderResponse, WithOtherFields<Header>
+}
+
+impl AnyHeader {
+    /// Wraps the header of the block, with the other fields of the block.
+    ///
+    /// Headers are flattened into their blocks, so fields of the header that are unknown to the
+    /// block, such as those of the [`AnyNetwork`](crate::AnyNetwork) blocks, are other fields of
+    /// the block.
+    pub fn from_block<B>(block: &B) -> Self
+    where
+        B: BlockResponse,
+        B::Header: Clone + Serialize + fmt::Debug + Send + Sync + 'static,
+    {
+        Self::with_other_fields(
+            block.header().clone(),
+            block.other_fields().cloned().unwrap_or_default(),
+        )
+    }
+}
+
+impl ReceiptResponse for AnyReceipt {
+    fn contract_address(&self) -> Option<Address> {
+        self.inner.contract_address()
+    }
+
+    fn status(&self) -> bool {
+        self.inner.status()
+    }
+
+    fn receipt_status(&self) -> ReceiptStatus {
+        self.inner.receipt_status()
+    }
+
+    fn block_hash(&self) -> Option<BlockHash> {
+        self.inner.block_hash()
+    }
+
+    fn block_number(&self) -> Option<u64> {
+        self.inner.block_number()
+    }
+
+    fn transaction_hash(&self) -> TxHash {
+        self.inner.transaction_hash()
+    }
+
+    fn transaction_index(&self) -> Option<u64> {
+        self.inner.transaction_index()
+    }
+
+    fn gas_used(&self) -> u128 {
+        self.inner.gas_used()
+    }
+
+    fn effective_gas_price(&self) -> u128 {
+        self.inner.effective_gas_price()
+    }
+
+    fn blob_gas_used(&self) -> Option<u128> {
+        self.inner.blob_gas_used()
+    }
+
+    fn blob_gas_price(&self) -> Option<u128> {
+        self.inner.blob_gas_price()
+    }
+
+    fn from(&self) -> Address {
+        self.inner.from()
+    }
+
+    fn to(&self) -> Option<Address> {
+        self.inner.to()
+    }
+
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        self.inner.authorization_list()
+    }
+
+    fn cumulative_gas_used(&self) -> u128 {
+        self.inner.cumulative_gas_used()
+    }
+
+    fn gas_cost(&self) -> U256 {
+        self.inner.gas_cost()
+    }
+
+    fn state_root(&self) -> Option<B256> {
+        self.inner.state_root()
+    }
+}
+
+impl TransactionResponse for AnyTransaction {
+    type Signature = Signature;
+
+    fn tx_hash(&self) -> TxHash {
+        self.inner.tx_hash()
+    }
+
+    fn nonce(&self) -> u64 {
+        self.inner.nonce()
+    }
+
+    fn block_hash(&self) -> Option<BlockHash> {
+        self.inner.block_hash()
+    }
+
+    fn block_number(&self) -> Option<u64> {
+        self.inner.block_number()
+    }
+
+    fn transaction_index(&self) -> Option<u64> {
+        self.inner.transaction_index()
+    }
+
+    fn from(&self) -> Address {
+        self.inner.from()
+    }
+
+    fn to(&self) -> Option<Address> {
+        self.inner.to()
+    }
+
+    fn value(&self) -> U256 {
+        self.inner.value()
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        self.inner.gas_price()
+    }
+
+    fn gas(&self) -> u64 {
+        self.inner.gas()
+    }
+
+    fn max_fee_per_gas(&self) -> Option<u128> {
+        self.inner.max_fee_per_gas()
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.inner.max_priority_fee_per_gas()
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.inner.max_fee_per_blob_gas()
+    }
+
+    fn input(&self) -> &Bytes {
+        self.inner.input()
+    }
+
+    fn signature(&self) -> Option<Signature> {
+        self.inner.signature()
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.inner.chain_id()
+    }
+
+    fn blob_versioned_hashes(&self) -> Option<Vec<B256>> {
+        self.inner.blob_versioned_hashes()
+    }
+
+    fn access_list(&self) -> Option<AccessList> {
+        self.inner.access_list()
+    }
+
+    fn transaction_type(&self) -> Option<u8> {
+        self.inner.transaction_type()
+    }
+
+    fn authorization_list(&self) -> Option<Vec<SignedAuthorization>> {
+        self.inner.authorization_list()
+    }
+
+    fn max_gas_cost(&self) -> U256 {
+        self.inner.max_gas_cost()
+    }
+}
+
+impl HeaderResponse for AnyHeader {
+    fn hash(&self) -> BlockHash {
+        self.inner.hash()
+    }
+
+    fn parent_hash(&self) -> BlockHash {
+        self.inner.parent_hash()
+    }
+
+    fn number(&self) -> u64 {
+        self.inner.number()
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.inner.timestamp()
+    }
+
+    fn extra_data(&self) -> &Bytes {
+        self.inner.extra_data()
+    }
+
+    fn transactions_root(&self) -> B256 {
+        self.inner.transactions_root()
+    }
+
+    fn receipts_root(&self) -> B256 {
+        self.inner.receipts_root()
+    }
+
+    fn logs_bloom(&self) -> Bloom {
+        self.inner.logs_bloom()
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.inner.gas_used()
+    }
+
+    fn withdrawals_root(&self) -> Option<B256> {
+        self.inner.withdrawals_root()
+    }
+
+    fn blob_gas_used(&self) -> Option<u64> {
+        self.inner.blob_gas_used()
+    }
+
+    fn excess_blob_gas(&self) -> Option<u64> {
+        self.inner.excess_blob_gas()
+    }
+
+    fn base_fee_per_gas(&self) -> Option<u64> {
+        self.inner.base_fee_per_gas()
+    }
+
+    fn next_block_blob_fee(&self) -> Option<u128> {
+        self.inner.next_block_blob_fee()
+    }
+
+    fn next_block_blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        self.inner.next_block_blob_fee_with_params(blob_params)
+    }
+
+    fn coinbase(&self) -> Address {
+        self.inner.coinbase()
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.inner.gas_limit()
+    }
+
+    fn mix_hash(&self) -> Option<B256> {
+        self.inner.mix_hash()
+    }
+
+    fn difficulty(&self) -> U256 {
+        self.inner.difficulty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_eth::Block;
+    use serde_json::json;
+
+    #[test]
+    fn roundtrips_unknown_fields() {
+        let json = json!({
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "sha3Uncles": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "transactionsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "receiptsRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "difficulty": "0x0",
+            "number": "0x2a",
+            "gasLimit": "0x0",
+            "gasUsed": "0x0",
+            "timestamp": "0x0",
+            "extraData": "0x",
+            "l1BlockNumber": "0x7",
+        });
+
+        let header: AnyHeader = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(header.number(), 42);
+        assert!(header.is::<Header>());
+        assert_eq!(header.other_fields().get("l1BlockNumber"), Some(&json!("0x7")));
+        let roundtrip = serde_json::to_value(&header).unwrap();
+        assert_eq!(roundtrip["l1BlockNumber"], json["l1BlockNumber"]);
+        assert_eq!(roundtrip["hash"], json["hash"]);
+
+        let mut block_json = json;
+        block_json["uncles"] = json!([]);
+        block_json["transactions"] = json!([]);
+        let block: WithOtherFields<Block> = serde_json::from_value(block_json).unwrap();
+        let header = AnyHeader::from_block(&block);
+        assert_eq!(header.downcast_ref::<Header>(), Some(block.header()));
+        assert_eq!(header.other_fields().get("l1BlockNumber"), Some(&json!("0x7")));
+    }
+}
@@ -7,6 +7,9 @@ use core::fmt;
 
 mod builder;
 
+mod erased;
+pub use erased::{AnyHeader, AnyReceipt, AnyTransaction};
+
 /// Transaction type for a catch-all network.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[doc(alias = "AnyTransactionType")]
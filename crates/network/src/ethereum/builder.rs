@@ -1,11 +1,11 @@
 use crate::{
-    BuildResult, Ethereum, Network, NetworkWallet, TransactionBuilder, TransactionBuilder4844,
-    TransactionBuilder7702, TransactionBuilderError,
+    BuildResult, Ethereum, FieldError, Network, NetworkWallet, TransactionBuilder,
+    TransactionBuilder4844, TransactionBuilder7702, TransactionBuilderError,
 };
 use alloy_consensus::{BlobTransactionSidecar, TxType, TypedTransaction};
 use alloy_eips::eip7702::SignedAuthorization;
 use alloy_primitives::{Address, Bytes, ChainId, TxKind, U256};
-use alloy_rpc_types_eth::{request::TransactionRequest, AccessList};
+use alloy_rpc_types_eth::{request::TransactionRequest, AccessList, TxTypeSelectionError};
 
 impl TransactionBuilder<Ethereum> for TransactionRequest {
     fn chain_id(&self) -> Option<ChainId> {
@@ -110,6 +110,56 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
         }
     }
 
+    fn validate_type(&self, ty: TxType) -> Result<(), Vec<FieldError>> {
+        let mut errors = match self.complete_type(ty) {
+            Ok(()) => Vec::new(),
+            Err(missing) => missing.into_iter().map(FieldError::Missing).collect(),
+        };
+
+        let invalid = |field, reason| FieldError::Invalid { field, reason };
+        if ty != TxType::Legacy && ty != TxType::Eip2930 {
+            if let (Some(max_fee), Some(max_priority_fee)) =
+                (self.max_fee_per_gas, self.max_priority_fee_per_gas)
+            {
+                if max_priority_fee > max_fee {
+                    errors.push(invalid("max_priority_fee_per_gas", "exceeds `max_fee_per_gas`"));
+                }
+            }
+        }
+        match ty {
+            TxType::Eip4844 => {
+                if self.to == Some(TxKind::Create) {
+                    errors.push(invalid("to", "blob transactions cannot create contracts"));
+                }
+                if let Some(sidecar) = &self.sidecar {
+                    if self.blob_versioned_hashes.as_ref().is_some_and(|hashes| {
+                        !hashes.iter().copied().eq(sidecar.versioned_hashes())
+                    }) {
+                        errors.push(invalid(
+                            "blob_versioned_hashes",
+                            "do not match the blobs of the sidecar",
+                        ));
+                    }
+                }
+            }
+            TxType::Eip7702 => {
+                if self.to == Some(TxKind::Create) {
+                    errors.push(invalid("to", "EIP-7702 transactions cannot create contracts"));
+                }
+                if self.authorization_list.as_ref().is_some_and(Vec::is_empty) {
+                    errors.push(invalid("authorization_list", "is empty"));
+                }
+            }
+            _ => {}
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     fn can_submit(&self) -> bool {
         // value and data may be None. If they are, they will be set to default.
         // gas fields and nonce may be None, if they are, they will be populated
@@ -137,26 +187,34 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
 
     #[doc(alias = "output_transaction_type")]
     fn output_tx_type(&self) -> TxType {
-        self.preferred_type()
+        self.tx_type_policy().select(self).unwrap_or_else(|_| self.preferred_type())
     }
 
     #[doc(alias = "output_transaction_type_checked")]
     fn output_tx_type_checked(&self) -> Option<TxType> {
-        self.buildable_type()
+        let ty = self.tx_type_policy().select(self).ok()?;
+        self.complete_type(ty).ok().map(|()| ty)
     }
 
     fn prep_for_submission(&mut self) {
-        self.transaction_type = Some(self.preferred_type() as u8);
+        self.transaction_type = Some(TransactionBuilder::output_tx_type(self) as u8);
         self.trim_conflicting_keys();
         self.populate_blob_hashes();
     }
 
-    fn build_unsigned(self) -> BuildResult<TypedTransaction, Ethereum> {
-        if let Err((tx_type, missing)) = self.missing_keys() {
-            return Err(TransactionBuilderError::InvalidTransactionRequest(tx_type, missing)
-                .into_unbuilt(self));
+    fn build_unsigned(mut self) -> BuildResult<TypedTransaction, Ethereum> {
+        let tx_type = match apply_tx_type_policy(&mut self) {
+            Ok(tx_type) => tx_type,
+            Err((tx_type, errors)) => {
+                return Err(
+                    TransactionBuilderError::InvalidFields(tx_type, errors).into_unbuilt(self)
+                )
+            }
+        };
+        if let Err(errors) = self.validate_type(tx_type) {
+            return Err(TransactionBuilderError::InvalidFields(tx_type, errors).into_unbuilt(self));
         }
-        Ok(self.build_typed_tx().expect("checked by missing_keys"))
+        Ok(self.build_typed_tx().expect("checked by validate_type"))
     }
 
     async fn build<W: NetworkWallet<Ethereum>>(
@@ -167,6 +225,25 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
     }
 }
 
+/// Selects the type of the request with the [`TxTypePolicy`](alloy_rpc_types_eth::TxTypePolicy)
+/// of its chain, and sets it as the transaction type of the request.
+///
+/// On failure, returns the invalid transaction type field, with the type it was selected as.
+pub(crate) fn apply_tx_type_policy(
+    request: &mut TransactionRequest,
+) -> Result<TxType, (TxType, Vec<FieldError>)> {
+    let policy = request.tx_type_policy();
+    request.apply_tx_type_policy(&policy).map_err(|err| {
+        let (tx_type, reason) = match err {
+            TxTypeSelectionError::Unsupported(tx_type) => {
+                (tx_type, "is not supported by the chain")
+            }
+            TxTypeSelectionError::UnknownType(_) => (request.preferred_type(), "is not supported"),
+        };
+        (tx_type, vec![FieldError::Invalid { field: "transaction_type", reason }])
+    })
+}
+
 impl TransactionBuilder4844 for TransactionRequest {
     fn max_fee_per_blob_gas(&self) -> Option<u128> {
         self.max_fee_per_blob_gas
@@ -199,11 +276,12 @@ impl TransactionBuilder7702 for TransactionRequest {
 #[cfg(test)]
 mod tests {
     use crate::{
-        TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702, TransactionBuilderError,
+        FieldError, TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702,
+        TransactionBuilderError,
     };
     use alloy_consensus::{BlobTransactionSidecar, TxEip1559, TxType, TypedTransaction};
     use alloy_eips::eip7702::Authorization;
-    use alloy_primitives::{Address, Signature, U256};
+    use alloy_primitives::{Address, Signature, TxKind, U256};
     use alloy_rpc_types_eth::{AccessList, TransactionRequest};
     use std::str::FromStr;
 
@@ -305,7 +383,7 @@ mod tests {
 
         let error = request.build_unsigned().unwrap_err();
 
-        assert!(matches!(error.error, TransactionBuilderError::InvalidTransactionRequest(_, _)));
+        assert!(matches!(error.error, TransactionBuilderError::InvalidFields(_, _)));
     }
 
     #[test]
@@ -314,16 +392,15 @@ mod tests {
 
         let error = request.build_unsigned().unwrap_err();
 
-        let TransactionBuilderError::InvalidTransactionRequest(tx_type, errors) = error.error
-        else {
+        let TransactionBuilderError::InvalidFields(tx_type, errors) = error.error else {
             panic!("wrong variant")
         };
 
         assert_eq!(tx_type, TxType::Legacy);
         assert_eq!(errors.len(), 3);
-        assert!(errors.contains(&"to"));
-        assert!(errors.contains(&"nonce"));
-        assert!(errors.contains(&"gas_limit"));
+        assert!(errors.contains(&FieldError::Missing("to")));
+        assert!(errors.contains(&FieldError::Missing("nonce")));
+        assert!(errors.contains(&FieldError::Missing("gas_limit")));
     }
 
     #[test]
@@ -332,18 +409,17 @@ mod tests {
 
         let error = request.build_unsigned().unwrap_err();
 
-        let TransactionBuilderError::InvalidTransactionRequest(tx_type, errors) = error.error
-        else {
+        let TransactionBuilderError::InvalidFields(tx_type, errors) = error.error else {
             panic!("wrong variant")
         };
 
         assert_eq!(tx_type, TxType::Eip1559);
         assert_eq!(errors.len(), 5);
-        assert!(errors.contains(&"to"));
-        assert!(errors.contains(&"nonce"));
-        assert!(errors.contains(&"gas_limit"));
-        assert!(errors.contains(&"max_priority_fee_per_gas"));
-        assert!(errors.contains(&"max_fee_per_gas"));
+        assert!(errors.contains(&FieldError::Missing("to")));
+        assert!(errors.contains(&FieldError::Missing("nonce")));
+        assert!(errors.contains(&FieldError::Missing("gas_limit")));
+        assert!(errors.contains(&FieldError::Missing("max_priority_fee_per_gas")));
+        assert!(errors.contains(&FieldError::Missing("max_fee_per_gas")));
     }
 
     #[test]
@@ -354,16 +430,15 @@ mod tests {
 
         let error = request.build_unsigned().unwrap_err();
 
-        let TransactionBuilderError::InvalidTransactionRequest(tx_type, errors) = error.error
-        else {
+        let TransactionBuilderError::InvalidFields(tx_type, errors) = error.error else {
             panic!("wrong variant")
         };
 
         assert_eq!(tx_type, TxType::Eip2930);
         assert_eq!(errors.len(), 3);
-        assert!(errors.contains(&"to"));
-        assert!(errors.contains(&"nonce"));
-        assert!(errors.contains(&"gas_limit"));
+        assert!(errors.contains(&FieldError::Missing("to")));
+        assert!(errors.contains(&FieldError::Missing("nonce")));
+        assert!(errors.contains(&FieldError::Missing("gas_limit")));
     }
 
     #[test]
@@ -373,20 +448,18 @@ mod tests {
 
         let error = request.build_unsigned().unwrap_err();
 
-        let TransactionBuilderError::InvalidTransactionRequest(tx_type, errors) = error.error
-        else {
+        let TransactionBuilderError::InvalidFields(tx_type, errors) = error.error else {
             panic!("wrong variant")
         };
 
         assert_eq!(tx_type, TxType::Eip4844);
-        assert_eq!(errors.len(), 7);
-        assert!(errors.contains(&"to"));
-        assert!(errors.contains(&"nonce"));
-        assert!(errors.contains(&"gas_limit"));
-        assert!(errors.contains(&"max_priority_fee_per_gas"));
-        assert!(errors.contains(&"max_fee_per_gas"));
-        assert!(errors.contains(&"to"));
-        assert!(errors.contains(&"max_fee_per_blob_gas"));
+        assert_eq!(errors.len(), 6);
+        assert!(errors.contains(&FieldError::Missing("to")));
+        assert!(errors.contains(&FieldError::Missing("nonce")));
+        assert!(errors.contains(&FieldError::Missing("gas_limit")));
+        assert!(errors.contains(&FieldError::Missing("max_priority_fee_per_gas")));
+        assert!(errors.contains(&FieldError::Missing("max_fee_per_gas")));
+        assert!(errors.contains(&FieldError::Missing("max_fee_per_blob_gas")));
     }
 
     #[test]
@@ -395,17 +468,88 @@ mod tests {
 
         let error = request.build_unsigned().unwrap_err();
 
-        let TransactionBuilderError::InvalidTransactionRequest(tx_type, errors) = error.error
-        else {
+        let TransactionBuilderError::InvalidFields(tx_type, errors) = error.error else {
             panic!("wrong variant")
         };
 
         assert_eq!(tx_type, TxType::Eip7702);
-        assert_eq!(errors.len(), 5);
-        assert!(errors.contains(&"to"));
-        assert!(errors.contains(&"nonce"));
-        assert!(errors.contains(&"gas_limit"));
-        assert!(errors.contains(&"max_priority_fee_per_gas"));
-        assert!(errors.contains(&"max_fee_per_gas"));
+        assert_eq!(errors.len(), 6);
+        assert!(errors.contains(&FieldError::Missing("to")));
+        assert!(errors.contains(&FieldError::Missing("nonce")));
+        assert!(errors.contains(&FieldError::Missing("gas_limit")));
+        assert!(errors.contains(&FieldError::Missing("max_priority_fee_per_gas")));
+        assert!(errors.contains(&FieldError::Missing("max_fee_per_gas")));
+        assert!(errors
+            .contains(&FieldError::Invalid { field: "authorization_list", reason: "is empty" }));
+    }
+
+    #[test]
+    fn test_invalid_field_values() {
+        let request = TransactionRequest::default()
+            .with_kind(TxKind::Create)
+            .with_nonce(0)
+            .with_gas_limit(21_000)
+            .with_max_fee_per_gas(1)
+            .with_max_priority_fee_per_gas(2)
+            .with_max_fee_per_blob_gas(1);
+
+        let error = request.build_unsigned().unwrap_err();
+
+        let TransactionBuilderError::InvalidFields(tx_type, errors) = &error.error else {
+            panic!("wrong variant")
+        };
+        assert_eq!(*tx_type, TxType::Eip4844);
+        assert_eq!(
+            errors,
+            &[
+                FieldError::Missing("sidecar"),
+                FieldError::Invalid {
+                    field: "max_priority_fee_per_gas",
+                    reason: "exceeds `max_fee_per_gas`"
+                },
+                FieldError::Invalid {
+                    field: "to",
+                    reason: "blob transactions cannot create contracts"
+                },
+            ]
+        );
+        assert_eq!(
+            error.error.to_string(),
+            "EIP-4844 transaction can't be built: missing `sidecar`, invalid \
+             `max_priority_fee_per_gas`: exceeds `max_fee_per_gas`, invalid `to`: blob \
+             transactions cannot create contracts"
+        );
+    }
+
+    #[test]
+    fn test_chain_tx_type_policy() {
+        // Celo only supports legacy transactions
+        let request = TransactionRequest::default()
+            .with_chain_id(42220)
+            .with_to(Address::ZERO)
+            .with_nonce(0)
+            .with_gas_limit(21_000)
+            .with_gas_price(1);
+        assert_eq!(request.output_tx_type(), TxType::Legacy);
+        assert!(matches!(request.clone().build_unsigned().unwrap(), TypedTransaction::Legacy(_)));
+
+        let ambiguous = TransactionRequest { gas_price: None, ..request.clone() };
+        assert_eq!(ambiguous.output_tx_type(), TxType::Legacy);
+        assert_eq!(ambiguous.output_tx_type_checked(), None);
+
+        let fee_market = request.with_max_fee_per_gas(1).with_max_priority_fee_per_gas(1);
+        let fee_market = TransactionRequest { gas_price: None, ..fee_market };
+        let error = fee_market.build_unsigned().unwrap_err();
+        let TransactionBuilderError::InvalidFields(tx_type, errors) = &error.error else {
+            panic!("wrong variant")
+        };
+        assert_eq!(*tx_type, TxType::Eip1559);
+        assert_eq!(
+            errors,
+            &[FieldError::Invalid {
+                field: "transaction_type",
+                reason: "is not supported by the chain"
+            }]
+        );
     }
 }
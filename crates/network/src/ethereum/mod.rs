@@ -1,6 +1,7 @@
 use crate::Network;
 
 mod builder;
+pub(crate) use builder::apply_tx_type_policy;
 
 mod wallet;
 pub use wallet::EthereumWallet;
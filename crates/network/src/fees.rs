@@ -0,0 +1,34 @@
+/// How the fees of the transactions of a [`Network`](crate::Network) are estimated, e.g. by the
+/// gas filler of the provider.
+///
+/// Networks select their model with [`Network::FEE_MODEL`](crate::Network::FEE_MODEL), so that
+/// networks with their own fee markets are estimated correctly without custom fillers. Fees that
+/// are already set on a transaction request are left untouched, and requests with a gas price or
+/// of a legacy or EIP-2930 type are always estimated as [`FeeModel::Legacy`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FeeModel {
+    /// EIP-1559 fees estimated from the rewards of recent blocks with `eth_feeHistory`, falling
+    /// back to [`FeeModel::Legacy`] if the network does not support EIP-1559.
+    #[default]
+    Eip1559,
+    /// EIP-1559 fees with the priority fee suggested by `eth_maxPriorityFeePerGas`, and a max fee
+    /// of twice the latest base fee plus the priority fee.
+    ///
+    /// This suits networks whose fee history is not representative, e.g. OP Stack chains, whose
+    /// sequencer suggests the priority fee needed for timely inclusion.
+    SuggestedPriorityFee,
+    /// EIP-1559 fees without a priority fee, with the max fee suggested by `eth_gasPrice`.
+    ///
+    /// This suits networks that ignore the priority fee, e.g. Arbitrum, whose gas price already
+    /// includes a buffer for base fee increases.
+    NoPriorityFee,
+    /// A legacy gas price suggested by `eth_gasPrice`.
+    Legacy,
+    /// The gas limit and EIP-1559 fees estimated together by the given method, called with the
+    /// transaction request, e.g. `zks_estimateFee` on zkSync.
+    ///
+    /// The method must return an object with `gasLimit`, `maxFeePerGas` and
+    /// `maxPriorityFeePerGas` quantities, in camel or snake case.
+    Rpc(&'static str),
+}
@@ -13,19 +13,29 @@ use core::fmt::{Debug, Display};
 
 mod transaction;
 pub use transaction::{
-    BuildResult, NetworkWallet, TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702,
-    TransactionBuilderError, TxSigner, TxSignerSync, UnbuiltTransactionError,
+    BuildResult, FieldError, NetworkWallet, TransactionBuilder, TransactionBuilder4844,
+    TransactionBuilder7702, TransactionBuilderError, TxSigner, TxSignerSync,
+    UnbuiltTransactionError,
 };
 
 mod ethereum;
 pub use ethereum::{Ethereum, EthereumWallet};
 
+mod fees;
+pub use fees::FeeModel;
+
+mod policy;
+pub use policy::{
+    PolicyViolation, PolicyWallet, SpendingLimit, TransactionSimulator, WalletPolicy,
+};
+
 mod any;
-pub use any::{AnyNetwork, AnyTxType};
+pub use any::{AnyHeader, AnyNetwork, AnyReceipt, AnyTransaction, AnyTxType};
 
 pub use alloy_eips::eip2718;
 pub use alloy_network_primitives::{
-    self as primitives, BlockResponse, HeaderResponse, ReceiptResponse, TransactionResponse,
+    self as primitives, BlockResponse, HeaderResponse, ReceiptResponse, ReceiptStatus,
+    TransactionResponse,
 };
 
 /// Captures type info for network-specific RPC requests/responses.
@@ -92,4 +102,9 @@ pub trait Network: Debug + Clone + Copy + Sized + Send + Sync + 'static {
     /// The JSON body of a block response.
     type BlockResponse: RpcObject
         + BlockResponse<Transaction = Self::TransactionResponse, Header = Self::HeaderResponse>;
+
+    // -- Fees --
+
+    /// How the fees of transactions are estimated. Defaults to [`FeeModel::Eip1559`].
+    const FEE_MODEL: FeeModel = FeeModel::Eip1559;
 }
@@ -0,0 +1,442 @@
+use crate::{Network, NetworkWallet, TransactionBuilder};
+use alloy_consensus::Transaction;
+use alloy_primitives::{map::HashSet, Address, FixedBytes, TxKind, U256};
+use futures_utils_wasm::{impl_future, BoxFuture};
+use std::{
+    collections::VecDeque,
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A violation of a [`WalletPolicy`], returned by a [`PolicyWallet`] instead of signing.
+///
+/// The violation is returned as [`alloy_signer::Error::Other`], see
+/// [`PolicyViolation::from_signer_error`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum PolicyViolation {
+    /// The value of the transaction exceeds the spending limit of the period.
+    #[error("value {value} exceeds the spending limit of {limit} with {spent} already spent")]
+    SpendingLimitExceeded {
+        /// The maximum value of the period.
+        limit: U256,
+        /// The value already spent in the period.
+        spent: U256,
+        /// The value of the transaction.
+        value: U256,
+    },
+    /// The destination of the transaction is denied.
+    #[error("destination {0} is denied")]
+    DestinationDenied(Address),
+    /// The destination of the transaction is not allowed.
+    #[error("destination {0} is not allowed")]
+    DestinationNotAllowed(Address),
+    /// The transaction creates a contract, while destinations are restricted.
+    #[error("contract creations are not allowed")]
+    ContractCreation,
+    /// The transaction calls a denied method.
+    #[error("method selector {0} is denied")]
+    SelectorDenied(FixedBytes<4>),
+    /// The transaction calls a method that is not allowed.
+    #[error("method selector {0} is not allowed")]
+    SelectorNotAllowed(FixedBytes<4>),
+    /// The simulation of the transaction failed, with the reason.
+    #[error("simulation failed: {0}")]
+    SimulationFailed(String),
+}
+
+impl PolicyViolation {
+    /// Returns the violation of a signing error, if it was caused by one.
+    pub fn from_signer_error(error: &alloy_signer::Error) -> Option<&Self> {
+        match error {
+            alloy_signer::Error::Other(error) => error.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// A limit of the value spent by a [`PolicyWallet`] in a sliding period.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpendingLimit {
+    /// The maximum value of the transactions signed in the period.
+    pub max_value: U256,
+    /// The length of the period.
+    pub period: Duration,
+}
+
+/// The rules enforced by a [`PolicyWallet`] before signing transactions.
+///
+/// The default policy allows all transactions.
+#[derive(Clone, Debug, Default)]
+pub struct WalletPolicy {
+    spending_limit: Option<SpendingLimit>,
+    allowed_destinations: Option<HashSet<Address>>,
+    denied_destinations: HashSet<Address>,
+    allowed_selectors: Option<HashSet<FixedBytes<4>>>,
+    denied_selectors: HashSet<FixedBytes<4>>,
+}
+
+impl WalletPolicy {
+    /// Limits the value of the transactions signed in each sliding period.
+    pub const fn with_spending_limit(mut self, max_value: U256, period: Duration) -> Self {
+        self.spending_limit = Some(SpendingLimit { max_value, period });
+        self
+    }
+
+    /// Only allows transactions to the given destinations, which also denies contract creations.
+    pub fn with_allowed_destinations(
+        mut self,
+        destinations: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        self.allowed_destinations = Some(destinations.into_iter().collect());
+        self
+    }
+
+    /// Denies transactions to the given destinations.
+    pub fn with_denied_destinations(
+        mut self,
+        destinations: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        self.denied_destinations.extend(destinations);
+        self
+    }
+
+    /// Only allows calls of the given method selectors. Transactions without calldata are still
+    /// allowed.
+    pub fn with_allowed_selectors(
+        mut self,
+        selectors: impl IntoIterator<Item = FixedBytes<4>>,
+    ) -> Self {
+        self.allowed_selectors = Some(selectors.into_iter().collect());
+        self
+    }
+
+    /// Denies calls of the given method selectors.
+    pub fn with_denied_selectors(
+        mut self,
+        selectors: impl IntoIterator<Item = FixedBytes<4>>,
+    ) -> Self {
+        self.denied_selectors.extend(selectors);
+        self
+    }
+
+    /// Returns the spending limit, if any.
+    pub const fn spending_limit(&self) -> Option<&SpendingLimit> {
+        self.spending_limit.as_ref()
+    }
+
+    /// Checks the destination and calldata of the transaction.
+    pub fn check(&self, tx: &impl Transaction) -> Result<(), PolicyViolation> {
+        match tx.to() {
+            TxKind::Call(to) => {
+                if self.denied_destinations.contains(&to) {
+                    return Err(PolicyViolation::DestinationDenied(to));
+                }
+                if self.allowed_destinations.as_ref().is_some_and(|allowed| !allowed.contains(&to))
+                {
+                    return Err(PolicyViolation::DestinationNotAllowed(to));
+                }
+
+                if let Some(selector) = tx.input().get(..4) {
+                    let selector = FixedBytes::from_slice(selector);
+                    if self.denied_selectors.contains(&selector) {
+                        return Err(PolicyViolation::SelectorDenied(selector));
+                    }
+                    if self
+                        .allowed_selectors
+                        .as_ref()
+                        .is_some_and(|allowed| !allowed.contains(&selector))
+                    {
+                        return Err(PolicyViolation::SelectorNotAllowed(selector));
+                    }
+                }
+            }
+            TxKind::Create if self.allowed_destinations.is_some() => {
+                return Err(PolicyViolation::ContractCreation)
+            }
+            TxKind::Create => {}
+        }
+        Ok(())
+    }
+}
+
+/// Simulates transactions before a [`PolicyWallet`] signs them, e.g. with `eth_call`.
+pub trait TransactionSimulator<N: Network>: fmt::Debug + Send + Sync {
+    /// Simulates the transaction from the sender, returning the reason if it fails.
+    fn simulate<'a>(
+        &'a self,
+        sender: Address,
+        tx: &'a N::UnsignedTx,
+    ) -> BoxFuture<'a, Result<(), String>>;
+}
+
+/// The values spent in the current period, oldest first.
+#[derive(Debug, Default)]
+struct Spending(VecDeque<(Instant, U256)>);
+
+impl Spending {
+    fn spent(&mut self, period: Duration) -> U256 {
+        let now = Instant::now();
+        while self.0.front().is_some_and(|(at, _)| now.duration_since(*at) >= period) {
+            self.0.pop_front();
+        }
+        self.0.iter().fold(U256::ZERO, |spent, (_, value)| spent.saturating_add(*value))
+    }
+
+    fn check(&mut self, limit: &SpendingLimit, value: U256) -> Result<U256, PolicyViolation> {
+        let spent = self.spent(limit.period);
+        if spent.saturating_add(value) > limit.max_value {
+            return Err(PolicyViolation::SpendingLimitExceeded {
+                limit: limit.max_value,
+                spent,
+                value,
+            });
+        }
+        Ok(spent)
+    }
+}
+
+/// A wallet that only signs transactions complying with a [`WalletPolicy`], as a guard rail for
+/// hot wallets.
+///
+/// Before signing, the destination and method selector of each transaction are checked, its value
+/// is checked against the spending limit, and it is simulated if a [`TransactionSimulator`] is
+/// set. Transactions violating the policy fail to sign with a [`PolicyViolation`].
+///
+/// The value of each signed transaction counts towards the spending limit from when it is signed,
+/// whether it is sent or not.
+pub struct PolicyWallet<W, N: Network> {
+    wallet: W,
+    policy: WalletPolicy,
+    simulator: Option<Arc<dyn TransactionSimulator<N>>>,
+    spending: Arc<Mutex<Spending>>,
+    _pd: PhantomData<fn() -> N>,
+}
+
+impl<W: fmt::Debug, N: Network> fmt::Debug for PolicyWallet<W, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyWallet")
+            .field("wallet", &self.wallet)
+            .field("policy", &self.policy)
+            .field("simulator", &self.simulator)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Clone, N: Network> Clone for PolicyWallet<W, N> {
+    fn clone(&self) -> Self {
+        Self {
+            wallet: self.wallet.clone(),
+            policy: self.policy.clone(),
+            simulator: self.simulator.clone(),
+            spending: self.spending.clone(),
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<W, N: Network> PolicyWallet<W, N> {
+    /// Wraps the wallet, enforcing the policy.
+    pub fn new(wallet: W, policy: WalletPolicy) -> Self {
+        Self { wallet, policy, simulator: None, spending: Default::default(), _pd: PhantomData }
+    }
+
+    /// Requires transactions to succeed in the simulator before signing them.
+    pub fn with_simulator(mut self, simulator: impl TransactionSimulator<N> + 'static) -> Self {
+        self.simulator = Some(Arc::new(simulator));
+        self
+    }
+
+    /// Returns the wrapped wallet.
+    pub const fn wallet(&self) -> &W {
+        &self.wallet
+    }
+
+    /// Returns the policy.
+    pub const fn policy(&self) -> &WalletPolicy {
+        &self.policy
+    }
+
+    /// Returns the value spent in the current period of the spending limit.
+    pub fn spent(&self) -> U256 {
+        self.policy
+            .spending_limit
+            .map_or(U256::ZERO, |limit| self.spending.lock().unwrap().spent(limit.period))
+    }
+}
+
+impl<W, N> NetworkWallet<N> for PolicyWallet<W, N>
+where
+    W: NetworkWallet<N>,
+    N: Network,
+    N::UnsignedTx: Transaction + Send + Sync,
+{
+    fn default_signer_address(&self) -> Address {
+        self.wallet.default_signer_address()
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        self.wallet.has_signer_for(address)
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        self.wallet.signer_addresses()
+    }
+
+    fn sign_transaction_from(
+        &self,
+        sender: Address,
+        tx: N::UnsignedTx,
+    ) -> impl_future!(<Output = alloy_signer::Result<N::TxEnvelope>>) {
+        async move {
+            self.policy.check(&tx).map_err(alloy_signer::Error::other)?;
+            let value = tx.value();
+            if let Some(limit) = &self.policy.spending_limit {
+                self.spending
+                    .lock()
+                    .unwrap()
+                    .check(limit, value)
+                    .map_err(alloy_signer::Error::other)?;
+            }
+            if let Some(simulator) = &self.simulator {
+                simulator.simulate(sender, &tx).await.map_err(|reason| {
+                    alloy_signer::Error::other(PolicyViolation::SimulationFailed(reason))
+                })?;
+            }
+
+            let signed = self.wallet.sign_transaction_from(sender, tx).await?;
+            // check again, other transactions may have been signed in the meantime
+            if let Some(limit) = &self.policy.spending_limit {
+                let mut spending = self.spending.lock().unwrap();
+                spending.check(limit, value).map_err(alloy_signer::Error::other)?;
+                spending.0.push_back((Instant::now(), value));
+            }
+            Ok(signed)
+        }
+    }
+
+    fn sign_request(
+        &self,
+        request: N::TransactionRequest,
+    ) -> impl_future!(<Output = alloy_signer::Result<N::TxEnvelope>>) {
+        async move {
+            let sender = request.from().unwrap_or_else(|| self.default_signer_address());
+            let tx = request.build_unsigned().map_err(alloy_signer::Error::other)?;
+            self.sign_transaction_from(sender, tx).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ethereum;
+    use alloy_consensus::{SignableTransaction, TxEip1559, TxEnvelope, TypedTransaction};
+    use alloy_primitives::{address, fixed_bytes, Bytes, Signature};
+
+    /// Signs EIP-1559 transactions with a test signature.
+    #[derive(Debug)]
+    struct TestWallet;
+
+    impl NetworkWallet<Ethereum> for TestWallet {
+        fn default_signer_address(&self) -> Address {
+            Address::ZERO
+        }
+
+        fn has_signer_for(&self, address: &Address) -> bool {
+            address.is_zero()
+        }
+
+        fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+            std::iter::once(Address::ZERO)
+        }
+
+        async fn sign_transaction_from(
+            &self,
+            _sender: Address,
+            tx: TypedTransaction,
+        ) -> alloy_signer::Result<TxEnvelope> {
+            Ok(tx.eip1559().unwrap().clone().into_signed(Signature::test_signature()).into())
+        }
+    }
+
+    #[derive(Debug)]
+    struct Reverts;
+
+    impl TransactionSimulator<Ethereum> for Reverts {
+        fn simulate<'a>(
+            &'a self,
+            _sender: Address,
+            _tx: &'a TypedTransaction,
+        ) -> BoxFuture<'a, Result<(), String>> {
+            Box::pin(async { Err("execution reverted".to_string()) })
+        }
+    }
+
+    fn tx(to: Address, value: u64, input: &[u8]) -> TypedTransaction {
+        TxEip1559 {
+            chain_id: 1,
+            to: to.into(),
+            value: U256::from(value),
+            input: Bytes::copy_from_slice(input),
+            ..Default::default()
+        }
+        .into()
+    }
+
+    async fn sign(
+        wallet: &PolicyWallet<TestWallet, Ethereum>,
+        tx: TypedTransaction,
+    ) -> Result<(), PolicyViolation> {
+        match NetworkWallet::<Ethereum>::sign_transaction(wallet, tx).await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(PolicyViolation::from_signer_error(&err).unwrap().clone()),
+        }
+    }
+
+    #[tokio::test]
+    async fn enforces_policy() {
+        let (allowed, denied) = (
+            address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"),
+            address!("000000000000000000000000000000000000dEaD"),
+        );
+        let transfer = fixed_bytes!("a9059cbb");
+        let policy = WalletPolicy::default()
+            .with_spending_limit(U256::from(100), Duration::from_secs(3600))
+            .with_allowed_destinations([allowed])
+            .with_denied_destinations([denied])
+            .with_allowed_selectors([transfer]);
+        let wallet = PolicyWallet::new(TestWallet, policy);
+
+        assert_eq!(sign(&wallet, tx(allowed, 60, &[])).await, Ok(()));
+        assert_eq!(sign(&wallet, tx(allowed, 0, transfer.as_slice())).await, Ok(()));
+        assert_eq!(
+            sign(&wallet, tx(allowed, 50, &[])).await,
+            Err(PolicyViolation::SpendingLimitExceeded {
+                limit: U256::from(100),
+                spent: U256::from(60),
+                value: U256::from(50)
+            })
+        );
+        assert_eq!(wallet.spent(), U256::from(60));
+        assert_eq!(
+            sign(&wallet, tx(denied, 0, &[])).await,
+            Err(PolicyViolation::DestinationDenied(denied))
+        );
+        assert_eq!(
+            sign(&wallet, tx(Address::ZERO, 0, &[])).await,
+            Err(PolicyViolation::DestinationNotAllowed(Address::ZERO))
+        );
+        assert_eq!(
+            sign(&wallet, tx(allowed, 0, &[0x09, 0x5e, 0xa7, 0xb3])).await,
+            Err(PolicyViolation::SelectorNotAllowed(fixed_bytes!("095ea7b3")))
+        );
+
+        let wallet = wallet.with_simulator(Reverts);
+        assert_eq!(
+            sign(&wallet, tx(allowed, 0, &[])).await,
+            Err(PolicyViolation::SimulationFailed("execution reverted".to_string()))
+        );
+    }
+}
@@ -21,13 +21,37 @@ pub struct UnbuiltTransactionError<N: Network> {
     pub error: TransactionBuilderError<N>,
 }
 
+/// A field of a transaction request that prevents building a transaction of some type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum FieldError {
+    /// The field is required by the transaction type, but not set.
+    #[error("missing `{0}`")]
+    Missing(&'static str),
+    /// The field is set to a value that the transaction type does not allow.
+    #[error("invalid `{field}`: {reason}")]
+    Invalid {
+        /// The name of the field.
+        field: &'static str,
+        /// Why the value is invalid.
+        reason: &'static str,
+    },
+}
+
 /// Error type for transaction builders.
 #[derive(Debug, thiserror::Error)]
 pub enum TransactionBuilderError<N: Network> {
     /// Invalid transaction request
+    ///
+    /// No longer returned by the builders of this crate, which report the missing and invalid
+    /// fields with [`InvalidFields`](Self::InvalidFields) instead.
+    #[deprecated = "builders return `InvalidFields` instead"]
     #[error("{0} transaction can't be built due to missing keys: {1:?}")]
     InvalidTransactionRequest(N::TxType, Vec<&'static str>),
 
+    /// Fields of the transaction request are missing or invalid for the transaction type.
+    #[error("{0} transaction can't be built: {errors}", errors = DisplayFieldErrors(.1))]
+    InvalidFields(N::TxType, Vec<FieldError>),
+
     /// Signer cannot produce signature type required for transaction.
     #[error("Signer cannot produce signature type required for transaction")]
     UnsupportedSignatureType,
@@ -41,6 +65,20 @@ pub enum TransactionBuilderError<N: Network> {
     Custom(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
 
+struct DisplayFieldErrors<'a>(&'a [FieldError]);
+
+impl core::fmt::Display for DisplayFieldErrors<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            error.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
 impl<N: Network> TransactionBuilderError<N> {
     /// Instantiate a custom error.
     pub fn custom<E>(e: E) -> Self
@@ -282,6 +320,21 @@ pub trait TransactionBuilder<N: Network>: Default + Sized + Send + Sync + 'stati
         self.complete_type(self.output_tx_type())
     }
 
+    /// Check if the fields are complete and valid to build the specified type, returning each
+    /// missing or invalid field.
+    ///
+    /// By default, this only reports the missing keys of [`complete_type`](Self::complete_type).
+    fn validate_type(&self, ty: N::TxType) -> Result<(), Vec<FieldError>> {
+        self.complete_type(ty)
+            .map_err(|missing| missing.into_iter().map(FieldError::Missing).collect())
+    }
+
+    /// Check if the fields are complete and valid to build the currently-preferred transaction
+    /// type, returning each missing or invalid field.
+    fn validate_preferred(&self) -> Result<(), Vec<FieldError>> {
+        self.validate_type(self.output_tx_type())
+    }
+
     /// Assert that the builder prefers a certain transaction type. This does
     /// not indicate that the builder is ready to build. This function uses a
     /// `dbg_assert_eq!` to check the builder status, and will have no affect
@@ -1,6 +1,6 @@
 mod builder;
 pub use builder::{
-    BuildResult, TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702,
+    BuildResult, FieldError, TransactionBuilder, TransactionBuilder4844, TransactionBuilder7702,
     TransactionBuilderError, UnbuiltTransactionError,
 };
 
@@ -191,6 +191,19 @@ impl<L, N> ProviderBuilder<L, Identity, N> {
     ) -> ProviderBuilder<L, JoinFill<Identity, ChainIdFiller>, N> {
         self.filler(ChainIdFiller::new(Some(chain_id)))
     }
+
+    /// Add a strict chain ID filler to the stack being built. The filler will
+    /// fill transactions with the chain ID reported by the provider via
+    /// [`Provider::get_chain_id`], and reject transactions if the provided
+    /// chain ID, or a chain ID already set on the transaction, differs from it.
+    ///
+    /// See [`ChainIdFiller::strict`].
+    pub fn with_strict_chain_id(
+        self,
+        chain_id: Option<ChainId>,
+    ) -> ProviderBuilder<L, JoinFill<Identity, ChainIdFiller>, N> {
+        self.filler(ChainIdFiller::strict(chain_id))
+    }
 }
 
 impl<L, F, N> ProviderBuilder<L, F, N> {
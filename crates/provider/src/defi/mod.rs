@@ -0,0 +1,33 @@
+//! Typed bindings and calldata builders for widely used DeFi contracts.
+//!
+//! - [`uniswap_v2`] quotes swaps from the reserves of pairs or through the router, and builds
+//!   router swaps,
+//! - [`uniswap_v3`] quotes swaps through `QuoterV2`, and builds `SwapRouter02` swaps,
+//! - [`weth`] builds WETH deposits and withdrawals, with the WETH address of known chains.
+//!
+//! The builders return calldata, to be sent to the router with a transaction request, and the
+//! quoters make calls through a [`Provider`].
+
+pub mod uniswap_v2;
+pub mod uniswap_v3;
+pub mod weth;
+
+use crate::Provider;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::Address;
+use alloy_sol_types::SolCall;
+use alloy_transport::{Transport, TransportResult};
+
+/// Calls the contract at the latest block, decoding the output.
+async fn call<P, T, N, C>(provider: &P, to: Address, call: C) -> TransportResult<C::Return>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    C: SolCall,
+{
+    let tx = N::TransactionRequest::default().with_to(to).with_input(call.abi_encode());
+    let output = provider.call(&tx).await?;
+    C::abi_decode_returns(&output, true).map_err(|err| RpcError::local_usage_str(&err.to_string()))
+}
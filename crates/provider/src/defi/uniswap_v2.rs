@@ -0,0 +1,231 @@
+//! [Uniswap V2](https://docs.uniswap.org/contracts/v2/overview) pairs and router.
+
+use crate::{defi::call, Provider};
+use alloy_network::Network;
+use alloy_primitives::{address, Address, Bytes, U256};
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport::{Transport, TransportResult};
+
+sol! {
+    /// The subset of the Uniswap V2 router used by the helpers.
+    #[allow(missing_docs)]
+    interface IUniswapV2Router02 {
+        function getAmountsOut(uint256 amountIn, address[] path) external view returns (uint256[] amounts);
+        function getAmountsIn(uint256 amountOut, address[] path) external view returns (uint256[] amounts);
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapTokensForExactTokens(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapExactETHForTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline) external payable returns (uint256[] amounts);
+        function swapExactTokensForETH(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+    }
+
+    /// The subset of a Uniswap V2 pair used by the helpers.
+    #[allow(missing_docs)]
+    interface IUniswapV2Pair {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
+    }
+}
+
+/// The address of the Uniswap V2 router on Ethereum mainnet.
+pub const ROUTER_ADDRESS: Address = address!("7a250d5630B4cF539739dF2C5dAcb4c659F2488D");
+
+/// The address of the Uniswap V2 factory on Ethereum mainnet.
+pub const FACTORY_ADDRESS: Address = address!("5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f");
+
+/// Returns the output of swapping `amount_in` against the reserves, after the 0.3% fee, like
+/// `UniswapV2Library.getAmountOut`.
+///
+/// Returns `None` if a reserve is empty or the computation overflows.
+pub fn get_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> Option<U256> {
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return None;
+    }
+    let amount_in_with_fee = amount_in.checked_mul(U256::from(997))?;
+    let numerator = amount_in_with_fee.checked_mul(reserve_out)?;
+    let denominator = reserve_in.checked_mul(U256::from(1000))?.checked_add(amount_in_with_fee)?;
+    Some(numerator / denominator)
+}
+
+/// Returns the input needed to receive `amount_out` from the reserves, after the 0.3% fee, like
+/// `UniswapV2Library.getAmountIn`.
+///
+/// Returns `None` if a reserve is empty, the output exceeds the reserve, or the computation
+/// overflows.
+pub fn get_amount_in(amount_out: U256, reserve_in: U256, reserve_out: U256) -> Option<U256> {
+    if reserve_in.is_zero() || amount_out >= reserve_out {
+        return None;
+    }
+    let numerator = reserve_in.checked_mul(amount_out)?.checked_mul(U256::from(1000))?;
+    let denominator = (reserve_out - amount_out).checked_mul(U256::from(997))?;
+    (numerator / denominator).checked_add(U256::from(1))
+}
+
+/// Returns the reserves of the pair, ordered as `(reserve_in, reserve_out)` for a swap of
+/// `token_in`.
+pub async fn get_reserves<P, T, N>(
+    provider: &P,
+    pair: Address,
+    token_in: Address,
+) -> TransportResult<(U256, U256)>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let token0 = call(provider, pair, IUniswapV2Pair::token0Call {}).await?._0;
+    let reserves = call(provider, pair, IUniswapV2Pair::getReservesCall {}).await?;
+    let (reserve0, reserve1) = (U256::from(reserves.reserve0), U256::from(reserves.reserve1));
+    Ok(if token0 == token_in { (reserve0, reserve1) } else { (reserve1, reserve0) })
+}
+
+/// Quotes the amounts along the path for swapping `amount_in` of the first token, with the
+/// router's `getAmountsOut`.
+pub async fn quote_exact_in<P, T, N>(
+    provider: &P,
+    router: Address,
+    amount_in: U256,
+    path: Vec<Address>,
+) -> TransportResult<Vec<U256>>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let call_data = IUniswapV2Router02::getAmountsOutCall { amountIn: amount_in, path };
+    Ok(call(provider, router, call_data).await?.amounts)
+}
+
+/// Quotes the amounts along the path for receiving `amount_out` of the last token, with the
+/// router's `getAmountsIn`.
+pub async fn quote_exact_out<P, T, N>(
+    provider: &P,
+    router: Address,
+    amount_out: U256,
+    path: Vec<Address>,
+) -> TransportResult<Vec<U256>>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let call_data = IUniswapV2Router02::getAmountsInCall { amountOut: amount_out, path };
+    Ok(call(provider, router, call_data).await?.amounts)
+}
+
+/// Returns the calldata of `swapExactTokensForTokens`, swapping exactly `amount_in` of the first
+/// token of the path for at least `amount_out_min` of the last, sent to `to`.
+pub fn swap_exact_tokens_for_tokens(
+    amount_in: U256,
+    amount_out_min: U256,
+    path: Vec<Address>,
+    to: Address,
+    deadline: U256,
+) -> Bytes {
+    IUniswapV2Router02::swapExactTokensForTokensCall {
+        amountIn: amount_in,
+        amountOutMin: amount_out_min,
+        path,
+        to,
+        deadline,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Returns the calldata of `swapTokensForExactTokens`, swapping at most `amount_in_max` of the
+/// first token of the path for exactly `amount_out` of the last, sent to `to`.
+pub fn swap_tokens_for_exact_tokens(
+    amount_out: U256,
+    amount_in_max: U256,
+    path: Vec<Address>,
+    to: Address,
+    deadline: U256,
+) -> Bytes {
+    IUniswapV2Router02::swapTokensForExactTokensCall {
+        amountOut: amount_out,
+        amountInMax: amount_in_max,
+        path,
+        to,
+        deadline,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Returns the calldata of `swapExactETHForTokens`, swapping the value of the transaction for at
+/// least `amount_out_min` of the last token of the path, which starts with WETH.
+pub fn swap_exact_eth_for_tokens(
+    amount_out_min: U256,
+    path: Vec<Address>,
+    to: Address,
+    deadline: U256,
+) -> Bytes {
+    IUniswapV2Router02::swapExactETHForTokensCall {
+        amountOutMin: amount_out_min,
+        path,
+        to,
+        deadline,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Returns the calldata of `swapExactTokensForETH`, swapping exactly `amount_in` of the first
+/// token of the path, which ends with WETH, for at least `amount_out_min` ether.
+pub fn swap_exact_tokens_for_eth(
+    amount_in: U256,
+    amount_out_min: U256,
+    path: Vec<Address>,
+    to: Address,
+    deadline: U256,
+) -> Bytes {
+    IUniswapV2Router02::swapExactTokensForETHCall {
+        amountIn: amount_in,
+        amountOutMin: amount_out_min,
+        path,
+        to,
+        deadline,
+    }
+    .abi_encode()
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_amounts() {
+        let (reserve_in, reserve_out) = (U256::from(1_000_000), U256::from(2_000_000));
+        let amount_out = get_amount_out(U256::from(1000), reserve_in, reserve_out).unwrap();
+        assert_eq!(amount_out, U256::from(1992));
+        let amount_in = get_amount_in(amount_out, reserve_in, reserve_out).unwrap();
+        assert!(amount_in <= U256::from(1000));
+        assert_eq!(
+            get_amount_out(amount_in, reserve_in, reserve_out),
+            Some(amount_out),
+            "the input for an output yields it"
+        );
+
+        assert_eq!(get_amount_out(U256::from(1), U256::ZERO, reserve_out), None);
+        assert_eq!(get_amount_in(reserve_out, reserve_in, reserve_out), None);
+        assert_eq!(get_amount_out(U256::MAX, reserve_in, reserve_out), None);
+    }
+
+    #[test]
+    fn encodes_swaps() {
+        let path = vec![Address::repeat_byte(1), Address::repeat_byte(2)];
+        let calldata = swap_exact_tokens_for_tokens(
+            U256::from(1),
+            U256::from(2),
+            path.clone(),
+            Address::repeat_byte(3),
+            U256::from(4),
+        );
+        let call =
+            IUniswapV2Router02::swapExactTokensForTokensCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(call.path, path);
+        assert_eq!(call.deadline, U256::from(4));
+    }
+}
@@ -0,0 +1,177 @@
+//! [Uniswap V3](https://docs.uniswap.org/contracts/v3/overview) quoter and router.
+
+use crate::{defi::call, Provider};
+use alloy_network::Network;
+use alloy_primitives::{address, aliases::U24, Address, Bytes, U160, U256};
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport::{Transport, TransportResult};
+
+sol! {
+    /// The subset of the Uniswap V3 `QuoterV2` used by the helpers.
+    #[allow(missing_docs)]
+    interface IQuoterV2 {
+        struct QuoteExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint256 amountIn;
+            uint24 fee;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        function quoteExactInputSingle(QuoteExactInputSingleParams params) external returns (uint256 amountOut, uint160 sqrtPriceX96After, uint32 initializedTicksCrossed, uint256 gasEstimate);
+        function quoteExactInput(bytes path, uint256 amountIn) external returns (uint256 amountOut, uint160[] sqrtPriceX96AfterList, uint32[] initializedTicksCrossedList, uint256 gasEstimate);
+    }
+
+    /// The subset of the Uniswap V3 `SwapRouter02` used by the helpers.
+    #[allow(missing_docs)]
+    interface ISwapRouter02 {
+        struct ExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        struct ExactInputParams {
+            bytes path;
+            address recipient;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+        }
+
+        function exactInputSingle(ExactInputSingleParams params) external payable returns (uint256 amountOut);
+        function exactInput(ExactInputParams params) external payable returns (uint256 amountOut);
+    }
+}
+
+/// The address of the Uniswap V3 `QuoterV2` on Ethereum mainnet.
+pub const QUOTER_V2_ADDRESS: Address = address!("61fFE014bA17989E743c5F6cB21bF9697530B21e");
+
+/// The address of the Uniswap V3 `SwapRouter02` on Ethereum mainnet.
+pub const SWAP_ROUTER_02_ADDRESS: Address = address!("68b3465833fb72A70ecDF485E0e4C7bD8665Fc45");
+
+/// A quote of a swap returned by `QuoterV2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+    /// The output of the swap.
+    pub amount_out: U256,
+    /// The gas the swap is estimated to use.
+    pub gas_estimate: U256,
+}
+
+/// Encodes a multi-hop swap path, the packed tokens with the fee tier of each pool between them.
+///
+/// Returns `None` unless there is exactly one fee per hop, or a fee does not fit in 24 bits.
+pub fn encode_path(tokens: &[Address], fees: &[u32]) -> Option<Bytes> {
+    if tokens.len() < 2 || fees.len() != tokens.len() - 1 {
+        return None;
+    }
+    let mut path = Vec::with_capacity(tokens.len() * 20 + fees.len() * 3);
+    path.extend_from_slice(tokens[0].as_slice());
+    for (token, fee) in tokens[1..].iter().zip(fees) {
+        let fee = U24::try_from(*fee).ok()?;
+        path.extend_from_slice(&fee.to_be_bytes::<3>());
+        path.extend_from_slice(token.as_slice());
+    }
+    Some(path.into())
+}
+
+/// Quotes swapping exactly `amount_in` of `token_in` for `token_out` in the pool of the fee tier.
+pub async fn quote_exact_input_single<P, T, N>(
+    provider: &P,
+    quoter: Address,
+    token_in: Address,
+    token_out: Address,
+    fee: U24,
+    amount_in: U256,
+) -> TransportResult<Quote>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let params = IQuoterV2::QuoteExactInputSingleParams {
+        tokenIn: token_in,
+        tokenOut: token_out,
+        amountIn: amount_in,
+        fee,
+        sqrtPriceLimitX96: U160::ZERO,
+    };
+    let quote = call(provider, quoter, IQuoterV2::quoteExactInputSingleCall { params }).await?;
+    Ok(Quote { amount_out: quote.amountOut, gas_estimate: quote.gasEstimate })
+}
+
+/// Quotes swapping exactly `amount_in` of the first token along the path, see [`encode_path`].
+pub async fn quote_exact_input<P, T, N>(
+    provider: &P,
+    quoter: Address,
+    path: Bytes,
+    amount_in: U256,
+) -> TransportResult<Quote>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let call_data = IQuoterV2::quoteExactInputCall { path, amountIn: amount_in };
+    let quote = call(provider, quoter, call_data).await?;
+    Ok(Quote { amount_out: quote.amountOut, gas_estimate: quote.gasEstimate })
+}
+
+/// Returns the calldata of `exactInputSingle`, swapping exactly `amount_in` of `token_in` for at
+/// least `amount_out_minimum` of `token_out` in the pool of the fee tier, sent to `recipient`.
+pub fn exact_input_single(
+    token_in: Address,
+    token_out: Address,
+    fee: U24,
+    recipient: Address,
+    amount_in: U256,
+    amount_out_minimum: U256,
+) -> Bytes {
+    let params = ISwapRouter02::ExactInputSingleParams {
+        tokenIn: token_in,
+        tokenOut: token_out,
+        fee,
+        recipient,
+        amountIn: amount_in,
+        amountOutMinimum: amount_out_minimum,
+        sqrtPriceLimitX96: U160::ZERO,
+    };
+    ISwapRouter02::exactInputSingleCall { params }.abi_encode().into()
+}
+
+/// Returns the calldata of `exactInput`, swapping exactly `amount_in` of the first token along the
+/// path for at least `amount_out_minimum` of the last, sent to `recipient`.
+pub fn exact_input(
+    path: Bytes,
+    recipient: Address,
+    amount_in: U256,
+    amount_out_minimum: U256,
+) -> Bytes {
+    let params = ISwapRouter02::ExactInputParams {
+        path,
+        recipient,
+        amountIn: amount_in,
+        amountOutMinimum: amount_out_minimum,
+    };
+    ISwapRouter02::exactInputCall { params }.abi_encode().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn encodes_paths() {
+        let (a, b, c) = (Address::repeat_byte(0xaa), Address::repeat_byte(0xbb), Address::ZERO);
+        let path = encode_path(&[a, b, c], &[500, 3000]).unwrap();
+        assert_eq!(path[..], [&a[..], &hex!("0001f4"), &b[..], &hex!("000bb8"), &c[..]].concat());
+
+        assert_eq!(encode_path(&[a, b], &[]), None);
+        assert_eq!(encode_path(&[a, b], &[1 << 24]), None);
+    }
+}
@@ -0,0 +1,61 @@
+//! Wrapped ether.
+
+use crate::predeploys::OP_WETH9;
+use alloy_chains::NamedChain;
+use alloy_primitives::{address, Address, Bytes, ChainId, U256};
+use alloy_sol_types::{sol, SolCall};
+
+sol! {
+    /// The WETH9 contract.
+    #[allow(missing_docs)]
+    interface IWETH9 {
+        function deposit() external payable;
+        function withdraw(uint256 wad) external;
+        function balanceOf(address owner) external view returns (uint256);
+    }
+}
+
+/// The address of WETH on Ethereum mainnet.
+pub const MAINNET_WETH: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+/// The address of WETH on Sepolia.
+pub const SEPOLIA_WETH: Address = address!("fFf9976782d46CC05630D1f6eBAb18b2324d6B14");
+
+/// The address of WETH on Arbitrum One.
+pub const ARBITRUM_WETH: Address = address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1");
+
+/// Returns the address of WETH on the chain, if it is known.
+pub fn weth(chain_id: ChainId) -> Option<Address> {
+    let chain = NamedChain::try_from(chain_id).ok()?;
+    match chain {
+        NamedChain::Mainnet => Some(MAINNET_WETH),
+        NamedChain::Sepolia => Some(SEPOLIA_WETH),
+        NamedChain::Arbitrum => Some(ARBITRUM_WETH),
+        chain if chain.is_optimism() => Some(OP_WETH9),
+        _ => None,
+    }
+}
+
+/// Returns the calldata of `deposit`, wrapping the value of the transaction.
+pub fn deposit() -> Bytes {
+    IWETH9::depositCall {}.abi_encode().into()
+}
+
+/// Returns the calldata of `withdraw`, unwrapping `amount` to ether.
+pub fn withdraw(amount: U256) -> Bytes {
+    IWETH9::withdrawCall { wad: amount }.abi_encode().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn builds_calls() {
+        assert_eq!(deposit()[..], hex!("d0e30db0"));
+        assert_eq!(withdraw(U256::from(1))[..4], hex!("2e1a7d4d"));
+        assert_eq!(weth(NamedChain::Base.into()), Some(OP_WETH9));
+        assert_eq!(weth(NamedChain::Polygon.into()), None);
+    }
+}
@@ -1,11 +1,14 @@
 //! This module extends the Ethereum JSON-RPC provider with the Anvil namespace's RPC methods.
 
 use crate::Provider;
-use alloy_network::Network;
+use alloy_eips::BlockNumberOrTag;
+use alloy_json_rpc::RpcError;
+use alloy_network::{BlockResponse, HeaderResponse, Network};
 use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
 use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
 use alloy_rpc_types_eth::Block;
-use alloy_transport::{Transport, TransportResult};
+use alloy_transport::{utils::Spawnable, RpcFut, Transport, TransportErrorKind, TransportResult};
+use std::{fmt, marker::PhantomData, time::Duration};
 
 /// Anvil namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -108,6 +111,21 @@ pub trait AnvilApi<N: Network, T>: Send + Sync {
     /// Takes a single parameter, which is the snapshot id to revert to.
     async fn anvil_revert(&self, id: U256) -> TransportResult<bool>;
 
+    /// Takes a snapshot, returning a guard that reverts to it explicitly or when dropped.
+    ///
+    /// See [`SnapshotGuard`].
+    async fn anvil_snapshot_guard(&self) -> TransportResult<SnapshotGuard>;
+
+    /// Resets the node to a fork of the given endpoint, at the given block or the latest block.
+    async fn anvil_fork_at(&self, url: String, block: Option<u64>) -> TransportResult<()>;
+
+    /// Moves the node to the given block.
+    ///
+    /// If the node is a fork, it is reset to a fork of the same endpoint at the block, discarding
+    /// all local changes. Otherwise, empty blocks are mined until the block is reached, which
+    /// fails if the block is in the past.
+    async fn anvil_roll_to(&self, block: u64) -> TransportResult<()>;
+
     /// Jump forward in time by the given amount of time, in seconds.
     async fn anvil_increase_time(&self, seconds: U256) -> TransportResult<i64>;
 
@@ -143,6 +161,14 @@ pub trait AnvilApi<N: Network, T>: Send + Sync {
         &self,
         request: N::TransactionRequest,
     ) -> TransportResult<TxHash>;
+
+    /// Returns a [`TimeMachine`] to control the block timestamps of the node.
+    fn time_machine(&self) -> TimeMachine<'_, Self, N, T>
+    where
+        Self: Sized,
+    {
+        TimeMachine::new(self)
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -266,6 +292,38 @@ where
         self.client().request("evm_revert", (id,)).await
     }
 
+    async fn anvil_snapshot_guard(&self) -> TransportResult<SnapshotGuard> {
+        let id = self.anvil_snapshot().await?;
+        let client = self.weak_client();
+        let revert = Box::pin(async move {
+            let client = client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+            client.request("evm_revert", (id,)).await
+        });
+        Ok(SnapshotGuard { id, revert: Some(revert) })
+    }
+
+    async fn anvil_fork_at(&self, url: String, block: Option<u64>) -> TransportResult<()> {
+        self.anvil_reset(Some(Forking { json_rpc_url: Some(url), block_number: block })).await
+    }
+
+    async fn anvil_roll_to(&self, block: u64) -> TransportResult<()> {
+        let info = self.anvil_node_info().await?;
+        if let Some(url) = info.fork_config.fork_url {
+            return self.anvil_fork_at(url, Some(block)).await;
+        }
+
+        let current = info.current_block_number;
+        if block < current {
+            return Err(RpcError::local_usage_str(
+                "can not roll back a chain that is not a fork, use a snapshot instead",
+            ));
+        }
+        if block > current {
+            self.anvil_mine(Some(U256::from(block - current)), None).await?;
+        }
+        Ok(())
+    }
+
     async fn anvil_increase_time(&self, seconds: U256) -> TransportResult<i64> {
         self.client().request("evm_increaseTime", (seconds,)).await
     }
@@ -310,6 +368,158 @@ where
     }
 }
 
+/// A snapshot of an Anvil node, which is reverted to explicitly or when the guard is dropped.
+///
+/// Returned by [`AnvilApi::anvil_snapshot_guard`]. Use [`SnapshotGuard::revert`] to revert and
+/// wait for the node to confirm it, or [`SnapshotGuard::keep`] to keep the changes.
+///
+/// Reverting on drop is best effort: the revert is sent from a detached task, which may never run,
+/// e.g. if the runtime shuts down after a test panicked. If the guard is dropped outside of a
+/// Tokio runtime, the snapshot is not reverted and a warning is logged. Tests sharing a node should call [`SnapshotGuard::revert`] explicitly, and only rely
+/// on the drop for early returns.
+#[must_use = "the snapshot is reverted immediately if the guard is not kept"]
+pub struct SnapshotGuard {
+    id: U256,
+    revert: Option<RpcFut<'static, bool>>,
+}
+
+impl fmt::Debug for SnapshotGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapshotGuard").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+impl SnapshotGuard {
+    /// Returns the ID of the snapshot.
+    pub const fn id(&self) -> U256 {
+        self.id
+    }
+
+    /// Reverts to the snapshot, returning whether the node reverted.
+    ///
+    /// Unlike dropping the guard, this waits for the revert and reports its errors.
+    pub async fn revert(mut self) -> TransportResult<bool> {
+        self.revert.take().expect("not reverted").await
+    }
+
+    /// Keeps the changes made since the snapshot, returning the ID of the snapshot.
+    pub fn keep(mut self) -> U256 {
+        self.revert = None;
+        self.id
+    }
+}
+
+impl Drop for SnapshotGuard {
+    /// Reverts to the snapshot in a detached task, on a best effort basis.
+    ///
+    /// Outside of a Tokio runtime, no task can be spawned, and the snapshot is not reverted.
+    fn drop(&mut self) {
+        if let Some(revert) = self.revert.take() {
+            let id = self.id;
+            #[cfg(not(target_arch = "wasm32"))]
+            if tokio::runtime::Handle::try_current().is_err() {
+                warn!(%id, "snapshot guard dropped outside of a Tokio runtime, not reverting");
+                return;
+            }
+            async move {
+                if let Err(err) = revert.await {
+                    warn!(%id, %err, "failed to revert snapshot");
+                }
+            }
+            .spawn_task();
+        }
+    }
+}
+
+/// Controls the block timestamps of an Anvil node, for testing time-dependent contracts.
+///
+/// Returned by [`AnvilApi::time_machine`]. The methods return the time machine again, so calls can
+/// be chained:
+///
+/// ```ignore
+/// let time = provider.time_machine();
+/// time.warp_by(Duration::from_secs(3600)).await?.mine(1).await?;
+/// ```
+pub struct TimeMachine<'a, P, N, T> {
+    provider: &'a P,
+    _pd: PhantomData<fn() -> (N, T)>,
+}
+
+impl<P, N, T> fmt::Debug for TimeMachine<'_, P, N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeMachine").finish_non_exhaustive()
+    }
+}
+
+impl<'a, P, N, T> TimeMachine<'a, P, N, T> {
+    /// Creates a new time machine for the provider.
+    pub const fn new(provider: &'a P) -> Self {
+        Self { provider, _pd: PhantomData }
+    }
+}
+
+impl<P, N, T> TimeMachine<'_, P, N, T>
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    /// Returns the timestamp of the latest block.
+    pub async fn now(&self) -> TransportResult<u64> {
+        let block = self.provider.get_block_by_number(BlockNumberOrTag::Latest, false).await?;
+        let block = block.ok_or(RpcError::NullResp)?;
+        Ok(block.header().timestamp())
+    }
+
+    /// Mines a block with the given timestamp.
+    pub async fn warp_to(&self, timestamp: u64) -> TransportResult<&Self> {
+        self.set_next_timestamp(timestamp).await?;
+        self.mine(1).await
+    }
+
+    /// Moves the time forward by the duration, and mines a block at the new time.
+    pub async fn warp_by(&self, duration: Duration) -> TransportResult<&Self> {
+        self.provider.anvil_increase_time(U256::from(duration.as_secs())).await?;
+        self.mine(1).await
+    }
+
+    /// Sets the timestamp of the next block, without mining it.
+    pub async fn set_next_timestamp(&self, timestamp: u64) -> TransportResult<&Self> {
+        self.provider.anvil_set_next_block_timestamp(timestamp).await?;
+        Ok(self)
+    }
+
+    /// Sets the interval between the timestamps of consecutive blocks.
+    pub async fn set_interval(&self, interval: Duration) -> TransportResult<&Self> {
+        self.provider.anvil_set_block_timestamp_interval(interval.as_secs()).await?;
+        Ok(self)
+    }
+
+    /// Removes the interval set with [`TimeMachine::set_interval`].
+    pub async fn clear_interval(&self) -> TransportResult<&Self> {
+        self.provider.anvil_remove_block_timestamp_interval().await?;
+        Ok(self)
+    }
+
+    /// Disables automining, so blocks are only mined with [`TimeMachine::mine`].
+    pub async fn pause(&self) -> TransportResult<&Self> {
+        self.provider.anvil_set_auto_mine(false).await?;
+        Ok(self)
+    }
+
+    /// Enables automining.
+    pub async fn resume(&self) -> TransportResult<&Self> {
+        self.provider.anvil_set_auto_mine(true).await?;
+        Ok(self)
+    }
+
+    /// Mines the given number of blocks.
+    pub async fn mine(&self, blocks: u64) -> TransportResult<&Self> {
+        self.provider.anvil_mine(Some(U256::from(blocks)), None).await?;
+        Ok(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -733,6 +943,63 @@ mod tests {
         assert_eq!(tx_count, 0);
     }
 
+    #[tokio::test]
+    async fn test_anvil_snapshot_guard() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let start = provider.get_block_number().await.unwrap();
+
+        let snapshot = provider.anvil_snapshot_guard().await.unwrap();
+        provider.evm_mine(None).await.unwrap();
+        assert!(snapshot.revert().await.unwrap());
+        assert_eq!(provider.get_block_number().await.unwrap(), start);
+
+        let snapshot = provider.anvil_snapshot_guard().await.unwrap();
+        provider.evm_mine(None).await.unwrap();
+        snapshot.keep();
+        assert_eq!(provider.get_block_number().await.unwrap(), start + 1);
+    }
+
+    #[test]
+    fn snapshot_guard_dropped_outside_runtime() {
+        let guard = SnapshotGuard { id: U256::from(1), revert: Some(Box::pin(async { Ok(true) })) };
+        drop(guard);
+    }
+
+    #[tokio::test]
+    async fn test_anvil_roll_to() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let start = provider.get_block_number().await.unwrap();
+
+        provider.anvil_roll_to(start + 5).await.unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), start + 5);
+
+        let err = provider.anvil_roll_to(start).await.unwrap_err();
+        assert!(matches!(err, RpcError::LocalUsageError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_time_machine() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let time = provider.time_machine();
+
+        let start = time.now().await.unwrap();
+        time.warp_to(start + 1000).await.unwrap();
+        assert_eq!(time.now().await.unwrap(), start + 1000);
+
+        time.warp_by(Duration::from_secs(60)).await.unwrap();
+        assert!(time.now().await.unwrap() >= start + 1060);
+
+        let now = time.now().await.unwrap();
+        time.set_interval(Duration::from_secs(10)).await.unwrap().mine(1).await.unwrap();
+        assert_eq!(time.now().await.unwrap(), now + 10);
+        time.clear_interval().await.unwrap();
+
+        time.pause().await.unwrap();
+        assert!(!provider.anvil_get_auto_mine().await.unwrap());
+        time.resume().await.unwrap();
+        assert!(provider.anvil_get_auto_mine().await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_anvil_increase_time() {
         let provider = ProviderBuilder::new().on_anvil();
@@ -1,14 +1,18 @@
 //! This module extends the Ethereum JSON-RPC provider with the Debug namespace's RPC methods.
 use crate::Provider;
-use alloy_network::Network;
+use alloy_json_rpc::RpcError;
+use alloy_network::{BlockResponse, Network};
 use alloy_primitives::{hex, Bytes, TxHash, B256};
+use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
     Block, BlockId, BlockNumberOrTag, Bundle, StateContext, TransactionRequest,
 };
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
-use alloy_transport::{Transport, TransportResult};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use async_stream::stream;
+use futures::Stream;
 
 /// Debug namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -96,6 +100,24 @@ pub trait DebugApi<N, T>: Send + Sync {
         trace_options: GethDebugTracingOptions,
     ) -> TransportResult<Vec<TraceResult>>;
 
+    /// Same as `debug_trace_block_by_number`, but traces the transactions of the block one by one
+    /// with `debug_traceTransaction`, yielding each trace as it completes.
+    ///
+    /// This avoids buffering the traces of the entire block in a single response, e.g. for
+    /// `prestateTracer` traces of large blocks.
+    ///
+    /// Failures to trace a transaction are yielded as [`TraceResult::Error`]. The stream ends after
+    /// the last transaction, or after the first transport error.
+    ///
+    /// # Note
+    ///
+    /// Not all nodes support this call.
+    fn debug_trace_block_by_number_stream(
+        &self,
+        block: BlockNumberOrTag,
+        trace_options: GethDebugTracingOptions,
+    ) -> impl Stream<Item = TransportResult<TraceResult>> + Send + 'static;
+
     /// Executes the given transaction without publishing it like `eth_call` and returns the trace
     /// of the execution.
     ///
@@ -128,6 +150,20 @@ pub trait DebugApi<N, T>: Send + Sync {
         state_context: StateContext,
         trace_options: GethDebugTracingCallOptions,
     ) -> TransportResult<Vec<GethTrace>>;
+
+    /// Re-executes a block to generate its execution witness, which contains the preimages of all
+    /// trie nodes, contract codes and keys required during the execution of the block, including
+    /// during state root recomputation.
+    ///
+    /// The first argument is the block number or tag of the block to re-execute.
+    ///
+    /// # Note
+    ///
+    /// Not all nodes support this call.
+    async fn debug_execution_witness(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> TransportResult<ExecutionWitness>;
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -199,6 +235,51 @@ where
         self.client().request("debug_traceBlockByNumber", (block, trace_options)).await
     }
 
+    fn debug_trace_block_by_number_stream(
+        &self,
+        block: BlockNumberOrTag,
+        trace_options: GethDebugTracingOptions,
+    ) -> impl Stream<Item = TransportResult<TraceResult>> + Send + 'static {
+        let client = self.weak_client();
+        stream! {
+            let Some(client) = client.upgrade() else {
+                yield Err(TransportErrorKind::backend_gone());
+                return;
+            };
+            let block: Option<N::BlockResponse> =
+                match client.request("eth_getBlockByNumber", (block, false)).await {
+                    Ok(block) => block,
+                    Err(err) => {
+                        yield Err(err);
+                        return;
+                    }
+                };
+            let Some(block) = block else {
+                yield Err(RpcError::NullResp);
+                return;
+            };
+
+            for hash in block.transactions().hashes() {
+                let trace = client
+                    .request("debug_traceTransaction", (hash, trace_options.clone()))
+                    .await;
+                match trace {
+                    Ok(result) => yield Ok(TraceResult::Success { result, tx_hash: Some(hash) }),
+                    Err(RpcError::ErrorResp(err)) => {
+                        yield Ok(TraceResult::Error {
+                            error: err.message.into_owned(),
+                            tx_hash: Some(hash),
+                        })
+                    }
+                    Err(err) => {
+                        yield Err(err);
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
     async fn debug_trace_call(
         &self,
         tx: TransactionRequest,
@@ -216,6 +297,13 @@ where
     ) -> TransportResult<Vec<GethTrace>> {
         self.client().request("debug_traceCallMany", (bundles, state_context, trace_options)).await
     }
+
+    async fn debug_execution_witness(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> TransportResult<ExecutionWitness> {
+        self.client().request("debug_executionWitness", (block,)).await
+    }
 }
 
 #[cfg(test)]
@@ -226,6 +314,7 @@ mod test {
     use alloy_network::TransactionBuilder;
     use alloy_node_bindings::{utils::run_with_tempdir, Geth, Reth};
     use alloy_primitives::{address, U256};
+    use futures::StreamExt;
 
     fn init_tracing() {
         let _ = tracing_subscriber::fmt::try_init();
@@ -257,6 +346,29 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_debug_trace_block_by_number_stream() {
+        init_tracing();
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_wallet();
+        let from = provider.default_signer_address();
+
+        let tx = TransactionRequest::default()
+            .from(from)
+            .to(address!("deadbeef00000000deadbeef00000000deadbeef"))
+            .value(U256::from(100));
+        let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+        let block = receipt.block_number.unwrap().into();
+
+        let traces = provider
+            .debug_trace_block_by_number_stream(block, GethDebugTracingOptions::default())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(traces.len(), 1);
+        let trace = traces.into_iter().next().unwrap().unwrap();
+        assert_eq!(trace.tx_hash(), Some(receipt.transaction_hash));
+        assert!(matches!(trace, TraceResult::Success { .. }));
+    }
+
     #[tokio::test]
     async fn test_debug_trace_call() {
         init_tracing();
@@ -389,4 +501,22 @@ mod test {
         })
         .await
     }
+
+    #[tokio::test]
+    #[cfg(not(windows))]
+    async fn debug_execution_witness() {
+        run_with_tempdir("reth-test-", |temp_dir| async move {
+            let reth = Reth::new().dev().disable_discovery().data_dir(temp_dir).spawn();
+            let provider =
+                ProviderBuilder::new().with_recommended_fillers().on_http(reth.endpoint_url());
+
+            let witness = provider
+                .debug_execution_witness(BlockNumberOrTag::Latest)
+                .await
+                .expect("debug_executionWitness call should succeed");
+
+            assert_eq!(witness.find_invalid_preimage(), None);
+        })
+        .await
+    }
 }
@@ -0,0 +1,109 @@
+//! This module extends the Ethereum JSON-RPC provider with the legacy mining RPC methods of the Eth
+//! and Miner namespaces.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::{Address, B256, B64, U256, U64};
+use alloy_rpc_types_eth::Work;
+use alloy_transport::{Transport, TransportResult};
+
+/// Mining rpc interface for proof-of-work and dev chains that still rely on the legacy mining
+/// methods.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait MinerApi<N, T>: Send + Sync {
+    /// Returns the hash of the current block, the seed hash and the target to meet.
+    async fn eth_get_work(&self) -> TransportResult<Work>;
+
+    /// Submits a proof-of-work solution, returning whether it was accepted.
+    async fn eth_submit_work(
+        &self,
+        nonce: B64,
+        pow_hash: B256,
+        mix_digest: B256,
+    ) -> TransportResult<bool>;
+
+    /// Reports the hashrate of a remote miner with the given ID.
+    async fn eth_submit_hashrate(&self, hashrate: U256, id: B256) -> TransportResult<bool>;
+
+    /// Returns the number of hashes per second the node is mining with.
+    async fn eth_hashrate(&self) -> TransportResult<u64>;
+
+    /// Returns whether the node is mining.
+    async fn eth_mining(&self) -> TransportResult<bool>;
+
+    /// Starts mining.
+    async fn miner_start(&self) -> TransportResult<()>;
+
+    /// Stops mining.
+    async fn miner_stop(&self) -> TransportResult<()>;
+
+    /// Sets the address that receives the mining rewards.
+    async fn miner_set_etherbase(&self, etherbase: Address) -> TransportResult<bool>;
+
+    /// Sets the gas limit the miner targets when mining blocks.
+    async fn miner_set_gas_limit(&self, gas_limit: u64) -> TransportResult<bool>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> MinerApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn eth_get_work(&self) -> TransportResult<Work> {
+        self.client().request_noparams("eth_getWork").await
+    }
+
+    async fn eth_submit_work(
+        &self,
+        nonce: B64,
+        pow_hash: B256,
+        mix_digest: B256,
+    ) -> TransportResult<bool> {
+        self.client().request("eth_submitWork", (nonce, pow_hash, mix_digest)).await
+    }
+
+    async fn eth_submit_hashrate(&self, hashrate: U256, id: B256) -> TransportResult<bool> {
+        self.client().request("eth_submitHashrate", (hashrate, id)).await
+    }
+
+    async fn eth_hashrate(&self) -> TransportResult<u64> {
+        self.client().request_noparams("eth_hashrate").map_resp(crate::utils::convert_u64).await
+    }
+
+    async fn eth_mining(&self) -> TransportResult<bool> {
+        self.client().request_noparams("eth_mining").await
+    }
+
+    async fn miner_start(&self) -> TransportResult<()> {
+        self.client().request_noparams("miner_start").await
+    }
+
+    async fn miner_stop(&self) -> TransportResult<()> {
+        self.client().request_noparams("miner_stop").await
+    }
+
+    async fn miner_set_etherbase(&self, etherbase: Address) -> TransportResult<bool> {
+        self.client().request("miner_setEtherbase", (etherbase,)).await
+    }
+
+    async fn miner_set_gas_limit(&self, gas_limit: u64) -> TransportResult<bool> {
+        self.client().request("miner_setGasLimit", (U64::from(gas_limit),)).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProviderBuilder;
+
+    #[tokio::test]
+    async fn call_eth_mining() {
+        let provider = ProviderBuilder::new().on_anvil();
+        // anvil mines on demand, so it is always mining
+        assert!(provider.eth_mining().await.unwrap());
+        assert_eq!(provider.eth_hashrate().await.unwrap(), 0);
+    }
+}
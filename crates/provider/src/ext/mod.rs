@@ -8,7 +8,7 @@ pub use admin::AdminApi;
 #[cfg(feature = "anvil-api")]
 mod anvil;
 #[cfg(feature = "anvil-api")]
-pub use anvil::AnvilApi;
+pub use anvil::{AnvilApi, SnapshotGuard, TimeMachine};
 
 #[cfg(feature = "engine-api")]
 mod engine;
@@ -20,6 +20,11 @@ mod debug;
 #[cfg(feature = "debug-api")]
 pub use debug::DebugApi;
 
+#[cfg(feature = "miner-api")]
+mod miner;
+#[cfg(feature = "miner-api")]
+pub use miner::MinerApi;
+
 #[cfg(feature = "net-api")]
 mod net;
 #[cfg(feature = "net-api")]
@@ -30,6 +35,11 @@ mod trace;
 #[cfg(feature = "trace-api")]
 pub use trace::{TraceApi, TraceCallList};
 
+#[cfg(feature = "personal-api")]
+mod personal;
+#[cfg(feature = "personal-api")]
+pub use personal::PersonalApi;
+
 #[cfg(feature = "rpc-api")]
 mod rpc;
 #[cfg(feature = "rpc-api")]
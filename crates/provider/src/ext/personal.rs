@@ -0,0 +1,139 @@
+//! This module extends the Ethereum JSON-RPC provider with the Personal namespace's RPC methods.
+//!
+//! These methods manage accounts whose keys are held by the node, and send passphrases and raw
+//! private keys to the node in plain text. They should only be used with private or development
+//! nodes over trusted connections.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::{Address, TxHash, B256};
+use alloy_transport::{Transport, TransportResult};
+
+/// Personal namespace rpc interface that gives access to the accounts managed by the node.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait PersonalApi<N: Network, T>: Send + Sync {
+    /// Returns the addresses of the accounts managed by the node.
+    async fn personal_list_accounts(&self) -> TransportResult<Vec<Address>>;
+
+    /// Creates a new account protected by the passphrase, returning its address.
+    async fn personal_new_account(&self, passphrase: &str) -> TransportResult<Address>;
+
+    /// Imports the raw private key as an account protected by the passphrase, returning its
+    /// address.
+    async fn personal_import_raw_key(
+        &self,
+        private_key: B256,
+        passphrase: &str,
+    ) -> TransportResult<Address>;
+
+    /// Unlocks the account for the given number of seconds, or for the node's default duration if
+    /// `None`.
+    ///
+    /// Returns whether the account was unlocked.
+    async fn personal_unlock_account(
+        &self,
+        address: Address,
+        passphrase: &str,
+        duration: Option<u64>,
+    ) -> TransportResult<bool>;
+
+    /// Locks the account, returning whether it was locked.
+    async fn personal_lock_account(&self, address: Address) -> TransportResult<bool>;
+
+    /// Unlocks the `from` account of the transaction with the passphrase, then signs and sends the
+    /// transaction, returning its hash.
+    ///
+    /// The account is only unlocked for this transaction.
+    async fn personal_send_transaction(
+        &self,
+        tx: N::TransactionRequest,
+        passphrase: &str,
+    ) -> TransportResult<TxHash>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> PersonalApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn personal_list_accounts(&self) -> TransportResult<Vec<Address>> {
+        self.client().request_noparams("personal_listAccounts").await
+    }
+
+    async fn personal_new_account(&self, passphrase: &str) -> TransportResult<Address> {
+        self.client().request("personal_newAccount", (passphrase,)).await
+    }
+
+    async fn personal_import_raw_key(
+        &self,
+        private_key: B256,
+        passphrase: &str,
+    ) -> TransportResult<Address> {
+        // the key is expected without the `0x` prefix
+        let private_key = alloy_primitives::hex::encode(private_key);
+        self.client().request("personal_importRawKey", (private_key, passphrase)).await
+    }
+
+    async fn personal_unlock_account(
+        &self,
+        address: Address,
+        passphrase: &str,
+        duration: Option<u64>,
+    ) -> TransportResult<bool> {
+        self.client().request("personal_unlockAccount", (address, passphrase, duration)).await
+    }
+
+    async fn personal_lock_account(&self, address: Address) -> TransportResult<bool> {
+        self.client().request("personal_lockAccount", (address,)).await
+    }
+
+    async fn personal_send_transaction(
+        &self,
+        tx: N::TransactionRequest,
+        passphrase: &str,
+    ) -> TransportResult<TxHash> {
+        self.client().request("personal_sendTransaction", (tx, passphrase)).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_network::TransactionBuilder;
+    use alloy_node_bindings::{utils::run_with_tempdir, Geth};
+    use alloy_primitives::{address, b256, U256};
+    use alloy_rpc_types_eth::TransactionRequest;
+
+    #[tokio::test]
+    async fn manage_node_accounts() {
+        run_with_tempdir("geth-test-", |temp_dir| async move {
+            let geth = Geth::new().disable_discovery().insecure_unlock().data_dir(temp_dir).spawn();
+            let provider = ProviderBuilder::new().on_http(geth.endpoint_url());
+
+            let created = provider.personal_new_account("secret").await.unwrap();
+            let key = b256!("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80");
+            let imported = provider.personal_import_raw_key(key, "secret").await.unwrap();
+            assert_eq!(imported, address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+
+            let accounts = provider.personal_list_accounts().await.unwrap();
+            assert!(accounts.contains(&created));
+            assert!(accounts.contains(&imported));
+
+            assert!(provider.personal_unlock_account(imported, "wrong", None).await.is_err());
+            assert!(provider.personal_unlock_account(imported, "secret", Some(60)).await.unwrap());
+            assert!(provider.personal_lock_account(imported).await.unwrap());
+
+            let tx = TransactionRequest::default()
+                .with_from(created)
+                .with_to(imported)
+                .with_value(U256::ZERO);
+            // the new account has no funds to pay for gas
+            assert!(provider.personal_send_transaction(tx, "secret").await.is_err());
+        })
+        .await;
+    }
+}
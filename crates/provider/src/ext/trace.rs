@@ -1,14 +1,19 @@
 //! This module extends the Ethereum JSON-RPC provider with the Trace namespace's RPC methods.
-use crate::{Provider, RpcWithBlock};
+use crate::{Funding, Provider, RpcWithBlock, StateChange};
 use alloy_eips::BlockId;
+use alloy_json_rpc::RpcError;
 use alloy_network::Network;
-use alloy_primitives::TxHash;
+use alloy_primitives::{Address, BlockNumber, TxHash};
 use alloy_rpc_types_eth::Index;
 use alloy_rpc_types_trace::{
-    filter::TraceFilter,
-    parity::{LocalizedTransactionTrace, TraceResults, TraceResultsWithTransactionHash, TraceType},
+    filter::{TraceFilter, TraceFilterMode},
+    parity::{
+        Action, LocalizedTransactionTrace, TraceOutput, TraceResults,
+        TraceResultsWithTransactionHash, TraceType,
+    },
 };
 use alloy_transport::{Transport, TransportResult};
+use std::collections::BTreeSet;
 
 /// List of trace calls for use with [`TraceApi::trace_call_many`]
 pub type TraceCallList<'a, N> = &'a [(<N as Network>::TransactionRequest, &'a [TraceType])];
@@ -96,6 +101,42 @@ where
         block: BlockId,
         trace_types: &[TraceType],
     ) -> TransportResult<Vec<TraceResultsWithTransactionHash>>;
+
+    /// Returns the blocks in `from_block + 1..=to_block` in which transactions changed the balance
+    /// of the account, with the balance before and after each of them.
+    ///
+    /// If the node supports `trace_filter`, only the blocks with traces from or to the account are
+    /// checked, so changes that are not caused by transactions, such as withdrawals and block
+    /// rewards, are not found. Otherwise, this falls back to [`Provider::get_balance_changes`].
+    ///
+    /// The default implementation returns [`RpcError::UnsupportedFeature`].
+    async fn trace_balance_changes(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Vec<StateChange>> {
+        let _ = (address, from_block, to_block);
+        Err(RpcError::UnsupportedFeature("trace_balance_changes"))
+    }
+
+    /// Returns the first funding of the account in `from_block..=to_block`.
+    ///
+    /// If the node supports `trace_filter`, this is the first successful trace that transferred
+    /// value to the account, created it, refunded it a self-destructed balance, or rewarded it,
+    /// including internal calls. Accounts funded otherwise, such as by withdrawals or the genesis
+    /// allocation, and nodes without `trace_filter` fall back to [`Provider::get_funding`].
+    ///
+    /// The default implementation returns [`RpcError::UnsupportedFeature`].
+    async fn trace_funding(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Option<Funding>> {
+        let _ = (address, from_block, to_block);
+        Err(RpcError::UnsupportedFeature("trace_funding"))
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -172,6 +213,104 @@ where
     ) -> TransportResult<Vec<TraceResultsWithTransactionHash>> {
         self.client().request("trace_replayBlockTransactions", (block, trace_types)).await
     }
+
+    async fn trace_balance_changes(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Vec<StateChange>> {
+        if from_block >= to_block || !self.supports("trace_filter").await? {
+            return self.get_balance_changes(address, from_block, to_block).await;
+        }
+
+        let filter = TraceFilter::default()
+            .from_block(from_block + 1)
+            .to_block(to_block)
+            .from_address(vec![address])
+            .to_address(vec![address])
+            .mode(TraceFilterMode::Union);
+        let blocks: BTreeSet<_> = self
+            .trace_filter(&filter)
+            .await?
+            .into_iter()
+            .filter_map(|trace| trace.block_number)
+            .collect();
+
+        let mut changes = Vec::new();
+        for block_number in blocks {
+            let before = self.get_balance(address).block_id((block_number - 1).into()).await?;
+            let after = self.get_balance(address).block_id(block_number.into()).await?;
+            if before != after {
+                changes.push(StateChange { block_number, before, after });
+            }
+        }
+        Ok(changes)
+    }
+
+    async fn trace_funding(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Option<Funding>> {
+        if !self.supports("trace_filter").await? {
+            return self.get_funding(address, from_block, to_block).await;
+        }
+
+        let mut after = 0;
+        loop {
+            let filter = TraceFilter::default()
+                .from_block(from_block)
+                .to_block(to_block)
+                .to_address(vec![address])
+                .after(after)
+                .count(TRACE_FILTER_PAGE_SIZE);
+            let traces = self.trace_filter(&filter).await?;
+            let page_len = traces.len() as u64;
+            if let Some(funding) = traces.iter().find_map(|trace| funding_of(trace, address)) {
+                return Ok(Some(funding));
+            }
+            if page_len < TRACE_FILTER_PAGE_SIZE {
+                return self.get_funding(address, from_block, to_block).await;
+            }
+            after += page_len;
+        }
+    }
+}
+
+/// The number of traces requested at once by [`TraceApi::trace_funding`].
+const TRACE_FILTER_PAGE_SIZE: u64 = 100;
+
+/// Returns the funding of the account by the trace, if it is a successful transfer to it.
+fn funding_of(trace: &LocalizedTransactionTrace, address: Address) -> Option<Funding> {
+    if trace.trace.error.is_some() {
+        return None;
+    }
+    let (funder, value) = match &trace.trace.action {
+        Action::Call(call) if call.to == address && !call.value.is_zero() => {
+            (Some(call.from), call.value)
+        }
+        Action::Create(create) => match &trace.trace.result {
+            Some(TraceOutput::Create(output)) if output.address == address => {
+                (Some(create.from), create.value)
+            }
+            _ => return None,
+        },
+        Action::Selfdestruct(selfdestruct)
+            if selfdestruct.refund_address == address && !selfdestruct.balance.is_zero() =>
+        {
+            (Some(selfdestruct.address), selfdestruct.balance)
+        }
+        Action::Reward(reward) if reward.author == address => (None, reward.value),
+        _ => return None,
+    };
+    Some(Funding {
+        block_number: trace.block_number?,
+        transaction_hash: trace.transaction_hash,
+        funder,
+        value,
+    })
 }
 
 #[cfg(test)]
@@ -180,8 +319,9 @@ mod test {
     use alloy_eips::BlockNumberOrTag;
     use alloy_network::TransactionBuilder;
     use alloy_node_bindings::{utils::run_with_tempdir, Reth};
-    use alloy_primitives::address;
+    use alloy_primitives::{address, U256};
     use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_rpc_types_trace::parity::{CallAction, TransactionTrace};
 
     use super::*;
 
@@ -326,4 +466,41 @@ mod test {
         })
         .await;
     }
+
+    #[test]
+    fn finds_funding_traces() {
+        let address = address!("0000000000000000000000000000000000000789");
+        let funder = address!("0000000000000000000000000000000000000456");
+        let localized = |action, error| LocalizedTransactionTrace {
+            trace: TransactionTrace { action, error, ..Default::default() },
+            block_hash: None,
+            block_number: Some(7),
+            transaction_hash: Some(TxHash::repeat_byte(1)),
+            transaction_position: Some(0),
+        };
+        let call = |to, value| {
+            Action::Call(CallAction {
+                from: funder,
+                to,
+                value: U256::from(value),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(funding_of(&localized(call(address, 0), None), address), None);
+        assert_eq!(funding_of(&localized(call(funder, 5), None), address), None);
+        assert_eq!(
+            funding_of(&localized(call(address, 5), Some("Reverted".into())), address),
+            None
+        );
+        assert_eq!(
+            funding_of(&localized(call(address, 5), None), address),
+            Some(Funding {
+                block_number: 7,
+                transaction_hash: Some(TxHash::repeat_byte(1)),
+                funder: Some(funder),
+                value: U256::from(5),
+            })
+        );
+    }
 }
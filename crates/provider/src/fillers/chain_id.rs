@@ -1,5 +1,6 @@
 use std::sync::{Arc, OnceLock};
 
+use alloy_json_rpc::RpcError;
 use alloy_network::{Network, TransactionBuilder};
 use alloy_primitives::ChainId;
 use alloy_transport::TransportResult;
@@ -17,7 +18,8 @@ use crate::{
 /// future transactions.
 ///
 /// Transactions that already have a chain_id set by the user will not be
-/// modified.
+/// modified. A [strict](ChainIdFiller::strict) filler additionally checks
+/// these chain IDs against the chain ID of the provider.
 ///
 /// # Example
 ///
@@ -36,7 +38,32 @@ use crate::{
 /// # }
 /// ```
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub struct ChainIdFiller(Arc<OnceLock<ChainId>>);
+pub struct ChainIdFiller {
+    chain_id: Arc<OnceLock<ChainId>>,
+    strict: Option<Arc<OnceLock<ChainId>>>,
+}
+
+/// An error returned by a strict [`ChainIdFiller`] when a chain ID does not match the chain ID of
+/// the provider.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ChainIdMismatch {
+    /// The chain ID the filler was configured with does not match.
+    #[error("configured chain ID {configured} does not match the provider chain ID {provider}")]
+    Configured {
+        /// The configured chain ID.
+        configured: ChainId,
+        /// The chain ID of the provider.
+        provider: ChainId,
+    },
+    /// The chain ID set on the transaction does not match.
+    #[error("transaction chain ID {transaction} does not match the provider chain ID {provider}")]
+    Transaction {
+        /// The chain ID of the transaction.
+        transaction: ChainId,
+        /// The chain ID of the provider.
+        provider: ChainId,
+    },
+}
 
 impl ChainIdFiller {
     /// Create a new [`ChainIdFiller`] with an optional chain ID.
@@ -49,7 +76,42 @@ impl ChainIdFiller {
         if let Some(chain_id) = chain_id {
             lock.set(chain_id).expect("brand new");
         }
-        Self(Arc::new(lock))
+        Self { chain_id: Arc::new(lock), strict: None }
+    }
+
+    /// Create a new strict [`ChainIdFiller`] with an optional chain ID.
+    ///
+    /// In addition to filling the chain ID, a strict filler fetches the chain ID of the provider
+    /// once and rejects transactions with a [`ChainIdMismatch`] error if either the configured
+    /// chain ID or a chain ID already set on the transaction differs from it. This prevents
+    /// signing transactions for the wrong chain, e.g. by passing the chain ID of the signer:
+    ///
+    /// ```
+    /// # use alloy_provider::fillers::ChainIdFiller;
+    /// # use alloy_signer::Signer;
+    /// # fn example(signer: impl Signer) {
+    /// let filler = ChainIdFiller::strict(signer.chain_id());
+    /// # }
+    /// ```
+    pub fn strict(chain_id: Option<ChainId>) -> Self {
+        Self { strict: Some(Default::default()), ..Self::new(chain_id) }
+    }
+
+    /// Returns `true` if this is a strict filler.
+    pub const fn is_strict(&self) -> bool {
+        self.strict.is_some()
+    }
+
+    /// Checks the chain ID of the transaction and the configured chain ID against the chain ID of
+    /// the provider.
+    fn check(&self, tx: Option<ChainId>, provider: ChainId) -> Result<(), ChainIdMismatch> {
+        if let Some(configured) = self.chain_id.get().copied().filter(|id| *id != provider) {
+            return Err(ChainIdMismatch::Configured { configured, provider });
+        }
+        if let Some(transaction) = tx.filter(|id| *id != provider) {
+            return Err(ChainIdMismatch::Transaction { transaction, provider });
+        }
+        Ok(())
     }
 }
 
@@ -57,15 +119,22 @@ impl<N: Network> TxFiller<N> for ChainIdFiller {
     type Fillable = ChainId;
 
     fn status(&self, tx: &N::TransactionRequest) -> FillerControlFlow {
-        if tx.chain_id().is_some() {
-            FillerControlFlow::Finished
-        } else {
-            FillerControlFlow::Ready
+        match (tx.chain_id(), &self.strict) {
+            (None, _) => FillerControlFlow::Ready,
+            (Some(_), None) => FillerControlFlow::Finished,
+            // the chain ID of the transaction must be checked against the provider
+            (Some(chain_id), Some(provider)) => {
+                if provider.get() == Some(&chain_id) {
+                    FillerControlFlow::Finished
+                } else {
+                    FillerControlFlow::Ready
+                }
+            }
         }
     }
 
     fn fill_sync(&self, tx: &mut SendableTx<N>) {
-        if let Some(chain_id) = self.0.get() {
+        if let Some(chain_id) = self.chain_id.get() {
             if let Some(builder) = tx.as_mut_builder() {
                 if builder.chain_id().is_none() {
                     builder.set_chain_id(*chain_id)
@@ -83,21 +152,93 @@ impl<N: Network> TxFiller<N> for ChainIdFiller {
         P: crate::Provider<T, N>,
         T: alloy_transport::Transport + Clone,
     {
-        match self.0.get().copied() {
+        if let Some(strict) = &self.strict {
+            if let Some(chain_id) = strict.get() {
+                return Ok(*chain_id);
+            }
+            let chain_id = provider.get_chain_id().await?;
+            return Ok(*strict.get_or_init(|| chain_id));
+        }
+
+        match self.chain_id.get().copied() {
             Some(chain_id) => Ok(chain_id),
             None => {
                 let chain_id = provider.get_chain_id().await?;
-                Ok(*self.0.get_or_init(|| chain_id))
+                Ok(*self.chain_id.get_or_init(|| chain_id))
             }
         }
     }
 
     async fn fill(
         &self,
-        _fillable: Self::Fillable,
+        fillable: Self::Fillable,
         mut tx: SendableTx<N>,
     ) -> TransportResult<SendableTx<N>> {
+        if self.is_strict() {
+            let tx_chain_id = tx.as_builder().and_then(|builder| builder.chain_id());
+            self.check(tx_chain_id, fillable).map_err(RpcError::local_usage)?;
+            if let Some(builder) = tx.as_mut_builder() {
+                builder.set_chain_id(fillable);
+            }
+            return Ok(tx);
+        }
+
         self.fill_sync(&mut tx);
         Ok(tx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Provider, ProviderBuilder};
+    use alloy_network::Ethereum;
+    use alloy_rpc_types_eth::TransactionRequest;
+
+    #[test]
+    fn strict_status() {
+        let tx = TransactionRequest::default().with_chain_id(1);
+        let filler = ChainIdFiller::new(None);
+        assert!(TxFiller::<Ethereum>::status(&filler, &tx).is_finished());
+
+        // the chain ID of the transaction is checked once the provider chain ID is known
+        let filler = ChainIdFiller::strict(None);
+        assert!(TxFiller::<Ethereum>::status(&filler, &tx).is_ready());
+        filler.strict.as_ref().unwrap().set(1).unwrap();
+        assert!(TxFiller::<Ethereum>::status(&filler, &tx).is_finished());
+        assert!(TxFiller::<Ethereum>::status(&filler, &tx.with_chain_id(2)).is_ready());
+    }
+
+    #[test]
+    fn strict_check() {
+        let filler = ChainIdFiller::strict(Some(1));
+        assert_eq!(filler.check(Some(1), 1), Ok(()));
+        assert_eq!(
+            filler.check(Some(1), 5),
+            Err(ChainIdMismatch::Configured { configured: 1, provider: 5 })
+        );
+
+        let filler = ChainIdFiller::strict(None);
+        assert_eq!(filler.check(None, 1), Ok(()));
+        assert_eq!(
+            filler.check(Some(2), 1),
+            Err(ChainIdMismatch::Transaction { transaction: 2, provider: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn strict_rejects_mismatched_chain_id() {
+        let provider = ProviderBuilder::new().with_strict_chain_id(None).on_anvil();
+        let chain_id = provider.get_chain_id().await.unwrap();
+
+        let tx = TransactionRequest::default().with_chain_id(chain_id);
+        let filled = provider.fill(tx.clone()).await.unwrap();
+        assert_eq!(filled.as_builder().unwrap().chain_id, Some(chain_id));
+
+        let err = provider.fill(tx.with_chain_id(chain_id + 1)).await.unwrap_err();
+        assert!(err.to_string().contains("does not match the provider chain ID"));
+
+        let provider = ProviderBuilder::new().with_strict_chain_id(Some(chain_id + 1)).on_anvil();
+        assert!(provider.fill(TransactionRequest::default()).await.is_err());
+    }
+}
@@ -6,10 +6,11 @@ use crate::{
     utils::Eip1559Estimation,
     Provider,
 };
-use alloy_eips::eip4844::BLOB_TX_MIN_BLOB_GASPRICE;
+use alloy_eips::eip4844::{BlobParams, BLOB_TX_MIN_BLOB_GASPRICE};
 use alloy_json_rpc::RpcError;
-use alloy_network::{Network, TransactionBuilder, TransactionBuilder4844};
+use alloy_network::{FeeModel, Network, TransactionBuilder, TransactionBuilder4844};
 use alloy_network_primitives::{BlockResponse, HeaderResponse};
+use alloy_primitives::{U128, U64};
 use alloy_rpc_types_eth::BlockNumberOrTag;
 use alloy_transport::{Transport, TransportResult};
 use futures::FutureExt;
@@ -46,6 +47,9 @@ pub enum GasFillable {
 /// - If the network does not support EIP-1559, it will fallback to the legacy tx and populate the
 ///   `gas_limit` and `gas_price` fields if unset.
 ///
+/// The EIP-1559 fees are estimated with the [`FeeModel`] of the network, see
+/// [`Network::FEE_MODEL`].
+///
 /// # Example
 ///
 /// ```
@@ -119,6 +123,92 @@ impl GasFiller {
 
         Ok(GasFillable::Eip1559 { gas_limit, estimate })
     }
+
+    async fn prepare_suggested<P, T, N>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+        model: FeeModel,
+    ) -> TransportResult<GasFillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let gas_limit_fut = tx.gas_limit().map_or_else(
+            || provider.estimate_gas(tx).into_future().right_future(),
+            |gas_limit| async move { Ok(gas_limit) }.left_future(),
+        );
+
+        let fees_fut = async {
+            if let (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) =
+                (tx.max_fee_per_gas(), tx.max_priority_fee_per_gas())
+            {
+                return Ok(Eip1559Estimation { max_fee_per_gas, max_priority_fee_per_gas });
+            }
+            if model == FeeModel::NoPriorityFee {
+                let max_fee_per_gas = provider.get_gas_price().await?;
+                return Ok(Eip1559Estimation { max_fee_per_gas, max_priority_fee_per_gas: 0 });
+            }
+
+            let base_fee_fut = async {
+                provider
+                    .get_block_by_number(BlockNumberOrTag::Latest, false)
+                    .await?
+                    .ok_or(RpcError::NullResp)?
+                    .header()
+                    .base_fee_per_gas()
+                    .ok_or(RpcError::UnsupportedFeature("eip1559"))
+            };
+            let (base_fee, max_priority_fee_per_gas) = futures::try_join!(
+                base_fee_fut,
+                provider.get_max_priority_fee_per_gas().into_future()
+            )?;
+            Ok(Eip1559Estimation {
+                max_fee_per_gas: base_fee as u128 * 2 + max_priority_fee_per_gas,
+                max_priority_fee_per_gas,
+            })
+        };
+
+        let (gas_limit, estimate) = futures::try_join!(gas_limit_fut, fees_fut)?;
+
+        Ok(GasFillable::Eip1559 { gas_limit, estimate })
+    }
+
+    async fn prepare_rpc<P, T, N>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+        method: &'static str,
+    ) -> TransportResult<GasFillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let fee: RpcFeeEstimate = provider.client().request(method, (tx,)).await?;
+        Ok(GasFillable::Eip1559 {
+            gas_limit: tx.gas_limit().unwrap_or(fee.gas_limit.to()),
+            estimate: Eip1559Estimation {
+                max_fee_per_gas: tx.max_fee_per_gas().unwrap_or(fee.max_fee_per_gas.to()),
+                max_priority_fee_per_gas: tx
+                    .max_priority_fee_per_gas()
+                    .unwrap_or(fee.max_priority_fee_per_gas.to()),
+            },
+        })
+    }
+}
+
+/// The response of the method of a [`FeeModel::Rpc`].
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcFeeEstimate {
+    #[serde(alias = "gas_limit")]
+    gas_limit: U64,
+    #[serde(alias = "max_fee_per_gas")]
+    max_fee_per_gas: U128,
+    #[serde(alias = "max_priority_fee_per_gas")]
+    max_priority_fee_per_gas: U128,
 }
 
 impl<N: Network> TxFiller<N> for GasFiller {
@@ -152,15 +242,23 @@ impl<N: Network> TxFiller<N> for GasFiller {
         P: Provider<T, N>,
         T: Transport + Clone,
     {
-        if tx.gas_price().is_some() {
-            self.prepare_legacy(provider, tx).await
-        } else {
-            match self.prepare_1559(provider, tx).await {
-                // fallback to legacy
-                Ok(estimate) => Ok(estimate),
-                Err(RpcError::UnsupportedFeature(_)) => self.prepare_legacy(provider, tx).await,
-                Err(e) => Err(e),
+        // legacy and eip2930 txs, either by gas price or by an explicitly selected type
+        if tx.gas_price().is_some() || matches!(tx.output_tx_type().into(), 0 | 1) {
+            return self.prepare_legacy(provider, tx).await;
+        }
+        let result = match N::FEE_MODEL {
+            FeeModel::Legacy => return self.prepare_legacy(provider, tx).await,
+            FeeModel::Rpc(method) => return self.prepare_rpc(provider, tx, method).await,
+            FeeModel::SuggestedPriorityFee | FeeModel::NoPriorityFee => {
+                self.prepare_suggested(provider, tx, N::FEE_MODEL).await
             }
+            _ => self.prepare_1559(provider, tx).await,
+        };
+        match result {
+            // fallback to legacy
+            Ok(estimate) => Ok(estimate),
+            Err(RpcError::UnsupportedFeature(_)) => self.prepare_legacy(provider, tx).await,
+            Err(e) => Err(e),
         }
     }
 
@@ -187,9 +285,54 @@ impl<N: Network> TxFiller<N> for GasFiller {
 }
 
 /// Filler for the `max_fee_per_blob_gas` field in EIP-4844 transactions.
+///
+/// The blob fee of the next block is computed from the latest header, with the Cancun blob
+/// parameters. Use [`BlobGasFiller::with_blob_params`] for chains on another blob schedule, e.g.
+/// after Prague.
 #[derive(Clone, Copy, Debug, Default)]
 pub struct BlobGasFiller;
 
+impl BlobGasFiller {
+    /// Creates a filler computing the blob fee of the next block with the given blob parameters,
+    /// which must be those of the chain's current fork, e.g. [`BlobParams::prague`].
+    pub const fn with_blob_params(blob_params: BlobParams) -> BlobParamsGasFiller {
+        BlobParamsGasFiller { blob_params }
+    }
+}
+
+/// Returns `true` if the transaction needs a `max_fee_per_blob_gas`.
+fn needs_blob_fee<B: TransactionBuilder4844>(tx: &B) -> bool {
+    // Nothing to fill if non-eip4844 tx or `max_fee_per_blob_gas` is already set to a valid value.
+    tx.blob_sidecar().is_some()
+        && !tx.max_fee_per_blob_gas().is_some_and(|gas| gas >= BLOB_TX_MIN_BLOB_GASPRICE)
+}
+
+/// Fetches the latest header and computes the blob fee of the next block with `blob_fee`, unless
+/// the transaction already has a valid `max_fee_per_blob_gas`.
+async fn prepare_blob_fee<P, T, N>(
+    provider: &P,
+    tx: &N::TransactionRequest,
+    blob_fee: impl FnOnce(&N::HeaderResponse) -> Option<u128>,
+) -> TransportResult<u128>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    N::TransactionRequest: TransactionBuilder4844,
+{
+    if let Some(max_fee_per_blob_gas) = tx.max_fee_per_blob_gas() {
+        if max_fee_per_blob_gas >= BLOB_TX_MIN_BLOB_GASPRICE {
+            return Ok(max_fee_per_blob_gas);
+        }
+    }
+
+    let block = provider
+        .get_block_by_number(BlockNumberOrTag::Latest, false)
+        .await?
+        .ok_or(RpcError::NullResp)?;
+    blob_fee(block.header()).ok_or(RpcError::UnsupportedFeature("eip4844"))
+}
+
 impl<N: Network> TxFiller<N> for BlobGasFiller
 where
     N::TransactionRequest: TransactionBuilder4844,
@@ -197,8 +340,150 @@ where
     type Fillable = u128;
 
     fn status(&self, tx: &<N as Network>::TransactionRequest) -> FillerControlFlow {
-        // Nothing to fill if non-eip4844 tx or `max_fee_per_blob_gas` is already set to a valid
-        // value.
+        if needs_blob_fee(tx) {
+            FillerControlFlow::Ready
+        } else {
+            FillerControlFlow::Finished
+        }
+    }
+
+    fn fill_sync(&self, _tx: &mut SendableTx<N>) {}
+
+    async fn prepare<P, T>(
+        &self,
+        provider: &P,
+        tx: &<N as Network>::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+    {
+        prepare_blob_fee(provider, tx, HeaderResponse::next_block_blob_fee).await
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        mut tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        if let Some(builder) = tx.as_mut_builder() {
+            builder.set_max_fee_per_blob_gas(fillable);
+        }
+        Ok(tx)
+    }
+}
+
+/// Filler for the `max_fee_per_blob_gas` field in EIP-4844 transactions, computing the blob fee
+/// of the next block with configurable blob parameters.
+///
+/// Created with [`BlobGasFiller::with_blob_params`].
+#[derive(Clone, Copy, Debug)]
+pub struct BlobParamsGasFiller {
+    blob_params: BlobParams,
+}
+
+impl BlobParamsGasFiller {
+    /// Returns the blob parameters used to compute the blob fee of the next block.
+    pub const fn blob_params(&self) -> BlobParams {
+        self.blob_params
+    }
+}
+
+impl<N: Network> TxFiller<N> for BlobParamsGasFiller
+where
+    N::TransactionRequest: TransactionBuilder4844,
+{
+    type Fillable = u128;
+
+    fn status(&self, tx: &<N as Network>::TransactionRequest) -> FillerControlFlow {
+        <BlobGasFiller as TxFiller<N>>::status(&BlobGasFiller, tx)
+    }
+
+    fn fill_sync(&self, _tx: &mut SendableTx<N>) {}
+
+    async fn prepare<P, T>(
+        &self,
+        provider: &P,
+        tx: &<N as Network>::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+    {
+        prepare_blob_fee(provider, tx, |header| {
+            header.next_block_blob_fee_with_params(self.blob_params)
+        })
+        .await
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        BlobGasFiller.fill(fillable, tx).await
+    }
+}
+
+/// Filler for the `max_fee_per_blob_gas` field in EIP-4844 transactions, based on the current blob
+/// base fee returned by [`Provider::get_blob_base_fee`].
+///
+/// The blob base fee is multiplied by a configurable percentage, to leave room for it to rise
+/// before the transaction is included, and optionally capped at a maximum.
+///
+/// Unlike [`BlobGasFiller`], which computes the blob fee of the next block from the latest header,
+/// this filler relies on the node's `eth_blobBaseFee` method.
+#[derive(Clone, Copy, Debug)]
+pub struct BlobBaseFeeFiller {
+    multiplier_percent: u128,
+    cap: Option<u128>,
+}
+
+impl Default for BlobBaseFeeFiller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlobBaseFeeFiller {
+    /// The default multiplier, in percent of the current blob base fee.
+    pub const DEFAULT_MULTIPLIER_PERCENT: u128 = 200;
+
+    /// Creates a new filler, which doubles the current blob base fee and has no cap.
+    pub const fn new() -> Self {
+        Self { multiplier_percent: Self::DEFAULT_MULTIPLIER_PERCENT, cap: None }
+    }
+
+    /// Sets the multiplier applied to the current blob base fee, in percent.
+    pub const fn with_multiplier_percent(mut self, multiplier_percent: u128) -> Self {
+        self.multiplier_percent = multiplier_percent;
+        self
+    }
+
+    /// Sets the maximum `max_fee_per_blob_gas` to fill.
+    ///
+    /// If the cap is below the current blob base fee, the transaction will not be included until
+    /// the blob base fee drops.
+    pub const fn with_cap(mut self, cap: u128) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Returns the `max_fee_per_blob_gas` to fill for the given blob base fee.
+    pub fn max_fee_per_blob_gas(&self, blob_base_fee: u128) -> u128 {
+        let fee = blob_base_fee.saturating_mul(self.multiplier_percent) / 100;
+        let fee = self.cap.map_or(fee, |cap| fee.min(cap));
+        fee.max(BLOB_TX_MIN_BLOB_GASPRICE)
+    }
+}
+
+impl<N: Network> TxFiller<N> for BlobBaseFeeFiller
+where
+    N::TransactionRequest: TransactionBuilder4844,
+{
+    type Fillable = u128;
+
+    fn status(&self, tx: &<N as Network>::TransactionRequest) -> FillerControlFlow {
         if tx.blob_sidecar().is_none()
             || tx.max_fee_per_blob_gas().is_some_and(|gas| gas >= BLOB_TX_MIN_BLOB_GASPRICE)
         {
@@ -213,26 +498,13 @@ where
     async fn prepare<P, T>(
         &self,
         provider: &P,
-        tx: &<N as Network>::TransactionRequest,
+        _tx: &<N as Network>::TransactionRequest,
     ) -> TransportResult<Self::Fillable>
     where
         P: Provider<T, N>,
         T: Transport + Clone,
     {
-        if let Some(max_fee_per_blob_gas) = tx.max_fee_per_blob_gas() {
-            if max_fee_per_blob_gas >= BLOB_TX_MIN_BLOB_GASPRICE {
-                return Ok(max_fee_per_blob_gas);
-            }
-        }
-
-        provider
-            .get_block_by_number(BlockNumberOrTag::Latest, false)
-            .await?
-            .ok_or(RpcError::NullResp)?
-            .header()
-            .next_block_blob_fee()
-            .map(Into::into)
-            .ok_or(RpcError::UnsupportedFeature("eip4844"))
+        provider.get_blob_base_fee().await.map(|fee| self.max_fee_per_blob_gas(fee))
     }
 
     async fn fill(
@@ -251,7 +523,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ProviderBuilder;
+    use crate::{
+        fillers::{ChainIdFiller, NonceFiller, SimpleNonceManager},
+        ProviderBuilder,
+    };
     use alloy_consensus::{SidecarBuilder, SimpleCoder};
     use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
     use alloy_primitives::{address, U256};
@@ -333,6 +608,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserializes_rpc_fee_estimates() {
+        let camel = r#"{"gasLimit":"0x5208","maxFeePerGas":"0x2","maxPriorityFeePerGas":"0x1"}"#;
+        // as returned by `zks_estimateFee`
+        let snake = r#"{"gas_limit":"0x5208","gas_per_pubdata_limit":"0x320","max_fee_per_gas":"0x2","max_priority_fee_per_gas":"0x1"}"#;
+        for json in [camel, snake] {
+            let fee: RpcFeeEstimate = serde_json::from_str(json).unwrap();
+            assert_eq!(fee.gas_limit.to::<u64>(), 21_000);
+            assert_eq!(fee.max_fee_per_gas.to::<u128>(), 2);
+            assert_eq!(fee.max_priority_fee_per_gas.to::<u128>(), 1);
+        }
+    }
+
+    #[test]
+    fn blob_base_fee_multiplier_and_cap() {
+        let filler = BlobBaseFeeFiller::new();
+        assert_eq!(filler.max_fee_per_blob_gas(10), 20);
+        assert_eq!(filler.max_fee_per_blob_gas(0), BLOB_TX_MIN_BLOB_GASPRICE);
+
+        let filler = filler.with_multiplier_percent(150).with_cap(100);
+        assert_eq!(filler.max_fee_per_blob_gas(10), 15);
+        assert_eq!(filler.max_fee_per_blob_gas(1_000), 100);
+    }
+
+    #[tokio::test]
+    async fn blob_base_fee_filler() {
+        init_tracing();
+
+        let provider = ProviderBuilder::new()
+            .with_gas_estimation()
+            .filler(BlobBaseFeeFiller::new().with_multiplier_percent(300))
+            .filler(NonceFiller::<SimpleNonceManager>::default())
+            .filler(ChainIdFiller::default())
+            .on_anvil_with_wallet();
+
+        let sidecar: SidecarBuilder<SimpleCoder> = SidecarBuilder::from_slice(b"Hello World");
+        let sidecar = sidecar.build().unwrap();
+
+        let tx = TransactionRequest {
+            to: Some(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            sidecar: Some(sidecar),
+            ..Default::default()
+        };
+
+        let blob_base_fee = provider.get_blob_base_fee().await.unwrap();
+        let tx = provider.send_transaction(tx).await.unwrap();
+        let receipt = tx.get_receipt().await.unwrap();
+        let tx = provider.get_transaction_by_hash(receipt.transaction_hash).await.unwrap().unwrap();
+
+        assert_eq!(tx.max_fee_per_blob_gas.unwrap(), (blob_base_fee * 3).max(1));
+    }
+
+    #[tokio::test]
+    async fn blob_gas_filler_with_blob_params() {
+        init_tracing();
+
+        let provider = ProviderBuilder::new()
+            .with_gas_estimation()
+            .filler(BlobGasFiller::with_blob_params(BlobParams::prague()))
+            .filler(NonceFiller::<SimpleNonceManager>::default())
+            .filler(ChainIdFiller::default())
+            .on_anvil_with_wallet();
+
+        let sidecar: SidecarBuilder<SimpleCoder> = SidecarBuilder::from_slice(b"Hello World");
+        let sidecar = sidecar.build().unwrap();
+
+        let tx = TransactionRequest {
+            to: Some(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            sidecar: Some(sidecar),
+            ..Default::default()
+        };
+
+        let header = provider.get_block_by_number(BlockNumberOrTag::Latest, false).await.unwrap();
+        let expected =
+            header.unwrap().header.next_block_blob_fee_with_params(BlobParams::prague()).unwrap();
+        let tx = provider.send_transaction(tx).await.unwrap();
+        let receipt = tx.get_receipt().await.unwrap();
+        let tx = provider.get_transaction_by_hash(receipt.transaction_hash).await.unwrap().unwrap();
+
+        assert_eq!(tx.max_fee_per_blob_gas.unwrap(), expected);
+    }
+
     #[tokio::test]
     async fn zero_max_fee_per_blob_gas() {
         init_tracing();
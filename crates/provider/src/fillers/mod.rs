@@ -7,16 +7,19 @@
 //! [`Provider`]: crate::Provider
 
 mod chain_id;
-pub use chain_id::ChainIdFiller;
+pub use chain_id::{ChainIdFiller, ChainIdMismatch};
 
 mod wallet;
 pub use wallet::WalletFiller;
 
+mod rotation;
+pub use rotation::{RotatingWallet, WalletRotation};
+
 mod nonce;
 pub use nonce::{CachedNonceManager, NonceFiller, NonceManager, SimpleNonceManager};
 
 mod gas;
-pub use gas::{BlobGasFiller, GasFillable, GasFiller};
+pub use gas::{BlobBaseFeeFiller, BlobGasFiller, BlobParamsGasFiller, GasFillable, GasFiller};
 
 mod join_fill;
 pub use join_fill::JoinFill;
@@ -0,0 +1,177 @@
+use alloy_network::{Ethereum, Network, NetworkWallet};
+use alloy_primitives::Address;
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, RwLock},
+};
+use tokio::sync::{watch, RwLock as Gate};
+
+/// A completed rotation of the signers of a [`RotatingWallet`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WalletRotation {
+    /// The number of rotations so far, starting at 0 for the initial wallet.
+    pub generation: u64,
+    /// The default signer of the replaced wallet.
+    pub previous_signer: Address,
+    /// The default signer of the new wallet.
+    pub signer: Address,
+}
+
+struct Inner<W> {
+    wallet: RwLock<Arc<W>>,
+    /// Held shared by in-flight signs, and exclusively while swapping the wallet.
+    gate: Gate<()>,
+    rotations: watch::Sender<WalletRotation>,
+}
+
+/// A wallet whose signers can be replaced while it is in use, to rotate keys of long-lived
+/// services without rebuilding their provider stack.
+///
+/// The wallet is cheap to clone, and clones share the signers. It is used like any other wallet,
+/// e.g. with [`ProviderBuilder::wallet`](crate::ProviderBuilder::wallet), and rotated through
+/// [`WalletProvider::wallet`](crate::WalletProvider::wallet).
+///
+/// [`RotatingWallet::rotate`] waits for in-flight signs to complete before swapping the wallet,
+/// and signs started during the rotation wait for the new wallet, so no transaction is signed by
+/// a replaced wallet after the rotation completed.
+///
+/// # Example
+///
+/// ```
+/// # use alloy_network::EthereumWallet;
+/// # use alloy_provider::{fillers::RotatingWallet, ProviderBuilder, WalletProvider};
+/// # async fn test(url: url::Url, wallet: EthereumWallet, rotated: EthereumWallet) {
+/// let provider = ProviderBuilder::new().wallet(RotatingWallet::new(wallet)).on_http(url);
+///
+/// let rotation = provider.wallet().rotate(rotated).await;
+/// println!("now signing as {}", rotation.signer);
+/// # }
+/// ```
+pub struct RotatingWallet<W, N: Network = Ethereum> {
+    inner: Arc<Inner<W>>,
+    _pd: PhantomData<fn() -> N>,
+}
+
+impl<W, N: Network> Clone for RotatingWallet<W, N> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), _pd: PhantomData }
+    }
+}
+
+impl<W: fmt::Debug, N: Network> fmt::Debug for RotatingWallet<W, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotatingWallet")
+            .field("wallet", &self.current())
+            .field("generation", &self.inner.rotations.borrow().generation)
+            .finish()
+    }
+}
+
+impl<W, N: Network> RotatingWallet<W, N> {
+    /// Returns the current wallet.
+    pub fn current(&self) -> Arc<W> {
+        self.inner.wallet.read().unwrap().clone()
+    }
+
+    /// Subscribes to rotations, starting with the latest one.
+    pub fn rotations(&self) -> watch::Receiver<WalletRotation> {
+        self.inner.rotations.subscribe()
+    }
+}
+
+impl<W: NetworkWallet<N>, N: Network> RotatingWallet<W, N> {
+    /// Wraps the initial wallet.
+    pub fn new(wallet: W) -> Self {
+        let signer = wallet.default_signer_address();
+        let (rotations, _) =
+            watch::channel(WalletRotation { generation: 0, previous_signer: signer, signer });
+        Self {
+            inner: Arc::new(Inner {
+                wallet: RwLock::new(Arc::new(wallet)),
+                gate: Gate::new(()),
+                rotations,
+            }),
+            _pd: PhantomData,
+        }
+    }
+
+    /// Replaces the wallet once the in-flight signs completed, returning the rotation.
+    pub async fn rotate(&self, wallet: W) -> WalletRotation {
+        let _drained = self.inner.gate.write().await;
+        let signer = wallet.default_signer_address();
+        let previous =
+            std::mem::replace(&mut *self.inner.wallet.write().unwrap(), Arc::new(wallet));
+
+        let mut rotation = None;
+        self.inner.rotations.send_modify(|latest| {
+            *latest = WalletRotation {
+                generation: latest.generation + 1,
+                previous_signer: previous.default_signer_address(),
+                signer,
+            };
+            rotation = Some(*latest);
+        });
+        rotation.expect("rotation is recorded")
+    }
+}
+
+impl<W, N> NetworkWallet<N> for RotatingWallet<W, N>
+where
+    W: NetworkWallet<N> + Send + Sync,
+    N: Network,
+    N::UnsignedTx: Send,
+{
+    fn default_signer_address(&self) -> Address {
+        self.current().default_signer_address()
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        self.current().has_signer_for(address)
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        self.current().signer_addresses().collect::<Vec<_>>().into_iter()
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: Address,
+        tx: N::UnsignedTx,
+    ) -> alloy_signer::Result<N::TxEnvelope> {
+        let _in_flight = self.inner.gate.read().await;
+        let wallet = self.current();
+        wallet.sign_transaction_from(sender, tx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{TxEip1559, TypedTransaction};
+    use alloy_network::EthereumWallet;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[tokio::test]
+    async fn rotates_signers() {
+        let (old, new) = (PrivateKeySigner::random(), PrivateKeySigner::random());
+        let (old_address, new_address) = (old.address(), new.address());
+        let wallet = RotatingWallet::<_, Ethereum>::new(EthereumWallet::from(old));
+        let mut rotations = wallet.rotations();
+        let tx = || TypedTransaction::from(TxEip1559 { chain_id: 1, ..Default::default() });
+
+        wallet.sign_transaction_from(old_address, tx()).await.unwrap();
+
+        let rotation = wallet.clone().rotate(EthereumWallet::from(new)).await;
+        assert_eq!(
+            rotation,
+            WalletRotation { generation: 1, previous_signer: old_address, signer: new_address }
+        );
+        assert!(rotations.has_changed().unwrap());
+        assert_eq!(*rotations.borrow_and_update(), rotation);
+
+        assert_eq!(NetworkWallet::<Ethereum>::default_signer_address(&wallet), new_address);
+        assert!(wallet.sign_transaction_from(old_address, tx()).await.is_err());
+        wallet.sign_transaction_from(new_address, tx()).await.unwrap();
+    }
+}
@@ -0,0 +1,384 @@
+use crate::{
+    ingest::{BlockRef, ChainTracker},
+    Provider,
+};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_network::{BlockResponse, Network};
+use alloy_primitives::BlockNumber;
+use alloy_transport::{Transport, TransportError};
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use std::{fmt, marker::PhantomData, pin::Pin, time::Duration};
+
+/// The default number of blocks that are fetched concurrently.
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// The default number of blocks that are tracked to resolve reorgs.
+const DEFAULT_REORG_DEPTH: usize = 128;
+
+/// The default interval at which the head is polled once the stream caught up.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+type Checkpoint = Box<dyn FnMut(&BlockRef) + Send>;
+
+/// A stream of [`IngestEvent`]s, see [`BlockIngest::into_stream`].
+pub type IngestStream<N> = Pin<Box<dyn Stream<Item = Result<IngestEvent<N>, IngestError>> + Send>>;
+
+/// An error of a [`BlockIngest`] stream, which ends the stream.
+#[derive(Debug, thiserror::Error)]
+pub enum IngestError {
+    /// A request failed.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// The node does not know a block below its head.
+    #[error("block {0} not found")]
+    MissingBlock(BlockNumber),
+    /// The node does not know the receipts of a block.
+    #[error("receipts of block {0} not found")]
+    MissingReceipts(BlockNumber),
+    /// The chain was reorganized below the tracked blocks.
+    #[error("reorg of block {0} is deeper than the tracked blocks")]
+    ReorgTooDeep(BlockNumber),
+}
+
+/// A block with its receipts, yielded by a [`BlockIngest`] stream.
+#[derive(Clone, Debug)]
+pub struct IngestedBlock<N: Network> {
+    /// The block, with the hashes of its transactions.
+    pub block: N::BlockResponse,
+    /// The receipts of the transactions of the block, empty if receipts are not fetched.
+    pub receipts: Vec<N::ReceiptResponse>,
+}
+
+impl<N: Network> IngestedBlock<N> {
+    /// Returns the reference of the block.
+    pub fn block_ref(&self) -> BlockRef {
+        BlockRef::from_header(self.block.header())
+    }
+}
+
+/// An event of a [`BlockIngest`] stream.
+#[derive(Clone, Debug)]
+pub enum IngestEvent<N: Network> {
+    /// The next block of the canonical chain.
+    Block(Box<IngestedBlock<N>>),
+    /// The chain was reorganized: the removed blocks are no longer canonical, and the stream
+    /// continues with the block after the fork point.
+    Rollback {
+        /// The most recent block that is still canonical.
+        fork_point: BlockRef,
+        /// The blocks that are no longer canonical, most recent first.
+        removed: Vec<BlockRef>,
+    },
+}
+
+/// Streams the blocks and receipts of a chain from a starting block to the live head.
+///
+/// Blocks are fetched with bounded concurrency, and delivered in order as
+/// [`IngestEvent::Block`]s. Each delivered block is pushed onto a [`ChainTracker`]. If a block
+/// does not extend the previous one, the fork point is searched among the tracked blocks, and an
+/// [`IngestEvent::Rollback`] is delivered before the blocks of the new chain.
+///
+/// The checkpoint callback is called with the most recent delivered block once the consumer
+/// polls for the next event, i.e. once it processed the block. Persisting the checkpoint and
+/// restarting with [`BlockIngest::resume`] continues the stream after the last processed
+/// block, and detects reorgs that happened in the meantime.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider + 'static) {
+/// use alloy_provider::ingest::{BlockIngest, IngestEvent};
+/// use futures::StreamExt;
+///
+/// let mut events = BlockIngest::new(provider, 20_000_000)
+///     .with_confirmations(2)
+///     .with_checkpoint(|checkpoint| println!("processed up to {}", checkpoint.number))
+///     .into_stream();
+/// while let Some(event) = events.next().await {
+///     match event.unwrap() {
+///         IngestEvent::Block(block) => println!("{} receipts", block.receipts.len()),
+///         IngestEvent::Rollback { fork_point, .. } => {
+///             println!("rolled back to {}", fork_point.number)
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub struct BlockIngest<P, T, N: Network> {
+    provider: P,
+    next_block: BlockNumber,
+    tracker: ChainTracker,
+    concurrency: usize,
+    confirmations: u64,
+    poll_interval: Duration,
+    receipts: bool,
+    checkpoint: Option<Checkpoint>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N: Network> fmt::Debug for BlockIngest<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockIngest")
+            .field("next_block", &self.next_block)
+            .field("tracker", &self.tracker)
+            .field("concurrency", &self.concurrency)
+            .field("confirmations", &self.confirmations)
+            .field("poll_interval", &self.poll_interval)
+            .field("receipts", &self.receipts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> BlockIngest<P, T, N>
+where
+    P: Provider<T, N> + 'static,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a stream starting at the block.
+    pub fn new(provider: P, from_block: BlockNumber) -> Self {
+        Self {
+            provider,
+            next_block: from_block,
+            tracker: ChainTracker::new(DEFAULT_REORG_DEPTH),
+            concurrency: DEFAULT_CONCURRENCY,
+            confirmations: 0,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            receipts: true,
+            checkpoint: None,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Creates a stream continuing after the checkpoint.
+    pub fn resume(provider: P, checkpoint: BlockRef) -> Self {
+        let mut ingest = Self::new(provider, checkpoint.number + 1);
+        let _ = ingest.tracker.push(checkpoint);
+        ingest
+    }
+
+    /// Sets the maximum number of blocks that are fetched concurrently. Defaults to 8.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the number of blocks that a block must be below the head to be delivered. Defaults to
+    /// 0, delivering the head.
+    pub const fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets the interval at which the head is polled once the stream caught up. Defaults to 2
+    /// seconds.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the number of blocks that are tracked to resolve reorgs, which is the maximum depth
+    /// of a reorg before the stream fails with [`IngestError::ReorgTooDeep`]. Defaults to 128.
+    pub fn with_reorg_depth(mut self, depth: usize) -> Self {
+        let mut tracker = ChainTracker::new(depth);
+        if let Some(head) = self.tracker.head() {
+            let _ = tracker.push(*head);
+        }
+        self.tracker = tracker;
+        self
+    }
+
+    /// Sets whether the receipts of the blocks are fetched, with `eth_getBlockReceipts`. Defaults
+    /// to `true`.
+    pub const fn with_receipts(mut self, receipts: bool) -> Self {
+        self.receipts = receipts;
+        self
+    }
+
+    /// Sets the callback called with the most recent block, once the consumer processed it.
+    pub fn with_checkpoint<F>(mut self, checkpoint: F) -> Self
+    where
+        F: FnMut(&BlockRef) + Send + 'static,
+    {
+        self.checkpoint = Some(Box::new(checkpoint));
+        self
+    }
+
+    /// Returns the stream of events.
+    pub fn into_stream(self) -> IngestStream<N> {
+        let Self {
+            provider,
+            mut next_block,
+            mut tracker,
+            concurrency,
+            confirmations,
+            poll_interval,
+            receipts,
+            mut checkpoint,
+            ..
+        } = self;
+        let mut commit = move |block: Option<BlockRef>| {
+            if let (Some(checkpoint), Some(block)) = (checkpoint.as_mut(), block) {
+                checkpoint(&block);
+            }
+        };
+
+        Box::pin(stream! {
+            loop {
+                let head = match provider.get_block_number().await {
+                    Ok(head) => head,
+                    Err(err) => {
+                        yield Err(err.into());
+                        return;
+                    }
+                };
+                let target = head.saturating_sub(confirmations);
+                if next_block > target {
+                    trace!(next_block, target, "waiting for blocks");
+                    tokio::time::sleep(poll_interval).await;
+                    continue;
+                }
+
+                let mut fetches = stream::iter(next_block..=target)
+                    .map(|number| fetch_block(&provider, number, receipts))
+                    .buffered(concurrency);
+                while let Some(fetched) = fetches.next().await {
+                    let block = match fetched {
+                        Ok(block) => block,
+                        Err(err) => {
+                            yield Err(err);
+                            return;
+                        }
+                    };
+                    let block_ref = block.block_ref();
+                    if tracker.push(block_ref).is_ok() {
+                        next_block = block_ref.number + 1;
+                        yield Ok(IngestEvent::Block(Box::new(block)));
+                        commit(Some(block_ref));
+                        continue;
+                    }
+
+                    let fork_point = match find_fork_point(&provider, &tracker).await {
+                        Ok(fork_point) => fork_point,
+                        Err(err) => {
+                            yield Err(err);
+                            return;
+                        }
+                    };
+                    let removed = tracker.rollback(fork_point.number);
+                    next_block = fork_point.number + 1;
+                    if !removed.is_empty() {
+                        warn!(fork_point = fork_point.number, depth = removed.len(), "reorg detected");
+                        yield Ok(IngestEvent::Rollback { fork_point, removed });
+                        commit(Some(fork_point));
+                    } else {
+                        // the block does not link to the tracked head, although the head is still
+                        // canonical, e.g. because the node is not in sync with itself yet
+                        debug!(number = block_ref.number, "block does not extend the head");
+                        tokio::time::sleep(poll_interval).await;
+                    }
+                    // the pending fetches may belong to the old chain
+                    break;
+                }
+            }
+        })
+    }
+}
+
+async fn fetch_block<P, T, N>(
+    provider: &P,
+    number: BlockNumber,
+    receipts: bool,
+) -> Result<IngestedBlock<N>, IngestError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let block = provider
+        .get_block_by_number(BlockNumberOrTag::Number(number), false)
+        .await?
+        .ok_or(IngestError::MissingBlock(number))?;
+    let receipts = if receipts {
+        // by hash, so that the receipts belong to the block even if it was reorged in between
+        let hash = BlockRef::from_header(block.header()).hash;
+        provider
+            .get_block_receipts(BlockId::hash(hash))
+            .await?
+            .ok_or(IngestError::MissingReceipts(number))?
+    } else {
+        Vec::new()
+    };
+    Ok(IngestedBlock { block, receipts })
+}
+
+/// Returns the most recent tracked block that is still canonical.
+async fn find_fork_point<P, T, N>(
+    provider: &P,
+    tracker: &ChainTracker,
+) -> Result<BlockRef, IngestError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    for tracked in tracker.blocks().rev() {
+        let canonical = provider
+            .get_block_by_number(BlockNumberOrTag::Number(tracked.number), false)
+            .await?
+            .ok_or(IngestError::MissingBlock(tracked.number))?;
+        if BlockRef::from_header(canonical.header()).hash == tracked.hash {
+            return Ok(*tracked);
+        }
+    }
+    Err(IngestError::ReorgTooDeep(tracker.blocks().next().map_or(0, |block| block.number)))
+}
+
+#[cfg(all(test, feature = "anvil-api"))]
+mod tests {
+    use super::*;
+    use crate::{ext::AnvilApi, ProviderBuilder};
+    use alloy_primitives::U256;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn ingests_blocks_and_reorgs() {
+        let provider = ProviderBuilder::new().on_anvil();
+        provider.anvil_mine(Some(U256::from(1)), None).await.unwrap();
+        let snapshot = provider.anvil_snapshot().await.unwrap();
+        provider.anvil_mine(Some(U256::from(2)), None).await.unwrap();
+
+        let checkpoint = Arc::new(Mutex::new(None));
+        let last = checkpoint.clone();
+        let mut events = BlockIngest::new(provider.clone(), 0)
+            .with_concurrency(2)
+            .with_poll_interval(Duration::from_millis(50))
+            .with_checkpoint(move |block| *last.lock().unwrap() = Some(*block))
+            .into_stream();
+        let mut blocks = Vec::new();
+        for number in 0..=3 {
+            let Some(Ok(IngestEvent::Block(block))) = events.next().await else { panic!() };
+            assert_eq!(block.block_ref().number, number);
+            blocks.push(block.block_ref());
+        }
+        // the last block is committed once the next event is polled
+        assert_eq!(*checkpoint.lock().unwrap(), Some(blocks[2]));
+
+        // replace blocks 2 and 3 with a longer chain
+        provider.anvil_revert(snapshot).await.unwrap();
+        provider.anvil_increase_time(U256::from(60)).await.unwrap();
+        provider.anvil_mine(Some(U256::from(3)), None).await.unwrap();
+
+        let Some(Ok(IngestEvent::Rollback { fork_point, removed })) = events.next().await else {
+            panic!()
+        };
+        assert_eq!(fork_point, blocks[1]);
+        assert_eq!(removed, [blocks[3], blocks[2]]);
+        assert_eq!(*checkpoint.lock().unwrap(), Some(blocks[3]));
+
+        let Some(Ok(IngestEvent::Block(block))) = events.next().await else { panic!() };
+        assert_eq!(block.block_ref().parent_hash, fork_point.hash);
+        assert_eq!(*checkpoint.lock().unwrap(), Some(fork_point));
+    }
+}
@@ -0,0 +1,15 @@
+//! Streaming ingestion of blocks and receipts, with checkpointing and reorg handling.
+
+mod block_ingest;
+pub use block_ingest::{BlockIngest, IngestError, IngestEvent, IngestStream, IngestedBlock};
+
+mod receipts;
+pub use receipts::{AdaptiveConcurrency, ReceiptFetcher, ReceiptSource, ReceiptStream};
+
+mod reorg;
+pub use reorg::Reorg;
+#[cfg(feature = "anvil-api")]
+pub use reorg::ReorgSimulator;
+
+mod tracker;
+pub use tracker::{BlockRef, ChainTracker};
@@ -0,0 +1,335 @@
+use crate::Provider;
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_json_rpc::RpcError;
+use alloy_network::{BlockResponse, Network};
+use alloy_primitives::BlockNumber;
+use alloy_transport::{
+    layers::{RateLimitRetryPolicy, RetryPolicy},
+    Transport, TransportError, TransportResult,
+};
+use async_stream::stream;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The JSON-RPC error code of methods that are not supported.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// A stream of the receipts of blocks, see [`ReceiptFetcher::fetch`].
+pub type ReceiptStream<'a, N> = Pin<
+    Box<
+        dyn Stream<Item = TransportResult<(BlockNumber, Vec<<N as Network>::ReceiptResponse>)>>
+            + Send
+            + 'a,
+    >,
+>;
+
+/// How a [`ReceiptFetcher`] fetches the receipts of a block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReceiptSource {
+    /// With one `eth_getBlockReceipts` request per block.
+    BlockReceipts,
+    /// With one `eth_getTransactionReceipt` request per transaction, after fetching the block.
+    TransactionReceipts,
+    /// With `eth_getBlockReceipts`, falling back to `eth_getTransactionReceipt` for good if the
+    /// node does not support it.
+    #[default]
+    Auto,
+}
+
+/// An additive-increase/multiplicative-decrease concurrency limit.
+///
+/// The limit grows by one after a window of fast requests as large as the limit, is decremented
+/// by slow requests, and is halved by rate limited requests. Rate limited requests also pause all
+/// requests until their backoff has elapsed.
+#[derive(Clone, Debug)]
+pub struct AdaptiveConcurrency {
+    min: usize,
+    max: usize,
+    limit: usize,
+    target_latency: Duration,
+    fast_requests: usize,
+    paused_until: Option<Instant>,
+}
+
+impl AdaptiveConcurrency {
+    /// Creates a limit between `min` and `max`, starting at `min`, that is decreased by requests
+    /// slower than the target latency.
+    pub fn new(min: usize, max: usize, target_latency: Duration) -> Self {
+        let min = min.max(1);
+        let max = max.max(min);
+        Self { min, max, limit: min, target_latency, fast_requests: 0, paused_until: None }
+    }
+
+    /// Returns the current limit.
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the instant until which requests are paused, if any.
+    pub const fn paused_until(&self) -> Option<Instant> {
+        self.paused_until
+    }
+
+    /// Records a successful request.
+    pub fn on_success(&mut self, latency: Duration) {
+        if latency > self.target_latency {
+            self.fast_requests = 0;
+            self.limit = self.limit.saturating_sub(1).max(self.min);
+            return;
+        }
+        self.fast_requests += 1;
+        if self.fast_requests >= self.limit {
+            self.fast_requests = 0;
+            self.limit = (self.limit + 1).min(self.max);
+        }
+    }
+
+    /// Records a rate limited request, pausing requests for the backoff.
+    pub fn on_rate_limited(&mut self, backoff: Duration) {
+        self.fast_requests = 0;
+        self.limit = (self.limit / 2).max(self.min);
+        let until = Instant::now() + backoff;
+        self.paused_until = Some(self.paused_until.map_or(until, |paused| paused.max(until)));
+    }
+}
+
+/// Fetches the receipts of many blocks, with a concurrency that adapts to the latency and rate
+/// limits of the node.
+///
+/// Blocks are fetched concurrently and delivered in the requested order. Errors that the
+/// [`RetryPolicy`] considers retryable, such as HTTP 429 responses, halve the concurrency and are
+/// retried after the backoff hinted by the node, or the initial backoff. This uses the same
+/// classification as the [`RetryBackoffLayer`], so both can be combined: requests retried by the
+/// layer are slow to complete, which also lowers the concurrency.
+///
+/// [`RetryBackoffLayer`]: alloy_transport::layers::RetryBackoffLayer
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) {
+/// use alloy_provider::ingest::ReceiptFetcher;
+/// use futures::StreamExt;
+///
+/// let fetcher = ReceiptFetcher::new(&provider).with_concurrency(4, 64);
+/// let mut receipts = fetcher.fetch(20_000_000..20_001_000);
+/// while let Some(Ok((number, receipts))) = receipts.next().await {
+///     println!("{number}: {} receipts", receipts.len());
+/// }
+/// # }
+/// ```
+pub struct ReceiptFetcher<P, T, N> {
+    provider: P,
+    source: ReceiptSource,
+    concurrency: AdaptiveConcurrency,
+    policy: Arc<dyn RetryPolicy>,
+    max_retries: u32,
+    initial_backoff: Duration,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> fmt::Debug for ReceiptFetcher<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReceiptFetcher")
+            .field("source", &self.source)
+            .field("concurrency", &self.concurrency)
+            .field("policy", &self.policy)
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> ReceiptFetcher<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a fetcher with a concurrency between 1 and 32, a target latency of one second, and
+    /// up to 10 retries of rate limited requests.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            source: ReceiptSource::default(),
+            concurrency: AdaptiveConcurrency::new(1, 32, Duration::from_secs(1)),
+            policy: Arc::new(RateLimitRetryPolicy::default()),
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(500),
+            _pd: PhantomData,
+        }
+    }
+
+    /// Sets how the receipts are fetched. Defaults to [`ReceiptSource::Auto`].
+    pub const fn with_source(mut self, source: ReceiptSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Sets the bounds of the concurrency, which starts at `min`.
+    pub fn with_concurrency(mut self, min: usize, max: usize) -> Self {
+        self.concurrency = AdaptiveConcurrency::new(min, max, self.concurrency.target_latency);
+        self
+    }
+
+    /// Sets the latency above which the concurrency is decreased.
+    pub fn with_target_latency(mut self, target_latency: Duration) -> Self {
+        self.concurrency =
+            AdaptiveConcurrency::new(self.concurrency.min, self.concurrency.max, target_latency);
+        self
+    }
+
+    /// Sets the policy deciding which errors are retried, and their backoff. Defaults to the
+    /// [`RateLimitRetryPolicy`].
+    pub fn with_retry_policy(mut self, policy: impl RetryPolicy + 'static) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// Sets the maximum number of retries of a request, and the backoff if the node does not hint
+    /// one.
+    pub const fn with_retries(mut self, max_retries: u32, initial_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Returns the receipts of the blocks, in the order of the blocks.
+    ///
+    /// The stream ends after the first error.
+    pub fn fetch(&self, blocks: impl IntoIterator<Item = BlockNumber>) -> ReceiptStream<'_, N> {
+        let mut blocks = blocks.into_iter().collect::<Vec<_>>().into_iter().enumerate();
+        let concurrency = Arc::new(Mutex::new(self.concurrency.clone()));
+        let source = Arc::new(Mutex::new(self.source));
+
+        Box::pin(stream! {
+            let mut in_flight = FuturesUnordered::new();
+            let mut ready = BTreeMap::new();
+            let mut next_index = 0;
+            loop {
+                while in_flight.len() < concurrency.lock().unwrap().limit() {
+                    let Some((index, number)) = blocks.next() else { break };
+                    let (concurrency, source) = (concurrency.clone(), source.clone());
+                    in_flight.push(async move {
+                        (index, number, self.fetch_with_retries(number, &concurrency, &source).await)
+                    });
+                }
+                let Some((index, number, result)) = in_flight.next().await else { break };
+                match result {
+                    Ok(receipts) => ready.insert(index, (number, receipts)),
+                    Err(err) => {
+                        yield Err(err);
+                        return;
+                    }
+                };
+                while let Some(receipts) = ready.remove(&next_index) {
+                    next_index += 1;
+                    yield Ok(receipts);
+                }
+            }
+        })
+    }
+
+    async fn fetch_with_retries(
+        &self,
+        number: BlockNumber,
+        concurrency: &Mutex<AdaptiveConcurrency>,
+        source: &Mutex<ReceiptSource>,
+    ) -> TransportResult<Vec<N::ReceiptResponse>> {
+        let mut retries = 0;
+        loop {
+            let paused_until = concurrency.lock().unwrap().paused_until();
+            if let Some(paused_until) = paused_until.filter(|until| *until > Instant::now()) {
+                tokio::time::sleep_until(paused_until.into()).await;
+            }
+
+            let start = Instant::now();
+            let source_now = *source.lock().unwrap();
+            match self.fetch_block(number, source_now).await {
+                Ok(receipts) => {
+                    concurrency.lock().unwrap().on_success(start.elapsed());
+                    return Ok(receipts);
+                }
+                Err(RpcError::ErrorResp(err))
+                    if source_now == ReceiptSource::Auto && err.code == METHOD_NOT_FOUND =>
+                {
+                    debug!(
+                        "eth_getBlockReceipts is not supported, using eth_getTransactionReceipt"
+                    );
+                    *source.lock().unwrap() = ReceiptSource::TransactionReceipts;
+                }
+                Err(err) if retries < self.max_retries && self.policy.should_retry(&err) => {
+                    retries += 1;
+                    let backoff = self.policy.backoff_hint(&err).unwrap_or(self.initial_backoff);
+                    debug!(number, %err, ?backoff, "receipts request rate limited");
+                    concurrency.lock().unwrap().on_rate_limited(backoff);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    async fn fetch_block(
+        &self,
+        number: BlockNumber,
+        source: ReceiptSource,
+    ) -> TransportResult<Vec<N::ReceiptResponse>> {
+        let missing =
+            || TransportError::local_usage_str(&format!("receipts of block {number} not found"));
+        if source != ReceiptSource::TransactionReceipts {
+            let block = BlockId::Number(BlockNumberOrTag::Number(number));
+            return self.provider.get_block_receipts(block).await?.ok_or_else(missing);
+        }
+
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Number(number), false)
+            .await?
+            .ok_or_else(missing)?;
+        let hashes = block.transactions().hashes().collect::<Vec<_>>();
+        let receipts = futures::future::try_join_all(hashes.into_iter().map(|hash| async move {
+            self.provider.get_transaction_receipt(hash).await?.ok_or_else(missing)
+        }))
+        .await?;
+        Ok(receipts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adapts_concurrency() {
+        let target = Duration::from_millis(100);
+        let mut concurrency = AdaptiveConcurrency::new(2, 4, target);
+        assert_eq!(concurrency.limit(), 2);
+
+        // a window of fast requests increases the limit, up to the maximum
+        for _ in 0..2 + 3 + 4 {
+            concurrency.on_success(target);
+        }
+        assert_eq!(concurrency.limit(), 4);
+        for _ in 0..8 {
+            concurrency.on_success(target);
+        }
+        assert_eq!(concurrency.limit(), 4);
+
+        concurrency.on_success(target * 2);
+        assert_eq!(concurrency.limit(), 3);
+
+        concurrency.on_rate_limited(Duration::from_secs(1));
+        assert_eq!(concurrency.limit(), 2);
+        assert!(concurrency.paused_until().unwrap() > Instant::now());
+        concurrency.on_rate_limited(Duration::ZERO);
+        assert_eq!(concurrency.limit(), 2);
+        assert!(concurrency.paused_until().unwrap() > Instant::now());
+    }
+}
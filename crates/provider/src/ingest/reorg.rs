@@ -0,0 +1,386 @@
+use super::{BlockRef, ChainTracker, IngestEvent};
+use alloy_network::Network;
+use alloy_primitives::{keccak256, BlockNumber, B256};
+
+/// A reorg of a chain, with the blocks a consumer should roll back and the blocks it should
+/// observe instead.
+///
+/// Reorgs are produced deterministically, either without a node with [`Reorg::synthetic`], or
+/// against anvil with `ReorgSimulator`, and come with assertions on what a correct consumer
+/// should observe.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_provider::ingest::{ChainTracker, Reorg};
+///
+/// let reorg = Reorg::synthetic(100, 2, 3);
+/// let mut tracker = ChainTracker::new(8);
+/// for block in reorg.old_chain() {
+///     tracker.push(block).unwrap();
+/// }
+///
+/// // the consumer under test handles the new chain
+/// let first = reorg.added[0];
+/// assert!(tracker.push(first).is_err());
+/// tracker.rollback(reorg.fork_point.number);
+/// for block in reorg.new_chain().skip(1) {
+///     tracker.push(block).unwrap();
+/// }
+/// reorg.assert_tracked(&tracker);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reorg {
+    /// The most recent block shared by both chains.
+    pub fork_point: BlockRef,
+    /// The blocks of the old chain above the fork point, most recent first like
+    /// [`IngestEvent::Rollback`].
+    pub removed: Vec<BlockRef>,
+    /// The blocks of the new chain above the fork point, oldest first.
+    pub added: Vec<BlockRef>,
+}
+
+impl Reorg {
+    /// Creates a reorg of synthetic blocks, replacing `depth` blocks above the fork point with
+    /// `added` blocks.
+    ///
+    /// Hashes are derived from the block number and the chain, so the same arguments always
+    /// produce the same blocks.
+    pub fn synthetic(fork_point: BlockNumber, depth: usize, added: usize) -> Self {
+        let fork_point = synthetic_block(fork_point, 0);
+        let chain = |fork, len| {
+            let mut parent = fork_point;
+            (1..=len as u64)
+                .map(|i| {
+                    let mut block = synthetic_block(fork_point.number + i, fork);
+                    block.parent_hash = parent.hash;
+                    parent = block;
+                    block
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut removed = chain(0, depth);
+        removed.reverse();
+        Self { fork_point, removed, added: chain(1, added) }
+    }
+
+    /// Returns the depth of the reorg, the number of removed blocks.
+    pub fn depth(&self) -> usize {
+        self.removed.len()
+    }
+
+    /// Returns the old chain from the fork point, oldest first.
+    pub fn old_chain(&self) -> impl Iterator<Item = BlockRef> + '_ {
+        std::iter::once(self.fork_point).chain(self.removed.iter().rev().copied())
+    }
+
+    /// Returns the new chain from the fork point, oldest first.
+    pub fn new_chain(&self) -> impl Iterator<Item = BlockRef> + '_ {
+        std::iter::once(self.fork_point).chain(self.added.iter().copied())
+    }
+
+    /// Asserts that the event rolls back exactly the removed blocks to the fork point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the event is not the expected [`IngestEvent::Rollback`].
+    #[track_caller]
+    pub fn assert_rollback<N: Network>(&self, event: &IngestEvent<N>) {
+        match event {
+            IngestEvent::Rollback { fork_point, removed } => {
+                assert_eq!(*fork_point, self.fork_point, "rolled back to the wrong fork point");
+                assert_eq!(*removed, self.removed, "rolled back the wrong blocks");
+            }
+            IngestEvent::Block(block) => {
+                let number = block.block_ref().number;
+                panic!("expected a rollback to {}, got block {number}", self.fork_point.number)
+            }
+        }
+    }
+
+    /// Asserts that the tracker follows the new chain: it tracks none of the removed blocks, and
+    /// its blocks above the fork point are the first added blocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tracker holds a removed block or diverges from the new chain.
+    #[track_caller]
+    pub fn assert_tracked(&self, tracker: &ChainTracker) {
+        for block in tracker.blocks() {
+            assert!(!self.removed.contains(block), "block {} was not rolled back", block.number);
+        }
+        let above = tracker
+            .blocks()
+            .filter(|block| block.number > self.fork_point.number)
+            .copied()
+            .collect::<Vec<_>>();
+        assert!(
+            self.added.starts_with(&above),
+            "tracked blocks above the fork point diverge from the new chain"
+        );
+        if let Some(tracked) = tracker.get(self.fork_point.number) {
+            assert_eq!(*tracked, self.fork_point, "the fork point is not tracked");
+        }
+    }
+}
+
+fn synthetic_block(number: BlockNumber, fork: u8) -> BlockRef {
+    let hash = |number: BlockNumber| -> B256 {
+        let mut preimage = [fork; 9];
+        preimage[1..].copy_from_slice(&number.to_be_bytes());
+        keccak256(preimage)
+    };
+    // the parent of a block on the old chain is on the old chain too
+    BlockRef { number, hash: hash(number), parent_hash: hash(number.wrapping_sub(1)) }
+}
+
+#[cfg(feature = "anvil-api")]
+pub use simulator::ReorgSimulator;
+
+#[cfg(feature = "anvil-api")]
+mod simulator {
+    use super::{BlockRef, Reorg};
+    use crate::{ext::AnvilApi, Provider};
+    use alloy_eips::BlockNumberOrTag;
+    use alloy_json_rpc::RpcError;
+    use alloy_network::{BlockResponse, Network};
+    use alloy_primitives::U256;
+    use alloy_transport::{Transport, TransportResult};
+    use std::{fmt, marker::PhantomData};
+
+    /// Produces reorgs of configurable depth on an anvil node, with divergent transactions on
+    /// each chain.
+    ///
+    /// The simulator snapshots the head as the fork point. Blocks [mined](Self::mine) afterwards
+    /// form the old chain, which [`reorg`](Self::reorg) replaces by reverting to the snapshot and
+    /// mining the new chain. The timestamps of the new chain are shifted, so that its blocks
+    /// differ from the old ones even when empty. The head of the new chain becomes the fork point
+    /// of the next reorg.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider + Clone) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_provider::ingest::ReorgSimulator;
+    ///
+    /// let mut simulator = ReorgSimulator::new(provider).await?;
+    /// simulator.mine_empty(3).await?;
+    /// // replace the 3 blocks with 4 empty ones
+    /// let reorg = simulator.reorg(vec![Vec::new(); 4]).await?;
+    /// assert_eq!(reorg.depth(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub struct ReorgSimulator<P, T, N: Network> {
+        provider: P,
+        fork_point: BlockRef,
+        snapshot: U256,
+        mined: Vec<BlockRef>,
+        _pd: PhantomData<fn() -> (T, N)>,
+    }
+
+    impl<P, T, N: Network> fmt::Debug for ReorgSimulator<P, T, N> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ReorgSimulator")
+                .field("fork_point", &self.fork_point)
+                .field("mined", &self.mined)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<P, T, N> ReorgSimulator<P, T, N>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        /// Creates a simulator forking at the current head of the node.
+        pub async fn new(provider: P) -> TransportResult<Self> {
+            let snapshot = provider.anvil_snapshot().await?;
+            let fork_point = head(&provider).await?;
+            Ok(Self { provider, fork_point, snapshot, mined: Vec::new(), _pd: PhantomData })
+        }
+
+        /// Returns the fork point of the next reorg.
+        pub const fn fork_point(&self) -> BlockRef {
+            self.fork_point
+        }
+
+        /// Returns the blocks mined above the fork point, oldest first, which the next reorg
+        /// removes.
+        pub fn mined(&self) -> &[BlockRef] {
+            &self.mined
+        }
+
+        /// Returns the provider.
+        pub const fn provider(&self) -> &P {
+            &self.provider
+        }
+
+        /// Mines a block with the transactions on top of the old chain.
+        pub async fn mine(&mut self, txs: Vec<N::TransactionRequest>) -> TransportResult<BlockRef> {
+            let block = mine_block(&self.provider, txs).await?;
+            self.mined.push(block);
+            Ok(block)
+        }
+
+        /// Mines empty blocks on top of the old chain.
+        pub async fn mine_empty(&mut self, count: usize) -> TransportResult<Vec<BlockRef>> {
+            let mut blocks = Vec::with_capacity(count);
+            for _ in 0..count {
+                blocks.push(self.mine(Vec::new()).await?);
+            }
+            Ok(blocks)
+        }
+
+        /// Replaces the blocks mined since the fork point with a new chain of one block per
+        /// entry, each with its transactions.
+        pub async fn reorg(
+            &mut self,
+            blocks: Vec<Vec<N::TransactionRequest>>,
+        ) -> TransportResult<Reorg> {
+            if blocks.is_empty() {
+                return Err(RpcError::local_usage_str("the new chain needs at least one block"));
+            }
+            if !self.provider.anvil_revert(self.snapshot).await? {
+                return Err(RpcError::local_usage_str("failed to revert to the fork point"));
+            }
+            self.provider.anvil_increase_time(U256::from(1)).await?;
+
+            let mut added = Vec::with_capacity(blocks.len());
+            for txs in blocks {
+                added.push(mine_block(&self.provider, txs).await?);
+            }
+            let mut removed = std::mem::take(&mut self.mined);
+            removed.reverse();
+            let reorg = Reorg { fork_point: self.fork_point, removed, added };
+
+            self.snapshot = self.provider.anvil_snapshot().await?;
+            self.fork_point = *reorg.added.last().expect("the new chain is not empty");
+            Ok(reorg)
+        }
+    }
+
+    /// Mines a block including the transactions, with automine disabled while they are sent.
+    async fn mine_block<P, T, N>(
+        provider: &P,
+        txs: Vec<N::TransactionRequest>,
+    ) -> TransportResult<BlockRef>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let mut hashes = Vec::with_capacity(txs.len());
+        if !txs.is_empty() {
+            let auto_mine = provider.anvil_get_auto_mine().await?;
+            provider.anvil_set_auto_mine(false).await?;
+            let mut sent = Ok(());
+            for tx in txs {
+                match provider.send_transaction(tx).await {
+                    Ok(pending) => hashes.push(*pending.tx_hash()),
+                    Err(err) => {
+                        sent = Err(err);
+                        break;
+                    }
+                }
+            }
+            // restore automine even if sending failed
+            let restored = provider.anvil_set_auto_mine(auto_mine).await;
+            sent?;
+            restored?;
+        }
+        provider.anvil_mine(Some(U256::from(1)), None).await?;
+        for hash in hashes {
+            if provider.get_transaction_receipt(hash).await?.is_none() {
+                return Err(RpcError::local_usage_str(&format!(
+                    "transaction {hash} was not mined"
+                )));
+            }
+        }
+        head(provider).await
+    }
+
+    async fn head<P, T, N>(provider: &P) -> TransportResult<BlockRef>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let block = provider
+            .get_block_by_number(BlockNumberOrTag::Latest, false)
+            .await?
+            .ok_or_else(|| RpcError::local_usage_str("latest block not found"))?;
+        Ok(BlockRef::from_header(block.header()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn produces_synthetic_reorgs() {
+        let reorg = Reorg::synthetic(10, 2, 3);
+        assert_eq!(reorg, Reorg::synthetic(10, 2, 3));
+        assert_eq!(reorg.depth(), 2);
+        assert_eq!(reorg.removed.iter().map(|b| b.number).collect::<Vec<_>>(), [12, 11]);
+        assert_eq!(reorg.added.iter().map(|b| b.number).collect::<Vec<_>>(), [11, 12, 13]);
+        assert_ne!(reorg.removed[1], reorg.added[0]);
+
+        let mut tracker = ChainTracker::new(8);
+        for block in reorg.old_chain() {
+            tracker.push(block).unwrap();
+        }
+        assert!(tracker.push(reorg.added[0]).is_err());
+        assert_eq!(tracker.rollback(reorg.fork_point.number), reorg.removed);
+        for block in reorg.new_chain().skip(1) {
+            tracker.push(block).unwrap();
+        }
+        reorg.assert_tracked(&tracker);
+    }
+
+    #[test]
+    #[should_panic = "was not rolled back"]
+    fn detects_missed_rollbacks() {
+        let reorg = Reorg::synthetic(10, 2, 3);
+        let mut tracker = ChainTracker::new(8);
+        for block in reorg.old_chain() {
+            tracker.push(block).unwrap();
+        }
+        reorg.assert_tracked(&tracker);
+    }
+}
+
+#[cfg(all(test, feature = "anvil-api"))]
+mod anvil_tests {
+    use super::*;
+    use crate::{
+        ingest::{BlockIngest, IngestEvent},
+        ProviderBuilder,
+    };
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn simulates_reorgs() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let mut simulator = ReorgSimulator::new(provider.clone()).await.unwrap();
+        simulator.mine_empty(2).await.unwrap();
+
+        let mut events = BlockIngest::new(provider, 0)
+            .with_poll_interval(Duration::from_millis(50))
+            .into_stream();
+        for _ in 0..=2 {
+            let Some(Ok(IngestEvent::Block(_))) = events.next().await else { panic!() };
+        }
+
+        let reorg = simulator.reorg(vec![Vec::new(); 3]).await.unwrap();
+        assert_eq!(reorg.depth(), 2);
+        reorg.assert_rollback(&events.next().await.unwrap().unwrap());
+        for expected in &reorg.added {
+            let Some(Ok(IngestEvent::Block(block))) = events.next().await else { panic!() };
+            assert_eq!(block.block_ref(), *expected);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use alloy_network_primitives::HeaderResponse;
+use alloy_primitives::{BlockHash, BlockNumber};
+use std::collections::{vec_deque, VecDeque};
+
+/// A block identified by its number and hash, with the hash of its parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockRef {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block.
+    pub hash: BlockHash,
+    /// The hash of the parent block.
+    pub parent_hash: BlockHash,
+}
+
+impl BlockRef {
+    /// Returns the reference of the block of the header.
+    pub fn from_header<H: HeaderResponse>(header: &H) -> Self {
+        Self { number: header.number(), hash: header.hash(), parent_hash: header.parent_hash() }
+    }
+
+    /// Returns `true` if this block is the child of the parent.
+    pub fn is_child_of(&self, parent: &Self) -> bool {
+        self.parent_hash == parent.hash && Some(self.number) == parent.number.checked_add(1)
+    }
+}
+
+/// Tracks the canonical chain over the most recent blocks, to detect reorgs.
+///
+/// The tracker holds a contiguous window of up to `depth` blocks. A block is only accepted if it
+/// extends the most recent one; otherwise the chain was reorganized, and the caller looks for the
+/// fork point among the tracked blocks and [rolls back](Self::rollback) to it.
+#[derive(Clone, Debug)]
+pub struct ChainTracker {
+    blocks: VecDeque<BlockRef>,
+    depth: usize,
+}
+
+impl ChainTracker {
+    /// Creates an empty tracker holding up to `depth` blocks, which bounds the depth of the reorgs
+    /// that can be resolved.
+    pub fn new(depth: usize) -> Self {
+        let depth = depth.max(1);
+        Self { blocks: VecDeque::with_capacity(depth), depth }
+    }
+
+    /// Returns the maximum number of tracked blocks.
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the most recent block.
+    pub fn head(&self) -> Option<&BlockRef> {
+        self.blocks.back()
+    }
+
+    /// Returns the tracked block at the height.
+    pub fn get(&self, number: BlockNumber) -> Option<&BlockRef> {
+        let index = number.checked_sub(self.blocks.front()?.number)?;
+        self.blocks.get(usize::try_from(index).ok()?)
+    }
+
+    /// Returns the tracked blocks, oldest first.
+    pub fn blocks(&self) -> vec_deque::Iter<'_, BlockRef> {
+        self.blocks.iter()
+    }
+
+    /// Returns `true` if the block extends the most recent block, or if the tracker is empty.
+    pub fn extends(&self, block: &BlockRef) -> bool {
+        self.head().map_or(true, |head| block.is_child_of(head))
+    }
+
+    /// Appends the block, evicting the oldest block if the tracker is full.
+    ///
+    /// Returns the block back if it does not [extend](Self::extends) the most recent block.
+    pub fn push(&mut self, block: BlockRef) -> Result<(), BlockRef> {
+        if !self.extends(&block) {
+            return Err(block);
+        }
+        if self.blocks.len() == self.depth {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back(block);
+        Ok(())
+    }
+
+    /// Removes the blocks above the height, returning them most recent first.
+    pub fn rollback(&mut self, number: BlockNumber) -> Vec<BlockRef> {
+        let mut removed = Vec::new();
+        while self.head().is_some_and(|head| head.number > number) {
+            removed.extend(self.blocks.pop_back());
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn block(number: u64, fork: u8) -> BlockRef {
+        let hash = |number: u64| {
+            let mut hash = B256::from(alloy_primitives::U256::from(number));
+            hash[0] = fork;
+            hash
+        };
+        BlockRef { number, hash: hash(number), parent_hash: hash(number.wrapping_sub(1)) }
+    }
+
+    #[test]
+    fn tracks_the_chain() {
+        let mut tracker = ChainTracker::new(3);
+        for number in 10..15 {
+            tracker.push(block(number, 0)).unwrap();
+        }
+        assert_eq!(tracker.blocks().map(|block| block.number).collect::<Vec<_>>(), [12, 13, 14]);
+        assert_eq!(tracker.get(13), Some(&block(13, 0)));
+        assert_eq!(tracker.get(11), None);
+        assert_eq!(tracker.get(15), None);
+
+        // a block of another fork or a gap is rejected
+        assert_eq!(tracker.push(block(15, 1)), Err(block(15, 1)));
+        assert!(tracker.push(block(16, 0)).is_err());
+
+        assert_eq!(tracker.rollback(12), [block(14, 0), block(13, 0)]);
+        assert_eq!(tracker.head(), Some(&block(12, 0)));
+        assert!(tracker.rollback(12).is_empty());
+
+        let mut fork = block(13, 1);
+        fork.parent_hash = block(12, 0).hash;
+        tracker.push(fork).unwrap();
+    }
+}
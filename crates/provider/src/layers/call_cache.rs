@@ -0,0 +1,387 @@
+use crate::{Caller, EthCall, EthCallParams, Provider, ProviderCall, ProviderLayer, RootProvider};
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{keccak256, BlockHash, Bytes, B256};
+use alloy_transport::{Transport, TransportResult};
+use lru::LruCache;
+use std::{
+    borrow::Cow,
+    fmt,
+    marker::PhantomData,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// The key of a cached call: the block it executed against, and the hash of the method and its
+/// serialized parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct CallKey {
+    block_hash: BlockHash,
+    call: B256,
+}
+
+/// The counters of a [`CallCache`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CallCacheMetrics {
+    /// The number of calls answered from the cache.
+    pub hits: u64,
+    /// The number of cacheable calls sent to the node.
+    pub misses: u64,
+    /// The number of calls sent to the node without caching, because they do not target a block
+    /// hash.
+    pub bypassed: u64,
+    /// The number of results evicted to respect the capacity.
+    pub evictions: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    bypassed: AtomicU64,
+    evictions: AtomicU64,
+}
+
+/// A bounded cache of `eth_call` results, shared by the clones of a [`CallCacheLayer`].
+///
+/// Only calls executed against a block hash are cached, since their result cannot change, while
+/// calls against a tag or number are always sent to the node. Failed calls are not cached.
+#[derive(Clone)]
+pub struct CallCache {
+    results: Arc<Mutex<LruCache<CallKey, Bytes>>>,
+    counters: Arc<Counters>,
+}
+
+impl fmt::Debug for CallCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallCache")
+            .field("len", &self.len())
+            .field("metrics", &self.metrics())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CallCache {
+    /// Creates a cache holding up to `capacity` results, evicting the least recently used.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            results: Arc::new(Mutex::new(LruCache::new(capacity))),
+            counters: Default::default(),
+        }
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.results.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the counters of the cache.
+    pub fn metrics(&self) -> CallCacheMetrics {
+        CallCacheMetrics {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            bypassed: self.counters.bypassed.load(Ordering::Relaxed),
+            evictions: self.counters.evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Removes the results of calls against the block, e.g. after it was reorged out, returning the
+    /// number of removed results.
+    pub fn invalidate_block(&self, block_hash: BlockHash) -> usize {
+        let mut results = self.results.lock().unwrap();
+        let keys = results
+            .iter()
+            .filter(|(key, _)| key.block_hash == block_hash)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        for key in &keys {
+            results.pop(key);
+        }
+        keys.len()
+    }
+
+    /// Removes all results.
+    pub fn clear(&self) {
+        self.results.lock().unwrap().clear();
+    }
+
+    fn get(&self, key: &CallKey) -> Option<Bytes> {
+        let result = self.results.lock().unwrap().get(key).cloned();
+        let counter = if result.is_some() { &self.counters.hits } else { &self.counters.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    fn insert(&self, key: CallKey, result: Bytes) {
+        if let Some((evicted, _)) = self.results.lock().unwrap().push(key, result) {
+            if evicted != key {
+                self.counters.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// A layer caching the results of `eth_call` against block hashes, see [`CallCache`].
+///
+/// This memoizes hot read paths like token metadata or oracle reads, when they are pinned to a
+/// block with [`EthCall::block`]:
+///
+/// ```
+/// # use alloy_eips::BlockId;
+/// # use alloy_provider::{layers::CallCacheLayer, Provider, ProviderBuilder};
+/// # use alloy_rpc_types_eth::TransactionRequest;
+/// # use std::num::NonZeroUsize;
+/// # async fn test(url: url::Url, tx: TransactionRequest) -> Result<(), Box<dyn std::error::Error>> {
+/// let layer = CallCacheLayer::new(NonZeroUsize::new(1024).unwrap());
+/// let cache = layer.cache().clone();
+/// let provider = ProviderBuilder::new().layer(layer).on_http(url);
+///
+/// let block = provider.get_block_number().await?;
+/// let hash = provider.get_block(block.into(), false.into()).await?.unwrap().header.hash;
+/// let first = provider.call(&tx).block(BlockId::hash(hash)).await?;
+/// let second = provider.call(&tx).block(BlockId::hash(hash)).await?;
+/// assert_eq!(first, second);
+/// assert_eq!(cache.metrics().hits, 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CallCacheLayer {
+    cache: CallCache,
+}
+
+impl CallCacheLayer {
+    /// Creates a layer with a new cache holding up to `capacity` results.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self { cache: CallCache::new(capacity) }
+    }
+
+    /// Creates a layer sharing an existing cache.
+    pub const fn with_cache(cache: CallCache) -> Self {
+        Self { cache }
+    }
+
+    /// Returns the cache of the layer.
+    pub const fn cache(&self) -> &CallCache {
+        &self.cache
+    }
+}
+
+impl<P, T, N> ProviderLayer<P, T, N> for CallCacheLayer
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    type Provider = CallCacheProvider<P, T, N>;
+
+    fn layer(&self, inner: P) -> Self::Provider {
+        CallCacheProvider { inner, cache: self.cache.clone(), _pd: PhantomData }
+    }
+}
+
+/// A provider caching the results of `eth_call` against block hashes, see [`CallCacheLayer`].
+#[derive(Clone)]
+pub struct CallCacheProvider<P, T, N> {
+    inner: P,
+    cache: CallCache,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P: fmt::Debug, T, N> fmt::Debug for CallCacheProvider<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallCacheProvider")
+            .field("inner", &self.inner)
+            .field("cache", &self.cache)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> CallCacheProvider<P, T, N> {
+    /// Returns the cache of the provider.
+    pub const fn cache(&self) -> &CallCache {
+        &self.cache
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> Provider<T, N> for CallCacheProvider<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<T, N> {
+        self.inner.root()
+    }
+
+    fn call<'req>(&self, tx: &'req N::TransactionRequest) -> EthCall<'req, T, N, Bytes> {
+        let cache = self.cache.clone();
+        self.inner.call(tx).wrap_caller(|inner| CachingCaller { inner, cache })
+    }
+}
+
+/// Answers calls against block hashes from the cache, sending the others to the inner caller.
+struct CachingCaller<C> {
+    inner: C,
+    cache: CallCache,
+}
+
+impl<'req, T, N, C> Caller<T, EthCallParams<'req, N>, Bytes> for CachingCaller<C>
+where
+    T: Transport + Clone,
+    N: Network,
+    C: Caller<T, EthCallParams<'req, N>, Bytes>,
+{
+    fn call(
+        &self,
+        method: Cow<'static, str>,
+        params: EthCallParams<'req, N>,
+    ) -> TransportResult<ProviderCall<T, serde_json::Value, Bytes>> {
+        let Some(BlockId::Hash(block)) = params.block() else {
+            self.cache.counters.bypassed.fetch_add(1, Ordering::Relaxed);
+            return self.inner.call(method, params);
+        };
+
+        let mut call = method.as_bytes().to_vec();
+        serde_json::to_writer(&mut call, &params).map_err(alloy_json_rpc::RpcError::ser_err)?;
+        let key = CallKey { block_hash: block.block_hash, call: keccak256(call) };
+        if let Some(result) = self.cache.get(&key) {
+            return Ok(ProviderCall::ready(Ok(result)));
+        }
+
+        let call = self.inner.call(method, params)?;
+        let cache = self.cache.clone();
+        Ok(ProviderCall::BoxedFuture(Box::pin(async move {
+            let result = call.await?;
+            cache.insert(key, result.clone());
+            Ok(result)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_eips::BlockNumberOrTag;
+    use alloy_primitives::address;
+    use alloy_rpc_types_eth::TransactionRequest;
+
+    #[test]
+    fn evicts_and_invalidates() {
+        let cache = CallCache::new(NonZeroUsize::new(2).unwrap());
+        let key = |block: u8, call: u8| CallKey {
+            block_hash: B256::repeat_byte(block),
+            call: B256::repeat_byte(call),
+        };
+
+        cache.insert(key(1, 1), Bytes::from_static(&[1]));
+        cache.insert(key(1, 2), Bytes::from_static(&[2]));
+        assert_eq!(cache.get(&key(1, 1)), Some(Bytes::from_static(&[1])));
+        cache.insert(key(2, 1), Bytes::from_static(&[3]));
+        assert_eq!(cache.get(&key(1, 2)), None);
+
+        assert_eq!(cache.invalidate_block(B256::repeat_byte(1)), 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            cache.metrics(),
+            CallCacheMetrics { hits: 1, misses: 1, bypassed: 0, evictions: 1 }
+        );
+    }
+
+    /// Answers every call with its number, without a node.
+    struct Counting<P> {
+        inner: P,
+        calls: Arc<AtomicU64>,
+    }
+
+    struct CountingCaller(Arc<AtomicU64>);
+
+    impl<T, N> Caller<T, EthCallParams<'_, N>, Bytes> for CountingCaller
+    where
+        T: Transport + Clone,
+        N: Network,
+    {
+        fn call(
+            &self,
+            _method: Cow<'static, str>,
+            _params: EthCallParams<'_, N>,
+        ) -> TransportResult<ProviderCall<T, serde_json::Value, Bytes>> {
+            let call = self.0.fetch_add(1, Ordering::Relaxed) + 1;
+            Ok(ProviderCall::ready(Ok(Bytes::from(vec![call as u8]))))
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl<P, T, N> Provider<T, N> for Counting<P>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        fn root(&self) -> &RootProvider<T, N> {
+            self.inner.root()
+        }
+
+        fn call<'req>(&self, tx: &'req N::TransactionRequest) -> EthCall<'req, T, N, Bytes> {
+            EthCall::new(CountingCaller(self.calls.clone()), tx)
+        }
+    }
+
+    #[tokio::test]
+    async fn wraps_inner_call() {
+        let calls = Arc::new(AtomicU64::new(0));
+        let root = crate::RootProvider::<_, alloy_network::Ethereum>::new_http(
+            "http://localhost:1".parse().unwrap(),
+        );
+        let layer = CallCacheLayer::new(NonZeroUsize::new(16).unwrap());
+        let provider = layer.layer(Counting { inner: root, calls: calls.clone() });
+
+        let tx = TransactionRequest::default();
+        let block = BlockId::hash(B256::repeat_byte(1));
+        assert_eq!(provider.call(&tx).block(block).await.unwrap(), Bytes::from_static(&[1]));
+        assert_eq!(provider.call(&tx).block(block).await.unwrap(), Bytes::from_static(&[1]));
+        assert_eq!(provider.call(&tx).await.unwrap(), Bytes::from_static(&[2]));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn caches_calls_against_block_hashes() {
+        let layer = CallCacheLayer::new(NonZeroUsize::new(16).unwrap());
+        let cache = layer.cache().clone();
+        let provider = ProviderBuilder::new().layer(layer).on_anvil();
+
+        let tx = TransactionRequest::default()
+            .to(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+            .input(Bytes::from_static(&[0x12, 0x34]).into());
+        let block = provider
+            .get_block(BlockNumberOrTag::Latest.into(), false.into())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let first = provider.call(&tx).block(BlockId::hash(block.header.hash)).await.unwrap();
+        let second = provider.call(&tx).block(BlockId::hash(block.header.hash)).await.unwrap();
+        assert_eq!(first, second);
+        provider.call(&tx).await.unwrap();
+
+        assert_eq!(
+            cache.metrics(),
+            CallCacheMetrics { hits: 1, misses: 1, bypassed: 1, evictions: 0 }
+        );
+        assert_eq!(cache.invalidate_block(block.header.hash), 1);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,459 @@
+use alloy_json_rpc::{ErrorPayload, RpcError};
+use alloy_network::Network;
+use alloy_primitives::{hex, Address, Bytes};
+use alloy_rpc_client::WeakClient;
+use alloy_sol_types::{sol, SolError, SolValue};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use serde_json::Value;
+use std::{borrow::Cow, marker::PhantomData, sync::Arc};
+
+use crate::{Caller, EthCall, EthCallParams, Provider, ProviderCall, ProviderLayer, RootProvider};
+
+sol! {
+    /// The error reverted by contracts that require an offchain lookup, see [EIP-3668].
+    ///
+    /// [EIP-3668]: https://eips.ethereum.org/EIPS/eip-3668
+    #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
+    error OffchainLookup(
+        address sender,
+        string[] urls,
+        bytes callData,
+        bytes4 callbackFunction,
+        bytes extraData
+    );
+}
+
+/// An error of the resolution of an [`OffchainLookup`].
+#[derive(Debug, thiserror::Error)]
+pub enum CcipReadError {
+    /// The call required more nested lookups than allowed by [`CcipReadConfig::max_lookups`].
+    #[error("exceeded the maximum of {0} offchain lookups")]
+    TooManyLookups(usize),
+    /// The sender of the lookup is not the called contract.
+    #[error("offchain lookup sender {sender} does not match the called contract {to:?}")]
+    SenderMismatch {
+        /// The sender of the lookup.
+        sender: Address,
+        /// The called contract.
+        to: Option<Address>,
+    },
+    /// The gateway URL is not allowed by the configuration. Such gateways are skipped, and the
+    /// error is reported in [`CcipReadError::GatewaysFailed`] if no other gateway responds.
+    #[error("gateway URL is not allowed: {0}")]
+    UrlNotAllowed(String),
+    /// The gateway rejected the request with a client error, which is not retried with the
+    /// remaining gateways.
+    #[error("gateway {url} rejected the request with status {status}: {message}")]
+    Rejected {
+        /// The URL of the gateway.
+        url: String,
+        /// The HTTP status code of the response.
+        status: u16,
+        /// The error message of the gateway.
+        message: String,
+    },
+    /// None of the gateways returned a response.
+    #[error("all gateways failed: {}", .0.join(", "))]
+    GatewaysFailed(Vec<String>),
+}
+
+/// The configuration of the [EIP-3668] offchain lookups of a [`CcipReadProvider`].
+///
+/// [EIP-3668]: https://eips.ethereum.org/EIPS/eip-3668
+#[derive(Clone, Debug)]
+pub struct CcipReadConfig {
+    /// The maximum number of nested lookups of a single call. Defaults to 4.
+    pub max_lookups: usize,
+    /// Only query gateways served over HTTPS. Defaults to `false`.
+    pub https_only: bool,
+    /// The hosts of the gateways that may be queried, or `None` to allow all hosts.
+    pub allowed_hosts: Option<Vec<String>>,
+}
+
+impl Default for CcipReadConfig {
+    fn default() -> Self {
+        Self { max_lookups: 4, https_only: false, allowed_hosts: None }
+    }
+}
+
+impl CcipReadConfig {
+    /// Sets the maximum number of nested lookups of a single call.
+    pub const fn with_max_lookups(mut self, max_lookups: usize) -> Self {
+        self.max_lookups = max_lookups;
+        self
+    }
+
+    /// Only query gateways served over HTTPS.
+    pub const fn https_only(mut self) -> Self {
+        self.https_only = true;
+        self
+    }
+
+    /// Only query gateways on the given hosts.
+    pub fn with_allowed_hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_hosts = Some(hosts.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns an error if the gateway URL is not allowed.
+    fn check_url(&self, url: &str) -> Result<(), CcipReadError> {
+        let not_allowed = || CcipReadError::UrlNotAllowed(url.to_string());
+        let parsed = url::Url::parse(url).map_err(|_| not_allowed())?;
+        let scheme_allowed = match parsed.scheme() {
+            "https" => true,
+            "http" => !self.https_only,
+            _ => false,
+        };
+        let host_allowed = self.allowed_hosts.as_ref().map_or(true, |hosts| {
+            parsed.host_str().is_some_and(|host| hosts.iter().any(|allowed| allowed == host))
+        });
+        if scheme_allowed && host_allowed {
+            Ok(())
+        } else {
+            Err(not_allowed())
+        }
+    }
+}
+
+/// A layer that wraps a provider in a [`CcipReadProvider`], which transparently resolves
+/// [EIP-3668] offchain lookups of `eth_call`.
+///
+/// [EIP-3668]: https://eips.ethereum.org/EIPS/eip-3668
+#[derive(Clone, Debug, Default)]
+pub struct CcipReadLayer {
+    config: Arc<CcipReadConfig>,
+    http: reqwest::Client,
+}
+
+impl CcipReadLayer {
+    /// Creates a new layer with the given configuration.
+    pub fn new(config: CcipReadConfig) -> Self {
+        Self { config: Arc::new(config), http: reqwest::Client::new() }
+    }
+
+    /// Sets the HTTP client used to query the gateways.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+impl<P, T, N> ProviderLayer<P, T, N> for CcipReadLayer
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    type Provider = CcipReadProvider<P, T, N>;
+
+    fn layer(&self, inner: P) -> Self::Provider {
+        CcipReadProvider { inner, layer: self.clone(), _pd: PhantomData }
+    }
+}
+
+/// A provider that transparently resolves [EIP-3668] offchain lookups of
+/// [`call`](Provider::call).
+///
+/// When a call reverts with [`OffchainLookup`], the provider queries the gateways of the lookup
+/// in order until one of them responds, and calls the callback function of the contract with the
+/// response. The callback may revert with another lookup, up to
+/// [`CcipReadConfig::max_lookups`] times.
+///
+/// This is required to resolve ENS names with wildcard or offchain resolvers.
+///
+/// [EIP-3668]: https://eips.ethereum.org/EIPS/eip-3668
+#[derive(Clone, Debug)]
+pub struct CcipReadProvider<P, T, N> {
+    inner: P,
+    layer: CcipReadLayer,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> Provider<T, N> for CcipReadProvider<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<T, N> {
+        self.inner.root()
+    }
+
+    fn call<'req>(&self, tx: &'req N::TransactionRequest) -> EthCall<'req, T, N, Bytes> {
+        let client = self.weak_client();
+        let layer = self.layer.clone();
+        self.inner.call(tx).wrap_caller(|inner| CcipCaller { inner, client, layer })
+    }
+}
+
+/// A [`Caller`] that resolves the offchain lookups of `eth_call`.
+///
+/// The call is sent to the inner caller, and only the callbacks of the lookups are sent to the
+/// client directly.
+#[derive(Debug)]
+struct CcipCaller<C, T> {
+    inner: C,
+    client: WeakClient<T>,
+    layer: CcipReadLayer,
+}
+
+impl<'req, T, N, C> Caller<T, EthCallParams<'req, N>, Bytes> for CcipCaller<C, T>
+where
+    T: Transport + Clone,
+    N: Network,
+    C: Caller<T, EthCallParams<'req, N>, Bytes>,
+{
+    fn call(
+        &self,
+        method: Cow<'static, str>,
+        params: EthCallParams<'req, N>,
+    ) -> TransportResult<ProviderCall<T, Value, Bytes>> {
+        if method != "eth_call" {
+            return self.inner.call(method, params);
+        }
+
+        let json = serde_json::to_value(&params).map_err(RpcError::ser_err)?;
+        let call = self.inner.call(method, params)?;
+        let client = self.client.clone();
+        let layer = self.layer.clone();
+        Ok(ProviderCall::BoxedFuture(Box::pin(async move {
+            match call.await {
+                Ok(output) => Ok(output),
+                Err(err) => resolve(&client, &layer, json, err).await,
+            }
+        })))
+    }
+}
+
+/// Resolves the offchain lookups of a failed call, until its callback succeeds.
+async fn resolve<T: Transport + Clone>(
+    client: &WeakClient<T>,
+    layer: &CcipReadLayer,
+    mut params: Value,
+    mut err: RpcError<TransportErrorKind>,
+) -> TransportResult<Bytes> {
+    let to = params[0].get("to").and_then(|to| serde_json::from_value::<Address>(to.clone()).ok());
+    for _ in 0..layer.config.max_lookups {
+        let Some(lookup) = err.as_error_resp().and_then(decode_lookup) else {
+            return Err(err);
+        };
+        let OffchainLookup { sender, urls, callData, callbackFunction, extraData } = lookup;
+        if Some(sender) != to {
+            return Err(RpcError::local_usage(CcipReadError::SenderMismatch { sender, to }));
+        }
+
+        let response =
+            fetch(layer, sender, &urls, &callData).await.map_err(RpcError::local_usage)?;
+        set_input(&mut params, callback_data(callbackFunction.0, response, extraData));
+
+        let client = client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        err = match client.request::<_, Bytes>("eth_call", params.clone()).await {
+            Ok(output) => return Ok(output),
+            Err(err) => err,
+        };
+    }
+    if err.as_error_resp().and_then(decode_lookup).is_some() {
+        return Err(RpcError::local_usage(CcipReadError::TooManyLookups(layer.config.max_lookups)));
+    }
+    Err(err)
+}
+
+/// Decodes the [`OffchainLookup`] revert of an error response, if any.
+fn decode_lookup(resp: &ErrorPayload) -> Option<OffchainLookup> {
+    OffchainLookup::abi_decode(&resp.as_revert_data()?, true).ok()
+}
+
+/// Replaces the input of the call in the `eth_call` parameters.
+fn set_input(params: &mut Value, input: Bytes) {
+    if let Some(tx) = params[0].as_object_mut() {
+        tx.remove("data");
+        tx.insert("input".to_string(), input.to_string().into());
+    }
+}
+
+/// Returns the calldata of the callback, `callbackFunction(response, extraData)`.
+fn callback_data(selector: [u8; 4], response: Bytes, extra_data: Bytes) -> Bytes {
+    [&selector[..], &(response, extra_data).abi_encode_params()].concat().into()
+}
+
+/// Queries the gateways in order until one of them responds.
+///
+/// As specified by EIP-3668, a client error of a gateway aborts the lookup, while other errors
+/// fall through to the next gateway. Gateways that are not allowed by the configuration are
+/// skipped.
+async fn fetch(
+    layer: &CcipReadLayer,
+    sender: Address,
+    urls: &[String],
+    data: &Bytes,
+) -> Result<Bytes, CcipReadError> {
+    let sender = hex::encode_prefixed(sender);
+    let data = hex::encode_prefixed(data);
+
+    let mut errors = Vec::new();
+    for template in urls {
+        let url = template.replace("{sender}", &sender).replace("{data}", &data);
+        if let Err(err) = layer.config.check_url(&url) {
+            errors.push(err.to_string());
+            continue;
+        }
+
+        let request = if template.contains("{data}") {
+            layer.http.get(&url)
+        } else {
+            let body = serde_json::json!({ "data": data, "sender": sender });
+            layer.http.post(&url).header("content-type", "application/json").body(body.to_string())
+        };
+
+        let resp = match request.send().await {
+            Ok(resp) => resp,
+            Err(err) => {
+                errors.push(format!("{url}: {err}"));
+                continue;
+            }
+        };
+        let status = resp.status();
+        let body = match resp.bytes().await {
+            Ok(body) => body,
+            Err(err) => {
+                errors.push(format!("{url}: {err}"));
+                continue;
+            }
+        };
+
+        if status.is_client_error() {
+            return Err(CcipReadError::Rejected {
+                url,
+                status: status.as_u16(),
+                message: gateway_message(&body),
+            });
+        }
+        if !status.is_success() {
+            errors.push(format!("{url}: status {status}: {}", gateway_message(&body)));
+            continue;
+        }
+        match serde_json::from_slice::<GatewayResponse>(&body) {
+            Ok(resp) => return Ok(resp.data),
+            Err(err) => errors.push(format!("{url}: invalid response: {err}")),
+        }
+    }
+    Err(CcipReadError::GatewaysFailed(errors))
+}
+
+/// The response of a gateway: `{"data": "0x.."}`.
+#[derive(serde::Deserialize)]
+struct GatewayResponse {
+    data: Bytes,
+}
+
+/// Returns the error message of a gateway response: `{"message": ".."}`, or the raw body.
+fn gateway_message(body: &[u8]) -> String {
+    serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|body| body.get("message")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| String::from_utf8_lossy(body).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+
+    #[test]
+    fn decodes_offchain_lookup() {
+        let lookup = OffchainLookup {
+            sender: address!("c1735677a60884abbcf72295e88d47764beda282"),
+            urls: vec!["https://gateway.example/{sender}/{data}.json".to_string()],
+            callData: bytes!("deadbeef"),
+            callbackFunction: [0x12, 0x34, 0x56, 0x78].into(),
+            extraData: bytes!("cafe"),
+        };
+        let data = Bytes::from(lookup.abi_encode());
+        assert_eq!(&data[..4], hex!("556f1830"));
+
+        let payload: ErrorPayload = serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": data,
+        }))
+        .unwrap();
+        assert_eq!(decode_lookup(&payload), Some(lookup));
+    }
+
+    #[test]
+    fn callback_calldata() {
+        let data = callback_data([0x12, 0x34, 0x56, 0x78], bytes!("aa"), bytes!("bb"));
+        let (response, extra_data) = <(Bytes, Bytes)>::abi_decode_params(&data[4..], true).unwrap();
+        assert_eq!(&data[..4], hex!("12345678"));
+        assert_eq!((response, extra_data), (bytes!("aa"), bytes!("bb")));
+    }
+
+    #[test]
+    fn replaces_call_input() {
+        let mut params = serde_json::json!([{ "to": "0xc1735677a60884abbcf72295e88d47764beda282", "data": "0x01", "input": "0x01" }, "latest"]);
+        set_input(&mut params, bytes!("0203"));
+        assert_eq!(
+            params,
+            serde_json::json!([{ "to": "0xc1735677a60884abbcf72295e88d47764beda282", "input": "0x0203" }, "latest"])
+        );
+    }
+
+    #[test]
+    fn gateway_allowlist() {
+        let config = CcipReadConfig::default();
+        assert!(config.check_url("http://localhost:8000/lookup").is_ok());
+        assert!(config.check_url("ftp://gateway.example").is_err());
+
+        let config = config.https_only().with_allowed_hosts(["gateway.example"]);
+        assert!(config.check_url("https://gateway.example/0x01").is_ok());
+        assert!(config.check_url("http://gateway.example/0x01").is_err());
+        assert!(config.check_url("https://evil.example/0x01").is_err());
+        assert!(config.check_url("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn skips_disallowed_gateways() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let _ = stream.read(&mut [0; 1024]).await.unwrap();
+            let body = r#"{"data":"0x1234"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let layer = CcipReadLayer::new(CcipReadConfig::default().with_allowed_hosts(["127.0.0.1"]));
+        let urls = [
+            "http://evil.example/{sender}/{data}".to_string(),
+            format!("http://127.0.0.1:{port}/{{sender}}/{{data}}"),
+        ];
+        let response = fetch(&layer, Address::ZERO, &urls, &bytes!("01")).await.unwrap();
+        assert_eq!(response, bytes!("1234"));
+
+        let err = fetch(&layer, Address::ZERO, &urls[..1], &bytes!("01")).await.unwrap_err();
+        assert!(
+            matches!(&err, CcipReadError::GatewaysFailed(errors) if errors[0].contains("not allowed")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn gateway_error_message() {
+        assert_eq!(gateway_message(br#"{"message":"unknown name"}"#), "unknown name");
+        assert_eq!(gateway_message(b"Bad Gateway"), "Bad Gateway");
+    }
+}
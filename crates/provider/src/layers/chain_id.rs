@@ -0,0 +1,142 @@
+use crate::{utils, Provider, ProviderCall, ProviderLayer, RootProvider};
+use alloy_network::Network;
+use alloy_primitives::{ChainId, U64};
+use alloy_rpc_client::NoParams;
+use alloy_transport::Transport;
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, RwLock},
+};
+
+/// A layer caching the chain ID of the endpoint after the first
+/// [`Provider::get_chain_id`] call.
+///
+/// This saves an `eth_chainId` request per filled transaction, but must only be used with
+/// endpoints whose chain ID does not change. The cache is shared by the clones of the layer, and
+/// must be [cleared](Self::clear) when the endpoint switches chains, e.g. after reconnecting to
+/// another node or after `anvil_setChainId` or `anvil_reset`.
+///
+/// ```
+/// # use alloy_provider::{layers::ChainIdCacheLayer, Provider, ProviderBuilder};
+/// # async fn test(url: url::Url) -> Result<(), Box<dyn std::error::Error>> {
+/// let layer = ChainIdCacheLayer::new();
+/// let provider = ProviderBuilder::new().layer(layer.clone()).on_http(url);
+///
+/// let chain_id = provider.get_chain_id().await?;
+/// assert_eq!(layer.cached_chain_id(), Some(chain_id));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ChainIdCacheLayer {
+    chain_id: Arc<RwLock<Option<ChainId>>>,
+}
+
+impl ChainIdCacheLayer {
+    /// Creates a layer with an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached chain ID, if it was already fetched.
+    pub fn cached_chain_id(&self) -> Option<ChainId> {
+        *self.chain_id.read().unwrap()
+    }
+
+    /// Clears the cached chain ID, so that it is fetched again by the next call.
+    pub fn clear(&self) {
+        *self.chain_id.write().unwrap() = None;
+    }
+}
+
+impl<P, T, N> ProviderLayer<P, T, N> for ChainIdCacheLayer
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    type Provider = ChainIdCacheProvider<P, T, N>;
+
+    fn layer(&self, inner: P) -> Self::Provider {
+        ChainIdCacheProvider { inner, cache: self.clone(), _pd: PhantomData }
+    }
+}
+
+/// A provider caching the chain ID of the endpoint, see [`ChainIdCacheLayer`].
+#[derive(Clone)]
+pub struct ChainIdCacheProvider<P, T, N> {
+    inner: P,
+    cache: ChainIdCacheLayer,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P: fmt::Debug, T, N> fmt::Debug for ChainIdCacheProvider<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainIdCacheProvider")
+            .field("inner", &self.inner)
+            .field("cache", &self.cache)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> ChainIdCacheProvider<P, T, N> {
+    /// Returns the cache of the provider.
+    pub const fn cache(&self) -> &ChainIdCacheLayer {
+        &self.cache
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> Provider<T, N> for ChainIdCacheProvider<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<T, N> {
+        self.inner.root()
+    }
+
+    fn get_chain_id(&self) -> ProviderCall<T, NoParams, U64, u64> {
+        if let Some(chain_id) = self.cache.cached_chain_id() {
+            return ProviderCall::ready(Ok(chain_id));
+        }
+
+        let cache = self.cache.chain_id.clone();
+        let call = self
+            .client()
+            .request_noparams("eth_chainId")
+            .map_resp(utils::convert_u64 as fn(U64) -> u64);
+        ProviderCall::BoxedFuture(Box::pin(async move {
+            let chain_id = call.await?;
+            *cache.write().unwrap() = Some(chain_id);
+            Ok(chain_id)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+
+    #[tokio::test]
+    async fn caches_chain_id() {
+        let layer = ChainIdCacheLayer::new();
+        let provider = ProviderBuilder::new().layer(layer.clone()).on_anvil();
+
+        let chain_id = provider.get_chain_id().await.unwrap();
+        assert_eq!(layer.cached_chain_id(), Some(chain_id));
+
+        // answered from the cache
+        *layer.chain_id.write().unwrap() = Some(chain_id + 1);
+        assert_eq!(provider.get_chain_id().await.unwrap(), chain_id + 1);
+
+        layer.clear();
+        assert_eq!(layer.cached_chain_id(), None);
+        assert_eq!(provider.get_chain_id().await.unwrap(), chain_id);
+    }
+}
@@ -0,0 +1,428 @@
+use crate::{PendingTransactionBuilder, Provider, ProviderLayer, RootProvider, SendableTx};
+use alloy_consensus::Transaction;
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_network::{Network, ReceiptResponse};
+use alloy_primitives::{keccak256, map::HashSet, Bytes, TxHash};
+use alloy_transport::{Transport, TransportError, TransportResult};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// The status of a transaction recorded by a [`Journal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JournalStatus {
+    /// The transaction was signed, and is being sent.
+    Signed,
+    /// The node accepted the transaction.
+    Submitted,
+    /// The node rejected the transaction, with the error.
+    Rejected(String),
+    /// The transaction was included in a block, see [`Journal::sync_status`].
+    Included {
+        /// The number of the block.
+        block_number: Option<u64>,
+        /// Whether the transaction succeeded.
+        success: bool,
+    },
+}
+
+/// A signed transaction recorded by a [`Journal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JournalEntry {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The nonce of the transaction, or `None` if the raw transaction could not be decoded.
+    pub nonce: Option<u64>,
+    /// The EIP-2718 encoded transaction.
+    pub payload: Bytes,
+    /// When the transaction was recorded, before it was sent.
+    pub timestamp: SystemTime,
+    /// The status of the transaction.
+    pub status: JournalStatus,
+    /// The idempotency key the transaction was sent with, see [`Journal::send_once`].
+    pub key: Option<String>,
+}
+
+/// A store of the entries of a [`Journal`].
+///
+/// Stores are called synchronously while transactions are sent, so persistent stores should be
+/// fast, e.g. by appending to a local file or database.
+pub trait JournalStore: fmt::Debug + Send + Sync {
+    /// Inserts a new entry.
+    fn insert(&self, entry: JournalEntry);
+
+    /// Updates the status of the entry of the transaction.
+    fn set_status(&self, hash: TxHash, status: JournalStatus);
+
+    /// Sets the idempotency key of the entry of the transaction.
+    fn set_key(&self, hash: TxHash, key: String);
+
+    /// Returns the entry of the transaction.
+    fn get(&self, hash: TxHash) -> Option<JournalEntry>;
+
+    /// Returns the latest entry with the idempotency key.
+    fn get_by_key(&self, key: &str) -> Option<JournalEntry>;
+
+    /// Returns all entries, in the order they were inserted.
+    fn entries(&self) -> Vec<JournalEntry>;
+}
+
+/// A [`JournalStore`] keeping the entries in memory.
+#[derive(Debug, Default)]
+pub struct MemoryJournalStore {
+    entries: Mutex<Vec<JournalEntry>>,
+    keys: Mutex<BTreeMap<String, TxHash>>,
+}
+
+impl MemoryJournalStore {
+    fn update(&self, hash: TxHash, f: impl FnOnce(&mut JournalEntry)) {
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(entry) = entries.iter_mut().rev().find(|entry| entry.hash == hash) {
+            f(entry);
+        }
+    }
+}
+
+impl JournalStore for MemoryJournalStore {
+    fn insert(&self, entry: JournalEntry) {
+        if let Some(key) = &entry.key {
+            self.keys.lock().unwrap().insert(key.clone(), entry.hash);
+        }
+        self.entries.lock().unwrap().push(entry);
+    }
+
+    fn set_status(&self, hash: TxHash, status: JournalStatus) {
+        self.update(hash, |entry| entry.status = status);
+    }
+
+    fn set_key(&self, hash: TxHash, key: String) {
+        self.keys.lock().unwrap().insert(key.clone(), hash);
+        self.update(hash, |entry| entry.key = Some(key));
+    }
+
+    fn get(&self, hash: TxHash) -> Option<JournalEntry> {
+        self.entries.lock().unwrap().iter().rev().find(|entry| entry.hash == hash).cloned()
+    }
+
+    fn get_by_key(&self, key: &str) -> Option<JournalEntry> {
+        let hash = *self.keys.lock().unwrap().get(key)?;
+        self.get(hash)
+    }
+
+    fn entries(&self) -> Vec<JournalEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+/// A journal of the signed transactions sent through a [`JournalLayer`], for audits and
+/// idempotent sends.
+///
+/// Journals are cheap to clone, clones share the store.
+#[derive(Clone, Debug)]
+pub struct Journal {
+    store: Arc<dyn JournalStore>,
+    in_flight: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Self::new(MemoryJournalStore::default())
+    }
+}
+
+impl Journal {
+    /// Creates a journal recording to the store.
+    pub fn new(store: impl JournalStore + 'static) -> Self {
+        Self { store: Arc::new(store), in_flight: Default::default() }
+    }
+
+    /// Returns the store of the journal.
+    pub fn store(&self) -> &dyn JournalStore {
+        &*self.store
+    }
+
+    /// Returns all entries, in the order they were recorded.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.store.entries()
+    }
+
+    /// Sends the transaction through the provider, unless a transaction was already sent with the
+    /// idempotency key, and returns the hash of the transaction sent with the key.
+    ///
+    /// The provider must record to this journal with a [`JournalLayer`]. Transactions rejected by
+    /// the node are sent again, and concurrent sends with the same key fail. The key is recorded
+    /// once the node accepted the transaction, so a crash while sending may still send it twice.
+    pub async fn send_once<P, T, N>(
+        &self,
+        provider: &P,
+        key: impl Into<String>,
+        tx: N::TransactionRequest,
+    ) -> TransportResult<TxHash>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let key = key.into();
+        if let Some(entry) = self.store.get_by_key(&key) {
+            if !matches!(entry.status, JournalStatus::Rejected(_)) {
+                return Ok(entry.hash);
+            }
+        }
+        if !self.in_flight.lock().unwrap().insert(key.clone()) {
+            return Err(TransportError::local_usage_str(&format!(
+                "transaction with idempotency key {key} is already being sent"
+            )));
+        }
+        let _guard = InFlightGuard { in_flight: &self.in_flight, key: &key };
+
+        let hash = *provider.send_transaction(tx).await?.tx_hash();
+        self.store.set_key(hash, key.clone());
+        Ok(hash)
+    }
+
+    /// Updates the status of the transaction from its receipt, if it was included, and returns the
+    /// updated entry.
+    pub async fn sync_status<P, T, N>(
+        &self,
+        provider: &P,
+        hash: TxHash,
+    ) -> TransportResult<Option<JournalEntry>>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        if let Some(receipt) = provider.get_transaction_receipt(hash).await? {
+            let status = JournalStatus::Included {
+                block_number: receipt.block_number(),
+                success: receipt.status(),
+            };
+            self.store.set_status(hash, status);
+        }
+        Ok(self.store.get(hash))
+    }
+
+    fn record(&self, payload: Bytes, nonce: Option<u64>) -> TxHash {
+        let hash = keccak256(&payload);
+        self.store.insert(JournalEntry {
+            hash,
+            nonce,
+            payload,
+            timestamp: SystemTime::now(),
+            status: JournalStatus::Signed,
+            key: None,
+        });
+        hash
+    }
+
+    fn record_result<T, N>(
+        &self,
+        hash: TxHash,
+        result: &TransportResult<PendingTransactionBuilder<'_, T, N>>,
+    ) where
+        T: Transport + Clone,
+        N: Network,
+    {
+        let status = match result {
+            Ok(_) => JournalStatus::Submitted,
+            Err(err) => JournalStatus::Rejected(err.to_string()),
+        };
+        self.store.set_status(hash, status);
+    }
+}
+
+/// Releases an idempotency key once its send completed or was cancelled.
+struct InFlightGuard<'a> {
+    in_flight: &'a Mutex<HashSet<String>>,
+    key: &'a str,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.in_flight.lock().unwrap().remove(self.key);
+    }
+}
+
+/// A layer that records the signed transactions sent through the provider to a [`Journal`].
+///
+/// Transactions are signed by the wallet filler, which wraps the layers of a
+/// [`ProviderBuilder`](crate::ProviderBuilder), so the layer sees the signed transactions. Requests
+/// signed by the node with `eth_sendTransaction` are not recorded.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(url: url::Url, wallet: alloy_network::EthereumWallet, tx: alloy_rpc_types_eth::TransactionRequest) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::{
+///     layers::{Journal, JournalLayer},
+///     ProviderBuilder,
+/// };
+///
+/// let journal = Journal::default();
+/// let provider = ProviderBuilder::new()
+///     .with_recommended_fillers()
+///     .wallet(wallet)
+///     .layer(JournalLayer::new(journal.clone()))
+///     .on_http(url);
+///
+/// // retrying this does not send the payment twice
+/// let hash = journal.send_once(&provider, "payment-42", tx).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JournalLayer {
+    journal: Journal,
+}
+
+impl JournalLayer {
+    /// Creates a layer recording to the journal.
+    pub const fn new(journal: Journal) -> Self {
+        Self { journal }
+    }
+
+    /// Returns the journal of the layer.
+    pub const fn journal(&self) -> &Journal {
+        &self.journal
+    }
+}
+
+impl<P, T, N> ProviderLayer<P, T, N> for JournalLayer
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    N::TxEnvelope: Transaction,
+{
+    type Provider = JournalProvider<P, T, N>;
+
+    fn layer(&self, inner: P) -> Self::Provider {
+        JournalProvider { inner, journal: self.journal.clone(), _pd: PhantomData }
+    }
+}
+
+/// A provider recording the signed transactions it sends to a [`Journal`], see
+/// [`JournalLayer`].
+#[derive(Clone)]
+pub struct JournalProvider<P, T, N> {
+    inner: P,
+    journal: Journal,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P: fmt::Debug, T, N> fmt::Debug for JournalProvider<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JournalProvider")
+            .field("inner", &self.inner)
+            .field("journal", &self.journal)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> JournalProvider<P, T, N> {
+    /// Returns the journal of the provider.
+    pub const fn journal(&self) -> &Journal {
+        &self.journal
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> Provider<T, N> for JournalProvider<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    N::TxEnvelope: Transaction,
+{
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<T, N> {
+        self.inner.root()
+    }
+
+    async fn send_raw_transaction(
+        &self,
+        encoded_tx: &[u8],
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        let nonce = N::TxEnvelope::decode_2718(&mut &encoded_tx[..]).ok().map(|tx| tx.nonce());
+        let hash = self.journal.record(Bytes::copy_from_slice(encoded_tx), nonce);
+        let result = self.inner.send_raw_transaction(encoded_tx).await;
+        self.journal.record_result(hash, &result);
+        result
+    }
+
+    #[doc(hidden)]
+    async fn send_transaction_internal(
+        &self,
+        tx: SendableTx<N>,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        let SendableTx::Envelope(envelope) = tx else {
+            return self.inner.send_transaction_internal(tx).await;
+        };
+        let hash = self.journal.record(envelope.encoded_2718().into(), Some(envelope.nonce()));
+        let result = self.inner.send_transaction_internal(SendableTx::Envelope(envelope)).await;
+        self.journal.record_result(hash, &result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_network::TransactionBuilder;
+    use alloy_primitives::{address, U256};
+    use alloy_rpc_types_eth::TransactionRequest;
+
+    #[test]
+    fn stores_entries() {
+        let store = MemoryJournalStore::default();
+        let entry = |hash, key: Option<&str>| JournalEntry {
+            hash,
+            nonce: Some(0),
+            payload: Bytes::new(),
+            timestamp: SystemTime::now(),
+            status: JournalStatus::Signed,
+            key: key.map(Into::into),
+        };
+        store.insert(entry(TxHash::with_last_byte(1), Some("a")));
+        store.insert(entry(TxHash::with_last_byte(2), None));
+        store.set_key(TxHash::with_last_byte(2), "b".into());
+        store.set_status(TxHash::with_last_byte(2), JournalStatus::Submitted);
+
+        assert_eq!(store.get_by_key("a").unwrap().hash, TxHash::with_last_byte(1));
+        let b = store.get_by_key("b").unwrap();
+        assert_eq!((b.hash, b.status), (TxHash::with_last_byte(2), JournalStatus::Submitted));
+        assert_eq!(store.entries().len(), 2);
+        assert!(store.get_by_key("c").is_none());
+    }
+
+    #[tokio::test]
+    async fn journals_sent_transactions() {
+        let journal = Journal::default();
+        let provider = ProviderBuilder::new()
+            .with_recommended_fillers()
+            .layer(JournalLayer::new(journal.clone()))
+            .on_anvil_with_wallet();
+
+        let tx = TransactionRequest::default()
+            .with_to(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+            .with_value(U256::from(100));
+        let hash = journal.send_once(&provider, "payment", tx.clone()).await.unwrap();
+        assert_eq!(journal.send_once(&provider, "payment", tx).await.unwrap(), hash);
+
+        let entries = journal.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].hash, hash);
+        assert_eq!(entries[0].nonce, Some(0));
+        assert_eq!(entries[0].key.as_deref(), Some("payment"));
+
+        let entry = journal.sync_status(&provider, hash).await.unwrap().unwrap();
+        assert!(matches!(entry.status, JournalStatus::Included { success: true, .. }));
+    }
+}
@@ -1,11 +1,35 @@
 //! Useful layer implementations for the provider. Currently this
-//! module contains the `AnvilLayer`, `AnvilProvider` and `ChainLayer`
-//! types.
+//! module contains the `AnvilLayer`, `AnvilProvider`, `CallCacheLayer`,
+//! `CallCacheProvider`, `CcipReadLayer`, `CcipReadProvider`,
+//! `ChainIdCacheLayer`, `ChainIdCacheProvider`, `ChainLayer`, `JournalLayer`,
+//! `JournalProvider`, `VerifiedLayer` and `VerifiedProvider` types.
 
 #[cfg(any(test, feature = "anvil-node"))]
 mod anvil;
 #[cfg(any(test, feature = "anvil-node"))]
 pub use anvil::{AnvilLayer, AnvilProvider};
 
+mod call_cache;
+pub use call_cache::{CallCache, CallCacheLayer, CallCacheMetrics, CallCacheProvider};
+
+#[cfg(feature = "ccip-read")]
+mod ccip;
+#[cfg(feature = "ccip-read")]
+pub use ccip::{CcipReadConfig, CcipReadError, CcipReadLayer, CcipReadProvider, OffchainLookup};
+
 mod chain;
 pub use chain::ChainLayer;
+
+mod chain_id;
+pub use chain_id::{ChainIdCacheLayer, ChainIdCacheProvider};
+
+mod journal;
+pub use journal::{
+    Journal, JournalEntry, JournalLayer, JournalProvider, JournalStatus, JournalStore,
+    MemoryJournalStore,
+};
+
+#[cfg(feature = "light-client")]
+mod verified;
+#[cfg(feature = "light-client")]
+pub use verified::{VerifiedLayer, VerifiedProvider};
@@ -0,0 +1,527 @@
+use alloy_consensus::{
+    constants::KECCAK_EMPTY,
+    proofs::{calculate_transaction_root, verify_account_proof, verify_storage_proof},
+    Account, TxEnvelope, EMPTY_ROOT_HASH,
+};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_json_rpc::{RpcError, RpcParam, RpcReturn};
+use alloy_network::Ethereum;
+use alloy_primitives::{
+    keccak256, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256, U64,
+};
+use alloy_rpc_client::{NoParams, WeakClient};
+use alloy_rpc_types_beacon::{
+    light_client::{LightClientHeader, LightClientStore},
+    payload::ExecutionPayloadHeader,
+};
+use alloy_rpc_types_eth::{
+    AccessListResult, Block, BlockTransactions, BlockTransactionsKind, EIP1186AccountProofResponse,
+    TransactionRequest,
+};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use std::{
+    borrow::Cow,
+    marker::PhantomData,
+    sync::{Arc, RwLock},
+};
+
+use crate::{Caller, EthCall, Provider, ProviderCall, ProviderLayer, RootProvider, RpcWithBlock};
+
+/// A layer that wraps a provider in a [`VerifiedProvider`], which only serves blocks attested by
+/// the headers of a [`LightClientStore`].
+#[derive(Clone, Debug)]
+pub struct VerifiedLayer {
+    store: Arc<RwLock<LightClientStore>>,
+}
+
+impl VerifiedLayer {
+    /// Creates a new layer from a light client store.
+    pub fn new(store: LightClientStore) -> Self {
+        Self { store: Arc::new(RwLock::new(store)) }
+    }
+
+    /// Creates a new layer from a shared light client store.
+    ///
+    /// The store can be advanced with new light client updates while the provider is in use.
+    pub const fn from_shared(store: Arc<RwLock<LightClientStore>>) -> Self {
+        Self { store }
+    }
+}
+
+impl<P, T> ProviderLayer<P, T, Ethereum> for VerifiedLayer
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    type Provider = VerifiedProvider<P, T>;
+
+    fn layer(&self, inner: P) -> Self::Provider {
+        VerifiedProvider::new(inner, self.store.clone())
+    }
+}
+
+/// A provider that verifies blocks against the execution headers of a [`LightClientStore`].
+///
+/// The light client store tracks the latest finalized and optimistic headers of the chain, as
+/// attested by the sync committee of the beacon chain. This provider only serves blocks that
+/// match one of these headers, and rejects all other blocks with an error:
+///
+/// - [`get_block_number`](Provider::get_block_number) returns the number of the optimistic header
+///   without a request.
+/// - [`get_block_by_number`](Provider::get_block_by_number) and
+///   [`get_block_by_hash`](Provider::get_block_by_hash) check that the hash of the returned header
+///   matches the attested block hash, and that full transactions match the transactions root.
+///   [`BlockNumberOrTag::Latest`] resolves to the optimistic header, and [`BlockNumberOrTag::Safe`]
+///   and [`BlockNumberOrTag::Finalized`] to the finalized header.
+/// - [`get_proof`](Provider::get_proof), [`get_account`](Provider::get_account),
+///   [`get_balance`](Provider::get_balance),
+///   [`get_transaction_count`](Provider::get_transaction_count),
+///   [`get_storage_at`](Provider::get_storage_at) and [`get_code_at`](Provider::get_code_at)
+///   fetch an `eth_getProof` proof at the attested block, and check it against the attested state
+///   root. The code is checked against the proven code hash.
+/// - [`call`](Provider::call), [`estimate_gas`](Provider::estimate_gas) and
+///   [`create_access_list`](Provider::create_access_list) can't be verified without executing the
+///   transaction locally, and always fail.
+///
+/// All other methods are forwarded to the inner provider unverified.
+#[derive(Clone, Debug)]
+pub struct VerifiedProvider<P, T> {
+    inner: P,
+    store: Arc<RwLock<LightClientStore>>,
+    _pd: PhantomData<fn() -> T>,
+}
+
+impl<P, T> VerifiedProvider<P, T>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    /// Creates a new verified provider.
+    pub const fn new(inner: P, store: Arc<RwLock<LightClientStore>>) -> Self {
+        Self { inner, store, _pd: PhantomData }
+    }
+
+    /// Returns the shared light client store.
+    pub const fn store(&self) -> &Arc<RwLock<LightClientStore>> {
+        &self.store
+    }
+
+    /// Returns the attested execution header for the given block number or tag, if any.
+    pub fn verified_header(&self, number: BlockNumberOrTag) -> Option<ExecutionPayloadHeader> {
+        attested_header(&self.store.read().unwrap(), number.into())
+    }
+
+    fn verified_header_by_hash(&self, hash: BlockHash) -> Option<ExecutionPayloadHeader> {
+        attested_header(&self.store.read().unwrap(), hash.into())
+    }
+
+    fn state_verifier(&self) -> StateVerifier<T> {
+        StateVerifier { client: self.weak_client(), store: self.store.clone() }
+    }
+}
+
+/// Returns the finalized and optimistic headers of the store.
+fn attested_headers(store: &LightClientStore) -> impl Iterator<Item = &LightClientHeader> {
+    [store.finalized_header(), store.optimistic_header()].into_iter()
+}
+
+/// Returns the attested execution header of the block, if any.
+fn attested_header(store: &LightClientStore, block: BlockId) -> Option<ExecutionPayloadHeader> {
+    let header = match block {
+        BlockId::Hash(hash) => {
+            attested_headers(store).find(|header| header.execution.block_hash == hash.block_hash)?
+        }
+        BlockId::Number(BlockNumberOrTag::Latest) => store.optimistic_header(),
+        BlockId::Number(BlockNumberOrTag::Safe | BlockNumberOrTag::Finalized) => {
+            store.finalized_header()
+        }
+        BlockId::Number(BlockNumberOrTag::Number(number)) => {
+            attested_headers(store).find(|header| header.execution.block_number == number)?
+        }
+        BlockId::Number(BlockNumberOrTag::Earliest | BlockNumberOrTag::Pending) => return None,
+    };
+    Some(header.execution.clone())
+}
+
+fn not_attested() -> RpcError<TransportErrorKind> {
+    RpcError::local_usage_str("block is not attested by the light client")
+}
+
+/// Fetches state with `eth_getProof`, and verifies it against the attested state roots.
+#[derive(Clone)]
+struct StateVerifier<T> {
+    client: WeakClient<T>,
+    store: Arc<RwLock<LightClientStore>>,
+}
+
+impl<T: Transport + Clone> StateVerifier<T> {
+    /// Returns the verified proof of the account and storage slots at the block, and the block
+    /// number it was verified at.
+    async fn proof(
+        &self,
+        block: BlockId,
+        address: Address,
+        keys: Vec<StorageKey>,
+    ) -> TransportResult<(BlockNumber, EIP1186AccountProofResponse)> {
+        let header =
+            attested_header(&self.store.read().unwrap(), block).ok_or_else(not_attested)?;
+        let client = self.client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        let proof: EIP1186AccountProofResponse = client
+            .request("eth_getProof", (address, &keys, BlockId::number(header.block_number)))
+            .await?;
+        verify_state_proof(&proof, header.state_root, address, &keys)?;
+        Ok((header.block_number, proof))
+    }
+
+    /// Returns the verified account at the block.
+    async fn account(&self, block: BlockId, address: Address) -> TransportResult<Account> {
+        let (_, proof) = self.proof(block, address, vec![]).await?;
+        Ok(proven_account(&proof))
+    }
+
+    /// Returns the verified value of the storage slot at the block.
+    async fn storage(
+        &self,
+        block: BlockId,
+        address: Address,
+        key: U256,
+    ) -> TransportResult<StorageValue> {
+        let (_, proof) = self.proof(block, address, vec![key.into()]).await?;
+        Ok(proof.storage_proof[0].value)
+    }
+
+    /// Returns the code of the account at the block, verified against the proven code hash.
+    async fn code(&self, block: BlockId, address: Address) -> TransportResult<Bytes> {
+        let (number, proof) = self.proof(block, address, vec![]).await?;
+        let client = self.client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        let code: Bytes = client.request("eth_getCode", (address, BlockId::number(number))).await?;
+        if keccak256(&code) != proven_account(&proof).code_hash {
+            return Err(RpcError::local_usage_str("code does not match the proven code hash"));
+        }
+        Ok(code)
+    }
+
+    /// Returns a [`RpcWithBlock`] resolving to the output of `f` at the requested block.
+    fn with_block<Params, Resp, Output, F, Fut>(
+        self,
+        f: F,
+    ) -> RpcWithBlock<T, Params, Resp, Output, fn(Resp) -> Output>
+    where
+        Params: RpcParam,
+        Resp: RpcReturn,
+        Output: 'static,
+        F: Fn(Self, BlockId) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = TransportResult<Output>> + Send + 'static,
+    {
+        RpcWithBlock::new_provider(move |block| {
+            ProviderCall::BoxedFuture(Box::pin(f(self.clone(), block)))
+        })
+    }
+}
+
+/// Returns the account of the proof.
+///
+/// Nodes report addresses without an account with a zero or empty storage and code hash.
+fn proven_account(proof: &EIP1186AccountProofResponse) -> Account {
+    Account {
+        nonce: proof.nonce,
+        balance: proof.balance,
+        storage_root: if proof.storage_hash.is_zero() {
+            EMPTY_ROOT_HASH
+        } else {
+            proof.storage_hash
+        },
+        code_hash: if proof.code_hash.is_zero() { KECCAK_EMPTY } else { proof.code_hash },
+    }
+}
+
+/// Verifies that the proof is the proof of the account and storage slots, against the state
+/// root.
+fn verify_state_proof(
+    proof: &EIP1186AccountProofResponse,
+    state_root: B256,
+    address: Address,
+    keys: &[StorageKey],
+) -> TransportResult<()> {
+    if proof.address != address
+        || proof.storage_proof.len() != keys.len()
+        || proof.storage_proof.iter().zip(keys).any(|(storage, key)| storage.key.0 != *key)
+    {
+        return Err(RpcError::local_usage_str("proof is not the proof of the requested state"));
+    }
+
+    let account = proven_account(proof);
+    let exists = account != Account::default();
+    verify_account_proof(state_root, address, exists.then_some(account), &proof.account_proof)
+        .map_err(RpcError::local_usage)?;
+    for storage in &proof.storage_proof {
+        verify_storage_proof(account.storage_root, storage.key.0, storage.value, &storage.proof)
+            .map_err(RpcError::local_usage)?;
+    }
+    Ok(())
+}
+
+/// Rejects calls, whose results can't be verified against the state root.
+struct Unverifiable;
+
+impl<T, Params, Resp> Caller<T, Params, Resp> for Unverifiable
+where
+    T: Transport + Clone,
+    Params: RpcParam,
+    Resp: RpcReturn,
+{
+    fn call(
+        &self,
+        method: Cow<'static, str>,
+        _params: Params,
+    ) -> TransportResult<ProviderCall<T, serde_json::Value, Resp>> {
+        Err(unverifiable(&method))
+    }
+}
+
+fn unverifiable(method: &str) -> RpcError<TransportErrorKind> {
+    RpcError::local_usage_str(&format!("{method} can't be verified by the light client"))
+}
+
+/// Verifies that the block matches the attested execution header.
+fn verify_block(block: &Block, expected: &ExecutionPayloadHeader) -> TransportResult<()> {
+    let header =
+        alloy_consensus::Header::try_from(block.header.clone()).map_err(RpcError::local_usage)?;
+    if block.header.hash != expected.block_hash || header.hash_slow() != expected.block_hash {
+        return Err(RpcError::local_usage_str("block does not match the attested block hash"));
+    }
+
+    if let BlockTransactions::Full(transactions) = &block.transactions {
+        let transactions = transactions
+            .iter()
+            .cloned()
+            .map(TxEnvelope::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RpcError::local_usage)?;
+        if calculate_transaction_root(&transactions) != header.transactions_root {
+            return Err(RpcError::local_usage_str(
+                "transactions do not match the attested transactions root",
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T> Provider<T> for VerifiedProvider<P, T>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<T> {
+        self.inner.root()
+    }
+
+    fn get_block_number(&self) -> ProviderCall<T, NoParams, U64, BlockNumber> {
+        let number = self.store.read().unwrap().optimistic_header().execution.block_number;
+        ProviderCall::ready(Ok(number))
+    }
+
+    async fn get_block_by_hash(
+        &self,
+        hash: BlockHash,
+        kind: BlockTransactionsKind,
+    ) -> TransportResult<Option<Block>> {
+        let expected = self.verified_header_by_hash(hash).ok_or_else(|| {
+            RpcError::local_usage_str("block is not attested by the light client")
+        })?;
+        let block = self.inner.get_block_by_hash(hash, kind).await?;
+        block.as_ref().map(|block| verify_block(block, &expected)).transpose()?;
+        Ok(block)
+    }
+
+    async fn get_block_by_number(
+        &self,
+        number: BlockNumberOrTag,
+        hydrate: bool,
+    ) -> TransportResult<Option<Block>> {
+        let expected = self.verified_header(number).ok_or_else(|| {
+            RpcError::local_usage_str("block is not attested by the light client")
+        })?;
+        // request the attested block by number, as the tags of the node may differ
+        let block = self
+            .inner
+            .get_block_by_number(BlockNumberOrTag::Number(expected.block_number), hydrate)
+            .await?;
+        block.as_ref().map(|block| verify_block(block, &expected)).transpose()?;
+        Ok(block)
+    }
+
+    fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<StorageKey>,
+    ) -> RpcWithBlock<T, (Address, Vec<StorageKey>), EIP1186AccountProofResponse> {
+        self.state_verifier().with_block(move |verifier, block| {
+            let keys = keys.clone();
+            async move { Ok(verifier.proof(block, address, keys).await?.1) }
+        })
+    }
+
+    fn get_account(&self, address: Address) -> RpcWithBlock<T, Address, Account> {
+        self.state_verifier().with_block(move |verifier, block| async move {
+            verifier.account(block, address).await
+        })
+    }
+
+    fn get_balance(&self, address: Address) -> RpcWithBlock<T, Address, U256, U256> {
+        self.state_verifier().with_block(move |verifier, block| async move {
+            Ok(verifier.account(block, address).await?.balance)
+        })
+    }
+
+    fn get_transaction_count(
+        &self,
+        address: Address,
+    ) -> RpcWithBlock<T, Address, U64, u64, fn(U64) -> u64> {
+        self.state_verifier().with_block(move |verifier, block| async move {
+            Ok(verifier.account(block, address).await?.nonce)
+        })
+    }
+
+    fn get_storage_at(
+        &self,
+        address: Address,
+        key: U256,
+    ) -> RpcWithBlock<T, (Address, U256), StorageValue> {
+        self.state_verifier().with_block(move |verifier, block| async move {
+            verifier.storage(block, address, key).await
+        })
+    }
+
+    fn get_code_at(&self, address: Address) -> RpcWithBlock<T, Address, Bytes> {
+        self.state_verifier()
+            .with_block(move |verifier, block| async move { verifier.code(block, address).await })
+    }
+
+    fn call<'req>(&self, tx: &'req TransactionRequest) -> EthCall<'req, T, Ethereum, Bytes> {
+        EthCall::new(Unverifiable, tx)
+    }
+
+    fn estimate_gas<'req>(
+        &self,
+        tx: &'req TransactionRequest,
+    ) -> EthCall<'req, T, Ethereum, U64, u64> {
+        EthCall::gas_estimate(Unverifiable, tx).map_resp(crate::utils::convert_u64)
+    }
+
+    fn create_access_list<'a>(
+        &self,
+        _request: &'a TransactionRequest,
+    ) -> RpcWithBlock<T, &'a TransactionRequest, AccessListResult> {
+        RpcWithBlock::new_provider(|_| {
+            ProviderCall::ready(Err(unverifiable("eth_createAccessList")))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use alloy_rpc_types_eth::EIP1186StorageProof;
+    use alloy_transport::BoxTransport;
+
+    fn block(transactions_root: B256) -> Block {
+        let mut block = Block::<alloy_rpc_types_eth::Transaction>::default();
+        block.header.number = 1;
+        block.header.transactions_root = transactions_root;
+        block.header.mix_hash = Some(B256::ZERO);
+        block.header.nonce = Some(Default::default());
+        block.header.hash =
+            alloy_consensus::Header::try_from(block.header.clone()).unwrap().hash_slow();
+        block.transactions = BlockTransactions::Full(vec![]);
+        block
+    }
+
+    #[test]
+    fn verifies_attested_blocks() {
+        let block = block(EMPTY_ROOT_HASH);
+        let expected = ExecutionPayloadHeader {
+            block_number: 1,
+            block_hash: block.header.hash,
+            ..Default::default()
+        };
+        verify_block(&block, &expected).unwrap();
+
+        let wrong_hash = ExecutionPayloadHeader { block_hash: B256::ZERO, ..expected.clone() };
+        assert!(verify_block(&block, &wrong_hash).is_err());
+
+        // the transactions do not match the transactions root of the attested header
+        let block = self::block(B256::ZERO);
+        let expected = ExecutionPayloadHeader { block_hash: block.header.hash, ..expected };
+        assert!(verify_block(&block, &expected).is_err());
+    }
+
+    /// Returns the root and the proof of a trie with a single leaf.
+    fn single_leaf_trie(key: impl AsRef<[u8]>, value: &[u8]) -> (B256, Vec<Bytes>) {
+        let mut path = vec![0x20];
+        path.extend_from_slice(keccak256(key).as_slice());
+        let mut node = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: path.as_slice().length() + value.length() }
+            .encode(&mut node);
+        path.as_slice().encode(&mut node);
+        value.encode(&mut node);
+        (keccak256(&node), vec![node.into()])
+    }
+
+    fn state_proof() -> (B256, EIP1186AccountProofResponse) {
+        let address = Address::with_last_byte(1);
+        let key = B256::with_last_byte(2);
+        let value = U256::from(3);
+        let (storage_root, storage_proof) = single_leaf_trie(key, &alloy_rlp::encode(value));
+        let account =
+            Account { nonce: 4, balance: U256::from(5), storage_root, ..Default::default() };
+        let (state_root, account_proof) = single_leaf_trie(address, &alloy_rlp::encode(account));
+
+        let proof = EIP1186AccountProofResponse {
+            address,
+            balance: account.balance,
+            code_hash: account.code_hash,
+            nonce: account.nonce,
+            storage_hash: storage_root,
+            account_proof,
+            storage_proof: vec![EIP1186StorageProof {
+                key: key.into(),
+                value,
+                proof: storage_proof,
+            }],
+        };
+        (state_root, proof)
+    }
+
+    #[test]
+    fn verifies_state_proofs() {
+        let (state_root, proof) = state_proof();
+        let keys = [B256::with_last_byte(2)];
+        verify_state_proof(&proof, state_root, proof.address, &keys).unwrap();
+
+        assert!(verify_state_proof(&proof, B256::ZERO, proof.address, &keys).is_err());
+        // the proof of another account or slot
+        assert!(verify_state_proof(&proof, state_root, Address::ZERO, &keys).is_err());
+        assert!(verify_state_proof(&proof, state_root, proof.address, &[B256::ZERO]).is_err());
+
+        let mut wrong_balance = proof.clone();
+        wrong_balance.balance += U256::from(1);
+        assert!(verify_state_proof(&wrong_balance, state_root, proof.address, &keys).is_err());
+
+        let mut wrong_value = proof.clone();
+        wrong_value.storage_proof[0].value = U256::ZERO;
+        assert!(verify_state_proof(&wrong_value, state_root, proof.address, &keys).is_err());
+    }
+
+    #[test]
+    fn rejects_unverifiable_calls() {
+        let err = Caller::<BoxTransport, (), Bytes>::call(&Unverifiable, "eth_call".into(), ())
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("eth_call can't be verified"));
+    }
+}
@@ -30,6 +30,9 @@ pub use builder::{Identity, ProviderBuilder, ProviderLayer, Stack};
 
 mod blocks;
 
+#[cfg(feature = "defi")]
+pub mod defi;
+
 pub mod ext;
 
 pub mod fillers;
@@ -40,12 +43,36 @@ pub use heart::{
     PendingTransactionError, WatchTxError,
 };
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ingest;
+
 pub mod layers;
 
+pub mod mempool;
+#[cfg(feature = "pubsub")]
+pub use mempool::{PendingTransactionStream, PendingTransactionsConfig};
+
+#[cfg(feature = "nft")]
+pub mod nft;
+
+mod pagination;
+pub use pagination::{Page, PageCursor, Paginated};
+
+pub mod predeploys;
+
 mod provider;
+#[cfg(feature = "pubsub")]
+pub use provider::LogStream;
 pub use provider::{
-    builder, Caller, EthCall, EthCallParams, FilterPollerBuilder, ParamsWithBlock, Provider,
-    ProviderCall, RootProvider, RpcWithBlock, SendableTx, WalletProvider,
+    array_slot, builder, mapping_slot, AddressActivity, AtBlock, BlockMismatch, BlockVerification,
+    CallGraph, CallGraphMode, CallHandle, CallId, CallOutputs, Caller, Capabilities, CodeKind,
+    CodeMetadata, Divergence, EthCall, EthCallParams, Execution, ExecutionComparer, ExecutionDiff,
+    FeeDataset, FeeDatasetBuilder, FeeSample, FilterPollerBuilder, Funding, HeaderCache,
+    LogConsistency, LogConsistencyError, NodeIdentity, NodeKind, NodeQuirks, ParamsWithBlock,
+    PreState, Provider, ProviderCall, RevertReason, RootProvider, RpcWithBlock, SendableTx,
+    StateChange, StorageReader, TokenAllowance, TokenMetadata, TokenMetadataResolver,
+    WalletProvider, EIP1822_PROXIABLE_SLOT, EIP1967_BEACON_SLOT, EIP1967_IMPLEMENTATION_SLOT,
+    EIP7702_DELEGATION_PREFIX, MULTICALL3_ADDRESS,
 };
 
 pub mod utils;
@@ -0,0 +1,455 @@
+use alloy_network_primitives::TransactionResponse;
+use alloy_primitives::{Address, Selector, U256};
+use alloy_sol_types::{sol, SolCall};
+use std::collections::HashMap;
+
+sol! {
+    #[allow(missing_docs)]
+    interface IERC20 {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool);
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+
+    #[allow(missing_docs)]
+    interface IUniswapV2Router {
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline);
+        function swapTokensForExactTokens(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline);
+        function swapExactETHForTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline);
+        function swapTokensForExactETH(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline);
+        function swapExactTokensForETH(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline);
+        function swapETHForExactTokens(uint256 amountOut, address[] path, address to, uint256 deadline);
+        function swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline);
+        function swapExactETHForTokensSupportingFeeOnTransferTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline);
+        function swapExactTokensForETHSupportingFeeOnTransferTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline);
+    }
+
+    #[allow(missing_docs)]
+    interface IUniswapV3Router {
+        struct ExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 deadline;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+            uint160 sqrtPriceLimitX96;
+        }
+        struct ExactInputParams {
+            bytes path;
+            address recipient;
+            uint256 deadline;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+        }
+        struct ExactOutputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 deadline;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+            uint160 sqrtPriceLimitX96;
+        }
+        struct ExactOutputParams {
+            bytes path;
+            address recipient;
+            uint256 deadline;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+        }
+        function exactInputSingle(ExactInputSingleParams params) external payable returns (uint256 amountOut);
+        function exactInput(ExactInputParams params) external payable returns (uint256 amountOut);
+        function exactOutputSingle(ExactOutputSingleParams params) external payable returns (uint256 amountIn);
+        function exactOutput(ExactOutputParams params) external payable returns (uint256 amountIn);
+    }
+
+    #[allow(missing_docs)]
+    interface IUniversalRouter {
+        function execute(bytes commands, bytes[] inputs, uint256 deadline) external payable;
+        function execute(bytes commands, bytes[] inputs) external payable;
+    }
+}
+
+/// The protocol of a swap recognized by a [`TxClassifier`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SwapProtocol {
+    /// A Uniswap V2 style router, also used by its many forks.
+    UniswapV2,
+    /// A Uniswap V3 `SwapRouter`.
+    UniswapV3,
+    /// The Uniswap `UniversalRouter`.
+    UniversalRouter,
+    /// Some other protocol, registered with [`TxClassifier::with_swap_selector`] or
+    /// [`TxClassifier::with_router`].
+    Other,
+}
+
+/// The classification of a pending transaction by a [`TxClassifier`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TxClassification {
+    /// A contract deployment.
+    Deployment,
+    /// An [EIP-4844] blob transaction.
+    ///
+    /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+    Blob {
+        /// The number of blobs.
+        blob_count: usize,
+    },
+    /// A transfer of ether without calldata.
+    NativeTransfer {
+        /// The recipient.
+        to: Address,
+        /// The transferred value.
+        value: U256,
+    },
+    /// An ERC-20 `transfer` or `transferFrom`.
+    Erc20Transfer {
+        /// The token contract.
+        token: Address,
+        /// The owner of the transferred tokens.
+        from: Address,
+        /// The recipient.
+        to: Address,
+        /// The transferred amount.
+        amount: U256,
+    },
+    /// An ERC-20 `approve`.
+    Erc20Approval {
+        /// The token contract.
+        token: Address,
+        /// The owner of the tokens.
+        owner: Address,
+        /// The approved spender.
+        spender: Address,
+        /// The approved amount.
+        amount: U256,
+    },
+    /// A swap through a known router.
+    Swap {
+        /// The router contract.
+        router: Address,
+        /// The protocol of the router.
+        protocol: SwapProtocol,
+        /// The selector of the called function, if any.
+        selector: Option<Selector>,
+    },
+    /// Any other contract call.
+    Call {
+        /// The called contract.
+        to: Address,
+        /// The selector of the called function, if the calldata is long enough.
+        selector: Option<Selector>,
+    },
+}
+
+/// Classifies pending transactions with selector tables and heuristics.
+///
+/// Transactions are classified in this order:
+///
+/// 1. Transactions without a recipient are [deployments](TxClassification::Deployment).
+/// 2. Transactions with blobs are [blob transactions](TxClassification::Blob).
+/// 3. Transactions without calldata are [native transfers](TxClassification::NativeTransfer).
+/// 4. Calls to a [known router](Self::with_router), or with a [known swap
+///    selector](Self::with_swap_selector), are [swaps](TxClassification::Swap).
+/// 5. Calls that decode as ERC-20 `transfer`, `transferFrom` or `approve` are [ERC-20
+///    transfers](TxClassification::Erc20Transfer) or [approvals](TxClassification::Erc20Approval).
+/// 6. Anything else is a [contract call](TxClassification::Call).
+///
+/// The classification only looks at the transaction itself, so e.g. a call with the ERC-20
+/// `transfer` selector to a contract that is not a token is still classified as a transfer.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_provider::mempool::{TxClassification, TxClassifier};
+/// use alloy_rpc_types_eth::Transaction;
+///
+/// let classifier = TxClassifier::new();
+/// let tx = Transaction::default();
+/// assert_eq!(classifier.classify(&tx), TxClassification::Deployment);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TxClassifier {
+    swap_selectors: HashMap<Selector, SwapProtocol>,
+    routers: HashMap<Address, SwapProtocol>,
+}
+
+impl Default for TxClassifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxClassifier {
+    /// Creates a new classifier with the selectors of the Uniswap V2, V3 and Universal routers.
+    pub fn new() -> Self {
+        use IUniswapV2Router as V2;
+        use IUniswapV3Router as V3;
+        use IUniversalRouter as Universal;
+
+        let v2 = [
+            V2::swapExactTokensForTokensCall::SELECTOR,
+            V2::swapTokensForExactTokensCall::SELECTOR,
+            V2::swapExactETHForTokensCall::SELECTOR,
+            V2::swapTokensForExactETHCall::SELECTOR,
+            V2::swapExactTokensForETHCall::SELECTOR,
+            V2::swapETHForExactTokensCall::SELECTOR,
+            V2::swapExactTokensForTokensSupportingFeeOnTransferTokensCall::SELECTOR,
+            V2::swapExactETHForTokensSupportingFeeOnTransferTokensCall::SELECTOR,
+            V2::swapExactTokensForETHSupportingFeeOnTransferTokensCall::SELECTOR,
+        ];
+        let v3 = [
+            V3::exactInputSingleCall::SELECTOR,
+            V3::exactInputCall::SELECTOR,
+            V3::exactOutputSingleCall::SELECTOR,
+            V3::exactOutputCall::SELECTOR,
+        ];
+        let universal = [Universal::execute_0Call::SELECTOR, Universal::execute_1Call::SELECTOR];
+
+        let swap_selectors = v2
+            .into_iter()
+            .map(|selector| (selector.into(), SwapProtocol::UniswapV2))
+            .chain(v3.into_iter().map(|selector| (selector.into(), SwapProtocol::UniswapV3)))
+            .chain(
+                universal
+                    .into_iter()
+                    .map(|selector| (selector.into(), SwapProtocol::UniversalRouter)),
+            )
+            .collect();
+        Self { swap_selectors, routers: HashMap::new() }
+    }
+
+    /// Creates a new classifier without any swap selectors.
+    pub fn empty() -> Self {
+        Self { swap_selectors: HashMap::new(), routers: HashMap::new() }
+    }
+
+    /// Classifies calls with the selector as swaps of the protocol.
+    pub fn with_swap_selector(mut self, selector: Selector, protocol: SwapProtocol) -> Self {
+        self.swap_selectors.insert(selector, protocol);
+        self
+    }
+
+    /// Classifies all calls to the router as swaps of the protocol, whatever their selector.
+    ///
+    /// This is useful for aggregators, which have too many entry points to list their selectors.
+    pub fn with_router(mut self, router: Address, protocol: SwapProtocol) -> Self {
+        self.routers.insert(router, protocol);
+        self
+    }
+
+    /// Classifies the transaction.
+    pub fn classify<Tx: TransactionResponse>(&self, tx: &Tx) -> TxClassification {
+        let Some(to) = tx.to() else {
+            return TxClassification::Deployment;
+        };
+        if let Some(blob_count) =
+            tx.blob_versioned_hashes().map(|hashes| hashes.len()).filter(|count| *count > 0)
+        {
+            return TxClassification::Blob { blob_count };
+        }
+
+        let input = tx.input();
+        if input.is_empty() {
+            return TxClassification::NativeTransfer { to, value: tx.value() };
+        }
+        let selector = input.get(..4).map(Selector::from_slice);
+
+        if let Some(protocol) = self
+            .routers
+            .get(&to)
+            .or_else(|| selector.and_then(|selector| self.swap_selectors.get(&selector)))
+        {
+            return TxClassification::Swap { router: to, protocol: *protocol, selector };
+        }
+
+        match selector.map(|selector| selector.0) {
+            Some(IERC20::transferCall::SELECTOR) => {
+                if let Ok(call) = IERC20::transferCall::abi_decode(input, true) {
+                    return TxClassification::Erc20Transfer {
+                        token: to,
+                        from: tx.from(),
+                        to: call.to,
+                        amount: call.amount,
+                    };
+                }
+            }
+            Some(IERC20::transferFromCall::SELECTOR) => {
+                if let Ok(call) = IERC20::transferFromCall::abi_decode(input, true) {
+                    return TxClassification::Erc20Transfer {
+                        token: to,
+                        from: call.from,
+                        to: call.to,
+                        amount: call.amount,
+                    };
+                }
+            }
+            Some(IERC20::approveCall::SELECTOR) => {
+                if let Ok(call) = IERC20::approveCall::abi_decode(input, true) {
+                    return TxClassification::Erc20Approval {
+                        token: to,
+                        owner: tx.from(),
+                        spender: call.spender,
+                        amount: call.amount,
+                    };
+                }
+            }
+            _ => {}
+        }
+
+        TxClassification::Call { to, selector }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, bytes, fixed_bytes};
+    use alloy_rpc_types_eth::Transaction;
+
+    const TOKEN: Address = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+    const SENDER: Address = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+
+    fn call(to: Address, input: Vec<u8>) -> Transaction {
+        Transaction { from: SENDER, to: Some(to), input: input.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn known_selectors() {
+        assert_eq!(IERC20::transferCall::SELECTOR, fixed_bytes!("a9059cbb"));
+        assert_eq!(IERC20::approveCall::SELECTOR, fixed_bytes!("095ea7b3"));
+        assert_eq!(
+            IUniswapV2Router::swapExactTokensForTokensCall::SELECTOR,
+            fixed_bytes!("38ed1739")
+        );
+        assert_eq!(IUniswapV3Router::exactInputSingleCall::SELECTOR, fixed_bytes!("414bf389"));
+        assert_eq!(IUniversalRouter::execute_0Call::SELECTOR, fixed_bytes!("3593564c"));
+    }
+
+    #[test]
+    fn classify_erc20() {
+        let classifier = TxClassifier::new();
+        let recipient = Address::with_last_byte(1);
+
+        let input = IERC20::transferCall { to: recipient, amount: U256::from(5) }.abi_encode();
+        assert_eq!(
+            classifier.classify(&call(TOKEN, input)),
+            TxClassification::Erc20Transfer {
+                token: TOKEN,
+                from: SENDER,
+                to: recipient,
+                amount: U256::from(5)
+            }
+        );
+
+        let input = IERC20::transferFromCall { from: recipient, to: SENDER, amount: U256::from(7) }
+            .abi_encode();
+        assert_eq!(
+            classifier.classify(&call(TOKEN, input)),
+            TxClassification::Erc20Transfer {
+                token: TOKEN,
+                from: recipient,
+                to: SENDER,
+                amount: U256::from(7)
+            }
+        );
+
+        let input = IERC20::approveCall { spender: recipient, amount: U256::MAX }.abi_encode();
+        assert_eq!(
+            classifier.classify(&call(TOKEN, input)),
+            TxClassification::Erc20Approval {
+                token: TOKEN,
+                owner: SENDER,
+                spender: recipient,
+                amount: U256::MAX
+            }
+        );
+
+        // truncated calldata is not a transfer
+        let input = IERC20::transferCall::SELECTOR.to_vec();
+        assert_eq!(
+            classifier.classify(&call(TOKEN, input)),
+            TxClassification::Call {
+                to: TOKEN,
+                selector: Some(IERC20::transferCall::SELECTOR.into())
+            }
+        );
+    }
+
+    #[test]
+    fn classify_swaps() {
+        let router = address!("7a250d5630B4cF539739dF2C5dAcb4c659F2488D");
+        let input = IUniswapV2Router::swapExactETHForTokensCall {
+            amountOutMin: U256::from(1),
+            path: vec![Address::with_last_byte(1), TOKEN],
+            to: SENDER,
+            deadline: U256::MAX,
+        }
+        .abi_encode();
+        let selector = Some(IUniswapV2Router::swapExactETHForTokensCall::SELECTOR.into());
+        assert_eq!(
+            TxClassifier::new().classify(&call(router, input.clone())),
+            TxClassification::Swap { router, protocol: SwapProtocol::UniswapV2, selector }
+        );
+        assert_eq!(
+            TxClassifier::empty().classify(&call(router, input)),
+            TxClassification::Call { to: router, selector }
+        );
+
+        let aggregator = Address::with_last_byte(0x11);
+        let classifier = TxClassifier::empty()
+            .with_router(aggregator, SwapProtocol::Other)
+            .with_swap_selector(fixed_bytes!("12345678"), SwapProtocol::Other);
+        assert_eq!(
+            classifier.classify(&call(aggregator, bytes!("aabbccdd").to_vec())),
+            TxClassification::Swap {
+                router: aggregator,
+                protocol: SwapProtocol::Other,
+                selector: Some(fixed_bytes!("aabbccdd"))
+            }
+        );
+        assert_eq!(
+            classifier.classify(&call(TOKEN, bytes!("12345678").to_vec())),
+            TxClassification::Swap {
+                router: TOKEN,
+                protocol: SwapProtocol::Other,
+                selector: Some(fixed_bytes!("12345678"))
+            }
+        );
+    }
+
+    #[test]
+    fn classify_other() {
+        let classifier = TxClassifier::new();
+        assert_eq!(
+            classifier.classify(&Transaction { input: bytes!("6080"), ..Default::default() }),
+            TxClassification::Deployment
+        );
+
+        let blob = Transaction {
+            to: Some(TOKEN),
+            blob_versioned_hashes: Some(vec![b256!(
+                "01b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded28"
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(classifier.classify(&blob), TxClassification::Blob { blob_count: 1 });
+
+        let transfer = Transaction { to: Some(TOKEN), value: U256::from(1), ..Default::default() };
+        assert_eq!(
+            classifier.classify(&transfer),
+            TxClassification::NativeTransfer { to: TOKEN, value: U256::from(1) }
+        );
+
+        assert_eq!(
+            classifier.classify(&call(TOKEN, vec![1, 2])),
+            TxClassification::Call { to: TOKEN, selector: None }
+        );
+    }
+}
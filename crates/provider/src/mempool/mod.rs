@@ -0,0 +1,11 @@
+//! Utilities for pending transactions in the mempool.
+
+mod classify;
+pub use classify::{SwapProtocol, TxClassification, TxClassifier};
+
+#[cfg(feature = "pubsub")]
+mod stream;
+#[cfg(feature = "pubsub")]
+pub(crate) use stream::{pending_transaction_stream, Pending};
+#[cfg(feature = "pubsub")]
+pub use stream::{PendingTransactionStream, PendingTransactionsConfig};
@@ -0,0 +1,248 @@
+//! Streams of pending transactions.
+
+use alloy_json_rpc::RpcReturn;
+use alloy_network_primitives::TransactionResponse;
+use alloy_primitives::TxHash;
+use alloy_rpc_client::{BatchRequest, WeakClient};
+use alloy_transport::Transport;
+use futures::{stream, Stream, StreamExt};
+use lru::LruCache;
+use std::{fmt, num::NonZeroUsize, pin::Pin, sync::Arc};
+
+/// A stream of pending transactions, see [`Provider::stream_full_pending_transactions`].
+///
+/// [`Provider::stream_full_pending_transactions`]: crate::Provider::stream_full_pending_transactions
+pub type PendingTransactionStream<Tx> = Pin<Box<dyn Stream<Item = Tx> + Send>>;
+
+type TxFilter<Tx> = Arc<dyn Fn(&Tx) -> bool + Send + Sync>;
+
+/// The default number of transaction hashes that are fetched in one batch.
+const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// The default number of recent transaction hashes that are remembered for deduplication.
+const DEFAULT_DEDUP_CAPACITY: NonZeroUsize = match NonZeroUsize::new(10_000) {
+    Some(capacity) => capacity,
+    None => unreachable!(),
+};
+
+/// Configuration of a [`PendingTransactionStream`].
+///
+/// # Examples
+///
+/// ```
+/// use alloy_network_primitives::TransactionResponse;
+/// use alloy_primitives::address;
+/// use alloy_provider::PendingTransactionsConfig;
+/// use alloy_rpc_types_eth::Transaction;
+///
+/// let router = address!("7a250d5630B4cF539739dF2C5dAcb4c659F2488D");
+/// let config = PendingTransactionsConfig::<Transaction>::default()
+///     .with_filter(move |tx| tx.to() == Some(router));
+/// ```
+pub struct PendingTransactionsConfig<Tx> {
+    batch_size: usize,
+    dedup_capacity: NonZeroUsize,
+    filters: Vec<TxFilter<Tx>>,
+}
+
+impl<Tx> Default for PendingTransactionsConfig<Tx> {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            dedup_capacity: DEFAULT_DEDUP_CAPACITY,
+            filters: Vec::new(),
+        }
+    }
+}
+
+impl<Tx> Clone for PendingTransactionsConfig<Tx> {
+    fn clone(&self) -> Self {
+        Self {
+            batch_size: self.batch_size,
+            dedup_capacity: self.dedup_capacity,
+            filters: self.filters.clone(),
+        }
+    }
+}
+
+impl<Tx> fmt::Debug for PendingTransactionsConfig<Tx> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingTransactionsConfig")
+            .field("batch_size", &self.batch_size)
+            .field("dedup_capacity", &self.dedup_capacity)
+            .field("filters", &self.filters.len())
+            .finish()
+    }
+}
+
+impl<Tx> PendingTransactionsConfig<Tx> {
+    /// Sets the maximum number of transaction hashes that are fetched in one batch, if the node
+    /// only announces hashes. Defaults to 100.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the number of recent transaction hashes that are remembered to drop duplicate
+    /// announcements. Defaults to 10,000.
+    pub const fn with_dedup_capacity(mut self, dedup_capacity: NonZeroUsize) -> Self {
+        self.dedup_capacity = dedup_capacity;
+        self
+    }
+
+    /// Adds a predicate that transactions must match to be yielded.
+    ///
+    /// If multiple filters are added, transactions must match all of them.
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Tx) -> bool + Send + Sync + 'static,
+    {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Returns `true` if the transaction matches all filters.
+    pub fn matches(&self, tx: &Tx) -> bool {
+        self.filters.iter().all(|filter| filter(tx))
+    }
+}
+
+/// A pending transaction announced by the node, either as full body or only by its hash.
+#[derive(Debug)]
+pub(crate) enum Pending<Tx> {
+    Full(Tx),
+    Hash(TxHash),
+}
+
+/// Returns a stream of the full bodies of the announced transactions.
+///
+/// Duplicate announcements are dropped, hashes are fetched in batches of `eth_getTransactionByHash`
+/// calls, and transactions that do not match the filters of the config are skipped. The stream
+/// ends when the announcements end or the client is dropped.
+pub(crate) fn pending_transaction_stream<T, Tx, S>(
+    client: WeakClient<T>,
+    announcements: S,
+    config: PendingTransactionsConfig<Tx>,
+) -> PendingTransactionStream<Tx>
+where
+    T: Transport + Clone,
+    Tx: TransactionResponse + RpcReturn,
+    S: Stream<Item = Pending<Tx>> + Send + 'static,
+{
+    let chunks = Box::pin(announcements.ready_chunks(config.batch_size));
+    let seen = LruCache::new(config.dedup_capacity);
+    stream::unfold(
+        (chunks, seen, client, config),
+        |(mut chunks, mut seen, client, config)| async move {
+            let (mut txs, hashes) = dedup(chunks.next().await?, &mut seen);
+            if !hashes.is_empty() {
+                txs.extend(fetch_transactions(&client, &hashes).await?);
+            }
+            txs.retain(|tx| config.matches(tx));
+            Some((stream::iter(txs), (chunks, seen, client, config)))
+        },
+    )
+    .flatten()
+    .boxed()
+}
+
+/// Drops the announcements that were already seen, and splits the rest into full transactions
+/// and hashes to fetch.
+fn dedup<Tx: TransactionResponse>(
+    chunk: Vec<Pending<Tx>>,
+    seen: &mut LruCache<TxHash, ()>,
+) -> (Vec<Tx>, Vec<TxHash>) {
+    let mut txs = Vec::new();
+    let mut hashes = Vec::new();
+    for pending in chunk {
+        let hash = match &pending {
+            Pending::Full(tx) => tx.tx_hash(),
+            Pending::Hash(hash) => *hash,
+        };
+        if seen.put(hash, ()).is_some() {
+            continue;
+        }
+        match pending {
+            Pending::Full(tx) => txs.push(tx),
+            Pending::Hash(hash) => hashes.push(hash),
+        }
+    }
+    (txs, hashes)
+}
+
+/// Fetches the transactions in one batch, skipping those that left the pool or failed.
+///
+/// Returns `None` if the client was dropped.
+async fn fetch_transactions<T, Tx>(client: &WeakClient<T>, hashes: &[TxHash]) -> Option<Vec<Tx>>
+where
+    T: Transport + Clone,
+    Tx: RpcReturn,
+{
+    let client = client.upgrade()?;
+    let mut batch = BatchRequest::new(&client);
+    let waiters: Vec<_> = hashes
+        .iter()
+        .filter_map(|hash| {
+            batch.add_call::<_, Option<Tx>>("eth_getTransactionByHash", &(hash,)).ok()
+        })
+        .collect();
+    if let Err(err) = batch.send().await {
+        debug!(%err, "failed to fetch pending transactions");
+        return Some(Vec::new());
+    }
+
+    let mut txs = Vec::with_capacity(waiters.len());
+    for waiter in waiters {
+        match waiter.await {
+            Ok(Some(tx)) => txs.push(tx),
+            // the transaction was mined or dropped in the meantime
+            Ok(None) => {}
+            Err(err) => debug!(%err, "failed to fetch pending transaction"),
+        }
+    }
+    Some(txs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_rpc_types_eth::Transaction;
+
+    fn tx(hash: u8, nonce: u64) -> Transaction {
+        Transaction { hash: B256::repeat_byte(hash), nonce, ..Default::default() }
+    }
+
+    #[test]
+    fn dedups_announcements() {
+        let mut seen = LruCache::new(NonZeroUsize::new(3).unwrap());
+        let chunk = vec![
+            Pending::Full(tx(1, 0)),
+            Pending::Hash(B256::repeat_byte(2)),
+            Pending::Hash(B256::repeat_byte(1)),
+            Pending::Full(tx(2, 0)),
+            Pending::Hash(B256::repeat_byte(3)),
+        ];
+        let (txs, hashes) = dedup(chunk, &mut seen);
+        assert_eq!(txs, vec![tx(1, 0)]);
+        assert_eq!(hashes, vec![B256::repeat_byte(2), B256::repeat_byte(3)]);
+
+        // the oldest hash is evicted once the capacity is reached
+        let chunk = vec![Pending::Full(tx(4, 0)), Pending::Full(tx(1, 0)), Pending::Full(tx(3, 0))];
+        let (txs, hashes) = dedup(chunk, &mut seen);
+        assert_eq!(txs, vec![tx(4, 0), tx(1, 0)]);
+        assert!(hashes.is_empty());
+    }
+
+    #[test]
+    fn filters_transactions() {
+        let config = PendingTransactionsConfig::<Transaction>::default()
+            .with_filter(|tx| tx.nonce > 1)
+            .with_filter(|tx| tx.nonce < 5);
+        let cloned = config.clone();
+        assert!(!config.matches(&tx(1, 1)));
+        assert!(cloned.matches(&tx(1, 2)));
+        assert!(!config.matches(&tx(1, 5)));
+        assert!(PendingTransactionsConfig::default().matches(&tx(1, 5)));
+    }
+}
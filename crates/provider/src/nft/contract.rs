@@ -0,0 +1,154 @@
+use crate::{
+    nft::{substitute_id, NftError, UriResolver},
+    Provider,
+};
+use alloy_json_rpc::RpcError;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{Address, FixedBytes, U256};
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport::{Transport, TransportResult};
+use std::marker::PhantomData;
+
+sol! {
+    #[allow(missing_docs)]
+    interface IERC165 {
+        function supportsInterface(bytes4 interfaceId) external view returns (bool);
+    }
+
+    #[allow(missing_docs)]
+    interface IERC721 {
+        function ownerOf(uint256 tokenId) external view returns (address);
+        function balanceOf(address owner) external view returns (uint256);
+        function tokenURI(uint256 tokenId) external view returns (string);
+    }
+
+    #[allow(missing_docs)]
+    interface IERC1155 {
+        function balanceOf(address account, uint256 id) external view returns (uint256);
+        function uri(uint256 id) external view returns (string);
+    }
+}
+
+/// The [ERC-165] interface ID of ERC-721.
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+pub const ERC721_INTERFACE_ID: FixedBytes<4> = FixedBytes([0x80, 0xac, 0x58, 0xcd]);
+
+/// The [ERC-165] interface ID of ERC-1155.
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+pub const ERC1155_INTERFACE_ID: FixedBytes<4> = FixedBytes([0xd9, 0xb6, 0x7a, 0x26]);
+
+/// The standard implemented by an NFT contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum NftStandard {
+    /// [ERC-721](https://eips.ethereum.org/EIPS/eip-721) non-fungible tokens.
+    Erc721,
+    /// [ERC-1155](https://eips.ethereum.org/EIPS/eip-1155) multi tokens.
+    Erc1155,
+}
+
+/// An NFT contract, whose calls are made through a [`Provider`].
+#[derive(Debug)]
+pub struct NftContract<'a, P, T, N> {
+    provider: &'a P,
+    address: Address,
+    standard: NftStandard,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> Clone for NftContract<'_, P, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P, T, N> Copy for NftContract<'_, P, T, N> {}
+
+impl<'a, P, T, N> NftContract<'a, P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new contract implementing the standard.
+    pub const fn new(provider: &'a P, address: Address, standard: NftStandard) -> Self {
+        Self { provider, address, standard, _pd: PhantomData }
+    }
+
+    /// Detects the standard of the contract with [ERC-165], returning `None` if it implements
+    /// neither ERC-721 nor ERC-1155.
+    ///
+    /// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+    pub async fn detect(provider: &'a P, address: Address) -> TransportResult<Option<Self>> {
+        let contract = Self::new(provider, address, NftStandard::Erc721);
+        for (interface_id, standard) in [
+            (ERC721_INTERFACE_ID, NftStandard::Erc721),
+            (ERC1155_INTERFACE_ID, NftStandard::Erc1155),
+        ] {
+            match contract.call(IERC165::supportsInterfaceCall { interfaceId: interface_id }).await
+            {
+                Ok(supported) if supported._0 => return Ok(Some(Self { standard, ..contract })),
+                // contracts without ERC-165 revert
+                Ok(_) | Err(RpcError::ErrorResp(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the address of the contract.
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the standard implemented by the contract.
+    pub const fn standard(&self) -> NftStandard {
+        self.standard
+    }
+
+    /// Returns the URI of the metadata of the token, with the `{id}` of ERC-1155 URIs substituted.
+    pub async fn token_uri(&self, id: U256) -> TransportResult<String> {
+        match self.standard {
+            NftStandard::Erc721 => Ok(self.call(IERC721::tokenURICall { tokenId: id }).await?._0),
+            NftStandard::Erc1155 => {
+                let uri = self.call(IERC1155::uriCall { id }).await?._0;
+                Ok(substitute_id(&uri, id))
+            }
+        }
+    }
+
+    /// Returns the metadata of the token, resolved with the resolver.
+    pub async fn metadata(
+        &self,
+        id: U256,
+        resolver: &UriResolver,
+    ) -> Result<serde_json::Value, NftError> {
+        let uri = self.token_uri(id).await?;
+        resolver.resolve_json(&uri).await
+    }
+
+    /// Returns the owner of the ERC-721 token.
+    pub async fn owner_of(&self, id: U256) -> TransportResult<Address> {
+        Ok(self.call(IERC721::ownerOfCall { tokenId: id }).await?._0)
+    }
+
+    /// Returns the balance of the owner of the token, which is the number of tokens owned by the
+    /// owner for ERC-721 contracts, whatever the token.
+    pub async fn balance_of(&self, owner: Address, id: U256) -> TransportResult<U256> {
+        match self.standard {
+            NftStandard::Erc721 => Ok(self.call(IERC721::balanceOfCall { owner }).await?._0),
+            NftStandard::Erc1155 => {
+                Ok(self.call(IERC1155::balanceOfCall { account: owner, id }).await?._0)
+            }
+        }
+    }
+
+    async fn call<C: SolCall>(&self, call: C) -> TransportResult<C::Return> {
+        let tx =
+            N::TransactionRequest::default().with_to(self.address).with_input(call.abi_encode());
+        let output = self.provider.call(&tx).await?;
+        C::abi_decode_returns(&output, true)
+            .map_err(|err| RpcError::local_usage_str(&err.to_string()))
+    }
+}
@@ -0,0 +1,43 @@
+//! A small NFT indexing toolkit for [ERC-721] and [ERC-1155] contracts.
+//!
+//! - [`NftContract`] reads the owners, balances and metadata of tokens,
+//! - [`UriResolver`] resolves token URIs, including `data:` and `ipfs://` URIs, with pluggable
+//!   [`UriFetcher`]s,
+//! - [`OwnershipIndex`] enumerates the owners of all tokens by scanning transfer logs, with
+//!   checkpointing.
+//!
+//! [ERC-721]: https://eips.ethereum.org/EIPS/eip-721
+//! [ERC-1155]: https://eips.ethereum.org/EIPS/eip-1155
+
+mod contract;
+pub use contract::{NftContract, NftStandard, ERC1155_INTERFACE_ID, ERC721_INTERFACE_ID};
+
+mod ownership;
+pub use ownership::{OwnershipIndex, DEFAULT_SCAN_CHUNK_SIZE};
+
+mod uri;
+pub use uri::{
+    decode_data_uri, ipfs_to_gateway, substitute_id, UriFetcher, UriResolver, DEFAULT_IPFS_GATEWAY,
+};
+
+use alloy_transport::TransportError;
+
+/// An error of the NFT helpers.
+#[derive(Debug, thiserror::Error)]
+pub enum NftError {
+    /// A call to the contract failed.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// No fetcher is registered for the scheme of the URI.
+    #[error("unsupported URI scheme: {0:?}")]
+    UnsupportedScheme(String),
+    /// The `data:` URI is malformed.
+    #[error("invalid data URI: {0}")]
+    InvalidDataUri(String),
+    /// The fetcher failed to fetch the URI.
+    #[error("failed to fetch URI: {0}")]
+    Fetch(String),
+    /// The metadata is not valid JSON.
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(#[from] serde_json::Error),
+}
@@ -0,0 +1,315 @@
+use crate::{nft::NftStandard, LogConsistency, Provider};
+use alloy_network::Network;
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use alloy_rpc_types_eth::{Filter, Log};
+use alloy_sol_types::{sol, SolEvent};
+use alloy_transport::{Transport, TransportResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+sol! {
+    #[allow(missing_docs)]
+    interface INftEvents {
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+    }
+}
+
+/// The default number of blocks scanned per `eth_getLogs` request by [`OwnershipIndex::sync`].
+pub const DEFAULT_SCAN_CHUNK_SIZE: u64 = 2_000;
+
+/// An index of the owners of the tokens of an NFT contract, built by scanning its transfer logs.
+///
+/// The index records the last block it scanned as a checkpoint, and can be serialized, so that
+/// scanning resumes from the checkpoint after a restart. Reorgs are not handled, so the index
+/// should only be synced up to a block that is not expected to be reorged, such as the finalized
+/// block.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example<P: alloy_provider::Provider>(provider: P) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_primitives::{address, U256};
+/// use alloy_provider::nft::{NftStandard, OwnershipIndex, DEFAULT_SCAN_CHUNK_SIZE};
+///
+/// let contract = address!("BC4CA0EdA7647A8aB7C2061c2E118A18a936f13D");
+/// let mut index = OwnershipIndex::new(contract, NftStandard::Erc721, 12_287_507);
+/// index.sync(&provider, 12_300_000, DEFAULT_SCAN_CHUNK_SIZE).await?;
+/// println!("{:?}", index.owner_of(U256::from(1)));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipIndex {
+    contract: Address,
+    standard: NftStandard,
+    start_block: BlockNumber,
+    checkpoint: Option<BlockNumber>,
+    owners: HashMap<U256, Address>,
+    balances: HashMap<Address, HashMap<U256, U256>>,
+}
+
+impl OwnershipIndex {
+    /// Creates an empty index of the contract, which scans its logs from the start block, e.g.
+    /// the deployment block of the contract.
+    pub fn new(contract: Address, standard: NftStandard, start_block: BlockNumber) -> Self {
+        Self {
+            contract,
+            standard,
+            start_block,
+            checkpoint: None,
+            owners: HashMap::new(),
+            balances: HashMap::new(),
+        }
+    }
+
+    /// Returns the indexed contract.
+    pub const fn contract(&self) -> Address {
+        self.contract
+    }
+
+    /// Returns the standard of the indexed contract.
+    pub const fn standard(&self) -> NftStandard {
+        self.standard
+    }
+
+    /// Returns the last scanned block, `None` if no block was scanned yet.
+    pub const fn checkpoint(&self) -> Option<BlockNumber> {
+        self.checkpoint
+    }
+
+    /// Returns the next block to scan.
+    pub fn next_block(&self) -> BlockNumber {
+        self.checkpoint.map_or(self.start_block, |checkpoint| checkpoint + 1)
+    }
+
+    /// Returns the owner of the ERC-721 token, `None` if it was never minted or was burned.
+    pub fn owner_of(&self, id: U256) -> Option<Address> {
+        self.owners.get(&id).copied()
+    }
+
+    /// Returns the balance of the owner of the token, which is `1` for the owner of an ERC-721
+    /// token.
+    pub fn balance_of(&self, owner: Address, id: U256) -> U256 {
+        match self.standard {
+            NftStandard::Erc721 => U256::from(self.owner_of(id) == Some(owner)),
+            NftStandard::Erc1155 => self
+                .balances
+                .get(&owner)
+                .and_then(|balances| balances.get(&id))
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the IDs of the tokens owned by the owner, in ascending order.
+    pub fn tokens_of(&self, owner: Address) -> Vec<U256> {
+        let mut ids: Vec<_> = match self.standard {
+            NftStandard::Erc721 => self
+                .owners
+                .iter()
+                .filter(|(_, &token_owner)| token_owner == owner)
+                .map(|(&id, _)| id)
+                .collect(),
+            NftStandard::Erc1155 => self
+                .balances
+                .get(&owner)
+                .map(|balances| balances.keys().copied().collect())
+                .unwrap_or_default(),
+        };
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Returns the event signatures of the transfer logs of the contract.
+    pub fn event_signatures(&self) -> Vec<B256> {
+        match self.standard {
+            NftStandard::Erc721 => vec![INftEvents::Transfer::SIGNATURE_HASH],
+            NftStandard::Erc1155 => vec![
+                INftEvents::TransferSingle::SIGNATURE_HASH,
+                INftEvents::TransferBatch::SIGNATURE_HASH,
+            ],
+        }
+    }
+
+    /// Applies the transfer log to the index, returning `false` if it is not a transfer log of
+    /// the contract.
+    ///
+    /// Logs must be applied in the order they were emitted, and removed logs are ignored.
+    pub fn apply_log(&mut self, log: &Log) -> bool {
+        if log.address() != self.contract || log.removed {
+            return false;
+        }
+        let (topics, data) = (log.topics(), &log.data().data);
+        match self.standard {
+            NftStandard::Erc721 => {
+                let Ok(transfer) = INftEvents::Transfer::decode_raw_log(topics, data, true) else {
+                    return false;
+                };
+                if transfer.to.is_zero() {
+                    self.owners.remove(&transfer.tokenId);
+                } else {
+                    self.owners.insert(transfer.tokenId, transfer.to);
+                }
+            }
+            NftStandard::Erc1155 => {
+                if let Ok(transfer) = INftEvents::TransferSingle::decode_raw_log(topics, data, true)
+                {
+                    self.transfer(transfer.from, transfer.to, transfer.id, transfer.value);
+                } else if let Ok(transfer) =
+                    INftEvents::TransferBatch::decode_raw_log(topics, data, true)
+                {
+                    for (id, value) in transfer.ids.into_iter().zip(transfer.values) {
+                        self.transfer(transfer.from, transfer.to, id, value);
+                    }
+                } else {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn transfer(&mut self, from: Address, to: Address, id: U256, value: U256) {
+        if !from.is_zero() {
+            let balances = self.balances.entry(from).or_default();
+            let balance = balances.entry(id).or_default();
+            *balance = balance.saturating_sub(value);
+            if balance.is_zero() {
+                balances.remove(&id);
+                if balances.is_empty() {
+                    self.balances.remove(&from);
+                }
+            }
+        }
+        if !to.is_zero() && !value.is_zero() {
+            let balance = self.balances.entry(to).or_default().entry(id).or_default();
+            *balance = balance.saturating_add(value);
+        }
+    }
+
+    /// Scans the transfer logs from the [next block](Self::next_block) up to `to_block`, with
+    /// `eth_getLogs` requests of up to `chunk_size` blocks.
+    ///
+    /// The checkpoint is advanced after each request, so a failed sync can be resumed.
+    pub async fn sync<P, T, N>(
+        &mut self,
+        provider: &P,
+        to_block: BlockNumber,
+        chunk_size: u64,
+    ) -> TransportResult<()>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let checks = LogConsistency::new();
+        let mut from_block = self.next_block();
+        while from_block <= to_block {
+            let chunk_end = from_block.saturating_add(chunk_size.max(1) - 1).min(to_block);
+            let filter = Filter::new()
+                .address(self.contract)
+                .event_signature(self.event_signatures())
+                .from_block(from_block)
+                .to_block(chunk_end);
+            for log in provider.get_logs_strict(&filter, &checks).await? {
+                self.apply_log(&log);
+            }
+            self.checkpoint = Some(chunk_end);
+            from_block = chunk_end + 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::LogData;
+
+    fn log(contract: Address, event: &impl SolEvent) -> Log {
+        Log {
+            inner: alloy_primitives::Log { address: contract, data: event.encode_log_data() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn indexes_erc721_transfers() {
+        let contract = Address::with_last_byte(0xff);
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let transfer = |from, to, id: u64| {
+            log(contract, &INftEvents::Transfer { from, to, tokenId: U256::from(id) })
+        };
+
+        let mut index = OwnershipIndex::new(contract, NftStandard::Erc721, 10);
+        assert_eq!(index.next_block(), 10);
+        for log in [
+            transfer(Address::ZERO, a, 1),
+            transfer(Address::ZERO, a, 2),
+            transfer(a, b, 1),
+            transfer(a, Address::ZERO, 2),
+        ] {
+            assert!(index.apply_log(&log));
+        }
+        assert_eq!(index.owner_of(U256::from(1)), Some(b));
+        assert_eq!(index.owner_of(U256::from(2)), None);
+        assert_eq!(index.tokens_of(b), vec![U256::from(1)]);
+        assert_eq!(index.balance_of(a, U256::from(1)), U256::ZERO);
+
+        // ERC-20 transfers have the same signature, without an indexed amount
+        let erc20 = Log {
+            inner: alloy_primitives::Log {
+                address: contract,
+                data: LogData::new_unchecked(
+                    vec![INftEvents::Transfer::SIGNATURE_HASH, a.into_word(), b.into_word()],
+                    U256::from(1).to_be_bytes_vec().into(),
+                ),
+            },
+            ..Default::default()
+        };
+        assert!(!index.apply_log(&erc20));
+        let other = INftEvents::Transfer { from: Address::ZERO, to: a, tokenId: U256::from(3) };
+        assert!(!index.apply_log(&log(b, &other)));
+    }
+
+    #[test]
+    fn indexes_erc1155_transfers() {
+        let contract = Address::with_last_byte(0xff);
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+
+        let mut index = OwnershipIndex::new(contract, NftStandard::Erc1155, 0);
+        index.apply_log(&log(
+            contract,
+            &INftEvents::TransferBatch {
+                operator: a,
+                from: Address::ZERO,
+                to: a,
+                ids: vec![U256::from(1), U256::from(2)],
+                values: vec![U256::from(10), U256::from(1)],
+            },
+        ));
+        index.apply_log(&log(
+            contract,
+            &INftEvents::TransferSingle {
+                operator: a,
+                from: a,
+                to: b,
+                id: U256::from(2),
+                value: U256::from(1),
+            },
+        ));
+        assert_eq!(index.balance_of(a, U256::from(1)), U256::from(10));
+        assert_eq!(index.tokens_of(a), vec![U256::from(1)]);
+        assert_eq!(index.tokens_of(b), vec![U256::from(2)]);
+
+        // the index resumes from its checkpoint after a round trip
+        index.checkpoint = Some(100);
+        let json = serde_json::to_string(&index).unwrap();
+        let restored: OwnershipIndex = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, index);
+        assert_eq!(restored.next_block(), 101);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::nft::NftError;
+use alloy_primitives::U256;
+use alloy_transport::Pbf;
+use base64::Engine;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// The default gateway `ipfs://` URIs are rewritten to.
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// Replaces each `{id}` in an ERC-1155 URI with the token ID, as lowercase hex padded to 64
+/// characters, see [EIP-1155](https://eips.ethereum.org/EIPS/eip-1155#metadata).
+pub fn substitute_id(uri: &str, id: U256) -> String {
+    uri.replace("{id}", &format!("{id:064x}"))
+}
+
+/// Decodes a `data:` URI, returning its media type and its payload.
+///
+/// Both base64 and percent-encoded payloads are supported. The media type defaults to
+/// `text/plain` if empty.
+pub fn decode_data_uri(uri: &str) -> Result<(String, Vec<u8>), NftError> {
+    let invalid = || NftError::InvalidDataUri(uri.to_string());
+    let rest = uri.strip_prefix("data:").ok_or_else(invalid)?;
+    let (header, payload) = rest.split_once(',').ok_or_else(invalid)?;
+    let (media_type, is_base64) =
+        header.strip_suffix(";base64").map_or((header, false), |media_type| (media_type, true));
+    let media_type = if media_type.is_empty() { "text/plain" } else { media_type };
+
+    let payload = if is_base64 {
+        base64::engine::general_purpose::STANDARD.decode(payload).map_err(|_| invalid())?
+    } else {
+        percent_decode(payload).ok_or_else(invalid)?
+    };
+    Ok((media_type.to_string(), payload))
+}
+
+fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = input.bytes();
+    let mut decoded = Vec::with_capacity(input.len());
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            decoded.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    Some(decoded)
+}
+
+/// Rewrites an `ipfs://` URI to the HTTP gateway, e.g. `ipfs://<cid>/1.json` to
+/// `https://ipfs.io/ipfs/<cid>/1.json`.
+///
+/// The legacy `ipfs://ipfs/<cid>` form is also accepted. Returns `None` if the URI is not an
+/// `ipfs://` URI.
+pub fn ipfs_to_gateway(uri: &str, gateway: &str) -> Option<String> {
+    let path = uri.strip_prefix("ipfs://")?;
+    let path = path.strip_prefix("ipfs/").unwrap_or(path);
+    Some(format!("{}/{path}", gateway.trim_end_matches('/')))
+}
+
+/// Fetches the content of URIs of a scheme, used by a [`UriResolver`].
+///
+/// With the `reqwest` feature, [`reqwest::Client`] implements this trait for HTTP URIs.
+pub trait UriFetcher: Send + Sync {
+    /// Fetches the content of the URI.
+    fn fetch(&self, uri: String) -> Pbf<'_, Vec<u8>, NftError>;
+}
+
+#[cfg(feature = "reqwest")]
+impl UriFetcher for reqwest::Client {
+    fn fetch(&self, uri: String) -> Pbf<'_, Vec<u8>, NftError> {
+        Box::pin(async move {
+            let fetch_error = |err: reqwest::Error| NftError::Fetch(err.to_string());
+            let response = self
+                .get(uri)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(fetch_error)?;
+            Ok(response.bytes().await.map_err(fetch_error)?.to_vec())
+        })
+    }
+}
+
+/// Resolves the content of token URIs.
+///
+/// `data:` URIs are decoded inline. `ipfs://` URIs are fetched by the fetcher of the `ipfs`
+/// scheme if there is one, and are otherwise rewritten to the IPFS gateway and fetched over
+/// HTTPS. All other URIs are fetched by the fetcher of their scheme.
+#[derive(Clone)]
+pub struct UriResolver {
+    ipfs_gateway: String,
+    fetchers: HashMap<String, Arc<dyn UriFetcher>>,
+}
+
+impl fmt::Debug for UriResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UriResolver")
+            .field("ipfs_gateway", &self.ipfs_gateway)
+            .field("schemes", &self.fetchers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Default for UriResolver {
+    fn default() -> Self {
+        Self { ipfs_gateway: DEFAULT_IPFS_GATEWAY.to_string(), fetchers: HashMap::new() }
+    }
+}
+
+impl UriResolver {
+    /// Creates a resolver without fetchers, which only resolves `data:` URIs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a resolver fetching `http`, `https` and, through the default gateway, `ipfs` URIs
+    /// with the client.
+    #[cfg(feature = "reqwest")]
+    pub fn with_http(client: reqwest::Client) -> Self {
+        let client = Arc::new(client);
+        Self::new().with_fetcher("http", client.clone()).with_fetcher("https", client)
+    }
+
+    /// Sets the IPFS gateway `ipfs://` URIs are rewritten to, if there is no `ipfs` fetcher.
+    pub fn with_ipfs_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.ipfs_gateway = gateway.into();
+        self
+    }
+
+    /// Sets the fetcher of the URIs of the scheme, e.g. `https` or `ar`.
+    pub fn with_fetcher(mut self, scheme: impl Into<String>, fetcher: Arc<dyn UriFetcher>) -> Self {
+        self.fetchers.insert(scheme.into(), fetcher);
+        self
+    }
+
+    /// Returns the content of the URI.
+    pub async fn resolve(&self, uri: &str) -> Result<Vec<u8>, NftError> {
+        let scheme = uri.split_once(':').map_or("", |(scheme, _)| scheme);
+        if scheme == "data" {
+            return decode_data_uri(uri).map(|(_, data)| data);
+        }
+        if let Some(fetcher) = self.fetchers.get(scheme) {
+            return fetcher.fetch(uri.to_string()).await;
+        }
+        if let (Some(url), Some(fetcher)) =
+            (ipfs_to_gateway(uri, &self.ipfs_gateway), self.fetchers.get("https"))
+        {
+            return fetcher.fetch(url).await;
+        }
+        Err(NftError::UnsupportedScheme(scheme.to_string()))
+    }
+
+    /// Returns the content of the URI, deserialized as JSON, e.g. the metadata of a token.
+    pub async fn resolve_json<T: serde::de::DeserializeOwned>(
+        &self,
+        uri: &str,
+    ) -> Result<T, NftError> {
+        Ok(serde_json::from_slice(&self.resolve(uri).await?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl UriFetcher for Recorder {
+        fn fetch(&self, uri: String) -> Pbf<'_, Vec<u8>, NftError> {
+            self.0.lock().unwrap().push(uri);
+            Box::pin(async { Ok(br#"{"name":"Token"}"#.to_vec()) })
+        }
+    }
+
+    #[test]
+    fn substitutes_ids() {
+        assert_eq!(
+            substitute_id("https://token-cdn-domain/{id}.json", U256::from(314592)),
+            "https://token-cdn-domain/000000000000000000000000000000000000000000000000000000000004cce0.json"
+        );
+    }
+
+    #[test]
+    fn decodes_data_uris() {
+        assert_eq!(
+            decode_data_uri("data:application/json;base64,eyJhIjoxfQ==").unwrap(),
+            ("application/json".to_string(), br#"{"a":1}"#.to_vec())
+        );
+        assert_eq!(
+            decode_data_uri("data:,%7B%22a%22%3A1%7D").unwrap(),
+            ("text/plain".to_string(), br#"{"a":1}"#.to_vec())
+        );
+        assert!(decode_data_uri("data:text/plain;base64").is_err());
+        assert!(decode_data_uri("data:,%7").is_err());
+    }
+
+    #[tokio::test]
+    async fn resolves_uris() {
+        let recorder = Arc::new(Recorder::default());
+        let resolver = UriResolver::new().with_fetcher("https", recorder.clone());
+
+        let metadata: serde_json::Value =
+            resolver.resolve_json("ipfs://ipfs/Qm/1.json").await.unwrap();
+        assert_eq!(metadata["name"], "Token");
+        resolver.resolve("https://example.com/1").await.unwrap();
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            ["https://ipfs.io/ipfs/Qm/1.json", "https://example.com/1"]
+        );
+
+        assert_eq!(resolver.resolve("data:,abc").await.unwrap(), b"abc");
+        assert!(matches!(
+            resolver.resolve("ar://tx").await,
+            Err(NftError::UnsupportedScheme(scheme)) if scheme == "ar"
+        ));
+    }
+}
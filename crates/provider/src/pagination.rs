@@ -0,0 +1,234 @@
+//! Cursor-based pagination of RPC methods.
+
+use alloy_transport::{RpcFut, TransportResult};
+use futures::{stream, Stream, StreamExt};
+use std::fmt;
+
+/// A page of items returned by a paginated RPC method.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page<T, C> {
+    /// The items of the page.
+    pub items: Vec<T>,
+    /// The cursor of the next page, `None` if this is the last page.
+    pub next: Option<C>,
+}
+
+impl<T, C> Page<T, C> {
+    /// Creates a new page.
+    pub const fn new(items: Vec<T>, next: Option<C>) -> Self {
+        Self { items, next }
+    }
+
+    /// Creates the last page.
+    pub const fn last(items: Vec<T>) -> Self {
+        Self { items, next: None }
+    }
+}
+
+/// The position of a [`Paginated`] in its pages.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum PageCursor<C> {
+    /// No page was fetched yet.
+    #[default]
+    First,
+    /// The cursor of the next page.
+    Next(C),
+    /// All pages were fetched.
+    End,
+}
+
+impl<C> PageCursor<C> {
+    /// Returns `true` if all pages were fetched.
+    pub const fn is_end(&self) -> bool {
+        matches!(self, Self::End)
+    }
+}
+
+type FetchPage<T, C> = Box<dyn FnMut(Option<C>) -> RpcFut<'static, Page<T, C>> + Send>;
+
+/// A paginated RPC method, which fetches its pages one at a time.
+///
+/// The method is called with the cursor of the page to fetch, `None` for the first page, and
+/// returns the items of the page together with the cursor of the next page.
+///
+/// Pages are only fetched on demand: [`Paginated::into_stream`] fetches the next page only once
+/// all items of the current page have been consumed, so a slow consumer never buffers more than
+/// one page.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(paginated: alloy_provider::Paginated<u64, u64>) -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::StreamExt;
+///
+/// let mut items = std::pin::pin!(paginated.into_stream());
+/// while let Some(item) = items.next().await {
+///     println!("{}", item?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Paginated<T, C> {
+    fetch: FetchPage<T, C>,
+    cursor: PageCursor<C>,
+}
+
+impl<T, C: fmt::Debug> fmt::Debug for Paginated<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Paginated").field("cursor", &self.cursor).finish_non_exhaustive()
+    }
+}
+
+impl<T, C> Paginated<T, C>
+where
+    T: Send + 'static,
+    C: Clone + Send + 'static,
+{
+    /// Creates a new paginated method from a function that fetches the page at a cursor.
+    pub fn new<F>(fetch: F) -> Self
+    where
+        F: FnMut(Option<C>) -> RpcFut<'static, Page<T, C>> + Send + 'static,
+    {
+        Self { fetch: Box::new(fetch), cursor: PageCursor::First }
+    }
+
+    /// Resumes the pagination at the given cursor, e.g. one saved from [`Paginated::cursor`].
+    pub fn with_cursor(mut self, cursor: PageCursor<C>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Returns the cursor of the next page.
+    pub const fn cursor(&self) -> &PageCursor<C> {
+        &self.cursor
+    }
+
+    /// Fetches the next page, returning `None` if all pages were fetched.
+    ///
+    /// If the request fails, the cursor is left unchanged, so the page can be retried.
+    pub async fn next_page(&mut self) -> TransportResult<Option<Vec<T>>> {
+        let cursor = match &self.cursor {
+            PageCursor::First => None,
+            PageCursor::Next(cursor) => Some(cursor.clone()),
+            PageCursor::End => return Ok(None),
+        };
+        let page = (self.fetch)(cursor).await?;
+        self.cursor = page.next.map_or(PageCursor::End, PageCursor::Next);
+        Ok(Some(page.items))
+    }
+
+    /// Returns a stream of the remaining pages.
+    ///
+    /// The stream ends after all pages were fetched, or after the first error.
+    pub fn into_pages(self) -> impl Stream<Item = TransportResult<Vec<T>>> + Send + 'static {
+        stream::unfold(Some(self), |paginated| async move {
+            let mut paginated = paginated?;
+            match paginated.next_page().await {
+                Ok(Some(items)) => Some((Ok(items), Some(paginated))),
+                Ok(None) => None,
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+    }
+
+    /// Returns a stream of the remaining items, fetching the next page once the items of the
+    /// current page are consumed.
+    ///
+    /// The stream ends after all pages were fetched, or after the first error.
+    pub fn into_stream(self) -> impl Stream<Item = TransportResult<T>> + Send + 'static {
+        self.into_pages().flat_map(|page| match page {
+            Ok(items) => stream::iter(items.into_iter().map(Ok).collect::<Vec<_>>()),
+            Err(err) => stream::iter(vec![Err(err)]),
+        })
+    }
+
+    /// Fetches all remaining pages and returns their items.
+    pub async fn collect_all(mut self) -> TransportResult<Vec<T>> {
+        let mut all = Vec::new();
+        while let Some(items) = self.next_page().await? {
+            all.extend(items);
+        }
+        Ok(all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::RpcError;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// Pages of three items out of `0..len`, with the next item as cursor.
+    fn numbers(len: u64, requests: Arc<AtomicUsize>) -> Paginated<u64, u64> {
+        Paginated::new(move |cursor: Option<u64>| {
+            requests.fetch_add(1, Ordering::SeqCst);
+            let start = cursor.unwrap_or_default();
+            let end = (start + 3).min(len);
+            Box::pin(
+                async move { Ok(Page::new((start..end).collect(), (end < len).then_some(end))) },
+            )
+        })
+    }
+
+    #[tokio::test]
+    async fn collects_all_pages() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let items = numbers(7, requests.clone()).collect_all().await.unwrap();
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn fetches_pages_on_demand() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let mut stream = std::pin::pin!(numbers(7, requests.clone()).into_stream());
+        assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+        for expected in 0..3 {
+            assert_eq!(stream.next().await.unwrap().unwrap(), expected);
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 3);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn resumes_at_cursor() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let mut paginated = numbers(7, requests.clone());
+        assert_eq!(paginated.next_page().await.unwrap(), Some(vec![0, 1, 2]));
+        assert_eq!(paginated.cursor(), &PageCursor::Next(3));
+
+        let cursor = paginated.cursor().clone();
+        let resumed = numbers(7, requests).with_cursor(cursor);
+        assert_eq!(resumed.collect_all().await.unwrap(), vec![3, 4, 5, 6]);
+
+        let mut done = numbers(7, Default::default()).with_cursor(PageCursor::End);
+        assert_eq!(done.next_page().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn stream_ends_after_error() {
+        let mut fail = true;
+        let paginated = Paginated::<u64, u64>::new(move |cursor| {
+            let result = match cursor {
+                None => Ok(Page::new(vec![0], Some(1))),
+                Some(_) if fail => {
+                    fail = false;
+                    Err(RpcError::local_usage_str("unavailable"))
+                }
+                Some(_) => Ok(Page::last(vec![1])),
+            };
+            Box::pin(async move { result })
+        });
+
+        let items: Vec<_> = paginated.into_stream().collect().await;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(items[0], Ok(0)));
+        assert!(items[1].is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! Well-known system contracts and predeploys of networks.
+//!
+//! [`predeploys`] returns the contracts deployed at fixed addresses on a chain, with the
+//! human-readable signatures of their commonly used functions and events, which can be parsed with
+//! `alloy-json-abi`.
+//!
+//! # Examples
+//!
+//! ```
+//! use alloy_provider::predeploys::{self, predeploy};
+//!
+//! let l1_block = predeploy(10, "L1Block").unwrap();
+//! assert_eq!(l1_block.address, predeploys::L1_BLOCK);
+//! assert!(predeploys::predeploys(1).iter().any(|contract| contract.name == "BeaconRoots"));
+//! ```
+
+use alloy_chains::NamedChain;
+use alloy_eips::{eip2935, eip4788, eip6110, eip7002, eip7251};
+use alloy_primitives::{address, Address, ChainId};
+
+/// A system contract or predeploy at a fixed address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Predeploy {
+    /// The name of the contract.
+    pub name: &'static str,
+    /// The address of the contract.
+    pub address: Address,
+    /// The human-readable signatures of the commonly used functions and events of the contract.
+    ///
+    /// This is empty for system contracts without an ABI, which are called with raw calldata.
+    pub abi: &'static [&'static str],
+}
+
+/// The address of the `L1Block` predeploy of OP Stack chains, with the attributes of the latest L1
+/// block.
+pub const L1_BLOCK: Address = address!("4200000000000000000000000000000000000015");
+
+/// The address of the `GasPriceOracle` predeploy of OP Stack chains, estimating L1 data fees.
+pub const GAS_PRICE_ORACLE: Address = address!("420000000000000000000000000000000000000F");
+
+/// The address of the `L2ToL1MessagePasser` predeploy of OP Stack chains, initiating withdrawals.
+pub const L2_TO_L1_MESSAGE_PASSER: Address = address!("4200000000000000000000000000000000000016");
+
+/// The address of the `L2CrossDomainMessenger` predeploy of OP Stack chains.
+pub const L2_CROSS_DOMAIN_MESSENGER: Address = address!("4200000000000000000000000000000000000007");
+
+/// The address of the `L2StandardBridge` predeploy of OP Stack chains.
+pub const L2_STANDARD_BRIDGE: Address = address!("4200000000000000000000000000000000000010");
+
+/// The address of the `WETH9` predeploy of OP Stack chains.
+pub const OP_WETH9: Address = address!("4200000000000000000000000000000000000006");
+
+/// The address of the `ArbSys` precompile of Arbitrum chains.
+pub const ARB_SYS: Address = address!("0000000000000000000000000000000000000064");
+
+/// The address of the `ArbGasInfo` precompile of Arbitrum chains.
+pub const ARB_GAS_INFO: Address = address!("000000000000000000000000000000000000006C");
+
+/// The address of the virtual `NodeInterface` contract of Arbitrum chains, only callable with
+/// `eth_call` and `eth_estimateGas`.
+pub const NODE_INTERFACE: Address = address!("00000000000000000000000000000000000000C8");
+
+/// The address of the deposit contract on Sepolia.
+pub const SEPOLIA_DEPOSIT_CONTRACT_ADDRESS: Address =
+    address!("7f02C3E3c98b133055B8B348B2Ac625669Ed295D");
+
+/// The address of the deposit contract on Holesky.
+pub const HOLESKY_DEPOSIT_CONTRACT_ADDRESS: Address =
+    address!("4242424242424242424242424242424242424242");
+
+const DEPOSIT_CONTRACT_ABI: &[&str] = &[
+    "function deposit(bytes pubkey, bytes withdrawal_credentials, bytes signature, bytes32 deposit_data_root) payable",
+    "function get_deposit_root() view returns (bytes32)",
+    "function get_deposit_count() view returns (bytes)",
+    "event DepositEvent(bytes pubkey, bytes withdrawal_credentials, bytes amount, bytes signature, bytes index)",
+];
+
+const ETHEREUM: [Predeploy; 4] = [
+    Predeploy { name: "BeaconRoots", address: eip4788::BEACON_ROOTS_ADDRESS, abi: &[] },
+    Predeploy { name: "HistoryStorage", address: eip2935::HISTORY_STORAGE_ADDRESS, abi: &[] },
+    Predeploy {
+        name: "WithdrawalRequests",
+        address: eip7002::WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+        abi: &[],
+    },
+    Predeploy {
+        name: "ConsolidationRequests",
+        address: eip7251::CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+        abi: &[],
+    },
+];
+
+const OP_STACK: [Predeploy; 7] = [
+    Predeploy { name: "BeaconRoots", address: eip4788::BEACON_ROOTS_ADDRESS, abi: &[] },
+    Predeploy {
+        name: "L1Block",
+        address: L1_BLOCK,
+        abi: &[
+            "function number() view returns (uint64)",
+            "function timestamp() view returns (uint64)",
+            "function basefee() view returns (uint256)",
+            "function blobBaseFee() view returns (uint256)",
+            "function hash() view returns (bytes32)",
+            "function sequenceNumber() view returns (uint64)",
+        ],
+    },
+    Predeploy {
+        name: "GasPriceOracle",
+        address: GAS_PRICE_ORACLE,
+        abi: &[
+            "function getL1Fee(bytes data) view returns (uint256)",
+            "function getL1GasUsed(bytes data) view returns (uint256)",
+            "function l1BaseFee() view returns (uint256)",
+            "function decimals() view returns (uint256)",
+            "function isEcotone() view returns (bool)",
+            "function isFjord() view returns (bool)",
+        ],
+    },
+    Predeploy {
+        name: "L2ToL1MessagePasser",
+        address: L2_TO_L1_MESSAGE_PASSER,
+        abi: &[
+            "function initiateWithdrawal(address target, uint256 gasLimit, bytes data) payable",
+            "function messageNonce() view returns (uint256)",
+            "event MessagePassed(uint256 indexed nonce, address indexed sender, address indexed target, uint256 value, uint256 gasLimit, bytes data, bytes32 withdrawalHash)",
+        ],
+    },
+    Predeploy {
+        name: "L2CrossDomainMessenger",
+        address: L2_CROSS_DOMAIN_MESSENGER,
+        abi: &[
+            "function sendMessage(address target, bytes message, uint32 minGasLimit) payable",
+            "function xDomainMessageSender() view returns (address)",
+        ],
+    },
+    Predeploy {
+        name: "L2StandardBridge",
+        address: L2_STANDARD_BRIDGE,
+        abi: &[
+            "function withdraw(address l2Token, uint256 amount, uint32 minGasLimit, bytes extraData) payable",
+            "function withdrawTo(address l2Token, address to, uint256 amount, uint32 minGasLimit, bytes extraData) payable",
+        ],
+    },
+    Predeploy {
+        name: "WETH9",
+        address: OP_WETH9,
+        abi: &[
+            "function deposit() payable",
+            "function withdraw(uint256 amount)",
+            "function balanceOf(address owner) view returns (uint256)",
+        ],
+    },
+];
+
+const ARBITRUM: [Predeploy; 3] = [
+    Predeploy {
+        name: "ArbSys",
+        address: ARB_SYS,
+        abi: &[
+            "function arbBlockNumber() view returns (uint256)",
+            "function arbChainID() view returns (uint256)",
+            "function withdrawEth(address destination) payable returns (uint256)",
+            "function sendTxToL1(address destination, bytes data) payable returns (uint256)",
+        ],
+    },
+    Predeploy {
+        name: "ArbGasInfo",
+        address: ARB_GAS_INFO,
+        abi: &[
+            "function getPricesInWei() view returns (uint256, uint256, uint256, uint256, uint256, uint256)",
+            "function getL1BaseFeeEstimate() view returns (uint256)",
+        ],
+    },
+    Predeploy {
+        name: "NodeInterface",
+        address: NODE_INTERFACE,
+        abi: &[
+            "function gasEstimateComponents(address to, bool contractCreation, bytes data) payable returns (uint64 gasEstimate, uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate)",
+            "function gasEstimateL1Component(address to, bool contractCreation, bytes data) payable returns (uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate)",
+            "function nitroGenesisBlock() pure returns (uint256 number)",
+        ],
+    },
+];
+
+/// Returns the system contracts and predeploys of the chain, or an empty list if the chain is not
+/// known.
+///
+/// Ethereum chains include their deposit contract and the system contracts of Cancun and Prague,
+/// OP Stack chains their predeploys and the beacon roots contract, and Arbitrum chains their
+/// precompiles and `NodeInterface`.
+pub fn predeploys(chain_id: ChainId) -> Vec<Predeploy> {
+    let Ok(chain) = NamedChain::try_from(chain_id) else { return Vec::new() };
+    if chain.is_optimism() {
+        return OP_STACK.to_vec();
+    }
+    if chain.is_arbitrum() {
+        return ARBITRUM.to_vec();
+    }
+
+    let deposit_contract = match chain {
+        NamedChain::Mainnet => eip6110::MAINNET_DEPOSIT_CONTRACT_ADDRESS,
+        NamedChain::Sepolia => SEPOLIA_DEPOSIT_CONTRACT_ADDRESS,
+        NamedChain::Holesky => HOLESKY_DEPOSIT_CONTRACT_ADDRESS,
+        _ => return Vec::new(),
+    };
+    let mut contracts = ETHEREUM.to_vec();
+    contracts.push(Predeploy {
+        name: "DepositContract",
+        address: deposit_contract,
+        abi: DEPOSIT_CONTRACT_ABI,
+    });
+    contracts
+}
+
+/// Returns the system contract or predeploy of the chain with the name.
+pub fn predeploy(chain_id: ChainId, name: &str) -> Option<Predeploy> {
+    predeploys(chain_id).into_iter().find(|contract| contract.name == name)
+}
+
+/// Returns the system contract or predeploy of the chain at the address.
+pub fn predeploy_at(chain_id: ChainId, address: Address) -> Option<Predeploy> {
+    predeploys(chain_id).into_iter().find(|contract| contract.address == address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_predeploys() {
+        let deposit = predeploy(NamedChain::Sepolia.into(), "DepositContract").unwrap();
+        assert_eq!(deposit.address, SEPOLIA_DEPOSIT_CONTRACT_ADDRESS);
+        assert_eq!(
+            predeploy_at(NamedChain::Mainnet.into(), eip4788::BEACON_ROOTS_ADDRESS).unwrap().name,
+            "BeaconRoots"
+        );
+
+        assert_eq!(predeploy(NamedChain::Base.into(), "GasPriceOracle").unwrap().abi.len(), 6);
+        assert!(predeploy(NamedChain::Base.into(), "DepositContract").is_none());
+        assert_eq!(
+            predeploy_at(NamedChain::Arbitrum.into(), NODE_INTERFACE).unwrap().name,
+            "NodeInterface"
+        );
+        assert!(predeploys(NamedChain::Polygon.into()).is_empty());
+        assert!(predeploys(123_456_789).is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{EthCall, Provider, RpcWithBlock};
+use alloy_network::Network;
+use alloy_primitives::{Address, Bytes, StorageKey, StorageValue, U256, U64};
+use alloy_rpc_types_eth::{BlockId, EIP1186AccountProofResponse};
+use alloy_transport::Transport;
+use std::marker::PhantomData;
+
+/// A view of a [`Provider`] whose state queries default to a fixed block.
+///
+/// Created by [`Provider::at_block`]. Each request can still be moved to another block, e.g. with
+/// [`RpcWithBlock::block_id`] or [`EthCall::block`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example<P: alloy_provider::Provider>(provider: P) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_primitives::Address;
+///
+/// let at = provider.at_block(15_537_394.into());
+/// let balance = at.get_balance(Address::ZERO).await?;
+/// let code = at.get_code_at(Address::ZERO).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AtBlock<'a, P, T, N> {
+    provider: &'a P,
+    block_id: BlockId,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> Clone for AtBlock<'_, P, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P, T, N> Copy for AtBlock<'_, P, T, N> {}
+
+impl<'a, P, T, N> AtBlock<'a, P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new view of the provider at the given block.
+    pub const fn new(provider: &'a P, block_id: BlockId) -> Self {
+        Self { provider, block_id, _pd: PhantomData }
+    }
+
+    /// Returns the block the queries default to.
+    pub const fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+
+    /// Returns the underlying provider.
+    pub const fn provider(&self) -> &'a P {
+        self.provider
+    }
+
+    /// Executes a call at the block, see [`Provider::call`].
+    pub fn call<'req>(&self, tx: &'req N::TransactionRequest) -> EthCall<'req, T, N, Bytes> {
+        self.provider.call(tx).block(self.block_id)
+    }
+
+    /// Gets the balance of the account at the block, see [`Provider::get_balance`].
+    pub fn get_balance(&self, address: Address) -> RpcWithBlock<T, Address, U256, U256> {
+        self.provider.get_balance(address).block_id(self.block_id)
+    }
+
+    /// Gets the storage value at the block, see [`Provider::get_storage_at`].
+    pub fn get_storage_at(
+        &self,
+        address: Address,
+        key: U256,
+    ) -> RpcWithBlock<T, (Address, U256), StorageValue> {
+        self.provider.get_storage_at(address, key).block_id(self.block_id)
+    }
+
+    /// Gets the bytecode at the block, see [`Provider::get_code_at`].
+    pub fn get_code_at(&self, address: Address) -> RpcWithBlock<T, Address, Bytes> {
+        self.provider.get_code_at(address).block_id(self.block_id)
+    }
+
+    /// Gets the transaction count of the account at the block, see
+    /// [`Provider::get_transaction_count`].
+    pub fn get_transaction_count(
+        &self,
+        address: Address,
+    ) -> RpcWithBlock<T, Address, U64, u64, fn(U64) -> u64> {
+        self.provider.get_transaction_count(address).block_id(self.block_id)
+    }
+
+    /// Gets the account and storage proofs at the block, see [`Provider::get_proof`].
+    pub fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<StorageKey>,
+    ) -> RpcWithBlock<T, (Address, Vec<StorageKey>), EIP1186AccountProofResponse> {
+        self.provider.get_proof(address, keys).block_id(self.block_id)
+    }
+
+    /// Gets the account at the block, see [`Provider::get_account`].
+    pub fn get_account(
+        &self,
+        address: Address,
+    ) -> RpcWithBlock<T, Address, alloy_consensus::Account> {
+        self.provider.get_account(address).block_id(self.block_id)
+    }
+}
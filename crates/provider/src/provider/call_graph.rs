@@ -0,0 +1,326 @@
+use crate::Provider;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_network_primitives::{BlockResponse, HeaderResponse};
+use alloy_primitives::{Address, Bytes};
+use alloy_rpc_types_eth::{
+    simulate::{SimBlock, SimulatePayload},
+    BlockId, TransactionRequest,
+};
+use alloy_sol_types::SolCall;
+use alloy_transport::{Transport, TransportResult};
+use futures::future::try_join_all;
+use std::{fmt, marker::PhantomData};
+
+/// The identifier of a call in a [`CallGraph`], used to declare dependencies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CallId(usize);
+
+/// A typed handle to a call in a [`CallGraph`], used to read its output from [`CallOutputs`].
+pub struct CallHandle<C> {
+    id: CallId,
+    _pd: PhantomData<fn() -> C>,
+}
+
+impl<C> Clone for CallHandle<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for CallHandle<C> {}
+
+impl<C> fmt::Debug for CallHandle<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CallHandle").field(&self.id).finish()
+    }
+}
+
+impl<C> CallHandle<C> {
+    /// Returns the identifier of the call.
+    pub const fn id(&self) -> CallId {
+        self.id
+    }
+}
+
+/// The outputs of the calls of a [`CallGraph`] executed so far.
+#[derive(Clone, Debug, Default)]
+pub struct CallOutputs {
+    outputs: Vec<Option<Bytes>>,
+}
+
+impl CallOutputs {
+    /// Decodes the output of the call.
+    ///
+    /// Fails if the output is malformed, or if the call was not executed yet, which happens when
+    /// building a call that does not declare it as a dependency.
+    pub fn get<C: SolCall>(&self, handle: &CallHandle<C>) -> TransportResult<C::Return> {
+        let output = self.raw(handle.id).ok_or_else(|| {
+            RpcError::local_usage_str("the call was not executed yet, declare it as a dependency")
+        })?;
+        C::abi_decode_returns(output, true).map_err(|e| RpcError::local_usage_str(&e.to_string()))
+    }
+
+    /// Returns the raw output of the call, if it was executed.
+    pub fn raw(&self, id: CallId) -> Option<&Bytes> {
+        self.outputs.get(id.0).and_then(Option::as_ref)
+    }
+}
+
+type BuildCall<'a> = Box<dyn FnOnce(&CallOutputs) -> TransportResult<(Address, Bytes)> + Send + 'a>;
+
+struct Node<'a> {
+    dependencies: Vec<CallId>,
+    build: BuildCall<'a>,
+}
+
+/// How a [`CallGraph`] executes the calls of each level.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CallGraphMode {
+    /// Concurrent `eth_call`s, one per call.
+    #[default]
+    EthCall,
+    /// A single `eth_simulateV1` request per level, if the node supports it.
+    Simulate,
+}
+
+/// A graph of typed calls whose outputs feed the inputs of later calls, executed against the
+/// same block.
+///
+/// Calls are added with [`CallGraph::add`], or with [`CallGraph::add_dependent`] to build a call
+/// from the decoded outputs of the calls it depends on. Since a call can only depend on calls
+/// added before it, the graph cannot have cycles. The calls are executed level by level: all
+/// calls whose dependencies are satisfied are sent together, see [`CallGraphMode`].
+///
+/// A block tag is resolved to its hash first, so that all levels read the same state.
+///
+/// # Examples
+///
+/// ```
+/// # use alloy_primitives::{address, Address};
+/// # use alloy_provider::{CallGraph, Provider};
+/// # use alloy_rpc_types_eth::BlockId;
+/// # use alloy_sol_types::sol;
+/// sol! {
+///     function getPool(address a, address b, uint24 fee) returns (address);
+///     function liquidity() returns (uint128);
+/// }
+///
+/// # async fn test(provider: impl Provider, factory: Address, a: Address, b: Address) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut graph = CallGraph::new(&provider, BlockId::latest());
+/// let pool = graph.add(factory, getPoolCall { a, b, fee: 3000u32.try_into()? });
+/// let liquidity = graph.add_dependent([pool.id()], move |outputs| {
+///     Ok((outputs.get(&pool)?._0, liquidityCall {}))
+/// });
+///
+/// let outputs = graph.execute().await?;
+/// println!("liquidity: {}", outputs.get(&liquidity)?._0);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CallGraph<'a, P, T, N> {
+    provider: &'a P,
+    block: BlockId,
+    mode: CallGraphMode,
+    nodes: Vec<Node<'a>>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> fmt::Debug for CallGraph<'_, P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallGraph")
+            .field("block", &self.block)
+            .field("mode", &self.mode)
+            .field("calls", &self.nodes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, P, T, N> CallGraph<'a, P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates an empty graph executing against the block.
+    pub const fn new(provider: &'a P, block: BlockId) -> Self {
+        Self { provider, block, mode: CallGraphMode::EthCall, nodes: Vec::new(), _pd: PhantomData }
+    }
+
+    /// Sets how the calls are executed.
+    pub const fn with_mode(mut self, mode: CallGraphMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Adds a call without dependencies.
+    pub fn add<C: SolCall + Send + 'a>(&mut self, to: Address, call: C) -> CallHandle<C> {
+        self.push(Vec::new(), move |_| Ok((to, call)))
+    }
+
+    /// Adds a call built from the outputs of its dependencies, returning the target and the call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dependency is not the [`CallId`] of a call added to this graph before.
+    pub fn add_dependent<C, F>(
+        &mut self,
+        dependencies: impl IntoIterator<Item = CallId>,
+        build: F,
+    ) -> CallHandle<C>
+    where
+        C: SolCall,
+        F: FnOnce(&CallOutputs) -> TransportResult<(Address, C)> + Send + 'a,
+    {
+        self.push(dependencies.into_iter().collect(), build)
+    }
+
+    fn push<C, F>(&mut self, dependencies: Vec<CallId>, build: F) -> CallHandle<C>
+    where
+        C: SolCall,
+        F: FnOnce(&CallOutputs) -> TransportResult<(Address, C)> + Send + 'a,
+    {
+        let id = CallId(self.nodes.len());
+        assert!(dependencies.iter().all(|dependency| *dependency < id), "unknown dependency");
+        self.nodes.push(Node {
+            dependencies,
+            build: Box::new(move |outputs| {
+                build(outputs).map(|(to, call)| (to, call.abi_encode().into()))
+            }),
+        });
+        CallHandle { id, _pd: PhantomData }
+    }
+
+    /// Executes the calls, failing if any call fails or cannot be built.
+    pub async fn execute(self) -> TransportResult<CallOutputs> {
+        let block = match self.block {
+            BlockId::Number(tag) => {
+                let block = self
+                    .provider
+                    .get_block_by_number(tag, false)
+                    .await?
+                    .ok_or_else(|| RpcError::local_usage_str("block not found"))?;
+                BlockId::hash(block.header().hash())
+            }
+            block => block,
+        };
+
+        // a call runs one level after its latest dependency
+        let mut levels: Vec<usize> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let level = node.dependencies.iter().map(|id| levels[id.0] + 1).max().unwrap_or(0);
+            levels.push(level);
+        }
+
+        let mut outputs = CallOutputs { outputs: vec![None; self.nodes.len()] };
+        let mut nodes = self.nodes.into_iter().map(Some).collect::<Vec<_>>();
+        for level in 0..=levels.iter().copied().max().unwrap_or(0) {
+            let mut ids = Vec::new();
+            let mut calls = Vec::new();
+            for (id, node) in nodes.iter_mut().enumerate() {
+                if levels[id] != level {
+                    continue;
+                }
+                let node = node.take().expect("each call is built once");
+                calls.push((node.build)(&outputs)?);
+                ids.push(id);
+            }
+            if calls.is_empty() {
+                continue;
+            }
+
+            let results = match self.mode {
+                CallGraphMode::EthCall => {
+                    try_join_all(calls.into_iter().map(|(to, input)| async move {
+                        let tx = N::TransactionRequest::default().with_to(to).with_input(input);
+                        self.provider.call(&tx).block(block).await
+                    }))
+                    .await?
+                }
+                CallGraphMode::Simulate => simulate(self.provider, calls, block).await?,
+            };
+            for (id, output) in ids.into_iter().zip(results) {
+                outputs.outputs[id] = Some(output);
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+async fn simulate<P, T, N>(
+    provider: &P,
+    calls: Vec<(Address, Bytes)>,
+    block: BlockId,
+) -> TransportResult<Vec<Bytes>>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let len = calls.len();
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimBlock {
+            block_overrides: None,
+            state_overrides: None,
+            calls: calls
+                .into_iter()
+                .map(|(to, input)| TransactionRequest::default().to(to).input(input.into()))
+                .collect(),
+        }],
+        trace_transfers: false,
+        validation: false,
+        return_full_transactions: false,
+    };
+    let blocks = provider.simulate(&payload).block_id(block).await?;
+    let results = blocks.into_iter().next().map(|block| block.calls).unwrap_or_default();
+    if results.len() != len {
+        return Err(RpcError::local_usage_str("eth_simulateV1 returned a wrong number of calls"));
+    }
+    results
+        .into_iter()
+        .map(|result| match result.error {
+            Some(error) if !result.status => {
+                Err(RpcError::local_usage_str(&format!("simulated call failed: {}", error.message)))
+            }
+            _ if !result.status => Err(RpcError::local_usage_str("simulated call reverted")),
+            _ => Ok(result.return_data),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_network::TransactionBuilder;
+    use alloy_primitives::{hex, U256};
+    use alloy_sol_types::sol;
+
+    sol! {
+        function counter() returns (uint256);
+    }
+
+    #[tokio::test]
+    async fn feeds_outputs_into_calls() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let bytecode = hex::decode("6080806040523460135760b2908160188239f35b5f80fdfe60808060405260043610156011575f80fd5b5f3560e01c90816361bc221a146065575063d09de08a14602f575f80fd5b346061575f3660031901126061575f5460018101809111604d575f55005b634e487b7160e01b5f52601160045260245ffd5b5f80fd5b346061575f3660031901126061576020905f548152f3fea2646970667358221220d802267a5f574e54a87a63d0ff8d733fdb275e6e6c502831d9e14f957bbcd7a264736f6c634300081a0033").unwrap();
+        let counter = provider
+            .send_transaction(TransactionRequest::default().with_deploy_code(bytecode))
+            .await
+            .unwrap()
+            .get_receipt()
+            .await
+            .unwrap()
+            .contract_address
+            .unwrap();
+
+        let mut graph = CallGraph::new(&provider, BlockId::latest());
+        let first = graph.add(counter, counterCall {});
+        let second = graph.add_dependent([first.id()], move |outputs| {
+            assert_eq!(outputs.get(&first)?._0, U256::ZERO);
+            Ok((counter, counterCall {}))
+        });
+        let outputs = graph.execute().await.unwrap();
+        assert_eq!(outputs.get(&second).unwrap()._0, U256::ZERO);
+    }
+}
@@ -24,6 +24,22 @@ where
     ) -> TransportResult<ProviderCall<T, serde_json::Value, Resp>>;
 }
 
+impl<T, Params, Resp, C> Caller<T, Params, Resp> for std::sync::Arc<C>
+where
+    T: Transport + Clone,
+    Params: RpcParam,
+    Resp: RpcReturn,
+    C: Caller<T, Params, Resp> + ?Sized,
+{
+    fn call(
+        &self,
+        method: Cow<'static, str>,
+        params: Params,
+    ) -> TransportResult<ProviderCall<T, serde_json::Value, Resp>> {
+        (**self).call(method, params)
+    }
+}
+
 impl<T, Params, Resp> Caller<T, Params, Resp> for WeakClient<T>
 where
     T: Transport + Clone,
@@ -0,0 +1,239 @@
+use crate::NodeIdentity;
+use alloy_eips::BlockNumberOrTag;
+use alloy_json_rpc::RpcError;
+use alloy_primitives::{Address, U256};
+use alloy_rpc_client::ClientRef;
+use alloy_transport::{Transport, TransportResult};
+use dashmap::DashMap;
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+/// The error code returned by JSON-RPC servers for unknown methods.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The cached capabilities of an RPC endpoint, as discovered by
+/// [`Provider::supports`](crate::Provider::supports).
+///
+/// The capabilities are discovered lazily and shared by all clones of a [`RootProvider`]:
+///
+/// - The namespaces enabled on the endpoint are queried once with `rpc_modules`, if the endpoint
+///   supports it. Methods of namespaces that are not enabled are unsupported.
+/// - Otherwise, whether a method is supported is probed with a trial call, which is then cached.
+///   The namespaces listed in the [`NodeQuirks`](crate::NodeQuirks) of the node implementation
+///   are not relied on, since nodes can be configured with other namespaces.
+///
+/// Whether the endpoint serves historical state, as an archive node does, is probed once with a
+/// state query at the genesis block, see [`Capabilities::archive`].
+///
+/// Entries can also be set manually with [`Capabilities::set`] and [`Capabilities::set_archive`],
+/// for example for endpoints that do not report unknown methods with the standard "method not
+/// found" error.
+///
+/// [`RootProvider`]: crate::RootProvider
+#[derive(Debug, Default)]
+pub struct Capabilities {
+    modules: OnceLock<Option<HashMap<String, String>>>,
+    methods: DashMap<String, bool>,
+    archive: RwLock<Option<bool>>,
+    identity: OnceLock<NodeIdentity>,
+}
+
+impl Capabilities {
+    /// Returns the namespaces enabled on the endpoint, with their versions, if they were
+    /// discovered with `rpc_modules`.
+    pub fn modules(&self) -> Option<&HashMap<String, String>> {
+        self.modules.get().and_then(Option::as_ref)
+    }
+
+    /// Returns the identity of the node, if it was already discovered.
+    pub fn identity(&self) -> Option<&NodeIdentity> {
+        self.identity.get()
+    }
+
+    /// Returns the identity of the node, querying `web3_clientVersion` if unknown.
+    pub(crate) async fn discover_identity<T: Transport + Clone>(
+        &self,
+        client: ClientRef<'_, T>,
+    ) -> TransportResult<&NodeIdentity> {
+        if let Some(identity) = self.identity() {
+            return Ok(identity);
+        }
+        let identity = match client.request_noparams::<String>("web3_clientVersion").await {
+            Ok(client_version) => NodeIdentity::parse(&client_version),
+            Err(RpcError::ErrorResp(_)) => NodeIdentity::unknown(),
+            Err(err) => return Err(err),
+        };
+        Ok(self.identity.get_or_init(|| identity))
+    }
+
+    /// Returns whether the method is supported, if known.
+    pub fn get(&self, method: &str) -> Option<bool> {
+        self.methods.get(method).map(|supported| *supported)
+    }
+
+    /// Sets whether the method is supported, overriding any discovered value.
+    pub fn set(&self, method: impl Into<String>, supported: bool) {
+        self.methods.insert(method.into(), supported);
+    }
+
+    /// Returns whether the endpoint serves historical state, if known.
+    pub fn archive(&self) -> Option<bool> {
+        *self.archive.read().unwrap()
+    }
+
+    /// Sets whether the endpoint serves historical state, overriding any discovered value.
+    pub fn set_archive(&self, archive: bool) {
+        *self.archive.write().unwrap() = Some(archive);
+    }
+
+    /// Clears all cached capabilities, except for the discovered namespaces.
+    pub fn clear(&self) {
+        self.methods.clear();
+        *self.archive.write().unwrap() = None;
+    }
+
+    /// Returns whether the endpoint serves historical state, probing it if unknown.
+    ///
+    /// Pruned nodes only keep the state of recent blocks, so the state at the genesis block is
+    /// only served by archive nodes, and by chains that are still young.
+    pub(crate) async fn discover_archive<T: Transport + Clone>(
+        &self,
+        client: ClientRef<'_, T>,
+    ) -> TransportResult<bool> {
+        if let Some(archive) = self.archive() {
+            return Ok(archive);
+        }
+        let archive = match client
+            .request::<_, U256>("eth_getBalance", (Address::ZERO, BlockNumberOrTag::Earliest))
+            .await
+        {
+            Ok(_) => true,
+            Err(RpcError::ErrorResp(_)) => false,
+            Err(err) => return Err(err),
+        };
+        self.set_archive(archive);
+        Ok(archive)
+    }
+
+    /// Returns whether the endpoint supports the method, probing it if unknown.
+    pub(crate) async fn supports<T: Transport + Clone>(
+        &self,
+        client: ClientRef<'_, T>,
+        method: &str,
+    ) -> TransportResult<bool> {
+        if let Some(supported) = self.get(method) {
+            return Ok(supported);
+        }
+
+        if self.modules.get().is_none() {
+            let modules = match client.request_noparams("rpc_modules").await {
+                Ok(modules) => Some(modules),
+                Err(RpcError::ErrorResp(_)) => None,
+                Err(err) => return Err(err),
+            };
+            let _ = self.modules.set(modules);
+        }
+        let namespace = method.split_once('_').map_or(method, |(namespace, _)| namespace);
+        if self.modules().is_some_and(|modules| !modules.contains_key(namespace)) {
+            self.set(method, false);
+            return Ok(false);
+        }
+
+        // Probe the method with a parameter that no method accepts, so that the call is rejected
+        // before it is executed. Any error other than "method not found" means that the method
+        // exists.
+        let supported = match client
+            .request::<_, serde_json::Value>(
+                method.to_string(),
+                [serde_json::json!({ "alloyCapabilityProbe": true })],
+            )
+            .await
+        {
+            Ok(_) => true,
+            Err(RpcError::ErrorResp(err)) => !is_method_not_found(err.code, &err.message),
+            Err(err) => return Err(err),
+        };
+        self.set(method, supported);
+        Ok(supported)
+    }
+}
+
+/// Messages naming the method, as `(prefix, suffix)` around its name, that endpoints return for
+/// unknown methods with another code than [`METHOD_NOT_FOUND`].
+const METHOD_NOT_FOUND_MESSAGES: &[(&str, &str)] = &[
+    ("the method ", " does not exist/is not available"),
+    ("method ", " not found"),
+    ("method ", " is not supported"),
+    ("", " is not supported"),
+];
+
+/// Returns `true` if the error response indicates that the method does not exist.
+///
+/// This is the case for the standard [`METHOD_NOT_FOUND`] code. Otherwise, the message must match
+/// one of the known vendor messages as a whole, so that other errors, such as "block not found",
+/// are not mistaken for unknown methods.
+pub(crate) fn is_method_not_found(code: i64, message: &str) -> bool {
+    if code == METHOD_NOT_FOUND {
+        return true;
+    }
+
+    let message = message.trim().to_ascii_lowercase();
+    message == "method not found"
+        || message.starts_with("unsupported method:")
+        || METHOD_NOT_FOUND_MESSAGES.iter().any(|(prefix, suffix)| {
+            message
+                .strip_prefix(prefix)
+                .and_then(|method| method.strip_suffix(suffix))
+                .is_some_and(is_method_name)
+        })
+}
+
+/// Returns `true` if the string is a possibly quoted method name, e.g. `eth_call`.
+fn is_method_name(name: &str) -> bool {
+    let name = name.trim_matches(|c| matches!(c, '\'' | '"' | '`'));
+    name.split_once('_').is_some_and(|(namespace, method)| {
+        !namespace.is_empty()
+            && !method.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_not_found_errors() {
+        assert!(is_method_not_found(-32601, "the method eth_foo does not exist/is not available"));
+        assert!(is_method_not_found(-32601, "block not found"));
+        assert!(is_method_not_found(-32000, "Method not found"));
+        assert!(is_method_not_found(-32000, "the method eth_foo does not exist/is not available"));
+        assert!(is_method_not_found(-32000, "method 'trace_filter' not found"));
+        assert!(is_method_not_found(-32000, "eth_getBlockReceipts is not supported"));
+        assert!(is_method_not_found(-32600, "Unsupported method: trace_filter on ETH_MAINNET"));
+
+        assert!(!is_method_not_found(-32000, "block not found"));
+        assert!(!is_method_not_found(-32000, "header not found"));
+        assert!(!is_method_not_found(-32000, "method handler crashed: block not found"));
+        assert!(!is_method_not_found(-32000, "transaction type not supported"));
+        assert!(!is_method_not_found(-32000, "pending block is not supported"));
+        assert!(!is_method_not_found(-32602, "invalid type: map, expected a block number"));
+        assert!(!is_method_not_found(-32000, "execution reverted"));
+    }
+
+    #[test]
+    fn manual_capabilities() {
+        let capabilities = Capabilities::default();
+        assert_eq!(capabilities.get("eth_getBlockReceipts"), None);
+        capabilities.set("eth_getBlockReceipts", false);
+        assert_eq!(capabilities.get("eth_getBlockReceipts"), Some(false));
+        capabilities.set_archive(true);
+        assert_eq!(capabilities.archive(), Some(true));
+        capabilities.clear();
+        assert_eq!(capabilities.get("eth_getBlockReceipts"), None);
+        assert_eq!(capabilities.archive(), None);
+        assert_eq!(capabilities.modules(), None);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::Provider;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{b256, hex, Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::BlockId;
+use alloy_transport::{Transport, TransportResult};
+
+/// The storage slot of the implementation address of an EIP-1967 proxy,
+/// `keccak256("eip1967.proxy.implementation") - 1`.
+pub const EIP1967_IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// The storage slot of the beacon address of an EIP-1967 beacon proxy,
+/// `keccak256("eip1967.proxy.beacon") - 1`.
+pub const EIP1967_BEACON_SLOT: B256 =
+    b256!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50");
+
+/// The storage slot of the implementation address of an EIP-1822 (UUPS) proxy,
+/// `keccak256("PROXIABLE")`.
+pub const EIP1822_PROXIABLE_SLOT: B256 =
+    b256!("c5f16f0fcc639fa48a6947836d9850f504798523bf8c9a3a87d5876cf622bcf7");
+
+/// The prefix of the code of an EOA that delegated to a contract with EIP-7702.
+pub const EIP7702_DELEGATION_PREFIX: [u8; 3] = hex!("ef0100");
+
+const EIP1167_PREFIX: [u8; 10] = hex!("363d3d373d3d3d363d73");
+const EIP1167_SUFFIX: [u8; 15] = hex!("5af43d82803e903d91602b57fd5bf3");
+
+/// The selector of `implementation()`, implemented by EIP-1967 beacons.
+const IMPLEMENTATION_SELECTOR: [u8; 4] = hex!("5c60da1b");
+
+/// The kind of code at an address, see [`Provider::get_code_with_metadata`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodeKind {
+    /// No code, the address is an externally owned account or empty.
+    Eoa,
+    /// Code that does not forward to another address.
+    Contract,
+    /// An EIP-1167 minimal proxy, forwarding to the implementation in its code.
+    MinimalProxy {
+        /// The implementation of the proxy.
+        implementation: Address,
+    },
+    /// An EIP-1967 proxy, forwarding to the implementation in its implementation slot.
+    Eip1967Proxy {
+        /// The implementation of the proxy.
+        implementation: Address,
+    },
+    /// An EIP-1822 proxy, forwarding to the implementation in its `PROXIABLE` slot.
+    Eip1822Proxy {
+        /// The implementation of the proxy.
+        implementation: Address,
+    },
+    /// An EIP-1967 beacon proxy, forwarding to the implementation returned by its beacon.
+    Eip1967BeaconProxy {
+        /// The beacon of the proxy.
+        beacon: Address,
+        /// The implementation returned by the beacon, or `None` if the beacon call failed.
+        implementation: Option<Address>,
+    },
+    /// An EOA that delegated to a contract with EIP-7702.
+    Delegated {
+        /// The contract whose code the EOA executes.
+        delegate: Address,
+    },
+}
+
+impl CodeKind {
+    /// Classifies the code by its content only, without reading the storage of proxies.
+    ///
+    /// Returns [`CodeKind::Contract`] for EIP-1967 and EIP-1822 proxies, see
+    /// [`Provider::get_code_with_metadata`].
+    pub fn from_code(code: &[u8]) -> Self {
+        if code.is_empty() {
+            return Self::Eoa;
+        }
+        if let Some(delegate) = code.strip_prefix(&EIP7702_DELEGATION_PREFIX) {
+            if delegate.len() == 20 {
+                return Self::Delegated { delegate: Address::from_slice(delegate) };
+            }
+        }
+        if code.len() == EIP1167_PREFIX.len() + 20 + EIP1167_SUFFIX.len()
+            && code.starts_with(&EIP1167_PREFIX)
+            && code.ends_with(&EIP1167_SUFFIX)
+        {
+            let implementation = &code[EIP1167_PREFIX.len()..EIP1167_PREFIX.len() + 20];
+            return Self::MinimalProxy { implementation: Address::from_slice(implementation) };
+        }
+        Self::Contract
+    }
+
+    /// Returns the address whose code is executed instead, if any.
+    pub const fn target(&self) -> Option<Address> {
+        match *self {
+            Self::Eoa | Self::Contract => None,
+            Self::MinimalProxy { implementation }
+            | Self::Eip1967Proxy { implementation }
+            | Self::Eip1822Proxy { implementation } => Some(implementation),
+            Self::Eip1967BeaconProxy { implementation, .. } => implementation,
+            Self::Delegated { delegate } => Some(delegate),
+        }
+    }
+}
+
+/// The code at an address, classified and with its implementation resolved.
+///
+/// Returned by [`Provider::get_code_with_metadata`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeMetadata {
+    /// The address of the code.
+    pub address: Address,
+    /// The code at the address.
+    pub code: Bytes,
+    /// The kind of the code.
+    pub kind: CodeKind,
+    /// The resolved code of the [target](CodeKind::target), or `None` if there is no target or
+    /// the depth limit was reached.
+    pub implementation: Option<Box<Self>>,
+}
+
+impl CodeMetadata {
+    /// Returns the last resolved code, e.g. the implementation behind a chain of proxies.
+    pub fn resolved(&self) -> &Self {
+        let mut metadata = self;
+        while let Some(implementation) = &metadata.implementation {
+            metadata = implementation;
+        }
+        metadata
+    }
+}
+
+pub(crate) async fn get_code_with_metadata<P, T, N>(
+    provider: &P,
+    mut address: Address,
+    block: BlockId,
+    max_depth: usize,
+) -> TransportResult<CodeMetadata>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    let mut chain = Vec::new();
+    loop {
+        let code = provider.get_code_at(address).block_id(block).await?;
+        let mut kind = CodeKind::from_code(&code);
+        if kind == CodeKind::Contract {
+            kind = proxy_kind(provider, address, block).await?;
+        }
+        chain.push(CodeMetadata { address, code, kind, implementation: None });
+
+        match kind.target() {
+            Some(target) if chain.len() <= max_depth => address = target,
+            _ => break,
+        }
+    }
+
+    let mut metadata = chain.pop().expect("code is fetched at least once");
+    while let Some(mut proxy) = chain.pop() {
+        proxy.implementation = Some(Box::new(metadata));
+        metadata = proxy;
+    }
+    Ok(metadata)
+}
+
+/// Reads the EIP-1967 and EIP-1822 slots of a contract, returning [`CodeKind::Contract`] if all
+/// are empty.
+async fn proxy_kind<P, T, N>(
+    provider: &P,
+    address: Address,
+    block: BlockId,
+) -> TransportResult<CodeKind>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    let slot = |slot: B256| provider.get_storage_at(address, slot.into()).block_id(block);
+
+    if let Some(implementation) = slot_address(slot(EIP1967_IMPLEMENTATION_SLOT).await?) {
+        return Ok(CodeKind::Eip1967Proxy { implementation });
+    }
+    let Some(beacon) = slot_address(slot(EIP1967_BEACON_SLOT).await?) else {
+        let implementation = slot_address(slot(EIP1822_PROXIABLE_SLOT).await?);
+        return Ok(implementation.map_or(CodeKind::Contract, |implementation| {
+            CodeKind::Eip1822Proxy { implementation }
+        }));
+    };
+
+    let tx = N::TransactionRequest::default()
+        .with_to(beacon)
+        .with_input(Bytes::from_static(&IMPLEMENTATION_SELECTOR));
+    let implementation = provider
+        .call(&tx)
+        .block(block)
+        .await
+        .ok()
+        .filter(|output| output.len() == 32)
+        .and_then(|output| slot_address(U256::from_be_slice(&output)));
+    Ok(CodeKind::Eip1967BeaconProxy { beacon, implementation })
+}
+
+/// Returns the address stored in the slot, or `None` if it is empty.
+fn slot_address(value: U256) -> Option<Address> {
+    let address = Address::from_word(value.into());
+    (!address.is_zero()).then_some(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn classifies_code() {
+        let implementation = address!("bEbc44782C7dB0a1A60Cb6fe97d0b483032FF1C7");
+        let minimal_proxy =
+            [&EIP1167_PREFIX[..], &implementation[..], &EIP1167_SUFFIX[..]].concat();
+        assert_eq!(CodeKind::from_code(&minimal_proxy), CodeKind::MinimalProxy { implementation });
+
+        let delegation = [&EIP7702_DELEGATION_PREFIX[..], &implementation[..]].concat();
+        assert_eq!(
+            CodeKind::from_code(&delegation),
+            CodeKind::Delegated { delegate: implementation }
+        );
+        assert_eq!(CodeKind::from_code(&delegation[..22]), CodeKind::Contract);
+
+        assert_eq!(CodeKind::from_code(&[]), CodeKind::Eoa);
+        assert_eq!(CodeKind::from_code(&hex!("6080604052")), CodeKind::Contract);
+        assert_eq!(CodeKind::Contract.target(), None);
+    }
+}
@@ -17,6 +17,23 @@ pub struct EthCallParams<'req, N: Network> {
     overrides: Option<&'req StateOverride>,
 }
 
+impl<'req, N: Network> EthCallParams<'req, N> {
+    /// Returns the transaction request of the call.
+    pub const fn data(&self) -> &'req N::TransactionRequest {
+        self.data
+    }
+
+    /// Returns the block of the call, if set.
+    pub const fn block(&self) -> Option<BlockId> {
+        self.block
+    }
+
+    /// Returns the state overrides of the call, if set.
+    pub const fn overrides(&self) -> Option<&'req StateOverride> {
+        self.overrides
+    }
+}
+
 impl<N: Network> serde::Serialize for EthCallParams<'_, N> {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let len = if self.overrides.is_some() { 3 } else { 2 };
@@ -59,7 +76,7 @@ where
     Map: Fn(Resp) -> Output,
 {
     Preparing {
-        caller: Arc<dyn Caller<T, EthCallParams<'req, N>, Resp>>,
+        caller: Arc<dyn Caller<T, EthCallParams<'req, N>, Resp> + 'req>,
         data: &'req N::TransactionRequest,
         overrides: Option<&'req StateOverride>,
         block: Option<BlockId>,
@@ -178,7 +195,7 @@ where
     Resp: RpcReturn,
     Map: Fn(Resp) -> Output,
 {
-    caller: Arc<dyn Caller<T, EthCallParams<'req, N>, Resp>>,
+    caller: Arc<dyn Caller<T, EthCallParams<'req, N>, Resp> + 'req>,
     data: &'req N::TransactionRequest,
     overrides: Option<&'req StateOverride>,
     block: Option<BlockId>,
@@ -278,6 +295,19 @@ where
         }
     }
 
+    /// Wraps the caller of this call, e.g. for a provider layer to intercept the requests while
+    /// keeping the callers of the layers below it.
+    pub fn wrap_caller<C>(
+        mut self,
+        wrap: impl FnOnce(Arc<dyn Caller<T, EthCallParams<'req, N>, Resp> + 'req>) -> C,
+    ) -> Self
+    where
+        C: Caller<T, EthCallParams<'req, N>, Resp> + 'req,
+    {
+        self.caller = Arc::new(wrap(self.caller));
+        self
+    }
+
     /// Set the state overrides for this call.
     pub const fn overrides(mut self, overrides: &'req StateOverride) -> Self {
         self.overrides = Some(overrides);
@@ -0,0 +1,325 @@
+use crate::Provider;
+use alloy_json_rpc::RpcError;
+use alloy_network::Network;
+use alloy_primitives::BlockNumber;
+use alloy_rpc_types_eth::{BlockNumberOrTag, FeeHistory};
+use alloy_transport::{Transport, TransportResult};
+use futures::{stream, StreamExt, TryStreamExt};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+/// The default number of blocks requested in one `eth_feeHistory` call, the maximum most nodes
+/// accept.
+const DEFAULT_CHUNK_SIZE: u64 = 1024;
+
+/// The default number of `eth_feeHistory` calls in flight.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// The fee market of a range of blocks, as columns indexed by block.
+///
+/// The value of a block is at index `block_number - first_block` of each column. Rewards are
+/// stored flat, with one value per [percentile](Self::percentiles) for each block.
+///
+/// Built by [`FeeDatasetBuilder`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeeDataset {
+    /// The first block of the dataset.
+    pub first_block: BlockNumber,
+    /// The percentiles of the rewards, in ascending order.
+    pub percentiles: Vec<f64>,
+    /// The base fee per gas of each block, 0 before London.
+    pub base_fee_per_gas: Vec<u128>,
+    /// The base fee per blob gas of each block, 0 before Cancun.
+    pub base_fee_per_blob_gas: Vec<u128>,
+    /// The ratio of gas used to the gas limit of each block.
+    pub gas_used_ratio: Vec<f64>,
+    /// The ratio of blob gas used to the maximum blob gas of each block, 0 before Cancun.
+    pub blob_gas_used_ratio: Vec<f64>,
+    /// The priority fee per gas at each percentile of each block, weighted by gas used.
+    pub rewards: Vec<u128>,
+}
+
+impl FeeDataset {
+    /// Returns the number of blocks.
+    pub fn len(&self) -> usize {
+        self.gas_used_ratio.len()
+    }
+
+    /// Returns whether the dataset has no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.gas_used_ratio.is_empty()
+    }
+
+    /// Returns the blocks of the dataset.
+    pub fn blocks(&self) -> Range<BlockNumber> {
+        self.first_block..self.first_block + self.len() as u64
+    }
+
+    /// Returns the fees of the block, if it is in the dataset.
+    pub fn get(&self, block_number: BlockNumber) -> Option<FeeSample<'_>> {
+        let index = usize::try_from(block_number.checked_sub(self.first_block)?).ok()?;
+        (index < self.len()).then(|| self.sample(index))
+    }
+
+    /// Returns an iterator over the fees of each block, in ascending order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = FeeSample<'_>> + '_ {
+        (0..self.len()).map(|index| self.sample(index))
+    }
+
+    /// Returns the rewards of each block at the percentile, if it was requested.
+    pub fn rewards_at(&self, percentile: f64) -> Option<impl Iterator<Item = u128> + '_> {
+        let position = self.percentiles.iter().position(|p| *p == percentile)?;
+        Some(self.rewards.chunks(self.percentiles.len()).map(move |rewards| rewards[position]))
+    }
+
+    fn sample(&self, index: usize) -> FeeSample<'_> {
+        let width = self.percentiles.len();
+        FeeSample {
+            block_number: self.first_block + index as u64,
+            base_fee_per_gas: self.base_fee_per_gas[index],
+            base_fee_per_blob_gas: self.base_fee_per_blob_gas[index],
+            gas_used_ratio: self.gas_used_ratio[index],
+            blob_gas_used_ratio: self.blob_gas_used_ratio[index],
+            rewards: &self.rewards[index * width..(index + 1) * width],
+        }
+    }
+
+    /// Appends the blocks of a fee history, which must start at the next block of the dataset.
+    fn append(&mut self, history: FeeHistory) -> TransportResult<()> {
+        let blocks = history.gas_used_ratio.len();
+        if history.oldest_block != self.first_block + self.len() as u64 {
+            return Err(RpcError::local_usage_str("fee history starts at an unexpected block"));
+        }
+        // the base fees include the block after the range, which is not part of the dataset
+        if history.base_fee_per_gas.len() < blocks {
+            return Err(RpcError::local_usage_str("fee history is missing base fees"));
+        }
+        self.base_fee_per_gas.extend(&history.base_fee_per_gas[..blocks]);
+        // blob fields are omitted by nodes before Cancun
+        self.base_fee_per_blob_gas.extend(
+            history.base_fee_per_blob_gas.iter().copied().chain(std::iter::repeat(0)).take(blocks),
+        );
+        self.blob_gas_used_ratio.extend(
+            history.blob_gas_used_ratio.iter().copied().chain(std::iter::repeat(0.0)).take(blocks),
+        );
+        self.gas_used_ratio.extend(history.gas_used_ratio);
+
+        if !self.percentiles.is_empty() {
+            let rewards = history.reward.unwrap_or_default();
+            if rewards.len() != blocks
+                || rewards.iter().any(|rewards| rewards.len() != self.percentiles.len())
+            {
+                return Err(RpcError::local_usage_str("fee history is missing rewards"));
+            }
+            self.rewards.extend(rewards.into_iter().flatten());
+        }
+        Ok(())
+    }
+}
+
+/// The fees of one block of a [`FeeDataset`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeeSample<'a> {
+    /// The block number.
+    pub block_number: BlockNumber,
+    /// The base fee per gas, 0 before London.
+    pub base_fee_per_gas: u128,
+    /// The base fee per blob gas, 0 before Cancun.
+    pub base_fee_per_blob_gas: u128,
+    /// The ratio of gas used to the gas limit.
+    pub gas_used_ratio: f64,
+    /// The ratio of blob gas used to the maximum blob gas, 0 before Cancun.
+    pub blob_gas_used_ratio: f64,
+    /// The priority fee per gas at each percentile of the dataset.
+    pub rewards: &'a [u128],
+}
+
+/// Collects the fee market of a range of blocks into a [`FeeDataset`], with `eth_feeHistory`.
+///
+/// The range is split into calls of [`with_chunk_size`](Self::with_chunk_size) blocks, sent with
+/// bounded concurrency. Nodes returning fewer blocks than requested, e.g. because of a lower
+/// limit, are asked again for the rest of the chunk.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example<P: alloy_provider::Provider>(provider: P) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::FeeDatasetBuilder;
+///
+/// let dataset = FeeDatasetBuilder::new(&provider, 20_000_000..=20_010_000)
+///     .with_percentiles(vec![10.0, 50.0, 90.0])
+///     .build()
+///     .await?;
+/// let median_tips = dataset.rewards_at(50.0).unwrap().collect::<Vec<_>>();
+/// # Ok(())
+/// # }
+/// ```
+pub struct FeeDatasetBuilder<'a, P, T, N> {
+    provider: &'a P,
+    blocks: RangeInclusive<BlockNumber>,
+    percentiles: Vec<f64>,
+    chunk_size: u64,
+    concurrency: usize,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> fmt::Debug for FeeDatasetBuilder<'_, P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeeDatasetBuilder")
+            .field("blocks", &self.blocks)
+            .field("percentiles", &self.percentiles)
+            .field("chunk_size", &self.chunk_size)
+            .field("concurrency", &self.concurrency)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, P, T, N> FeeDatasetBuilder<'a, P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a builder collecting the blocks, without rewards.
+    pub const fn new(provider: &'a P, blocks: RangeInclusive<BlockNumber>) -> Self {
+        Self {
+            provider,
+            blocks,
+            percentiles: Vec::new(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Sets the percentiles of the rewards, from 0 to 100 in ascending order.
+    pub fn with_percentiles(mut self, percentiles: Vec<f64>) -> Self {
+        self.percentiles = percentiles;
+        self
+    }
+
+    /// Sets the number of blocks requested in one call, 1024 by default.
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets the number of calls in flight, 4 by default.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Collects the dataset.
+    pub async fn build(self) -> TransportResult<FeeDataset> {
+        let (start, end) = self.blocks.clone().into_inner();
+        if start > end {
+            return Err(RpcError::local_usage_str("from block is after to block"));
+        }
+        if !self.percentiles.windows(2).all(|w| w[0] <= w[1])
+            || self.percentiles.iter().any(|p| !(0.0..=100.0).contains(p))
+        {
+            return Err(RpcError::local_usage_str("percentiles must be ascending from 0 to 100"));
+        }
+
+        let chunks = (start..=end).step_by(self.chunk_size as usize).map(|chunk_start| {
+            (chunk_start, chunk_start.saturating_add(self.chunk_size - 1).min(end))
+        });
+        let chunks = stream::iter(chunks)
+            .map(|(chunk_start, chunk_end)| self.fetch_chunk(chunk_start, chunk_end))
+            .buffered(self.concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut dataset = FeeDataset {
+            first_block: start,
+            percentiles: self.percentiles.clone(),
+            ..Default::default()
+        };
+        for history in chunks.into_iter().flatten() {
+            dataset.append(history)?;
+        }
+        Ok(dataset)
+    }
+
+    /// Fetches the fee histories covering the chunk, in ascending order.
+    async fn fetch_chunk(&self, start: u64, end: u64) -> TransportResult<Vec<FeeHistory>> {
+        let mut histories = Vec::new();
+        let mut last = end;
+        loop {
+            let history = self
+                .provider
+                .get_fee_history(
+                    last - start + 1,
+                    BlockNumberOrTag::Number(last),
+                    &self.percentiles,
+                )
+                .await?;
+            let oldest = history.oldest_block;
+            if history.gas_used_ratio.is_empty() || oldest < start || oldest > last {
+                return Err(RpcError::local_usage_str("fee history is not in the requested range"));
+            }
+            histories.push(history);
+            if oldest == start {
+                break;
+            }
+            last = oldest - 1;
+        }
+        histories.reverse();
+        Ok(histories)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+
+    fn history(oldest_block: u64, blocks: u64) -> FeeHistory {
+        FeeHistory {
+            base_fee_per_gas: (0..=blocks).map(|i| (oldest_block + i) as u128).collect(),
+            gas_used_ratio: vec![0.5; blocks as usize],
+            base_fee_per_blob_gas: Vec::new(),
+            blob_gas_used_ratio: Vec::new(),
+            oldest_block,
+            reward: Some((0..blocks).map(|i| vec![i as u128, 10 * i as u128]).collect()),
+        }
+    }
+
+    #[test]
+    fn appends_fee_histories() {
+        let mut dataset =
+            FeeDataset { first_block: 10, percentiles: vec![25.0, 75.0], ..Default::default() };
+        dataset.append(history(10, 2)).unwrap();
+        dataset.append(history(12, 3)).unwrap();
+        assert!(dataset.append(history(14, 1)).is_err());
+
+        assert_eq!(dataset.blocks(), 10..15);
+        assert_eq!(dataset.base_fee_per_gas, [10, 11, 12, 13, 14]);
+        assert_eq!(dataset.base_fee_per_blob_gas, [0; 5]);
+        let sample = dataset.get(13).unwrap();
+        assert_eq!(sample.rewards, [1, 10]);
+        assert_eq!(dataset.get(15), None);
+        assert_eq!(dataset.rewards_at(75.0).unwrap().collect::<Vec<_>>(), [0, 10, 0, 10, 20]);
+        assert_eq!(dataset.iter().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn builds_fee_dataset() {
+        let provider = ProviderBuilder::new().on_anvil();
+        provider.raw_request::<_, ()>("anvil_mine".into(), (10,)).await.unwrap();
+
+        let dataset = FeeDatasetBuilder::new(&provider, 1..=10)
+            .with_percentiles(vec![50.0])
+            .with_chunk_size(3)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(dataset.blocks(), 1..11);
+        assert_eq!(dataset.rewards.len(), 10);
+        assert!(dataset.iter().all(|sample| sample.base_fee_per_gas > 0));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::Provider;
+use alloy_json_rpc::RpcError;
+use alloy_network::Network;
+use alloy_primitives::{Address, BlockNumber, TxHash, U256};
+use alloy_transport::{Transport, TransportResult};
+use std::future::Future;
+
+/// A change of an account balance or storage slot between two consecutive blocks.
+///
+/// Returned by [`Provider::get_balance_changes`] and [`Provider::get_storage_changes`].
+///
+/// [`Provider::get_balance_changes`]: crate::Provider::get_balance_changes
+/// [`Provider::get_storage_changes`]: crate::Provider::get_storage_changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StateChange {
+    /// The block that changed the value.
+    pub block_number: BlockNumber,
+    /// The value after the previous block.
+    pub before: U256,
+    /// The value after this block.
+    pub after: U256,
+}
+
+impl StateChange {
+    /// Returns the signed difference `after - before`, as `(magnitude, is_negative)`.
+    pub fn delta(&self) -> (U256, bool) {
+        if self.after >= self.before {
+            (self.after - self.before, false)
+        } else {
+            (self.before - self.after, true)
+        }
+    }
+}
+
+/// The first funding of an account, the transfer that gave it a balance or created it.
+///
+/// Returned by [`Provider::get_funding`] and the funding helpers of the extension traits, which
+/// find the transaction and funder with different precision, see there.
+///
+/// [`Provider::get_funding`]: crate::Provider::get_funding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Funding {
+    /// The block of the funding.
+    pub block_number: BlockNumber,
+    /// The transaction of the funding, `None` for genesis allocations, block rewards and
+    /// withdrawals, or if it could not be identified.
+    pub transaction_hash: Option<TxHash>,
+    /// The account that sent the funds, `None` if it could not be identified.
+    pub funder: Option<Address>,
+    /// The value received.
+    pub value: U256,
+}
+
+/// The activity of an account over a range of blocks, as seen from its state.
+///
+/// Returned by [`Provider::get_address_activity`].
+///
+/// [`Provider::get_address_activity`]: crate::Provider::get_address_activity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressActivity {
+    /// The first block in which the account had a balance or a nonce.
+    pub first_seen: BlockNumber,
+    /// The block of the last transaction sent by the account, `None` if it sent none.
+    pub last_sent: Option<BlockNumber>,
+    /// The number of transactions sent by the account, or its contract creations, as of the
+    /// last block.
+    pub nonce: u64,
+}
+
+/// Finds the first block in `from_block..=to_block` for which `predicate` holds, by binary search,
+/// assuming it keeps holding in all later blocks.
+///
+/// Returns `None` if `predicate` does not hold at `to_block`.
+pub(crate) async fn first_block_where<F, Fut>(
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    predicate: F,
+) -> TransportResult<Option<BlockNumber>>
+where
+    F: Fn(BlockNumber) -> Fut,
+    Fut: Future<Output = TransportResult<bool>>,
+{
+    if from_block > to_block {
+        return Err(RpcError::local_usage_str("from block is after to block"));
+    }
+    if !predicate(to_block).await? {
+        return Ok(None);
+    }
+
+    let (mut lo, mut hi) = (from_block, to_block);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if predicate(mid).await? {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Ok(Some(lo))
+}
+
+/// Finds the first block in `from_block..=to_block` at which the account has a nonce or a
+/// balance, by binary search over the state at each block.
+pub(crate) async fn first_seen<P, T, N>(
+    provider: &P,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> TransportResult<Option<BlockNumber>>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    first_block_where(from_block, to_block, |block| async move {
+        let nonce = provider.get_transaction_count(address).block_id(block.into()).await?;
+        Ok(nonce > 0 || !provider.get_balance(address).block_id(block.into()).await?.is_zero())
+    })
+    .await
+}
+
+/// Finds some of the blocks in `from_block + 1..=to_block` that changed the value returned by
+/// `fetch`, by binary search over the state at each block.
+///
+/// Ranges with the same value at both ends are not searched, so a value that changes and then
+/// returns to its previous value within such a range is missed.
+pub(crate) async fn bisect_changes<F, Fut>(
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    fetch: F,
+) -> TransportResult<Vec<StateChange>>
+where
+    F: Fn(BlockNumber) -> Fut,
+    Fut: Future<Output = TransportResult<U256>>,
+{
+    if from_block > to_block {
+        return Err(RpcError::local_usage_str("from block is after to block"));
+    }
+
+    let mut changes = Vec::new();
+    let mut ranges = vec![(from_block, fetch(from_block).await?, to_block, fetch(to_block).await?)];
+    while let Some((lo, lo_value, hi, hi_value)) = ranges.pop() {
+        if lo_value == hi_value {
+            continue;
+        }
+        if hi - lo == 1 {
+            changes.push(StateChange { block_number: hi, before: lo_value, after: hi_value });
+            continue;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let mid_value = fetch(mid).await?;
+        // the lower half is pushed last so that changes are found in ascending order
+        ranges.push((mid, mid_value, hi, hi_value));
+        ranges.push((lo, lo_value, mid, mid_value));
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn finds_first_block() {
+        let requests = AtomicUsize::new(0);
+        let predicate = |threshold: BlockNumber| {
+            let requests = &requests;
+            move |block: BlockNumber| {
+                requests.fetch_add(1, Ordering::Relaxed);
+                async move { Ok(block >= threshold) }
+            }
+        };
+        assert_eq!(first_block_where(0, 1000, predicate(737)).await.unwrap(), Some(737));
+        assert!(requests.load(Ordering::Relaxed) <= 12);
+        assert_eq!(first_block_where(5, 1000, predicate(0)).await.unwrap(), Some(5));
+        assert_eq!(first_block_where(0, 1000, predicate(1001)).await.unwrap(), None);
+        assert!(first_block_where(2, 1, predicate(0)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn bisects_changes() {
+        // value after each block
+        let values = [5u64, 5, 5, 7, 7, 7, 7, 2, 2, 9, 9, 9, 9, 9, 9, 9];
+        let requests = AtomicUsize::new(0);
+        let fetch = |block: BlockNumber| {
+            requests.fetch_add(1, Ordering::SeqCst);
+            let value = U256::from(values[block as usize]);
+            async move { Ok(value) }
+        };
+
+        let changes = bisect_changes(0, 15, fetch).await.unwrap();
+        let expected = [(3, 5, 7), (7, 7, 2), (9, 2, 9)].map(|(block_number, before, after)| {
+            StateChange { block_number, before: U256::from(before), after: U256::from(after) }
+        });
+        assert_eq!(changes, expected);
+        assert!(requests.load(Ordering::SeqCst) < values.len());
+
+        assert_eq!(changes[1].delta(), (U256::from(5), true));
+        assert_eq!(changes[2].delta(), (U256::from(7), false));
+
+        assert!(bisect_changes(4, 6, fetch).await.unwrap().is_empty());
+        assert_eq!(bisect_changes(2, 3, fetch).await.unwrap(), expected[..1]);
+        assert!(bisect_changes(3, 2, fetch).await.is_err());
+    }
+}
@@ -0,0 +1,190 @@
+/// A node implementation, as detected from its `web3_clientVersion`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NodeKind {
+    /// [Geth](https://github.com/ethereum/go-ethereum).
+    Geth,
+    /// [Reth](https://github.com/paradigmxyz/reth).
+    Reth,
+    /// [Erigon](https://github.com/erigontech/erigon).
+    Erigon,
+    /// [Nethermind](https://github.com/NethermindEth/nethermind).
+    Nethermind,
+    /// [Besu](https://github.com/hyperledger/besu).
+    Besu,
+    /// [Anvil](https://github.com/foundry-rs/foundry).
+    Anvil,
+    /// An implementation that is not known.
+    Unknown,
+}
+
+impl NodeKind {
+    /// Detects the implementation from the name part of a client version, e.g. `Geth`.
+    fn from_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "geth" => Self::Geth,
+            "reth" => Self::Reth,
+            "erigon" => Self::Erigon,
+            "nethermind" => Self::Nethermind,
+            "besu" => Self::Besu,
+            "anvil" => Self::Anvil,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Returns the quirks of the implementation.
+    pub const fn quirks(self) -> &'static NodeQuirks {
+        match self {
+            Self::Geth => &GETH,
+            Self::Reth => &RETH,
+            Self::Erigon => &ERIGON,
+            Self::Nethermind => &NETHERMIND,
+            Self::Besu => &BESU,
+            Self::Anvil => &ANVIL,
+            Self::Unknown => &UNKNOWN,
+        }
+    }
+}
+
+/// The identity of a node, as reported by `web3_clientVersion`.
+///
+/// Returned by [`Provider::get_node_identity`](crate::Provider::get_node_identity).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeIdentity {
+    /// The detected implementation.
+    pub kind: NodeKind,
+    /// The version of the implementation, e.g. `1.13.14-stable-2bd6bd01`, if reported.
+    pub version: Option<String>,
+    /// The full client version string, e.g. `Geth/v1.13.14-stable-2bd6bd01/linux-amd64/go1.21.7`.
+    pub client_version: String,
+}
+
+impl NodeIdentity {
+    /// Parses a `web3_clientVersion` string of the form `name/version/...`.
+    pub fn parse(client_version: &str) -> Self {
+        let mut parts = client_version.split('/');
+        let kind = NodeKind::from_name(parts.next().unwrap_or_default().trim());
+        let version = parts
+            .next()
+            .map(|version| version.trim().trim_start_matches('v'))
+            .filter(|version| !version.is_empty())
+            .map(str::to_string);
+        Self { kind, version, client_version: client_version.to_string() }
+    }
+
+    /// Returns an identity for a node that did not report its version.
+    pub const fn unknown() -> Self {
+        Self { kind: NodeKind::Unknown, version: None, client_version: String::new() }
+    }
+
+    /// Returns the quirks of the node implementation.
+    pub const fn quirks(&self) -> &'static NodeQuirks {
+        self.kind.quirks()
+    }
+}
+
+/// Known differences in behavior between node implementations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NodeQuirks {
+    /// The namespaces the implementation serves, if known.
+    ///
+    /// This is only a hint: nodes can be configured with other namespaces, so
+    /// [`Provider::supports`](crate::Provider::supports) still probes methods of namespaces that
+    /// are not listed.
+    pub namespaces: Option<&'static [&'static str]>,
+}
+
+impl NodeQuirks {
+    /// Returns whether the implementation serves methods of the namespace, if known.
+    ///
+    /// This is only a hint, see [`NodeQuirks::namespaces`].
+    pub fn supports_namespace(&self, namespace: &str) -> Option<bool> {
+        self.namespaces.map(|namespaces| namespaces.contains(&namespace))
+    }
+}
+
+const GETH: NodeQuirks = NodeQuirks {
+    namespaces: Some(&[
+        "eth", "net", "web3", "debug", "txpool", "admin", "miner", "personal", "engine", "les",
+        "rpc",
+    ]),
+};
+
+const RETH: NodeQuirks = NodeQuirks {
+    namespaces: Some(&[
+        "eth", "net", "web3", "debug", "trace", "txpool", "admin", "ots", "reth", "engine", "rpc",
+    ]),
+};
+
+const ERIGON: NodeQuirks = NodeQuirks {
+    namespaces: Some(&[
+        "eth", "net", "web3", "debug", "trace", "txpool", "admin", "erigon", "ots", "parity",
+        "engine", "bor",
+    ]),
+};
+
+const NETHERMIND: NodeQuirks = NodeQuirks {
+    namespaces: Some(&[
+        "eth", "net", "web3", "debug", "trace", "txpool", "admin", "parity", "personal", "proof",
+        "engine", "health", "rpc",
+    ]),
+};
+
+const BESU: NodeQuirks = NodeQuirks {
+    namespaces: Some(&[
+        "eth", "net", "web3", "debug", "trace", "txpool", "admin", "miner", "clique", "ibft",
+        "qbft", "perm", "priv", "eea", "plugins", "engine", "rpc",
+    ]),
+};
+
+const ANVIL: NodeQuirks = NodeQuirks {
+    namespaces: Some(&[
+        "eth", "net", "web3", "debug", "trace", "txpool", "anvil", "hardhat", "evm", "ots",
+        "erigon", "personal",
+    ]),
+};
+
+const UNKNOWN: NodeQuirks = NodeQuirks { namespaces: None };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_client_versions() {
+        let cases = [
+            (
+                "Geth/v1.13.14-stable-2bd6bd01/linux-amd64/go1.21.7",
+                NodeKind::Geth,
+                "1.13.14-stable-2bd6bd01",
+            ),
+            ("reth/v1.0.5-603e39ab/x86_64-unknown-linux-gnu", NodeKind::Reth, "1.0.5-603e39ab"),
+            ("erigon/2.59.0/linux-amd64/go1.21.6", NodeKind::Erigon, "2.59.0"),
+            (
+                "Nethermind/v1.25.4+20b10b35/linux-x64/dotnet8.0.2",
+                NodeKind::Nethermind,
+                "1.25.4+20b10b35",
+            ),
+            ("besu/v24.1.2/linux-x86_64/openjdk-java-17", NodeKind::Besu, "24.1.2"),
+            ("anvil/v0.2.0", NodeKind::Anvil, "0.2.0"),
+        ];
+        for (client_version, kind, version) in cases {
+            let identity = NodeIdentity::parse(client_version);
+            assert_eq!(identity.kind, kind, "{client_version}");
+            assert_eq!(identity.version.as_deref(), Some(version), "{client_version}");
+            assert_eq!(identity.client_version, client_version);
+        }
+
+        let identity = NodeIdentity::parse("MyNode");
+        assert_eq!(identity.kind, NodeKind::Unknown);
+        assert_eq!(identity.version, None);
+    }
+
+    #[test]
+    fn quirk_profiles() {
+        assert_eq!(NodeKind::Geth.quirks().supports_namespace("trace"), Some(false));
+        assert_eq!(NodeKind::Erigon.quirks().supports_namespace("trace"), Some(true));
+        assert_eq!(NodeKind::Unknown.quirks().supports_namespace("trace"), None);
+    }
+}
@@ -0,0 +1,506 @@
+use crate::{Page, Paginated};
+use alloy_json_rpc::RpcError;
+use alloy_network_primitives::HeaderResponse;
+use alloy_primitives::{BlockHash, BlockNumber, U64};
+use alloy_rpc_client::{ClientRef, WeakClient};
+use alloy_rpc_types_eth::{BlockNumberOrTag, Filter, FilterBlockOption, Log};
+use alloy_transport::{Transport, TransportError, TransportErrorKind, TransportResult};
+use lru::LruCache;
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::trace;
+
+/// A stream of logs, see [`Provider::subscribe_logs_from`](crate::Provider::subscribe_logs_from).
+#[cfg(feature = "pubsub")]
+pub type LogStream = std::pin::Pin<Box<dyn futures::Stream<Item = TransportResult<Log>> + Send>>;
+
+/// Substrings of the messages of rate limit errors, in lowercase.
+const RATE_LIMIT_MESSAGES: &[&str] =
+    &["rate limit", "rate exceeded", "too many requests", "request limit", "credits"];
+
+/// Substrings of the messages of errors returned by nodes for `eth_getLogs` requests over too
+/// many blocks or with too many results, in lowercase.
+const LOG_LIMIT_MESSAGES: &[&str] = &[
+    "more than",
+    "too many",
+    "block range",
+    "range is too",
+    "range too",
+    "response size",
+    "limit exceeded",
+    "max results",
+    "exceed maximum",
+];
+
+/// A cache of the hashes of recent blocks, used by [`LogConsistency`] to cross-check the block
+/// hashes of logs.
+///
+/// The cache is filled by the caller, e.g. from a block subscription, and holds the most recently
+/// inserted blocks. Clones share the same cache.
+#[derive(Clone, Debug)]
+pub struct HeaderCache {
+    hashes: Arc<Mutex<LruCache<BlockNumber, BlockHash>>>,
+}
+
+impl HeaderCache {
+    /// Creates a cache holding the hashes of up to `capacity` blocks.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self { hashes: Arc::new(Mutex::new(LruCache::new(capacity))) }
+    }
+
+    /// Inserts the hash of the block, replacing the previous hash at its height, e.g. after a
+    /// reorg.
+    pub fn insert(&self, number: BlockNumber, hash: BlockHash) {
+        self.hashes.lock().unwrap().put(number, hash);
+    }
+
+    /// Inserts the hash of the block of the header.
+    pub fn insert_header<H: HeaderResponse>(&self, header: &H) {
+        self.insert(header.number(), header.hash());
+    }
+
+    /// Returns the cached hash of the block at the height.
+    pub fn get(&self, number: BlockNumber) -> Option<BlockHash> {
+        self.hashes.lock().unwrap().peek(&number).copied()
+    }
+
+    /// Returns the number of cached blocks.
+    pub fn len(&self) -> usize {
+        self.hashes.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no blocks are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// An inconsistency in the logs returned by `eth_getLogs`, detected by [`LogConsistency`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum LogConsistencyError {
+    /// A log is missing its block number, block hash, transaction index or log index, e.g. a
+    /// pending log.
+    #[error("log {index} of the response is missing its position in the chain")]
+    MissingPosition {
+        /// The index of the log in the response.
+        index: usize,
+    },
+    /// The logs are not ordered by block number and log index.
+    #[error("log {log_index} of block {block_number} is out of order")]
+    OutOfOrder {
+        /// The block number of the log.
+        block_number: BlockNumber,
+        /// The log index of the log.
+        log_index: u64,
+    },
+    /// A log index appears more than once in a block.
+    #[error("log index {log_index} of block {block_number} is duplicated")]
+    DuplicateLogIndex {
+        /// The block number of the logs.
+        block_number: BlockNumber,
+        /// The duplicated log index.
+        log_index: u64,
+    },
+    /// The transaction indices of a block decrease while its log indices increase.
+    #[error("transaction index of log {log_index} of block {block_number} decreases")]
+    NonMonotonicTransactionIndex {
+        /// The block number of the log.
+        block_number: BlockNumber,
+        /// The log index of the log.
+        log_index: u64,
+    },
+    /// The logs of a block have different block hashes, e.g. because the response spans a reorg.
+    #[error("logs of block {block_number} have different block hashes")]
+    InconsistentBlockHash {
+        /// The block number of the logs.
+        block_number: BlockNumber,
+    },
+    /// The block hash of a log does not match the [`HeaderCache`].
+    #[error(
+        "block hash {found} of block {block_number} does not match the cached hash {expected}"
+    )]
+    BlockHashMismatch {
+        /// The block number of the log.
+        block_number: BlockNumber,
+        /// The cached block hash.
+        expected: BlockHash,
+        /// The block hash of the log.
+        found: BlockHash,
+    },
+}
+
+/// Ordering and consistency checks of the logs returned by `eth_getLogs`.
+///
+/// By default, logs are sorted by `(blockNumber, logIndex)`, since some backends return them out
+/// of order, and are then validated:
+/// - every log has a block number, block hash, transaction index and log index,
+/// - no log index appears twice in a block,
+/// - transaction indices do not decrease within a block,
+/// - all logs of a block have the same block hash, which matches the [`HeaderCache`], if any.
+///
+/// Responses failing any check are rejected with a [`LogConsistencyError`], rather than passed
+/// through. Used by [`Provider::get_logs_strict`](crate::Provider::get_logs_strict).
+#[derive(Clone, Debug, Default)]
+pub struct LogConsistency {
+    require_sorted: bool,
+    headers: Option<HeaderCache>,
+}
+
+impl LogConsistency {
+    /// Creates the default checks, which sort the logs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects logs that are not already sorted, instead of sorting them.
+    pub const fn require_sorted(mut self) -> Self {
+        self.require_sorted = true;
+        self
+    }
+
+    /// Cross-checks the block hashes of the logs against the cache.
+    pub fn with_header_cache(mut self, headers: HeaderCache) -> Self {
+        self.headers = Some(headers);
+        self
+    }
+
+    /// Sorts and validates the logs.
+    pub fn check(&self, mut logs: Vec<Log>) -> Result<Vec<Log>, LogConsistencyError> {
+        for (index, log) in logs.iter().enumerate() {
+            if position(log).is_none() || log.block_hash.is_none() {
+                return Err(LogConsistencyError::MissingPosition { index });
+            }
+        }
+
+        let key =
+            |log: &Log| position(log).map(|(block_number, log_index, _)| (block_number, log_index));
+        if self.require_sorted {
+            if let Some(pair) = logs.windows(2).find(|pair| key(&pair[0]) > key(&pair[1])) {
+                let (block_number, log_index, _) = position(&pair[1]).unwrap();
+                return Err(LogConsistencyError::OutOfOrder { block_number, log_index });
+            }
+        } else {
+            // stable, so that duplicates keep their order
+            logs.sort_by_key(key);
+        }
+
+        for pair in logs.windows(2) {
+            let (prev_block, prev_index, prev_tx) = position(&pair[0]).unwrap();
+            let (block_number, log_index, tx_index) = position(&pair[1]).unwrap();
+            if block_number != prev_block {
+                continue;
+            }
+            if log_index == prev_index {
+                return Err(LogConsistencyError::DuplicateLogIndex { block_number, log_index });
+            }
+            if tx_index < prev_tx {
+                return Err(LogConsistencyError::NonMonotonicTransactionIndex {
+                    block_number,
+                    log_index,
+                });
+            }
+            if pair[0].block_hash != pair[1].block_hash {
+                return Err(LogConsistencyError::InconsistentBlockHash { block_number });
+            }
+        }
+
+        if let Some(headers) = &self.headers {
+            for log in &logs {
+                let (block_number, ..) = position(log).unwrap();
+                let found = log.block_hash.unwrap();
+                match headers.get(block_number) {
+                    Some(expected) if expected != found => {
+                        return Err(LogConsistencyError::BlockHashMismatch {
+                            block_number,
+                            expected,
+                            found,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(logs)
+    }
+}
+
+/// Returns `true` if the error is a node refusing an `eth_getLogs` request because of its block
+/// range or number of results, so that the request succeeds over a smaller range.
+pub(crate) fn is_log_limit_err(err: &TransportError) -> bool {
+    let Some(payload) = err.as_error_resp() else { return false };
+    let message = payload.message.to_lowercase();
+    // rate limits share error codes with log limits, e.g. -32005 on Infura
+    if RATE_LIMIT_MESSAGES.iter().any(|limit| message.contains(limit)) {
+        return false;
+    }
+    LOG_LIMIT_MESSAGES.iter().any(|limit| message.contains(limit))
+}
+
+/// Paginates `eth_getLogs` over the block range of the filter, see
+/// [`Provider::get_logs_paginated`](crate::Provider::get_logs_paginated).
+pub(crate) fn paginate_logs<T: Transport + Clone>(
+    client: WeakClient<T>,
+    filter: Filter,
+    page_size: u64,
+) -> Paginated<Log, BlockNumber> {
+    // shared by all pages, so that later pages start with the range that last succeeded
+    let span = Arc::new(AtomicU64::new(page_size.max(1)));
+    let last_block = Arc::new(Mutex::new(None));
+    Paginated::new(move |cursor: Option<BlockNumber>| {
+        let client = client.upgrade();
+        let filter = filter.clone();
+        let span = span.clone();
+        let last_block = last_block.clone();
+        Box::pin(async move {
+            let client = client.ok_or_else(TransportErrorKind::backend_gone)?;
+            let FilterBlockOption::Range { from_block, to_block } = filter.block_option else {
+                return Ok(Page::last(client.request("eth_getLogs", (&filter,)).await?));
+            };
+
+            let from = match cursor {
+                Some(from) => from,
+                None => resolve_block(&client, from_block).await?,
+            };
+            // the end of the range is resolved once, so that the pages end at the same tip
+            let cached = *last_block.lock().unwrap();
+            let end = match cached {
+                Some(end) => end,
+                None => {
+                    let end = resolve_block(&client, to_block).await?;
+                    *last_block.lock().unwrap() = Some(end);
+                    end
+                }
+            };
+            if from > end {
+                return Ok(Page::last(Vec::new()));
+            }
+
+            loop {
+                let to = end.min(from.saturating_add(span.load(Ordering::Relaxed) - 1));
+                let page = filter.clone().from_block(from).to_block(to);
+                match client.request("eth_getLogs", (&page,)).await {
+                    Ok(logs) => return Ok(Page::new(logs, (to < end).then_some(to + 1))),
+                    Err(err) if to > from && is_log_limit_err(&err) => {
+                        trace!(from, to, %err, "eth_getLogs limit exceeded, halving the range");
+                        span.store((to - from).div_ceil(2), Ordering::Relaxed);
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        })
+    })
+}
+
+/// Resolves the block of a filter range to its number, `None` being the latest block.
+async fn resolve_block<T: Transport + Clone>(
+    client: ClientRef<'_, T>,
+    block: Option<BlockNumberOrTag>,
+) -> TransportResult<BlockNumber> {
+    #[derive(Debug, serde::Deserialize)]
+    struct NumberOnly {
+        number: U64,
+    }
+
+    match block.unwrap_or_default() {
+        BlockNumberOrTag::Number(number) => Ok(number),
+        BlockNumberOrTag::Earliest => Ok(0),
+        BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => {
+            client.request_noparams::<U64>("eth_blockNumber").await.map(|number| number.to())
+        }
+        tag => {
+            let block: Option<NumberOnly> =
+                client.request("eth_getBlockByNumber", (tag, false)).await?;
+            let block = block.ok_or_else(|| {
+                RpcError::local_usage_str(&format!("{tag} block of the range not found"))
+            })?;
+            Ok(block.number.to())
+        }
+    }
+}
+
+/// Streams the historical logs, then the live logs of blocks after `tip` and removed logs.
+#[cfg(feature = "pubsub")]
+pub(crate) fn backfill_then_live(
+    historical: impl futures::Stream<Item = TransportResult<Log>> + Send + 'static,
+    live: impl futures::Stream<Item = Log> + Send + 'static,
+    tip: BlockNumber,
+) -> LogStream {
+    use futures::StreamExt;
+
+    // logs of the backfilled blocks may also be received from the subscription
+    let live = live.filter(move |log| {
+        std::future::ready(log.removed || !matches!(log.block_number, Some(n) if n <= tip))
+    });
+    Box::pin(historical.chain(live.map(Ok)))
+}
+
+/// Returns the `(block_number, log_index, transaction_index)` of the log.
+fn position(log: &Log) -> Option<(BlockNumber, u64, u64)> {
+    Some((log.block_number?, log.log_index?, log.transaction_index?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload};
+    use alloy_primitives::B256;
+    use alloy_rpc_client::RpcClient;
+    use alloy_transport::TransportFut;
+    use futures::StreamExt;
+    use serde_json::value::{to_raw_value, RawValue};
+
+    fn log(block_number: u64, transaction_index: u64, log_index: u64) -> Log {
+        Log {
+            block_number: Some(block_number),
+            block_hash: Some(B256::with_last_byte(block_number as u8)),
+            transaction_index: Some(transaction_index),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sorts_and_validates_logs() {
+        let checks = LogConsistency::new();
+        let logs = vec![log(2, 0, 0), log(1, 1, 3), log(1, 0, 1)];
+        let sorted = checks.check(logs.clone()).unwrap();
+        assert_eq!(sorted, vec![log(1, 0, 1), log(1, 1, 3), log(2, 0, 0)]);
+        assert_eq!(
+            checks.clone().require_sorted().check(logs),
+            Err(LogConsistencyError::OutOfOrder { block_number: 1, log_index: 3 })
+        );
+
+        assert_eq!(
+            checks.check(vec![log(1, 0, 1), log(1, 1, 1)]),
+            Err(LogConsistencyError::DuplicateLogIndex { block_number: 1, log_index: 1 })
+        );
+        assert_eq!(
+            checks.check(vec![log(1, 1, 1), log(1, 0, 2)]),
+            Err(LogConsistencyError::NonMonotonicTransactionIndex {
+                block_number: 1,
+                log_index: 2
+            })
+        );
+
+        let mut reorged = log(1, 0, 2);
+        reorged.block_hash = Some(B256::repeat_byte(0xff));
+        assert_eq!(
+            checks.check(vec![log(1, 0, 1), reorged]),
+            Err(LogConsistencyError::InconsistentBlockHash { block_number: 1 })
+        );
+
+        let pending = Log { log_index: Some(0), ..Default::default() };
+        assert_eq!(
+            checks.check(vec![log(1, 0, 0), pending]),
+            Err(LogConsistencyError::MissingPosition { index: 1 })
+        );
+    }
+
+    #[test]
+    fn cross_checks_block_hashes() {
+        let headers = HeaderCache::new(NonZeroUsize::new(2).unwrap());
+        let checks = LogConsistency::new().with_header_cache(headers.clone());
+        headers.insert(1, B256::with_last_byte(1));
+        assert!(checks.check(vec![log(1, 0, 0), log(2, 0, 0)]).is_ok());
+
+        headers.insert(2, B256::repeat_byte(0xff));
+        assert_eq!(
+            checks.check(vec![log(1, 0, 0), log(2, 0, 0)]),
+            Err(LogConsistencyError::BlockHashMismatch {
+                block_number: 2,
+                expected: B256::repeat_byte(0xff),
+                found: B256::with_last_byte(2),
+            })
+        );
+
+        // block 1 is evicted
+        headers.insert(3, B256::with_last_byte(3));
+        assert_eq!(headers.len(), 2);
+        assert!(headers.get(1).is_none());
+    }
+
+    /// A node at block 19, refusing `eth_getLogs` over more than 4 blocks, with a log per block.
+    fn limited_node(requests: Arc<Mutex<Vec<(u64, u64)>>>) -> RpcClient<impl Transport + Clone> {
+        let transport = tower::service_fn(move |request: RequestPacket| -> TransportFut<'static> {
+            let RequestPacket::Single(request) = request else { unreachable!() };
+            let payload = match request.method() {
+                "eth_blockNumber" => {
+                    ResponsePayload::Success(to_raw_value(&U64::from(19)).unwrap())
+                }
+                "eth_getLogs" => {
+                    let params = request.params().unwrap().get();
+                    let (filter,): (Filter,) = serde_json::from_str(params).unwrap();
+                    let (from, to) =
+                        (filter.get_from_block().unwrap(), filter.get_to_block().unwrap());
+                    requests.lock().unwrap().push((from, to));
+                    if to - from >= 4 {
+                        ResponsePayload::Failure(ErrorPayload {
+                            code: -32005,
+                            message: "query returned more than 10000 results".into(),
+                            data: None,
+                        })
+                    } else {
+                        let logs = (from..=to).map(|n| log(n, 0, 0)).collect::<Vec<_>>();
+                        ResponsePayload::Success(to_raw_value(&logs).unwrap())
+                    }
+                }
+                method => panic!("unexpected method {method}"),
+            };
+            let response = Response::<Box<RawValue>> { id: request.id().clone(), payload };
+            Box::pin(async move { Ok(ResponsePacket::Single(response)) })
+        });
+        RpcClient::new(transport, true)
+    }
+
+    #[tokio::test]
+    async fn paginates_and_bisects_logs() {
+        let requests = Arc::default();
+        let client = limited_node(Arc::clone(&requests));
+        let filter = Filter::new().from_block(3);
+        let logs = paginate_logs(client.get_weak(), filter, 10).collect_all().await.unwrap();
+        assert_eq!(
+            logs.iter().map(|log| log.block_number.unwrap()).collect::<Vec<_>>(),
+            (3..=19).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                (3, 12),
+                (3, 7),
+                (3, 4),
+                (5, 6),
+                (7, 8),
+                (9, 10),
+                (11, 12),
+                (13, 14),
+                (15, 16),
+                (17, 18),
+                (19, 19)
+            ]
+        );
+
+        // a single block is not split
+        let filter = Filter::new().from_block(0).to_block(19);
+        let mut logs = std::pin::pin!(paginate_logs(client.get_weak(), filter, 1).into_stream());
+        assert_eq!(logs.next().await.unwrap().unwrap().block_number, Some(0));
+    }
+
+    #[test]
+    fn detects_log_limit_errors() {
+        let err = |code, message: &'static str| {
+            TransportError::ErrorResp(ErrorPayload { code, message: message.into(), data: None })
+        };
+        assert!(is_log_limit_err(&err(-32005, "query returned more than 10000 results")));
+        assert!(is_log_limit_err(&err(-32602, "eth_getLogs block range is too wide")));
+        assert!(is_log_limit_err(&err(-32000, "Log response size exceeded.")));
+        assert!(!is_log_limit_err(&err(-32000, "header not found")));
+        assert!(!is_log_limit_err(&err(429, "rate limit exceeded")));
+        assert!(!is_log_limit_err(&err(-32005, "project ID request rate exceeded")));
+    }
+}
@@ -1,9 +1,54 @@
+mod at_block;
+pub use at_block::AtBlock;
+
+mod call_graph;
+pub use call_graph::{CallGraph, CallGraphMode, CallHandle, CallId, CallOutputs};
+
+mod capabilities;
+pub(crate) use capabilities::is_method_not_found;
+pub use capabilities::Capabilities;
+
+pub(crate) mod code;
+pub use code::{
+    CodeKind, CodeMetadata, EIP1822_PROXIABLE_SLOT, EIP1967_BEACON_SLOT,
+    EIP1967_IMPLEMENTATION_SLOT, EIP7702_DELEGATION_PREFIX,
+};
+
+mod fees;
+pub use fees::{FeeDataset, FeeDatasetBuilder, FeeSample};
+
+pub(crate) mod history;
+pub use history::{AddressActivity, Funding, StateChange};
+
+pub(crate) mod logs;
+#[cfg(feature = "pubsub")]
+pub use logs::LogStream;
+pub use logs::{HeaderCache, LogConsistency, LogConsistencyError};
+
+pub(crate) mod multicall;
+pub use multicall::{TokenAllowance, MULTICALL3_ADDRESS};
+
+mod simulation;
+pub use simulation::{Divergence, Execution, ExecutionComparer, ExecutionDiff, PreState};
+
+mod storage;
+pub use storage::{array_slot, mapping_slot, StorageReader};
+
+mod tokens;
+pub use tokens::{TokenMetadata, TokenMetadataResolver};
+
+mod identity;
+pub use identity::{NodeIdentity, NodeKind, NodeQuirks};
+
 mod eth_call;
 pub use eth_call::{EthCall, EthCallParams};
 
 mod prov_call;
 pub use prov_call::ProviderCall;
 
+mod revert;
+pub use revert::RevertReason;
+
 mod root;
 pub use root::{builder, RootProvider};
 
@@ -13,6 +58,9 @@ pub use sendable::SendableTx;
 mod r#trait;
 pub use r#trait::{FilterPollerBuilder, Provider};
 
+mod verify;
+pub use verify::{BlockMismatch, BlockVerification};
+
 mod wallet;
 pub use wallet::WalletProvider;
 
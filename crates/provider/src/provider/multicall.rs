@@ -0,0 +1,223 @@
+use crate::Provider;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_network_primitives::{BlockResponse, HeaderResponse};
+use alloy_primitives::{address, Address, Bytes, U256};
+use alloy_rpc_types_eth::BlockId;
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport::{Transport, TransportResult};
+use futures::future::try_join_all;
+use std::future::Future;
+
+sol! {
+    #[allow(missing_docs)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+        struct Call3Result {
+            bool success;
+            bytes returnData;
+        }
+        function aggregate3(Call3[] calldata calls) external payable returns (Call3Result[] memory returnData);
+    }
+
+    #[allow(missing_docs)]
+    interface IERC20Snapshot {
+        function balanceOf(address owner) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+    }
+}
+
+/// The address of the [Multicall3](https://github.com/mds1/multicall) contract, which is deployed
+/// at the same address on most chains.
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// The maximum number of calls aggregated in a single `eth_call`, which keeps the calls well below
+/// the gas and response size limits of common nodes.
+pub(crate) const MULTICALL_CHUNK_SIZE: usize = 500;
+
+/// An ERC-20 allowance, returned by [`Provider::get_allowances`](crate::Provider::get_allowances).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenAllowance {
+    /// The token.
+    pub token: Address,
+    /// The spender.
+    pub spender: Address,
+    /// The allowance, `None` if the token call failed or returned malformed data.
+    pub allowance: Option<U256>,
+}
+
+/// Returns the calls of `balanceOf(holder)` on each token.
+pub(crate) fn balance_calls(holder: Address, tokens: &[Address]) -> Vec<IMulticall3::Call3> {
+    let call_data: Bytes = IERC20Snapshot::balanceOfCall { owner: holder }.abi_encode().into();
+    tokens
+        .iter()
+        .map(|&target| IMulticall3::Call3 {
+            target,
+            allowFailure: true,
+            callData: call_data.clone(),
+        })
+        .collect()
+}
+
+/// Returns the calls of `allowance(owner, spender)` on each token, for each spender, ordered by
+/// token.
+pub(crate) fn allowance_calls(
+    owner: Address,
+    spenders: &[Address],
+    tokens: &[Address],
+) -> Vec<IMulticall3::Call3> {
+    tokens
+        .iter()
+        .flat_map(|&target| {
+            spenders.iter().map(move |&spender| IMulticall3::Call3 {
+                target,
+                allowFailure: true,
+                callData: IERC20Snapshot::allowanceCall { owner, spender }.abi_encode().into(),
+            })
+        })
+        .collect()
+}
+
+/// Decodes the `uint256` returned by a token call, `None` if the call failed.
+pub(crate) fn decode_amount(output: Option<Bytes>) -> Option<U256> {
+    IERC20Snapshot::balanceOfCall::abi_decode_returns(&output?, false).ok().map(|ret| ret._0)
+}
+
+/// Executes the calls through Multicall3, in chunks of [`MULTICALL_CHUNK_SIZE`] calls executed
+/// concurrently with `call`, which sends an `eth_call` to Multicall3 with the input.
+///
+/// Returns the output of each call, `None` if it reverted.
+pub(crate) async fn aggregate<F, Fut>(
+    calls: Vec<IMulticall3::Call3>,
+    call: F,
+) -> TransportResult<Vec<Option<Bytes>>>
+where
+    F: Fn(Bytes) -> Fut,
+    Fut: Future<Output = TransportResult<Bytes>>,
+{
+    let chunks = calls.chunks(MULTICALL_CHUNK_SIZE).map(|chunk| {
+        let input = IMulticall3::aggregate3Call { calls: chunk.to_vec() }.abi_encode();
+        let output = call(input.into());
+        async move {
+            let output = output.await?;
+            if output.is_empty() {
+                return Err(RpcError::local_usage_str("Multicall3 is not deployed"));
+            }
+            let results = IMulticall3::aggregate3Call::abi_decode_returns(&output, true)
+                .map_err(|err| RpcError::local_usage_str(&err.to_string()))?
+                .returnData;
+            if results.len() != chunk.len() {
+                return Err(RpcError::local_usage_str(
+                    "Multicall3 returned the wrong number of results",
+                ));
+            }
+            Ok(results)
+        }
+    });
+
+    let results = try_join_all(chunks).await?;
+    Ok(results
+        .into_iter()
+        .flatten()
+        .map(|result| result.success.then_some(result.returnData))
+        .collect())
+}
+
+/// Executes the calls through Multicall3 with `eth_call` at the block, see [`aggregate`].
+///
+/// If the calls are split into several chunks, a block tag is first resolved to its number, so
+/// that all chunks read the same state.
+pub(crate) async fn aggregate_at<P, T, N>(
+    provider: &P,
+    calls: Vec<IMulticall3::Call3>,
+    block: BlockId,
+) -> TransportResult<Vec<Option<Bytes>>>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    let block = match block {
+        BlockId::Number(tag) if !tag.is_number() && calls.len() > MULTICALL_CHUNK_SIZE => {
+            let block = provider
+                .get_block_by_number(tag, false)
+                .await?
+                .ok_or_else(|| RpcError::local_usage_str("block not found"))?;
+            BlockId::number(block.header().number())
+        }
+        block => block,
+    };
+    aggregate(calls, |input| async move {
+        let tx = N::TransactionRequest::default().with_to(MULTICALL3_ADDRESS).with_input(input);
+        provider.call(&tx).block(block).await
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolValue;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Mimics Multicall3 and tokens where the balance of `holder` is the last byte of the token
+    /// address, and tokens with a zero last byte revert.
+    fn execute(input: Bytes) -> Bytes {
+        let calls = IMulticall3::aggregate3Call::abi_decode(&input, true).unwrap().calls;
+        let results = calls
+            .into_iter()
+            .map(|call| {
+                let balance = call.target.0[19];
+                IMulticall3::Call3Result {
+                    success: balance != 0,
+                    returnData: U256::from(balance).abi_encode().into(),
+                }
+            })
+            .collect::<Vec<_>>();
+        IMulticall3::aggregate3Call::abi_encode_returns(&(results,)).into()
+    }
+
+    #[tokio::test]
+    async fn aggregates_in_chunks() {
+        let tokens = (0..=MULTICALL_CHUNK_SIZE as u64 + 1)
+            .map(|i| Address::with_last_byte((i % 256) as u8))
+            .collect::<Vec<_>>();
+        let requests = AtomicUsize::new(0);
+        let balances = aggregate(balance_calls(Address::ZERO, &tokens), |input| {
+            requests.fetch_add(1, Ordering::Relaxed);
+            async move { Ok(execute(input)) }
+        })
+        .await
+        .unwrap()
+        .into_iter()
+        .map(decode_amount)
+        .collect::<Vec<_>>();
+
+        assert_eq!(requests.load(Ordering::Relaxed), 2);
+        assert_eq!(balances.len(), tokens.len());
+        assert_eq!(balances[0], None);
+        assert_eq!(balances[1], Some(U256::from(1)));
+        assert_eq!(balances[MULTICALL_CHUNK_SIZE + 1], Some(U256::from(245)));
+
+        let err = aggregate(balance_calls(Address::ZERO, &tokens), |_| async { Ok(Bytes::new()) })
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "local usage error: Multicall3 is not deployed");
+    }
+
+    #[test]
+    fn orders_allowance_calls_by_token() {
+        let tokens = [Address::with_last_byte(1), Address::with_last_byte(2)];
+        let spenders = [Address::with_last_byte(3), Address::with_last_byte(4)];
+        let calls = allowance_calls(Address::ZERO, &spenders, &tokens);
+        let targets = calls.iter().map(|call| call.target).collect::<Vec<_>>();
+        assert_eq!(targets, [tokens[0], tokens[0], tokens[1], tokens[1]]);
+
+        let call = IERC20Snapshot::allowanceCall::abi_decode(&calls[1].callData, true).unwrap();
+        assert_eq!(call.spender, spenders[1]);
+    }
+}
@@ -0,0 +1,131 @@
+use alloy_json_rpc::is_revert_message;
+use alloy_primitives::{Bytes, TxHash};
+use alloy_sol_types::{decode_revert_reason, ContractError, GenericContractError, SolInterface};
+
+/// The revert data of a failed transaction, as returned by
+/// [`Provider::get_revert_reason`](crate::Provider::get_revert_reason).
+///
+/// The raw revert data can be decoded into the standard `Error(string)` and `Panic(uint256)`
+/// errors with [`RevertReason::decode`], or into a set of custom errors with
+/// [`RevertReason::decode_custom`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevertReason {
+    /// The hash of the replayed transaction.
+    pub transaction_hash: TxHash,
+    /// The raw revert data. Empty if the transaction reverted without data.
+    pub data: Bytes,
+}
+
+impl RevertReason {
+    /// Creates a new [`RevertReason`] for the given transaction hash and revert data.
+    pub const fn new(transaction_hash: TxHash, data: Bytes) -> Self {
+        Self { transaction_hash, data }
+    }
+
+    /// Returns the revert of a traced call frame with the given error and output, or `None` if the
+    /// frame did not fail with a revert, e.g. if it ran out of gas or hit an invalid opcode.
+    pub(crate) fn from_frame(
+        transaction_hash: TxHash,
+        error: Option<&str>,
+        output: Option<Bytes>,
+    ) -> Option<Self> {
+        error
+            .filter(|error| is_revert_message(error))
+            .map(|_| Self::new(transaction_hash, output.unwrap_or_default()))
+    }
+
+    /// Returns `true` if the transaction reverted without any revert data.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Decodes the revert data into a standard `Error(string)` or `Panic(uint256)` error.
+    ///
+    /// Returns `None` if the data is not a standard Solidity error.
+    pub fn decode(&self) -> Option<GenericContractError> {
+        GenericContractError::abi_decode(&self.data, false).ok()
+    }
+
+    /// Decodes the revert data into the given set of custom errors, falling back to the standard
+    /// `Error(string)` and `Panic(uint256)` errors.
+    ///
+    /// Returns `None` if the data does not match any of the errors.
+    pub fn decode_custom<E: SolInterface>(&self) -> Option<ContractError<E>> {
+        ContractError::abi_decode(&self.data, false).ok()
+    }
+
+    /// Returns a human-readable reason for the revert, if one can be decoded.
+    ///
+    /// This handles `Error(string)`, `Panic(uint256)` and raw UTF-8 revert data.
+    pub fn reason(&self) -> Option<String> {
+        decode_revert_reason(&self.data).filter(|reason| !reason.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{hex, U256};
+    use alloy_sol_types::{sol, Panic, PanicKind, Revert, SolError};
+
+    sol! {
+        #[derive(Debug, PartialEq, Eq)]
+        interface Errors {
+            error InsufficientBalance(uint256 available, uint256 required);
+        }
+    }
+
+    #[test]
+    fn decode_standard_errors() {
+        let revert = RevertReason::new(TxHash::ZERO, Revert::from("not owner").abi_encode().into());
+        assert_eq!(revert.reason().as_deref(), Some("revert: not owner"));
+        assert_eq!(revert.decode(), Some(ContractError::Revert(Revert::from("not owner"))));
+
+        let panic = RevertReason::new(
+            TxHash::ZERO,
+            Panic::from(PanicKind::DivisionByZero as u64).abi_encode().into(),
+        );
+        assert!(panic.decode().unwrap().as_panic().is_some());
+        assert!(!panic.is_empty());
+    }
+
+    #[test]
+    fn decode_custom_errors() {
+        let err = Errors::InsufficientBalance { available: U256::from(1), required: U256::from(2) };
+        let revert = RevertReason::new(TxHash::ZERO, err.abi_encode().into());
+        assert_eq!(revert.decode(), None);
+
+        let decoded = revert.decode_custom::<Errors::ErrorsErrors>().unwrap();
+        assert_eq!(
+            decoded,
+            ContractError::CustomError(Errors::ErrorsErrors::InsufficientBalance(err))
+        );
+    }
+
+    #[test]
+    fn traced_frames() {
+        let data = Bytes::from(Revert::from("not owner").abi_encode());
+        let revert =
+            RevertReason::from_frame(TxHash::ZERO, Some("execution reverted"), Some(data.clone()));
+        assert_eq!(revert, Some(RevertReason::new(TxHash::ZERO, data)));
+
+        // other failures are not reverts
+        assert_eq!(RevertReason::from_frame(TxHash::ZERO, Some("out of gas"), None), None);
+        assert_eq!(
+            RevertReason::from_frame(TxHash::ZERO, Some("invalid opcode: INVALID"), None),
+            None
+        );
+        assert_eq!(RevertReason::from_frame(TxHash::ZERO, None, Some(Bytes::new())), None);
+    }
+
+    #[test]
+    fn empty_revert() {
+        let revert = RevertReason::new(TxHash::ZERO, Bytes::new());
+        assert!(revert.is_empty());
+        assert_eq!(revert.decode(), None);
+        assert_eq!(revert.reason(), None);
+
+        let raw = RevertReason::new(TxHash::ZERO, hex!("deadbeef").into());
+        assert_eq!(raw.decode(), None);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     blocks::NewBlocks,
     heart::{Heartbeat, HeartbeatHandle},
-    Identity, ProviderBuilder,
+    Capabilities, Identity, ProviderBuilder,
 };
 use alloy_network::{Ethereum, Network};
 use alloy_rpc_client::{BuiltInConnectionString, ClientBuilder, ClientRef, RpcClient, WeakClient};
@@ -99,6 +99,15 @@ impl<T: Transport + Clone, N: Network> RootProvider<T, N> {
         self.pubsub_frontend()?.unsubscribe(id)
     }
 
+    /// Returns a stream of the connection events of the pubsub transport, e.g. disconnections and
+    /// reconnections.
+    #[cfg(feature = "pubsub")]
+    pub fn connection_events(
+        &self,
+    ) -> alloy_transport::TransportResult<alloy_pubsub::ConnectionEvents> {
+        self.pubsub_frontend().map(PubSubFrontend::connection_events)
+    }
+
     #[cfg(feature = "pubsub")]
     pub(crate) fn pubsub_frontend(&self) -> alloy_transport::TransportResult<&PubSubFrontend> {
         self.inner
@@ -107,6 +116,13 @@ impl<T: Transport + Clone, N: Network> RootProvider<T, N> {
             .ok_or_else(alloy_transport::TransportErrorKind::pubsub_unavailable)
     }
 
+    /// Returns the capabilities of the endpoint discovered so far.
+    ///
+    /// See [`Provider::supports`](crate::Provider::supports).
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.inner.capabilities
+    }
+
     #[inline]
     pub(crate) fn get_heart(&self) -> &HeartbeatHandle<N> {
         self.inner.heart.get_or_init(|| {
@@ -122,18 +138,29 @@ impl<T: Transport + Clone, N: Network> RootProvider<T, N> {
 pub(crate) struct RootProviderInner<T, N: Network = Ethereum> {
     client: RpcClient<T>,
     heart: OnceLock<HeartbeatHandle<N>>,
+    capabilities: Arc<Capabilities>,
     _network: PhantomData<N>,
 }
 
 impl<T, N: Network> Clone for RootProviderInner<T, N> {
     fn clone(&self) -> Self {
-        Self { client: self.client.clone(), heart: self.heart.clone(), _network: PhantomData }
+        Self {
+            client: self.client.clone(),
+            heart: self.heart.clone(),
+            capabilities: self.capabilities.clone(),
+            _network: PhantomData,
+        }
     }
 }
 
 impl<T: Transport + Clone, N: Network> RootProviderInner<T, N> {
     pub(crate) fn new(client: RpcClient<T>) -> Self {
-        Self { client, heart: Default::default(), _network: PhantomData }
+        Self {
+            client,
+            heart: Default::default(),
+            capabilities: Default::default(),
+            _network: PhantomData,
+        }
     }
 
     pub(crate) fn weak_client(&self) -> WeakClient<T> {
@@ -147,6 +174,11 @@ impl<T: Transport + Clone, N: Network> RootProviderInner<T, N> {
 
 impl<T: Transport + Clone, N: Network> RootProviderInner<T, N> {
     fn boxed(self) -> RootProviderInner<BoxTransport, N> {
-        RootProviderInner { client: self.client.boxed(), heart: self.heart, _network: PhantomData }
+        RootProviderInner {
+            client: self.client.boxed(),
+            heart: self.heart,
+            capabilities: self.capabilities,
+            _network: PhantomData,
+        }
     }
 }
@@ -0,0 +1,355 @@
+use crate::Provider;
+use alloy_network::{BlockResponse, HeaderResponse, Network, ReceiptResponse, TransactionResponse};
+use alloy_primitives::{Bytes, Log, TxHash, TxKind, U256};
+use alloy_rpc_types_eth::{
+    simulate::{SimBlock, SimCallResult, SimulatePayload},
+    BlockOverrides, BlockTransactionsKind, Filter, TransactionRequest,
+};
+use alloy_transport::{Transport, TransportError, TransportResult};
+use std::{fmt, marker::PhantomData};
+
+/// The outcome of the execution of a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Execution {
+    /// Whether the transaction succeeded.
+    pub status: bool,
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<Log>,
+    /// The data returned by the transaction, if known.
+    pub output: Option<Bytes>,
+}
+
+impl From<SimCallResult> for Execution {
+    fn from(result: SimCallResult) -> Self {
+        Self {
+            status: result.status,
+            gas_used: result.gas_used,
+            logs: result.logs.into_iter().map(|log| log.inner).collect(),
+            output: Some(result.return_data),
+        }
+    }
+}
+
+/// A difference between the simulated and the actual execution of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Divergence {
+    /// The transaction succeeded in one execution, and failed in the other.
+    Status {
+        /// The simulated status.
+        simulated: bool,
+        /// The actual status.
+        actual: bool,
+    },
+    /// The transaction used a different amount of gas.
+    GasUsed {
+        /// The simulated gas used.
+        simulated: u64,
+        /// The actual gas used.
+        actual: u64,
+    },
+    /// The transaction emitted a different number of logs.
+    LogCount {
+        /// The number of simulated logs.
+        simulated: usize,
+        /// The number of actual logs.
+        actual: usize,
+    },
+    /// The log at the index, among the logs of the transaction, differs in its address, topics or
+    /// data.
+    Log {
+        /// The index of the log.
+        index: usize,
+    },
+    /// The transaction returned different data.
+    Output,
+}
+
+/// The comparison of the simulated and the actual execution of a transaction, see
+/// [`ExecutionComparer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionDiff {
+    /// The hash of the transaction.
+    pub transaction_hash: TxHash,
+    /// The simulated execution.
+    pub simulated: Execution,
+    /// The actual execution.
+    pub actual: Execution,
+    /// The differences between the executions, empty if they match.
+    pub divergences: Vec<Divergence>,
+}
+
+impl ExecutionDiff {
+    /// Compares the executions of the transaction.
+    ///
+    /// The outputs are only compared if both are known.
+    pub fn new(transaction_hash: TxHash, simulated: Execution, actual: Execution) -> Self {
+        let mut divergences = Vec::new();
+        if simulated.status != actual.status {
+            divergences
+                .push(Divergence::Status { simulated: simulated.status, actual: actual.status });
+        }
+        if simulated.gas_used != actual.gas_used {
+            divergences.push(Divergence::GasUsed {
+                simulated: simulated.gas_used,
+                actual: actual.gas_used,
+            });
+        }
+        if simulated.logs.len() != actual.logs.len() {
+            divergences.push(Divergence::LogCount {
+                simulated: simulated.logs.len(),
+                actual: actual.logs.len(),
+            });
+        }
+        divergences.extend(
+            simulated
+                .logs
+                .iter()
+                .zip(&actual.logs)
+                .enumerate()
+                .filter(|(_, (simulated, actual))| simulated != actual)
+                .map(|(index, _)| Divergence::Log { index }),
+        );
+        if let (Some(simulated), Some(actual)) = (&simulated.output, &actual.output) {
+            if simulated != actual {
+                divergences.push(Divergence::Output);
+            }
+        }
+        Self { transaction_hash, simulated, actual, divergences }
+    }
+
+    /// Returns `true` if the simulation matches the actual execution.
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// The state a mined transaction is simulated on by an [`ExecutionComparer`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PreState {
+    /// The state after the parent block. Transactions whose execution depends on the transactions
+    /// before them in their block diverge.
+    #[default]
+    ParentBlock,
+    /// The state after the parent block and the transactions before the transaction in its block,
+    /// which is the state the transaction was actually executed on.
+    PrecedingTransactions,
+}
+
+/// Simulates mined transactions with `eth_simulateV1`, and compares the simulations with their
+/// receipts.
+///
+/// The transaction is simulated on the [`PreState`] in a block with the environment of its actual
+/// block. Simulating on the parent block flags transactions affected by the transactions before
+/// them in their block, e.g. frontrun or backrun transactions, while simulating on the exact
+/// pre-state flags executions that cannot be reproduced, e.g. to monitor a sequencer.
+///
+/// The actual output of a transaction is not part of its receipt, so outputs are only compared if
+/// traced with `with_traced_output`, which requires the `debug-api` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example<P: alloy_provider::Provider>(provider: P, hash: alloy_primitives::TxHash) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::ExecutionComparer;
+///
+/// let diff = ExecutionComparer::new(provider).diff(hash).await?;
+/// if !diff.is_consistent() {
+///     println!("{hash} depends on its block: {:?}", diff.divergences);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ExecutionComparer<P, T, N> {
+    provider: P,
+    pre_state: PreState,
+    traced_output: bool,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> fmt::Debug for ExecutionComparer<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutionComparer")
+            .field("pre_state", &self.pre_state)
+            .field("traced_output", &self.traced_output)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> ExecutionComparer<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a comparer simulating on the [`PreState::ParentBlock`].
+    pub const fn new(provider: P) -> Self {
+        Self { provider, pre_state: PreState::ParentBlock, traced_output: false, _pd: PhantomData }
+    }
+
+    /// Sets the state transactions are simulated on.
+    pub const fn with_pre_state(mut self, pre_state: PreState) -> Self {
+        self.pre_state = pre_state;
+        self
+    }
+
+    /// Also compares the outputs, tracing the actual output with `debug_traceTransaction`.
+    #[cfg(feature = "debug-api")]
+    pub const fn with_traced_output(mut self) -> Self {
+        self.traced_output = true;
+        self
+    }
+
+    /// Simulates the mined transaction and compares the simulation with its receipt.
+    pub async fn diff(&self, hash: TxHash) -> TransportResult<ExecutionDiff> {
+        let missing = |what: &str| TransportError::local_usage_str(&format!("{what} not found"));
+        let receipt = self
+            .provider
+            .get_transaction_receipt(hash)
+            .await?
+            .ok_or_else(|| missing("transaction receipt"))?;
+        let block_hash = receipt.block_hash().ok_or_else(|| missing("transaction block"))?;
+        let index = receipt.transaction_index().ok_or_else(|| missing("transaction index"))?;
+        let block = self
+            .provider
+            .get_block_by_hash(block_hash, BlockTransactionsKind::Full)
+            .await?
+            .ok_or_else(|| missing("transaction block"))?;
+        let Some(transactions) = block.transactions().as_transactions() else {
+            return Err(missing("block transactions"));
+        };
+        let preceding = transactions.get(..=index as usize).ok_or_else(|| {
+            TransportError::local_usage_str("transaction index out of bounds of its block")
+        })?;
+        let calls = match self.pre_state {
+            PreState::ParentBlock => &preceding[index as usize..],
+            PreState::PrecedingTransactions => preceding,
+        };
+
+        let header = block.header();
+        let overrides = BlockOverrides {
+            number: Some(U256::from(header.number())),
+            difficulty: Some(header.difficulty()),
+            time: Some(header.timestamp()),
+            gas_limit: Some(header.gas_limit()),
+            coinbase: Some(header.coinbase()),
+            random: header.mix_hash(),
+            base_fee: header.base_fee_per_gas().map(U256::from),
+            block_hash: None,
+        };
+        let payload = SimulatePayload::default().extend(
+            SimBlock::default()
+                .with_block_overrides(overrides)
+                .extend_calls(calls.iter().map(transaction_request)),
+        );
+        let mut simulated = self.provider.simulate(&payload).hash(header.parent_hash()).await?;
+        let result = simulated
+            .pop()
+            .and_then(|mut block| block.calls.pop())
+            .ok_or_else(|| missing("simulated call"))?;
+
+        let logs = self.provider.get_logs(&Filter::new().at_block_hash(block_hash)).await?;
+        let actual = Execution {
+            status: receipt.status(),
+            gas_used: receipt.gas_used() as u64,
+            logs: logs
+                .into_iter()
+                .filter(|log| log.transaction_hash == Some(hash))
+                .map(|log| log.inner)
+                .collect(),
+            output: self.traced_output(hash).await?,
+        };
+        Ok(ExecutionDiff::new(hash, result.into(), actual))
+    }
+
+    #[cfg(feature = "debug-api")]
+    async fn traced_output(&self, hash: TxHash) -> TransportResult<Option<Bytes>> {
+        use crate::ext::DebugApi;
+        use alloy_rpc_types_trace::geth::{GethDebugBuiltInTracerType, GethDebugTracingOptions};
+
+        if !self.traced_output {
+            return Ok(None);
+        }
+        let options = GethDebugTracingOptions::default()
+            .with_tracer(GethDebugBuiltInTracerType::CallTracer.into());
+        let frame = self
+            .provider
+            .debug_trace_transaction(hash, options)
+            .await?
+            .try_into_call_frame()
+            .map_err(|err| TransportError::local_usage_str(&err.to_string()))?;
+        Ok(Some(frame.output.unwrap_or_default()))
+    }
+
+    #[cfg(not(feature = "debug-api"))]
+    async fn traced_output(&self, _hash: TxHash) -> TransportResult<Option<Bytes>> {
+        Ok(None)
+    }
+}
+
+/// Returns the request executing the transaction.
+fn transaction_request<T: TransactionResponse>(tx: &T) -> TransactionRequest {
+    let max_fee_per_gas = tx.max_fee_per_gas();
+    TransactionRequest {
+        from: Some(tx.from()),
+        to: Some(tx.to().map_or(TxKind::Create, TxKind::Call)),
+        gas: Some(tx.gas()),
+        // dynamic fee transactions also return their effective gas price
+        gas_price: if max_fee_per_gas.is_some() { None } else { tx.gas_price() },
+        max_fee_per_gas,
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas(),
+        max_fee_per_blob_gas: tx.max_fee_per_blob_gas(),
+        value: Some(tx.value()),
+        input: tx.input().clone().into(),
+        nonce: Some(tx.nonce()),
+        chain_id: tx.chain_id(),
+        access_list: tx.access_list(),
+        transaction_type: tx.transaction_type(),
+        blob_versioned_hashes: tx.blob_versioned_hashes(),
+        authorization_list: tx.authorization_list(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, bytes, LogData};
+
+    #[test]
+    fn diffs_executions() {
+        let log = |data: Bytes| Log {
+            address: address!("4200000000000000000000000000000000000006"),
+            data: LogData::new_unchecked(
+                vec![b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")],
+                data,
+            ),
+        };
+        let actual = Execution {
+            status: true,
+            gas_used: 50_000,
+            logs: vec![log(bytes!("01")), log(bytes!("02"))],
+            output: None,
+        };
+
+        let diff = ExecutionDiff::new(TxHash::ZERO, actual.clone(), actual.clone());
+        assert!(diff.is_consistent());
+
+        let simulated = Execution {
+            status: true,
+            gas_used: 48_000,
+            logs: vec![log(bytes!("01")), log(bytes!("03")), log(bytes!("04"))],
+            output: Some(bytes!("01")),
+        };
+        let diff = ExecutionDiff::new(TxHash::ZERO, simulated, actual);
+        assert_eq!(
+            diff.divergences,
+            [
+                Divergence::GasUsed { simulated: 48_000, actual: 50_000 },
+                Divergence::LogCount { simulated: 3, actual: 2 },
+                Divergence::Log { index: 1 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use crate::Provider;
+use alloy_json_rpc::RpcError;
+use alloy_network::Network;
+use alloy_network_primitives::{BlockResponse, HeaderResponse};
+use alloy_primitives::{keccak256, Address, StorageValue, B256, U256};
+use alloy_rpc_client::BatchRequest;
+use alloy_rpc_types_eth::BlockId;
+use alloy_transport::{Transport, TransportResult};
+use futures::{stream, StreamExt, TryStreamExt};
+use std::{fmt, marker::PhantomData};
+
+/// The default number of slots fetched in one batch request.
+const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// The default number of batch requests in flight.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Reads many storage slots of a contract at the same block, with batched `eth_getStorageAt`
+/// requests.
+///
+/// Slots are split into batch requests of [`with_batch_size`](Self::with_batch_size) slots, sent
+/// with bounded concurrency. Values are returned in the order of the slots.
+///
+/// A block tag is resolved to its hash by each read. Use [`pin`](Self::pin) to resolve it once,
+/// so that several reads see the same state.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example<P: alloy_provider::Provider>(provider: P) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_primitives::{Address, U256};
+/// use alloy_provider::StorageReader;
+/// use alloy_rpc_types_eth::BlockId;
+///
+/// let token = Address::ZERO;
+/// let reader = StorageReader::new(&provider, token, BlockId::latest()).pin().await?;
+/// // the first ten slots, and the balances of two holders in the mapping at slot 3
+/// let slots = reader.read_range(U256::ZERO, 10).await?;
+/// let holders = [Address::repeat_byte(1).into_word(), Address::repeat_byte(2).into_word()];
+/// let balances = reader.read_mapping(U256::from(3), holders).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct StorageReader<'a, P: ?Sized, T, N> {
+    provider: &'a P,
+    address: Address,
+    block: BlockId,
+    batch_size: usize,
+    concurrency: usize,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P: ?Sized, T, N> Clone for StorageReader<'_, P, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: ?Sized, T, N> Copy for StorageReader<'_, P, T, N> {}
+
+impl<P: ?Sized, T, N> fmt::Debug for StorageReader<'_, P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageReader")
+            .field("address", &self.address)
+            .field("block", &self.block)
+            .field("batch_size", &self.batch_size)
+            .field("concurrency", &self.concurrency)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, P, T, N> StorageReader<'a, P, T, N>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a reader of the storage of the contract at the block.
+    pub const fn new(provider: &'a P, address: Address, block: BlockId) -> Self {
+        Self {
+            provider,
+            address,
+            block,
+            batch_size: DEFAULT_BATCH_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Sets the number of slots fetched in one batch request, 100 by default.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the number of batch requests in flight, 4 by default.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Returns the block the slots are read at.
+    pub const fn block(&self) -> BlockId {
+        self.block
+    }
+
+    /// Resolves a block tag to its hash, so that all reads see the same state.
+    pub async fn pin(mut self) -> TransportResult<Self> {
+        self.block = self.resolve_block().await?;
+        Ok(self)
+    }
+
+    /// Reads the slots.
+    pub async fn read(
+        &self,
+        slots: impl IntoIterator<Item = U256>,
+    ) -> TransportResult<Vec<StorageValue>> {
+        self.read_with(slots, |_, value| value).await
+    }
+
+    /// Reads the slots, decoding each value with its slot.
+    pub async fn read_with<R>(
+        &self,
+        slots: impl IntoIterator<Item = U256>,
+        mut decode: impl FnMut(U256, StorageValue) -> R,
+    ) -> TransportResult<Vec<R>> {
+        let slots = slots.into_iter().collect::<Vec<_>>();
+        let block = self.resolve_block().await?;
+        // owned chunks keep the future `Send` when awaited in the `Provider` methods
+        let values = stream::iter(slots.chunks(self.batch_size).map(<[U256]>::to_vec))
+            .map(|chunk| async move { self.read_batch(&chunk, block).await })
+            .buffered(self.concurrency)
+            .try_concat()
+            .await?;
+        Ok(slots.into_iter().zip(values).map(|(slot, value)| decode(slot, value)).collect())
+    }
+
+    /// Reads `count` consecutive slots from `start`.
+    pub async fn read_range(
+        &self,
+        start: U256,
+        count: usize,
+    ) -> TransportResult<Vec<StorageValue>> {
+        self.read((0..count).map(|i| start.wrapping_add(U256::from(i)))).await
+    }
+
+    /// Reads the values of the keys in the mapping at the slot, see [`mapping_slot`].
+    ///
+    /// Keys are the 32-byte words Solidity hashes for value types, e.g. `address.into_word()`.
+    /// The keys of a mapping cannot be enumerated from storage, so they must be known, for
+    /// instance from the logs of the contract.
+    pub async fn read_mapping(
+        &self,
+        slot: U256,
+        keys: impl IntoIterator<Item = B256>,
+    ) -> TransportResult<Vec<(B256, StorageValue)>> {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+        let values = self.read(keys.iter().map(|key| mapping_slot(key, slot))).await?;
+        Ok(keys.into_iter().zip(values).collect())
+    }
+
+    /// Reads the elements of the dynamic array at the slot, each taking a full slot.
+    ///
+    /// The length is read first, at the same block as the elements.
+    pub async fn read_array(&self, slot: U256) -> TransportResult<Vec<StorageValue>> {
+        let reader = self.pin().await?;
+        let len = reader.read([slot]).await?[0];
+        let len = usize::try_from(len)
+            .map_err(|_| RpcError::local_usage_str("array length overflows"))?;
+        reader.read_range(array_slot(slot), len).await
+    }
+
+    async fn read_batch(
+        &self,
+        slots: &[U256],
+        block: BlockId,
+    ) -> TransportResult<Vec<StorageValue>> {
+        let mut batch = BatchRequest::new(self.provider.client());
+        let waiters = slots
+            .iter()
+            .map(|slot| {
+                batch.add_call::<_, StorageValue>("eth_getStorageAt", &(self.address, slot, block))
+            })
+            .collect::<TransportResult<Vec<_>>>()?;
+        batch.send().await?;
+
+        let mut values = Vec::with_capacity(waiters.len());
+        for waiter in waiters {
+            values.push(waiter.await?);
+        }
+        Ok(values)
+    }
+
+    async fn resolve_block(&self) -> TransportResult<BlockId> {
+        match self.block {
+            BlockId::Number(tag) if !tag.is_number() => {
+                let block = self
+                    .provider
+                    .get_block_by_number(tag, false)
+                    .await?
+                    .ok_or_else(|| RpcError::local_usage_str("block not found"))?;
+                Ok(BlockId::hash(block.header().hash()))
+            }
+            block => Ok(block),
+        }
+    }
+}
+
+/// Returns the slot of the value of the key in the mapping at the slot, `keccak256(key . slot)`.
+pub fn mapping_slot(key: &B256, slot: U256) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(key.as_slice());
+    preimage[32..].copy_from_slice(&slot.to_be_bytes::<32>());
+    keccak256(preimage).into()
+}
+
+/// Returns the slot of the first element of the dynamic array at the slot, `keccak256(slot)`.
+pub fn array_slot(slot: U256) -> U256 {
+    keccak256(slot.to_be_bytes::<32>()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_network::TransactionBuilder;
+    use alloy_primitives::{b256, hex};
+    use alloy_rpc_types_eth::TransactionRequest;
+
+    #[test]
+    fn computes_slots() {
+        assert_eq!(
+            B256::from(array_slot(U256::from(2))),
+            b256!("405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ace")
+        );
+        // balances[address(0)] at slot 0
+        assert_eq!(
+            B256::from(mapping_slot(&B256::ZERO, U256::ZERO)),
+            b256!("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5")
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_slots_in_batches() {
+        let provider = ProviderBuilder::new().on_anvil();
+        // constructor storing `i + 1` in the slots 0 to 4
+        let bytecode =
+            hex::decode("60015f5560026001556003600255600460035560056004555f80f3").unwrap();
+        let contract = provider
+            .send_transaction(TransactionRequest::default().with_deploy_code(bytecode))
+            .await
+            .unwrap()
+            .get_receipt()
+            .await
+            .unwrap()
+            .contract_address
+            .unwrap();
+
+        let reader = StorageReader::new(&provider, contract, BlockId::latest()).with_batch_size(2);
+        let values = reader.read_range(U256::ZERO, 6).await.unwrap();
+        assert_eq!(values, [1, 2, 3, 4, 5, 0].map(U256::from));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{
+    provider::multicall::{self, IMulticall3},
+    Provider,
+};
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_rpc_types_eth::BlockId;
+use alloy_sol_types::{sol, SolCall, SolValue};
+use alloy_transport::{BoxTransport, Transport, TransportResult};
+use dashmap::DashMap;
+use std::{fmt, marker::PhantomData};
+
+sol! {
+    #[allow(missing_docs)]
+    interface IERC20Metadata {
+        function name() external view returns (string);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+    }
+}
+
+/// The metadata of an ERC-20 token, resolved by a [`TokenMetadataResolver`].
+///
+/// Each field is `None` if the token does not implement the method, or returns malformed data.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenMetadata {
+    /// The name of the token.
+    pub name: Option<String>,
+    /// The symbol of the token.
+    pub symbol: Option<String>,
+    /// The number of decimals of the token.
+    pub decimals: Option<u8>,
+}
+
+impl TokenMetadata {
+    fn decode(name: Option<Bytes>, symbol: Option<Bytes>, decimals: Option<Bytes>) -> Self {
+        Self {
+            name: name.and_then(|data| decode_string(&data)),
+            symbol: symbol.and_then(|data| decode_string(&data)),
+            decimals: decimals.and_then(|data| {
+                U256::abi_decode(&data, false).ok().and_then(|decimals| decimals.try_into().ok())
+            }),
+        }
+    }
+}
+
+/// Decodes a `string`, or a `bytes32` padded with zeros as returned by older tokens such as MKR.
+fn decode_string(data: &[u8]) -> Option<String> {
+    if let Ok(string) = String::abi_decode(data, true) {
+        return Some(string);
+    }
+    if data.len() != 32 {
+        return None;
+    }
+    let len = data.iter().position(|&byte| byte == 0).unwrap_or(32);
+    // the rest of a zero-padded string must be zeros
+    if data[len..].iter().any(|&byte| byte != 0) {
+        return None;
+    }
+    String::from_utf8(data[..len].to_vec()).ok()
+}
+
+/// Resolves and caches the [`TokenMetadata`] of ERC-20 tokens.
+///
+/// `name`, `symbol` and `decimals` are fetched together through
+/// [Multicall3](crate::MULTICALL3_ADDRESS), so tokens that do not implement some of the methods
+/// still resolve, and names and symbols returned as `bytes32` are decoded as strings. The metadata
+/// is cached per token address, since it is not expected to change.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example<P: alloy_provider::Provider>(provider: P) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_primitives::address;
+/// use alloy_provider::TokenMetadataResolver;
+///
+/// let resolver = TokenMetadataResolver::new(provider);
+/// let mkr = resolver.resolve(address!("9f8F72aA9304c8B593d555F12eF6589cC3A579A2")).await?;
+/// assert_eq!(mkr.symbol.as_deref(), Some("MKR"));
+/// # Ok(())
+/// # }
+/// ```
+pub struct TokenMetadataResolver<P, T = BoxTransport, N = Ethereum> {
+    provider: P,
+    cache: DashMap<Address, TokenMetadata>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> fmt::Debug for TokenMetadataResolver<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenMetadataResolver").field("cached", &self.cache.len()).finish()
+    }
+}
+
+impl<P, T, N> TokenMetadataResolver<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new resolver with an empty cache.
+    pub fn new(provider: P) -> Self {
+        Self { provider, cache: DashMap::new(), _pd: PhantomData }
+    }
+
+    /// Returns the underlying provider.
+    pub const fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Returns the cached metadata of the token, if any.
+    pub fn cached(&self, token: Address) -> Option<TokenMetadata> {
+        self.cache.get(&token).map(|metadata| metadata.clone())
+    }
+
+    /// Inserts the metadata of the token into the cache, e.g. from a token list.
+    pub fn insert(&self, token: Address, metadata: TokenMetadata) {
+        self.cache.insert(token, metadata);
+    }
+
+    /// Removes all cached metadata.
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+
+    /// Returns the metadata of the token, from the cache if possible.
+    pub async fn resolve(&self, token: Address) -> TransportResult<TokenMetadata> {
+        Ok(self.resolve_many(&[token]).await?.remove(0))
+    }
+
+    /// Returns the metadata of each token, fetching all tokens missing from the cache together.
+    pub async fn resolve_many(&self, tokens: &[Address]) -> TransportResult<Vec<TokenMetadata>> {
+        let mut missing =
+            tokens.iter().filter(|&token| !self.cache.contains_key(token)).collect::<Vec<_>>();
+        missing.sort();
+        missing.dedup();
+
+        if !missing.is_empty() {
+            let calls = missing
+                .iter()
+                .flat_map(|&&target| {
+                    [
+                        IERC20Metadata::nameCall {}.abi_encode(),
+                        IERC20Metadata::symbolCall {}.abi_encode(),
+                        IERC20Metadata::decimalsCall {}.abi_encode(),
+                    ]
+                    .map(|call_data| IMulticall3::Call3 {
+                        target,
+                        allowFailure: true,
+                        callData: call_data.into(),
+                    })
+                })
+                .collect();
+            let mut outputs = multicall::aggregate_at(&self.provider, calls, BlockId::latest())
+                .await?
+                .into_iter();
+            for &&token in &missing {
+                let (name, symbol, decimals) =
+                    (outputs.next().flatten(), outputs.next().flatten(), outputs.next().flatten());
+                self.cache.insert(token, TokenMetadata::decode(name, symbol, decimals));
+            }
+        }
+
+        Ok(tokens.iter().map(|token| self.cached(*token).unwrap_or_default()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn decodes_non_standard_metadata() {
+        let string = |s: &str| Some(Bytes::from(s.to_string().abi_encode()));
+        let bytes32 = |s: &str| Some(Bytes::from(B256::right_padding_from(s.as_bytes()).0));
+        let uint = |n: u64| Some(Bytes::from(U256::from(n).abi_encode()));
+
+        assert_eq!(
+            TokenMetadata::decode(string("Wrapped Ether"), string("WETH"), uint(18)),
+            TokenMetadata {
+                name: Some("Wrapped Ether".into()),
+                symbol: Some("WETH".into()),
+                decimals: Some(18)
+            }
+        );
+        assert_eq!(
+            TokenMetadata::decode(bytes32("Maker"), bytes32("MKR"), uint(18)),
+            TokenMetadata {
+                name: Some("Maker".into()),
+                symbol: Some("MKR".into()),
+                decimals: Some(18)
+            }
+        );
+        assert_eq!(
+            TokenMetadata::decode(None, Some(Bytes::from_static(b"garbage")), uint(256)),
+            TokenMetadata::default()
+        );
+
+        // a full bytes32 has no padding
+        let full = "a".repeat(32);
+        assert_eq!(decode_string(&B256::right_padding_from(full.as_bytes()).0), Some(full));
+        assert_eq!(decode_string(&B256::with_last_byte(b'a').0), None);
+    }
+}
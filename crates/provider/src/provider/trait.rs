@@ -4,15 +4,26 @@
 
 use crate::{
     heart::PendingTransactionError,
-    utils::{self, Eip1559Estimation, EstimatorFunction},
-    EthCall, Identity, PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig,
-    ProviderBuilder, ProviderCall, RootProvider, RpcWithBlock, SendableTx,
+    provider::{
+        code::{self, CodeMetadata},
+        history::{self, StateChange},
+        is_method_not_found, logs,
+        multicall::{self, TokenAllowance},
+        verify,
+    },
+    utils::{
+        self, Eip1559Estimation, Eip1559EstimatorParams, Eip1559FeeEstimate, EstimatorFunction,
+    },
+    AtBlock, BlockVerification, EthCall, Identity, LogConsistency, NodeIdentity, Paginated,
+    PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig, ProviderBuilder,
+    ProviderCall, RevertReason, RootProvider, RpcWithBlock, SendableTx, StorageReader,
 };
-use alloy_eips::eip2718::Encodable2718;
-use alloy_json_rpc::{RpcError, RpcParam, RpcReturn};
-use alloy_network::{Ethereum, Network};
+use alloy_consensus::proofs::InclusionProof;
+use alloy_eips::{eip2718::Encodable2718, eip4844::BlobParams};
+use alloy_json_rpc::{is_revert_message, RequestPriority, RpcError, RpcParam, RpcReturn};
+use alloy_network::{Ethereum, Network, TransactionBuilder};
 use alloy_network_primitives::{
-    BlockResponse, BlockTransactionsKind, HeaderResponse, ReceiptResponse,
+    BlockResponse, BlockTransactionsKind, HeaderResponse, ReceiptResponse, TransactionResponse,
 };
 use alloy_primitives::{
     hex, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, B256, U128,
@@ -101,6 +112,17 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.root().weak_client()
     }
 
+    /// Returns a view of the provider whose state queries default to the given block.
+    ///
+    /// See [`AtBlock`] for the supported queries.
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    fn at_block(&self, block_id: BlockId) -> AtBlock<'_, Self, T, N>
+    where
+        Self: Sized,
+    {
+        AtBlock::new(self, block_id)
+    }
+
     /// Gets the accounts in the remote node. This is usually empty unless you're using a local
     /// node.
     fn get_accounts(&self) -> ProviderCall<T, NoParams, Vec<Address>> {
@@ -202,11 +224,17 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
     /// Estimates the EIP1559 `maxFeePerGas` and `maxPriorityFeePerGas` fields.
     ///
     /// Receives an optional [EstimatorFunction] that can be used to modify
-    /// how to estimate these fees.
+    /// how to estimate these fees. Without an estimator, this is equivalent to
+    /// [`estimate_eip1559_fees_with`](Self::estimate_eip1559_fees_with) with the default
+    /// [`Eip1559EstimatorParams`].
     async fn estimate_eip1559_fees(
         &self,
         estimator: Option<EstimatorFunction>,
     ) -> TransportResult<Eip1559Estimation> {
+        let Some(estimator) = estimator else {
+            return self.estimate_eip1559_fees_with(&Default::default()).await.map(Into::into);
+        };
+
         let fee_history = self
             .get_fee_history(
                 utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
@@ -214,26 +242,34 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
                 &[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
             )
             .await?;
+        let base_fee_per_gas = latest_base_fee(self, &fee_history).await?;
 
-        // if the base fee of the Latest block is 0 then we need check if the latest block even has
-        // a base fee/supports EIP1559
-        let base_fee_per_gas = match fee_history.latest_block_base_fee() {
-            Some(base_fee) if base_fee != 0 => base_fee,
-            _ => {
-                // empty response, fetch basefee from latest block directly
-                self.get_block_by_number(BlockNumberOrTag::Latest, false)
-                    .await?
-                    .ok_or(RpcError::NullResp)?
-                    .header()
-                    .base_fee_per_gas()
-                    .ok_or(RpcError::UnsupportedFeature("eip1559"))?
-                    .into()
-            }
-        };
+        Ok(estimator(base_fee_per_gas, &fee_history.reward.unwrap_or_default()))
+    }
 
-        Ok(estimator.unwrap_or(utils::eip1559_default_estimator)(
+    /// Estimates the EIP1559 fees from the fee history of the given number of past blocks, with
+    /// the given reward percentiles and base fee multiplier.
+    ///
+    /// Returns the suggested `maxFeePerGas` and `maxPriorityFeePerGas` fields, together with the
+    /// base fee of the latest block, the projected base fee of the next block, and the median
+    /// priority fee of each reward percentile.
+    async fn estimate_eip1559_fees_with(
+        &self,
+        params: &Eip1559EstimatorParams,
+    ) -> TransportResult<Eip1559FeeEstimate> {
+        let fee_history = self
+            .get_fee_history(
+                params.block_count,
+                BlockNumberOrTag::Latest,
+                &params.reward_percentiles,
+            )
+            .await?;
+        let base_fee_per_gas = latest_base_fee(self, &fee_history).await?;
+
+        Ok(params.estimate(
             base_fee_per_gas,
-            &fee_history.reward.unwrap_or_default(),
+            fee_history.next_block_base_fee(),
+            fee_history.reward.as_deref().unwrap_or_default(),
         ))
     }
 
@@ -273,13 +309,32 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
     }
 
     /// Gets a block by either its hash, tag, or number, with full transactions or only hashes.
+    ///
+    /// If the block is identified by an [EIP-1898] hash with `requireCanonical` set, the block is
+    /// also checked against the canonical block at its number, and an error is returned if it was
+    /// reorged out.
+    ///
+    /// [EIP-1898]: https://eips.ethereum.org/EIPS/eip-1898
     async fn get_block(
         &self,
         block: BlockId,
         kind: BlockTransactionsKind,
     ) -> TransportResult<Option<N::BlockResponse>> {
         match block {
-            BlockId::Hash(hash) => self.get_block_by_hash(hash.into(), kind).await,
+            BlockId::Hash(hash) => {
+                let Some(block) = self.get_block_by_hash(hash.block_hash, kind).await? else {
+                    return Ok(None);
+                };
+                if hash.requires_canonical() {
+                    let number = block.header().number();
+                    let canonical = self.get_block_by_number(number.into(), false).await?;
+                    if canonical.map(|canonical| canonical.header().hash()) != Some(hash.block_hash)
+                    {
+                        return Err(RpcError::local_usage_str("block is not canonical"));
+                    }
+                }
+                Ok(Some(block))
+            }
             BlockId::Number(number) => {
                 let full = matches!(kind, BlockTransactionsKind::Full);
                 self.get_block_by_number(number, full).await
@@ -349,6 +404,23 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getCode", address).into()
     }
 
+    /// Gets the bytecode at the [Address] at the block, classified as an EOA, a contract, a proxy
+    /// or an EIP-7702 delegation.
+    ///
+    /// The implementations of EIP-1167 minimal proxies, EIP-1967 proxies and beacon proxies and
+    /// EIP-1822 proxies, and the delegates of EIP-7702 delegations are resolved recursively,
+    /// following up to `max_depth` indirections. Contracts are only detected as EIP-1967 or
+    /// EIP-1822 proxies by reading their storage, so this costs up to three extra requests per
+    /// contract.
+    async fn get_code_with_metadata(
+        &self,
+        address: Address,
+        block: BlockId,
+        max_depth: usize,
+    ) -> TransportResult<CodeMetadata> {
+        code::get_code_with_metadata(self, address, block, max_depth).await
+    }
+
     /// Watch for new blocks by polling the provider with
     /// [`eth_getFilterChanges`](Self::get_filter_changes).
     ///
@@ -506,6 +578,52 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getLogs", (filter,)).await
     }
 
+    /// Retrieves a [`Vec<Log>`] with the given [Filter], sorted and validated with the
+    /// [`LogConsistency`] checks.
+    ///
+    /// Inconsistent responses are rejected with a local usage error wrapping the
+    /// [`LogConsistencyError`](crate::LogConsistencyError).
+    async fn get_logs_strict(
+        &self,
+        filter: &Filter,
+        checks: &LogConsistency,
+    ) -> TransportResult<Vec<Log>> {
+        let logs = self.get_logs(filter).await?;
+        checks.check(logs).map_err(RpcError::local_usage)
+    }
+
+    /// Retrieves the logs of the [Filter] over its block range, in pages of up to `page_size`
+    /// blocks.
+    ///
+    /// Nodes refusing a page because of its block range or number of results, such as with
+    /// "query returned more than 10000 results", are asked again for half of the range, until
+    /// it succeeds or is a single block. Later pages start with the range that last succeeded.
+    ///
+    /// The end of the range is resolved when the first page is fetched, and a missing `fromBlock`
+    /// or `toBlock` is the latest block, as for `eth_getLogs`. Filters for a block hash are
+    /// fetched in a single page.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::address;
+    /// use alloy_rpc_types_eth::Filter;
+    /// use futures::StreamExt;
+    ///
+    /// let address = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+    /// let filter = Filter::new().address(address).from_block(6_082_465);
+    /// let mut logs = std::pin::pin!(provider.get_logs_paginated(&filter, 10_000).into_stream());
+    /// while let Some(log) = logs.next().await {
+    ///     println!("{:?}", log?.block_number);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_logs_paginated(&self, filter: &Filter, page_size: u64) -> Paginated<Log, BlockNumber> {
+        logs::paginate_logs(self.weak_client(), filter.clone(), page_size)
+    }
+
     /// Get the account and storage values of the specified account including the merkle proofs.
     ///
     /// This call can be used to verify that the data has not been tampered with.
@@ -526,6 +644,191 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getStorageAt", (address, key)).into()
     }
 
+    /// Returns some of the blocks in `from_block + 1..=to_block` that changed the balance of the
+    /// account, with the balance before and after each of them.
+    ///
+    /// The changes are found by binary search over the balance at each block, so the result is not
+    /// exhaustive: changes within a range are only searched for if the balance differs at its
+    /// ends, and a balance that changes and then returns to the same value within such a range,
+    /// e.g. from A to B and back to A, is not reported. See also
+    /// [`TraceApi::trace_balance_changes`](crate::ext::TraceApi::trace_balance_changes), which
+    /// checks every block with traces of the account instead.
+    ///
+    /// The search requires a node that serves historical state, and fails with
+    /// [`RpcError::UnsupportedFeature`] if [`Provider::is_archive`] returns `false`.
+    async fn get_balance_changes(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Vec<StateChange>> {
+        if !self.is_archive().await? {
+            return Err(RpcError::UnsupportedFeature("historical state"));
+        }
+        history::bisect_changes(from_block, to_block, |block| async move {
+            self.get_balance(address).block_id(block.into()).await
+        })
+        .await
+    }
+
+    /// Returns some of the blocks in `from_block + 1..=to_block` that changed the storage slot of
+    /// the account, with the value before and after each of them.
+    ///
+    /// See [`Provider::get_balance_changes`] for the requirements and limitations.
+    async fn get_storage_changes(
+        &self,
+        address: Address,
+        key: U256,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Vec<StateChange>> {
+        if !self.is_archive().await? {
+            return Err(RpcError::UnsupportedFeature("historical state"));
+        }
+        history::bisect_changes(from_block, to_block, |block| async move {
+            self.get_storage_at(address, key).block_id(block.into()).await
+        })
+        .await
+    }
+
+    /// Returns when the account was first seen in `from_block..=to_block`, and when it last sent a
+    /// transaction, or `None` if it has neither a balance nor a nonce at `to_block`.
+    ///
+    /// Both are found by binary search over the state at each block, which requires an archive
+    /// node. An account is assumed to keep a balance or a nonce once it has one, which only fails
+    /// for accounts emptied by a self-destruct.
+    async fn get_address_activity(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Option<history::AddressActivity>> {
+        let Some(first_seen) = history::first_seen(self, address, from_block, to_block).await?
+        else {
+            return Ok(None);
+        };
+
+        let nonce = self.get_transaction_count(address).block_id(to_block.into()).await?;
+        let last_sent = if nonce == 0 {
+            None
+        } else {
+            history::first_block_where(first_seen, to_block, |block| async move {
+                Ok(self.get_transaction_count(address).block_id(block.into()).await? == nonce)
+            })
+            .await?
+        };
+        Ok(Some(history::AddressActivity { first_seen, last_sent, nonce }))
+    }
+
+    /// Returns the first funding of the account in `from_block..=to_block`, or `None` if it has
+    /// neither a balance nor a nonce at `to_block`.
+    ///
+    /// The block is found as in [`Provider::get_address_activity`]. The transaction and funder are
+    /// those of the first plain transfer to the account in that block, and are `None` for funds
+    /// received through internal calls, withdrawals or block rewards, or if the account already
+    /// had a balance or nonce at `from_block`, e.g. from the genesis allocation.
+    ///
+    /// See also [`TraceApi::trace_funding`](crate::ext::TraceApi::trace_funding), which also finds
+    /// internal transfers.
+    async fn get_funding(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Option<history::Funding>> {
+        let Some(block_number) = history::first_seen(self, address, from_block, to_block).await?
+        else {
+            return Ok(None);
+        };
+        let balance = self.get_balance(address).block_id(block_number.into()).await?;
+        let mut funding =
+            history::Funding { block_number, transaction_hash: None, funder: None, value: balance };
+        if block_number == from_block {
+            return Ok(Some(funding));
+        }
+
+        let block = self
+            .get_block_by_number(block_number.into(), true)
+            .await?
+            .ok_or_else(|| RpcError::local_usage_str("block not found"))?;
+        let transfer = block.transactions().txns().find(|tx| {
+            TransactionResponse::to(*tx) == Some(address)
+                && !TransactionResponse::value(*tx).is_zero()
+        });
+        if let Some(tx) = transfer {
+            funding.transaction_hash = Some(tx.tx_hash());
+            funding.funder = Some(TransactionResponse::from(tx));
+            funding.value = TransactionResponse::value(tx);
+        }
+        Ok(Some(funding))
+    }
+
+    /// Returns the ERC-20 balances of the holder for each token at the block, `None` for tokens
+    /// whose `balanceOf` call failed or returned malformed data.
+    ///
+    /// The calls are aggregated through [Multicall3](crate::MULTICALL3_ADDRESS) in chunks, and a
+    /// block tag is first resolved to its number, so that all chunks read the same state.
+    async fn get_erc20_balances(
+        &self,
+        holder: Address,
+        tokens: &[Address],
+        block: BlockId,
+    ) -> TransportResult<Vec<Option<U256>>> {
+        let calls = multicall::balance_calls(holder, tokens);
+        let outputs = multicall::aggregate_at(self, calls, block).await?;
+        Ok(outputs.into_iter().map(multicall::decode_amount).collect())
+    }
+
+    /// Returns the ERC-20 allowances of the owner to each spender for each token at the block,
+    /// ordered by token and then by spender.
+    ///
+    /// See [`Provider::get_erc20_balances`] for how the calls are made.
+    async fn get_allowances(
+        &self,
+        owner: Address,
+        spenders: &[Address],
+        tokens: &[Address],
+        block: BlockId,
+    ) -> TransportResult<Vec<TokenAllowance>> {
+        let calls = multicall::allowance_calls(owner, spenders, tokens);
+        let outputs = multicall::aggregate_at(self, calls, block).await?;
+        let pairs = tokens.iter().flat_map(|&token| spenders.iter().map(move |&s| (token, s)));
+        Ok(pairs
+            .zip(outputs)
+            .map(|((token, spender), output)| TokenAllowance {
+                token,
+                spender,
+                allowance: multicall::decode_amount(output),
+            })
+            .collect())
+    }
+
+    /// Returns the storage slots of the account that differ between two blocks, with their values
+    /// at `from_block` and `to_block`.
+    ///
+    /// The slots are read at both blocks concurrently with a [`StorageReader`] each, which sends
+    /// them in batch requests.
+    async fn diff_storage(
+        &self,
+        address: Address,
+        keys: Vec<U256>,
+        from_block: BlockId,
+        to_block: BlockId,
+    ) -> TransportResult<Vec<(U256, StorageValue, StorageValue)>> {
+        let from_reader = StorageReader::new(self, address, from_block);
+        let to_reader = StorageReader::new(self, address, to_block);
+        let (before, after) = futures::try_join!(
+            from_reader.read(keys.iter().copied()),
+            to_reader.read(keys.iter().copied()),
+        )?;
+        Ok(keys
+            .into_iter()
+            .zip(before.into_iter().zip(after))
+            .filter(|(_, (before, after))| before != after)
+            .map(|(key, (before, after))| (key, before, after))
+            .collect())
+    }
+
     /// Gets a transaction by its [TxHash].
     fn get_transaction_by_hash(
         &self,
@@ -570,6 +873,214 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getTransactionReceipt", (hash,)).into()
     }
 
+    /// Builds a Merkle Patricia Trie inclusion proof of the transaction with the given hash in
+    /// the `transactionsRoot` of its block.
+    ///
+    /// The block is fetched with full transactions and the transaction trie is rebuilt locally.
+    /// The computed root is checked against the `transactionsRoot` of the fetched header, which
+    /// itself must be checked against a trusted header before relying on the proof.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    async fn get_transaction_proof(&self, hash: TxHash) -> TransportResult<Option<InclusionProof>>
+    where
+        N::TxEnvelope: TryFrom<N::TransactionResponse>,
+        <N::TxEnvelope as TryFrom<N::TransactionResponse>>::Error:
+            std::error::Error + Send + Sync + 'static,
+    {
+        let Some(tx) = self.get_transaction_by_hash(hash).await? else {
+            return Ok(None);
+        };
+        let (Some(block_hash), Some(index)) = (tx.block_hash(), tx.transaction_index()) else {
+            return Ok(None);
+        };
+
+        let block = self
+            .get_block_by_hash(block_hash, BlockTransactionsKind::Full)
+            .await?
+            .ok_or(RpcError::NullResp)?;
+        let transactions = block
+            .transactions()
+            .as_transactions()
+            .ok_or_else(|| RpcError::local_usage_str("block is missing full transactions"))?
+            .iter()
+            .cloned()
+            .map(N::TxEnvelope::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RpcError::local_usage)?;
+
+        let proof = InclusionProof::new_2718(&transactions, index as usize)
+            .ok_or_else(|| RpcError::local_usage_str("transaction index out of bounds"))?;
+        if proof.root != block.header().transactions_root() {
+            return Err(RpcError::local_usage_str("computed transactions root mismatch"));
+        }
+        Ok(Some(proof))
+    }
+
+    /// Builds a Merkle Patricia Trie inclusion proof of the receipt of the transaction with the
+    /// given hash in the `receiptsRoot` of its block.
+    ///
+    /// The receipts of the block are fetched with `eth_getBlockReceipts`, or one by one if the
+    /// endpoint does not [support](Self::supports) it, and the receipt trie is rebuilt locally. The
+    /// computed root is checked against the `receiptsRoot` of the fetched header, which itself
+    /// must be checked against a trusted header before relying on the proof.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    async fn get_receipt_proof(&self, hash: TxHash) -> TransportResult<Option<InclusionProof>>
+    where
+        N::ReceiptEnvelope: From<N::ReceiptResponse>,
+    {
+        let Some(receipt) = self.get_transaction_receipt(hash).await? else {
+            return Ok(None);
+        };
+        let (Some(block_hash), Some(index)) = (receipt.block_hash(), receipt.transaction_index())
+        else {
+            return Ok(None);
+        };
+
+        let block = self
+            .get_block_by_hash(block_hash, BlockTransactionsKind::Hashes)
+            .await?
+            .ok_or(RpcError::NullResp)?;
+        let receipts = verify::get_receipts_of(self, &block).await?;
+
+        let proof = InclusionProof::new_2718(&receipts, index as usize)
+            .ok_or_else(|| RpcError::local_usage_str("receipt index out of bounds"))?;
+        if proof.root != block.header().receipts_root() {
+            return Err(RpcError::local_usage_str("computed receipts root mismatch"));
+        }
+        Ok(Some(proof))
+    }
+
+    /// Fetches the block with its receipts and verifies its header against its contents.
+    ///
+    /// The transactions, receipts and withdrawals roots, the logs bloom, the gas used and the blob
+    /// gas accounting are recomputed locally, and each field that does not match is reported in
+    /// the [`BlockVerification`]. This spot-checks the consistency of the responses of an
+    /// untrusted endpoint, not the validity of the block itself: the hash of the header must still
+    /// be checked against a trusted source.
+    ///
+    /// The excess blob gas is verified with the given blob parameters, which must be those of the
+    /// fork of the block, e.g. [`BlobParams::cancun`] or [`BlobParams::prague`]. This requires the
+    /// parent block, and fails with [`RpcError::NullResp`] if the node does not know it.
+    ///
+    /// Returns `None` if the block does not exist.
+    async fn verify_block(
+        &self,
+        block: BlockId,
+        blob_params: BlobParams,
+    ) -> TransportResult<Option<BlockVerification>>
+    where
+        N::TxEnvelope: TryFrom<N::TransactionResponse>,
+        <N::TxEnvelope as TryFrom<N::TransactionResponse>>::Error:
+            std::error::Error + Send + Sync + 'static,
+        N::ReceiptEnvelope: From<N::ReceiptResponse>,
+    {
+        let Some(block) = self.get_block(block, BlockTransactionsKind::Full).await? else {
+            return Ok(None);
+        };
+        let header = block.header();
+        let transactions = block
+            .transactions()
+            .as_transactions()
+            .ok_or_else(|| RpcError::local_usage_str("block is missing full transactions"))?
+            .iter()
+            .cloned()
+            .map(N::TxEnvelope::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RpcError::local_usage)?;
+
+        let receipts = verify::get_receipts_of(self, &block).await?;
+
+        let parent = if header.excess_blob_gas().is_some() && header.number() > 0 {
+            let parent = self
+                .get_block_by_hash(header.parent_hash(), BlockTransactionsKind::Hashes)
+                .await?
+                .ok_or(RpcError::NullResp)?;
+            Some(parent)
+        } else {
+            None
+        };
+        let parent = parent.as_ref().map(BlockResponse::header);
+        Ok(Some(BlockVerification::new(&block, &transactions, &receipts, parent, blob_params)))
+    }
+
+    /// Replays a failed transaction and returns its [`RevertReason`].
+    ///
+    /// The transaction is replayed with `debug_traceTransaction`, which re-executes it in its
+    /// original context, after the transactions that precede it in the block.
+    ///
+    /// If the node does not support `debug_traceTransaction`, the first transaction of a block is
+    /// re-executed with `eth_call` on top of the state of the parent block instead, using the
+    /// original sender, recipient, calldata, value, gas limit and access list. Later transactions
+    /// of a block can't be replayed this way, and return the "method not found" error.
+    ///
+    /// Returns `None` if the transaction is not mined, did not fail, or if the replay did not
+    /// revert, e.g. because the transaction ran out of gas or hit an invalid opcode.
+    async fn get_revert_reason(&self, hash: TxHash) -> TransportResult<Option<RevertReason>> {
+        /// The top call frame of the `callTracer`.
+        #[derive(Debug, serde::Deserialize)]
+        struct CallFrame {
+            #[serde(default)]
+            output: Option<Bytes>,
+            #[serde(default)]
+            error: Option<String>,
+        }
+
+        let Some(receipt) = self.get_transaction_receipt(hash).await? else {
+            return Ok(None);
+        };
+        if receipt.status() {
+            return Ok(None);
+        }
+        let Some(block_number) = receipt.block_number() else {
+            return Ok(None);
+        };
+
+        let tracer = serde_json::json!({ "tracer": "callTracer" });
+        let not_found = match self
+            .client()
+            .request::<_, CallFrame>("debug_traceTransaction", (hash, tracer))
+            .await
+        {
+            Ok(frame) => {
+                return Ok(RevertReason::from_frame(hash, frame.error.as_deref(), frame.output))
+            }
+            Err(RpcError::ErrorResp(err)) if is_method_not_found(err.code, &err.message) => err,
+            Err(err) => return Err(err),
+        };
+        if receipt.transaction_index() != Some(0) {
+            return Err(RpcError::ErrorResp(not_found));
+        }
+
+        let tx = self.get_transaction_by_hash(hash).await?.ok_or(RpcError::NullResp)?;
+
+        let mut request = N::TransactionRequest::default()
+            .with_from(tx.from())
+            .with_input(tx.input().clone())
+            .with_value(tx.value())
+            .with_gas_limit(tx.gas());
+        match tx.to() {
+            Some(to) => request.set_to(to),
+            None => request.set_create(),
+        }
+        if let Some(access_list) = tx.access_list() {
+            request.set_access_list(access_list);
+        }
+
+        let parent = BlockId::number(block_number.saturating_sub(1));
+        match self.call(&request).block(parent).await {
+            Ok(_) => Ok(None),
+            Err(RpcError::ErrorResp(err)) => match err.as_revert_data() {
+                Some(data) => Ok(Some(RevertReason::new(hash, data))),
+                None if is_revert_message(&err.message) => {
+                    Ok(Some(RevertReason::new(hash, Bytes::new())))
+                }
+                None => Err(RpcError::ErrorResp(err)),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
     /// Gets an uncle block through the tag [BlockId] and index [u64].
     async fn get_uncle(&self, tag: BlockId, idx: u64) -> TransportResult<Option<N::BlockResponse>> {
         let idx = U64::from(idx);
@@ -644,13 +1155,20 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
 
     /// Broadcasts a raw transaction RLP bytes to the network.
     ///
+    /// The request is sent with [`RequestPriority::High`], so that layers may let it preempt
+    /// other traffic.
+    ///
     /// See [`send_transaction`](Self::send_transaction) for more details.
     async fn send_raw_transaction(
         &self,
         encoded_tx: &[u8],
     ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
         let rlp_hex = hex::encode_prefixed(encoded_tx);
-        let tx_hash = self.client().request("eth_sendRawTransaction", (rlp_hex,)).await?;
+        let tx_hash = self
+            .client()
+            .request("eth_sendRawTransaction", (rlp_hex,))
+            .with_priority(RequestPriority::High)
+            .await?;
         Ok(PendingTransactionBuilder::new(self.root(), tx_hash))
     }
 
@@ -829,6 +1347,72 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.root().get_subscription(id).await
     }
 
+    /// Returns a stream of the full bodies of pending transactions, matching the filters of the
+    /// given config.
+    ///
+    /// This subscribes with `eth_subscribe("newPendingTransactions", true)` if the node supports
+    /// it, and otherwise subscribes to the transaction hashes and fetches the bodies in batches of
+    /// `eth_getTransactionByHash` calls. Duplicate announcements are dropped in both cases.
+    ///
+    /// # Errors
+    ///
+    /// This method is only available on `pubsub` clients, such as WebSockets or IPC, and will
+    /// return a [`PubsubUnavailable`](alloy_transport::TransportErrorKind::PubsubUnavailable)
+    /// transport error if the client does not support it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_network_primitives::TransactionResponse;
+    /// use alloy_provider::PendingTransactionsConfig;
+    /// use futures::StreamExt;
+    ///
+    /// let config = PendingTransactionsConfig::default().with_filter(|tx| tx.to().is_none());
+    /// let mut deployments = provider.stream_full_pending_transactions(config).await?;
+    /// while let Some(tx) = deployments.next().await {
+    ///     println!("new pending deployment: {}", tx.tx_hash());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "pubsub")]
+    async fn stream_full_pending_transactions(
+        &self,
+        config: crate::PendingTransactionsConfig<N::TransactionResponse>,
+    ) -> TransportResult<crate::PendingTransactionStream<N::TransactionResponse>> {
+        use crate::mempool::{self, Pending};
+        use alloy_pubsub::SubscriptionItem;
+        use futures::StreamExt;
+
+        self.root().pubsub_frontend()?;
+        let announcements =
+            match self.client().request("eth_subscribe", ("newPendingTransactions", true)).await {
+                Ok(id) => {
+                    let sub = self.root().get_subscription::<N::TransactionResponse>(id).await?;
+                    // nodes that ignore the flag announce hashes instead
+                    sub.into_any_stream()
+                        .filter_map(|item| async move {
+                            match item {
+                                SubscriptionItem::Item(tx) => Some(Pending::Full(tx)),
+                                SubscriptionItem::Other(value) => {
+                                    serde_json::from_str(value.get()).ok().map(Pending::Hash)
+                                }
+                            }
+                        })
+                        .boxed()
+                }
+                Err(RpcError::ErrorResp(_)) => self
+                    .subscribe_pending_transactions()
+                    .await?
+                    .into_stream()
+                    .map(Pending::Hash)
+                    .boxed(),
+                Err(err) => return Err(err),
+            };
+        Ok(mempool::pending_transaction_stream(self.weak_client(), announcements, config))
+    }
+
     /// Subscribe to a stream of logs matching given filter.
     ///
     /// # Errors
@@ -869,6 +1453,38 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.root().get_subscription(id).await
     }
 
+    /// Streams the logs of the [Filter] from its `fromBlock`, first the historical logs up to the
+    /// latest block with [`get_logs_paginated`](Self::get_logs_paginated), then the new logs
+    /// from a [`subscribe_logs`](Self::subscribe_logs) subscription.
+    ///
+    /// The subscription is made before the historical logs are fetched, so that no block is
+    /// missed between them. The `toBlock` of the filter is ignored, and filters for a block hash
+    /// are rejected.
+    ///
+    /// # Errors
+    ///
+    /// This method is only available on `pubsub` clients, such as WebSockets or IPC, and will
+    /// return a [`PubsubUnavailable`](alloy_transport::TransportErrorKind::PubsubUnavailable)
+    /// transport error if the client does not support it.
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_logs_from(
+        &self,
+        filter: &Filter,
+        page_size: u64,
+    ) -> TransportResult<crate::LogStream> {
+        if filter.get_block_hash().is_some() {
+            return Err(RpcError::local_usage_str("cannot stream the logs of a block hash filter"));
+        }
+        let mut live = filter.clone();
+        live.block_option =
+            alloy_rpc_types_eth::FilterBlockOption::Range { from_block: None, to_block: None };
+        let subscription = self.subscribe_logs(&live).await?;
+
+        let tip = self.get_block_number().await?;
+        let historical = self.get_logs_paginated(&filter.clone().to_block(tip), page_size);
+        Ok(logs::backfill_then_live(historical.into_stream(), subscription.into_stream(), tip))
+    }
+
     /// Subscribe to an RPC event.
     #[cfg(feature = "pubsub")]
     #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
@@ -900,6 +1516,31 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request_noparams("web3_clientVersion").into()
     }
 
+    /// Returns the identity of the node, detected from `web3_clientVersion`.
+    ///
+    /// The identity is queried once and cached in the [`Capabilities`](crate::Capabilities) of
+    /// the [`RootProvider`]. Nodes that do not support `web3_clientVersion` are identified as
+    /// [`NodeKind::Unknown`](crate::NodeKind::Unknown).
+    async fn get_node_identity(&self) -> TransportResult<NodeIdentity> {
+        self.root().capabilities().discover_identity(self.client()).await.cloned()
+    }
+
+    /// Returns whether the endpoint supports the given RPC method.
+    ///
+    /// Support is discovered with `rpc_modules` and trial calls, and cached in the
+    /// [`Capabilities`](crate::Capabilities) of the [`RootProvider`], see there for details.
+    async fn supports(&self, method: &str) -> TransportResult<bool> {
+        self.root().capabilities().supports(self.client(), method).await
+    }
+
+    /// Returns whether the endpoint serves historical state, as an archive node does.
+    ///
+    /// This is probed once with a state query at the genesis block, and cached in the
+    /// [`Capabilities`](crate::Capabilities) of the [`RootProvider`], see there for details.
+    async fn is_archive(&self) -> TransportResult<bool> {
+        self.root().capabilities().discover_archive(self.client()).await
+    }
+
     /// Gets the `Keccak-256` hash of the given data.
     #[doc(alias = "web3_sha3")]
     fn get_sha3(&self, data: &[u8]) -> ProviderCall<T, (String,), B256> {
@@ -914,6 +1555,31 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
             .into()
     }
 
+    /// Gets the version of the Ethereum wire protocol of the node, e.g. `65` for `eth/65`.
+    ///
+    /// Post-merge nodes may not support this method.
+    #[doc(alias = "eth_protocolVersion")]
+    fn get_protocol_version(&self) -> ProviderCall<T, NoParams, U64, u64> {
+        self.client()
+            .request_noparams("eth_protocolVersion")
+            .map_resp(utils::convert_u64 as fn(U64) -> u64)
+            .into()
+    }
+
+    /// Gets the address receiving the mining rewards of the node.
+    #[doc(alias = "eth_coinbase")]
+    fn get_coinbase(&self) -> ProviderCall<T, NoParams, Address> {
+        self.client().request_noparams("eth_coinbase").into()
+    }
+
+    /// Returns whether the node is mining, e.g. a dev node with automine enabled.
+    ///
+    /// See [`MinerApi`](crate::ext::MinerApi) for the other mining methods.
+    #[doc(alias = "eth_mining")]
+    fn is_mining(&self) -> ProviderCall<T, NoParams, bool> {
+        self.client().request_noparams("eth_mining").into()
+    }
+
     /* ---------------------------------------- raw calls --------------------------------------- */
 
     /// Sends a raw JSON-RPC request.
@@ -1027,6 +1693,32 @@ impl<T: Transport + Clone, N: Network> Provider<T, N> for RootProvider<T, N> {
     }
 }
 
+/// Returns the base fee of the latest block of the fee history, falling back to the latest block
+/// if the fee history has none.
+async fn latest_base_fee<P, T, N>(provider: &P, fee_history: &FeeHistory) -> TransportResult<u128>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    // if the base fee of the Latest block is 0 then we need check if the latest block even has
+    // a base fee/supports EIP1559
+    match fee_history.latest_block_base_fee() {
+        Some(base_fee) if base_fee != 0 => Ok(base_fee),
+        _ => {
+            // empty response, fetch basefee from latest block directly
+            Ok(provider
+                .get_block_by_number(BlockNumberOrTag::Latest, false)
+                .await?
+                .ok_or(RpcError::NullResp)?
+                .header()
+                .base_fee_per_gas()
+                .ok_or(RpcError::UnsupportedFeature("eip1559"))?
+                .into())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -1477,6 +2169,115 @@ mod tests {
         assert_eq!(count, 0);
     }
 
+    #[tokio::test]
+    async fn gets_state_at_block() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let sender = provider.get_accounts().await.unwrap()[0];
+        let to = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+
+        let tx = TransactionRequest {
+            value: Some(U256::from(100)),
+            from: Some(sender),
+            to: Some(to.into()),
+            gas_price: Some(20e9 as u128),
+            gas: Some(21000),
+            ..Default::default()
+        };
+        provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+
+        let genesis = provider.at_block(0.into());
+        assert_eq!(genesis.block_id(), BlockId::number(0));
+        assert_eq!(genesis.get_balance(to).await.unwrap(), U256::ZERO);
+        assert_eq!(genesis.get_transaction_count(sender).await.unwrap(), 0);
+        assert!(genesis.get_code_at(to).await.unwrap().is_empty());
+        assert_eq!(genesis.get_storage_at(to, U256::ZERO).await.unwrap(), U256::ZERO);
+
+        let latest = provider.at_block(BlockId::latest());
+        assert_eq!(latest.get_balance(to).await.unwrap(), U256::from(100));
+        assert_eq!(latest.get_transaction_count(sender).await.unwrap(), 1);
+        // requests can still be moved to another block
+        assert_eq!(latest.get_balance(to).block_id(0.into()).await.unwrap(), U256::ZERO);
+    }
+
+    #[tokio::test]
+    async fn gets_balance_changes() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let sender = provider.get_accounts().await.unwrap()[0];
+        let to = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+
+        let other = Address::with_last_byte(1);
+        for (recipient, value) in [(to, 100), (other, 1), (other, 1), (to, 50)] {
+            let tx = TransactionRequest {
+                value: Some(U256::from(value)),
+                from: Some(sender),
+                to: Some(recipient.into()),
+                gas_price: Some(20e9 as u128),
+                gas: Some(21000),
+                ..Default::default()
+            };
+            provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+        }
+        // blocks 2 and 3 do not touch the account
+        let latest = provider.get_block_number().await.unwrap();
+        assert_eq!(latest, 4);
+        assert!(provider.is_archive().await.unwrap());
+
+        let changes = provider.get_balance_changes(to, 0, latest).await.unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                StateChange { block_number: 1, before: U256::ZERO, after: U256::from(100) },
+                StateChange { block_number: 4, before: U256::from(100), after: U256::from(150) },
+            ]
+        );
+        assert!(provider.get_storage_changes(to, U256::ZERO, 0, latest).await.unwrap().is_empty());
+        assert!(provider
+            .diff_storage(to, vec![U256::ZERO], 0.into(), BlockId::latest())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn gets_funding_and_activity() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let sender = provider.get_accounts().await.unwrap()[0];
+        let to = Address::with_last_byte(7);
+
+        let mut hashes = Vec::new();
+        for recipient in [Address::with_last_byte(1), to, to] {
+            let tx = TransactionRequest::default()
+                .with_from(sender)
+                .with_to(recipient)
+                .with_value(U256::from(10));
+            let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+            hashes.push(receipt.transaction_hash);
+        }
+
+        let funding = provider.get_funding(to, 0, 3).await.unwrap().unwrap();
+        assert_eq!(
+            funding,
+            history::Funding {
+                block_number: 2,
+                transaction_hash: Some(hashes[1]),
+                funder: Some(sender),
+                value: U256::from(10),
+            }
+        );
+        assert!(provider.get_funding(Address::with_last_byte(8), 0, 3).await.unwrap().is_none());
+
+        let activity = provider.get_address_activity(sender, 0, 3).await.unwrap().unwrap();
+        assert_eq!(
+            activity,
+            history::AddressActivity { first_seen: 0, last_sent: Some(3), nonce: 3 }
+        );
+        let activity = provider.get_address_activity(to, 0, 3).await.unwrap().unwrap();
+        assert_eq!(activity, history::AddressActivity { first_seen: 2, last_sent: None, nonce: 0 });
+    }
+
     #[tokio::test]
     async fn gets_block_by_hash() {
         init_tracing();
@@ -1490,6 +2291,27 @@ mod tests {
         assert_eq!(block.header.hash, hash);
     }
 
+    #[tokio::test]
+    async fn gets_canonical_block_by_hash() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let block = provider.get_block_by_number(0.into(), false).await.unwrap().unwrap();
+        let hash = block.header.hash;
+        let id = BlockId::hash_canonical(hash);
+        let block = provider.get_block(id, BlockTransactionsKind::Hashes).await.unwrap().unwrap();
+        assert_eq!(block.header.hash, hash);
+
+        let balance = provider.get_balance(Address::ZERO).block_id(id).await.unwrap();
+        assert_eq!(balance, U256::ZERO);
+
+        let missing = BlockId::hash_canonical(B256::repeat_byte(1));
+        assert!(provider
+            .get_block(missing, BlockTransactionsKind::Hashes)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
     #[tokio::test]
     async fn gets_block_by_hash_with_raw_req() {
         init_tracing();
@@ -1533,6 +2355,27 @@ mod tests {
         assert!(version.contains("anvil"), "{version}");
     }
 
+    #[tokio::test]
+    async fn probes_supported_methods() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        assert!(provider.supports("eth_getBlockReceipts").await.unwrap());
+        assert!(!provider.supports("eth_notAMethod").await.unwrap());
+        assert_eq!(provider.root().capabilities().get("eth_notAMethod"), Some(false));
+
+        provider.root().capabilities().set("eth_getBlockReceipts", false);
+        assert!(!provider.supports("eth_getBlockReceipts").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn gets_node_identity() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let identity = provider.get_node_identity().await.unwrap();
+        assert_eq!(identity.kind, crate::NodeKind::Anvil);
+        assert!(identity.version.is_some());
+        assert_eq!(provider.root().capabilities().identity(), Some(&identity));
+    }
+
     #[tokio::test]
     async fn gets_sha3() {
         init_tracing();
@@ -1561,6 +2404,16 @@ mod tests {
         assert_eq!(chain_id, dev_chain_id);
     }
 
+    #[tokio::test]
+    async fn gets_misc_node_info() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+
+        assert!(provider.get_protocol_version().await.is_ok());
+        assert!(provider.get_coinbase().await.is_ok());
+        assert!(provider.is_mining().await.unwrap());
+    }
+
     #[tokio::test]
     async fn gets_storage_at() {
         init_tracing();
@@ -1581,6 +2434,174 @@ mod tests {
         assert!(tx.is_none());
     }
 
+    #[tokio::test]
+    async fn gets_transaction_and_receipt_proofs() {
+        init_tracing();
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_wallet();
+
+        let req = TransactionRequest::default()
+            .from(provider.default_signer_address())
+            .to(Address::repeat_byte(5))
+            .value(U256::from(1));
+        let receipt = provider.send_transaction(req).await.unwrap().get_receipt().await.unwrap();
+
+        let block = provider
+            .get_block_by_hash(receipt.block_hash.unwrap(), BlockTransactionsKind::Hashes)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let tx_proof =
+            provider.get_transaction_proof(receipt.transaction_hash).await.unwrap().unwrap();
+        tx_proof.verify(block.header.transactions_root).unwrap();
+
+        let receipt_proof =
+            provider.get_receipt_proof(receipt.transaction_hash).await.unwrap().unwrap();
+        receipt_proof.verify(block.header.receipts_root).unwrap();
+
+        let missing = b256!("5c03fab9114ceb98994b43892ade87ddfd9ae7e8f293935c3bd29d435dc9fd95");
+        assert!(provider.get_transaction_proof(missing).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn gets_revert_reason_not_found() {
+        init_tracing();
+
+        let provider = ProviderBuilder::new().on_anvil();
+        let tx_hash = b256!("5c03fab9114ceb98994b43892ade87ddfd9ae7e8f293935c3bd29d435dc9fd95");
+        let reason = provider.get_revert_reason(tx_hash).await.expect("failed to replay tx");
+
+        assert!(reason.is_none());
+    }
+
+    /// Returns a provider answering the methods with the given results, or with "method not
+    /// found" for other methods.
+    fn mock_provider(
+        responses: Vec<(&'static str, serde_json::Value)>,
+    ) -> RootProvider<BoxTransport> {
+        use alloy_json_rpc::{
+            ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload,
+        };
+        use alloy_rpc_client::RpcClient;
+        use alloy_transport::TransportFut;
+
+        let transport = tower::service_fn(move |request: RequestPacket| -> TransportFut<'static> {
+            let RequestPacket::Single(request) = request else { unreachable!() };
+            let payload = match responses.iter().find(|(method, _)| *method == request.method()) {
+                Some((_, serde_json::Value::Object(error))) if error.contains_key("code") => {
+                    ResponsePayload::Failure(
+                        serde_json::from_value::<ErrorPayload>(error.clone().into()).unwrap(),
+                    )
+                }
+                Some((_, result)) => {
+                    ResponsePayload::Success(serde_json::value::to_raw_value(result).unwrap())
+                }
+                None => ResponsePayload::Failure(ErrorPayload::method_not_found()),
+            };
+            let response = Response { id: request.id().clone(), payload };
+            Box::pin(async move { Ok(ResponsePacket::Single(response)) })
+        });
+        RootProvider::new(RpcClient::new(BoxTransport::new(transport), true))
+    }
+
+    fn failed_receipt(transaction_index: u64) -> serde_json::Value {
+        serde_json::json!({
+            "blockHash": B256::with_last_byte(1),
+            "blockNumber": "0x2",
+            "contractAddress": null,
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "from": Address::with_last_byte(1),
+            "gasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "status": "0x0",
+            "to": Address::with_last_byte(2),
+            "transactionHash": B256::with_last_byte(3),
+            "transactionIndex": U64::from(transaction_index),
+            "type": "0x2"
+        })
+    }
+
+    #[tokio::test]
+    async fn gets_revert_reason_from_trace() {
+        use alloy_sol_types::{Revert, SolError};
+
+        let output = Bytes::from(Revert::from("not owner").abi_encode());
+        let provider = mock_provider(vec![
+            ("eth_getTransactionReceipt", failed_receipt(1)),
+            (
+                "debug_traceTransaction",
+                serde_json::json!({
+                    "type": "CALL",
+                    "from": Address::with_last_byte(1),
+                    "to": Address::with_last_byte(2),
+                    "gas": "0x5208",
+                    "gasUsed": "0x5208",
+                    "input": "0x",
+                    "output": output,
+                    "error": "execution reverted",
+                    "revertReason": "not owner"
+                }),
+            ),
+        ]);
+
+        let reason = provider.get_revert_reason(B256::with_last_byte(3)).await.unwrap().unwrap();
+        assert_eq!(reason.data, output);
+        assert_eq!(reason.reason().as_deref(), Some("revert: not owner"));
+    }
+
+    #[tokio::test]
+    async fn gets_revert_reason_from_call() {
+        use alloy_sol_types::{Revert, SolError};
+
+        let output = Bytes::from(Revert::from("not owner").abi_encode());
+        let transaction = serde_json::json!({
+            "hash": B256::with_last_byte(3),
+            "nonce": "0x0",
+            "blockHash": B256::with_last_byte(1),
+            "blockNumber": "0x2",
+            "transactionIndex": "0x0",
+            "from": Address::with_last_byte(1),
+            "to": Address::with_last_byte(2),
+            "value": "0x0",
+            "gas": "0x5208",
+            "input": "0x",
+            "type": "0x2",
+            "chainId": "0x1",
+            "maxFeePerGas": "0x1",
+            "maxPriorityFeePerGas": "0x1",
+            "accessList": [],
+            "yParity": "0x0",
+            "v": "0x0",
+            "r": "0x1",
+            "s": "0x1"
+        });
+        let mut responses = vec![
+            ("eth_getTransactionReceipt", failed_receipt(0)),
+            ("eth_getTransactionByHash", transaction),
+            (
+                "eth_call",
+                serde_json::json!({
+                    "code": 3,
+                    "message": "execution reverted: not owner",
+                    "data": output
+                }),
+            ),
+        ];
+
+        let provider = mock_provider(responses.clone());
+        let reason = provider.get_revert_reason(B256::with_last_byte(3)).await.unwrap().unwrap();
+        assert_eq!(reason.data, output);
+        assert_eq!(reason.reason().as_deref(), Some("revert: not owner"));
+
+        // later transactions of a block depend on the transactions before them
+        responses[0].1 = failed_receipt(1);
+        let provider = mock_provider(responses);
+        let err = provider.get_revert_reason(B256::with_last_byte(3)).await.unwrap_err();
+        assert_eq!(err.as_error_resp().map(|err| err.code), Some(-32601));
+    }
+
     #[tokio::test]
     async fn gets_transaction_by_hash() {
         init_tracing();
@@ -1660,6 +2681,21 @@ mod tests {
         assert_eq!(fee_history.oldest_block, 0_u64);
     }
 
+    #[tokio::test]
+    async fn estimates_eip1559_fees_with_params() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let params = Eip1559EstimatorParams::default()
+            .with_block_count(5)
+            .with_reward_percentiles([10.0, 50.0, 90.0])
+            .with_base_fee_multiplier_percent(125);
+        let estimate = provider.estimate_eip1559_fees_with(&params).await.unwrap();
+        assert_eq!(estimate.priority_fees.len(), 3);
+        assert_eq!(estimate.max_priority_fee_per_gas, estimate.priority_fees[0]);
+        assert!(estimate.max_fee_per_gas > estimate.base_fee_per_gas);
+        assert!(estimate.next_block_base_fee.is_some());
+    }
+
     #[tokio::test]
     async fn gets_block_receipts() {
         init_tracing();
@@ -1669,6 +2705,25 @@ mod tests {
         assert!(receipts.is_some());
     }
 
+    #[tokio::test]
+    async fn verifies_block() {
+        init_tracing();
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_wallet();
+
+        let req = TransactionRequest::default()
+            .from(provider.default_signer_address())
+            .to(Address::repeat_byte(5))
+            .value(U256::from(1));
+        let receipt = provider.send_transaction(req).await.unwrap().get_receipt().await.unwrap();
+
+        let block = BlockId::hash(receipt.block_hash.unwrap());
+        let verification =
+            provider.verify_block(block, BlobParams::cancun()).await.unwrap().unwrap();
+        assert_eq!(verification.mismatches, []);
+        let missing = provider.verify_block(BlockId::number(u64::MAX), BlobParams::cancun());
+        assert!(missing.await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn sends_raw_transaction() {
         init_tracing();
@@ -0,0 +1,279 @@
+use alloy_consensus::{
+    proofs::{calculate_receipt_root, calculate_transaction_root, calculate_withdrawals_root},
+    TxReceipt,
+};
+use alloy_eips::{
+    eip2718::Encodable2718,
+    eip4844::{BlobParams, DATA_GAS_PER_BLOB},
+};
+use alloy_json_rpc::RpcError;
+use alloy_network::{BlockResponse, HeaderResponse, Network, TransactionResponse};
+use alloy_primitives::{BlockHash, BlockNumber, Bloom, B256};
+use alloy_rpc_types_eth::BlockId;
+use alloy_transport::{Transport, TransportResult};
+
+use crate::Provider;
+
+/// A header field that does not match the contents of its block, found by
+/// [`Provider::verify_block`](crate::Provider::verify_block).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockMismatch {
+    /// The `transactionsRoot` is not the root of the transactions.
+    TransactionsRoot {
+        /// The root of the header.
+        header: B256,
+        /// The root of the transactions.
+        computed: B256,
+    },
+    /// The `receiptsRoot` is not the root of the receipts.
+    ReceiptsRoot {
+        /// The root of the header.
+        header: B256,
+        /// The root of the receipts.
+        computed: B256,
+    },
+    /// The `logsBloom` is not the union of the blooms of the receipts.
+    LogsBloom {
+        /// The bloom of the header.
+        header: Box<Bloom>,
+        /// The union of the blooms of the receipts.
+        computed: Box<Bloom>,
+    },
+    /// The `gasUsed` is not the cumulative gas used by the receipts.
+    GasUsed {
+        /// The gas used of the header.
+        header: u64,
+        /// The cumulative gas used of the last receipt.
+        computed: u64,
+    },
+    /// The `blobGasUsed` is not the blob gas of the blobs of the transactions.
+    BlobGasUsed {
+        /// The blob gas used of the header.
+        header: Option<u64>,
+        /// The blob gas of the blobs of the transactions.
+        computed: u64,
+    },
+    /// The `excessBlobGas` does not follow from the parent block, with the blob parameters of
+    /// the fork of the block.
+    ExcessBlobGas {
+        /// The excess blob gas of the header.
+        header: u64,
+        /// The excess blob gas following from the parent block.
+        computed: u64,
+    },
+    /// The `withdrawalsRoot` is not the root of the withdrawals, or only one of them is present.
+    WithdrawalsRoot {
+        /// The root of the header.
+        header: Option<B256>,
+        /// The root of the withdrawals.
+        computed: Option<B256>,
+    },
+}
+
+/// The result of [`Provider::verify_block`](crate::Provider::verify_block).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockVerification {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block, as returned by the node.
+    pub hash: BlockHash,
+    /// The header fields that do not match the contents of the block.
+    pub mismatches: Vec<BlockMismatch>,
+}
+
+impl BlockVerification {
+    /// Verifies the header of the block against its transactions, in the order of the block, and
+    /// their receipts.
+    ///
+    /// The excess blob gas is only verified if the header of the parent block is given, with the
+    /// blob parameters of the fork of the block, e.g. [`BlobParams::prague`].
+    pub fn new<B: BlockResponse>(
+        block: &B,
+        transactions: &[impl Encodable2718],
+        receipts: &[impl Encodable2718 + TxReceipt],
+        parent: Option<&B::Header>,
+        blob_params: BlobParams,
+    ) -> Self {
+        let header = block.header();
+        let mut mismatches = Vec::new();
+
+        let computed = calculate_transaction_root(transactions);
+        if computed != header.transactions_root() {
+            mismatches.push(BlockMismatch::TransactionsRoot {
+                header: header.transactions_root(),
+                computed,
+            });
+        }
+
+        let computed = calculate_receipt_root(receipts);
+        if computed != header.receipts_root() {
+            mismatches
+                .push(BlockMismatch::ReceiptsRoot { header: header.receipts_root(), computed });
+        }
+
+        let computed = receipts.iter().fold(Bloom::ZERO, |bloom, receipt| bloom | receipt.bloom());
+        if computed != header.logs_bloom() {
+            mismatches.push(BlockMismatch::LogsBloom {
+                header: Box::new(header.logs_bloom()),
+                computed: Box::new(computed),
+            });
+        }
+
+        let computed = receipts.last().map_or(0, |receipt| receipt.cumulative_gas_used() as u64);
+        if computed != header.gas_used() {
+            mismatches.push(BlockMismatch::GasUsed { header: header.gas_used(), computed });
+        }
+
+        let blobs = block
+            .transactions()
+            .txns()
+            .filter_map(|tx| tx.blob_versioned_hashes())
+            .map(|hashes| hashes.len() as u64)
+            .sum::<u64>();
+        let computed = blobs * DATA_GAS_PER_BLOB;
+        if header.blob_gas_used().map_or(computed != 0, |used| used != computed) {
+            mismatches
+                .push(BlockMismatch::BlobGasUsed { header: header.blob_gas_used(), computed });
+        }
+
+        if let (Some(excess), Some(parent)) = (header.excess_blob_gas(), parent) {
+            // the parent of the first block of the fork has no blob gas fields, which count as 0
+            let computed = blob_params.next_block_excess_blob_gas(
+                parent.excess_blob_gas().unwrap_or_default(),
+                parent.blob_gas_used().unwrap_or_default(),
+            );
+            if excess != computed {
+                mismatches.push(BlockMismatch::ExcessBlobGas { header: excess, computed });
+            }
+        }
+
+        let computed = block.withdrawals().map(calculate_withdrawals_root);
+        if computed != header.withdrawals_root() {
+            mismatches.push(BlockMismatch::WithdrawalsRoot {
+                header: header.withdrawals_root(),
+                computed,
+            });
+        }
+
+        Self { number: header.number(), hash: header.hash(), mismatches }
+    }
+
+    /// Returns `true` if the header matches the contents of the block.
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Fetches the receipts of the block with `eth_getBlockReceipts`, or one by one if the endpoint
+/// does not [support](Provider::supports) it.
+pub(crate) async fn get_receipts_of<P, T, N>(
+    provider: &P,
+    block: &N::BlockResponse,
+) -> TransportResult<Vec<N::ReceiptEnvelope>>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+    N::ReceiptEnvelope: From<N::ReceiptResponse>,
+{
+    let receipts = if provider.supports("eth_getBlockReceipts").await? {
+        provider
+            .get_block_receipts(BlockId::hash(block.header().hash()))
+            .await?
+            .ok_or(RpcError::NullResp)?
+    } else {
+        let mut receipts = Vec::new();
+        for hash in block.transactions().hashes() {
+            receipts.push(provider.get_transaction_receipt(hash).await?.ok_or(RpcError::NullResp)?);
+        }
+        receipts
+    };
+    Ok(receipts.into_iter().map(Into::into).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{constants::EMPTY_ROOT_HASH, ReceiptEnvelope, TxEnvelope};
+    use alloy_primitives::b256;
+    use alloy_rpc_types_eth::{Block, BlockTransactions, Header, Transaction};
+
+    #[test]
+    fn verifies_empty_blocks() {
+        let header = Header {
+            transactions_root: EMPTY_ROOT_HASH,
+            receipts_root: EMPTY_ROOT_HASH,
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        };
+        let mut block = Block::<Transaction> {
+            header,
+            transactions: BlockTransactions::Full(vec![]),
+            withdrawals: Some(vec![]),
+            ..Default::default()
+        };
+        let verify = |block: &Block, parent: Option<&Header>| {
+            BlockVerification::new(
+                block,
+                &[] as &[TxEnvelope],
+                &[] as &[ReceiptEnvelope],
+                parent,
+                BlobParams::cancun(),
+            )
+            .mismatches
+        };
+        assert_eq!(verify(&block, None), []);
+
+        block.header.gas_used = 21_000;
+        block.header.withdrawals_root = None;
+        let root = b256!("0000000000000000000000000000000000000000000000000000000000000001");
+        block.header.transactions_root = root;
+        assert_eq!(
+            verify(&block, None),
+            [
+                BlockMismatch::TransactionsRoot { header: root, computed: EMPTY_ROOT_HASH },
+                BlockMismatch::GasUsed { header: 21_000, computed: 0 },
+                BlockMismatch::WithdrawalsRoot { header: None, computed: Some(EMPTY_ROOT_HASH) },
+            ]
+        );
+
+        let parent = Header {
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(8 * DATA_GAS_PER_BLOB),
+            ..Default::default()
+        };
+        assert_eq!(
+            verify(&block, Some(&parent))[2],
+            BlockMismatch::ExcessBlobGas { header: 0, computed: 5 * DATA_GAS_PER_BLOB }
+        );
+
+        // the Prague excess blob gas is not valid for a Cancun block
+        block.header.excess_blob_gas = Some(2 * DATA_GAS_PER_BLOB);
+        assert_eq!(
+            verify(&block, Some(&parent))[2],
+            BlockMismatch::ExcessBlobGas {
+                header: 2 * DATA_GAS_PER_BLOB,
+                computed: 5 * DATA_GAS_PER_BLOB
+            }
+        );
+        let mismatches = BlockVerification::new(
+            &block,
+            &[] as &[TxEnvelope],
+            &[] as &[ReceiptEnvelope],
+            Some(&parent),
+            BlobParams::prague(),
+        )
+        .mismatches;
+        assert_eq!(mismatches.len(), 3);
+        assert!(!mismatches.iter().any(|m| matches!(m, BlockMismatch::ExcessBlobGas { .. })));
+
+        // the parent of the first Cancun block has no blob gas fields
+        let mismatches = verify(&block, Some(&Header::default()));
+        assert_eq!(
+            mismatches[2],
+            BlockMismatch::ExcessBlobGas { header: 2 * DATA_GAS_PER_BLOB, computed: 0 }
+        );
+    }
+}
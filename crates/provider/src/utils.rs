@@ -24,10 +24,16 @@ pub struct Eip1559Estimation {
 }
 
 fn estimate_priority_fee(rewards: &[Vec<u128>]) -> u128 {
+    median_reward(rewards, 0, EIP1559_MIN_PRIORITY_FEE)
+}
+
+/// Returns the median of the non-zero rewards at the given percentile index, or `min` if there are
+/// none.
+fn median_reward(rewards: &[Vec<u128>], index: usize, min: u128) -> u128 {
     let mut rewards =
-        rewards.iter().filter_map(|r| r.first()).filter(|r| **r > 0_u128).collect::<Vec<_>>();
+        rewards.iter().filter_map(|r| r.get(index)).filter(|r| **r > 0_u128).collect::<Vec<_>>();
     if rewards.is_empty() {
-        return EIP1559_MIN_PRIORITY_FEE;
+        return min;
     }
 
     rewards.sort_unstable();
@@ -37,7 +43,120 @@ fn estimate_priority_fee(rewards: &[Vec<u128>]) -> u128 {
     let median =
         if n % 2 == 0 { (*rewards[n / 2 - 1] + *rewards[n / 2]) / 2 } else { *rewards[n / 2] };
 
-    std::cmp::max(median, EIP1559_MIN_PRIORITY_FEE)
+    std::cmp::max(median, min)
+}
+
+/// The parameters of [`Provider::estimate_eip1559_fees_with`].
+///
+/// The default parameters match the estimation of [`Provider::estimate_eip1559_fees`].
+///
+/// [`Provider::estimate_eip1559_fees_with`]: crate::Provider::estimate_eip1559_fees_with
+/// [`Provider::estimate_eip1559_fees`]: crate::Provider::estimate_eip1559_fees
+#[derive(Clone, Debug, PartialEq)]
+pub struct Eip1559EstimatorParams {
+    /// The number of past blocks to fetch the fee history of.
+    pub block_count: u64,
+    /// The percentiles of the priority fees of each block to fetch.
+    ///
+    /// The suggested priority fee is the median of the first percentile over all blocks.
+    pub reward_percentiles: Vec<f64>,
+    /// The multiplier of the base fee used for the max fee per gas, in percent, which allows the
+    /// transaction to stay valid if the base fee surges over the next blocks.
+    pub base_fee_multiplier_percent: u128,
+    /// The minimum priority fee to suggest.
+    pub min_priority_fee: u128,
+}
+
+impl Default for Eip1559EstimatorParams {
+    fn default() -> Self {
+        Self {
+            block_count: EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+            reward_percentiles: vec![EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
+            base_fee_multiplier_percent: EIP1559_BASE_FEE_MULTIPLIER * 100,
+            min_priority_fee: EIP1559_MIN_PRIORITY_FEE,
+        }
+    }
+}
+
+impl Eip1559EstimatorParams {
+    /// Sets the number of past blocks to fetch the fee history of.
+    pub const fn with_block_count(mut self, block_count: u64) -> Self {
+        self.block_count = block_count;
+        self
+    }
+
+    /// Sets the percentiles of the priority fees to fetch.
+    pub fn with_reward_percentiles(mut self, reward_percentiles: impl Into<Vec<f64>>) -> Self {
+        self.reward_percentiles = reward_percentiles.into();
+        self
+    }
+
+    /// Sets the multiplier of the base fee used for the max fee per gas, in percent, e.g. `150`
+    /// for 1.5x the base fee.
+    pub const fn with_base_fee_multiplier_percent(
+        mut self,
+        base_fee_multiplier_percent: u128,
+    ) -> Self {
+        self.base_fee_multiplier_percent = base_fee_multiplier_percent;
+        self
+    }
+
+    /// Sets the minimum priority fee to suggest.
+    pub const fn with_min_priority_fee(mut self, min_priority_fee: u128) -> Self {
+        self.min_priority_fee = min_priority_fee;
+        self
+    }
+
+    /// Estimates the fees from the base fee of the latest block and the rewards of the fee
+    /// history.
+    pub fn estimate(
+        &self,
+        base_fee_per_gas: u128,
+        next_block_base_fee: Option<u128>,
+        rewards: &[Vec<u128>],
+    ) -> Eip1559FeeEstimate {
+        let priority_fees = (0..self.reward_percentiles.len())
+            .map(|index| median_reward(rewards, index, self.min_priority_fee))
+            .collect::<Vec<_>>();
+        let max_priority_fee_per_gas =
+            priority_fees.first().copied().unwrap_or(self.min_priority_fee);
+        let max_base_fee_per_gas =
+            base_fee_per_gas.saturating_mul(self.base_fee_multiplier_percent) / 100;
+
+        Eip1559FeeEstimate {
+            base_fee_per_gas,
+            next_block_base_fee,
+            max_fee_per_gas: max_base_fee_per_gas + max_priority_fee_per_gas,
+            max_priority_fee_per_gas,
+            priority_fees,
+        }
+    }
+}
+
+/// Return type of [`Provider::estimate_eip1559_fees_with`].
+///
+/// [`Provider::estimate_eip1559_fees_with`]: crate::Provider::estimate_eip1559_fees_with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Eip1559FeeEstimate {
+    /// The base fee per gas of the latest block.
+    pub base_fee_per_gas: u128,
+    /// The projected base fee per gas of the next block, if returned by the node.
+    pub next_block_base_fee: Option<u128>,
+    /// The suggested max fee per gas, the multiplied base fee plus the priority fee.
+    pub max_fee_per_gas: u128,
+    /// The suggested max priority fee per gas.
+    pub max_priority_fee_per_gas: u128,
+    /// The median priority fee per gas of each of the requested reward percentiles.
+    pub priority_fees: Vec<u128>,
+}
+
+impl From<Eip1559FeeEstimate> for Eip1559Estimation {
+    fn from(estimate: Eip1559FeeEstimate) -> Self {
+        Self {
+            max_fee_per_gas: estimate.max_fee_per_gas,
+            max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+        }
+    }
 }
 
 /// The default EIP-1559 fee estimator.
@@ -124,4 +243,36 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_eip1559_estimator_params() {
+        let rewards = vec![
+            vec![1_000_000_000_u128, 5_000_000_000_u128],
+            vec![2_000_000_000_u128, 6_000_000_000_u128],
+            vec![3_000_000_000_u128, 9_000_000_000_u128],
+        ];
+
+        // the default params match the default estimator
+        let estimate = Eip1559EstimatorParams::default().estimate(10_000_000_000, None, &rewards);
+        assert_eq!(
+            Eip1559Estimation::from(estimate),
+            eip1559_default_estimator(10_000_000_000, &rewards)
+        );
+
+        let estimate = Eip1559EstimatorParams::default()
+            .with_reward_percentiles([10.0, 90.0])
+            .with_base_fee_multiplier_percent(150)
+            .with_min_priority_fee(2_500_000_000)
+            .estimate(10_000_000_000, Some(11_000_000_000), &rewards);
+        assert_eq!(
+            estimate,
+            Eip1559FeeEstimate {
+                base_fee_per_gas: 10_000_000_000,
+                next_block_base_fee: Some(11_000_000_000),
+                max_fee_per_gas: 17_500_000_000,
+                max_priority_fee_per_gas: 2_500_000_000,
+                priority_fees: vec![2_500_000_000, 6_000_000_000],
+            }
+        );
+    }
 }
@@ -0,0 +1,94 @@
+use alloy_primitives::B256;
+use futures::{ready, Stream, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+
+/// The number of events buffered for each [`ConnectionEvents`] stream.
+pub(crate) const EVENTS_CHANNEL_SIZE: usize = 16;
+
+/// A change in the connection state of a pubsub service.
+///
+/// See [`PubSubFrontend::connection_events`].
+///
+/// [`PubSubFrontend::connection_events`]: crate::PubSubFrontend::connection_events
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectionEvent {
+    /// The backend has connected to the server.
+    Connected,
+    /// The connection to the server has been lost.
+    Disconnected {
+        /// Why the connection was lost.
+        reason: String,
+    },
+    /// The service is connecting a new backend.
+    Reconnecting {
+        /// The number of reconnections made by the service, including this
+        /// one.
+        attempt: u32,
+    },
+    /// A subscription could not be re-established after a reconnection and
+    /// has been dropped, ending its streams.
+    SubscriptionDropped {
+        /// The local ID of the subscription.
+        id: B256,
+    },
+}
+
+/// A stream of [`ConnectionEvent`]s.
+///
+/// Events are buffered, and the oldest events are skipped if the stream is not
+/// polled fast enough. The stream ends when the pubsub service stops.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ConnectionEvents {
+    inner: BroadcastStream<ConnectionEvent>,
+}
+
+impl ConnectionEvents {
+    pub(crate) fn new(rx: broadcast::Receiver<ConnectionEvent>) -> Self {
+        Self { inner: BroadcastStream::new(rx) }
+    }
+}
+
+impl Stream for ConnectionEvents {
+    type Item = ConnectionEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(event)) => return Poll::Ready(Some(event)),
+                Some(Err(BroadcastStreamRecvError::Lagged(count))) => {
+                    debug!(count, "connection events lagged");
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn skips_lagged_events() {
+        let (tx, rx) = broadcast::channel(2);
+        let events = ConnectionEvents::new(rx);
+        for attempt in 1..=3 {
+            tx.send(ConnectionEvent::Reconnecting { attempt }).unwrap();
+        }
+        tx.send(ConnectionEvent::Connected).unwrap();
+        drop(tx);
+
+        let events = events.collect::<Vec<_>>().await;
+        assert_eq!(
+            events,
+            [ConnectionEvent::Reconnecting { attempt: 3 }, ConnectionEvent::Connected]
+        );
+    }
+}
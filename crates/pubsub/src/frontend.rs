@@ -1,14 +1,20 @@
-use crate::{ix::PubSubInstruction, managers::InFlight, RawSubscription};
+use crate::{
+    ix::PubSubInstruction, managers::InFlight, ConnectionEvent, ConnectionEvents, LagPolicy,
+    RawSubscription,
+};
 use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
 use alloy_primitives::B256;
 use alloy_transport::{TransportError, TransportErrorKind, TransportFut, TransportResult};
-use futures::{future::try_join_all, FutureExt, TryFutureExt};
+use futures::{
+    future::{join_all, try_join_all},
+    FutureExt, TryFutureExt,
+};
 use std::{
     future::Future,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 
 /// A `PubSubFrontend` is [`Transport`] composed of a channel to a running
 /// PubSub service.
@@ -17,22 +23,40 @@ use tokio::sync::{mpsc, oneshot};
 #[derive(Debug)]
 pub struct PubSubFrontend {
     tx: mpsc::UnboundedSender<PubSubInstruction>,
+    /// The sender of the connection events of the service.
+    events: broadcast::Sender<ConnectionEvent>,
     /// The number of items to buffer in new subscription channels. Defaults to
     /// 16. See [`tokio::sync::broadcast::channel`] for a description.
     channel_size: AtomicUsize,
+    /// The [`LagPolicy`] of new subscriptions.
+    lag_policy: AtomicU8,
 }
 
 impl Clone for PubSubFrontend {
     fn clone(&self) -> Self {
         let channel_size = self.channel_size.load(Ordering::Relaxed);
-        Self { tx: self.tx.clone(), channel_size: AtomicUsize::new(channel_size) }
+        let lag_policy = self.lag_policy.load(Ordering::Relaxed);
+        Self {
+            tx: self.tx.clone(),
+            events: self.events.clone(),
+            channel_size: AtomicUsize::new(channel_size),
+            lag_policy: AtomicU8::new(lag_policy),
+        }
     }
 }
 
 impl PubSubFrontend {
     /// Create a new frontend.
-    pub(crate) const fn new(tx: mpsc::UnboundedSender<PubSubInstruction>) -> Self {
-        Self { tx, channel_size: AtomicUsize::new(16) }
+    pub(crate) const fn new(
+        tx: mpsc::UnboundedSender<PubSubInstruction>,
+        events: broadcast::Sender<ConnectionEvent>,
+    ) -> Self {
+        Self {
+            tx,
+            events,
+            channel_size: AtomicUsize::new(16),
+            lag_policy: AtomicU8::new(LagPolicy::DropOldest.to_u8()),
+        }
     }
 
     /// Get the subscription ID for a local ID.
@@ -50,6 +74,12 @@ impl PubSubFrontend {
         }
     }
 
+    /// Get a stream of the connection events of the service, starting with
+    /// the next event.
+    pub fn connection_events(&self) -> ConnectionEvents {
+        ConnectionEvents::new(self.events.subscribe())
+    }
+
     /// Unsubscribe from a subscription.
     pub fn unsubscribe(&self, id: B256) -> TransportResult<()> {
         self.tx
@@ -57,6 +87,26 @@ impl PubSubFrontend {
             .map_err(|_| TransportErrorKind::backend_gone())
     }
 
+    /// Shut down the pubsub service.
+    ///
+    /// This unsubscribes from all subscriptions with `eth_unsubscribe`, ending
+    /// their streams, and waits for the server to acknowledge the requests.
+    /// The service and its connection are then closed, and all further
+    /// requests fail with [`TransportErrorKind::BackendGone`].
+    ///
+    /// The future does not resolve until the server has responded to all
+    /// `eth_unsubscribe` requests, so callers should apply a timeout.
+    pub async fn shutdown(&self) -> TransportResult<()> {
+        let (tx, rx) = oneshot::channel();
+        self.tx
+            .send(PubSubInstruction::Shutdown(tx))
+            .map_err(|_| TransportErrorKind::backend_gone())?;
+        let unsubscribes = rx.await.map_err(|_| TransportErrorKind::backend_gone())?;
+        // the subscriptions are removed either way, so errors are ignored
+        let _ = join_all(unsubscribes).await;
+        self.tx.send(PubSubInstruction::Close).map_err(|_| TransportErrorKind::backend_gone())
+    }
+
     /// Send a request.
     pub fn send(
         &self,
@@ -64,9 +114,10 @@ impl PubSubFrontend {
     ) -> impl Future<Output = TransportResult<Response>> + Send + 'static {
         let tx = self.tx.clone();
         let channel_size = self.channel_size.load(Ordering::Relaxed);
+        let lag_policy = self.lag_policy();
 
         async move {
-            let (in_flight, rx) = InFlight::new(req, channel_size);
+            let (in_flight, rx) = InFlight::new(req, channel_size, lag_policy);
             tx.send(PubSubInstruction::Request(in_flight))
                 .map_err(|_| TransportErrorKind::backend_gone())?;
             rx.await.map_err(|_| TransportErrorKind::backend_gone())?
@@ -101,6 +152,19 @@ impl PubSubFrontend {
         debug_assert_ne!(channel_size, 0, "channel size must be non-zero");
         self.channel_size.store(channel_size, Ordering::Relaxed);
     }
+
+    /// Get the currently configured [`LagPolicy`]. This is how new
+    /// subscriptions handle consumers that do not keep up with their
+    /// notifications. Defaults to [`LagPolicy::DropOldest`].
+    pub fn lag_policy(&self) -> LagPolicy {
+        LagPolicy::from_u8(self.lag_policy.load(Ordering::Relaxed))
+    }
+
+    /// Set the [`LagPolicy`] of new subscriptions. Defaults to
+    /// [`LagPolicy::DropOldest`].
+    pub fn set_lag_policy(&self, lag_policy: LagPolicy) {
+        self.lag_policy.store(lag_policy.to_u8(), Ordering::Relaxed);
+    }
 }
 
 impl tower::Service<RequestPacket> for PubSubFrontend {
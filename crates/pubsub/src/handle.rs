@@ -91,6 +91,21 @@ impl ConnectionInterface {
         self.from_frontend.recv().await
     }
 
+    /// Receive a request from the frontend without waiting, returning `None`
+    /// if no request is ready, or if the frontend has dropped or issued a
+    /// shutdown instruction.
+    ///
+    /// This allows backends to batch the requests that are already queued
+    /// after receiving one with [`recv_from_frontend`].
+    ///
+    /// [`recv_from_frontend`]: Self::recv_from_frontend
+    pub fn try_recv_from_frontend(&mut self) -> Option<Box<RawValue>> {
+        match self.shutdown.try_recv() {
+            Err(TryRecvError::Empty) => self.from_frontend.try_recv().ok(),
+            _ => None,
+        }
+    }
+
     /// Close the interface, sending an error to the frontend.
     pub fn close_with_error(self) {
         let _ = self.error.send(());
@@ -1,5 +1,7 @@
 use crate::{managers::InFlight, RawSubscription};
+use alloy_json_rpc::Response;
 use alloy_primitives::B256;
+use alloy_transport::TransportResult;
 use std::fmt;
 use tokio::sync::oneshot;
 
@@ -11,6 +13,11 @@ pub(crate) enum PubSubInstruction {
     GetSub(B256, oneshot::Sender<RawSubscription>),
     /// Unsubscribe from a subscription.
     Unsubscribe(B256),
+    /// Unsubscribe from all subscriptions, returning the receivers of the
+    /// `eth_unsubscribe` responses.
+    Shutdown(oneshot::Sender<Vec<oneshot::Receiver<TransportResult<Response>>>>),
+    /// Stop the service.
+    Close,
 }
 
 impl fmt::Debug for PubSubInstruction {
@@ -19,6 +26,8 @@ impl fmt::Debug for PubSubInstruction {
             Self::Request(arg0) => f.debug_tuple("Request").field(arg0).finish(),
             Self::GetSub(arg0, _) => f.debug_tuple("GetSub").field(arg0).finish(),
             Self::Unsubscribe(arg0) => f.debug_tuple("Unsubscribe").field(arg0).finish(),
+            Self::Shutdown(_) => f.write_str("Shutdown"),
+            Self::Close => f.write_str("Close"),
         }
     }
 }
@@ -12,6 +12,9 @@ extern crate tracing;
 mod connect;
 pub use connect::PubSubConnect;
 
+mod events;
+pub use events::{ConnectionEvent, ConnectionEvents};
+
 mod frontend;
 pub use frontend::PubSubFrontend;
 
@@ -22,10 +25,13 @@ pub use handle::{ConnectionHandle, ConnectionInterface};
 
 mod managers;
 
+mod remap;
+pub use remap::{subscription_key, SubIdAllocator, Subscribed, SubscriptionRemapper, Unsubscribed};
+
 mod service;
 
 mod sub;
 pub use sub::{
-    RawSubscription, SubAnyStream, SubResultStream, Subscription, SubscriptionItem,
-    SubscriptionStream,
+    LagPolicy, Lagged, RawSubscription, SubAnyStream, SubLagStream, SubResultStream, Subscription,
+    SubscriptionItem, SubscriptionStream,
 };
@@ -1,4 +1,4 @@
-use crate::RawSubscription;
+use crate::{LagPolicy, RawSubscription};
 use alloy_json_rpc::SerializedRequest;
 use alloy_primitives::B256;
 use serde_json::value::RawValue;
@@ -14,6 +14,10 @@ pub(crate) struct ActiveSubscription {
     pub(crate) request: SerializedRequest,
     /// The channel via which notifications are broadcast.
     pub(crate) tx: broadcast::Sender<Box<RawValue>>,
+    /// The number of notifications the channel buffers.
+    capacity: usize,
+    /// How the subscription handles slow consumers.
+    lag_policy: LagPolicy,
 }
 
 // NB: We implement this to prevent any incorrect future implementations.
@@ -50,16 +54,23 @@ impl fmt::Debug for ActiveSubscription {
             .field("local_id", &self.local_id)
             .field("request", &self.request)
             .field("subscribers", &self.tx.receiver_count())
+            .field("lag_policy", &self.lag_policy)
             .finish()
     }
 }
 
 impl ActiveSubscription {
     /// Create a new active subscription.
-    pub(crate) fn new(request: SerializedRequest, channel_size: usize) -> Self {
+    pub(crate) fn new(
+        request: SerializedRequest,
+        channel_size: usize,
+        lag_policy: LagPolicy,
+    ) -> Self {
         let local_id = request.params_hash();
         let (tx, _rx) = broadcast::channel(channel_size);
-        Self { request, local_id, tx }
+        // the channel rounds its capacity up to a power of two
+        let capacity = channel_size.next_power_of_two();
+        Self { request, local_id, tx, capacity, lag_policy }
     }
 
     /// Serialize the request as a boxed [`RawValue`].
@@ -71,14 +82,95 @@ impl ActiveSubscription {
 
     /// Get a subscription.
     pub(crate) fn subscribe(&self) -> RawSubscription {
-        RawSubscription { rx: self.tx.subscribe(), local_id: self.local_id }
+        RawSubscription {
+            rx: self.tx.subscribe(),
+            local_id: self.local_id,
+            lag_policy: self.lag_policy,
+        }
     }
 
     /// Notify the subscription channel of a new value, if any receiver exists.
     /// If no receiver exists, the notification is dropped.
-    pub(crate) fn notify(&mut self, notification: Box<RawValue>) {
-        if self.tx.receiver_count() > 0 {
-            let _ = self.tx.send(notification);
+    ///
+    /// If the subscription parks on lag and the channel is full, the
+    /// notification is returned instead, to be retried later.
+    pub(crate) fn notify(&mut self, notification: Box<RawValue>) -> Result<(), Box<RawValue>> {
+        if self.tx.receiver_count() == 0 {
+            return Ok(());
+        }
+        if self.lag_policy == LagPolicy::Park && self.tx.len() >= self.capacity {
+            return Err(notification);
+        }
+        let _ = self.tx.send(notification);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Lagged, Subscription};
+    use alloy_json_rpc::{Id, Request};
+    use futures::StreamExt;
+
+    fn active(lag_policy: LagPolicy) -> ActiveSubscription {
+        let request = Request::new("eth_subscribe", Id::Number(1), ("newHeads",));
+        ActiveSubscription::new(request.serialize().unwrap(), 2, lag_policy)
+    }
+
+    fn value(n: u64) -> Box<RawValue> {
+        serde_json::value::to_raw_value(&n).unwrap()
+    }
+
+    #[test]
+    fn parks_when_full() {
+        let mut sub = active(LagPolicy::Park);
+        // notifications without receivers are dropped
+        assert!(sub.notify(value(0)).is_ok());
+
+        let mut rx = sub.subscribe();
+        assert_eq!(rx.lag_policy(), LagPolicy::Park);
+        assert!(sub.notify(value(1)).is_ok());
+        assert!(sub.notify(value(2)).is_ok());
+        assert_eq!(sub.notify(value(3)).unwrap_err().get(), "3");
+
+        assert_eq!(rx.try_recv().unwrap().get(), "1");
+        assert!(sub.notify(value(3)).is_ok());
+        assert_eq!(rx.try_recv().unwrap().get(), "2");
+        assert_eq!(rx.try_recv().unwrap().get(), "3");
+    }
+
+    #[tokio::test]
+    async fn lag_policies() {
+        let mut sub = active(LagPolicy::DropOldest);
+        let stream = Subscription::<u64>::from(sub.subscribe()).into_stream();
+        for n in 0..4 {
+            sub.notify(value(n)).unwrap();
+        }
+        drop(sub);
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![2, 3]);
+
+        let mut sub = active(LagPolicy::Error);
+        let stream = Subscription::<u64>::from(sub.subscribe()).into_stream();
+        for n in 0..4 {
+            sub.notify(value(n)).unwrap();
+        }
+        assert!(stream.collect::<Vec<_>>().await.is_empty());
+
+        // the lag stream reports the missed notifications
+        let mut sub = active(LagPolicy::DropOldest);
+        let stream = Subscription::<u64>::from(sub.subscribe()).into_lag_stream();
+        for n in 0..4 {
+            sub.notify(value(n)).unwrap();
+        }
+        drop(sub);
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![Err(Lagged(2)), Ok(2), Ok(3)]);
+
+        let mut sub = active(LagPolicy::Error);
+        let stream = Subscription::<u64>::from(sub.subscribe()).into_lag_stream();
+        for n in 0..4 {
+            sub.notify(value(n)).unwrap();
         }
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![Err(Lagged(2))]);
     }
 }
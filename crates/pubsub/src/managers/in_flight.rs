@@ -1,3 +1,4 @@
+use crate::LagPolicy;
 use alloy_json_rpc::{Response, ResponsePayload, SerializedRequest, SubId};
 use alloy_transport::{TransportError, TransportResult};
 use std::fmt;
@@ -14,6 +15,9 @@ pub(crate) struct InFlight {
     /// The number of items to buffer in the subscription channel.
     pub(crate) channel_size: usize,
 
+    /// The lag policy of the subscription.
+    pub(crate) lag_policy: LagPolicy,
+
     /// The channel to send the response on.
     pub(crate) tx: oneshot::Sender<TransportResult<Response>>,
 }
@@ -23,6 +27,7 @@ impl fmt::Debug for InFlight {
         f.debug_struct("InFlight")
             .field("request", &self.request)
             .field("channel_size", &self.channel_size)
+            .field("lag_policy", &self.lag_policy)
             .field("tx_is_closed", &self.tx.is_closed())
             .finish()
     }
@@ -33,10 +38,11 @@ impl InFlight {
     pub(crate) fn new(
         request: SerializedRequest,
         channel_size: usize,
+        lag_policy: LagPolicy,
     ) -> (Self, oneshot::Receiver<TransportResult<Response>>) {
         let (tx, rx) = oneshot::channel();
 
-        (Self { request, channel_size, tx }, rx)
+        (Self { request, channel_size, lag_policy, tx }, rx)
     }
 
     /// Check if the request is a subscription.
@@ -24,6 +24,11 @@ impl RequestManager {
         self.reqs.insert(in_flight.request.id().clone(), in_flight);
     }
 
+    /// Get an in-flight request by its ID.
+    pub(crate) fn get(&self, id: &Id) -> Option<&InFlight> {
+        self.reqs.get(id)
+    }
+
     /// Handle a response by sending the payload to the waiter.
     ///
     /// If the request created a new subscription, this function returns the
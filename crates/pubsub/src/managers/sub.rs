@@ -1,4 +1,4 @@
-use crate::{managers::ActiveSubscription, RawSubscription};
+use crate::{managers::ActiveSubscription, LagPolicy, RawSubscription};
 use alloy_json_rpc::{EthNotification, SerializedRequest, SubId};
 use alloy_primitives::B256;
 use bimap::BiBTreeMap;
@@ -28,8 +28,9 @@ impl SubscriptionManager {
         request: SerializedRequest,
         server_id: SubId,
         channel_size: usize,
+        lag_policy: LagPolicy,
     ) -> RawSubscription {
-        let active = ActiveSubscription::new(request, channel_size);
+        let active = ActiveSubscription::new(request, channel_size, lag_policy);
         let sub = active.subscribe();
 
         let local_id = active.local_id;
@@ -45,8 +46,9 @@ impl SubscriptionManager {
         request: SerializedRequest,
         server_id: SubId,
         channel_size: usize,
+        lag_policy: LagPolicy,
     ) -> RawSubscription {
-        let local_id = request.params_hash();
+        let local_id = crate::subscription_key(&request);
 
         // If we already know a subscription with the exact params,
         // we can just update the server_id and get a new listener.
@@ -54,7 +56,7 @@ impl SubscriptionManager {
             self.change_server_id(local_id, server_id);
             self.get_subscription(local_id).expect("checked existence")
         } else {
-            self.insert(request, server_id, channel_size)
+            self.insert(request, server_id, channel_size, lag_policy)
         }
     }
 
@@ -78,6 +80,11 @@ impl SubscriptionManager {
         self.local_to_server.insert(local_id, server_id);
     }
 
+    /// Check whether a subscription exists.
+    pub(crate) fn contains(&self, local_id: &B256) -> bool {
+        self.local_to_sub.contains_left(local_id)
+    }
+
     /// Remove a subscription by its local_id.
     pub(crate) fn remove_sub(&mut self, local_id: B256) {
         let _ = self.local_to_sub.remove_by_left(&local_id);
@@ -87,13 +94,16 @@ impl SubscriptionManager {
     /// Notify the subscription channel of a new value, if the sub is known,
     /// and if any receiver exists. If the sub id is unknown, or no receiver
     /// exists, the notification is dropped.
-    pub(crate) fn notify(&mut self, notification: EthNotification) {
-        if let Some(local_id) = self.local_id_for(&notification.subscription) {
-            if let Some((_, mut sub)) = self.local_to_sub.remove_by_left(&local_id) {
-                sub.notify(notification.result);
-                self.local_to_sub.insert(local_id, sub);
-            }
-        }
+    ///
+    /// If the subscription parks on lag and its channel is full, the
+    /// notification is returned instead, to be retried later.
+    pub(crate) fn notify(&mut self, notification: EthNotification) -> Option<EthNotification> {
+        let local_id = self.local_id_for(&notification.subscription)?;
+        let (_, mut sub) = self.local_to_sub.remove_by_left(&local_id)?;
+        let EthNotification { subscription, result } = notification;
+        let parked = sub.notify(result).err();
+        self.local_to_sub.insert(local_id, sub);
+        parked.map(|result| EthNotification { subscription, result })
     }
 
     /// Get a receiver for a subscription.
@@ -0,0 +1,299 @@
+use alloy_json_rpc::{EthNotification, SerializedRequest, SubId};
+use alloy_primitives::{B256, U256};
+use std::{collections::HashMap, hash::Hash};
+
+/// Returns the key identifying the subscription made by the request.
+///
+/// Requests with the same parameters, e.g. two `eth_subscribe` requests for `newHeads`, have the
+/// same key, and can share an upstream subscription. This is also the local ID of the
+/// subscriptions of a [`PubSubFrontend`](crate::PubSubFrontend).
+pub fn subscription_key(request: &SerializedRequest) -> B256 {
+    request.params_hash()
+}
+
+/// Allocates subscription IDs that are unique for the lifetime of the allocator.
+#[derive(Debug, Default)]
+pub struct SubIdAllocator {
+    next: u64,
+}
+
+impl SubIdAllocator {
+    /// Creates a new allocator.
+    pub const fn new() -> Self {
+        Self { next: 0 }
+    }
+
+    /// Allocates a new subscription ID.
+    pub fn allocate(&mut self) -> SubId {
+        self.next += 1;
+        SubId::Number(U256::from(self.next))
+    }
+}
+
+/// A downstream subscription, created by [`SubscriptionRemapper::subscribe`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subscribed {
+    /// The ID of the subscription returned to the downstream client.
+    pub local_id: SubId,
+    /// The key of the upstream subscription.
+    pub key: B256,
+    /// Whether no upstream subscription with the key exists yet, and it must be made and then
+    /// [bound](SubscriptionRemapper::bind).
+    pub is_new: bool,
+}
+
+/// The result of removing a downstream subscription with
+/// [`SubscriptionRemapper::unsubscribe`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Unsubscribed {
+    /// The client has no subscription with the ID.
+    Unknown,
+    /// Other downstream subscriptions still share the upstream subscription.
+    Shared,
+    /// This was the last downstream subscription of the upstream subscription, which should be
+    /// cancelled if it is bound to a server ID.
+    Last {
+        /// The server ID of the upstream subscription, if bound.
+        server_id: Option<SubId>,
+    },
+}
+
+#[derive(Debug)]
+struct UpstreamSubscription<C> {
+    server_id: Option<SubId>,
+    subscribers: Vec<(C, SubId)>,
+}
+
+/// Maps the subscriptions of many downstream clients onto shared upstream subscriptions.
+///
+/// This is the building block of pubsub proxies: downstream subscriptions with the same
+/// [key](subscription_key) share a single upstream subscription, and each downstream
+/// subscription gets its own local ID from a [`SubIdAllocator`], so IDs never collide between
+/// clients, nor with the server IDs of the upstream.
+///
+/// `C` identifies a downstream client, e.g. a connection ID.
+///
+/// The remapper only tracks IDs: making and cancelling upstream subscriptions, and delivering
+/// notifications, is up to the caller.
+#[derive(Debug)]
+pub struct SubscriptionRemapper<C> {
+    allocator: SubIdAllocator,
+    upstream: HashMap<B256, UpstreamSubscription<C>>,
+    server_to_key: HashMap<SubId, B256>,
+    local_to_key: HashMap<SubId, B256>,
+}
+
+impl<C> Default for SubscriptionRemapper<C> {
+    fn default() -> Self {
+        Self {
+            allocator: SubIdAllocator::new(),
+            upstream: HashMap::new(),
+            server_to_key: HashMap::new(),
+            local_to_key: HashMap::new(),
+        }
+    }
+}
+
+impl<C: Clone + Eq + Hash> SubscriptionRemapper<C> {
+    /// Creates a new remapper.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of upstream subscriptions.
+    pub fn len(&self) -> usize {
+        self.upstream.len()
+    }
+
+    /// Returns `true` if there are no subscriptions.
+    pub fn is_empty(&self) -> bool {
+        self.upstream.is_empty()
+    }
+
+    /// Returns the server ID the upstream subscription with the key is bound to.
+    pub fn server_id(&self, key: &B256) -> Option<&SubId> {
+        self.upstream.get(key)?.server_id.as_ref()
+    }
+
+    /// Adds a downstream subscription of the client to the upstream subscription with the key.
+    pub fn subscribe(&mut self, client: C, key: B256) -> Subscribed {
+        let local_id = self.allocator.allocate();
+        self.local_to_key.insert(local_id.clone(), key);
+
+        let mut is_new = false;
+        let upstream = self.upstream.entry(key).or_insert_with(|| {
+            is_new = true;
+            UpstreamSubscription { server_id: None, subscribers: Vec::new() }
+        });
+        upstream.subscribers.push((client, local_id.clone()));
+
+        Subscribed { local_id, key, is_new }
+    }
+
+    /// Binds the upstream subscription with the key to its server ID, replacing the previous
+    /// server ID, if any.
+    ///
+    /// Returns `false` if there is no upstream subscription with the key, in which case the
+    /// upstream subscription should be cancelled.
+    pub fn bind(&mut self, key: B256, server_id: SubId) -> bool {
+        let Some(upstream) = self.upstream.get_mut(&key) else { return false };
+        if let Some(old) = upstream.server_id.replace(server_id.clone()) {
+            self.server_to_key.remove(&old);
+        }
+        self.server_to_key.insert(server_id, key);
+        true
+    }
+
+    /// Removes the downstream subscription of the client with the local ID.
+    pub fn unsubscribe(&mut self, client: &C, local_id: &SubId) -> Unsubscribed {
+        let Some(key) = self.local_to_key.get(local_id).copied() else {
+            return Unsubscribed::Unknown;
+        };
+        let Some(upstream) = self.upstream.get_mut(&key) else { return Unsubscribed::Unknown };
+        let Some(pos) =
+            upstream.subscribers.iter().position(|(c, id)| c == client && id == local_id)
+        else {
+            // the ID belongs to another client
+            return Unsubscribed::Unknown;
+        };
+
+        upstream.subscribers.swap_remove(pos);
+        self.local_to_key.remove(local_id);
+        if !upstream.subscribers.is_empty() {
+            return Unsubscribed::Shared;
+        }
+
+        let server_id = self.upstream.remove(&key).and_then(|upstream| upstream.server_id);
+        if let Some(server_id) = &server_id {
+            self.server_to_key.remove(server_id);
+        }
+        Unsubscribed::Last { server_id }
+    }
+
+    /// Removes all downstream subscriptions of the client, e.g. when it disconnects.
+    ///
+    /// Returns the server IDs of the upstream subscriptions that no longer have subscribers, and
+    /// should be cancelled.
+    pub fn remove_client(&mut self, client: &C) -> Vec<SubId> {
+        let local_ids = self
+            .upstream
+            .values()
+            .flat_map(|upstream| upstream.subscribers.iter())
+            .filter(|(c, _)| c == client)
+            .map(|(_, id)| id.clone())
+            .collect::<Vec<_>>();
+
+        local_ids
+            .iter()
+            .filter_map(|local_id| match self.unsubscribe(client, local_id) {
+                Unsubscribed::Last { server_id } => server_id,
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Unbinds all upstream subscriptions from their server IDs, e.g. when the upstream
+    /// reconnects.
+    ///
+    /// Returns the keys of the upstream subscriptions, which should be made again and bound to
+    /// their new server IDs.
+    pub fn drop_server_ids(&mut self) -> Vec<B256> {
+        self.server_to_key.clear();
+        self.upstream
+            .iter_mut()
+            .map(|(key, upstream)| {
+                upstream.server_id = None;
+                *key
+            })
+            .collect()
+    }
+
+    /// Returns the notification of an upstream subscription, rewritten for each of its downstream
+    /// subscriptions, along with the client to deliver it to.
+    ///
+    /// Returns nothing if the server ID of the notification is unknown.
+    pub fn route<T: Clone>(
+        &self,
+        notification: &EthNotification<T>,
+    ) -> Vec<(C, EthNotification<T>)> {
+        let Some(upstream) = self
+            .server_to_key
+            .get(&notification.subscription)
+            .and_then(|key| self.upstream.get(key))
+        else {
+            return Vec::new();
+        };
+
+        upstream
+            .subscribers
+            .iter()
+            .map(|(client, local_id)| {
+                let notification = EthNotification {
+                    subscription: local_id.clone(),
+                    result: notification.result.clone(),
+                };
+                (client.clone(), notification)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(server_id: &SubId) -> EthNotification<u64> {
+        EthNotification { subscription: server_id.clone(), result: 1 }
+    }
+
+    #[test]
+    fn multiplexes_subscriptions() {
+        let mut remapper = SubscriptionRemapper::new();
+        let heads = B256::with_last_byte(1);
+        let logs = B256::with_last_byte(2);
+
+        let a = remapper.subscribe("a", heads);
+        let b = remapper.subscribe("b", heads);
+        let c = remapper.subscribe("a", logs);
+        assert!(a.is_new && !b.is_new && c.is_new);
+        assert_ne!(a.local_id, b.local_id);
+        assert_eq!(remapper.len(), 2);
+
+        let server_id = SubId::from("0xabc".to_string());
+        assert!(remapper.bind(heads, server_id.clone()));
+        let routed = remapper.route(&notification(&server_id));
+        assert_eq!(routed.len(), 2);
+        assert_eq!((routed[0].0, &routed[0].1.subscription), ("a", &a.local_id));
+        assert_eq!((routed[1].0, &routed[1].1.subscription), ("b", &b.local_id));
+        assert!(remapper.route(&notification(&a.local_id)).is_empty());
+
+        // clients can only cancel their own subscriptions
+        assert_eq!(remapper.unsubscribe(&"b", &a.local_id), Unsubscribed::Unknown);
+        assert_eq!(remapper.unsubscribe(&"a", &a.local_id), Unsubscribed::Shared);
+        assert_eq!(
+            remapper.unsubscribe(&"b", &b.local_id),
+            Unsubscribed::Last { server_id: Some(server_id.clone()) }
+        );
+        assert!(remapper.route(&notification(&server_id)).is_empty());
+
+        assert_eq!(remapper.remove_client(&"a"), Vec::<SubId>::new());
+        assert!(remapper.is_empty());
+    }
+
+    #[test]
+    fn rebinds_after_reconnect() {
+        let mut remapper = SubscriptionRemapper::new();
+        let key = B256::with_last_byte(1);
+        let sub = remapper.subscribe(1, key);
+        remapper.bind(key, SubId::from(U256::from(10)));
+
+        assert_eq!(remapper.drop_server_ids(), vec![key]);
+        assert!(remapper.server_id(&key).is_none());
+        assert!(remapper.route(&notification(&SubId::from(U256::from(10)))).is_empty());
+
+        remapper.bind(key, SubId::from(U256::from(20)));
+        let routed = remapper.route(&notification(&SubId::from(U256::from(20))));
+        assert_eq!(routed[0].1.subscription, sub.local_id);
+        assert_eq!(remapper.remove_client(&1), vec![SubId::from(U256::from(20))]);
+    }
+}
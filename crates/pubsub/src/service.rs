@@ -2,16 +2,46 @@ use crate::{
     handle::ConnectionHandle,
     ix::PubSubInstruction,
     managers::{InFlight, RequestManager, SubscriptionManager},
-    PubSubConnect, PubSubFrontend, RawSubscription,
+    ConnectionEvent, LagPolicy, PubSubConnect, PubSubFrontend, RawSubscription,
 };
-use alloy_json_rpc::{Id, PubSubItem, Request, Response, ResponsePayload, SubId};
+use alloy_json_rpc::{EthNotification, Id, PubSubItem, Request, Response, ResponsePayload, SubId};
 use alloy_primitives::B256;
 use alloy_transport::{
     utils::{to_json_raw_value, Spawnable},
     TransportErrorKind, TransportResult,
 };
 use serde_json::value::RawValue;
-use tokio::sync::{mpsc, oneshot};
+use std::collections::VecDeque;
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+/// How often parked notifications are retried, see [`LagPolicy::Park`].
+#[cfg(not(target_arch = "wasm32"))]
+const PARK_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// Waits before retrying the parked notifications.
+///
+/// The timer is only created when the future is first polled, i.e. while
+/// notifications are parked, so that connections without parked
+/// notifications never need the time driver. On wasm, the retry happens after
+/// yielding to the executor instead.
+async fn park_retry_delay() {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(PARK_RETRY_INTERVAL).await;
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let mut yielded = false;
+        std::future::poll_fn(|cx| {
+            if std::mem::replace(&mut yielded, true) {
+                std::task::Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }
+        })
+        .await;
+    }
+}
 
 /// The service contains the backend handle, a subscription manager, and the
 /// configuration details required to reconnect.
@@ -31,6 +61,17 @@ pub(crate) struct PubSubService<T> {
 
     /// The request manager.
     pub(crate) in_flights: RequestManager,
+
+    /// Notifications that could not be delivered because the channel of a
+    /// subscription with [`LagPolicy::Park`] was full. While this is not
+    /// empty, no new items are read from the backend.
+    pub(crate) parked: VecDeque<EthNotification>,
+
+    /// The sender of connection events.
+    pub(crate) events: broadcast::Sender<ConnectionEvent>,
+
+    /// The number of reconnections made so far.
+    pub(crate) reconnects: u32,
 }
 
 impl<T: PubSubConnect> PubSubService<T> {
@@ -39,15 +80,25 @@ impl<T: PubSubConnect> PubSubService<T> {
         let handle = connector.connect().await?;
 
         let (tx, reqs) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(crate::events::EVENTS_CHANNEL_SIZE);
         let this = Self {
             handle,
             connector,
             reqs,
             subs: SubscriptionManager::default(),
             in_flights: Default::default(),
+            parked: VecDeque::new(),
+            events: events.clone(),
+            reconnects: 0,
         };
+        this.emit(ConnectionEvent::Connected);
         this.spawn();
-        Ok(PubSubFrontend::new(tx))
+        Ok(PubSubFrontend::new(tx, events))
+    }
+
+    /// Send a connection event to the listeners, if any.
+    fn emit(&self, event: ConnectionEvent) {
+        let _ = self.events.send(event);
     }
 
     /// Reconnect by dropping the backend and creating a new one.
@@ -59,10 +110,14 @@ impl<T: PubSubConnect> PubSubService<T> {
 
     /// Reconnect the backend, re-issue pending requests, and re-start active
     /// subscriptions.
-    async fn reconnect(&mut self) -> TransportResult<()> {
+    async fn reconnect(&mut self, reason: &str) -> TransportResult<()> {
         info!("Reconnecting pubsub service backend.");
+        self.emit(ConnectionEvent::Disconnected { reason: reason.to_string() });
+        self.reconnects += 1;
+        self.emit(ConnectionEvent::Reconnecting { attempt: self.reconnects });
 
         let mut old_handle = self.get_new_backend().await?;
+        self.emit(ConnectionEvent::Connected);
 
         debug!("Draining old backend to_handle");
 
@@ -92,7 +147,7 @@ impl<T: PubSubConnect> PubSubService<T> {
             let req = sub.request().to_owned();
             // 0 is a dummy value, we don't care about the channel size here,
             // as none of these will result in channel creation.
-            let (in_flight, _) = InFlight::new(req.clone(), 0);
+            let (in_flight, _) = InFlight::new(req.clone(), 0, LagPolicy::default());
             self.in_flights.insert(in_flight);
 
             let msg = req.into_serialized();
@@ -141,6 +196,35 @@ impl<T: PubSubConnect> PubSubService<T> {
         Ok(())
     }
 
+    /// Service a shutdown instruction, by unsubscribing from all
+    /// subscriptions.
+    ///
+    /// The `eth_unsubscribe` requests are tracked like other requests, so that
+    /// the frontend can wait for the server to acknowledge them.
+    fn service_shutdown(
+        &mut self,
+        tx: oneshot::Sender<Vec<oneshot::Receiver<TransportResult<Response>>>>,
+    ) -> TransportResult<()> {
+        let local_ids = self.subs.iter().map(|(local_id, _)| *local_id).collect::<Vec<_>>();
+        let mut unsubscribes = Vec::with_capacity(local_ids.len());
+        for local_id in local_ids {
+            if let Some(server_id) = self.subs.server_id_for(&local_id).cloned() {
+                let id = Id::String(format!("unsubscribe-{local_id}"));
+                let req = Request::new("eth_unsubscribe", id, [server_id]);
+                let req = req.serialize().expect("no ser error");
+                let (in_flight, rx) = InFlight::new(req, 0, LagPolicy::default());
+                self.service_request(in_flight)?;
+                unsubscribes.push(rx);
+            }
+            self.subs.remove_sub(local_id);
+        }
+        self.parked.clear();
+
+        debug!(count = unsubscribes.len(), "Unsubscribed from all subscriptions");
+        let _ = tx.send(unsubscribes);
+        Ok(())
+    }
+
     /// Service an instruction
     fn service_ix(&mut self, ix: PubSubInstruction) -> TransportResult<()> {
         trace!(?ix, "servicing instruction");
@@ -151,23 +235,73 @@ impl<T: PubSubConnect> PubSubService<T> {
                 Ok(())
             }
             PubSubInstruction::Unsubscribe(alias) => self.service_unsubscribe(alias),
+            PubSubInstruction::Shutdown(tx) => self.service_shutdown(tx),
+            // handled by the service loop
+            PubSubInstruction::Close => Ok(()),
         }
     }
 
     /// Handle an item from the backend.
     fn handle_item(&mut self, item: PubSubItem) -> TransportResult<()> {
         match item {
-            PubSubItem::Response(resp) => match self.in_flights.handle_response(resp) {
-                Some((server_id, in_flight)) => self.handle_sub_response(in_flight, server_id),
-                None => Ok(()),
-            },
+            PubSubItem::Response(resp) => {
+                if resp.is_error() {
+                    self.drop_failed_resubscription(&resp.id);
+                }
+                match self.in_flights.handle_response(resp) {
+                    Some((server_id, in_flight)) => self.handle_sub_response(in_flight, server_id),
+                    None => Ok(()),
+                }
+            }
             PubSubItem::Notification(notification) => {
-                self.subs.notify(notification);
+                self.notify(notification);
                 Ok(())
             }
         }
     }
 
+    /// Drop a subscription if the request with the given ID failed to
+    /// re-establish it after a reconnection.
+    ///
+    /// Subscriptions being re-established have no server ID until the server
+    /// accepts them again. A failed `eth_subscribe` with the params of a live
+    /// subscription leaves that subscription untouched.
+    fn drop_failed_resubscription(&mut self, id: &Id) {
+        let Some(in_flight) = self.in_flights.get(id) else { return };
+        if !in_flight.is_subscription() {
+            return;
+        }
+        let local_id = in_flight.request().params_hash();
+        if self.subs.contains(&local_id) && self.subs.server_id_for(&local_id).is_none() {
+            warn!(%local_id, "failed to re-establish subscription, dropping it");
+            self.subs.remove_sub(local_id);
+            self.emit(ConnectionEvent::SubscriptionDropped { id: local_id });
+        }
+    }
+
+    /// Deliver a notification, parking it if its subscription is full.
+    ///
+    /// Notifications are parked in order, so once one is parked, all
+    /// following ones are parked too.
+    fn notify(&mut self, notification: EthNotification) {
+        if !self.parked.is_empty() {
+            self.parked.push_back(notification);
+        } else if let Some(parked) = self.subs.notify(notification) {
+            trace!(subscription = ?parked.subscription, "parking notification");
+            self.parked.push_back(parked);
+        }
+    }
+
+    /// Retry delivering the parked notifications, in order.
+    fn retry_parked(&mut self) {
+        while let Some(notification) = self.parked.pop_front() {
+            if let Some(parked) = self.subs.notify(notification) {
+                self.parked.push_front(parked);
+                break;
+            }
+        }
+    }
+
     /// Rewrite the subscription id and insert into the subscriptions manager
     fn handle_sub_response(
         &mut self,
@@ -177,7 +311,8 @@ impl<T: PubSubConnect> PubSubService<T> {
         let request = in_flight.request;
         let id = request.id().clone();
 
-        let sub = self.subs.upsert(request, server_id, in_flight.channel_size);
+        let sub =
+            self.subs.upsert(request, server_id, in_flight.channel_size, in_flight.lag_policy);
 
         // Serialized B256 is always a valid serialized U256 too.
         let ser_alias = to_json_raw_value(sub.local_id())?;
@@ -196,29 +331,37 @@ impl<T: PubSubConnect> PubSubService<T> {
             let result: TransportResult<()> = loop {
                 // We bias the loop so that we always handle new messages before
                 // reconnecting, and always reconnect before dispatching new
-                // requests.
+                // requests. While notifications are parked, no new messages are
+                // read, which applies backpressure to the server.
                 tokio::select! {
                     biased;
 
-                    item_opt = self.handle.from_socket.recv() => {
+                    _ = park_retry_delay(), if !self.parked.is_empty() => {
+                        self.retry_parked();
+                    }
+
+                    item_opt = self.handle.from_socket.recv(), if self.parked.is_empty() => {
                         if let Some(item) = item_opt {
                             if let Err(e) = self.handle_item(item) {
                                 break Err(e)
                             }
-                        } else if let Err(e) = self.reconnect().await {
+                        } else if let Err(e) = self.reconnect("connection closed").await {
                             break Err(e)
                         }
                     }
 
                     _ = &mut self.handle.error => {
                         error!("Pubsub service backend error.");
-                        if let Err(e) = self.reconnect().await {
+                        if let Err(e) = self.reconnect("backend error").await {
                             break Err(e)
                         }
                     }
 
                     req_opt = self.reqs.recv() => {
-                        if let Some(req) = req_opt {
+                        if let Some(PubSubInstruction::Close) = req_opt {
+                            info!("Pubsub service closed. Shutting down.");
+                            break Ok(())
+                        } else if let Some(req) = req_opt {
                             if let Err(e) = self.service_ix(req) {
                                 break Err(e)
                             }
@@ -232,6 +375,7 @@ impl<T: PubSubConnect> PubSubService<T> {
 
             if let Err(err) = result {
                 error!(%err, "pubsub service reconnection error");
+                self.emit(ConnectionEvent::Disconnected { reason: err.to_string() });
             }
         };
         fut.spawn_task();
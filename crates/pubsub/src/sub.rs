@@ -6,6 +6,83 @@ use std::{pin::Pin, task};
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 
+/// How a subscription handles consumers that do not keep up with its
+/// notifications.
+///
+/// Each subscription buffers up to
+/// [`channel_size`](crate::PubSubFrontend::channel_size) notifications for its
+/// slowest consumer. The policy decides what happens once that buffer is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LagPolicy {
+    /// Drop the oldest notifications. The receive methods return
+    /// [`RecvError::Lagged`] with the number of missed notifications, and the
+    /// streams skip them. This is the default.
+    ///
+    /// [`RecvError::Lagged`]: broadcast::error::RecvError::Lagged
+    #[default]
+    DropOldest,
+    /// Drop the oldest notifications, like [`LagPolicy::DropOldest`], but end
+    /// the streams of the subscription after logging the number of missed
+    /// notifications, so that consumers notice the gap.
+    ///
+    /// Use [`Subscription::into_lag_stream`] to tell the lag apart from the
+    /// normal end of the subscription: its stream yields [`Lagged`] with the
+    /// number of missed notifications before it ends.
+    Error,
+    /// Stop reading from the connection while the buffer is full, so that no
+    /// notification is dropped.
+    ///
+    /// This applies backpressure to the server, and delays all other
+    /// notifications and responses on the same connection until the slow
+    /// consumer catches up.
+    Park,
+}
+
+impl LagPolicy {
+    pub(crate) const fn to_u8(self) -> u8 {
+        match self {
+            Self::DropOldest => 0,
+            Self::Error => 1,
+            Self::Park => 2,
+        }
+    }
+
+    pub(crate) const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Error,
+            2 => Self::Park,
+            _ => Self::DropOldest,
+        }
+    }
+
+    /// Handles a lag of a stream, returning `true` if the stream should
+    /// continue.
+    fn handle_lag(self, id: &B256, err: BroadcastStreamRecvError) -> bool {
+        if self == Self::Error {
+            error!(%err, %id, "stream lagged, ending it");
+            false
+        } else {
+            // This is OK.
+            debug!(%err, %id, "stream lagged");
+            true
+        }
+    }
+}
+
+/// The error yielded by a [`SubLagStream`] when its consumer did not keep up
+/// with the subscription, with the number of missed notifications.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lagged(pub u64);
+
+impl std::fmt::Display for Lagged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "subscription lagged, missed {} notifications", self.0)
+    }
+}
+
+impl std::error::Error for Lagged {}
+
 /// A Subscription is a feed of notifications from the server, identified by a
 /// local ID.
 ///
@@ -17,6 +94,8 @@ pub struct RawSubscription {
     pub(crate) rx: broadcast::Receiver<Box<RawValue>>,
     /// The local ID of the subscription.
     pub(crate) local_id: B256,
+    /// How the subscription handles slow consumers.
+    pub(crate) lag_policy: LagPolicy,
 }
 
 impl RawSubscription {
@@ -25,6 +104,11 @@ impl RawSubscription {
         &self.local_id
     }
 
+    /// Get the [`LagPolicy`] of the subscription.
+    pub const fn lag_policy(&self) -> LagPolicy {
+        self.lag_policy
+    }
+
     /// Wrapper for [`blocking_recv`]. Block the current thread until a message
     /// is available.
     ///
@@ -57,7 +141,7 @@ impl RawSubscription {
     ///
     /// [`resubscribe`]: broadcast::Receiver::resubscribe
     pub fn resubscribe(&self) -> Self {
-        Self { rx: self.rx.resubscribe(), local_id: self.local_id }
+        Self { rx: self.rx.resubscribe(), local_id: self.local_id, lag_policy: self.lag_policy }
     }
 
     /// Wrapper for [`same_channel`]. Returns `true` if the two subscriptions
@@ -138,6 +222,11 @@ impl<T> Subscription<T> {
         self.inner.local_id()
     }
 
+    /// Get the [`LagPolicy`] of the subscription.
+    pub const fn lag_policy(&self) -> LagPolicy {
+        self.inner.lag_policy()
+    }
+
     /// Convert the subscription into its inner [`RawSubscription`].
     pub fn into_raw(self) -> RawSubscription {
         self.inner
@@ -226,6 +315,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn into_stream(self) -> SubscriptionStream<T> {
         SubscriptionStream {
             id: self.inner.local_id,
+            lag_policy: self.inner.lag_policy,
             inner: self.inner.into_stream(),
             _pd: std::marker::PhantomData,
         }
@@ -235,7 +325,20 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn into_result_stream(self) -> SubResultStream<T> {
         SubResultStream {
             id: self.inner.local_id,
+            lag_policy: self.inner.lag_policy,
+            inner: self.inner.into_stream(),
+            _pd: std::marker::PhantomData,
+        }
+    }
+
+    /// Convert the subscription into a stream that yields [`Lagged`] when
+    /// notifications were missed, see [`SubLagStream`].
+    pub fn into_lag_stream(self) -> SubLagStream<T> {
+        SubLagStream {
+            id: self.inner.local_id,
+            lag_policy: self.inner.lag_policy,
             inner: self.inner.into_stream(),
+            done: false,
             _pd: std::marker::PhantomData,
         }
     }
@@ -244,6 +347,7 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn into_any_stream(self) -> SubAnyStream<T> {
         SubAnyStream {
             id: self.inner.local_id,
+            lag_policy: self.inner.lag_policy,
             inner: self.inner.into_stream(),
             _pd: std::marker::PhantomData,
         }
@@ -324,6 +428,7 @@ impl<T: DeserializeOwned> Subscription<T> {
 #[derive(Debug)]
 pub struct SubAnyStream<T> {
     id: B256,
+    lag_policy: LagPolicy,
     inner: BroadcastStream<Box<RawValue>>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
@@ -345,10 +450,11 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
         loop {
             match ready!(self.inner.poll_next_unpin(cx)) {
                 Some(Ok(value)) => return task::Poll::Ready(Some(value.into())),
-                Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
-                    continue;
+                Some(Err(err)) => {
+                    if self.lag_policy.handle_lag(&self.id, err) {
+                        continue;
+                    }
+                    return task::Poll::Ready(None);
                 }
                 None => return task::Poll::Ready(None),
             }
@@ -362,6 +468,7 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
 #[derive(Debug)]
 pub struct SubscriptionStream<T> {
     id: B256,
+    lag_policy: LagPolicy,
     inner: BroadcastStream<Box<RawValue>>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
@@ -390,10 +497,11 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
                         continue;
                     }
                 },
-                Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
-                    continue;
+                Some(Err(err)) => {
+                    if self.lag_policy.handle_lag(&self.id, err) {
+                        continue;
+                    }
+                    return task::Poll::Ready(None);
                 }
                 None => return task::Poll::Ready(None),
             }
@@ -408,6 +516,7 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
 #[derive(Debug)]
 pub struct SubResultStream<T> {
     id: B256,
+    lag_policy: LagPolicy,
     inner: BroadcastStream<Box<RawValue>>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
@@ -431,10 +540,65 @@ impl<T: DeserializeOwned> Stream for SubResultStream<T> {
                 Some(Ok(value)) => {
                     return task::Poll::Ready(Some(serde_json::from_str(value.get())))
                 }
-                Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
-                    continue;
+                Some(Err(err)) => {
+                    if self.lag_policy.handle_lag(&self.id, err) {
+                        continue;
+                    }
+                    return task::Poll::Ready(None);
+                }
+                None => return task::Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// A stream of notifications from the server, identified by a local ID, that
+/// reports missed notifications.
+///
+/// Like [`SubscriptionStream`], this stream yields only the expected type, but
+/// yields [`Lagged`] with the number of missed notifications when its consumer
+/// did not keep up. With [`LagPolicy::Error`], the stream ends after the
+/// [`Lagged`] error; otherwise it continues with the next notification.
+#[derive(Debug)]
+pub struct SubLagStream<T> {
+    id: B256,
+    lag_policy: LagPolicy,
+    inner: BroadcastStream<Box<RawValue>>,
+    done: bool,
+    _pd: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> SubLagStream<T> {
+    /// Get the local ID of the subscription.
+    pub const fn id(&self) -> &B256 {
+        &self.id
+    }
+}
+
+impl<T: DeserializeOwned> Stream for SubLagStream<T> {
+    type Item = Result<T, Lagged>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        if self.done {
+            return task::Poll::Ready(None);
+        }
+        loop {
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(value)) => match serde_json::from_str(value.get()) {
+                    Ok(item) => return task::Poll::Ready(Some(Ok(item))),
+                    Err(err) => {
+                        error!(%err, %self.id, "failed deserializing subscription item");
+                        continue;
+                    }
+                },
+                Some(Err(BroadcastStreamRecvError::Lagged(missed))) => {
+                    self.done = !self
+                        .lag_policy
+                        .handle_lag(&self.id, BroadcastStreamRecvError::Lagged(missed));
+                    return task::Poll::Ready(Some(Err(Lagged(missed))));
                 }
                 None => return task::Poll::Ready(None),
             }
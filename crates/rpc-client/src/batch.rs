@@ -161,7 +161,13 @@ where
     }
 
     /// Send the batch future via its connection.
+    ///
+    /// If the client has been shut down, the batch fails with
+    /// [`TransportErrorKind::ClientClosed`].
     pub fn send(self) -> BatchFuture<Conn> {
+        if self.transport.is_closed() {
+            return BatchFuture::SerError(Some(TransportErrorKind::client_closed()));
+        }
         BatchFuture::Prepared {
             transport: self.transport.transport.clone(),
             requests: self.requests,
@@ -1,17 +1,21 @@
+use crate::shutdown::{InFlightGuard, ShutdownState};
 use alloy_json_rpc::{
-    transform_response, try_deserialize_ok, Request, RequestPacket, ResponsePacket, RpcParam,
-    RpcResult, RpcReturn,
+    transform_response, try_deserialize_ok, Request, RequestPacket, RequestPriority,
+    ResponsePacket, RpcParam, RpcResult, RpcReturn,
 };
-use alloy_transport::{RpcFut, Transport, TransportError, TransportResult};
+use alloy_transport::{RpcFut, Transport, TransportError, TransportErrorKind, TransportResult};
 use core::panic;
 use futures::FutureExt;
 use serde_json::value::RawValue;
 use std::{
+    borrow::Cow,
     fmt,
     future::Future,
     marker::PhantomData,
     pin::Pin,
+    sync::Arc,
     task::{self, ready, Poll::Ready},
+    time::Instant,
 };
 use tower::Service;
 
@@ -26,10 +30,12 @@ where
     Prepared {
         request: Option<Request<Params>>,
         connection: Conn,
+        shutdown: Option<Arc<ShutdownState>>,
     },
     AwaitingResponse {
         #[pin]
         fut: <Conn as Service<RequestPacket>>::Future,
+        guard: Option<InFlightGuard>,
     },
     Complete,
 }
@@ -41,9 +47,11 @@ where
 {
     fn clone(&self) -> Self {
         match self {
-            Self::Prepared { request, connection } => {
-                Self::Prepared { request: request.clone(), connection: connection.clone() }
-            }
+            Self::Prepared { request, connection, shutdown } => Self::Prepared {
+                request: request.clone(),
+                connection: connection.clone(),
+                shutdown: shutdown.clone(),
+            },
             _ => panic!("cloned after dispatch"),
         }
     }
@@ -73,7 +81,7 @@ where
     fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
         loop {
             match self.as_mut().project() {
-                CallStateProj::Prepared { connection, request } => {
+                CallStateProj::Prepared { connection, request, shutdown } => {
                     if let Err(e) =
                         task::ready!(Service::<RequestPacket>::poll_ready(connection, cx))
                     {
@@ -81,8 +89,21 @@ where
                         return Ready(RpcResult::Err(e));
                     }
 
+                    let guard = match shutdown.as_ref().map(ShutdownState::enter) {
+                        Some(None) => {
+                            self.set(Self::Complete);
+                            return Ready(RpcResult::Err(TransportErrorKind::client_closed()));
+                        }
+                        guard => guard.flatten(),
+                    };
+
                     let request = request.take().expect("no request");
-                    debug!(method=%request.meta.method, id=%request.meta.id, "sending request");
+                    debug!(
+                        method=%request.meta.method,
+                        id=%request.meta.id,
+                        correlation_id=request.meta.correlation_id(),
+                        "sending request"
+                    );
                     trace!(params_ty=%std::any::type_name::<Params>(), ?request, "full request");
                     let request = request.serialize();
                     let fut = match request {
@@ -96,13 +117,21 @@ where
                             return Ready(RpcResult::Err(TransportError::ser_err(err)));
                         }
                     };
-                    self.set(Self::AwaitingResponse { fut });
+                    self.set(Self::AwaitingResponse { fut, guard });
                 }
-                CallStateProj::AwaitingResponse { fut } => {
-                    let res = match task::ready!(fut.poll(cx)) {
-                        Ok(ResponsePacket::Single(res)) => Ready(transform_response(res)),
-                        Err(e) => Ready(RpcResult::Err(e)),
-                        _ => panic!("received batch response from single request"),
+                CallStateProj::AwaitingResponse { fut, guard } => {
+                    let res = match fut.poll(cx) {
+                        Ready(Ok(ResponsePacket::Single(res))) => Ready(transform_response(res)),
+                        Ready(Err(e)) => Ready(RpcResult::Err(e)),
+                        Ready(_) => panic!("received batch response from single request"),
+                        // the client stopped waiting for the response
+                        task::Poll::Pending => match guard {
+                            Some(guard) => {
+                                task::ready!(guard.poll_aborted(cx));
+                                Ready(RpcResult::Err(TransportErrorKind::client_closed()))
+                            }
+                            None => return task::Poll::Pending,
+                        },
                     };
                     self.set(Self::Complete);
                     return res;
@@ -167,11 +196,20 @@ where
     #[doc(hidden)]
     pub fn new(req: Request<Params>, connection: Conn) -> Self {
         Self {
-            state: CallState::Prepared { request: Some(req), connection },
+            state: CallState::Prepared { request: Some(req), connection, shutdown: None },
             map: Some(std::convert::identity),
             _pd: PhantomData,
         }
     }
+
+    /// Fail the call if the client has been shut down, and track it while it
+    /// is in flight.
+    pub(crate) fn with_shutdown(mut self, state: Arc<ShutdownState>) -> Self {
+        if let CallState::Prepared { shutdown, .. } = &mut self.state {
+            *shutdown = Some(state);
+        }
+        self
+    }
 }
 
 impl<Conn, Params, Resp, Output, Map> RpcCall<Conn, Params, Resp, Output, Map>
@@ -225,6 +263,75 @@ where
         self.request_mut().meta.set_subscription_status(status);
     }
 
+    /// Set the scheduling priority of the request, see [`RequestMeta::priority`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    pub fn set_priority(&mut self, priority: RequestPriority) {
+        self.request_mut().meta.set_priority(priority);
+    }
+
+    /// Set the scheduling priority of the request, see [`RequestMeta::priority`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    pub fn with_priority(mut self, priority: RequestPriority) -> Self {
+        self.set_priority(priority);
+        self
+    }
+
+    /// Set the correlation ID of the request, see [`RequestMeta::correlation_id`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    pub fn set_correlation_id(&mut self, correlation_id: Option<Cow<'static, str>>) {
+        self.request_mut().meta.set_correlation_id(correlation_id);
+    }
+
+    /// Set the correlation ID of the request, see [`RequestMeta::correlation_id`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<Cow<'static, str>>) -> Self {
+        self.set_correlation_id(Some(correlation_id.into()));
+        self
+    }
+
+    /// Set the time after which the response is no longer useful, see
+    /// [`RequestMeta::deadline`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.request_mut().meta.set_deadline(deadline);
+    }
+
+    /// Set the time after which the response is no longer useful, see
+    /// [`RequestMeta::deadline`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.set_deadline(Some(deadline));
+        self
+    }
+
+    /// Mark the request as safe or unsafe to send more than once, see
+    /// [`RequestMeta::is_idempotent`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    pub fn set_idempotent(&mut self, idempotent: bool) {
+        self.request_mut().meta.set_idempotent(idempotent);
+    }
+
     /// Get a mutable reference to the params of the request.
     ///
     /// This is useful for modifying the params after the request has been
@@ -266,12 +373,12 @@ where
         self,
         map: impl Fn(Params) -> NewParams,
     ) -> RpcCall<Conn, NewParams, Resp, Output, Map> {
-        let CallState::Prepared { request, connection } = self.state else {
+        let CallState::Prepared { request, connection, shutdown } = self.state else {
             panic!("Cannot get request after request has been sent");
         };
         let request = request.expect("no request in prepared").map_params(map);
         RpcCall {
-            state: CallState::Prepared { request: Some(request), connection },
+            state: CallState::Prepared { request: Some(request), connection, shutdown },
             map: self.map,
             _pd: PhantomData,
         }
@@ -291,13 +398,13 @@ where
     ///
     /// Panics if called after the request has been polled.
     pub fn into_owned_params(self) -> RpcCall<Conn, Params::Owned, Resp, Output, Map> {
-        let CallState::Prepared { request, connection } = self.state else {
+        let CallState::Prepared { request, connection, shutdown } = self.state else {
             panic!("Cannot get params after request has been sent");
         };
         let request = request.expect("no request in prepared").into_owned_params();
 
         RpcCall {
-            state: CallState::Prepared { request: Some(request), connection },
+            state: CallState::Prepared { request: Some(request), connection, shutdown },
             map: self.map,
             _pd: PhantomData,
         }
@@ -1,13 +1,19 @@
-use crate::{poller::PollerBuilder, BatchRequest, ClientBuilder, RpcCall};
+use crate::{
+    poller::PollerBuilder, shutdown::ShutdownState, BatchRequest, ClientBuilder, RequestIdStrategy,
+    RpcCall,
+};
 use alloy_json_rpc::{Id, Request, RpcParam, RpcReturn};
 use alloy_transport::{BoxTransport, Transport};
 use alloy_transport_http::Http;
+use futures::future::{select, Either};
 use std::{
     borrow::Cow,
+    future::Future,
     ops::Deref,
+    pin::pin,
     sync::{
         atomic::{AtomicU64, Ordering},
-        Arc, Weak,
+        Arc, OnceLock, Weak,
     },
     time::Duration,
 };
@@ -119,12 +125,34 @@ impl<T: Transport + Clone> RpcClient<T> {
     /// This will create a new client if this instance is not the only reference to the inner
     /// client.
     pub fn boxed(self) -> RpcClient<BoxTransport> {
-        let inner = match Arc::try_unwrap(self.0) {
+        RpcClient::from_inner(self.into_owned_inner().boxed())
+    }
+
+    /// Sets the strategy used to generate request IDs.
+    ///
+    /// This will create a new client if this instance is not the only reference to the inner
+    /// client.
+    pub fn with_id_strategy(self, id_strategy: RequestIdStrategy) -> Self {
+        Self::from_inner(self.into_owned_inner().with_id_strategy(id_strategy))
+    }
+
+    /// Sets the correlation ID of all requests made by the client.
+    ///
+    /// This will create a new client if this instance is not the only reference to the inner
+    /// client.
+    pub fn with_correlation_id(self, correlation_id: impl Into<Cow<'static, str>>) -> Self {
+        Self::from_inner(self.into_owned_inner().with_correlation_id(correlation_id))
+    }
+
+    /// Returns the inner client, or a copy of it if this instance is not the only reference to it.
+    fn into_owned_inner(self) -> RpcClientInner<T> {
+        match Arc::try_unwrap(self.0) {
             Ok(inner) => inner,
             Err(inner) => RpcClientInner::new(inner.transport.clone(), inner.is_local)
-                .with_id(inner.id.load(Ordering::Relaxed)),
-        };
-        RpcClient::from_inner(inner.boxed())
+                .with_id(inner.id.load(Ordering::Relaxed))
+                .with_id_strategy(inner.id_strategy.clone())
+                .with_correlation_id_opt(inner.correlation_id.clone()),
+        }
     }
 }
 
@@ -152,7 +180,8 @@ impl<T> Deref for RpcClient<T> {
 ///
 /// ### Note
 ///
-/// IDs are allocated sequentially, starting at 0. IDs are reserved via
+/// IDs are allocated sequentially, starting at 0, and generated with the
+/// client's [`RequestIdStrategy`]. IDs are reserved via
 /// [`RpcClientInner::next_id`]. Note that allocated IDs may not be used. There
 /// is no guarantee that a prepared [`RpcCall`] will be sent, or that a sent
 /// call will receive a response.
@@ -164,8 +193,14 @@ pub struct RpcClientInner<T> {
     pub(crate) is_local: bool,
     /// The next request ID to use.
     pub(crate) id: AtomicU64,
+    /// The strategy used to generate request IDs.
+    pub(crate) id_strategy: RequestIdStrategy,
+    /// The correlation ID of all requests made by the client.
+    pub(crate) correlation_id: Option<Cow<'static, str>>,
     /// The poll interval for the client in milliseconds.
     pub(crate) poll_interval: AtomicU64,
+    /// The shutdown state, shared with the in-flight calls.
+    pub(crate) shutdown: OnceLock<Arc<ShutdownState>>,
 }
 
 impl<T> RpcClientInner<T> {
@@ -179,7 +214,10 @@ impl<T> RpcClientInner<T> {
             transport: t,
             is_local,
             id: AtomicU64::new(0),
+            id_strategy: RequestIdStrategy::Sequential,
+            correlation_id: None,
             poll_interval: if is_local { AtomicU64::new(250) } else { AtomicU64::new(7000) },
+            shutdown: OnceLock::new(),
         }
     }
 
@@ -189,6 +227,36 @@ impl<T> RpcClientInner<T> {
         Self { id: AtomicU64::new(id), ..self }
     }
 
+    /// Sets the strategy used to generate request IDs.
+    #[inline]
+    pub fn with_id_strategy(self, id_strategy: RequestIdStrategy) -> Self {
+        Self { id_strategy, ..self }
+    }
+
+    /// Returns the strategy used to generate request IDs.
+    #[inline]
+    pub const fn id_strategy(&self) -> &RequestIdStrategy {
+        &self.id_strategy
+    }
+
+    /// Sets the correlation ID of all requests made by the client.
+    ///
+    /// See [`RequestMeta::correlation_id`](alloy_json_rpc::RequestMeta::correlation_id).
+    #[inline]
+    pub fn with_correlation_id(self, correlation_id: impl Into<Cow<'static, str>>) -> Self {
+        self.with_correlation_id_opt(Some(correlation_id.into()))
+    }
+
+    fn with_correlation_id_opt(self, correlation_id: Option<Cow<'static, str>>) -> Self {
+        Self { correlation_id, ..self }
+    }
+
+    /// Returns the correlation ID of all requests made by the client, if any.
+    #[inline]
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
     /// Returns the default poll interval (milliseconds) for the client.
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.poll_interval.load(Ordering::Relaxed))
@@ -242,7 +310,9 @@ impl<T> RpcClientInner<T> {
         method: impl Into<Cow<'static, str>>,
         params: Params,
     ) -> Request<Params> {
-        Request::new(method, self.next_id(), params)
+        let mut request = Request::new(method, self.next_id(), params);
+        request.meta.set_correlation_id(self.correlation_id.clone());
+        request
     }
 
     /// `true` if the client believes the transport is local.
@@ -268,10 +338,21 @@ impl<T> RpcClientInner<T> {
         self.id.fetch_add(1, Ordering::Relaxed)
     }
 
-    /// Reserve a request ID u64.
+    /// Reserve a request ID, generated with the client's [`RequestIdStrategy`].
     #[inline]
     pub fn next_id(&self) -> Id {
-        self.increment_id().into()
+        self.id_strategy.generate(self.increment_id())
+    }
+
+    /// Returns the shutdown state of the client.
+    pub(crate) fn shutdown_state(&self) -> &Arc<ShutdownState> {
+        self.shutdown.get_or_init(Default::default)
+    }
+
+    /// Returns `true` if the client has been shut down, see
+    /// [`shutdown`](RpcClientInner::shutdown).
+    pub fn is_closed(&self) -> bool {
+        self.shutdown.get().is_some_and(|state| state.is_closed())
     }
 }
 
@@ -293,7 +374,7 @@ impl<T: Transport + Clone> RpcClientInner<T> {
         params: Params,
     ) -> RpcCall<T, Params, Resp> {
         let request = self.make_request(method, params);
-        RpcCall::new(request, self.transport.clone())
+        RpcCall::new(request, self.transport.clone()).with_shutdown(self.shutdown_state().clone())
     }
 
     /// Prepares an [`RpcCall`] with no parameters.
@@ -319,15 +400,84 @@ impl<T: Transport + Clone> RpcClientInner<T> {
             transport: self.transport.boxed(),
             is_local: self.is_local,
             id: self.id,
+            id_strategy: self.id_strategy,
+            correlation_id: self.correlation_id,
             poll_interval: self.poll_interval,
+            shutdown: self.shutdown,
+        }
+    }
+
+    /// Gracefully shut down the client, waiting at most `timeout` for the
+    /// in-flight requests.
+    ///
+    /// See [`shutdown_with`](Self::shutdown_with) for details. The timeout is
+    /// measured with the Tokio timer, which requires a Tokio runtime with the
+    /// time driver enabled. Use [`shutdown_with`](Self::shutdown_with) with a
+    /// timer of the runtime otherwise.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.shutdown_with(tokio::time::sleep(timeout)).await
+    }
+
+    /// Gracefully shut down the client, waiting for the in-flight requests
+    /// until the `deadline` future completes.
+    ///
+    /// The client stops accepting new requests, which fail with
+    /// [`TransportErrorKind::ClientClosed`], and waits for the in-flight
+    /// requests to complete. Requests that are still in flight when the
+    /// deadline is reached also fail with [`TransportErrorKind::ClientClosed`].
+    /// If the transport is a pubsub transport, all subscriptions are then
+    /// cancelled with `eth_unsubscribe` and the connection is closed, until
+    /// the deadline.
+    ///
+    /// The deadline can be any future, e.g. the timer of the runtime, which
+    /// makes this usable on any runtime and on wasm.
+    ///
+    /// Returns `true` if all in-flight requests completed before the deadline.
+    ///
+    /// [`TransportErrorKind::ClientClosed`]: alloy_transport::TransportErrorKind::ClientClosed
+    pub async fn shutdown_with(&self, deadline: impl Future<Output = ()>) -> bool {
+        let state = self.shutdown_state();
+        state.close();
+
+        let mut deadline = pin!(deadline);
+        let drained = match select(pin!(state.wait_drained()), deadline.as_mut()).await {
+            Either::Left(_) => true,
+            Either::Right(_) => {
+                debug!("in-flight requests did not complete before the shutdown deadline");
+                state.abort();
+                false
+            }
+        };
+
+        #[cfg(feature = "pubsub")]
+        if let Some(frontend) = self.pubsub_frontend() {
+            let shutdown = pin!(frontend.shutdown());
+            // the deadline can't be polled again once it completed, but the
+            // unsubscriptions are still dispatched
+            let result = if drained {
+                match select(shutdown, deadline).await {
+                    Either::Left((result, _)) => Some(result),
+                    Either::Right(_) => None,
+                }
+            } else {
+                futures::FutureExt::now_or_never(shutdown)
+            };
+            if let Some(Err(err)) = result {
+                debug!(%err, "failed to shut down pubsub service");
+            }
         }
+
+        drained
     }
 }
 
 #[cfg(feature = "pubsub")]
 mod pubsub_impl {
     use super::*;
-    use alloy_pubsub::{PubSubConnect, PubSubFrontend, RawSubscription, Subscription};
+    use alloy_pubsub::{
+        ConnectionEvents, LagPolicy, PubSubConnect, PubSubFrontend, RawSubscription, Subscription,
+    };
     use alloy_transport::TransportResult;
 
     impl RpcClientInner<PubSubFrontend> {
@@ -343,6 +493,12 @@ mod pubsub_impl {
         ) -> Subscription<T> {
             Subscription::from(self.get_raw_subscription(id).await)
         }
+
+        /// Get a stream of the connection events of the pubsub service, e.g.
+        /// disconnections and reconnections.
+        pub fn connection_events(&self) -> ConnectionEvents {
+            self.transport.connection_events()
+        }
     }
 
     impl RpcClient<PubSubFrontend> {
@@ -368,6 +524,17 @@ mod pubsub_impl {
         pub fn set_channel_size(&self, size: usize) {
             self.transport.set_channel_size(size)
         }
+
+        /// Get the currently configured [`LagPolicy`] of new subscriptions.
+        /// Defaults to [`LagPolicy::DropOldest`].
+        pub fn lag_policy(&self) -> LagPolicy {
+            self.transport.lag_policy()
+        }
+
+        /// Set the [`LagPolicy`] of new subscriptions.
+        pub fn set_lag_policy(&self, lag_policy: LagPolicy) {
+            self.transport.set_lag_policy(lag_policy)
+        }
     }
 }
 
@@ -382,4 +549,46 @@ mod tests {
             .with_poll_interval(poll_interval);
         assert_eq!(client.poll_interval(), poll_interval);
     }
+
+    #[test]
+    fn test_client_with_id_strategy() {
+        let client = RpcClient::new_http(reqwest::Url::parse("http://localhost").unwrap())
+            .with_id_strategy(RequestIdStrategy::prefixed("worker-1"))
+            .with_correlation_id("job-7");
+        assert_eq!(client.next_id(), Id::String("worker-1-0".into()));
+
+        let request = client.make_request("eth_blockNumber", ());
+        assert_eq!(request.meta.id, Id::String("worker-1-1".into()));
+        assert_eq!(request.meta.correlation_id(), Some("job-7"));
+
+        // the settings survive boxing a shared client
+        let _shared = client.clone();
+        let boxed = client.boxed();
+        assert_eq!(boxed.id_strategy(), &RequestIdStrategy::prefixed("worker-1"));
+        assert_eq!(boxed.correlation_id(), Some("job-7"));
+        assert_eq!(boxed.next_id(), Id::String("worker-1-2".into()));
+    }
+
+    #[tokio::test]
+    async fn test_client_shutdown() {
+        use alloy_json_rpc::{RequestPacket, RpcError};
+        use alloy_transport::{TransportErrorKind, TransportFut};
+
+        // a transport that never responds
+        let transport = tower::service_fn(|_: RequestPacket| -> TransportFut<'static> {
+            Box::pin(futures::future::pending())
+        });
+        let client = RpcClient::new(transport, true);
+
+        let call = tokio::spawn(client.request_noparams::<u64>("eth_blockNumber"));
+        tokio::task::yield_now().await;
+
+        assert!(!client.shutdown(Duration::from_millis(10)).await);
+        assert!(client.is_closed());
+        let err = call.await.unwrap().unwrap_err();
+        assert!(matches!(err, RpcError::Transport(TransportErrorKind::ClientClosed)));
+
+        let err = client.request_noparams::<u64>("eth_blockNumber").await.unwrap_err();
+        assert!(matches!(err, RpcError::Transport(TransportErrorKind::ClientClosed)));
+    }
 }
@@ -0,0 +1,76 @@
+use alloy_json_rpc::Id;
+use std::borrow::Cow;
+
+/// The strategy an [`RpcClient`](crate::RpcClient) uses to generate request IDs.
+///
+/// IDs only need to be unique among the in-flight requests of a client, but deterministic IDs
+/// make it easier to match requests across the logs of several services.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RequestIdStrategy {
+    /// Sequential numbers, e.g. `3`. This is the default.
+    #[default]
+    Sequential,
+    /// Random version 4 UUIDs, e.g. `"67e55044-10b1-426f-9247-bb680e5fe0c8"`.
+    ///
+    /// These are unique across clients, e.g. when many clients share a proxy. Requires the
+    /// `random-ids` feature, which needs a source of randomness on the target.
+    #[cfg(feature = "random-ids")]
+    Uuid,
+    /// Sequential numbers prefixed with a fixed string, e.g. `"worker-1-3"` with the prefix
+    /// `"worker-1"`.
+    ///
+    /// These are unique across clients with distinct prefixes, while staying deterministic.
+    Prefixed(Cow<'static, str>),
+}
+
+impl RequestIdStrategy {
+    /// Creates a strategy generating sequential numbers prefixed with the prefix.
+    pub fn prefixed(prefix: impl Into<Cow<'static, str>>) -> Self {
+        Self::Prefixed(prefix.into())
+    }
+
+    /// Generates the ID of the request with the sequence number.
+    pub fn generate(&self, sequence: u64) -> Id {
+        match self {
+            Self::Sequential => Id::Number(sequence),
+            #[cfg(feature = "random-ids")]
+            Self::Uuid => Id::String(uuid_v4()),
+            Self::Prefixed(prefix) => Id::String(format!("{prefix}-{sequence}")),
+        }
+    }
+}
+
+/// Generates a random version 4 UUID.
+#[cfg(feature = "random-ids")]
+fn uuid_v4() -> String {
+    let mut bytes = alloy_primitives::B128::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = alloy_primitives::hex::encode(bytes);
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_ids() {
+        assert_eq!(RequestIdStrategy::Sequential.generate(3), Id::Number(3));
+        assert_eq!(
+            RequestIdStrategy::prefixed("worker-1").generate(3),
+            Id::String("worker-1-3".into())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "random-ids")]
+    fn generate_uuids() {
+        let Id::String(uuid) = RequestIdStrategy::Uuid.generate(3) else { panic!("not a string") };
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+        assert!(matches!(&uuid[19..20], "8" | "9" | "a" | "b"));
+        assert_ne!(RequestIdStrategy::Uuid.generate(3), Id::String(uuid));
+    }
+}
@@ -24,9 +24,14 @@ pub use call::RpcCall;
 mod client;
 pub use client::{ClientRef, NoParams, RpcClient, RpcClientInner, WeakClient};
 
+mod id;
+pub use id::RequestIdStrategy;
+
 mod poller;
 pub use poller::{PollChannel, PollerBuilder};
 
+mod shutdown;
+
 #[cfg(feature = "ws")]
 pub use alloy_transport_ws::WsConnect;
 
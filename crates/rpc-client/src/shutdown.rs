@@ -0,0 +1,147 @@
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, Shared},
+};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+use tokio::sync::Notify;
+
+/// The shutdown state of a client, shared with its in-flight calls.
+pub(crate) struct ShutdownState {
+    /// Whether the client has stopped accepting new requests.
+    closed: AtomicBool,
+    /// The number of calls that have been sent, but not completed.
+    in_flight: AtomicUsize,
+    /// Notified when the last in-flight call completes.
+    drained: Notify,
+    /// Dropped to abort the in-flight calls.
+    abort: Mutex<Option<oneshot::Sender<()>>>,
+    /// Completes once the in-flight calls are aborted.
+    aborted: Shared<oneshot::Receiver<()>>,
+}
+
+impl fmt::Debug for ShutdownState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShutdownState")
+            .field("closed", &self.is_closed())
+            .field("in_flight", &self.in_flight.load(Ordering::SeqCst))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ShutdownState {
+    fn default() -> Self {
+        let (abort, aborted) = oneshot::channel();
+        Self {
+            closed: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+            drained: Notify::new(),
+            abort: Mutex::new(Some(abort)),
+            aborted: aborted.shared(),
+        }
+    }
+}
+
+impl ShutdownState {
+    /// Returns `true` if the client has stopped accepting new requests.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Stop accepting new requests.
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Register an in-flight call, or return `None` if the client is closed.
+    pub(crate) fn enter(self: &Arc<Self>) -> Option<InFlightGuard> {
+        // the call is registered before checking, so that `wait_drained` can not miss it
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlightGuard { state: self.clone(), aborted: self.aborted.clone() };
+        (!self.is_closed()).then_some(guard)
+    }
+
+    /// Wait until there are no in-flight calls.
+    pub(crate) async fn wait_drained(&self) {
+        loop {
+            let notified = self.drained.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Wake all in-flight calls, which then fail.
+    pub(crate) fn abort(&self) {
+        self.abort.lock().unwrap().take();
+    }
+}
+
+/// Tracks an in-flight call of a client. See [`ShutdownState::enter`].
+#[derive(Debug)]
+pub(crate) struct InFlightGuard {
+    state: Arc<ShutdownState>,
+    aborted: Shared<oneshot::Receiver<()>>,
+}
+
+impl InFlightGuard {
+    /// Poll whether the call has been aborted.
+    pub(crate) fn poll_aborted(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.aborted.poll_unpin(cx).map(drop)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.state.drained.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{future::poll_fn, time::Duration};
+
+    #[tokio::test]
+    async fn drains_in_flight() {
+        let state = Arc::new(ShutdownState::default());
+        let guard = state.enter().unwrap();
+        state.close();
+        assert!(state.enter().is_none());
+
+        let waiter = tokio::spawn({
+            let state = state.clone();
+            async move { state.wait_drained().await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!waiter.is_finished());
+
+        drop(guard);
+        waiter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn aborts_in_flight() {
+        let state = Arc::new(ShutdownState::default());
+        let mut guard = state.enter().unwrap();
+        poll_fn(|cx| {
+            assert!(guard.poll_aborted(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        state.abort();
+        poll_fn(|cx| guard.poll_aborted(cx)).await;
+    }
+}
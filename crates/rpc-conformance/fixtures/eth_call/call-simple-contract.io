@@ -0,0 +1,3 @@
+// performs a basic contract call with default settings
+>> {"jsonrpc":"2.0","id":1,"method":"eth_call","params":[{"from":null,"to":"0x0ee3ab1371c93e7c0c281cc0c2107cdebc8b1930","data":"0x"},"latest"]}
+<< {"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000001"}
@@ -0,0 +1,3 @@
+// gets fee history information
+>> {"jsonrpc":"2.0","id":1,"method":"eth_feeHistory","params":["0x1","0x2",[95,99]]}
+<< {"jsonrpc":"2.0","id":1,"result":{"oldestBlock":"0x2","reward":[["0x1","0x1"]],"baseFeePerGas":["0x2dbf1f9a","0x281d1c3c"],"gasUsedRatio":[0.0021455],"baseFeePerBlobGas":["0x1","0x1"],"blobGasUsedRatio":[0]}}
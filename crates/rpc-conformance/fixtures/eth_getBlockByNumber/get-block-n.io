@@ -0,0 +1,3 @@
+// gets a block by number
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getBlockByNumber","params":["0x2",true]}
+<< {"jsonrpc":"2.0","id":1,"result":{"baseFeePerGas":"0x2dbf1f9a","blobGasUsed":"0x0","difficulty":"0x0","excessBlobGas":"0x0","extraData":"0x","gasLimit":"0x23f3e20","gasUsed":"0x19d36","hash":"0x2c8e6e4c2ee5ee4c679f2a4c731da8a1d24eb29c3df328e0cde8a4b9bdca7ff4","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","miner":"0x0000000000000000000000000000000000000000","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","nonce":"0x0000000000000000","number":"0x2","parentBeaconBlockRoot":"0xf653da50cdff4733f13f7a5e338290e883bdf04adf3f112709728063ea965d6c","parentHash":"0x7b0bb3b6c8342c1a0493c6985cb23b676db1ff5a0cd4b7ecbbcad0b0fd5a8ff4","receiptsRoot":"0x9e2a4ef4a6c304d96329a0ddd57bdf0f5e57fc1ed933ff7c626ba2b9f6303b23","requestsHash":"0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855","sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","size":"0x2a8","stateRoot":"0xd0c8552cf3b2e9bd35b3a4f6b978e4a1e48a1c1cc35b6ec7a8ab85b4e4a3fa2b","timestamp":"0x14","transactions":[],"transactionsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","uncles":[],"withdrawals":[],"withdrawalsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"}}
@@ -0,0 +1,3 @@
+// queries for logs with two topics, with both topics set explicitly
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getLogs","params":[{"address":null,"fromBlock":"0x1","toBlock":"0x3","topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"]}]}
+<< {"jsonrpc":"2.0","id":1,"result":[{"address":"0x0c2c51a0990aee1d73c1228de158688341557508","topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"],"data":"0x","blockNumber":"0x2","transactionHash":"0x3f0e77f4b954583b19bbd23d388ed9a7d38a9bf6ee7fa1bb6ab2ba9d624b0d95","transactionIndex":"0x0","blockHash":"0x2c8e6e4c2ee5ee4c679f2a4c731da8a1d24eb29c3df328e0cde8a4b9bdca7ff4","logIndex":"0x0","removed":false}]}
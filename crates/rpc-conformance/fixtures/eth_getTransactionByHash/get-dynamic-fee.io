@@ -0,0 +1,3 @@
+// gets a dynamic fee transaction
+>> {"jsonrpc":"2.0","id":1,"method":"eth_getTransactionByHash","params":["0x3f0e77f4b954583b19bbd23d388ed9a7d38a9bf6ee7fa1bb6ab2ba9d624b0d95"]}
+<< {"jsonrpc":"2.0","id":1,"result":{"blockHash":"0x2c8e6e4c2ee5ee4c679f2a4c731da8a1d24eb29c3df328e0cde8a4b9bdca7ff4","blockNumber":"0x2","from":"0x7435ed30a8b4aeb0877cef0c6e8cffe834eb865f","gas":"0x186a0","gasPrice":"0x2dbf1f9b","maxFeePerGas":"0x5763d64c","maxPriorityFeePerGas":"0x1","hash":"0x3f0e77f4b954583b19bbd23d388ed9a7d38a9bf6ee7fa1bb6ab2ba9d624b0d95","input":"0x","nonce":"0x1","to":"0x0c2c51a0990aee1d73c1228de158688341557508","transactionIndex":"0x0","value":"0x1","type":"0x2","accessList":[],"chainId":"0xc72dd9d5e883e","v":"0x1","r":"0x6f2c2d1d3ae0b35ecc9039a9695f14c9f7c09bcf3ab1b4a2f08bd6c6ad4a5e10","s":"0x3f6a568a2e1a2d7d36a9ba7bdcc1fdf1c1d6a1d7f1a6efc2c6b2a7cbd4a3e2f1","yParity":"0x1"}}
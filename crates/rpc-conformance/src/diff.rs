@@ -0,0 +1,104 @@
+use serde_json::Value;
+use std::fmt;
+
+/// A difference between the JSON of a fixture and the JSON serialized by alloy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Difference {
+    /// A field of the fixture is not serialized by alloy.
+    Missing {
+        /// The JSON path of the field, e.g. `$.transactions[0].yParity`.
+        path: String,
+    },
+    /// alloy serializes a field that is not in the fixture.
+    Unexpected {
+        /// The JSON path of the field.
+        path: String,
+    },
+    /// A value differs.
+    Changed {
+        /// The JSON path of the value.
+        path: String,
+        /// The value of the fixture.
+        expected: Value,
+        /// The value serialized by alloy.
+        actual: Value,
+    },
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { path } => write!(f, "missing {path}"),
+            Self::Unexpected { path } => write!(f, "unexpected {path}"),
+            Self::Changed { path, expected, actual } => {
+                write!(f, "changed {path}: expected {expected}, got {actual}")
+            }
+        }
+    }
+}
+
+/// Returns the differences between the expected and actual JSON values.
+///
+/// Object fields that are `null` are treated like absent fields, since the specification does not
+/// distinguish them for optional fields, and numbers are compared by value.
+pub fn diff(expected: &Value, actual: &Value) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_at("$", expected, actual, &mut differences);
+    differences
+}
+
+fn diff_at(path: &str, expected: &Value, actual: &Value, differences: &mut Vec<Difference>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected) in expected {
+                let path = format!("{path}.{key}");
+                match actual.get(key) {
+                    Some(actual) => diff_at(&path, expected, actual, differences),
+                    None if expected.is_null() => {}
+                    None => differences.push(Difference::Missing { path }),
+                }
+            }
+            for (key, actual) in actual {
+                if !expected.contains_key(key) && !actual.is_null() {
+                    differences.push(Difference::Unexpected { path: format!("{path}.{key}") });
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                diff_at(&format!("{path}[{index}]"), expected, actual, differences);
+            }
+        }
+        // `0` and `0.0` are the same JSON number
+        (Value::Number(expected), Value::Number(actual))
+            if expected.as_f64().is_some() && expected.as_f64() == actual.as_f64() => {}
+        _ if expected == actual => {}
+        _ => differences.push(Difference::Changed {
+            path: path.to_string(),
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diffs_json() {
+        let expected = json!({"a": "0x1", "b": [1, 2], "c": null, "d": {"e": true}, "h": 0});
+        let actual = json!({"a": "0x01", "b": [1, 3], "d": {}, "f": 1, "g": null, "h": 0.0});
+        assert_eq!(
+            diff(&expected, &actual).iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "changed $.a: expected \"0x1\", got \"0x01\"",
+                "changed $.b[1]: expected 2, got 3",
+                "missing $.d.e",
+                "unexpected $.f",
+            ]
+        );
+        assert!(diff(&expected, &expected).is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::ConformanceError;
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A JSON-RPC exchange of the execution-apis test fixtures.
+///
+/// Fixtures are `.io` files of the form:
+///
+/// ```text
+/// // an optional description
+/// >> {"jsonrpc":"2.0","id":1,"method":"eth_blockNumber"}
+/// << {"jsonrpc":"2.0","id":1,"result":"0x2d"}
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fixture {
+    /// The name of the fixture, e.g. `eth_blockNumber/simple-test`.
+    pub name: String,
+    /// The method of the request.
+    pub method: String,
+    /// The params of the request, `null` if absent.
+    pub params: Value,
+    /// The result of the response, `None` if the response is an error.
+    pub result: Option<Value>,
+}
+
+impl Fixture {
+    /// Parses the fixture from the contents of its `.io` file.
+    pub fn parse(name: impl Into<String>, contents: &str) -> Result<Self, ConformanceError> {
+        let name = name.into();
+        let malformed = |reason: &str| ConformanceError::Malformed {
+            fixture: name.clone(),
+            reason: reason.to_string(),
+        };
+
+        let (mut request, mut response) = (None, None);
+        for line in contents.lines().map(str::trim) {
+            if let Some(json) = line.strip_prefix(">>") {
+                request = Some(
+                    serde_json::from_str::<Value>(json)
+                        .map_err(|err| malformed(&err.to_string()))?,
+                );
+            } else if let Some(json) = line.strip_prefix("<<") {
+                response = Some(
+                    serde_json::from_str::<Value>(json)
+                        .map_err(|err| malformed(&err.to_string()))?,
+                );
+            } else if !line.is_empty() && !line.starts_with("//") {
+                return Err(malformed("unexpected line"));
+            }
+        }
+
+        let mut request = request.ok_or_else(|| malformed("missing request"))?;
+        let mut response = response.ok_or_else(|| malformed("missing response"))?;
+        let method =
+            request["method"].as_str().ok_or_else(|| malformed("missing method"))?.to_string();
+        let params = request.get_mut("params").map(Value::take).unwrap_or_default();
+        let result = response.get_mut("result").map(Value::take);
+        Ok(Self { name, method, params, result })
+    }
+
+    /// Loads the fixtures of a directory laid out like the `tests` directory of execution-apis,
+    /// with one subdirectory of `.io` files per method, sorted by name.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Self>, ConformanceError> {
+        let dir = dir.as_ref();
+        let mut paths = Vec::new();
+        for entry in read_dir(dir)? {
+            if entry.is_dir() {
+                paths.extend(
+                    read_dir(&entry)?
+                        .into_iter()
+                        .filter(|path| path.extension().is_some_and(|extension| extension == "io")),
+                );
+            }
+        }
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| {
+                let name = path.strip_prefix(dir).unwrap_or(path).with_extension("");
+                let contents = fs::read_to_string(path)
+                    .map_err(|err| ConformanceError::Io(path.clone(), err))?;
+                Self::parse(name.to_string_lossy().replace('\\', "/"), &contents)
+            })
+            .collect()
+    }
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, ConformanceError> {
+    let io_error = |err| ConformanceError::Io(dir.to_path_buf(), err);
+    fs::read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()).map_err(io_error))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fixtures() {
+        let fixture = Fixture::parse(
+            "eth_blockNumber/simple-test",
+            "// retrieves the block number\n\
+             >> {\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"eth_blockNumber\"}\n\
+             << {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":\"0x2d\"}\n",
+        )
+        .unwrap();
+        assert_eq!(fixture.method, "eth_blockNumber");
+        assert_eq!(fixture.params, Value::Null);
+        assert_eq!(fixture.result, Some(Value::from("0x2d")));
+
+        let err = Fixture::parse("broken", ">> {}").unwrap_err();
+        assert_eq!(err.to_string(), "malformed fixture broken: missing response");
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{diff, ConformanceError, Difference, Fixture};
+use alloy_primitives::{Bytes, B256, U256, U64};
+use alloy_rpc_types_eth::{
+    server::{EthRequest, NetRequest, Web3Request},
+    AccessListResult, Block, EIP1186AccountProofResponse, FeeHistory, Log, SyncStatus, Transaction,
+    TransactionReceipt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+type RoundTrip = fn(&Value) -> Result<Value, serde_json::Error>;
+
+fn round_trip<T: DeserializeOwned + Serialize>(value: &Value) -> Result<Value, serde_json::Error> {
+    serde_json::to_value(T::deserialize(value)?)
+}
+
+/// The outcome of checking the fixtures with a [`Harness`].
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The names of the fixtures that passed.
+    pub passed: Vec<String>,
+    /// The names of the fixtures of methods without a registered response type.
+    pub skipped: Vec<String>,
+    /// The failures.
+    pub failures: Vec<ConformanceError>,
+}
+
+impl Report {
+    /// Returns `true` if no fixture failed.
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Checks fixtures against the alloy RPC types.
+///
+/// For each fixture, the harness checks that:
+/// - the params deserialize into the [`EthRequest`], [`NetRequest`] or [`Web3Request`] of the
+///   method, if the method is part of these namespaces,
+/// - the result deserializes into the response type registered for the method, and serializes back
+///   to the same JSON, up to the allowed differences.
+///
+/// [`Harness::default`] registers the response types of the standard methods.
+#[derive(Clone, Debug)]
+pub struct Harness {
+    results: HashMap<String, RoundTrip>,
+    allowed: HashMap<String, Vec<String>>,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        register_defaults(Self::new())
+    }
+}
+
+impl Harness {
+    /// Creates a harness without response types, which only checks request params.
+    pub fn new() -> Self {
+        Self { results: HashMap::new(), allowed: HashMap::new() }
+    }
+
+    /// Checks the results of the method against the response type `T`.
+    pub fn with_result<T: DeserializeOwned + Serialize>(
+        mut self,
+        method: impl Into<String>,
+    ) -> Self {
+        self.results.insert(method.into(), round_trip::<T>);
+        self
+    }
+
+    /// Allows the results of the method to differ at the JSON path and below, e.g. `$.uncles` or
+    /// `$.transactions[0].yParity`, for known deviations of alloy from the specification.
+    pub fn allow_difference(mut self, method: impl Into<String>, path: impl Into<String>) -> Self {
+        self.allowed.entry(method.into()).or_default().push(path.into());
+        self
+    }
+
+    /// Checks the fixture, returning `Ok(false)` if no response type is registered for its
+    /// method and only the params were checked.
+    pub fn check(&self, fixture: &Fixture) -> Result<bool, ConformanceError> {
+        self.check_params(fixture)?;
+
+        let (Some(round_trip), Some(result)) = (self.results.get(&fixture.method), &fixture.result)
+        else {
+            return Ok(false);
+        };
+        let actual = round_trip(result)
+            .map_err(|source| ConformanceError::Result { fixture: fixture.name.clone(), source })?;
+        let allowed = self.allowed.get(&fixture.method).map(Vec::as_slice).unwrap_or_default();
+        let differences = diff(result, &actual)
+            .into_iter()
+            .filter(|difference| !is_allowed(difference, allowed))
+            .collect::<Vec<_>>();
+        if differences.is_empty() {
+            Ok(true)
+        } else {
+            Err(ConformanceError::Mismatch { fixture: fixture.name.clone(), differences })
+        }
+    }
+
+    fn check_params(&self, fixture: &Fixture) -> Result<(), ConformanceError> {
+        let (method, params) = (fixture.method.as_str(), fixture.params.clone());
+        let result = if EthRequest::METHODS.contains(&method) {
+            EthRequest::from_parts(method, params).map(drop)
+        } else if NetRequest::METHODS.contains(&method) {
+            NetRequest::from_parts(method, params).map(drop)
+        } else if Web3Request::METHODS.contains(&method) {
+            Web3Request::from_parts(method, params).map(drop)
+        } else {
+            Ok(())
+        };
+        result.map_err(|err| ConformanceError::Params {
+            fixture: fixture.name.clone(),
+            reason: err.to_string(),
+        })
+    }
+
+    /// Checks all fixtures.
+    pub fn run<'a>(&self, fixtures: impl IntoIterator<Item = &'a Fixture>) -> Report {
+        let mut report = Report::default();
+        for fixture in fixtures {
+            match self.check(fixture) {
+                Ok(true) => report.passed.push(fixture.name.clone()),
+                Ok(false) => report.skipped.push(fixture.name.clone()),
+                Err(err) => report.failures.push(err),
+            }
+        }
+        report
+    }
+}
+
+/// Registers the response types of the standard methods.
+fn register_defaults(harness: Harness) -> Harness {
+    harness
+        .with_result::<U64>("eth_blockNumber")
+        .with_result::<U64>("eth_chainId")
+        .with_result::<U256>("eth_gasPrice")
+        .with_result::<U256>("eth_maxPriorityFeePerGas")
+        .with_result::<U256>("eth_blobBaseFee")
+        .with_result::<U256>("eth_getBalance")
+        .with_result::<U64>("eth_getTransactionCount")
+        .with_result::<U64>("eth_estimateGas")
+        .with_result::<Bytes>("eth_getCode")
+        .with_result::<B256>("eth_getStorageAt")
+        .with_result::<Bytes>("eth_call")
+        .with_result::<SyncStatus>("eth_syncing")
+        .with_result::<FeeHistory>("eth_feeHistory")
+        .with_result::<AccessListResult>("eth_createAccessList")
+        .with_result::<EIP1186AccountProofResponse>("eth_getProof")
+        .with_result::<Option<Block>>("eth_getBlockByHash")
+        .with_result::<Option<Block>>("eth_getBlockByNumber")
+        .with_result::<Option<U64>>("eth_getBlockTransactionCountByHash")
+        .with_result::<Option<U64>>("eth_getBlockTransactionCountByNumber")
+        .with_result::<Option<Transaction>>("eth_getTransactionByHash")
+        .with_result::<Option<Transaction>>("eth_getTransactionByBlockHashAndIndex")
+        .with_result::<Option<Transaction>>("eth_getTransactionByBlockNumberAndIndex")
+        .with_result::<Option<TransactionReceipt>>("eth_getTransactionReceipt")
+        .with_result::<Option<Vec<TransactionReceipt>>>("eth_getBlockReceipts")
+        .with_result::<Vec<Log>>("eth_getLogs")
+        // the EIP-7685 requests commitment is still serialized as `requestsRoot`
+        .allow_difference("eth_getBlockByHash", "$.requestsHash")
+        .allow_difference("eth_getBlockByNumber", "$.requestsHash")
+}
+
+fn is_allowed(difference: &Difference, allowed: &[String]) -> bool {
+    let path = match difference {
+        Difference::Missing { path }
+        | Difference::Unexpected { path }
+        | Difference::Changed { path, .. } => path,
+    };
+    allowed.iter().any(|allowed| {
+        path.strip_prefix(allowed.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+    })
+}
@@ -0,0 +1,112 @@
+#![doc = include_str!("../README.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/alloy.jpg",
+    html_favicon_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/favicon.ico"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod diff;
+pub use diff::{diff, Difference};
+
+mod fixture;
+pub use fixture::Fixture;
+
+mod harness;
+pub use harness::{Harness, Report};
+
+use std::path::PathBuf;
+
+/// The environment variable pointing to the `tests` directory of an execution-apis checkout.
+pub const EXECUTION_APIS_TESTS_ENV: &str = "ALLOY_EXECUTION_APIS_TESTS";
+
+/// A failure of the conformance harness.
+#[derive(Debug, thiserror::Error)]
+pub enum ConformanceError {
+    /// A fixture could not be read.
+    #[error("failed to read {}: {1}", .0.display())]
+    Io(PathBuf, #[source] std::io::Error),
+    /// A fixture is malformed.
+    #[error("malformed fixture {fixture}: {reason}")]
+    Malformed {
+        /// The name of the fixture.
+        fixture: String,
+        /// The reason the fixture is malformed.
+        reason: String,
+    },
+    /// The params of the fixture do not deserialize into the request type.
+    #[error("invalid params in {fixture}: {reason}")]
+    Params {
+        /// The name of the fixture.
+        fixture: String,
+        /// The deserialization error.
+        reason: String,
+    },
+    /// The result of the fixture does not deserialize into the response type.
+    #[error("invalid result in {fixture}: {source}")]
+    Result {
+        /// The name of the fixture.
+        fixture: String,
+        /// The deserialization error.
+        source: serde_json::Error,
+    },
+    /// The result of the fixture does not serialize back to the same JSON.
+    #[error(
+        "result of {fixture} does not round-trip: {}",
+        differences.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    Mismatch {
+        /// The name of the fixture.
+        fixture: String,
+        /// The differences between the fixture and the serialized result.
+        differences: Vec<Difference>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn assert_conformance(dir: &Path) {
+        let fixtures = Fixture::load_dir(dir).unwrap();
+        assert!(!fixtures.is_empty(), "no fixtures in {}", dir.display());
+
+        let report = Harness::default().run(&fixtures);
+        let failures = report.failures.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(report.is_success(), "{} failures:\n{}", failures.len(), failures.join("\n"));
+    }
+
+    #[test]
+    fn bundled_fixtures() {
+        assert_conformance(&Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures"));
+    }
+
+    #[test]
+    #[ignore = "requires ALLOY_EXECUTION_APIS_TESTS"]
+    fn execution_apis_fixtures() {
+        let dir = std::env::var_os(EXECUTION_APIS_TESTS_ENV)
+            .unwrap_or_else(|| panic!("{EXECUTION_APIS_TESTS_ENV} is not set"));
+        assert_conformance(Path::new(&dir));
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let fixture = Fixture::parse(
+            "eth_chainId/padded",
+            ">> {\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"eth_chainId\"}\n\
+             << {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":\"0x01\"}",
+        )
+        .unwrap();
+        let err = Harness::default().check(&fixture).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "result of eth_chainId/padded does not round-trip: changed $: expected \"0x01\", got \"0x1\""
+        );
+        assert!(Harness::default().allow_difference("eth_chainId", "$").check(&fixture).unwrap());
+        assert!(!Harness::new().check(&fixture).unwrap());
+
+        let fixture = Fixture { params: serde_json::json!(["0x1"]), ..fixture };
+        assert!(matches!(Harness::new().check(&fixture), Err(ConformanceError::Params { .. })));
+    }
+}
@@ -0,0 +1,128 @@
+use alloy_primitives::B256;
+use serde_json::value::RawValue;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Mutex,
+};
+
+/// The methods cached by default, whose responses never change for the same parameters.
+pub const DEFAULT_CACHED_METHODS: &[&str] = &[
+    "eth_chainId",
+    "net_version",
+    "eth_getBlockByHash",
+    "eth_getBlockTransactionCountByHash",
+    "eth_getTransactionByBlockHashAndIndex",
+    "eth_getUncleCountByBlockHash",
+];
+
+/// A cache of successful responses, keyed by the method and the parameters of the request.
+///
+/// Only the responses of the cached methods are stored, and `null` results are never stored,
+/// since they usually mean the requested data is not available yet. When the cache is full, the
+/// oldest entry is evicted.
+#[derive(Debug)]
+pub struct ResponseCache {
+    methods: HashSet<String>,
+    max_entries: usize,
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    responses: HashMap<(String, B256), Box<RawValue>>,
+    order: VecDeque<(String, B256)>,
+}
+
+impl ResponseCache {
+    /// Creates a cache of the [`DEFAULT_CACHED_METHODS`] holding up to `max_entries` responses.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            methods: DEFAULT_CACHED_METHODS.iter().map(|method| method.to_string()).collect(),
+            max_entries,
+            entries: Default::default(),
+        }
+    }
+
+    /// Also caches the responses of the method.
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.methods.insert(method.into());
+        self
+    }
+
+    /// Stops caching the responses of the method.
+    pub fn without_method(mut self, method: &str) -> Self {
+        self.methods.remove(method);
+        self
+    }
+
+    /// Returns `true` if the responses of the method are cached.
+    pub fn is_cached(&self, method: &str) -> bool {
+        self.methods.contains(method)
+    }
+
+    /// Returns the number of cached responses.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().responses.len()
+    }
+
+    /// Returns `true` if no responses are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached result of the request with the method and the hash of its parameters.
+    pub fn get(&self, method: &str, params_hash: B256) -> Option<Box<RawValue>> {
+        if !self.is_cached(method) {
+            return None;
+        }
+        self.entries.lock().unwrap().responses.get(&(method.to_string(), params_hash)).cloned()
+    }
+
+    /// Stores the result of the request with the method and the hash of its parameters, if the
+    /// method is cached and the result is not `null`.
+    pub fn insert(&self, method: &str, params_hash: B256, result: &RawValue) {
+        if !self.is_cached(method) || result.get() == "null" || self.max_entries == 0 {
+            return;
+        }
+
+        let key = (method.to_string(), params_hash);
+        let mut entries = self.entries.lock().unwrap();
+        if entries.responses.insert(key.clone(), result.to_owned()).is_none() {
+            entries.order.push_back(key);
+        }
+        while entries.order.len() > self.max_entries {
+            let Some(oldest) = entries.order.pop_front() else { break };
+            entries.responses.remove(&oldest);
+        }
+    }
+
+    /// Removes all cached responses.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.responses.clear();
+        entries.order.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest() {
+        let cache = ResponseCache::new(2).without_method("net_version");
+        let one = RawValue::from_string("\"0x1\"".into()).unwrap();
+        let null = RawValue::from_string("null".into()).unwrap();
+
+        cache.insert("eth_chainId", B256::with_last_byte(1), &one);
+        cache.insert("eth_getBlockByHash", B256::with_last_byte(1), &null);
+        cache.insert("net_version", B256::with_last_byte(1), &one);
+        assert_eq!(cache.len(), 1);
+
+        cache.insert("eth_chainId", B256::with_last_byte(2), &one);
+        cache.insert("eth_chainId", B256::with_last_byte(3), &one);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("eth_chainId", B256::with_last_byte(1)).is_none());
+        assert_eq!(cache.get("eth_chainId", B256::with_last_byte(3)).unwrap().get(), "\"0x1\"");
+    }
+}
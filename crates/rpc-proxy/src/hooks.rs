@@ -0,0 +1,48 @@
+use alloy_json_rpc::{Response, SerializedRequest};
+
+/// The action to take for a request, returned by a [`RequestHook`].
+#[derive(Debug)]
+pub enum RequestAction {
+    /// Forward the request, which may have been rewritten.
+    Forward(SerializedRequest),
+    /// Answer the request with the response instead of forwarding it.
+    Respond(Response),
+}
+
+/// A hook called on each request before it is forwarded.
+///
+/// Hooks can rewrite requests, e.g. to pin a block tag, or answer them directly, e.g. to reject
+/// methods the proxy does not expose. Closures taking a [`SerializedRequest`] and returning a
+/// [`RequestAction`] implement this trait.
+pub trait RequestHook: Send + Sync + 'static {
+    /// Returns the action to take for the request.
+    fn on_request(&self, request: SerializedRequest) -> RequestAction;
+}
+
+impl<F> RequestHook for F
+where
+    F: Fn(SerializedRequest) -> RequestAction + Send + Sync + 'static,
+{
+    fn on_request(&self, request: SerializedRequest) -> RequestAction {
+        self(request)
+    }
+}
+
+/// A hook called on each response before it is returned to the client.
+///
+/// Hooks are called with the method of the request, and are not called for responses returned by
+/// a [`RequestHook`]. Closures taking the method and a [`Response`] and returning a [`Response`]
+/// implement this trait.
+pub trait ResponseHook: Send + Sync + 'static {
+    /// Returns the response to return to the client.
+    fn on_response(&self, method: &str, response: Response) -> Response;
+}
+
+impl<F> ResponseHook for F
+where
+    F: Fn(&str, Response) -> Response + Send + Sync + 'static,
+{
+    fn on_response(&self, method: &str, response: Response) -> Response {
+        self(method, response)
+    }
+}
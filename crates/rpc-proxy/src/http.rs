@@ -0,0 +1,87 @@
+use crate::Proxy;
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    header, Method, Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use std::convert::Infallible;
+use tokio::net::TcpListener;
+
+/// Serves the proxy over HTTP on the listener, handling each connection in a new task.
+///
+/// JSON-RPC messages are accepted as the body of `POST` requests. Returns only if accepting a
+/// connection fails.
+pub async fn serve_http(listener: TcpListener, proxy: Proxy) -> std::io::Result<()> {
+    loop {
+        let (stream, remote) = listener.accept().await?;
+        let proxy = proxy.clone();
+        tokio::spawn(async move {
+            let service = hyper::service::service_fn(move |request| {
+                let proxy = proxy.clone();
+                async move { Ok::<_, Infallible>(handle(&proxy, request).await) }
+            });
+            if let Err(err) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!(%err, %remote, "http connection failed");
+            }
+        });
+    }
+}
+
+async fn handle(proxy: &Proxy, request: Request<Incoming>) -> Response<Full<Bytes>> {
+    if request.method() != Method::POST {
+        return status(StatusCode::METHOD_NOT_ALLOWED);
+    }
+    let body = match request.into_body().collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(_) => return status(StatusCode::BAD_REQUEST),
+    };
+    let body = String::from_utf8_lossy(&body);
+
+    let Some(reply) = proxy.handle_raw(&body).await else {
+        return status(StatusCode::NO_CONTENT);
+    };
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Full::new(Bytes::from(reply)))
+        .expect("valid response")
+}
+
+fn status(status: StatusCode) -> Response<Full<Bytes>> {
+    Response::builder().status(status).body(Full::default()).expect("valid response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, ResponsePayload};
+    use alloy_transport::TransportFut;
+    use alloy_transport_http::Http;
+    use serde_json::value::RawValue;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn serve_over_http() {
+        let upstream = tower::service_fn(|packet: RequestPacket| -> TransportFut<'static> {
+            let RequestPacket::Single(request) = packet else { unreachable!() };
+            let result = RawValue::from_string("\"0x1\"".into()).unwrap();
+            let response =
+                Response { id: request.id().clone(), payload: ResponsePayload::Success(result) };
+            Box::pin(async move { Ok(ResponsePacket::Single(response)) })
+        });
+        let proxy = Proxy::builder(upstream).build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        tokio::spawn(serve_http(listener, proxy));
+
+        let request =
+            alloy_json_rpc::Request::new("eth_chainId", 1.into(), ()).serialize().unwrap();
+        let response = Http::new(url).oneshot(RequestPacket::Single(request)).await.unwrap();
+        let ResponsePacket::Single(response) = response else { unreachable!() };
+        assert_eq!(response.payload.as_success().unwrap().get(), "\"0x1\"");
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{RequestAction, RequestHook, ResponseCache, ResponseHook};
+use alloy_json_rpc::{
+    ErrorPayload, Id, Request, RequestPacket, Response, ResponsePacket, ResponsePayload,
+    SerializedRequest,
+};
+use alloy_transport::{BoxTransport, Transport, TransportError, TransportFut};
+use futures::future::join_all;
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Service, ServiceExt};
+
+/// A JSON-RPC proxy that forwards requests to upstream transports.
+///
+/// Each request is handled as follows:
+/// 1. the [`RequestHook`]s are called in order, and may rewrite it or answer it directly
+/// 2. if the method is cached and the [`ResponseCache`] holds its result, the cached result is
+///    returned
+/// 3. otherwise, it is forwarded to the upstream of the first route matching its method, or to the
+///    default upstream
+/// 4. the [`ResponseHook`]s are called in order with the response
+///
+/// Requests of a batch are handled concurrently, and may be forwarded to different upstreams.
+///
+/// The proxy is itself a [`Transport`], so it can be used by RPC clients directly or served with
+/// [`serve_http`](crate::serve_http) and [`serve_ws`](crate::serve_ws). Subscriptions are not
+/// supported, since upstream notifications are not forwarded.
+#[derive(Clone, Debug)]
+pub struct Proxy {
+    inner: Arc<ProxyInner>,
+}
+
+struct ProxyInner {
+    upstream: BoxTransport,
+    routes: Vec<(String, BoxTransport)>,
+    cache: Option<ResponseCache>,
+    request_hooks: Vec<Box<dyn RequestHook>>,
+    response_hooks: Vec<Box<dyn ResponseHook>>,
+}
+
+impl std::fmt::Debug for ProxyInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyInner")
+            .field("routes", &self.routes.iter().map(|(pattern, _)| pattern).collect::<Vec<_>>())
+            .field("cache", &self.cache)
+            .field("request_hooks", &self.request_hooks.len())
+            .field("response_hooks", &self.response_hooks.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A builder for a [`Proxy`].
+#[derive(Debug)]
+pub struct ProxyBuilder {
+    inner: ProxyInner,
+}
+
+impl ProxyBuilder {
+    /// Creates a builder for a proxy forwarding requests to the upstream by default.
+    pub fn new<T: Transport + Clone>(upstream: T) -> Self {
+        Self {
+            inner: ProxyInner {
+                upstream: BoxTransport::new(upstream),
+                routes: Vec::new(),
+                cache: None,
+                request_hooks: Vec::new(),
+                response_hooks: Vec::new(),
+            },
+        }
+    }
+
+    /// Forwards the requests whose method matches the pattern to the upstream.
+    ///
+    /// The pattern is either a method name, or a prefix followed by `*`, e.g. `debug_*`. Routes
+    /// are matched in the order they are added.
+    pub fn route<T: Transport + Clone>(mut self, pattern: impl Into<String>, upstream: T) -> Self {
+        self.inner.routes.push((pattern.into(), BoxTransport::new(upstream)));
+        self
+    }
+
+    /// Caches responses in the cache.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.inner.cache = Some(cache);
+        self
+    }
+
+    /// Adds a hook called on each request before it is forwarded.
+    pub fn request_hook(mut self, hook: impl RequestHook) -> Self {
+        self.inner.request_hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Adds a hook called on each response before it is returned.
+    pub fn response_hook(mut self, hook: impl ResponseHook) -> Self {
+        self.inner.response_hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Builds the proxy.
+    pub fn build(self) -> Proxy {
+        Proxy { inner: Arc::new(self.inner) }
+    }
+}
+
+/// A request received by the proxy.
+#[derive(Deserialize)]
+struct IncomingRequest {
+    #[serde(default)]
+    id: Option<Id>,
+    method: String,
+    #[serde(default)]
+    params: Option<Box<RawValue>>,
+}
+
+impl IncomingRequest {
+    fn serialize(self) -> serde_json::Result<SerializedRequest> {
+        let id = self.id.unwrap_or(Id::None);
+        match self.params {
+            Some(params) => Request::new(self.method, id, params).serialize(),
+            None => Request::new(self.method, id, ()).serialize(),
+        }
+    }
+}
+
+/// A single request or a batch of requests received by the proxy.
+enum IncomingPacket {
+    Single(IncomingRequest),
+    Batch(Vec<Box<RawValue>>),
+}
+
+impl IncomingPacket {
+    fn parse(body: &str) -> serde_json::Result<Self> {
+        if body.trim_start().starts_with('[') {
+            serde_json::from_str(body).map(Self::Batch)
+        } else {
+            serde_json::from_str(body).map(Self::Single)
+        }
+    }
+}
+
+const fn error_response(id: Id, error: ErrorPayload) -> Response {
+    Response { id, payload: ResponsePayload::Failure(error) }
+}
+
+fn matches_route(pattern: &str, method: &str) -> bool {
+    pattern.strip_suffix('*').map_or_else(|| pattern == method, |prefix| method.starts_with(prefix))
+}
+
+impl Proxy {
+    /// Creates a builder for a proxy forwarding requests to the upstream by default.
+    pub fn builder<T: Transport + Clone>(upstream: T) -> ProxyBuilder {
+        ProxyBuilder::new(upstream)
+    }
+
+    /// Returns the response cache of the proxy, if any.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.inner.cache.as_ref()
+    }
+
+    /// Returns the upstream the method is forwarded to.
+    fn upstream(&self, method: &str) -> &BoxTransport {
+        self.inner
+            .routes
+            .iter()
+            .find(|(pattern, _)| matches_route(pattern, method))
+            .map_or(&self.inner.upstream, |(_, upstream)| upstream)
+    }
+
+    /// Handles a single request.
+    pub async fn handle_request(&self, mut request: SerializedRequest) -> Response {
+        for hook in &self.inner.request_hooks {
+            match hook.on_request(request) {
+                RequestAction::Forward(forwarded) => request = forwarded,
+                RequestAction::Respond(response) => return response,
+            }
+        }
+
+        let method = request.method().to_string();
+        let id = request.id().clone();
+        let cache = self.inner.cache.as_ref().filter(|cache| cache.is_cached(&method));
+        let params_hash = cache.map(|_| request.params_hash()).unwrap_or_default();
+
+        let cached = cache.and_then(|cache| cache.get(&method, params_hash));
+        let response = if let Some(result) = cached {
+            trace!(%method, "serving cached response");
+            Response { id, payload: ResponsePayload::Success(result) }
+        } else {
+            let response = self.forward(request).await;
+            if let (Some(cache), ResponsePayload::Success(result)) = (cache, &response.payload) {
+                cache.insert(&method, params_hash, result);
+            }
+            response
+        };
+
+        self.inner
+            .response_hooks
+            .iter()
+            .fold(response, |response, hook| hook.on_response(&method, response))
+    }
+
+    /// Forwards the request to its upstream.
+    async fn forward(&self, request: SerializedRequest) -> Response {
+        let id = request.id().clone();
+        let upstream = self.upstream(request.method()).clone();
+        match upstream.oneshot(RequestPacket::Single(request)).await {
+            Ok(ResponsePacket::Single(response)) => response,
+            Ok(ResponsePacket::Batch(responses)) => {
+                responses.into_iter().next().unwrap_or_else(|| {
+                    error_response(
+                        id,
+                        ErrorPayload::internal_error_message("empty response".into()),
+                    )
+                })
+            }
+            Err(err) => {
+                debug!(%err, "upstream request failed");
+                error_response(id, ErrorPayload::internal_error_message(err.to_string().into()))
+            }
+        }
+    }
+
+    /// Handles a packet of requests.
+    pub async fn handle_packet(&self, packet: RequestPacket) -> ResponsePacket {
+        match packet {
+            RequestPacket::Single(request) => {
+                ResponsePacket::Single(self.handle_request(request).await)
+            }
+            RequestPacket::Batch(requests) => ResponsePacket::Batch(
+                join_all(requests.into_iter().map(|request| self.handle_request(request))).await,
+            ),
+        }
+    }
+
+    /// Handles the raw body of a JSON-RPC message, returning the raw body of the reply.
+    ///
+    /// Returns `None` if the message only contains notifications, which get no reply.
+    pub async fn handle_raw(&self, body: &str) -> Option<String> {
+        let reply = match IncomingPacket::parse(body) {
+            Ok(IncomingPacket::Single(request)) => {
+                let response = self.handle_incoming(request).await?;
+                serde_json::to_string(&response)
+            }
+            Ok(IncomingPacket::Batch(requests)) if requests.is_empty() => {
+                serde_json::to_string(&error_response(Id::None, ErrorPayload::invalid_request()))
+            }
+            Ok(IncomingPacket::Batch(requests)) => {
+                let responses = join_all(requests.iter().map(|request| async move {
+                    match serde_json::from_str::<IncomingRequest>(request.get()) {
+                        Ok(request) => self.handle_incoming(request).await,
+                        Err(_) => Some(error_response(Id::None, ErrorPayload::invalid_request())),
+                    }
+                }))
+                .await;
+                let responses = responses.into_iter().flatten().collect::<Vec<_>>();
+                if responses.is_empty() {
+                    return None;
+                }
+                serde_json::to_string(&responses)
+            }
+            Err(_) => serde_json::to_string(&error_response(Id::None, ErrorPayload::parse_error())),
+        };
+        Some(reply.expect("responses serialize to JSON"))
+    }
+
+    async fn handle_incoming(&self, request: IncomingRequest) -> Option<Response> {
+        let is_notification = request.id.is_none();
+        let response = match request.serialize() {
+            Ok(request) => self.handle_request(request).await,
+            Err(_) => error_response(Id::None, ErrorPayload::invalid_request()),
+        };
+        (!is_notification).then_some(response)
+    }
+}
+
+impl Service<RequestPacket> for Proxy {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, packet: RequestPacket) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move { Ok(this.handle_packet(packet).await) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// An upstream answering every request with its name, counting the requests it receives.
+    fn upstream(name: &'static str, calls: Arc<AtomicUsize>) -> BoxTransport {
+        BoxTransport::new(tower::service_fn(
+            move |packet: RequestPacket| -> TransportFut<'static> {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let RequestPacket::Single(request) = packet else { unreachable!() };
+                let result = RawValue::from_string(format!("\"{name}\"")).unwrap();
+                let response = Response {
+                    id: request.id().clone(),
+                    payload: ResponsePayload::Success(result),
+                };
+                Box::pin(async move { Ok(ResponsePacket::Single(response)) })
+            },
+        ))
+    }
+
+    #[tokio::test]
+    async fn routes_caches_and_rewrites() {
+        let default_calls = Arc::new(AtomicUsize::default());
+        let debug_calls = Arc::new(AtomicUsize::default());
+        let proxy = Proxy::builder(upstream("default", default_calls.clone()))
+            .route("debug_*", upstream("debug", debug_calls.clone()))
+            .cache(ResponseCache::new(16))
+            .request_hook(|request: SerializedRequest| {
+                if request.method() == "admin_peers" {
+                    let id = request.id().clone();
+                    RequestAction::Respond(error_response(id, ErrorPayload::method_not_found()))
+                } else {
+                    RequestAction::Forward(request)
+                }
+            })
+            .response_hook(|method: &str, mut response: Response| {
+                if method == "web3_clientVersion" {
+                    let result = RawValue::from_string("\"proxy\"".into()).unwrap();
+                    response.payload = ResponsePayload::Success(result);
+                }
+                response
+            })
+            .build();
+
+        let reply = proxy
+            .handle_raw(r#"{"jsonrpc":"2.0","id":1,"method":"debug_traceTransaction","params":[]}"#)
+            .await
+            .unwrap();
+        assert_eq!(reply, r#"{"jsonrpc":"2.0","id":1,"result":"debug"}"#);
+        assert_eq!(debug_calls.load(Ordering::SeqCst), 1);
+
+        for _ in 0..2 {
+            let reply =
+                proxy.handle_raw(r#"{"jsonrpc":"2.0","id":"a","method":"eth_chainId"}"#).await;
+            assert_eq!(reply.unwrap(), r#"{"jsonrpc":"2.0","id":"a","result":"default"}"#);
+        }
+        assert_eq!(default_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(proxy.cache().unwrap().len(), 1);
+
+        let reply = proxy
+            .handle_raw(
+                r#"[{"jsonrpc":"2.0","id":1,"method":"admin_peers"},{"jsonrpc":"2.0","id":2,"method":"web3_clientVersion"},{"jsonrpc":"2.0","method":"eth_blockNumber"}]"#,
+            )
+            .await
+            .unwrap();
+        let responses: Vec<Response> = serde_json::from_str(&reply).unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].payload.as_error().unwrap().code, -32601);
+        assert_eq!(responses[1].payload.as_success().unwrap().get(), "\"proxy\"");
+        // the notification is forwarded, but gets no response
+        assert_eq!(default_calls.load(Ordering::SeqCst), 3);
+
+        assert!(proxy
+            .handle_raw(r#"{"jsonrpc":"2.0","method":"eth_blockNumber"}"#)
+            .await
+            .is_none());
+        let reply = proxy.handle_raw("{").await.unwrap();
+        assert!(reply.contains("-32700"));
+        let reply = proxy.handle_raw("[]").await.unwrap();
+        assert!(reply.contains("-32600"));
+    }
+}
@@ -0,0 +1,39 @@
+use crate::Proxy;
+use futures::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Serves the proxy over WebSocket on the listener, handling each connection in a new task.
+///
+/// JSON-RPC messages are accepted as text messages, and the messages of a connection are handled
+/// in order. Returns only if accepting a connection fails.
+pub async fn serve_ws(listener: TcpListener, proxy: Proxy) -> std::io::Result<()> {
+    loop {
+        let (stream, remote) = listener.accept().await?;
+        let proxy = proxy.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle(&proxy, stream).await {
+                debug!(%err, %remote, "ws connection failed");
+            }
+        });
+    }
+}
+
+async fn handle(
+    proxy: &Proxy,
+    stream: TcpStream,
+) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+    let mut socket = tokio_tungstenite::accept_async(stream).await?;
+    while let Some(message) = socket.next().await {
+        let body = match message? {
+            Message::Text(text) => text,
+            Message::Binary(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Message::Close(_) => break,
+            Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => continue,
+        };
+        if let Some(reply) = proxy.handle_raw(&body).await {
+            socket.send(Message::Text(reply)).await?;
+        }
+    }
+    Ok(())
+}
@@ -18,6 +18,9 @@ pub mod events;
 /// Types and functions related to the beacon block header.
 pub mod header;
 
+/// Types and functions related to the light client.
+pub mod light_client;
+
 /// Types and functions related to the beacon block payload.
 pub mod payload;
 
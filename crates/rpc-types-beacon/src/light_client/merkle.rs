@@ -0,0 +1,260 @@
+//! SSZ Merkleization of the containers needed by the light client.
+//!
+//! See also <https://github.com/ethereum/consensus-specs/blob/dev/ssz/simple-serialize.md#merkleization>
+
+use super::SyncCommittee;
+use crate::{header::BeaconBlockHeader, payload::ExecutionPayloadHeader, BlsPublicKey};
+use alloy_primitives::{FixedBytes, B256, U256};
+use sha2::{Digest, Sha256};
+
+/// Returns the SHA-256 hash of the concatenation of two nodes.
+pub(crate) fn hash_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// Merkleizes the given chunks into a tree of `2^depth` leaves, padding with zero chunks.
+pub(crate) fn merkleize(chunks: &[B256], depth: usize) -> B256 {
+    debug_assert!(chunks.len() <= 1 << depth);
+
+    let mut layer = chunks.to_vec();
+    let mut zero = B256::ZERO;
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer.chunks_exact(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+        zero = hash_pair(&zero, &zero);
+    }
+    layer.first().copied().unwrap_or(zero)
+}
+
+/// Returns the chunk of a `uint64`.
+fn u64_chunk(value: u64) -> B256 {
+    let mut chunk = B256::ZERO;
+    chunk[..8].copy_from_slice(&value.to_le_bytes());
+    chunk
+}
+
+/// Returns the chunk of a `uint256`.
+fn u256_chunk(value: U256) -> B256 {
+    B256::from(value.to_le_bytes::<32>())
+}
+
+/// Returns the chunk of a byte vector of at most 32 bytes.
+fn bytes_chunk(bytes: &[u8]) -> B256 {
+    let mut chunk = B256::ZERO;
+    chunk[..bytes.len()].copy_from_slice(bytes);
+    chunk
+}
+
+/// Returns the root of a byte vector longer than 32 bytes.
+fn byte_vector_root(bytes: &[u8], depth: usize) -> B256 {
+    let chunks: Vec<_> = bytes.chunks(32).map(bytes_chunk).collect();
+    merkleize(&chunks, depth)
+}
+
+/// Returns the root of a BLS public key.
+fn pubkey_root(pubkey: &BlsPublicKey) -> B256 {
+    byte_vector_root(pubkey.as_slice(), 1)
+}
+
+/// Returns the root of a `ByteList[32]`, such as the extra data of an execution payload.
+fn extra_data_root(extra_data: &[u8]) -> B256 {
+    hash_pair(&bytes_chunk(extra_data), &u64_chunk(extra_data.len() as u64))
+}
+
+/// Returns `true` if `leaf` is the node at `index` of the tree with the given `depth` and `root`,
+/// according to `branch`.
+///
+/// The index is the position of the leaf in its layer, i.e. the generalized index without its
+/// leading bit.
+///
+/// See also <https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#is_valid_merkle_branch>
+pub fn is_valid_merkle_branch(
+    leaf: B256,
+    branch: &[B256],
+    depth: usize,
+    index: u64,
+    root: B256,
+) -> bool {
+    if branch.len() != depth {
+        return false;
+    }
+    let computed = branch.iter().enumerate().fold(leaf, |node, (i, sibling)| {
+        if (index >> i) & 1 == 1 {
+            hash_pair(sibling, &node)
+        } else {
+            hash_pair(&node, sibling)
+        }
+    });
+    computed == root
+}
+
+/// Returns `true` if `leaf` is the node at the generalized index `gindex` of the tree with the
+/// given `root`, according to `branch`.
+pub(crate) fn is_valid_normalized_merkle_branch(
+    leaf: B256,
+    branch: &[B256],
+    gindex: u64,
+    root: B256,
+) -> bool {
+    let depth = gindex.ilog2() as usize;
+    is_valid_merkle_branch(leaf, branch, depth, gindex - (1 << depth), root)
+}
+
+impl BeaconBlockHeader {
+    /// Returns the SSZ hash tree root of the header, which is the root of the block.
+    pub fn hash_tree_root(&self) -> B256 {
+        merkleize(
+            &[
+                u64_chunk(self.slot),
+                u64_chunk(self.proposer_index),
+                self.parent_root,
+                self.state_root,
+                self.body_root,
+            ],
+            3,
+        )
+    }
+}
+
+impl ExecutionPayloadHeader {
+    /// Returns the SSZ hash tree root of the header.
+    ///
+    /// The fork of the header is determined by the presence of the Capella and Deneb fields.
+    pub fn hash_tree_root(&self) -> B256 {
+        let mut chunks = vec![
+            self.parent_hash,
+            bytes_chunk(self.fee_recipient.as_slice()),
+            self.state_root,
+            self.receipts_root,
+            byte_vector_root(self.logs_bloom.as_slice(), 3),
+            self.prev_randao,
+            u64_chunk(self.block_number),
+            u64_chunk(self.gas_limit),
+            u64_chunk(self.gas_used),
+            u64_chunk(self.timestamp),
+            extra_data_root(&self.extra_data),
+            u256_chunk(self.base_fee_per_gas),
+            self.block_hash,
+            self.transactions_root,
+        ];
+        if let Some(withdrawals_root) = self.withdrawals_root {
+            chunks.push(withdrawals_root);
+        }
+        if let (Some(blob_gas_used), Some(excess_blob_gas)) =
+            (self.blob_gas_used, self.excess_blob_gas)
+        {
+            chunks.push(u64_chunk(blob_gas_used));
+            chunks.push(u64_chunk(excess_blob_gas));
+        }
+        let depth = chunks.len().next_power_of_two().ilog2() as usize;
+        merkleize(&chunks, depth)
+    }
+}
+
+impl SyncCommittee {
+    /// Returns the SSZ hash tree root of the sync committee.
+    pub fn hash_tree_root(&self) -> B256 {
+        let pubkeys: Vec<_> = self.pubkeys.iter().map(pubkey_root).collect();
+        let depth = super::SYNC_COMMITTEE_SIZE.ilog2() as usize;
+        hash_pair(&merkleize(&pubkeys, depth), &pubkey_root(&self.aggregate_pubkey))
+    }
+}
+
+/// Returns the signing domain for the given domain type, fork version and genesis validators
+/// root.
+///
+/// See also <https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#compute_domain>
+pub(crate) fn compute_domain(
+    domain_type: FixedBytes<4>,
+    fork_version: FixedBytes<4>,
+    genesis_validators_root: B256,
+) -> B256 {
+    let fork_data_root = hash_pair(&bytes_chunk(fork_version.as_slice()), &genesis_validators_root);
+    let mut domain = B256::ZERO;
+    domain[..4].copy_from_slice(domain_type.as_slice());
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    domain
+}
+
+/// Returns the signing root of an object with the given hash tree root in the given domain.
+pub(crate) fn compute_signing_root(object_root: B256, domain: B256) -> B256 {
+    hash_pair(&object_root, &domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, hex};
+
+    #[test]
+    fn merkleize_pads_with_zero_hashes() {
+        let zero1 = hash_pair(&B256::ZERO, &B256::ZERO);
+        let zero2 = hash_pair(&zero1, &zero1);
+        assert_eq!(merkleize(&[], 2), zero2);
+        assert_eq!(merkleize(&[B256::ZERO], 0), B256::ZERO);
+
+        let a = B256::repeat_byte(1);
+        assert_eq!(merkleize(&[a], 2), hash_pair(&hash_pair(&a, &B256::ZERO), &zero1));
+    }
+
+    #[test]
+    fn zero_hash() {
+        // `hash(0^64)`, the root of a tree with two zero leaves
+        assert_eq!(
+            hash_pair(&B256::ZERO, &B256::ZERO),
+            b256!("f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b")
+        );
+    }
+
+    #[test]
+    fn merkle_branches() {
+        let leaves: Vec<_> = (0..8u8).map(B256::repeat_byte).collect();
+        let root = merkleize(&leaves, 3);
+
+        let n01 = hash_pair(&leaves[0], &leaves[1]);
+        let n23 = hash_pair(&leaves[2], &leaves[3]);
+        let n4567 =
+            hash_pair(&hash_pair(&leaves[4], &leaves[5]), &hash_pair(&leaves[6], &leaves[7]));
+
+        // leaf 2 has the generalized index 8 + 2
+        let branch = [leaves[3], n01, n4567];
+        assert!(is_valid_merkle_branch(leaves[2], &branch, 3, 2, root));
+        assert!(is_valid_normalized_merkle_branch(leaves[2], &branch, 10, root));
+        assert!(!is_valid_merkle_branch(leaves[2], &branch, 3, 3, root));
+        assert!(!is_valid_merkle_branch(leaves[2], &branch[..2], 3, 2, root));
+
+        // the inner node `n23` has the generalized index 4 + 1
+        assert!(is_valid_normalized_merkle_branch(n23, &[n01, n4567], 5, root));
+    }
+
+    #[test]
+    fn extra_data_is_mixed_in_with_length() {
+        let extra_data = hex!("deadbeef");
+        let mut chunk = B256::ZERO;
+        chunk[..4].copy_from_slice(&extra_data);
+        assert_eq!(extra_data_root(&extra_data), hash_pair(&chunk, &u64_chunk(4)));
+    }
+
+    #[test]
+    fn execution_header_fork_depth() {
+        let mut header = ExecutionPayloadHeader::default();
+        let bellatrix = header.hash_tree_root();
+
+        header.withdrawals_root = Some(B256::ZERO);
+        // a zero withdrawals root merkleizes like the padding of a Bellatrix header
+        assert_eq!(header.hash_tree_root(), bellatrix);
+
+        header.withdrawals_root = Some(B256::repeat_byte(1));
+        let capella = header.hash_tree_root();
+        assert_ne!(capella, bellatrix);
+
+        header.blob_gas_used = Some(1);
+        header.excess_blob_gas = Some(2);
+        assert_ne!(header.hash_tree_root(), capella);
+    }
+}
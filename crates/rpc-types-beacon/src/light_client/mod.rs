@@ -0,0 +1,154 @@
+//! Light client types of the beacon API.
+//!
+//! With the `light-client` feature enabled, this module also provides a [`LightClientStore`] that
+//! verifies these types according to the [light client sync protocol], which allows tracking
+//! the finalized and optimistic execution headers of the chain with only a trusted block root.
+//!
+//! Only post-Capella headers, which carry an execution payload header, are supported.
+//!
+//! See also <https://ethereum.github.io/beacon-APIs/#/Beacon/getLightClientBootstrap>
+//!
+//! [light client sync protocol]: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md
+
+use crate::{
+    events::light_client_optimistic::SyncAggregate, header::BeaconBlockHeader,
+    payload::ExecutionPayloadHeader, BlsPublicKey,
+};
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+#[cfg(feature = "light-client")]
+mod merkle;
+#[cfg(feature = "light-client")]
+pub use merkle::is_valid_merkle_branch;
+
+#[cfg(feature = "light-client")]
+mod store;
+#[cfg(feature = "light-client")]
+pub use store::{Fork, LightClientConfig, LightClientError, LightClientStore};
+
+/// The number of validators in a sync committee.
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+
+/// The number of slots in an epoch.
+pub const SLOTS_PER_EPOCH: u64 = 32;
+
+/// The number of epochs in a sync committee period.
+pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+
+/// The response of the light client endpoints: `{"version": .., "data": ..}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientResponse<T> {
+    /// The fork the data belongs to.
+    pub version: String,
+    /// The light client data.
+    pub data: T,
+}
+
+/// A beacon block header together with the execution payload header of the block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientHeader {
+    /// The [`BeaconBlockHeader`] object from the CL spec.
+    pub beacon: BeaconBlockHeader,
+    /// The header of the execution payload of the block.
+    pub execution: ExecutionPayloadHeader,
+    /// The Merkle branch of the execution payload header in the block body.
+    pub execution_branch: Vec<B256>,
+}
+
+/// The public keys of a sync committee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncCommittee {
+    /// The public keys of the members of the sync committee.
+    pub pubkeys: Vec<BlsPublicKey>,
+    /// The aggregate of all public keys of the sync committee.
+    pub aggregate_pubkey: BlsPublicKey,
+}
+
+/// The response of `/eth/v1/beacon/light_client/bootstrap/{block_root}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientBootstrap {
+    /// The header of the trusted block.
+    pub header: LightClientHeader,
+    /// The sync committee of the period of the trusted block.
+    pub current_sync_committee: SyncCommittee,
+    /// The Merkle branch of the current sync committee in the state of the trusted block.
+    pub current_sync_committee_branch: Vec<B256>,
+}
+
+/// An update of the light client, as returned by `/eth/v1/beacon/light_client/updates`.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientUpdate {
+    /// The header attested by the sync committee.
+    pub attested_header: LightClientHeader,
+    /// The sync committee of the next period.
+    pub next_sync_committee: SyncCommittee,
+    /// The Merkle branch of the next sync committee in the attested state.
+    pub next_sync_committee_branch: Vec<B256>,
+    /// The finalized header of the attested state.
+    pub finalized_header: LightClientHeader,
+    /// The Merkle branch of the finalized checkpoint root in the attested state.
+    pub finality_branch: Vec<B256>,
+    /// The sync committee bits and signature.
+    pub sync_aggregate: SyncAggregate,
+    /// The slot at which the sync aggregate was included.
+    #[serde_as(as = "DisplayFromStr")]
+    pub signature_slot: u64,
+}
+
+/// The response of `/eth/v1/beacon/light_client/finality_update`.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientFinalityUpdate {
+    /// The header attested by the sync committee.
+    pub attested_header: LightClientHeader,
+    /// The finalized header of the attested state.
+    pub finalized_header: LightClientHeader,
+    /// The Merkle branch of the finalized checkpoint root in the attested state.
+    pub finality_branch: Vec<B256>,
+    /// The sync committee bits and signature.
+    pub sync_aggregate: SyncAggregate,
+    /// The slot at which the sync aggregate was included.
+    #[serde_as(as = "DisplayFromStr")]
+    pub signature_slot: u64,
+}
+
+/// The response of `/eth/v1/beacon/light_client/optimistic_update`.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientOptimisticUpdate {
+    /// The header attested by the sync committee.
+    pub attested_header: LightClientHeader,
+    /// The sync committee bits and signature.
+    pub sync_aggregate: SyncAggregate,
+    /// The slot at which the sync aggregate was included.
+    #[serde_as(as = "DisplayFromStr")]
+    pub signature_slot: u64,
+}
+
+/// Returns the epoch of the given slot.
+pub const fn compute_epoch_at_slot(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH
+}
+
+/// Returns the sync committee period of the given slot.
+pub const fn compute_sync_committee_period_at_slot(slot: u64) -> u64 {
+    compute_epoch_at_slot(slot) / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_light_client_bootstrap() {
+        let s = r#"{"version":"deneb","data":{"header":{"beacon":{"slot":"8192","proposer_index":"7","parent_root":"0x1111111111111111111111111111111111111111111111111111111111111111","state_root":"0x1111111111111111111111111111111111111111111111111111111111111111","body_root":"0x1111111111111111111111111111111111111111111111111111111111111111"},"execution":{"parent_hash":"0x1111111111111111111111111111111111111111111111111111111111111111","fee_recipient":"0x0000000000000000000000000000000000000000","state_root":"0x1111111111111111111111111111111111111111111111111111111111111111","receipts_root":"0x1111111111111111111111111111111111111111111111111111111111111111","logs_bloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","prev_randao":"0x1111111111111111111111111111111111111111111111111111111111111111","block_number":"19000000","gas_limit":"30000000","gas_used":"21000","timestamp":"1700000000","extra_data":"0x","base_fee_per_gas":"7","block_hash":"0x1111111111111111111111111111111111111111111111111111111111111111","transactions_root":"0x1111111111111111111111111111111111111111111111111111111111111111","withdrawals_root":"0x1111111111111111111111111111111111111111111111111111111111111111","blob_gas_used":"0","excess_blob_gas":"0"},"execution_branch":["0x1111111111111111111111111111111111111111111111111111111111111111","0x1111111111111111111111111111111111111111111111111111111111111111","0x1111111111111111111111111111111111111111111111111111111111111111","0x1111111111111111111111111111111111111111111111111111111111111111"]},"current_sync_committee":{"pubkeys":["0x939393939393939393939393939393939393939393939393939393939393939393939393939393939393939393939393","0x939393939393939393939393939393939393939393939393939393939393939393939393939393939393939393939393"],"aggregate_pubkey":"0x939393939393939393939393939393939393939393939393939393939393939393939393939393939393939393939393"},"current_sync_committee_branch":["0x1111111111111111111111111111111111111111111111111111111111111111","0x1111111111111111111111111111111111111111111111111111111111111111","0x1111111111111111111111111111111111111111111111111111111111111111","0x1111111111111111111111111111111111111111111111111111111111111111","0x1111111111111111111111111111111111111111111111111111111111111111"]}}"#;
+        let resp: LightClientResponse<LightClientBootstrap> = serde_json::from_str(s).unwrap();
+        assert_eq!(resp.data.header.execution.block_number, 19000000);
+        assert_eq!(resp.data.header.execution.blob_gas_used, Some(0));
+        let json: serde_json::Value = serde_json::from_str(s).unwrap();
+        assert_eq!(json, serde_json::to_value(resp).unwrap());
+    }
+}
@@ -0,0 +1,770 @@
+//! Verification of light client updates.
+
+use super::{
+    compute_epoch_at_slot, compute_sync_committee_period_at_slot,
+    merkle::{compute_domain, compute_signing_root, is_valid_normalized_merkle_branch},
+    LightClientBootstrap, LightClientFinalityUpdate, LightClientHeader,
+    LightClientOptimisticUpdate, LightClientUpdate, SyncCommittee, SYNC_COMMITTEE_SIZE,
+};
+use crate::{constants::BLS_DST_SIG, events::light_client_optimistic::SyncAggregate};
+use alloy_primitives::{b256, fixed_bytes, FixedBytes, B256};
+use blst::{
+    min_pk::{PublicKey, Signature},
+    BLST_ERROR,
+};
+
+/// The generalized index of the execution payload in the beacon block body.
+const EXECUTION_PAYLOAD_GINDEX: u64 = 25;
+
+/// The generalized index of the finalized checkpoint root in the beacon state.
+const FINALIZED_ROOT_GINDEX: u64 = 105;
+/// The generalized index of the current sync committee in the beacon state.
+const CURRENT_SYNC_COMMITTEE_GINDEX: u64 = 54;
+/// The generalized index of the next sync committee in the beacon state.
+const NEXT_SYNC_COMMITTEE_GINDEX: u64 = 55;
+
+/// The generalized index of the finalized checkpoint root in the beacon state, since Electra.
+const FINALIZED_ROOT_GINDEX_ELECTRA: u64 = 169;
+/// The generalized index of the current sync committee in the beacon state, since Electra.
+const CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA: u64 = 86;
+/// The generalized index of the next sync committee in the beacon state, since Electra.
+const NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA: u64 = 87;
+
+/// The domain type of sync committee signatures.
+const DOMAIN_SYNC_COMMITTEE: FixedBytes<4> = fixed_bytes!("07000000");
+
+/// A fork of the beacon chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fork {
+    /// The epoch at which the fork activates.
+    pub epoch: u64,
+    /// The fork version.
+    pub version: FixedBytes<4>,
+}
+
+/// The chain parameters needed to verify light client updates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightClientConfig {
+    /// The genesis validators root of the chain.
+    pub genesis_validators_root: B256,
+    /// The genesis fork version of the chain.
+    pub genesis_fork_version: FixedBytes<4>,
+    /// The forks of the chain, in activation order.
+    pub forks: Vec<Fork>,
+    /// The epoch of the Electra fork, which changed the layout of the beacon state.
+    pub electra_fork_epoch: Option<u64>,
+}
+
+impl LightClientConfig {
+    /// Returns the configuration of Ethereum mainnet.
+    pub fn mainnet() -> Self {
+        Self {
+            genesis_validators_root: b256!(
+                "4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"
+            ),
+            genesis_fork_version: fixed_bytes!("00000000"),
+            forks: vec![
+                Fork { epoch: 74240, version: fixed_bytes!("01000000") },
+                Fork { epoch: 144896, version: fixed_bytes!("02000000") },
+                Fork { epoch: 194048, version: fixed_bytes!("03000000") },
+                Fork { epoch: 269568, version: fixed_bytes!("04000000") },
+                Fork { epoch: 364032, version: fixed_bytes!("05000000") },
+            ],
+            electra_fork_epoch: Some(364032),
+        }
+    }
+
+    /// Returns the fork version at the given epoch.
+    pub fn fork_version(&self, epoch: u64) -> FixedBytes<4> {
+        self.forks
+            .iter()
+            .rev()
+            .find(|fork| fork.epoch <= epoch)
+            .map_or(self.genesis_fork_version, |fork| fork.version)
+    }
+
+    fn is_electra(&self, slot: u64) -> bool {
+        self.electra_fork_epoch.is_some_and(|epoch| compute_epoch_at_slot(slot) >= epoch)
+    }
+
+    fn finalized_root_gindex(&self, slot: u64) -> u64 {
+        if self.is_electra(slot) {
+            FINALIZED_ROOT_GINDEX_ELECTRA
+        } else {
+            FINALIZED_ROOT_GINDEX
+        }
+    }
+
+    fn current_sync_committee_gindex(&self, slot: u64) -> u64 {
+        if self.is_electra(slot) {
+            CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA
+        } else {
+            CURRENT_SYNC_COMMITTEE_GINDEX
+        }
+    }
+
+    fn next_sync_committee_gindex(&self, slot: u64) -> u64 {
+        if self.is_electra(slot) {
+            NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA
+        } else {
+            NEXT_SYNC_COMMITTEE_GINDEX
+        }
+    }
+}
+
+/// Errors that can occur when verifying light client data.
+#[derive(Debug, thiserror::Error)]
+pub enum LightClientError {
+    /// The bootstrap header does not match the trusted block root.
+    #[error("bootstrap header root {got} does not match the trusted block root {expected}")]
+    UntrustedBootstrap {
+        /// The trusted block root.
+        expected: B256,
+        /// The root of the bootstrap header.
+        got: B256,
+    },
+    /// The execution payload header is not part of the beacon block body.
+    #[error("invalid execution payload branch")]
+    InvalidExecutionBranch,
+    /// The sync committee is not part of the attested state.
+    #[error("invalid sync committee branch")]
+    InvalidSyncCommitteeBranch,
+    /// The finalized header is not part of the attested state.
+    #[error("invalid finality branch")]
+    InvalidFinalityBranch,
+    /// The sync committee has the wrong number of members.
+    #[error("sync committee has {0} members, expected {SYNC_COMMITTEE_SIZE}")]
+    InvalidSyncCommitteeSize(usize),
+    /// The sync aggregate is malformed.
+    #[error("invalid sync aggregate")]
+    InvalidSyncAggregate,
+    /// Not enough members of the sync committee signed the update.
+    #[error("insufficient sync committee participation: {participants} of {SYNC_COMMITTEE_SIZE}")]
+    InsufficientParticipation {
+        /// The number of members that signed the update.
+        participants: usize,
+    },
+    /// The slots of the update are not ordered as
+    /// `signature_slot > attested_slot >= finalized_slot`.
+    #[error("invalid update slots")]
+    InvalidSlots,
+    /// The update was signed by a sync committee unknown to the store.
+    #[error("update signed in sync committee period {got}, store is at period {store}")]
+    UnknownSyncCommittee {
+        /// The sync committee period of the store.
+        store: u64,
+        /// The sync committee period of the signature.
+        got: u64,
+    },
+    /// The update does not advance the store.
+    #[error("update is not newer than the store")]
+    IrrelevantUpdate,
+    /// The next sync committee conflicts with the one already known to the store.
+    #[error("next sync committee conflicts with the known next sync committee")]
+    ConflictingSyncCommittee,
+    /// A BLS public key or signature could not be decoded, or the signature is invalid.
+    #[error("invalid sync committee signature: {0:?}")]
+    InvalidSignature(BLST_ERROR),
+}
+
+/// A light client store.
+///
+/// The store is initialized from a [`LightClientBootstrap`] of a trusted block root, and then
+/// advanced with updates signed by a supermajority of the sync committee.
+///
+/// See also <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#lightclientstore>
+#[derive(Debug, Clone)]
+pub struct LightClientStore {
+    config: LightClientConfig,
+    finalized_header: LightClientHeader,
+    optimistic_header: LightClientHeader,
+    current_sync_committee: SyncCommittee,
+    next_sync_committee: Option<SyncCommittee>,
+}
+
+/// The parts of the different update types that are verified by the store.
+struct UpdateRef<'a> {
+    attested_header: &'a LightClientHeader,
+    next_sync_committee: Option<(&'a SyncCommittee, &'a [B256])>,
+    finality: Option<(&'a LightClientHeader, &'a [B256])>,
+    sync_aggregate: &'a SyncAggregate,
+    signature_slot: u64,
+}
+
+impl LightClientStore {
+    /// Initializes the store from a bootstrap of the given trusted block root.
+    ///
+    /// The trusted block root should be a recent finalized block root obtained out of band, for
+    /// example a [weak subjectivity checkpoint].
+    ///
+    /// [weak subjectivity checkpoint]: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/weak-subjectivity.md
+    pub fn bootstrap(
+        config: LightClientConfig,
+        trusted_block_root: B256,
+        bootstrap: LightClientBootstrap,
+    ) -> Result<Self, LightClientError> {
+        let header = bootstrap.header;
+        validate_header(&header)?;
+
+        let root = header.beacon.hash_tree_root();
+        if root != trusted_block_root {
+            return Err(LightClientError::UntrustedBootstrap {
+                expected: trusted_block_root,
+                got: root,
+            });
+        }
+
+        validate_sync_committee(&bootstrap.current_sync_committee)?;
+        if !is_valid_normalized_merkle_branch(
+            bootstrap.current_sync_committee.hash_tree_root(),
+            &bootstrap.current_sync_committee_branch,
+            config.current_sync_committee_gindex(header.beacon.slot),
+            header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidSyncCommitteeBranch);
+        }
+
+        Ok(Self {
+            config,
+            optimistic_header: header.clone(),
+            finalized_header: header,
+            current_sync_committee: bootstrap.current_sync_committee,
+            next_sync_committee: None,
+        })
+    }
+
+    /// Returns the configuration of the store.
+    pub const fn config(&self) -> &LightClientConfig {
+        &self.config
+    }
+
+    /// Returns the latest verified finalized header.
+    pub const fn finalized_header(&self) -> &LightClientHeader {
+        &self.finalized_header
+    }
+
+    /// Returns the latest verified attested header, which may not be finalized yet.
+    pub const fn optimistic_header(&self) -> &LightClientHeader {
+        &self.optimistic_header
+    }
+
+    /// Returns the sync committee of the current period.
+    pub const fn current_sync_committee(&self) -> &SyncCommittee {
+        &self.current_sync_committee
+    }
+
+    /// Returns the sync committee of the next period, if known.
+    pub const fn next_sync_committee(&self) -> Option<&SyncCommittee> {
+        self.next_sync_committee.as_ref()
+    }
+
+    /// Verifies and applies a full update, which may also rotate the sync committee.
+    pub fn process_update(&mut self, update: &LightClientUpdate) -> Result<(), LightClientError> {
+        self.apply(UpdateRef {
+            attested_header: &update.attested_header,
+            next_sync_committee: Some((
+                &update.next_sync_committee,
+                &update.next_sync_committee_branch,
+            )),
+            finality: Some((&update.finalized_header, &update.finality_branch)),
+            sync_aggregate: &update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        })
+    }
+
+    /// Verifies and applies a finality update.
+    pub fn process_finality_update(
+        &mut self,
+        update: &LightClientFinalityUpdate,
+    ) -> Result<(), LightClientError> {
+        self.apply(UpdateRef {
+            attested_header: &update.attested_header,
+            next_sync_committee: None,
+            finality: Some((&update.finalized_header, &update.finality_branch)),
+            sync_aggregate: &update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        })
+    }
+
+    /// Verifies and applies an optimistic update, which only advances the optimistic header.
+    pub fn process_optimistic_update(
+        &mut self,
+        update: &LightClientOptimisticUpdate,
+    ) -> Result<(), LightClientError> {
+        self.apply(UpdateRef {
+            attested_header: &update.attested_header,
+            next_sync_committee: None,
+            finality: None,
+            sync_aggregate: &update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        })
+    }
+
+    fn apply(&mut self, update: UpdateRef<'_>) -> Result<(), LightClientError> {
+        self.validate_update(&update)?;
+
+        if let Some((finalized_header, _)) = update.finality {
+            let store_period =
+                compute_sync_committee_period_at_slot(self.finalized_header.beacon.slot);
+            let finalized_period =
+                compute_sync_committee_period_at_slot(finalized_header.beacon.slot);
+            if finalized_period == store_period + 1 {
+                // the store can only advance into the next period if it knows its sync committee
+                let next = self.next_sync_committee.take().ok_or(
+                    LightClientError::UnknownSyncCommittee {
+                        store: store_period,
+                        got: finalized_period,
+                    },
+                )?;
+                self.current_sync_committee = next;
+                self.next_sync_committee = update.next_sync_committee.map(|(c, _)| c.clone());
+            } else if finalized_period == store_period && self.next_sync_committee.is_none() {
+                self.next_sync_committee = update.next_sync_committee.map(|(c, _)| c.clone());
+            }
+            if finalized_header.beacon.slot > self.finalized_header.beacon.slot {
+                self.finalized_header = finalized_header.clone();
+            }
+        }
+
+        if update.attested_header.beacon.slot > self.optimistic_header.beacon.slot {
+            self.optimistic_header = update.attested_header.clone();
+        }
+        if self.finalized_header.beacon.slot > self.optimistic_header.beacon.slot {
+            self.optimistic_header = self.finalized_header.clone();
+        }
+        Ok(())
+    }
+
+    /// Validates the update against the store.
+    ///
+    /// See also <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#validate_light_client_update>
+    fn validate_update(&self, update: &UpdateRef<'_>) -> Result<(), LightClientError> {
+        let attested = &update.attested_header;
+        validate_header(attested)?;
+
+        let finalized_slot = update.finality.map_or(attested.beacon.slot, |(h, _)| h.beacon.slot);
+        if !(update.signature_slot > attested.beacon.slot && attested.beacon.slot >= finalized_slot)
+        {
+            return Err(LightClientError::InvalidSlots);
+        }
+
+        let store_period = compute_sync_committee_period_at_slot(self.finalized_header.beacon.slot);
+        let signature_period = compute_sync_committee_period_at_slot(update.signature_slot);
+        let committee = match &self.next_sync_committee {
+            _ if signature_period == store_period => &self.current_sync_committee,
+            Some(next) if signature_period == store_period + 1 => next,
+            _ => {
+                return Err(LightClientError::UnknownSyncCommittee {
+                    store: store_period,
+                    got: signature_period,
+                })
+            }
+        };
+
+        let attested_period = compute_sync_committee_period_at_slot(attested.beacon.slot);
+        let learns_next_committee = self.next_sync_committee.is_none()
+            && update.next_sync_committee.is_some()
+            && attested_period == store_period;
+        if attested.beacon.slot <= self.finalized_header.beacon.slot && !learns_next_committee {
+            return Err(LightClientError::IrrelevantUpdate);
+        }
+
+        if let Some((finalized_header, finality_branch)) = update.finality {
+            validate_header(finalized_header)?;
+            if !is_valid_normalized_merkle_branch(
+                finalized_header.beacon.hash_tree_root(),
+                finality_branch,
+                self.config.finalized_root_gindex(attested.beacon.slot),
+                attested.beacon.state_root,
+            ) {
+                return Err(LightClientError::InvalidFinalityBranch);
+            }
+        }
+
+        if let Some((next_sync_committee, branch)) = update.next_sync_committee {
+            validate_sync_committee(next_sync_committee)?;
+            if attested_period == store_period {
+                if let Some(known) = &self.next_sync_committee {
+                    if known != next_sync_committee {
+                        return Err(LightClientError::ConflictingSyncCommittee);
+                    }
+                }
+            }
+            if !is_valid_normalized_merkle_branch(
+                next_sync_committee.hash_tree_root(),
+                branch,
+                self.config.next_sync_committee_gindex(attested.beacon.slot),
+                attested.beacon.state_root,
+            ) {
+                return Err(LightClientError::InvalidSyncCommitteeBranch);
+            }
+        }
+
+        let fork_version = self
+            .config
+            .fork_version(compute_epoch_at_slot(update.signature_slot.saturating_sub(1)));
+        let domain = compute_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            fork_version,
+            self.config.genesis_validators_root,
+        );
+        let signing_root = compute_signing_root(attested.beacon.hash_tree_root(), domain);
+        verify_sync_aggregate(committee, update.sync_aggregate, signing_root)
+    }
+}
+
+/// Verifies that the execution payload header is part of the beacon block body.
+fn validate_header(header: &LightClientHeader) -> Result<(), LightClientError> {
+    if !is_valid_normalized_merkle_branch(
+        header.execution.hash_tree_root(),
+        &header.execution_branch,
+        EXECUTION_PAYLOAD_GINDEX,
+        header.beacon.body_root,
+    ) {
+        return Err(LightClientError::InvalidExecutionBranch);
+    }
+    Ok(())
+}
+
+fn validate_sync_committee(committee: &SyncCommittee) -> Result<(), LightClientError> {
+    if committee.pubkeys.len() != SYNC_COMMITTEE_SIZE {
+        return Err(LightClientError::InvalidSyncCommitteeSize(committee.pubkeys.len()));
+    }
+    Ok(())
+}
+
+/// Verifies that a supermajority of the sync committee signed the signing root.
+fn verify_sync_aggregate(
+    committee: &SyncCommittee,
+    aggregate: &SyncAggregate,
+    signing_root: B256,
+) -> Result<(), LightClientError> {
+    let bits = &aggregate.sync_committee_bits;
+    if bits.len() * 8 != SYNC_COMMITTEE_SIZE {
+        return Err(LightClientError::InvalidSyncAggregate);
+    }
+
+    let participants = committee
+        .pubkeys
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| bits[i / 8] >> (i % 8) & 1 == 1)
+        .map(|(_, pubkey)| PublicKey::from_bytes(pubkey.as_slice()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(LightClientError::InvalidSignature)?;
+    if participants.len() * 3 < SYNC_COMMITTEE_SIZE * 2 {
+        return Err(LightClientError::InsufficientParticipation {
+            participants: participants.len(),
+        });
+    }
+
+    let signature = Signature::from_bytes(&aggregate.sync_committee_signature)
+        .map_err(LightClientError::InvalidSignature)?;
+    let participants: Vec<_> = participants.iter().collect();
+    match signature.fast_aggregate_verify(true, signing_root.as_slice(), BLS_DST_SIG, &participants)
+    {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        err => Err(LightClientError::InvalidSignature(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::BeaconBlockHeader,
+        light_client::{merkle::hash_pair, EPOCHS_PER_SYNC_COMMITTEE_PERIOD, SLOTS_PER_EPOCH},
+        payload::ExecutionPayloadHeader,
+        BlsPublicKey,
+    };
+    use alloy_primitives::{Bytes, U256};
+    use blst::min_pk::{AggregateSignature, SecretKey};
+    use std::collections::HashMap;
+
+    /// A sparse Merkle tree with the given nodes, and zero leaves at `depth`.
+    struct Tree {
+        nodes: HashMap<u64, B256>,
+        depth: u32,
+    }
+
+    impl Tree {
+        fn new(depth: u32, nodes: &[(u64, B256)]) -> Self {
+            Self { nodes: nodes.iter().copied().collect(), depth }
+        }
+
+        fn node(&self, gindex: u64) -> B256 {
+            if let Some(node) = self.nodes.get(&gindex) {
+                return *node;
+            }
+            if gindex.ilog2() >= self.depth {
+                return B256::ZERO;
+            }
+            hash_pair(&self.node(2 * gindex), &self.node(2 * gindex + 1))
+        }
+
+        fn root(&self) -> B256 {
+            self.node(1)
+        }
+
+        fn branch(&self, mut gindex: u64) -> Vec<B256> {
+            let mut branch = Vec::new();
+            while gindex > 1 {
+                branch.push(self.node(gindex ^ 1));
+                gindex /= 2;
+            }
+            branch
+        }
+    }
+
+    struct Committee {
+        secret_keys: Vec<SecretKey>,
+        committee: SyncCommittee,
+    }
+
+    impl Committee {
+        fn new(seed: u8) -> Self {
+            // a few distinct keys are enough, members may share keys
+            let secret_keys: Vec<_> =
+                (0..4).map(|i| SecretKey::key_gen(&[seed + i; 32], &[]).unwrap()).collect();
+            let pubkeys = (0..SYNC_COMMITTEE_SIZE)
+                .map(|i| BlsPublicKey::from(secret_keys[i % 4].sk_to_pk().to_bytes()))
+                .collect();
+            let committee = SyncCommittee { pubkeys, aggregate_pubkey: BlsPublicKey::ZERO };
+            Self { secret_keys, committee }
+        }
+
+        fn sign(&self, signing_root: B256, participants: usize) -> SyncAggregate {
+            let mut bits = [0u8; SYNC_COMMITTEE_SIZE / 8];
+            let signatures: Vec<_> = (0..participants)
+                .map(|i| {
+                    bits[i / 8] |= 1 << (i % 8);
+                    self.secret_keys[i % 4].sign(signing_root.as_slice(), BLS_DST_SIG, &[])
+                })
+                .collect();
+            let signatures: Vec<_> = signatures.iter().collect();
+            let signature = AggregateSignature::aggregate(&signatures, true).unwrap();
+            SyncAggregate {
+                sync_committee_bits: Bytes::copy_from_slice(&bits),
+                sync_committee_signature: Bytes::copy_from_slice(
+                    &signature.to_signature().to_bytes(),
+                ),
+            }
+        }
+    }
+
+    fn config() -> LightClientConfig {
+        LightClientConfig {
+            genesis_validators_root: B256::repeat_byte(0x42),
+            genesis_fork_version: fixed_bytes!("00000001"),
+            forks: vec![Fork { epoch: 0, version: fixed_bytes!("03000001") }],
+            electra_fork_epoch: None,
+        }
+    }
+
+    fn header(slot: u64, state: &Tree) -> LightClientHeader {
+        let execution = execution(slot);
+        let body = Tree::new(4, &[(EXECUTION_PAYLOAD_GINDEX, execution.hash_tree_root())]);
+        LightClientHeader {
+            beacon: BeaconBlockHeader {
+                slot,
+                proposer_index: 1,
+                parent_root: B256::ZERO,
+                state_root: state.root(),
+                body_root: body.root(),
+            },
+            execution_branch: body.branch(EXECUTION_PAYLOAD_GINDEX),
+            execution,
+        }
+    }
+
+    fn execution(number: u64) -> ExecutionPayloadHeader {
+        ExecutionPayloadHeader {
+            block_number: number,
+            block_hash: B256::with_last_byte(number as u8),
+            base_fee_per_gas: U256::from(7),
+            withdrawals_root: Some(B256::ZERO),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        }
+    }
+
+    fn signing_root(config: &LightClientConfig, header: &LightClientHeader, slot: u64) -> B256 {
+        let fork_version = config.fork_version(compute_epoch_at_slot(slot - 1));
+        let domain =
+            compute_domain(DOMAIN_SYNC_COMMITTEE, fork_version, config.genesis_validators_root);
+        compute_signing_root(header.beacon.hash_tree_root(), domain)
+    }
+
+    fn bootstrap(committee: &Committee) -> (B256, LightClientBootstrap) {
+        let state =
+            Tree::new(6, &[(CURRENT_SYNC_COMMITTEE_GINDEX, committee.committee.hash_tree_root())]);
+        let header = header(64, &state);
+        let bootstrap = LightClientBootstrap {
+            current_sync_committee: committee.committee.clone(),
+            current_sync_committee_branch: state.branch(CURRENT_SYNC_COMMITTEE_GINDEX),
+            header,
+        };
+        (bootstrap.header.beacon.hash_tree_root(), bootstrap)
+    }
+
+    fn finality_update(
+        committee: &Committee,
+        attested_slot: u64,
+        finalized_slot: u64,
+        participants: usize,
+    ) -> LightClientFinalityUpdate {
+        let finalized_header = header(finalized_slot, &Tree::new(6, &[]));
+        let state =
+            Tree::new(6, &[(FINALIZED_ROOT_GINDEX, finalized_header.beacon.hash_tree_root())]);
+        let attested_header = header(attested_slot, &state);
+        let signature_slot = attested_slot + 1;
+        let sync_aggregate =
+            committee.sign(signing_root(&config(), &attested_header, signature_slot), participants);
+        LightClientFinalityUpdate {
+            attested_header,
+            finalized_header,
+            finality_branch: state.branch(FINALIZED_ROOT_GINDEX),
+            sync_aggregate,
+            signature_slot,
+        }
+    }
+
+    #[test]
+    fn gindex_depths() {
+        assert_eq!(EXECUTION_PAYLOAD_GINDEX.ilog2(), 4);
+        assert_eq!(FINALIZED_ROOT_GINDEX.ilog2(), 6);
+        assert_eq!(CURRENT_SYNC_COMMITTEE_GINDEX.ilog2(), 5);
+        assert_eq!(NEXT_SYNC_COMMITTEE_GINDEX.ilog2(), 5);
+        assert_eq!(FINALIZED_ROOT_GINDEX_ELECTRA.ilog2(), 7);
+        assert_eq!(NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA.ilog2(), 6);
+    }
+
+    #[test]
+    fn mainnet_fork_versions() {
+        let config = LightClientConfig::mainnet();
+        assert_eq!(config.fork_version(0), fixed_bytes!("00000000"));
+        assert_eq!(config.fork_version(74240), fixed_bytes!("01000000"));
+        assert_eq!(config.fork_version(269567), fixed_bytes!("03000000"));
+        assert_eq!(config.fork_version(269568), fixed_bytes!("04000000"));
+    }
+
+    #[test]
+    fn bootstrap_requires_trusted_root() {
+        let committee = Committee::new(1);
+        let (root, bootstrap) = bootstrap(&committee);
+
+        let store = LightClientStore::bootstrap(config(), root, bootstrap.clone()).unwrap();
+        assert_eq!(store.finalized_header(), &bootstrap.header);
+        assert_eq!(store.next_sync_committee(), None);
+
+        let err = LightClientStore::bootstrap(config(), B256::ZERO, bootstrap.clone()).unwrap_err();
+        assert!(matches!(err, LightClientError::UntrustedBootstrap { .. }));
+
+        let mut invalid = bootstrap.clone();
+        invalid.current_sync_committee_branch[0] = B256::repeat_byte(1);
+        let err = LightClientStore::bootstrap(config(), root, invalid).unwrap_err();
+        assert!(matches!(err, LightClientError::InvalidSyncCommitteeBranch));
+
+        let mut invalid = bootstrap;
+        invalid.header.execution.gas_used = 1;
+        let err = LightClientStore::bootstrap(config(), root, invalid).unwrap_err();
+        assert!(matches!(err, LightClientError::InvalidExecutionBranch));
+    }
+
+    #[test]
+    fn process_finality_update() {
+        let committee = Committee::new(1);
+        let (root, bootstrap) = bootstrap(&committee);
+        let mut store = LightClientStore::bootstrap(config(), root, bootstrap).unwrap();
+
+        let update = finality_update(&committee, 128, 96, 400);
+        store.process_finality_update(&update).unwrap();
+        assert_eq!(store.finalized_header(), &update.finalized_header);
+        assert_eq!(store.optimistic_header(), &update.attested_header);
+        assert_eq!(store.finalized_header().execution.block_number, 96);
+
+        // updates attesting to finalized headers do not advance the store anymore
+        let err =
+            store.process_finality_update(&finality_update(&committee, 96, 64, 400)).unwrap_err();
+        assert!(matches!(err, LightClientError::IrrelevantUpdate));
+    }
+
+    #[test]
+    fn rejects_invalid_finality_updates() {
+        let committee = Committee::new(1);
+        let (root, bootstrap) = bootstrap(&committee);
+        let store = LightClientStore::bootstrap(config(), root, bootstrap).unwrap();
+        let process = |update: &LightClientFinalityUpdate| {
+            store.clone().process_finality_update(update).unwrap_err()
+        };
+
+        let update = finality_update(&committee, 128, 96, 300);
+        assert!(matches!(
+            process(&update),
+            LightClientError::InsufficientParticipation { participants: 300 }
+        ));
+
+        let update = finality_update(&Committee::new(10), 128, 96, 512);
+        assert!(matches!(process(&update), LightClientError::InvalidSignature(_)));
+
+        let mut update = finality_update(&committee, 128, 96, 512);
+        update.finality_branch.swap(0, 1);
+        assert!(matches!(process(&update), LightClientError::InvalidFinalityBranch));
+
+        let mut update = finality_update(&committee, 128, 96, 512);
+        update.signature_slot = 128;
+        assert!(matches!(process(&update), LightClientError::InvalidSlots));
+
+        let mut update = finality_update(&committee, 128, 96, 512);
+        update.signature_slot = 32 * 256 * 2;
+        assert!(matches!(
+            process(&update),
+            LightClientError::UnknownSyncCommittee { store: 0, got: 2 }
+        ));
+    }
+
+    #[test]
+    fn process_update_rotates_sync_committee() {
+        let committee = Committee::new(1);
+        let next = Committee::new(20);
+        let (root, bootstrap) = bootstrap(&committee);
+        let mut store = LightClientStore::bootstrap(config(), root, bootstrap).unwrap();
+
+        let period = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+        let update = |signer: &Committee, attested_slot: u64, finalized_slot: u64| {
+            let finalized_header = header(finalized_slot, &Tree::new(6, &[]));
+            let state = Tree::new(
+                6,
+                &[
+                    (FINALIZED_ROOT_GINDEX, finalized_header.beacon.hash_tree_root()),
+                    (NEXT_SYNC_COMMITTEE_GINDEX, next.committee.hash_tree_root()),
+                ],
+            );
+            let attested_header = header(attested_slot, &state);
+            let signature_slot = attested_slot + 1;
+            LightClientUpdate {
+                sync_aggregate: signer
+                    .sign(signing_root(&config(), &attested_header, signature_slot), 512),
+                attested_header,
+                next_sync_committee: next.committee.clone(),
+                next_sync_committee_branch: state.branch(NEXT_SYNC_COMMITTEE_GINDEX),
+                finalized_header,
+                finality_branch: state.branch(FINALIZED_ROOT_GINDEX),
+                signature_slot,
+            }
+        };
+
+        // updates signed in the next period are rejected until the next committee is known
+        let err =
+            store.clone().process_update(&update(&next, period + 64, period + 32)).unwrap_err();
+        assert!(matches!(err, LightClientError::UnknownSyncCommittee { store: 0, got: 1 }));
+
+        store.process_update(&update(&committee, 128, 96)).unwrap();
+        assert_eq!(store.next_sync_committee(), Some(&next.committee));
+
+        store.process_update(&update(&next, period + 64, period + 32)).unwrap();
+        assert_eq!(store.current_sync_committee(), &next.committee);
+        assert_eq!(store.finalized_header().beacon.slot, period + 32);
+    }
+}
@@ -77,9 +77,11 @@ pub struct ExecutionPayloadHeader {
     pub logs_bloom: Bloom,
     /// The previous Randao value of the execution payload.
     pub prev_randao: B256,
-    /// The block number of the execution payload, represented as a string.
+    /// The block number of the execution payload, represented as a `u64`.
+    ///
+    /// Serialized as a quoted decimal string, as in the beacon API, like the other `u64` fields.
     #[serde_as(as = "DisplayFromStr")]
-    pub block_number: String,
+    pub block_number: u64,
     /// The gas limit of the execution payload, represented as a `u64`.
     #[serde_as(as = "DisplayFromStr")]
     pub gas_limit: u64,
@@ -98,6 +100,17 @@ pub struct ExecutionPayloadHeader {
     pub block_hash: B256,
     /// The transactions root of the execution payload.
     pub transactions_root: B256,
+    /// The withdrawals root of the execution payload, since Capella.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<B256>,
+    /// The blob gas used by the execution payload, since Deneb.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub blob_gas_used: Option<u64>,
+    /// The excess blob gas of the execution payload, since Deneb.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub excess_blob_gas: Option<u64>,
 }
 
 #[serde_as]
@@ -475,6 +475,23 @@ mod tests {
         assert_eq!(bytes, bid.as_ssz_bytes());
     }
 
+    #[cfg(feature = "ssz")]
+    #[test]
+    fn capella_payload_block_ssz_roundtrip() {
+        use alloy_consensus::TxEnvelope;
+        use ssz::{Decode, Encode};
+
+        let bytes = include_bytes!("examples/relay_signed_bid_submission_capella.ssz").to_vec();
+        let payload = SignedBidSubmissionV2::from_ssz_bytes(&bytes).unwrap().execution_payload;
+
+        let block = payload.clone().try_into_block::<TxEnvelope>().unwrap();
+        assert_eq!(block.header.hash_slow(), payload.payload_inner.block_hash);
+
+        let roundtrip = ExecutionPayloadV2::from_block_slow(&block);
+        assert_eq!(roundtrip, payload);
+        assert_eq!(roundtrip.as_ssz_bytes(), payload.as_ssz_bytes());
+    }
+
     #[test]
     fn test_can_parse_validation_request_body() {
         const VALIDATION_REQUEST_BODY: &str = include_str!("examples/relay_single_payload.json");
@@ -1,5 +1,8 @@
 use crate::header::Header;
-use alloy_eips::eip4844::{deserialize_blob, Blob, BlobTransactionSidecar, Bytes48};
+use alloy_eips::eip4844::{
+    decode::{BlobDecoder, BlobDecodingError},
+    deserialize_blob, Blob, BlobTransactionSidecar, Bytes48,
+};
 use alloy_primitives::B256;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
@@ -33,6 +36,22 @@ impl BeaconBlobBundle {
     pub fn get_blob(&self, index: u64) -> Option<&BlobData> {
         self.data.iter().find(|blob| blob.index == index)
     }
+
+    /// Returns the blobs of the bundle, ordered by index.
+    pub fn blobs(&self) -> Vec<Blob> {
+        let mut data: Vec<_> = self.data.iter().collect();
+        data.sort_by_key(|blob| blob.index);
+        data.into_iter().map(|blob| *blob.blob).collect()
+    }
+
+    /// Decodes the payloads packed into the blobs of the bundle, ordered by index, with the given
+    /// decoder.
+    pub fn decode_data<D: BlobDecoder>(
+        &self,
+        decoder: &D,
+    ) -> Result<Vec<Vec<u8>>, BlobDecodingError> {
+        decoder.decode(&self.blobs())
+    }
 }
 
 /// Yields an iterator for BlobData
@@ -114,4 +133,15 @@ mod tests {
         assert_eq!(json, serde_json::to_value(resp.clone()).unwrap());
         assert_eq!(6, resp.data.len());
     }
+
+    #[test]
+    fn decode_sidecar_bundle() {
+        let s = include_str!("examples/sidecar.json");
+        let resp: BeaconBlobBundle = serde_json::from_str(s).unwrap();
+        let blobs = resp.blobs();
+        assert_eq!(blobs.len(), 6);
+        assert_eq!(blobs[0], *resp.get_blob(0).unwrap().blob);
+        // the example blobs are not packed with the simple coder
+        assert!(resp.decode_data(&alloy_eips::eip4844::builder::SimpleCoder::default()).is_err());
+    }
 }
@@ -1,6 +1,6 @@
 //! Types for the `debug` API.
 
-use alloy_primitives::{map::B256HashMap, Bytes};
+use alloy_primitives::{keccak256, map::B256HashMap, Bytes, B256};
 use serde::{Deserialize, Serialize};
 
 /// Represents the execution witness of a block. Contains an optional map of state preimages.
@@ -24,3 +24,41 @@ pub struct ExecutionWitness {
     #[serde(default)]
     pub keys: Option<B256HashMap<Bytes>>,
 }
+
+impl ExecutionWitness {
+    /// Returns the hash of the first entry of the witness whose preimage does not hash to it, if
+    /// any.
+    ///
+    /// This checks the trie nodes, the contract codes and the hashed keys of the witness.
+    pub fn find_invalid_preimage(&self) -> Option<B256> {
+        self.state
+            .iter()
+            .chain(self.codes.iter())
+            .chain(self.keys.iter().flatten())
+            .find(|(hash, preimage)| keccak256(preimage) != **hash)
+            .map(|(hash, _)| *hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_execution_witness() {
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        let s = format!(r#"{{"state":{{}},"codes":{{"{}":"{}"}}}}"#, keccak256(&code), code);
+        let witness: ExecutionWitness = serde_json::from_str(&s).unwrap();
+        assert_eq!(witness.codes.len(), 1);
+        assert_eq!(witness.keys, None);
+        assert_eq!(witness.find_invalid_preimage(), None);
+    }
+
+    #[test]
+    fn invalid_preimage() {
+        let mut witness = ExecutionWitness::default();
+        witness.state.insert(keccak256([1]), Bytes::from_static(&[1]));
+        witness.keys = Some([(B256::ZERO, Bytes::from_static(&[2]))].into_iter().collect());
+        assert_eq!(witness.find_invalid_preimage(), Some(B256::ZERO));
+    }
+}
@@ -8,3 +8,6 @@
 
 mod debug;
 pub use debug::*;
+
+mod verkle;
+pub use verkle::*;
@@ -0,0 +1,143 @@
+//! Types for the execution witness of a block in a Verkle tree, see [EIP-6800].
+//!
+//! [EIP-6800]: https://eips.ethereum.org/EIPS/eip-6800
+
+use alloy_primitives::{Bytes, FixedBytes, B256};
+use serde::{Deserialize, Serialize};
+
+/// The stem of a Verkle tree key, i.e. the key without its last byte.
+pub type Stem = FixedBytes<31>;
+
+/// The execution witness of a block in a Verkle tree.
+///
+/// Contains the pre- and post-state of all leaves accessed during the execution of the block,
+/// together with a proof of the pre-state against the state root of the parent block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerkleExecutionWitness {
+    /// The accessed leaves, grouped by stem.
+    pub state_diff: Vec<StemStateDiff>,
+    /// The proof of the pre-state of the accessed leaves.
+    pub verkle_proof: VerkleProof,
+}
+
+impl VerkleExecutionWitness {
+    /// Returns an iterator over the full keys of all accessed leaves, together with their
+    /// pre-state value, if the leaf was present.
+    pub fn pre_state(&self) -> impl Iterator<Item = (B256, Option<B256>)> + '_ {
+        self.leaves().map(|(key, diff)| (key, diff.current_value))
+    }
+
+    /// Returns an iterator over the full keys of all written leaves, together with their
+    /// post-state value.
+    pub fn post_state(&self) -> impl Iterator<Item = (B256, B256)> + '_ {
+        self.leaves().filter_map(|(key, diff)| diff.new_value.map(|value| (key, value)))
+    }
+
+    fn leaves(&self) -> impl Iterator<Item = (B256, &SuffixStateDiff)> + '_ {
+        self.state_diff
+            .iter()
+            .flat_map(|stem| stem.suffix_diffs.iter().map(|diff| (stem.key(diff.suffix), diff)))
+    }
+}
+
+/// The accessed leaves of a single stem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StemStateDiff {
+    /// The stem of the leaves.
+    pub stem: Stem,
+    /// The accessed leaves of the stem.
+    pub suffix_diffs: Vec<SuffixStateDiff>,
+}
+
+impl StemStateDiff {
+    /// Returns the full key of the leaf with the given suffix.
+    pub fn key(&self, suffix: u8) -> B256 {
+        let mut key = B256::ZERO;
+        key[..31].copy_from_slice(self.stem.as_slice());
+        key[31] = suffix;
+        key
+    }
+}
+
+/// The pre- and post-state of a single leaf.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuffixStateDiff {
+    /// The last byte of the key of the leaf.
+    #[serde(with = "hex_byte")]
+    pub suffix: u8,
+    /// The value of the leaf before the execution of the block, `None` if it was absent.
+    pub current_value: Option<B256>,
+    /// The value of the leaf after the execution of the block, `None` if it was not written.
+    pub new_value: Option<B256>,
+}
+
+/// A multiproof of a set of leaves of a Verkle tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerkleProof {
+    /// The stems present in the tree at the paths of the absent stems of the witness.
+    pub other_stems: Vec<Stem>,
+    /// The depth and extension status of each stem of the witness.
+    pub depth_extension_present: Bytes,
+    /// The commitments of the internal nodes along the paths of the witness, sorted by path.
+    pub commitments_by_path: Vec<B256>,
+    /// The commitment to the quotient polynomial of the multiproof.
+    pub d: B256,
+    /// The inner product argument of the multiproof.
+    pub ipa_proof: IpaProof,
+}
+
+/// An inner product argument proof.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpaProof {
+    /// The left commitments of each round.
+    pub cl: Vec<B256>,
+    /// The right commitments of each round.
+    pub cr: Vec<B256>,
+    /// The final evaluation of the folded polynomial.
+    pub final_evaluation: B256,
+}
+
+/// (De)serializes a single byte as a hex string, e.g. `0x0a`.
+mod hex_byte {
+    use alloy_primitives::hex;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(byte: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode_prefixed([*byte]))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        match hex::decode(&s).map_err(D::Error::custom)?.as_slice() {
+            [byte] => Ok(*byte),
+            _ => Err(D::Error::custom("expected a single byte")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn serde_verkle_execution_witness() {
+        let s = r#"{"stateDiff":[{"stem":"0x0e88cc6bf033a3ff779335e720d5a7edf907cc70ab7ff31375cd485db779fc","suffixDiffs":[{"suffix":"0x00","currentValue":"0x0000000000000000000000000000000000000000000000000000000000000000","newValue":null},{"suffix":"0x81","currentValue":null,"newValue":"0x0100000000000000000000000000000000000000000000000000000000000000"}]}],"verkleProof":{"otherStems":[],"depthExtensionPresent":"0x0a","commitmentsByPath":["0x4900c9eebcfd806ec3a04fe2c703cabba5e0b9b7bd5e6d1982031ae2b2de1e8b"],"d":"0x1c11a51f1d2b1ddfaffcbd9d2cd40a1b0c2b7e6e8bb92c0cfc22d9e9ecd93c50","ipaProof":{"cl":["0x5e67466d40c8b6e5ce6c07ee6ec24a6fd3ab21b8d7a5b4e86f1b0674507c1a25"],"cr":["0x2ba8c1d6e4e143f1dc1bb5a49d4c0ed70ae37ac1fcc3a44c7ffd3bd8b77c16e4"],"finalEvaluation":"0x0bc4d5e18c6b2a2cd2dc6cda5a3c1a7d50d9a2b1fdf2b4b7e45a4b7c7e20a3e2"}}}"#;
+        let witness: VerkleExecutionWitness = serde_json::from_str(s).unwrap();
+        assert_eq!(witness.state_diff[0].suffix_diffs[1].suffix, 0x81);
+        let json: serde_json::Value = serde_json::from_str(s).unwrap();
+        assert_eq!(json, serde_json::to_value(&witness).unwrap());
+
+        let key = b256!("0e88cc6bf033a3ff779335e720d5a7edf907cc70ab7ff31375cd485db779fc81");
+        assert_eq!(witness.pre_state().nth(1), Some((key, None)));
+        assert_eq!(
+            witness.post_state().collect::<Vec<_>>(),
+            vec![(key, b256!("0100000000000000000000000000000000000000000000000000000000000000"))]
+        );
+    }
+}
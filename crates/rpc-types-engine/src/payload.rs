@@ -1,13 +1,22 @@
 //! Payload types.
 
+use crate::MaybeCancunPayloadFields;
 use alloc::{
     string::{String, ToString},
     vec::Vec,
 };
-use alloy_consensus::{Blob, Bytes48};
+use alloy_consensus::{
+    constants::MAXIMUM_EXTRA_DATA_SIZE, proofs, Blob, Block, BlockBody, Bytes48, Header,
+    RequestsByType, SealedBlock, EMPTY_OMMER_ROOT_HASH,
+};
 use alloy_eips::{
-    eip4844::BlobTransactionSidecar, eip4895::Withdrawal, eip6110::DepositRequest,
-    eip7002::WithdrawalRequest, eip7251::ConsolidationRequest, BlockNumHash,
+    eip2718::{Decodable2718, Encodable2718},
+    eip4844::BlobTransactionSidecar,
+    eip4895::Withdrawal,
+    eip6110::DepositRequest,
+    eip7002::WithdrawalRequest,
+    eip7251::ConsolidationRequest,
+    BlockNumHash,
 };
 use alloy_primitives::{Address, Bloom, Bytes, B256, B64, U256};
 use core::iter::{FromIterator, IntoIterator};
@@ -197,6 +206,92 @@ impl ExecutionPayloadV1 {
     pub const fn block_num_hash(&self) -> BlockNumHash {
         BlockNumHash::new(self.block_number, self.block_hash)
     }
+
+    /// Converts a block into a payload, with the given hash of the block.
+    ///
+    /// The fields of the header that are not part of the payload, e.g. the ommers hash, are
+    /// dropped, as are the withdrawals and requests.
+    pub fn from_block_unchecked<T: Encodable2718>(block_hash: B256, block: &Block<T>) -> Self {
+        let header = &block.header;
+        Self {
+            parent_hash: header.parent_hash,
+            fee_recipient: header.beneficiary,
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            logs_bloom: header.logs_bloom,
+            prev_randao: header.mix_hash,
+            block_number: header.number,
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            timestamp: header.timestamp,
+            extra_data: header.extra_data.clone(),
+            base_fee_per_gas: U256::from(header.base_fee_per_gas.unwrap_or_default()),
+            block_hash,
+            transactions: block
+                .body
+                .transactions
+                .iter()
+                .map(|tx| tx.encoded_2718().into())
+                .collect(),
+        }
+    }
+
+    /// Converts a block into a payload, hashing its header.
+    pub fn from_block_slow<T: Encodable2718>(block: &Block<T>) -> Self {
+        Self::from_block_unchecked(block.header.hash_slow(), block)
+    }
+
+    /// Converts the payload into a post-merge block, decoding its transactions.
+    ///
+    /// The block hash is not checked, see [`ExecutionPayload::try_into_sealed_block`].
+    pub fn try_into_block<T: Decodable2718>(self) -> Result<Block<T>, PayloadError> {
+        if self.extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(PayloadError::ExtraData(self.extra_data));
+        }
+        let base_fee_per_gas = self
+            .base_fee_per_gas
+            .try_into()
+            .map_err(|_| PayloadError::BaseFee(self.base_fee_per_gas))?;
+
+        let transactions = self
+            .transactions
+            .iter()
+            .map(|tx| {
+                let mut buf = tx.as_ref();
+                let tx = T::decode_2718(&mut buf).map_err(alloy_rlp::Error::from)?;
+                if !buf.is_empty() {
+                    return Err(alloy_rlp::Error::UnexpectedLength.into());
+                }
+                Ok(tx)
+            })
+            .collect::<Result<_, PayloadError>>()?;
+
+        let header = Header {
+            parent_hash: self.parent_hash,
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            beneficiary: self.fee_recipient,
+            state_root: self.state_root,
+            transactions_root: proofs::ordered_trie_root_with_encoder(
+                &self.transactions,
+                |tx, buf| buf.extend_from_slice(tx),
+            ),
+            receipts_root: self.receipts_root,
+            logs_bloom: self.logs_bloom,
+            difficulty: U256::ZERO,
+            number: self.block_number,
+            gas_limit: self.gas_limit,
+            gas_used: self.gas_used,
+            timestamp: self.timestamp,
+            mix_hash: self.prev_randao,
+            nonce: B64::ZERO,
+            base_fee_per_gas: Some(base_fee_per_gas),
+            extra_data: self.extra_data,
+            ..Default::default()
+        };
+        let body =
+            BlockBody { transactions, ommers: Vec::new(), withdrawals: None, requests: None };
+        Ok(Block::new(header, body))
+    }
 }
 
 /// This structure maps on the ExecutionPayloadV2 structure of the beacon chain spec.
@@ -220,6 +315,29 @@ impl ExecutionPayloadV2 {
     pub const fn timestamp(&self) -> u64 {
         self.payload_inner.timestamp
     }
+
+    /// Converts a block into a payload, with the given hash of the block, see
+    /// [`ExecutionPayloadV1::from_block_unchecked`].
+    pub fn from_block_unchecked<T: Encodable2718>(block_hash: B256, block: &Block<T>) -> Self {
+        Self {
+            payload_inner: ExecutionPayloadV1::from_block_unchecked(block_hash, block),
+            withdrawals: block.body.withdrawals.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Converts a block into a payload, hashing its header.
+    pub fn from_block_slow<T: Encodable2718>(block: &Block<T>) -> Self {
+        Self::from_block_unchecked(block.header.hash_slow(), block)
+    }
+
+    /// Converts the payload into a post-shanghai block, see
+    /// [`ExecutionPayloadV1::try_into_block`].
+    pub fn try_into_block<T: Decodable2718>(self) -> Result<Block<T>, PayloadError> {
+        let mut block = self.payload_inner.try_into_block()?;
+        block.header.withdrawals_root = Some(proofs::calculate_withdrawals_root(&self.withdrawals));
+        block.body.withdrawals = Some(self.withdrawals);
+        Ok(block)
+    }
 }
 
 #[cfg(feature = "ssz")]
@@ -344,6 +462,34 @@ impl ExecutionPayloadV3 {
     pub const fn timestamp(&self) -> u64 {
         self.payload_inner.payload_inner.timestamp
     }
+
+    /// Converts a block into a payload, with the given hash of the block, see
+    /// [`ExecutionPayloadV1::from_block_unchecked`].
+    ///
+    /// The parent beacon block root is not part of the payload, and is passed with the
+    /// [`CancunPayloadFields`](crate::CancunPayloadFields) instead.
+    pub fn from_block_unchecked<T: Encodable2718>(block_hash: B256, block: &Block<T>) -> Self {
+        Self {
+            payload_inner: ExecutionPayloadV2::from_block_unchecked(block_hash, block),
+            blob_gas_used: block.header.blob_gas_used.unwrap_or_default(),
+            excess_blob_gas: block.header.excess_blob_gas.unwrap_or_default(),
+        }
+    }
+
+    /// Converts a block into a payload, hashing its header.
+    pub fn from_block_slow<T: Encodable2718>(block: &Block<T>) -> Self {
+        Self::from_block_unchecked(block.header.hash_slow(), block)
+    }
+
+    /// Converts the payload into a post-cancun block, see [`ExecutionPayloadV1::try_into_block`].
+    ///
+    /// The parent beacon block root of the header is not set, as it is not part of the payload.
+    pub fn try_into_block<T: Decodable2718>(self) -> Result<Block<T>, PayloadError> {
+        let mut block = self.payload_inner.try_into_block()?;
+        block.header.blob_gas_used = Some(self.blob_gas_used);
+        block.header.excess_blob_gas = Some(self.excess_blob_gas);
+        Ok(block)
+    }
 }
 
 #[cfg(feature = "ssz")]
@@ -481,6 +627,38 @@ impl ExecutionPayloadV4 {
     pub const fn timestamp(&self) -> u64 {
         self.payload_inner.payload_inner.timestamp()
     }
+
+    /// Converts a block into a payload, with the given hash of the block, see
+    /// [`ExecutionPayloadV3::from_block_unchecked`].
+    pub fn from_block_unchecked<T: Encodable2718>(block_hash: B256, block: &Block<T>) -> Self {
+        let RequestsByType { deposits, withdrawals, consolidations } =
+            block.body.requests.as_ref().map(|requests| requests.by_type()).unwrap_or_default();
+        Self {
+            payload_inner: ExecutionPayloadV3::from_block_unchecked(block_hash, block),
+            deposit_requests: deposits,
+            withdrawal_requests: withdrawals,
+            consolidation_requests: consolidations,
+        }
+    }
+
+    /// Converts a block into a payload, hashing its header.
+    pub fn from_block_slow<T: Encodable2718>(block: &Block<T>) -> Self {
+        Self::from_block_unchecked(block.header.hash_slow(), block)
+    }
+
+    /// Converts the payload into a post-prague block, see [`ExecutionPayloadV3::try_into_block`].
+    pub fn try_into_block<T: Decodable2718>(self) -> Result<Block<T>, PayloadError> {
+        let mut block = self.payload_inner.try_into_block()?;
+        let requests = RequestsByType {
+            deposits: self.deposit_requests,
+            withdrawals: self.withdrawal_requests,
+            consolidations: self.consolidation_requests,
+        }
+        .into_requests();
+        block.header.requests_root = Some(requests.root());
+        block.body.requests = Some(requests);
+        Ok(block)
+    }
 }
 
 #[cfg(feature = "ssz")]
@@ -859,6 +1037,63 @@ impl ExecutionPayload {
     pub const fn prev_randao(&self) -> B256 {
         self.as_v1().prev_randao
     }
+
+    /// Converts a block into a payload, with the given hash of the block.
+    ///
+    /// The version of the payload follows from the header: [`ExecutionPayloadV4`] if it has a
+    /// requests root, [`ExecutionPayloadV3`] if it has blob gas or a parent beacon block root,
+    /// [`ExecutionPayloadV2`] if it has a withdrawals root, and [`ExecutionPayloadV1`] otherwise.
+    pub fn from_block_unchecked<T: Encodable2718>(block_hash: B256, block: &Block<T>) -> Self {
+        let header = &block.header;
+        if header.requests_root.is_some() {
+            ExecutionPayloadV4::from_block_unchecked(block_hash, block).into()
+        } else if header.blob_gas_used.is_some()
+            || header.excess_blob_gas.is_some()
+            || header.parent_beacon_block_root.is_some()
+        {
+            ExecutionPayloadV3::from_block_unchecked(block_hash, block).into()
+        } else if header.withdrawals_root.is_some() {
+            ExecutionPayloadV2::from_block_unchecked(block_hash, block).into()
+        } else {
+            ExecutionPayloadV1::from_block_unchecked(block_hash, block).into()
+        }
+    }
+
+    /// Converts a block into a payload, hashing its header, see
+    /// [`from_block_unchecked`](Self::from_block_unchecked).
+    pub fn from_block_slow<T: Encodable2718>(block: &Block<T>) -> Self {
+        Self::from_block_unchecked(block.header.hash_slow(), block)
+    }
+
+    /// Converts the payload into a block, decoding its transactions.
+    ///
+    /// The block hash is not checked, and the parent beacon block root is not set, see
+    /// [`try_into_sealed_block`](Self::try_into_sealed_block).
+    pub fn try_into_block<T: Decodable2718>(self) -> Result<Block<T>, PayloadError> {
+        match self {
+            Self::V1(payload) => payload.try_into_block(),
+            Self::V2(payload) => payload.try_into_block(),
+            Self::V3(payload) => payload.try_into_block(),
+            Self::V4(payload) => payload.try_into_block(),
+        }
+    }
+
+    /// Converts the payload into a block, with the parent beacon block root of the given cancun
+    /// fields, and checks that the hash of its header is the block hash of the payload.
+    pub fn try_into_sealed_block<T: Decodable2718>(
+        self,
+        cancun_fields: &MaybeCancunPayloadFields,
+    ) -> Result<SealedBlock<T>, PayloadError> {
+        let block_hash = self.block_hash();
+        let mut block = self.try_into_block()?;
+        block.header.parent_beacon_block_root = cancun_fields.parent_beacon_block_root();
+
+        let block = block.seal_slow();
+        if block.hash() != block_hash {
+            return Err(PayloadError::BlockHash { execution: block.hash(), consensus: block_hash });
+        }
+        Ok(block)
+    }
 }
 
 impl From<ExecutionPayloadV1> for ExecutionPayload {
@@ -1568,4 +1803,57 @@ mod tests {
         let payload = r#"{"parentHash":"0x24e8df372a61cdcdb1a163b52aaa1785e0c869d28c3b742ac09e826bbb524723","feeRecipient":"0x4200000000000000000000000000000000000011","stateRoot":"0x9a5db45897f1ff1e620a6c14b0a6f1b3bcdbed59f2adc516a34c9a9d6baafa71","receiptsRoot":"0x8af6f74835d47835deb5628ca941d00e0c9fd75585f26dabdcb280ec7122e6af","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","prevRandao":"0xf37b24eeff594848072a05f74c8600001706c83e489a9132e55bf43a236e42ec","blockNumber":"0xe3d5d8","gasLimit":"0x17d7840","gasUsed":"0xb705","timestamp":"0x65a118c0","extraData":"0x","baseFeePerGas":"0x7a0ff32","blockHash":"0xf5c147b2d60a519b72434f0a8e082e18599021294dd9085d7597b0ffa638f1c0","withdrawals":[],"transactions":["0x7ef90159a05ba0034ffdcb246703298224564720b66964a6a69d0d7e9ffd970c546f7c048094deaddeaddeaddeaddeaddeaddeaddeaddead00019442000000000000000000000000000000000000158080830f424080b90104015d8eb900000000000000000000000000000000000000000000000000000000009e1c4a0000000000000000000000000000000000000000000000000000000065a11748000000000000000000000000000000000000000000000000000000000000000a4b479e5fa8d52dd20a8a66e468b56e993bdbffcccf729223aabff06299ab36db000000000000000000000000000000000000000000000000000000000000000400000000000000000000000073b4168cc87f35cc239200a20eb841cded23493b000000000000000000000000000000000000000000000000000000000000083400000000000000000000000000000000000000000000000000000000000f4240"]}"#;
         let _payload = serde_json::from_str::<ExecutionPayloadInputV2>(payload).unwrap();
     }
+
+    #[test]
+    fn block_payload_roundtrip() {
+        use alloy_consensus::{
+            proofs, Request, Requests, SignableTransaction, TxEip1559, TxEnvelope,
+        };
+        use alloy_primitives::Signature;
+
+        let tx = TxEip1559 { chain_id: 1, gas_limit: 21000, ..Default::default() };
+        let tx: TxEnvelope = tx.into_signed(Signature::test_signature()).into();
+        let withdrawals = vec![Withdrawal { index: 1, amount: 2, ..Default::default() }];
+        let requests = Requests(vec![
+            Request::DepositRequest(DepositRequest { index: 3, ..Default::default() }),
+            Request::ConsolidationRequest(ConsolidationRequest::default()),
+        ]);
+        let header = Header {
+            number: 1,
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            transactions_root: proofs::calculate_transaction_root(core::slice::from_ref(&tx)),
+            withdrawals_root: Some(proofs::calculate_withdrawals_root(&withdrawals)),
+            requests_root: Some(requests.root()),
+            base_fee_per_gas: Some(7),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::with_last_byte(1)),
+            ..Default::default()
+        };
+        let body = BlockBody {
+            transactions: vec![tx],
+            ommers: vec![],
+            withdrawals: Some(withdrawals),
+            requests: Some(requests),
+        };
+        let block = Block::new(header, body);
+
+        let payload = ExecutionPayload::from_block_slow(&block);
+        assert!(payload.as_v4().is_some());
+        assert_eq!(payload.block_hash(), block.header.hash_slow());
+
+        let cancun_fields = MaybeCancunPayloadFields::from(crate::CancunPayloadFields {
+            parent_beacon_block_root: B256::with_last_byte(1),
+            versioned_hashes: vec![],
+        });
+        let sealed = payload.clone().try_into_sealed_block::<TxEnvelope>(&cancun_fields).unwrap();
+        assert_eq!(sealed.hash(), payload.block_hash());
+        assert_eq!(sealed.unseal(), block);
+
+        // without the parent beacon block root the header hashes differently
+        assert_matches::assert_matches!(
+            payload.try_into_sealed_block::<TxEnvelope>(&MaybeCancunPayloadFields::none()),
+            Err(PayloadError::BlockHash { .. })
+        );
+    }
 }
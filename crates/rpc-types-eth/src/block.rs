@@ -2,6 +2,7 @@
 
 use crate::{ConversionError, Transaction, Withdrawal};
 use alloc::collections::BTreeMap;
+use alloy_eips::eip4844::BlobParams;
 use alloy_network_primitives::{
     BlockResponse, BlockTransactions, HeaderResponse, TransactionResponse,
 };
@@ -157,6 +158,13 @@ impl Header {
         self.excess_blob_gas.map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for _this_ block, computed with the blob parameters of its fork.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None
+    pub fn blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        Some(blob_params.calc_blob_fee(self.excess_blob_gas?))
+    }
+
     /// Returns the blob fee for the next block according to the EIP-4844 spec.
     ///
     /// Returns `None` if `excess_blob_gas` is None.
@@ -166,6 +174,16 @@ impl Header {
         self.next_block_excess_blob_gas().map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for the next block, computed with the blob parameters of the fork of
+    /// the next block.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None.
+    ///
+    /// See also [Self::next_block_excess_blob_gas_with_params]
+    pub fn next_block_blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        Some(blob_params.calc_blob_fee(self.next_block_excess_blob_gas_with_params(blob_params)?))
+    }
+
     /// Calculate excess blob gas for the next block according to the EIP-4844
     /// spec.
     ///
@@ -173,6 +191,14 @@ impl Header {
     pub fn next_block_excess_blob_gas(&self) -> Option<u64> {
         Some(calc_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
+
+    /// Calculate excess blob gas for the next block, with the blob parameters of the fork of the
+    /// next block.
+    ///
+    /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
+    pub fn next_block_excess_blob_gas_with_params(&self, blob_params: BlobParams) -> Option<u64> {
+        Some(blob_params.next_block_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+    }
 }
 
 impl TryFrom<Header> for alloy_consensus::Header {
@@ -235,6 +261,10 @@ impl HeaderResponse for Header {
         self.hash
     }
 
+    fn parent_hash(&self) -> BlockHash {
+        self.parent_hash
+    }
+
     fn number(&self) -> u64 {
         self.number
     }
@@ -247,6 +277,34 @@ impl HeaderResponse for Header {
         &self.extra_data
     }
 
+    fn transactions_root(&self) -> B256 {
+        self.transactions_root
+    }
+
+    fn receipts_root(&self) -> B256 {
+        self.receipts_root
+    }
+
+    fn logs_bloom(&self) -> Bloom {
+        self.logs_bloom
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
+    fn withdrawals_root(&self) -> Option<B256> {
+        self.withdrawals_root
+    }
+
+    fn blob_gas_used(&self) -> Option<u64> {
+        self.blob_gas_used
+    }
+
+    fn excess_blob_gas(&self) -> Option<u64> {
+        self.excess_blob_gas
+    }
+
     fn base_fee_per_gas(&self) -> Option<u64> {
         self.base_fee_per_gas
     }
@@ -255,6 +313,10 @@ impl HeaderResponse for Header {
         self.next_block_blob_fee()
     }
 
+    fn next_block_blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        self.next_block_blob_fee_with_params(blob_params)
+    }
+
     fn coinbase(&self) -> Address {
         self.miner
     }
@@ -386,6 +448,10 @@ impl<T: TransactionResponse, H: HeaderResponse> BlockResponse for Block<T, H> {
     fn transactions_mut(&mut self) -> &mut BlockTransactions<Self::Transaction> {
         &mut self.transactions
     }
+
+    fn withdrawals(&self) -> Option<&[Withdrawal]> {
+        self.withdrawals.as_deref()
+    }
 }
 
 #[cfg(test)]
@@ -27,7 +27,7 @@ use alloy_serde::WithOtherFields;
 pub type AnyNetworkBlock = WithOtherFields<Block<WithOtherFields<Transaction>>>;
 
 pub use alloy_network_primitives::{
-    BlockTransactionHashes, BlockTransactions, BlockTransactionsKind,
+    BlockTransactionHashes, BlockTransactions, BlockTransactionsError, BlockTransactionsKind,
 };
 
 mod call;
@@ -53,6 +53,9 @@ pub mod pubsub;
 mod raw_log;
 pub use raw_log::{logs_bloom, Log as RawLog};
 
+#[cfg(feature = "server")]
+pub mod server;
+
 pub mod state;
 
 mod syncing;
@@ -72,3 +75,6 @@ pub use erc4337::{
 };
 
 pub mod simulate;
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
@@ -0,0 +1,126 @@
+//! Support for the [`postgres_types`] crate.
+//!
+//! The response types are stored as `JSONB` or `JSON`, in their RPC representation. Hashes and
+//! integers implement [`ToSql`] and [`FromSql`] through the `postgres` feature of
+//! `alloy-primitives`, as `BYTEA` and `NUMERIC`. Addresses, blooms and bytes can be stored as
+//! `BYTEA` with the [`Bytea`] wrapper.
+//!
+//! **WARNING**: like `postgres_types`, this module is not yet stable and is exempt from the semver
+//! guarantees of this crate.
+
+use crate::{Block, Header, Log, Transaction, TransactionReceipt};
+use alloy_primitives::{Address, Bloom, Bytes, B64};
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, Json, ToSql, Type};
+use std::error::Error;
+
+type BoxedError = Box<dyn Error + Sync + Send>;
+
+macro_rules! impl_json_sql {
+    ($($ty:ty),* $(,)?) => {$(
+        impl ToSql for $ty {
+            fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxedError> {
+                Json(self).to_sql(ty, out)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                <Json<Self> as ToSql>::accepts(ty)
+            }
+
+            to_sql_checked!();
+        }
+
+        impl<'a> FromSql<'a> for $ty {
+            fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxedError> {
+                Json::<Self>::from_sql(ty, raw).map(|json| json.0)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                <Json<Self> as FromSql<'_>>::accepts(ty)
+            }
+        }
+    )*};
+}
+
+impl_json_sql!(Block, Header, Transaction, TransactionReceipt, Log);
+
+/// A wrapper storing bytes as `BYTEA`, for types of `alloy-primitives` that do not implement
+/// [`ToSql`] and [`FromSql`] themselves, e.g. [`Address`].
+///
+/// ```
+/// use alloy_primitives::Address;
+/// use alloy_rpc_types_eth::postgres::Bytea;
+/// use postgres_types::ToSql;
+///
+/// let params: [&(dyn ToSql + Sync); 1] = [&Bytea(Address::ZERO)];
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bytea<T>(pub T);
+
+impl<T: AsRef<[u8]> + std::fmt::Debug> ToSql for Bytea<T> {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxedError> {
+        out.extend_from_slice(self.0.as_ref());
+        Ok(IsNull::No)
+    }
+
+    accepts!(BYTEA);
+
+    to_sql_checked!();
+}
+
+macro_rules! impl_bytea_from_sql {
+    ($($ty:ty),* $(,)?) => {$(
+        impl<'a> FromSql<'a> for Bytea<$ty> {
+            fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxedError> {
+                Ok(Self(<$ty>::try_from(raw)?))
+            }
+
+            accepts!(BYTEA);
+        }
+    )*};
+}
+
+impl_bytea_from_sql!(Address, Bloom, B64);
+
+impl<'a> FromSql<'a> for Bytea<Bytes> {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxedError> {
+        Ok(Self(Bytes::copy_from_slice(raw)))
+    }
+
+    accepts!(BYTEA);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes, U256};
+
+    fn round_trip<T>(value: &T, ty: &Type) -> T
+    where
+        T: ToSql + for<'a> FromSql<'a>,
+    {
+        let mut out = BytesMut::new();
+        assert!(matches!(value.to_sql_checked(ty, &mut out).unwrap(), IsNull::No));
+        T::from_sql(ty, &out).unwrap()
+    }
+
+    #[test]
+    fn round_trips_bytea() {
+        let address = Bytea(address!("4200000000000000000000000000000000000006"));
+        assert_eq!(round_trip(&address, &Type::BYTEA), address);
+        let data = Bytea(bytes!("c0ffee"));
+        assert_eq!(round_trip(&data, &Type::BYTEA), data);
+        assert!(Bytea::<Address>::from_sql(&Type::BYTEA, &[0; 4]).is_err());
+        assert!(Bytea(Address::ZERO).to_sql_checked(&Type::TEXT, &mut BytesMut::new()).is_err());
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let log = Log { block_number: Some(7), log_index: Some(2), ..Default::default() };
+        assert_eq!(round_trip(&log, &Type::JSONB), log);
+        assert_eq!(round_trip(&log, &Type::JSON), log);
+
+        let header = Header { number: 1, difficulty: U256::from(2), ..Default::default() };
+        assert_eq!(round_trip(&header, &Type::JSONB), header);
+    }
+}
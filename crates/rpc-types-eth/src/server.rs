@@ -0,0 +1,472 @@
+//! Server-side support for the `eth`, `net` and `web3` namespaces.
+//!
+//! For each namespace, this module provides:
+//! - a request enum, e.g. [`EthRequest`], with a variant for each method that holds its decoded
+//!   parameters, which is deserialized from a JSON-RPC request object or built from a method name
+//!   and its raw parameters with e.g. [`EthRequest::from_parts`].
+//! - a handler trait, e.g. [`EthHandler`], with a method for each RPC method and a
+//!   [`dispatch`](EthHandler::dispatch) method that calls the handler of a request and serializes
+//!   its response.
+//!
+//! Handler methods that are not implemented return [`ServerError::UnsupportedMethod`], so mocks
+//! and proxies only need to implement the methods they serve.
+//!
+//! ```
+//! use alloy_primitives::U64;
+//! use alloy_rpc_types_eth::server::{EthHandler, EthRequest, ServerError};
+//!
+//! struct Mock;
+//!
+//! impl EthHandler for Mock {
+//!     type Error = ServerError;
+//!
+//!     async fn block_number(&self) -> Result<U64, Self::Error> {
+//!         Ok(U64::from(1))
+//!     }
+//! }
+//!
+//! # async fn example() -> Result<(), ServerError> {
+//! let request: EthRequest =
+//!     serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"method":"eth_blockNumber"}"#).unwrap();
+//! assert_eq!(Mock.dispatch(request).await?, serde_json::json!("0x1"));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    state::StateOverride, AccessListResult, Block, BlockOverrides, EIP1186AccountProofResponse,
+    FeeHistory, Filter, FilterChanges, Index, Log, SyncStatus, Transaction, TransactionReceipt,
+    TransactionRequest,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, Bytes, B256, U128, U256, U64};
+use core::fmt;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+
+/// An error returned when decoding or dispatching a request.
+#[derive(Debug)]
+pub enum ServerError {
+    /// The method is not part of the namespace.
+    UnknownMethod(String),
+    /// The parameters of the request are not valid for the method.
+    InvalidParams {
+        /// The method of the request.
+        method: &'static str,
+        /// The error decoding the parameters.
+        source: serde_json::Error,
+    },
+    /// The handler does not implement the method.
+    UnsupportedMethod(&'static str),
+    /// The response of the handler could not be serialized.
+    Serialization(serde_json::Error),
+}
+
+impl ServerError {
+    /// Returns the JSON-RPC error code of the error.
+    pub const fn code(&self) -> i64 {
+        match self {
+            Self::UnknownMethod(_) | Self::UnsupportedMethod(_) => -32601,
+            Self::InvalidParams { .. } => -32602,
+            Self::Serialization(_) => -32603,
+        }
+    }
+
+    fn invalid_params(method: &'static str, msg: impl fmt::Display) -> Self {
+        Self::InvalidParams { method, source: serde::de::Error::custom(msg) }
+    }
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMethod(method) => write!(f, "the method {method} does not exist"),
+            Self::InvalidParams { method, source } => {
+                write!(f, "invalid params for {method}: {source}")
+            }
+            Self::UnsupportedMethod(method) => write!(f, "the method {method} is not supported"),
+            Self::Serialization(err) => write!(f, "failed to serialize response: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ServerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidParams { source, .. } | Self::Serialization(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Splits the raw parameters of a request into its positional parameters.
+fn positional_params(method: &'static str, params: Value) -> Result<Vec<Value>, ServerError> {
+    match params {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(params) => Ok(params),
+        _ => Err(ServerError::invalid_params(method, "expected an array of parameters")),
+    }
+}
+
+/// Decodes the next positional parameter, treating a missing parameter as `null` so optional
+/// trailing parameters may be omitted.
+fn next_param<T: DeserializeOwned>(
+    method: &'static str,
+    params: &mut alloc::vec::IntoIter<Value>,
+) -> Result<T, ServerError> {
+    T::deserialize(params.next().unwrap_or(Value::Null))
+        .map_err(|source| ServerError::InvalidParams { method, source })
+}
+
+/// The raw method and parameters of a JSON-RPC request object.
+#[derive(Deserialize)]
+struct RawRequest {
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+macro_rules! rpc_namespace {
+    (
+        $(#[$request_meta:meta])*
+        $request:ident,
+        $(#[$handler_meta:meta])*
+        $handler:ident;
+        $(
+            $(#[$meta:meta])*
+            $variant:ident = $method:literal => fn $fn_name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;
+        )*
+    ) => {
+        $(#[$request_meta])*
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum $request {
+            $(
+                $(#[$meta])*
+                #[doc = ""]
+                #[doc = concat!("Method: `", $method, "`.")]
+                #[allow(missing_docs)]
+                $variant { $($arg: $ty),* },
+            )*
+        }
+
+        impl $request {
+            /// The methods of the namespace.
+            pub const METHODS: &'static [&'static str] = &[$($method),*];
+
+            /// Returns the method of the request.
+            pub const fn method(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => $method,)*
+                }
+            }
+
+            /// Decodes the request from its method and raw parameters, which are either `null` or
+            /// an array of positional parameters.
+            pub fn from_parts(method: &str, params: Value) -> Result<Self, ServerError> {
+                match method {
+                    $(
+                        $method => {
+                            let mut params = positional_params($method, params)?.into_iter();
+                            $(let $arg = next_param::<$ty>($method, &mut params)?;)*
+                            if params.next().is_some() {
+                                return Err(ServerError::invalid_params($method, "too many parameters"));
+                            }
+                            Ok(Self::$variant { $($arg),* })
+                        }
+                    )*
+                    _ => Err(ServerError::UnknownMethod(method.into())),
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $request {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let RawRequest { method, params } = RawRequest::deserialize(deserializer)?;
+                Self::from_parts(&method, params).map_err(serde::de::Error::custom)
+            }
+        }
+
+        $(#[$handler_meta])*
+        pub trait $handler: Send + Sync {
+            /// The error returned by the handler.
+            type Error: From<ServerError> + Send;
+
+            $(
+                $(#[$meta])*
+                #[doc = ""]
+                #[doc = concat!("Handles `", $method, "`.")]
+                fn $fn_name(
+                    &self,
+                    $($arg: $ty),*
+                ) -> impl core::future::Future<Output = Result<$ret, Self::Error>> + Send {
+                    let _ = ($($arg,)*);
+                    core::future::ready(Err(ServerError::UnsupportedMethod($method).into()))
+                }
+            )*
+
+            /// Calls the handler of the request and serializes its response.
+            fn dispatch(
+                &self,
+                request: $request,
+            ) -> impl core::future::Future<Output = Result<Value, Self::Error>> + Send {
+                async move {
+                    let response = match request {
+                        $(
+                            $request::$variant { $($arg),* } => {
+                                serde_json::to_value(self.$fn_name($($arg),*).await?)
+                            }
+                        )*
+                    };
+                    Ok(response.map_err(ServerError::Serialization)?)
+                }
+            }
+        }
+    };
+}
+
+rpc_namespace! {
+    /// A request of the `eth` namespace.
+    EthRequest,
+    /// A handler of the requests of the `eth` namespace.
+    EthHandler;
+
+    /// Returns the number of the most recent block.
+    BlockNumber = "eth_blockNumber" => fn block_number() -> U64;
+    /// Returns the chain ID.
+    ChainId = "eth_chainId" => fn chain_id() -> U64;
+    /// Returns the current gas price.
+    GasPrice = "eth_gasPrice" => fn gas_price() -> U128;
+    /// Returns the current max priority fee per gas.
+    MaxPriorityFeePerGas = "eth_maxPriorityFeePerGas" => fn max_priority_fee_per_gas() -> U128;
+    /// Returns the current blob base fee.
+    BlobBaseFee = "eth_blobBaseFee" => fn blob_base_fee() -> U128;
+    /// Returns the fee history of a range of blocks.
+    FeeHistory = "eth_feeHistory" => fn fee_history(
+        block_count: U64,
+        newest_block: BlockNumberOrTag,
+        reward_percentiles: Option<Vec<f64>>
+    ) -> FeeHistory;
+    /// Returns the sync status of the node.
+    Syncing = "eth_syncing" => fn syncing() -> SyncStatus;
+    /// Returns the accounts managed by the node.
+    Accounts = "eth_accounts" => fn accounts() -> Vec<Address>;
+    /// Returns the version of the Ethereum wire protocol of the node.
+    ProtocolVersion = "eth_protocolVersion" => fn protocol_version() -> U64;
+    /// Returns the address receiving the mining rewards of the node.
+    Coinbase = "eth_coinbase" => fn coinbase() -> Address;
+    /// Returns whether the node is mining.
+    Mining = "eth_mining" => fn mining() -> bool;
+    /// Returns the number of hashes per second the node is mining with.
+    Hashrate = "eth_hashrate" => fn hashrate() -> U64;
+    /// Returns the balance of an account.
+    GetBalance = "eth_getBalance" => fn get_balance(
+        address: Address,
+        block: Option<BlockId>
+    ) -> U256;
+    /// Returns the nonce of an account.
+    GetTransactionCount = "eth_getTransactionCount" => fn get_transaction_count(
+        address: Address,
+        block: Option<BlockId>
+    ) -> U64;
+    /// Returns the code of an account.
+    GetCode = "eth_getCode" => fn get_code(address: Address, block: Option<BlockId>) -> Bytes;
+    /// Returns the value of a storage slot of an account.
+    GetStorageAt = "eth_getStorageAt" => fn get_storage_at(
+        address: Address,
+        slot: U256,
+        block: Option<BlockId>
+    ) -> B256;
+    /// Returns the account and storage proofs of an account.
+    GetProof = "eth_getProof" => fn get_proof(
+        address: Address,
+        keys: Vec<B256>,
+        block: Option<BlockId>
+    ) -> EIP1186AccountProofResponse;
+    /// Returns a block by its hash.
+    GetBlockByHash = "eth_getBlockByHash" => fn get_block_by_hash(
+        hash: B256,
+        full: bool
+    ) -> Option<Block>;
+    /// Returns a block by its number.
+    GetBlockByNumber = "eth_getBlockByNumber" => fn get_block_by_number(
+        number: BlockNumberOrTag,
+        full: bool
+    ) -> Option<Block>;
+    /// Returns the number of transactions of a block by its hash.
+    GetBlockTransactionCountByHash = "eth_getBlockTransactionCountByHash"
+        => fn get_block_transaction_count_by_hash(hash: B256) -> Option<U64>;
+    /// Returns the number of transactions of a block by its number.
+    GetBlockTransactionCountByNumber = "eth_getBlockTransactionCountByNumber"
+        => fn get_block_transaction_count_by_number(number: BlockNumberOrTag) -> Option<U64>;
+    /// Returns the number of uncles of a block by its hash.
+    GetUncleCountByBlockHash = "eth_getUncleCountByBlockHash"
+        => fn get_uncle_count_by_block_hash(hash: B256) -> Option<U64>;
+    /// Returns the number of uncles of a block by its number.
+    GetUncleCountByBlockNumber = "eth_getUncleCountByBlockNumber"
+        => fn get_uncle_count_by_block_number(number: BlockNumberOrTag) -> Option<U64>;
+    /// Returns the receipts of a block.
+    GetBlockReceipts = "eth_getBlockReceipts" => fn get_block_receipts(
+        block: BlockId
+    ) -> Option<Vec<TransactionReceipt>>;
+    /// Returns a transaction by its hash.
+    GetTransactionByHash = "eth_getTransactionByHash" => fn get_transaction_by_hash(
+        hash: B256
+    ) -> Option<Transaction>;
+    /// Returns a transaction by the hash of its block and its index in the block.
+    GetTransactionByBlockHashAndIndex = "eth_getTransactionByBlockHashAndIndex"
+        => fn get_transaction_by_block_hash_and_index(hash: B256, index: Index)
+        -> Option<Transaction>;
+    /// Returns a transaction by the number of its block and its index in the block.
+    GetTransactionByBlockNumberAndIndex = "eth_getTransactionByBlockNumberAndIndex"
+        => fn get_transaction_by_block_number_and_index(number: BlockNumberOrTag, index: Index)
+        -> Option<Transaction>;
+    /// Returns the receipt of a transaction.
+    GetTransactionReceipt = "eth_getTransactionReceipt" => fn get_transaction_receipt(
+        hash: B256
+    ) -> Option<TransactionReceipt>;
+    /// Executes a call without creating a transaction.
+    Call = "eth_call" => fn call(
+        request: Box<TransactionRequest>,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>
+    ) -> Bytes;
+    /// Estimates the gas a transaction uses.
+    EstimateGas = "eth_estimateGas" => fn estimate_gas(
+        request: Box<TransactionRequest>,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>
+    ) -> U64;
+    /// Creates the access list of a transaction.
+    CreateAccessList = "eth_createAccessList" => fn create_access_list(
+        request: Box<TransactionRequest>,
+        block: Option<BlockId>
+    ) -> AccessListResult;
+    /// Returns the logs matching a filter.
+    GetLogs = "eth_getLogs" => fn get_logs(filter: Box<Filter>) -> Vec<Log>;
+    /// Installs a log filter, returning its ID.
+    NewFilter = "eth_newFilter" => fn new_filter(filter: Box<Filter>) -> U256;
+    /// Installs a block filter, returning its ID.
+    NewBlockFilter = "eth_newBlockFilter" => fn new_block_filter() -> U256;
+    /// Installs a pending transaction filter, returning its ID.
+    NewPendingTransactionFilter = "eth_newPendingTransactionFilter"
+        => fn new_pending_transaction_filter() -> U256;
+    /// Returns the changes of a filter since it was last polled.
+    GetFilterChanges = "eth_getFilterChanges" => fn get_filter_changes(id: U256) -> FilterChanges;
+    /// Returns all logs matching a log filter.
+    GetFilterLogs = "eth_getFilterLogs" => fn get_filter_logs(id: U256) -> Vec<Log>;
+    /// Uninstalls a filter, returning whether it was installed.
+    UninstallFilter = "eth_uninstallFilter" => fn uninstall_filter(id: U256) -> bool;
+    /// Submits a signed transaction, returning its hash.
+    SendRawTransaction = "eth_sendRawTransaction" => fn send_raw_transaction(bytes: Bytes) -> B256;
+    /// Signs and submits a transaction with an account managed by the node, returning its hash.
+    SendTransaction = "eth_sendTransaction" => fn send_transaction(
+        request: Box<TransactionRequest>
+    ) -> B256;
+    /// Signs a transaction with an account managed by the node.
+    SignTransaction = "eth_signTransaction" => fn sign_transaction(
+        request: Box<TransactionRequest>
+    ) -> Bytes;
+    /// Signs a message with an account managed by the node.
+    Sign = "eth_sign" => fn sign(address: Address, message: Bytes) -> Bytes;
+}
+
+rpc_namespace! {
+    /// A request of the `net` namespace.
+    NetRequest,
+    /// A handler of the requests of the `net` namespace.
+    NetHandler;
+
+    /// Returns the network ID.
+    Version = "net_version" => fn version() -> String;
+    /// Returns whether the node is listening for network connections.
+    Listening = "net_listening" => fn listening() -> bool;
+    /// Returns the number of connected peers.
+    PeerCount = "net_peerCount" => fn peer_count() -> U64;
+}
+
+rpc_namespace! {
+    /// A request of the `web3` namespace.
+    Web3Request,
+    /// A handler of the requests of the `web3` namespace.
+    Web3Handler;
+
+    /// Returns the version of the client.
+    ClientVersion = "web3_clientVersion" => fn client_version() -> String;
+    /// Returns the Keccak-256 hash of the data.
+    Sha3 = "web3_sha3" => fn sha3(data: Bytes) -> B256;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, keccak256};
+
+    #[test]
+    fn decode_requests() {
+        let request: EthRequest = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":1,"method":"eth_getBalance","params":["0x0000000000000000000000000000000000000001"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            EthRequest::GetBalance {
+                address: address!("0000000000000000000000000000000000000001"),
+                block: None
+            }
+        );
+        assert_eq!(request.method(), "eth_getBalance");
+
+        let request =
+            EthRequest::from_parts("eth_getBlockByNumber", serde_json::json!(["latest", false]))
+                .unwrap();
+        assert_eq!(
+            request,
+            EthRequest::GetBlockByNumber { number: BlockNumberOrTag::Latest, full: false }
+        );
+        assert_eq!(
+            EthRequest::from_parts("eth_blockNumber", Value::Null).unwrap(),
+            EthRequest::BlockNumber {}
+        );
+        assert!(EthRequest::METHODS.contains(&"eth_call"));
+
+        assert!(matches!(
+            EthRequest::from_parts("eth_foo", Value::Null),
+            Err(ServerError::UnknownMethod(method)) if method == "eth_foo"
+        ));
+        assert!(matches!(
+            EthRequest::from_parts("eth_getBlockByNumber", serde_json::json!(["latest"])),
+            Err(ServerError::InvalidParams { method: "eth_getBlockByNumber", .. })
+        ));
+        assert!(matches!(
+            EthRequest::from_parts("eth_chainId", serde_json::json!([1])),
+            Err(ServerError::InvalidParams { method: "eth_chainId", .. })
+        ));
+        assert!(serde_json::from_str::<NetRequest>(r#"{"method":"eth_chainId"}"#).is_err());
+    }
+
+    struct Web3;
+
+    impl Web3Handler for Web3 {
+        type Error = ServerError;
+
+        async fn sha3(&self, data: Bytes) -> Result<B256, Self::Error> {
+            Ok(keccak256(data))
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatch_requests() {
+        let request = Web3Request::from_parts("web3_sha3", serde_json::json!(["0x"])).unwrap();
+        assert_eq!(
+            Web3.dispatch(request).await.unwrap(),
+            serde_json::to_value(keccak256([])).unwrap()
+        );
+
+        let err = Web3.dispatch(Web3Request::ClientVersion {}).await.unwrap_err();
+        assert!(matches!(err, ServerError::UnsupportedMethod("web3_clientVersion")));
+        assert_eq!(err.code(), -32601);
+    }
+}
@@ -1,8 +1,11 @@
 //! 'eth_simulateV1' Request / Response types: <https://github.com/ethereum/execution-apis/pull/484>
 
-use crate::{state::StateOverride, Block, BlockOverrides, Log, TransactionRequest};
-use alloc::{string::String, vec::Vec};
-use alloy_primitives::Bytes;
+use crate::{
+    state::StateOverride, Block, BlockOverrides, BlockTransactions, Log, Transaction,
+    TransactionRequest,
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloy_primitives::{address, b256, Address, Bytes, B256, I256, U256};
 
 /// The maximum number of blocks that can be simulated in a single request,
 pub const MAX_SIMULATE_BLOCKS: u64 = 256;
@@ -152,12 +155,250 @@ pub struct SimulateError {
     pub message: String,
 }
 
+/// The address `eth_simulateV1` uses as the emitter of the `Transfer` logs
+/// it creates for ether transfers, if `traceTransfers` is enabled.
+pub const ETH_TRANSFER_LOG_ADDRESS: Address = address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+
+/// The topic of the ERC-20 `Transfer(address,address,uint256)` event.
+const TRANSFER_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// The topic of the ERC-20 `Approval(address,address,uint256)` event.
+const APPROVAL_TOPIC: B256 =
+    b256!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+
+/// An ERC-20 approval granted during a simulation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TokenApproval {
+    /// The token contract.
+    pub token: Address,
+    /// The account granting the approval.
+    pub owner: Address,
+    /// The account allowed to spend the tokens.
+    pub spender: Address,
+    /// The approved amount.
+    pub amount: U256,
+}
+
+/// The asset changes of a simulation, for transaction previews.
+///
+/// The summary is built from the `Transfer` and `Approval` logs and call
+/// results of a simulation, see [`SimulationSummary::from_simulated_blocks`].
+/// Accounts whose balance is unchanged are not included.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SimulationSummary {
+    /// The ether balance change of each account.
+    pub eth_changes: BTreeMap<Address, I256>,
+    /// The ERC-20 balance changes of each account, by token contract.
+    pub token_changes: BTreeMap<Address, BTreeMap<Address, I256>>,
+    /// The ERC-20 approvals granted, in order.
+    pub approvals: Vec<TokenApproval>,
+    /// The addresses of the contracts created, in order.
+    pub contracts_created: Vec<Address>,
+}
+
+impl SimulationSummary {
+    /// Creates a summary of the results of an `eth_simulateV1` call.
+    ///
+    /// Ether transfers are only included if the simulation was run with
+    /// [`SimulatePayload::with_trace_transfers`], and created contracts only
+    /// if it was run with [`SimulatePayload::with_full_transactions`].
+    /// Failed calls are skipped.
+    pub fn from_simulated_blocks<'a>(
+        blocks: impl IntoIterator<Item = &'a SimulatedBlock<Block<Transaction>>>,
+    ) -> Self {
+        let mut summary = Self::default();
+        for block in blocks {
+            let transactions = match &block.inner.transactions {
+                BlockTransactions::Full(transactions) => transactions.as_slice(),
+                _ => &[],
+            };
+            for (i, call) in block.calls.iter().enumerate() {
+                if !call.status {
+                    continue;
+                }
+                for log in &call.logs {
+                    summary.record_log(&log.inner);
+                }
+                if let Some(tx) = transactions.get(i).filter(|tx| tx.to.is_none()) {
+                    summary.record_contract_created(tx.from.create(tx.nonce));
+                }
+            }
+        }
+        summary
+    }
+
+    /// Records an ether transfer.
+    pub fn record_eth_transfer(&mut self, from: Address, to: Address, value: U256) {
+        if from == to || value.is_zero() {
+            return;
+        }
+        let value = I256::try_from(value).unwrap_or(I256::MAX);
+        apply_change(&mut self.eth_changes, from, -value);
+        apply_change(&mut self.eth_changes, to, value);
+    }
+
+    /// Records an ERC-20 token transfer.
+    pub fn record_token_transfer(
+        &mut self,
+        token: Address,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) {
+        if from == to || value.is_zero() {
+            return;
+        }
+        let value = I256::try_from(value).unwrap_or(I256::MAX);
+        for (account, change) in [(from, -value), (to, value)] {
+            let changes = self.token_changes.entry(account).or_default();
+            apply_change(changes, token, change);
+            if changes.is_empty() {
+                self.token_changes.remove(&account);
+            }
+        }
+    }
+
+    /// Records an ERC-20 approval.
+    pub fn record_approval(&mut self, approval: TokenApproval) {
+        self.approvals.push(approval);
+    }
+
+    /// Records a created contract.
+    pub fn record_contract_created(&mut self, address: Address) {
+        self.contracts_created.push(address);
+    }
+
+    /// Records the asset changes of a log, if it is an ERC-20 `Transfer` or
+    /// `Approval` event, or an ether transfer log emitted by `eth_simulateV1`.
+    ///
+    /// Logs of other events, including ERC-721 transfers, are ignored.
+    pub fn record_log(&mut self, log: &alloy_primitives::Log) {
+        let [topic, from, to] = log.topics() else { return };
+        let Ok(value) = <[u8; 32]>::try_from(log.data.data.as_ref()) else { return };
+        let (from, to, value) =
+            (Address::from_word(*from), Address::from_word(*to), U256::from_be_bytes(value));
+
+        match *topic {
+            TRANSFER_TOPIC if log.address == ETH_TRANSFER_LOG_ADDRESS => {
+                self.record_eth_transfer(from, to, value)
+            }
+            TRANSFER_TOPIC => self.record_token_transfer(log.address, from, to, value),
+            APPROVAL_TOPIC => self.record_approval(TokenApproval {
+                token: log.address,
+                owner: from,
+                spender: to,
+                amount: value,
+            }),
+            _ => {}
+        }
+    }
+
+    /// Returns the ether balance change of the account.
+    pub fn eth_change(&self, account: Address) -> I256 {
+        self.eth_changes.get(&account).copied().unwrap_or_default()
+    }
+
+    /// Returns the ERC-20 balance change of the account for the token.
+    pub fn token_change(&self, account: Address, token: Address) -> I256 {
+        self.token_changes
+            .get(&account)
+            .and_then(|changes| changes.get(&token))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the simulation changed no balances, granted no
+    /// approvals and created no contracts.
+    pub fn is_empty(&self) -> bool {
+        self.eth_changes.is_empty()
+            && self.token_changes.is_empty()
+            && self.approvals.is_empty()
+            && self.contracts_created.is_empty()
+    }
+}
+
+/// Adds the change to the entry of the key, removing it if it nets to zero.
+fn apply_change(changes: &mut BTreeMap<Address, I256>, key: Address, change: I256) {
+    let entry = changes.entry(key).or_default();
+    *entry = entry.saturating_add(change);
+    if entry.is_zero() {
+        changes.remove(&key);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::{Address, TxKind};
+    use alloy_primitives::{LogData, TxKind};
     use serde_json::json;
 
+    fn transfer_log(emitter: Address, topic: B256, from: Address, to: Address, value: u64) -> Log {
+        let data = LogData::new_unchecked(
+            vec![topic, from.into_word(), to.into_word()],
+            U256::from(value).to_be_bytes_vec().into(),
+        );
+        Log { inner: alloy_primitives::Log { address: emitter, data }, ..Default::default() }
+    }
+
+    #[test]
+    fn summarizes_simulated_blocks() {
+        let alice = Address::with_last_byte(1);
+        let bob = Address::with_last_byte(2);
+        let token = Address::with_last_byte(3);
+
+        let call = |logs| SimCallResult {
+            return_data: Bytes::new(),
+            logs,
+            gas_used: 21000,
+            status: true,
+            error: None,
+        };
+        let deploy = Transaction { from: alice, nonce: 7, to: None, ..Default::default() };
+        let block = SimulatedBlock {
+            inner: Block {
+                transactions: BlockTransactions::Full(vec![
+                    Transaction { from: alice, to: Some(token), ..Default::default() },
+                    deploy,
+                ]),
+                ..Default::default()
+            },
+            calls: vec![
+                call(vec![
+                    transfer_log(ETH_TRANSFER_LOG_ADDRESS, TRANSFER_TOPIC, alice, bob, 100),
+                    transfer_log(token, TRANSFER_TOPIC, alice, bob, 5),
+                    transfer_log(token, TRANSFER_TOPIC, bob, alice, 2),
+                    transfer_log(token, APPROVAL_TOPIC, alice, bob, 10),
+                ]),
+                call(vec![]),
+                SimCallResult {
+                    status: false,
+                    ..call(vec![transfer_log(token, TRANSFER_TOPIC, bob, alice, 3)])
+                },
+            ],
+        };
+
+        let summary = SimulationSummary::from_simulated_blocks([&block]);
+        assert_eq!(summary.eth_change(alice), I256::try_from(-100).unwrap());
+        assert_eq!(summary.eth_change(bob), I256::try_from(100).unwrap());
+        assert_eq!(summary.token_change(alice, token), I256::try_from(-3).unwrap());
+        assert_eq!(summary.token_change(bob, token), I256::try_from(3).unwrap());
+        assert_eq!(
+            summary.approvals,
+            [TokenApproval { token, owner: alice, spender: bob, amount: U256::from(10) }]
+        );
+        assert_eq!(summary.contracts_created, [alice.create(7)]);
+
+        let mut summary = SimulationSummary::default();
+        summary.record_token_transfer(token, alice, bob, U256::from(1));
+        summary.record_token_transfer(token, bob, alice, U256::from(1));
+        assert!(summary.is_empty());
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_eth_simulate_v1_account_not_precompile() {
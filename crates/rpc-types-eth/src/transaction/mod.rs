@@ -28,6 +28,9 @@ pub use receipt::TransactionReceipt;
 #[cfg(feature = "serde")]
 pub use receipt::AnyTransactionReceipt;
 
+mod policy;
+pub use policy::{TxTypePolicy, TxTypeSelectionError};
+
 pub mod request;
 pub use request::{TransactionInput, TransactionRequest};
 
@@ -572,4 +575,26 @@ mod tests {
         assert!(request.gas_price.is_none());
         assert!(request.max_fee_per_gas.is_some());
     }
+
+    #[test]
+    fn max_gas_cost() {
+        use alloy_network_primitives::TransactionResponse;
+
+        let legacy = Transaction { gas: 21000, gas_price: Some(10), ..Default::default() };
+        assert_eq!(legacy.max_gas_cost(), U256::from(210000));
+        assert_eq!(legacy.gas_u128(), 21000);
+
+        let blob = Transaction {
+            gas: 21000,
+            gas_price: Some(10),
+            max_fee_per_gas: Some(u128::MAX),
+            max_fee_per_blob_gas: Some(3),
+            blob_versioned_hashes: Some(vec![B256::ZERO; 2]),
+            ..Default::default()
+        };
+        assert_eq!(
+            blob.max_gas_cost(),
+            U256::from(21000) * U256::from(u128::MAX) + U256::from(2 * 131072 * 3)
+        );
+    }
 }
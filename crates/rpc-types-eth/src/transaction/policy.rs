@@ -0,0 +1,186 @@
+//! Selection of the type of transaction a [`TransactionRequest`] is built as.
+
+use crate::TransactionRequest;
+use alloy_chains::Chain;
+use alloy_consensus::TxType;
+
+/// A policy controlling which type of transaction a [`TransactionRequest`] is built as, based on
+/// the transaction types the chain supports.
+///
+/// The type is the [`TransactionRequest::preferred_type`], unless the request is ambiguous, i.e.
+/// it would be built as EIP-1559 without setting the max fee per gas or the max priority fee per
+/// gas. Ambiguous requests are built as EIP-1559 if the chain supports it, or else as EIP-2930 if
+/// the access list is set and the chain supports it, or else as legacy.
+///
+/// Selecting a type the chain does not support is an error, rather than silently dropping the
+/// fields of the request that need it.
+///
+/// The transaction builders of `alloy-network` apply the policy of the chain of the request, see
+/// [`TransactionRequest::tx_type_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TxTypePolicy {
+    /// Whether the chain supports EIP-2930 access list transactions.
+    pub eip2930: bool,
+    /// Whether the chain supports EIP-1559 fee market transactions.
+    pub eip1559: bool,
+    /// Whether the chain supports EIP-4844 blob transactions.
+    pub eip4844: bool,
+    /// Whether the chain supports EIP-7702 set code transactions.
+    pub eip7702: bool,
+}
+
+impl Default for TxTypePolicy {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl TxTypePolicy {
+    /// A policy for chains that support all transaction types.
+    pub const fn all() -> Self {
+        Self { eip2930: true, eip1559: true, eip4844: true, eip7702: true }
+    }
+
+    /// A policy for chains that only support legacy transactions.
+    pub const fn legacy() -> Self {
+        Self { eip2930: false, eip1559: false, eip4844: false, eip7702: false }
+    }
+
+    /// Returns the policy for the chain, based on the capabilities known to the chain registry.
+    ///
+    /// Chains known not to support EIP-1559 only support legacy transactions, and only Ethereum
+    /// chains support blob transactions. Unknown chains are assumed to support all types.
+    pub const fn for_chain(chain: Chain) -> Self {
+        match chain.named() {
+            Some(named) if named.is_legacy() => Self::legacy(),
+            Some(named) => Self { eip4844: named.is_ethereum(), ..Self::all() },
+            None => Self::all(),
+        }
+    }
+
+    /// Returns `true` if the chain supports the transaction type.
+    pub const fn supports(&self, ty: TxType) -> bool {
+        match ty {
+            TxType::Legacy => true,
+            TxType::Eip2930 => self.eip2930,
+            TxType::Eip1559 => self.eip1559,
+            TxType::Eip4844 => self.eip4844,
+            TxType::Eip7702 => self.eip7702,
+        }
+    }
+
+    /// Selects the type of transaction the request is built as.
+    pub fn select(&self, request: &TransactionRequest) -> Result<TxType, TxTypeSelectionError> {
+        let ty = match request.transaction_type {
+            Some(ty) => TxType::try_from(ty).map_err(|_| TxTypeSelectionError::UnknownType(ty))?,
+            None => self.infer(request),
+        };
+        if !self.supports(ty) {
+            return Err(TxTypeSelectionError::Unsupported(ty));
+        }
+        Ok(ty)
+    }
+
+    const fn infer(&self, request: &TransactionRequest) -> TxType {
+        let preferred = request.preferred_type();
+        let ambiguous = matches!(preferred, TxType::Eip1559)
+            && request.max_fee_per_gas.is_none()
+            && request.max_priority_fee_per_gas.is_none();
+        if !ambiguous || self.eip1559 {
+            preferred
+        } else if request.access_list.is_some() && self.eip2930 {
+            TxType::Eip2930
+        } else {
+            TxType::Legacy
+        }
+    }
+}
+
+/// An error returned when a [`TxTypePolicy`] cannot select a transaction type for a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, derive_more::Display)]
+pub enum TxTypeSelectionError {
+    /// The request needs a transaction type the chain does not support.
+    #[display("transaction type {_0} is not supported by the chain")]
+    Unsupported(TxType),
+    /// The request sets an unknown transaction type.
+    #[display("unknown transaction type {_0}")]
+    UnknownType(u8),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TxTypeSelectionError {}
+
+impl TransactionRequest {
+    /// Returns the policy of the chain of the request, see [`TxTypePolicy::for_chain`], or a
+    /// policy supporting all types if the chain ID is not set.
+    pub fn tx_type_policy(&self) -> TxTypePolicy {
+        self.chain_id
+            .map_or_else(TxTypePolicy::all, |chain_id| TxTypePolicy::for_chain(chain_id.into()))
+    }
+
+    /// Selects the type of transaction the request is built as with the policy, and sets it as
+    /// the [`transaction_type`](Self::transaction_type) of the request.
+    ///
+    /// The builders then build the request as the selected type, see
+    /// [`TransactionRequest::preferred_type`].
+    pub fn apply_tx_type_policy(
+        &mut self,
+        policy: &TxTypePolicy,
+    ) -> Result<TxType, TxTypeSelectionError> {
+        let ty = policy.select(self)?;
+        self.transaction_type = Some(ty as u8);
+        Ok(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_chains::NamedChain;
+    use alloy_eips::eip2930::AccessList;
+
+    #[test]
+    fn selects_supported_types() {
+        let empty = TransactionRequest::default();
+        assert_eq!(TxTypePolicy::all().select(&empty), Ok(TxType::Eip1559));
+        assert_eq!(TxTypePolicy::legacy().select(&empty), Ok(TxType::Legacy));
+
+        let with_access_list = TransactionRequest::default().access_list(AccessList::default());
+        let policy = TxTypePolicy { eip2930: true, ..TxTypePolicy::legacy() };
+        assert_eq!(policy.select(&with_access_list), Ok(TxType::Eip2930));
+
+        let fee_market = TransactionRequest::default().max_fee_per_gas(1);
+        assert_eq!(
+            TxTypePolicy::legacy().select(&fee_market),
+            Err(TxTypeSelectionError::Unsupported(TxType::Eip1559))
+        );
+
+        let explicit = TransactionRequest::default().transaction_type(0).max_fee_per_gas(1);
+        assert_eq!(TxTypePolicy::all().select(&explicit), Ok(TxType::Legacy));
+        assert_eq!(
+            TxTypePolicy::all().select(&TransactionRequest::default().transaction_type(0x7e)),
+            Err(TxTypeSelectionError::UnknownType(0x7e))
+        );
+    }
+
+    #[test]
+    fn chain_policies() {
+        assert_eq!(TxTypePolicy::for_chain(NamedChain::Mainnet.into()), TxTypePolicy::all());
+        assert_eq!(TxTypePolicy::for_chain(NamedChain::Celo.into()), TxTypePolicy::legacy());
+        assert!(!TxTypePolicy::for_chain(NamedChain::Optimism.into()).eip4844);
+        assert_eq!(TxTypePolicy::for_chain(Chain::from_id(123_456_789)), TxTypePolicy::all());
+    }
+
+    #[test]
+    fn applied_type_is_preferred() {
+        let mut request = TransactionRequest::default().nonce(0).gas_limit(21_000);
+        assert_eq!(request.preferred_type(), TxType::Eip1559);
+        assert_eq!(request.tx_type_policy(), TxTypePolicy::all());
+        request.chain_id = Some(NamedChain::Celo as u64);
+        assert_eq!(request.tx_type_policy(), TxTypePolicy::legacy());
+
+        request.apply_tx_type_policy(&TxTypePolicy::legacy()).unwrap();
+        assert_eq!(request.transaction_type, Some(0));
+        assert_eq!(request.preferred_type(), TxType::Legacy);
+    }
+}
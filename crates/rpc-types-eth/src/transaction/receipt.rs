@@ -115,6 +115,14 @@ impl TransactionReceipt {
     }
 }
 
+impl From<TransactionReceipt> for ReceiptEnvelope<alloy_primitives::Log> {
+    /// Converts the receipt into its consensus representation, dropping the RPC metadata of the
+    /// receipt and its logs.
+    fn from(receipt: TransactionReceipt) -> Self {
+        receipt.inner.map_logs(|log| log.inner)
+    }
+}
+
 impl<T> TransactionReceipt<T> {
     /// Maps the inner receipt value of this receipt.
     pub fn map_inner<U, F>(self, f: F) -> TransactionReceipt<U>
@@ -213,7 +221,7 @@ mod test {
     use super::*;
     use crate::TransactionReceipt;
     use alloy_consensus::{Eip658Value, Receipt, ReceiptWithBloom};
-    use alloy_primitives::{address, b256, bloom, Bloom};
+    use alloy_primitives::{address, b256, bloom, Bloom, U256};
     use arbitrary::Arbitrary;
     use rand::Rng;
 
@@ -226,6 +234,90 @@ mod test {
             TransactionReceipt::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap();
     }
 
+    #[test]
+    fn receipt_status_pre_byzantium() {
+        use alloy_network_primitives::{ReceiptResponse, ReceiptStatus};
+
+        let root = b256!("8c8a3b0b0c4b1a9bd2b2f63f60f4ad3b1e0d6c4d5b9a5d8c1f0e6f36e4e2d1a0");
+        let mut receipt = TransactionReceipt {
+            inner: ReceiptEnvelope::Legacy(ReceiptWithBloom {
+                receipt: Receipt {
+                    status: Eip658Value::PostState(root),
+                    cumulative_gas_used: 21000,
+                    logs: vec![],
+                },
+                logs_bloom: Bloom::ZERO,
+            }),
+            transaction_hash: B256::ZERO,
+            transaction_index: Some(0),
+            block_hash: Some(B256::ZERO),
+            block_number: Some(4_000_000),
+            gas_used: 21000,
+            effective_gas_price: 20_000_000_000,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::ZERO,
+            to: Some(Address::ZERO),
+            contract_address: None,
+            state_root: Some(root),
+            authorization_list: None,
+        };
+        // the coerced status reports success
+        assert!(ReceiptResponse::status(&receipt));
+        assert_eq!(receipt.receipt_status(), ReceiptStatus::Unknown(root));
+        assert_eq!(receipt.receipt_status().as_bool(), None);
+
+        receipt.state_root = None;
+        receipt.inner = ReceiptEnvelope::Legacy(ReceiptWithBloom {
+            receipt: Receipt {
+                status: Eip658Value::Eip658(false),
+                cumulative_gas_used: 0,
+                logs: vec![],
+            },
+            logs_bloom: Bloom::ZERO,
+        });
+        assert_eq!(receipt.receipt_status(), ReceiptStatus::Failure);
+    }
+
+    #[test]
+    fn checked_gas_accessors() {
+        let mut receipt = TransactionReceipt {
+            inner: ReceiptEnvelope::Eip4844(ReceiptWithBloom {
+                receipt: Receipt {
+                    status: Eip658Value::Eip658(true),
+                    cumulative_gas_used: u64::MAX as u128 + 1,
+                    logs: vec![],
+                },
+                logs_bloom: Bloom::ZERO,
+            }),
+            transaction_hash: B256::ZERO,
+            transaction_index: Some(0),
+            block_hash: Some(B256::ZERO),
+            block_number: Some(1),
+            gas_used: 21000,
+            effective_gas_price: 10,
+            blob_gas_used: Some(131072),
+            blob_gas_price: Some(2),
+            from: Address::ZERO,
+            to: Some(Address::ZERO),
+            contract_address: None,
+            state_root: None,
+            authorization_list: None,
+        };
+        assert_eq!(receipt.gas_used_u64(), Some(21000));
+        assert_eq!(receipt.cumulative_gas_used_u64(), None);
+        assert_eq!(receipt.blob_gas_used_u64(), Some(131072));
+        assert_eq!(receipt.gas_cost(), U256::from(21000 * 10 + 131072 * 2));
+
+        receipt.gas_used = u128::MAX;
+        receipt.effective_gas_price = u128::MAX;
+        assert_eq!(receipt.gas_used_u64(), None);
+        assert_eq!(
+            receipt.gas_cost(),
+            U256::from(u128::MAX) * U256::from(u128::MAX) + U256::from(262144)
+        );
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_sanity() {
@@ -344,6 +436,11 @@ mod test {
         assert_eq!(other.l1_fee, "0x105d4b2024");
         assert_eq!(other.l1_gas_price, "0x5d749a07e");
         assert_eq!(other.l1_gas_used, "0x800");
+
+        let err = serde_json::from_str::<alloy_serde::Strict<TransactionReceipt>>(receipt_json)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("unknown fields: $.l1BaseFeeScalar, "), "{err}");
+        serde_json::from_str::<alloy_serde::Strict<AnyTransactionReceipt>>(receipt_json).unwrap();
     }
 
     #[test]
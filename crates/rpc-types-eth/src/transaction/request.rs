@@ -469,12 +469,25 @@ impl TransactionRequest {
     /// Check this builder's preferred type, based on the fields that are set.
     ///
     /// Types are preferred as follows:
+    /// - the type set in `transaction_type`, if it is a known type
     /// - EIP-7702 if authorization_list is set
     /// - EIP-4844 if sidecar or max_blob_fee_per_gas is set
     /// - EIP-2930 if access_list is set
     /// - Legacy if gas_price is set and access_list is unset
     /// - EIP-1559 in all other cases
+    ///
+    /// To select the type based on the transaction types the chain supports, see
+    /// [`TxTypePolicy`](crate::TxTypePolicy).
     pub const fn preferred_type(&self) -> TxType {
+        match self.transaction_type {
+            Some(0) => return TxType::Legacy,
+            Some(1) => return TxType::Eip2930,
+            Some(2) => return TxType::Eip1559,
+            Some(3) => return TxType::Eip4844,
+            Some(4) => return TxType::Eip7702,
+            _ => {}
+        }
+
         if self.authorization_list.is_some() {
             TxType::Eip7702
         } else if self.sidecar.is_some() || self.max_fee_per_blob_gas.is_some() {
@@ -517,10 +530,6 @@ impl TransactionRequest {
         let mut missing = self.check_reqd_fields();
         self.check_1559_fields(&mut missing);
 
-        if self.to.is_none() {
-            missing.push("to");
-        }
-
         if self.sidecar.is_none() {
             missing.push("sidecar");
         }
@@ -0,0 +1,301 @@
+//! Gas usage reports, aggregated per contract and function from receipts and traces.
+
+use crate::geth::CallFrame;
+use alloy_primitives::{Address, Selector};
+use alloy_rpc_types_eth::{Transaction, TransactionReceipt};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Write};
+
+/// The function of a contract that gas was used for in a [`GasReport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GasReportFunction {
+    /// The deployment of the contract.
+    Deployment,
+    /// A call with calldata shorter than a selector, which is handled by the fallback or receive
+    /// function.
+    Fallback,
+    /// A call of the function with the selector.
+    Selector(Selector),
+}
+
+impl GasReportFunction {
+    /// Returns the function called with the calldata.
+    pub fn from_input(input: &[u8]) -> Self {
+        input
+            .get(..4)
+            .map_or(Self::Fallback, |selector| Self::Selector(Selector::from_slice(selector)))
+    }
+}
+
+/// Statistics of the gas used by the calls of a function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasStats {
+    /// The number of calls.
+    pub calls: u64,
+    /// The lowest gas used by a call.
+    pub min: u64,
+    /// The highest gas used by a call.
+    pub max: u64,
+    /// The total gas used by all calls.
+    pub total: u64,
+}
+
+impl GasStats {
+    /// Records the gas used by a call.
+    pub fn record(&mut self, gas_used: u64) {
+        self.min = if self.calls == 0 { gas_used } else { self.min.min(gas_used) };
+        self.max = self.max.max(gas_used);
+        self.total = self.total.saturating_add(gas_used);
+        self.calls += 1;
+    }
+
+    /// Returns the mean gas used per call, rounded down.
+    pub const fn mean(&self) -> u64 {
+        match self.total.checked_div(self.calls) {
+            Some(mean) => mean,
+            None => 0,
+        }
+    }
+}
+
+/// An entry of a [`GasReport`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasReportEntry {
+    /// The contract.
+    pub contract: Address,
+    /// The label of the contract, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_label: Option<String>,
+    /// The function of the contract.
+    pub function: GasReportFunction,
+    /// The signature of the function, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// The gas used by the calls of the function.
+    #[serde(flatten)]
+    pub stats: GasStats,
+}
+
+/// Aggregates the gas used per contract and function across transactions and traces.
+///
+/// Gas is recorded from receipts with [`GasReport::add_receipt`], which includes the intrinsic
+/// gas of the transactions, or from `callTracer` traces with [`GasReport::add_call_frame`], which
+/// also records the internal calls. The gas used by a traced call includes the gas used by its
+/// sub-calls.
+///
+/// The report can be rendered as a markdown table with [`GasReport::to_markdown`], or serialized
+/// as a list of [`GasReportEntry`]s.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasReport {
+    stats: BTreeMap<(Address, GasReportFunction), GasStats>,
+    labels: BTreeMap<Address, String>,
+    signatures: BTreeMap<Selector, String>,
+}
+
+impl GasReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Labels the contract in the report, e.g. with its name.
+    pub fn with_label(mut self, contract: Address, label: impl Into<String>) -> Self {
+        self.labels.insert(contract, label.into());
+        self
+    }
+
+    /// Names the function with the selector in the report, e.g. with its signature.
+    pub fn with_signature(mut self, selector: Selector, signature: impl Into<String>) -> Self {
+        self.signatures.insert(selector, signature.into());
+        self
+    }
+
+    /// Records the gas used by a call of the contract.
+    pub fn record(&mut self, contract: Address, function: GasReportFunction, gas_used: u64) {
+        self.stats.entry((contract, function)).or_default().record(gas_used);
+    }
+
+    /// Records the gas used by a transaction, as reported by its receipt.
+    ///
+    /// Transactions that neither call nor create a contract are skipped.
+    pub fn add_receipt<T>(&mut self, tx: &Transaction, receipt: &TransactionReceipt<T>) {
+        let gas_used = receipt.gas_used.try_into().unwrap_or(u64::MAX);
+        match (receipt.to, receipt.contract_address) {
+            (Some(to), _) => self.record(to, GasReportFunction::from_input(&tx.input), gas_used),
+            (None, Some(contract)) => {
+                self.record(contract, GasReportFunction::Deployment, gas_used)
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Records the gas used by the call of the trace and all of its sub-calls.
+    pub fn add_call_frame(&mut self, frame: &CallFrame) {
+        if let Some(to) = frame.to {
+            let function = match frame.typ.as_str() {
+                "CREATE" | "CREATE2" => GasReportFunction::Deployment,
+                _ => GasReportFunction::from_input(&frame.input),
+            };
+            self.record(to, function, frame.gas_used.saturating_to());
+        }
+        for call in &frame.calls {
+            self.add_call_frame(call);
+        }
+    }
+
+    /// Merges the gas used recorded by another report into this one.
+    pub fn merge(&mut self, other: &Self) {
+        for (key, other) in &other.stats {
+            let stats = self.stats.entry(*key).or_default();
+            if stats.calls == 0 {
+                *stats = *other;
+            } else if other.calls > 0 {
+                stats.calls += other.calls;
+                stats.min = stats.min.min(other.min);
+                stats.max = stats.max.max(other.max);
+                stats.total = stats.total.saturating_add(other.total);
+            }
+        }
+        for (contract, label) in &other.labels {
+            self.labels.entry(*contract).or_insert_with(|| label.clone());
+        }
+        for (selector, signature) in &other.signatures {
+            self.signatures.entry(*selector).or_insert_with(|| signature.clone());
+        }
+    }
+
+    /// Returns the statistics of a function of the contract, if it was called.
+    pub fn get(&self, contract: Address, function: GasReportFunction) -> Option<&GasStats> {
+        self.stats.get(&(contract, function))
+    }
+
+    /// Returns `true` if no gas was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    /// Returns the entries of the report, ordered by contract and function.
+    pub fn entries(&self) -> Vec<GasReportEntry> {
+        self.stats
+            .iter()
+            .map(|(&(contract, function), &stats)| GasReportEntry {
+                contract,
+                contract_label: self.labels.get(&contract).cloned(),
+                function,
+                signature: match function {
+                    GasReportFunction::Selector(selector) => {
+                        self.signatures.get(&selector).cloned()
+                    }
+                    _ => None,
+                },
+                stats,
+            })
+            .collect()
+    }
+
+    /// Renders the report as a markdown table.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from(
+            "| Contract | Function | Calls | Min | Mean | Max | Total |\n\
+             |----------|----------|-------|-----|------|-----|-------|\n",
+        );
+        for entry in self.entries() {
+            let contract = entry.contract_label.unwrap_or_else(|| entry.contract.to_string());
+            let function = match (entry.function, entry.signature) {
+                (_, Some(signature)) => signature,
+                (GasReportFunction::Selector(selector), None) => selector.to_string(),
+                (GasReportFunction::Deployment, None) => "(deployment)".to_string(),
+                (GasReportFunction::Fallback, None) => "(fallback)".to_string(),
+            };
+            let GasStats { calls, min, max, total } = entry.stats;
+            let mean = entry.stats.mean();
+            writeln!(
+                out,
+                "| {contract} | {function} | {calls} | {min} | {mean} | {max} | {total} |"
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+impl Serialize for GasReport {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.entries().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{bytes, U256};
+
+    #[test]
+    fn aggregates_calls() {
+        let token = Address::with_last_byte(1);
+        let transfer = Selector::from([0xa9, 0x05, 0x9c, 0xbb]);
+
+        let frame = CallFrame {
+            to: Some(token),
+            input: bytes!("a9059cbb0000"),
+            gas_used: U256::from(50_000),
+            typ: "CALL".to_string(),
+            calls: vec![CallFrame {
+                to: Some(Address::with_last_byte(2)),
+                gas_used: U256::from(100),
+                typ: "CALL".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut report = GasReport::new()
+            .with_label(token, "Token")
+            .with_signature(transfer, "transfer(address,uint256)");
+        report.add_call_frame(&frame);
+        report.record(token, GasReportFunction::Selector(transfer), 30_000);
+
+        let contract = Address::with_last_byte(3);
+        let receipt = TransactionReceipt {
+            inner: (),
+            transaction_hash: Default::default(),
+            transaction_index: None,
+            block_hash: None,
+            block_number: None,
+            gas_used: 1_000_000,
+            effective_gas_price: 0,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::ZERO,
+            to: None,
+            contract_address: Some(contract),
+            state_root: None,
+            authorization_list: None,
+        };
+        report.add_receipt(&Transaction::default(), &receipt);
+
+        let stats = report.get(token, GasReportFunction::Selector(transfer)).unwrap();
+        assert_eq!(*stats, GasStats { calls: 2, min: 30_000, max: 50_000, total: 80_000 });
+        assert_eq!(stats.mean(), 40_000);
+        assert_eq!(report.get(contract, GasReportFunction::Deployment).unwrap().total, 1_000_000);
+
+        let markdown = report.to_markdown();
+        assert!(markdown
+            .contains("| Token | transfer(address,uint256) | 2 | 30000 | 40000 | 50000 | 80000 |"));
+        assert!(markdown.contains("| (fallback) | 1 | 100 | 100 | 100 | 100 |"));
+        assert!(markdown.contains("| (deployment) | 1 |"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 3);
+        assert_eq!(json[0]["contractLabel"], "Token");
+        assert_eq!(json[0]["calls"], 2);
+
+        let mut merged = GasReport::new();
+        merged.merge(&report);
+        merged.merge(&report);
+        assert_eq!(merged.get(token, GasReportFunction::Selector(transfer)).unwrap().calls, 4);
+    }
+}
@@ -1,7 +1,8 @@
 //! Geth call tracer types.
 
 use crate::parity::LocalizedTransactionTrace;
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{Address, Bytes, Log, B256, U256};
+use alloy_rpc_types_eth::simulate::SimulationSummary;
 use serde::{Deserialize, Serialize};
 
 /// The response object for `debug_traceTransaction` with `"tracer": "callTracer"`.
@@ -45,6 +46,52 @@ pub struct CallFrame {
     pub typ: String,
 }
 
+impl CallFrame {
+    /// Returns `true` if the call succeeded.
+    pub const fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Summarizes the asset changes of the call and its successful sub-calls.
+    ///
+    /// Ether transfers and created contracts are taken from the call frames,
+    /// and ERC-20 transfers and approvals from their logs, which are only
+    /// recorded if the trace was run with [`CallConfig::with_log`].
+    pub fn simulation_summary(&self) -> SimulationSummary {
+        let mut summary = SimulationSummary::default();
+        self.record_summary(&mut summary);
+        summary
+    }
+
+    fn record_summary(&self, summary: &mut SimulationSummary) {
+        if !self.is_success() {
+            return;
+        }
+
+        let value = self.value.unwrap_or_default();
+        match (self.typ.as_str(), self.to) {
+            ("CALL" | "SELFDESTRUCT", Some(to)) => {
+                summary.record_eth_transfer(self.from, to, value)
+            }
+            ("CREATE" | "CREATE2", Some(to)) => {
+                summary.record_contract_created(to);
+                summary.record_eth_transfer(self.from, to, value);
+            }
+            _ => {}
+        }
+
+        // logs and sub-calls are recorded in execution order
+        let mut logs = self.logs.iter().peekable();
+        for (i, call) in self.calls.iter().enumerate() {
+            while let Some(log) = logs.next_if(|log| log.position.map_or(true, |p| p <= i as u64)) {
+                log.record_summary(summary);
+            }
+            call.record_summary(summary);
+        }
+        logs.for_each(|log| log.record_summary(summary));
+    }
+}
+
 /// Represents a recorded log that is emitted during a trace call.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CallLogFrame {
@@ -62,6 +109,15 @@ pub struct CallLogFrame {
     pub position: Option<u64>,
 }
 
+impl CallLogFrame {
+    fn record_summary(&self, summary: &mut SimulationSummary) {
+        if let (Some(address), Some(topics)) = (self.address, &self.topics) {
+            let data = self.data.clone().unwrap_or_default();
+            summary.record_log(&Log::new_unchecked(address, topics.clone(), data));
+        }
+    }
+}
+
 /// The configuration for the call tracer.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -156,4 +212,20 @@ mod tests {
         let _trace: CallFrame = serde_json::from_str(ONLY_TOP_CALL).unwrap();
         let _trace: CallFrame = serde_json::from_str(WITH_LOG).unwrap();
     }
+
+    #[test]
+    fn test_simulation_summary() {
+        let trace: CallFrame = serde_json::from_str(WITH_LOG).unwrap();
+        let summary = trace.simulation_summary();
+
+        let token = trace.to.unwrap();
+        let to = "0xdbf03b407c01e7cd3cbea99509d93f8dddc8c6fb".parse().unwrap();
+        assert_eq!(summary.token_change(trace.from, token), "-10000000".parse().unwrap());
+        assert_eq!(summary.token_change(to, token), "10000000".parse().unwrap());
+        assert!(summary.eth_changes.is_empty());
+
+        let mut failed = trace;
+        failed.error = Some("execution reverted".into());
+        assert!(failed.simulation_summary().is_empty());
+    }
 }
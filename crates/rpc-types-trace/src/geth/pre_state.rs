@@ -1,6 +1,7 @@
 //! Pre-state Geth tracer types.
 
 use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::state::{AccountOverride, StateOverride};
 use serde::{Deserialize, Serialize};
 use std::collections::{btree_map, BTreeMap};
 
@@ -48,6 +49,15 @@ impl PreStateFrame {
             _ => None,
         }
     }
+
+    /// Converts the state before the transaction into a [StateOverride], see
+    /// [PreStateMode::to_state_override] and [DiffMode::pre_state_override].
+    pub fn to_state_override(&self) -> StateOverride {
+        match self {
+            Self::Default(mode) => mode.to_state_override(),
+            Self::Diff(mode) => mode.pre_state_override(),
+        }
+    }
 }
 
 /// Includes all the account states necessary to execute a given transaction.
@@ -58,6 +68,17 @@ impl PreStateFrame {
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PreStateMode(pub BTreeMap<Address, AccountState>);
 
+impl PreStateMode {
+    /// Converts the account states into a [StateOverride], which can be used to re-execute the
+    /// transaction with `eth_call` against the same state on a different node or at a different
+    /// block.
+    ///
+    /// See [AccountState::to_account_override].
+    pub fn to_state_override(&self) -> StateOverride {
+        self.0.iter().map(|(address, state)| (*address, state.to_account_override())).collect()
+    }
+}
+
 /// Represents the account states before and after the transaction is executed.
 ///
 /// This corresponds to the [DiffMode] of the [PreStateConfig].
@@ -96,6 +117,14 @@ impl DiffMode {
         self
     }
 
+    /// Converts the pre state into a [StateOverride].
+    ///
+    /// This only includes the accounts changed by the transaction, see
+    /// [PreStateMode::to_state_override] to override all accounts it touched.
+    pub fn pre_state_override(&self) -> StateOverride {
+        self.pre.iter().map(|(address, state)| (*address, state.to_account_override())).collect()
+    }
+
     /// Removes all zero values from the storage of the [AccountState]s.
     pub fn remove_zero_storage_values(&mut self) {
         for state in self.pre.values_mut().chain(self.post.values_mut()) {
@@ -157,6 +186,22 @@ impl AccountState {
         }
     }
 
+    /// Converts the account state into an [AccountOverride].
+    ///
+    /// As the tracer omits zero nonces and empty code, missing values are overridden with zero
+    /// and empty code. The storage slots are overridden individually, so slots not touched by the
+    /// transaction keep their values.
+    pub fn to_account_override(&self) -> AccountOverride {
+        AccountOverride {
+            balance: Some(self.balance.unwrap_or_default()),
+            nonce: Some(self.nonce.unwrap_or_default()),
+            code: Some(self.code.clone().unwrap_or_default()),
+            state_diff: (!self.storage.is_empty())
+                .then(|| self.storage.iter().map(|(slot, value)| (*slot, *value)).collect()),
+            ..Default::default()
+        }
+    }
+
     /// Removes balance,nonce or code if they match the given account info.
     ///
     /// This is useful for comparing pre vs post state and only keep changed values in post state.
@@ -231,6 +276,7 @@ impl PreStateConfig {
 mod tests {
     use super::*;
     use crate::geth::*;
+    use alloy_primitives::address;
 
     // See <https://github.com/ethereum/go-ethereum/tree/master/eth/tracers/internal/tracetest/testdata>
     const DEFAULT: &str = include_str!("../../test_data/pre_state_tracer/default.json");
@@ -267,6 +313,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_to_state_override() {
+        let trace: PreStateFrame = serde_json::from_str(DEFAULT).unwrap();
+        let overrides = trace.to_state_override();
+        assert_eq!(overrides.len(), 4);
+
+        let account = &overrides[&address!("332b656504f4eabb44c8617a42af37461a34e9dc")];
+        assert_eq!(account.balance, Some(U256::from(0x11faea4f35e5af80000u128)));
+        assert_eq!(account.nonce, Some(0));
+        assert_eq!(account.code, Some(Bytes::new()));
+        assert_eq!(account.state, None);
+        assert_eq!(account.state_diff.as_ref().unwrap()[&B256::ZERO], B256::ZERO);
+
+        let account = &overrides[&address!("52bc44d5378309ee2abf1539bf71de1b7d7be3b5")];
+        assert_eq!(account.nonce, Some(28922));
+        assert_eq!(account.state_diff, None);
+
+        let trace: PreStateFrame = serde_json::from_str(DIFF_MODE).unwrap();
+        let diff = trace.as_diff().unwrap();
+        assert_eq!(trace.to_state_override().len(), diff.pre.len());
+    }
+
     #[test]
     fn test_is_diff_mode() {
         assert!(PreStateConfig { diff_mode: Some(true) }.is_diff_mode());
@@ -17,8 +17,10 @@
 
 pub mod common;
 pub mod filter;
+pub mod gas_report;
 pub mod geth;
 pub mod opcode;
 pub mod otterscan;
 pub mod parity;
 pub mod tracerequest;
+pub mod tree;
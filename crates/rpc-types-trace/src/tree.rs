@@ -0,0 +1,458 @@
+//! A navigable call tree, built from `callTracer` or parity traces.
+//!
+//! The nodes of a [`CallFrameTree`] are stored in depth-first order, and
+//! refer to their parent and children by index.
+
+use crate::{
+    geth::CallFrame,
+    parity::{Action, CallType, TraceOutput, TransactionTrace},
+};
+use alloy_primitives::{Address, Bytes, U256};
+
+/// The kind of a call in a [`CallFrameTree`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CallKind {
+    /// A `CALL`.
+    Call,
+    /// A `STATICCALL`.
+    StaticCall,
+    /// A `DELEGATECALL`.
+    DelegateCall,
+    /// A `CALLCODE`.
+    CallCode,
+    /// An `AUTHCALL`.
+    AuthCall,
+    /// A `CREATE`.
+    Create,
+    /// A `CREATE2`.
+    Create2,
+    /// A `SELFDESTRUCT`.
+    Selfdestruct,
+    /// A call of a kind that is not known.
+    Unknown,
+}
+
+impl CallKind {
+    /// Parses the `type` of a geth call frame, e.g. `CALL`.
+    pub fn from_geth(typ: &str) -> Self {
+        match typ {
+            "CALL" => Self::Call,
+            "STATICCALL" => Self::StaticCall,
+            "DELEGATECALL" => Self::DelegateCall,
+            "CALLCODE" => Self::CallCode,
+            "AUTHCALL" => Self::AuthCall,
+            "CREATE" => Self::Create,
+            "CREATE2" => Self::Create2,
+            "SELFDESTRUCT" => Self::Selfdestruct,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Returns `true` if the call creates a contract.
+    pub const fn is_create(&self) -> bool {
+        matches!(self, Self::Create | Self::Create2)
+    }
+
+    /// Returns `true` if the value of the call is transferred from the caller
+    /// to the callee.
+    ///
+    /// The value of delegate calls and `CALLCODE` stays with the caller.
+    pub const fn transfers_value(&self) -> bool {
+        matches!(
+            self,
+            Self::Call | Self::AuthCall | Self::Create | Self::Create2 | Self::Selfdestruct
+        )
+    }
+}
+
+impl From<CallType> for CallKind {
+    fn from(call_type: CallType) -> Self {
+        match call_type {
+            CallType::Call => Self::Call,
+            CallType::CallCode => Self::CallCode,
+            CallType::DelegateCall => Self::DelegateCall,
+            CallType::StaticCall => Self::StaticCall,
+            CallType::AuthCall => Self::AuthCall,
+            CallType::None => Self::Unknown,
+        }
+    }
+}
+
+/// A call in a [`CallFrameTree`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallNode {
+    /// The index of the node in the tree.
+    pub idx: usize,
+    /// The index of the parent node, `None` for the root.
+    pub parent: Option<usize>,
+    /// The indices of the child nodes, in execution order.
+    pub children: Vec<usize>,
+    /// The depth of the call, `0` for the root.
+    pub depth: usize,
+    /// The kind of the call.
+    pub kind: CallKind,
+    /// The caller. For selfdestructs, the destroyed contract.
+    pub from: Address,
+    /// The callee, the created contract, or the beneficiary of a selfdestruct.
+    ///
+    /// `None` if a contract creation failed.
+    pub to: Option<Address>,
+    /// The value of the call.
+    pub value: U256,
+    /// The calldata, or the init code for contract creations.
+    pub input: Bytes,
+    /// The output of the call, if any.
+    pub output: Option<Bytes>,
+    /// The gas used by the call.
+    pub gas_used: u64,
+    /// The error message, if the call failed.
+    pub error: Option<String>,
+}
+
+impl CallNode {
+    /// Returns `true` if the call itself failed.
+    ///
+    /// A successful call is still reverted if one of its ancestors failed, see
+    /// [`CallFrameTree::is_reverted`].
+    pub const fn is_failed(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// A value transfer of a call in a [`CallFrameTree`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValueTransfer {
+    /// The index of the node making the transfer.
+    pub idx: usize,
+    /// The sender.
+    pub from: Address,
+    /// The recipient.
+    pub to: Address,
+    /// The transferred value.
+    pub value: U256,
+}
+
+/// A navigable tree of the calls of a transaction.
+///
+/// Built from a `callTracer` trace with [`CallFrameTree::from_call_frame`],
+/// or from the parity traces of a transaction with
+/// [`CallFrameTree::from_parity_traces`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallFrameTree {
+    nodes: Vec<CallNode>,
+}
+
+impl CallFrameTree {
+    /// Builds the tree of a `callTracer` trace.
+    pub fn from_call_frame(frame: &CallFrame) -> Self {
+        let mut tree = Self { nodes: Vec::new() };
+        tree.push_call_frame(frame, None);
+        tree
+    }
+
+    fn push_call_frame(&mut self, frame: &CallFrame, parent: Option<usize>) {
+        let idx = self.push(
+            parent,
+            CallNode {
+                idx: 0,
+                parent,
+                children: Vec::new(),
+                depth: 0,
+                kind: CallKind::from_geth(&frame.typ),
+                from: frame.from,
+                to: frame.to,
+                value: frame.value.unwrap_or_default(),
+                input: frame.input.clone(),
+                output: frame.output.clone(),
+                gas_used: frame.gas_used.saturating_to(),
+                error: frame.error.clone(),
+            },
+        );
+        for call in &frame.calls {
+            self.push_call_frame(call, Some(idx));
+        }
+    }
+
+    /// Builds the tree from the parity traces of a single transaction, as
+    /// returned by `trace_transaction` or `trace_replayTransaction`.
+    ///
+    /// Reward traces are skipped. Returns `None` if there are no traces, or if
+    /// the trace addresses do not form a single tree.
+    pub fn from_parity_traces<'a>(
+        traces: impl IntoIterator<Item = &'a TransactionTrace>,
+    ) -> Option<Self> {
+        let mut traces = traces
+            .into_iter()
+            .filter(|trace| !matches!(trace.action, Action::Reward(_)))
+            .collect::<Vec<_>>();
+        // depth-first order
+        traces.sort_by(|a, b| a.trace_address.cmp(&b.trace_address));
+
+        let mut tree = Self { nodes: Vec::with_capacity(traces.len()) };
+        // the nodes on the path to the last node, with their trace addresses
+        let mut path: Vec<(usize, &[usize])> = Vec::new();
+        for trace in traces {
+            let depth = trace.trace_address.len();
+            if tree.nodes.is_empty() != (depth == 0) || depth > path.len() {
+                return None;
+            }
+            path.truncate(depth);
+            if path.last().is_some_and(|(_, address)| **address != trace.trace_address[..depth - 1])
+            {
+                return None;
+            }
+            let parent = path.last().map(|(idx, _)| *idx);
+
+            let (kind, from, to, value, input) = match &trace.action {
+                Action::Call(call) => (
+                    call.call_type.into(),
+                    call.from,
+                    Some(call.to),
+                    call.value,
+                    call.input.clone(),
+                ),
+                Action::Create(create) => {
+                    let to = match &trace.result {
+                        Some(TraceOutput::Create(output)) => Some(output.address),
+                        _ => None,
+                    };
+                    (CallKind::Create, create.from, to, create.value, create.init.clone())
+                }
+                Action::Selfdestruct(selfdestruct) => (
+                    CallKind::Selfdestruct,
+                    selfdestruct.address,
+                    Some(selfdestruct.refund_address),
+                    selfdestruct.balance,
+                    Bytes::new(),
+                ),
+                Action::Reward(_) => unreachable!("reward traces are skipped"),
+            };
+            let gas_used = match &trace.result {
+                Some(TraceOutput::Call(output)) => output.gas_used,
+                Some(TraceOutput::Create(output)) => output.gas_used,
+                None => 0,
+            };
+
+            let idx = tree.push(
+                parent,
+                CallNode {
+                    idx: 0,
+                    parent,
+                    children: Vec::new(),
+                    depth: 0,
+                    kind,
+                    from,
+                    to,
+                    value,
+                    input,
+                    output: trace.result.as_ref().map(|result| result.output().clone()),
+                    gas_used,
+                    error: trace.error.clone(),
+                },
+            );
+            path.push((idx, &trace.trace_address));
+        }
+
+        (!tree.nodes.is_empty()).then_some(tree)
+    }
+
+    fn push(&mut self, parent: Option<usize>, mut node: CallNode) -> usize {
+        let idx = self.nodes.len();
+        node.idx = idx;
+        if let Some(parent) = parent {
+            node.depth = self.nodes[parent].depth + 1;
+            self.nodes[parent].children.push(idx);
+        }
+        self.nodes.push(node);
+        idx
+    }
+
+    /// Returns the root call.
+    pub fn root(&self) -> &CallNode {
+        &self.nodes[0]
+    }
+
+    /// Returns the node at the index.
+    pub fn get(&self, idx: usize) -> Option<&CallNode> {
+        self.nodes.get(idx)
+    }
+
+    /// Returns all nodes, in depth-first order.
+    pub fn nodes(&self) -> &[CallNode] {
+        &self.nodes
+    }
+
+    /// Returns the number of calls in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `false`, trees always contain the root call.
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns an iterator over the nodes, in depth-first order.
+    pub fn iter(&self) -> std::slice::Iter<'_, CallNode> {
+        self.nodes.iter()
+    }
+
+    /// Returns an iterator over the children of the node, `None` if there is no node at the
+    /// index.
+    pub fn children(&self, idx: usize) -> Option<impl Iterator<Item = &CallNode> + '_> {
+        let node = self.nodes.get(idx)?;
+        Some(node.children.iter().map(|&child| &self.nodes[child]))
+    }
+
+    /// Returns the parent of the node, `None` for the root or if there is no node at the index.
+    pub fn parent(&self, idx: usize) -> Option<&CallNode> {
+        self.nodes.get(idx)?.parent.map(|parent| &self.nodes[parent])
+    }
+
+    /// Returns an iterator over the ancestors of the node, starting with its
+    /// parent.
+    pub fn ancestors(&self, idx: usize) -> impl Iterator<Item = &CallNode> + '_ {
+        std::iter::successors(self.parent(idx), |node| self.parent(node.idx))
+    }
+
+    /// Returns an iterator over the node and its descendants, in depth-first
+    /// order, `None` if there is no node at the index.
+    pub fn subtree(&self, idx: usize) -> Option<impl Iterator<Item = &CallNode> + '_> {
+        let (node, rest) = self.nodes.get(idx..)?.split_first()?;
+        let depth = node.depth;
+        Some(std::iter::once(node).chain(rest.iter().take_while(move |node| node.depth > depth)))
+    }
+
+    /// Returns the trace address of the node, i.e. the indices of the calls on
+    /// the path from the root, as used by parity traces, `None` if there is no
+    /// node at the index.
+    pub fn trace_address(&self, idx: usize) -> Option<Vec<usize>> {
+        let mut node = self.nodes.get(idx)?;
+        let mut address = Vec::with_capacity(node.depth);
+        while let Some(parent) = self.parent(node.idx) {
+            address.push(parent.children.iter().position(|&child| child == node.idx).unwrap());
+            node = parent;
+        }
+        address.reverse();
+        Some(address)
+    }
+
+    /// Returns `true` if the changes of the call are reverted, because it or
+    /// one of its ancestors failed.
+    ///
+    /// Returns `false` if there is no node at the index.
+    pub fn is_reverted(&self, idx: usize) -> bool {
+        self.nodes.get(idx).is_some_and(CallNode::is_failed)
+            || self.ancestors(idx).any(CallNode::is_failed)
+    }
+
+    /// Returns an iterator over the calls that failed.
+    pub fn failed_calls(&self) -> impl Iterator<Item = &CallNode> + '_ {
+        self.nodes.iter().filter(|node| node.is_failed())
+    }
+
+    /// Returns an iterator over the value transfers of the calls that are not
+    /// reverted, in depth-first order.
+    pub fn value_transfers(&self) -> impl Iterator<Item = ValueTransfer> + '_ {
+        self.nodes.iter().filter_map(move |node| {
+            if node.value.is_zero() || !node.kind.transfers_value() || self.is_reverted(node.idx) {
+                return None;
+            }
+            Some(ValueTransfer { idx: node.idx, from: node.from, to: node.to?, value: node.value })
+        })
+    }
+}
+
+impl<'a> IntoIterator for &'a CallFrameTree {
+    type Item = &'a CallNode;
+    type IntoIter = std::slice::Iter<'a, CallNode>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<&CallFrame> for CallFrameTree {
+    fn from(frame: &CallFrame) -> Self {
+        Self::from_call_frame(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parity::{CallAction, CallOutput};
+
+    fn frame(typ: &str, to: u8, value: u64, calls: Vec<CallFrame>) -> CallFrame {
+        CallFrame {
+            from: Address::with_last_byte(to - 1),
+            to: Some(Address::with_last_byte(to)),
+            value: Some(U256::from(value)),
+            typ: typ.to_string(),
+            calls,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn call_frame_tree() {
+        let mut failed = frame("CALL", 4, 5, vec![frame("CALL", 5, 6, vec![])]);
+        failed.error = Some("execution reverted".to_string());
+        let root = frame(
+            "CALL",
+            2,
+            1,
+            vec![frame("DELEGATECALL", 3, 2, vec![frame("CREATE", 7, 3, vec![])]), failed],
+        );
+
+        let tree = CallFrameTree::from_call_frame(&root);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.iter().map(|node| node.depth).collect::<Vec<_>>(), [0, 1, 2, 1, 2]);
+        assert_eq!(tree.children(0).unwrap().map(|node| node.idx).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(tree.parent(2).unwrap().idx, 1);
+        assert_eq!(tree.ancestors(4).map(|node| node.idx).collect::<Vec<_>>(), [3, 0]);
+        assert_eq!(tree.subtree(1).unwrap().map(|node| node.idx).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(tree.trace_address(4).unwrap(), [1, 0]);
+        assert!(tree.children(5).is_none());
+        assert!(tree.parent(5).is_none());
+        assert!(tree.subtree(5).is_none());
+        assert!(tree.trace_address(5).is_none());
+        assert!(!tree.is_reverted(5));
+
+        assert!(tree.is_reverted(4));
+        assert!(!tree.get(4).unwrap().is_failed());
+        assert_eq!(tree.failed_calls().map(|node| node.idx).collect::<Vec<_>>(), [3]);
+        assert_eq!(tree.value_transfers().map(|transfer| transfer.idx).collect::<Vec<_>>(), [0, 2]);
+    }
+
+    #[test]
+    fn parity_traces_tree() {
+        let trace = |trace_address: Vec<usize>, subtraces| TransactionTrace {
+            action: Action::Call(CallAction {
+                call_type: CallType::Call,
+                value: U256::from(1),
+                ..Default::default()
+            }),
+            error: None,
+            result: Some(TraceOutput::Call(CallOutput { gas_used: 1, output: Bytes::new() })),
+            subtraces,
+            trace_address,
+        };
+        let traces = [trace(vec![], 2), trace(vec![1], 0), trace(vec![0], 1), trace(vec![0, 0], 0)];
+
+        let tree = CallFrameTree::from_parity_traces(&traces).unwrap();
+        assert_eq!(tree.len(), 4);
+        for node in &tree {
+            let idx = traces
+                .iter()
+                .position(|t| Some(&t.trace_address) == tree.trace_address(node.idx).as_ref());
+            assert_eq!(traces[idx.unwrap()].subtraces, node.children.len());
+        }
+        assert_eq!(tree.value_transfers().count(), 4);
+
+        assert!(CallFrameTree::from_parity_traces(&traces[1..]).is_none());
+        assert!(
+            CallFrameTree::from_parity_traces(&[traces[0].clone(), traces[3].clone()]).is_none()
+        );
+    }
+}
@@ -33,6 +33,9 @@ pub use ttd::*;
 mod other;
 pub use other::{OtherFields, WithOtherFields};
 
+mod strict;
+pub use strict::Strict;
+
 /// Serialize a byte vec as a hex string _without_ the "0x" prefix.
 ///
 /// This behaves the same as [`hex::encode`].
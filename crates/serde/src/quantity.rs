@@ -89,6 +89,177 @@ pub mod vec {
     }
 }
 
+/// Serde functions for encoding primitive numbers using the canonical Ethereum "quantity" format,
+/// rejecting non-canonical input.
+///
+/// The default [`quantity`](self) deserializers are lenient, and also accept leading zeros,
+/// upper-case digits, decimal strings and missing `0x` prefixes. The deserializers of this module
+/// only accept values matching `^0x([1-9a-f]+[0-9a-f]*|0)$`, which is what nodes serving the
+/// JSON-RPC API are expected to produce and what strict clients accept.
+///
+/// Serialization is the same as [`quantity`](self), which is always canonical.
+///
+/// Non-human-readable formats are handled the same as [`quantity`](self).
+pub mod strict {
+    use super::private::ConvertRuint;
+    use core::{fmt, marker::PhantomData, str::FromStr};
+    use serde::{de, Deserializer, Serializer};
+
+    /// An error returned when a string is not a canonical "quantity".
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum QuantityError {
+        /// The string does not start with `0x`.
+        MissingPrefix,
+        /// The string has no digits after the `0x` prefix.
+        Empty,
+        /// The string has leading zeros.
+        LeadingZero,
+        /// The string contains a character that is not a lower-case hex digit.
+        InvalidDigit,
+    }
+
+    impl fmt::Display for QuantityError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(match self {
+                Self::MissingPrefix => "quantity is missing the 0x prefix",
+                Self::Empty => "quantity has no digits",
+                Self::LeadingZero => "quantity has leading zeros",
+                Self::InvalidDigit => {
+                    "quantity contains a character that is not a lower-case hex digit"
+                }
+            })
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for QuantityError {}
+
+    /// Checks that the string is a canonical "quantity".
+    pub fn validate(s: &str) -> Result<(), QuantityError> {
+        let digits = s.strip_prefix("0x").ok_or(QuantityError::MissingPrefix)?;
+        match digits.as_bytes() {
+            [] => Err(QuantityError::Empty),
+            [b'0'] => Ok(()),
+            [b'0', ..] => Err(QuantityError::LeadingZero),
+            digits if digits.iter().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) => Ok(()),
+            _ => Err(QuantityError::InvalidDigit),
+        }
+    }
+
+    /// Serializes a primitive number as a "quantity" hex string.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ConvertRuint,
+        S: Serializer,
+    {
+        super::serialize(value, serializer)
+    }
+
+    /// Deserializes a primitive number from a canonical "quantity" hex string.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: ConvertRuint,
+        T::Ruint: FromStr,
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return super::deserialize(deserializer);
+        }
+        deserializer.deserialize_str(StrictVisitor(PhantomData))
+    }
+
+    struct StrictVisitor<T>(PhantomData<T>);
+
+    impl<T> de::Visitor<'_> for StrictVisitor<T>
+    where
+        T: ConvertRuint,
+        T::Ruint: FromStr,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a canonical hex quantity")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            validate(v).map_err(E::custom)?;
+            let ruint =
+                v.parse::<T::Ruint>().map_err(|_| E::custom("quantity does not fit the type"))?;
+            Ok(T::from_ruint(ruint))
+        }
+    }
+
+    /// Serde functions for encoding optional primitive numbers using the canonical Ethereum
+    /// "quantity" format.
+    ///
+    /// See [`strict`](self) for more information.
+    pub mod opt {
+        use super::ConvertRuint;
+        use core::str::FromStr;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        /// Serializes an optional primitive number as a "quantity" hex string.
+        pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: ConvertRuint,
+            S: Serializer,
+        {
+            super::super::opt::serialize(value, serializer)
+        }
+
+        /// Deserializes an optional primitive number from a canonical "quantity" hex string.
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            T: ConvertRuint,
+            T::Ruint: FromStr,
+            D: Deserializer<'de>,
+        {
+            #[derive(Deserialize)]
+            #[serde(bound(deserialize = "T: ConvertRuint, T::Ruint: FromStr"))]
+            struct Strict<T>(#[serde(deserialize_with = "super::deserialize")] T);
+
+            Ok(Option::<Strict<T>>::deserialize(deserializer)?.map(|Strict(value)| value))
+        }
+    }
+
+    /// Serde functions for encoding a list of primitive numbers using the canonical Ethereum
+    /// "quantity" format.
+    ///
+    /// See [`strict`](self) for more information.
+    pub mod vec {
+        use super::ConvertRuint;
+        use core::str::FromStr;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        #[cfg(not(feature = "std"))]
+        use alloc::vec::Vec;
+
+        /// Serializes a vector of primitive numbers as a "quantity" hex string.
+        pub fn serialize<T, S>(value: &[T], serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: ConvertRuint,
+            S: Serializer,
+        {
+            super::super::vec::serialize(value, serializer)
+        }
+
+        /// Deserializes a vector of primitive numbers from canonical "quantity" hex strings.
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+        where
+            T: ConvertRuint,
+            T::Ruint: FromStr,
+            D: Deserializer<'de>,
+        {
+            #[derive(Deserialize)]
+            #[serde(bound(deserialize = "T: ConvertRuint, T::Ruint: FromStr"))]
+            struct Strict<T>(#[serde(deserialize_with = "super::deserialize")] T);
+
+            let vec = Vec::<Strict<T>>::deserialize(deserializer)?;
+            Ok(vec.into_iter().map(|Strict(value)| value).collect())
+        }
+    }
+}
+
 /// serde functions for handling `Vec<Vec<u128>>` via [U128](alloy_primitives::U128)
 pub mod u128_vec_vec_opt {
     use alloy_primitives::U128;
@@ -180,7 +351,7 @@ mod tests {
     use serde::{Deserialize, Serialize};
 
     #[cfg(not(feature = "std"))]
-    use alloc::{string::ToString, vec, vec::Vec};
+    use alloc::{format, string::ToString, vec, vec::Vec};
 
     #[test]
     fn test_hex_u64() {
@@ -285,4 +456,38 @@ mod tests {
         let deserialized: Value = serde_json::from_str(&s).unwrap();
         assert_eq!(val, deserialized);
     }
+
+    #[test]
+    fn test_strict_quantity() {
+        #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+        struct Value {
+            #[serde(with = "super::strict")]
+            inner: u64,
+            #[serde(with = "super::strict::opt")]
+            opt: Option<u128>,
+            #[serde(with = "super::strict::vec")]
+            vec: Vec<u8>,
+        }
+
+        let val = Value { inner: 0, opt: Some(1000), vec: vec![1, 255] };
+        let s = serde_json::to_string(&val).unwrap();
+        assert_eq!(s, r#"{"inner":"0x0","opt":"0x3e8","vec":["0x1","0xff"]}"#);
+        assert_eq!(serde_json::from_str::<Value>(&s).unwrap(), val);
+
+        let val = Value { inner: 1, opt: None, vec: vec![] };
+        assert_eq!(
+            serde_json::from_str::<Value>(r#"{"inner":"0x1","opt":null,"vec":[]}"#).unwrap(),
+            val
+        );
+
+        for inner in ["0x01", "0x00", "0x", "1", "0x3E8", "0xg", "0x10000000000000000"] {
+            let s = format!(r#"{{"inner":"{inner}","opt":null,"vec":[]}}"#);
+            assert!(serde_json::from_str::<Value>(&s).is_err(), "{inner}");
+        }
+        assert!(serde_json::from_str::<Value>(r#"{"inner":1,"opt":null,"vec":[]}"#).is_err());
+        assert!(serde_json::from_str::<Value>(r#"{"inner":"0x1","opt":"0x01","vec":[]}"#).is_err());
+        assert!(
+            serde_json::from_str::<Value>(r#"{"inner":"0x1","opt":null,"vec":["0x100"]}"#).is_err()
+        );
+    }
 }
@@ -0,0 +1,141 @@
+//! Support for rejecting unknown fields.
+
+use alloc::{format, string::String, vec::Vec};
+use core::ops::{Deref, DerefMut};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// A wrapper rejecting the fields unknown to the inner type when deserializing, including the
+/// fields of nested objects.
+///
+/// The RPC types ignore unknown fields, so that the responses of nodes with extensions still
+/// deserialize. This wrapper is the strict alternative for users checking conformance: it fails
+/// with the JSON paths of the fields that the inner type does not serialize back, e.g.
+/// `unknown fields: $.transactions[0].l1Fee`.
+///
+/// Fields set to `null` are not considered unknown. Fields that are captured by [`OtherFields`]
+/// are known, so `Strict<WithOtherFields<T>>` is as permissive as `WithOtherFields<T>` at the top
+/// level.
+///
+/// [`serde(deny_unknown_fields)`][deny] cannot be used instead, since it is not supported by the
+/// flattened types.
+///
+/// [`OtherFields`]: crate::OtherFields
+/// [deny]: https://serde.rs/container-attrs.html#deny_unknown_fields
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Strict<T>(pub T);
+
+impl<T> Strict<T> {
+    /// Returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Strict<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Strict<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Strict<T>
+where
+    T: DeserializeOwned + Serialize,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let inner = T::deserialize(&value).map_err(serde::de::Error::custom)?;
+        let known = serde_json::to_value(&inner).map_err(serde::de::Error::custom)?;
+
+        let mut unknown = Vec::new();
+        unknown_fields("$", &value, &known, &mut unknown);
+        if unknown.is_empty() {
+            Ok(Self(inner))
+        } else {
+            Err(serde::de::Error::custom(format!("unknown fields: {}", unknown.join(", "))))
+        }
+    }
+}
+
+/// Collects the paths of the fields of `value` that are absent from `known`.
+fn unknown_fields(path: &str, value: &Value, known: &Value, unknown: &mut Vec<String>) {
+    match (value, known) {
+        (Value::Object(value), Value::Object(known)) => {
+            for (key, value) in value {
+                let path = format!("{path}.{key}");
+                match known.get(key) {
+                    Some(known) => unknown_fields(&path, value, known, unknown),
+                    None if value.is_null() => {}
+                    None => unknown.push(path),
+                }
+            }
+        }
+        (Value::Array(values), Value::Array(known)) => {
+            for (index, (value, known)) in values.iter().zip(known).enumerate() {
+                unknown_fields(&format!("{path}[{index}]"), value, known, unknown);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WithOtherFields;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Inner {
+        a: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        b: Option<u64>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Outer {
+        #[serde(flatten)]
+        inner: Inner,
+        items: Vec<Inner>,
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let outer: Strict<Outer> =
+            serde_json::from_str(r#"{"a":1,"b":null,"items":[{"a":2,"b":3}]}"#).unwrap();
+        assert_eq!(outer.inner, Inner { a: 1, b: None });
+
+        let err = serde_json::from_str::<Strict<Outer>>(
+            r#"{"a":1,"c":2,"items":[{"a":2},{"a":3,"d":4}]}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "unknown fields: $.c, $.items[1].d");
+
+        // lenient by default
+        serde_json::from_str::<Outer>(r#"{"a":1,"c":2,"items":[]}"#).unwrap();
+    }
+
+    #[test]
+    fn other_fields_are_known() {
+        let outer: Strict<WithOtherFields<Outer>> =
+            serde_json::from_str(r#"{"a":1,"c":2,"items":[]}"#).unwrap();
+        assert_eq!(outer.other.get("c"), Some(&Value::from(2)));
+
+        let err = serde_json::from_str::<Strict<WithOtherFields<Outer>>>(
+            r#"{"a":1,"items":[{"a":2,"d":4}]}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "unknown fields: $.items[0].d");
+    }
+}
@@ -24,6 +24,9 @@ pub use mnemonic::{MnemonicBuilder, MnemonicBuilderError};
 
 mod private_key;
 
+mod tx_gen;
+pub use tx_gen::{intrinsic_gas, TxFork, TxGenerator};
+
 #[cfg(feature = "yubihsm")]
 mod yubi;
 
@@ -0,0 +1,482 @@
+//! Deterministic generation of random signed transactions.
+
+use crate::PrivateKeySigner;
+use alloy_consensus::{
+    SignableTransaction, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant, TxEip7702, TxEnvelope,
+    TxLegacy,
+};
+use alloy_eips::{
+    eip2930::{AccessList, AccessListItem},
+    eip4844::VERSIONED_HASH_VERSION_KZG,
+    eip7702::Authorization,
+};
+use alloy_network::TxSignerSync;
+use alloy_primitives::{Address, Bytes, ChainId, TxKind, U256};
+use alloy_signer::SignerSync;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The base cost of a transaction.
+const TX_BASE_GAS: u64 = 21_000;
+/// The additional cost of a contract creation.
+const TX_CREATE_GAS: u64 = 32_000;
+/// The cost per zero byte of input.
+const TX_DATA_ZERO_GAS: u64 = 4;
+/// The cost per non-zero byte of input, before and since Istanbul.
+const TX_DATA_NON_ZERO_GAS: (u64, u64) = (68, 16);
+/// The cost per word of init code, since Shanghai.
+const INIT_CODE_WORD_GAS: u64 = 2;
+/// The cost per address of the access list.
+const ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+/// The cost per storage key of the access list.
+const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+/// The cost per authorization, charged as if the authority was empty.
+const PER_EMPTY_ACCOUNT_GAS: u64 = 25_000;
+/// The floor cost per token of input, since Prague.
+const TOTAL_COST_FLOOR_PER_TOKEN: u64 = 10;
+
+/// The forks a [`TxGenerator`] targets, each enabling the transaction types and gas rules it
+/// introduced.
+///
+/// Later forks include the rules of all earlier forks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum TxFork {
+    /// Legacy transactions only, with the original input costs.
+    Homestead,
+    /// Cheaper non-zero input bytes, see [EIP-2028](https://eips.ethereum.org/EIPS/eip-2028).
+    Istanbul,
+    /// Access list transactions, see [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930).
+    Berlin,
+    /// Dynamic fee transactions, see [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559).
+    London,
+    /// Init code costs, see [EIP-3860](https://eips.ethereum.org/EIPS/eip-3860).
+    Shanghai,
+    /// Blob transactions, see [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844).
+    Cancun,
+    /// Set code transactions and input cost floors, see
+    /// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) and
+    /// [EIP-7623](https://eips.ethereum.org/EIPS/eip-7623).
+    Prague,
+}
+
+impl TxFork {
+    /// Returns the maximum number of blobs in a transaction.
+    pub const fn max_blobs(self) -> usize {
+        match self {
+            Self::Prague => 9,
+            Self::Cancun => 6,
+            _ => 0,
+        }
+    }
+}
+
+/// Returns the intrinsic gas of a transaction at the fork, the gas charged before execution.
+///
+/// Since Prague, this includes the floor cost of the input, see
+/// [EIP-7623](https://eips.ethereum.org/EIPS/eip-7623).
+pub fn intrinsic_gas(
+    fork: TxFork,
+    input: &[u8],
+    is_create: bool,
+    access_list: &AccessList,
+    authorizations: usize,
+) -> u64 {
+    let zeros = input.iter().filter(|byte| **byte == 0).count() as u64;
+    let non_zeros = input.len() as u64 - zeros;
+    let non_zero_gas =
+        if fork >= TxFork::Istanbul { TX_DATA_NON_ZERO_GAS.1 } else { TX_DATA_NON_ZERO_GAS.0 };
+
+    let mut gas = TX_BASE_GAS + zeros * TX_DATA_ZERO_GAS + non_zeros * non_zero_gas;
+    if is_create {
+        gas += TX_CREATE_GAS;
+        if fork >= TxFork::Shanghai {
+            gas += (input.len() as u64).div_ceil(32) * INIT_CODE_WORD_GAS;
+        }
+    }
+    for item in access_list.iter() {
+        gas +=
+            ACCESS_LIST_ADDRESS_GAS + item.storage_keys.len() as u64 * ACCESS_LIST_STORAGE_KEY_GAS;
+    }
+    gas += authorizations as u64 * PER_EMPTY_ACCOUNT_GAS;
+
+    if fork >= TxFork::Prague {
+        let tokens = zeros + non_zeros * 4;
+        gas = gas.max(TX_BASE_GAS + tokens * TOTAL_COST_FLOOR_PER_TOKEN);
+    }
+    gas
+}
+
+/// Generates random signed transactions of all the types of a fork, for load-testing nodes and
+/// populating test chains.
+///
+/// Transactions are signed by a set of generated or [provided](Self::with_signers) signers, with
+/// consecutive nonces per signer, the chain ID of the generator, and a gas limit covering their
+/// [intrinsic gas](intrinsic_gas). Fees are drawn up to [`with_max_fee`](Self::with_max_fee), so
+/// the signers must be funded for the transactions to be included.
+///
+/// Blob transactions carry random versioned hashes, and their sidecars when the `kzg` feature is
+/// enabled. Set code transactions carry authorizations signed by random authorities.
+///
+/// The same seed and settings always produce the same transactions.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_consensus::Transaction;
+/// use alloy_signer_local::{TxFork, TxGenerator};
+///
+/// let mut generator = TxGenerator::new(42, TxFork::Prague, 1);
+/// for tx in generator.generate(10) {
+///     assert_eq!(tx.chain_id(), Some(1));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TxGenerator {
+    rng: StdRng,
+    fork: TxFork,
+    chain_id: ChainId,
+    signers: Vec<(PrivateKeySigner, u64)>,
+    max_input_len: usize,
+    max_access_list_len: usize,
+    max_blobs: usize,
+    max_authorizations: usize,
+    max_fee: u128,
+}
+
+impl TxGenerator {
+    /// Creates a generator for the fork and chain, seeded with `seed`, with 4 random signers.
+    pub fn new(seed: u64, fork: TxFork, chain_id: ChainId) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let signers = (0..4).map(|_| (PrivateKeySigner::random_with(&mut rng), 0)).collect();
+        Self {
+            rng,
+            fork,
+            chain_id,
+            signers,
+            max_input_len: 256,
+            max_access_list_len: 4,
+            max_blobs: fork.max_blobs(),
+            max_authorizations: 4,
+            max_fee: 100_000_000_000,
+        }
+    }
+
+    /// Sets the signers of the transactions, with the next nonce of each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `signers` is empty.
+    pub fn with_signers(mut self, signers: Vec<(PrivateKeySigner, u64)>) -> Self {
+        assert!(!signers.is_empty(), "at least one signer is needed");
+        self.signers = signers;
+        self
+    }
+
+    /// Sets the maximum length of the input, 256 bytes by default.
+    pub const fn with_max_input_len(mut self, max_input_len: usize) -> Self {
+        self.max_input_len = max_input_len;
+        self
+    }
+
+    /// Sets the maximum number of addresses in access lists, 4 by default.
+    pub const fn with_max_access_list_len(mut self, max_access_list_len: usize) -> Self {
+        self.max_access_list_len = max_access_list_len;
+        self
+    }
+
+    /// Sets the maximum number of blobs, up to the maximum of the fork. Blob transactions are
+    /// not generated if 0.
+    pub fn with_max_blobs(mut self, max_blobs: usize) -> Self {
+        self.max_blobs = max_blobs.min(self.fork.max_blobs());
+        self
+    }
+
+    /// Sets the maximum number of authorizations, 4 by default. Set code transactions are not
+    /// generated if 0.
+    pub const fn with_max_authorizations(mut self, max_authorizations: usize) -> Self {
+        self.max_authorizations = max_authorizations;
+        self
+    }
+
+    /// Sets the maximum fee per gas and per blob gas, 100 gwei by default.
+    pub const fn with_max_fee(mut self, max_fee: u128) -> Self {
+        self.max_fee = max_fee;
+        self
+    }
+
+    /// Returns the addresses of the signers.
+    pub fn signers(&self) -> impl Iterator<Item = Address> + '_ {
+        self.signers.iter().map(|(signer, _)| signer.address())
+    }
+
+    /// Generates `count` transactions.
+    pub fn generate(&mut self, count: usize) -> Vec<TxEnvelope> {
+        (0..count).map(|_| self.next_transaction()).collect()
+    }
+
+    /// Generates the next transaction.
+    pub fn next_transaction(&mut self) -> TxEnvelope {
+        let mut types = vec![0u8];
+        if self.fork >= TxFork::Berlin {
+            types.push(1);
+        }
+        if self.fork >= TxFork::London {
+            types.push(2);
+        }
+        if self.max_blobs > 0 {
+            types.push(3);
+        }
+        if self.fork >= TxFork::Prague && self.max_authorizations > 0 {
+            types.push(4);
+        }
+        let ty = types[self.rng.gen_range(0..types.len())];
+
+        let index = self.rng.gen_range(0..self.signers.len());
+        let nonce = self.signers[index].1;
+        self.signers[index].1 += 1;
+
+        // blob and set code transactions cannot create contracts
+        let to = if ty < 3 && self.rng.gen_bool(0.1) {
+            TxKind::Create
+        } else {
+            TxKind::Call(self.rng.gen())
+        };
+        let value = U256::from(self.rng.gen_range(0..1_000_000_000_000_000u64));
+        let input = self.input();
+        let access_list = if ty == 0 { AccessList::default() } else { self.access_list() };
+        let authorization_list = if ty == 4 { self.authorizations() } else { Vec::new() };
+        let gas_limit = intrinsic_gas(
+            self.fork,
+            &input,
+            to.is_create(),
+            &access_list,
+            authorization_list.len(),
+        ) + self.rng.gen_range(0..50_000);
+        let max_fee_per_gas = self.rng.gen_range(1..=self.max_fee.max(1));
+        let max_priority_fee_per_gas = self.rng.gen_range(0..=max_fee_per_gas);
+
+        let chain_id = self.chain_id;
+        let signer = &self.signers[index].0;
+        match ty {
+            0 => sign(
+                signer,
+                TxLegacy {
+                    chain_id: Some(chain_id),
+                    nonce,
+                    gas_price: max_fee_per_gas,
+                    gas_limit,
+                    to,
+                    value,
+                    input,
+                },
+            ),
+            1 => sign(
+                signer,
+                TxEip2930 {
+                    chain_id,
+                    nonce,
+                    gas_price: max_fee_per_gas,
+                    gas_limit,
+                    to,
+                    value,
+                    access_list,
+                    input,
+                },
+            ),
+            2 => sign(
+                signer,
+                TxEip1559 {
+                    chain_id,
+                    nonce,
+                    gas_limit,
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                    to,
+                    value,
+                    access_list,
+                    input,
+                },
+            ),
+            3 => {
+                let tx = TxEip4844 {
+                    chain_id,
+                    nonce,
+                    gas_limit,
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                    to: to.to().copied().unwrap_or_default(),
+                    value,
+                    access_list,
+                    blob_versioned_hashes: Vec::new(),
+                    max_fee_per_blob_gas: self.rng.gen_range(1..=self.max_fee.max(1)),
+                    input,
+                };
+                let tx = self.with_blobs(tx);
+                sign(&self.signers[index].0, tx)
+            }
+            _ => sign(
+                signer,
+                TxEip7702 {
+                    chain_id,
+                    nonce,
+                    gas_limit,
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                    to: to.to().copied().unwrap_or_default(),
+                    value,
+                    access_list,
+                    authorization_list,
+                    input,
+                },
+            ),
+        }
+    }
+
+    fn input(&mut self) -> Bytes {
+        let len = self.rng.gen_range(0..=self.max_input_len);
+        // mix zero and non-zero bytes, which are priced differently
+        (0..len).map(|_| if self.rng.gen_bool(0.3) { 0 } else { self.rng.gen() }).collect()
+    }
+
+    fn access_list(&mut self) -> AccessList {
+        let len = self.rng.gen_range(0..=self.max_access_list_len);
+        let items = (0..len)
+            .map(|_| AccessListItem {
+                address: self.rng.gen(),
+                storage_keys: (0..self.rng.gen_range(0..4)).map(|_| self.rng.gen()).collect(),
+            })
+            .collect();
+        AccessList(items)
+    }
+
+    fn authorizations(&mut self) -> Vec<alloy_eips::eip7702::SignedAuthorization> {
+        let len = self.rng.gen_range(1..=self.max_authorizations);
+        (0..len)
+            .map(|_| {
+                let authority = PrivateKeySigner::random_with(&mut self.rng);
+                let authorization = Authorization {
+                    chain_id: U256::from(self.chain_id),
+                    address: self.rng.gen(),
+                    nonce: 0,
+                };
+                let signature = authority
+                    .sign_hash_sync(&authorization.signature_hash())
+                    .expect("local signing does not fail");
+                authorization.into_signed(signature)
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "kzg"))]
+    fn with_blobs(&mut self, mut tx: TxEip4844) -> TxEip4844Variant {
+        use alloy_primitives::B256;
+
+        let blobs = self.rng.gen_range(1..=self.max_blobs);
+        tx.blob_versioned_hashes = (0..blobs)
+            .map(|_| {
+                let mut hash: B256 = self.rng.gen();
+                hash[0] = VERSIONED_HASH_VERSION_KZG;
+                hash
+            })
+            .collect();
+        tx.into()
+    }
+
+    #[cfg(feature = "kzg")]
+    fn with_blobs(&mut self, mut tx: TxEip4844) -> TxEip4844Variant {
+        use alloy_consensus::{SidecarBuilder, SimpleCoder, TxEip4844WithSidecar};
+
+        // fill all but the last blob, and part of the last one
+        let blobs = self.rng.gen_range(1..=self.max_blobs);
+        let len = (blobs - 1) * 4096 * 31 + self.rng.gen_range(1..4096 * 31);
+        let data = (0..len).map(|_| self.rng.gen()).collect::<Vec<u8>>();
+        let sidecar =
+            SidecarBuilder::<SimpleCoder>::from_slice(&data).build().expect("the blobs are valid");
+        tx.blob_versioned_hashes = sidecar.versioned_hashes().collect();
+        debug_assert_eq!(tx.blob_versioned_hashes[0][0], VERSIONED_HASH_VERSION_KZG);
+        TxEip4844WithSidecar::from_tx_and_sidecar(tx, sidecar).into()
+    }
+}
+
+impl Iterator for TxGenerator {
+    type Item = TxEnvelope;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_transaction())
+    }
+}
+
+fn sign<T>(signer: &PrivateKeySigner, mut tx: T) -> TxEnvelope
+where
+    T: SignableTransaction<alloy_primitives::Signature>,
+    TxEnvelope: From<alloy_consensus::Signed<T>>,
+{
+    let signature = signer.sign_transaction_sync(&mut tx).expect("local signing does not fail");
+    tx.into_signed(signature).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Transaction;
+    use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+    use alloy_primitives::B256;
+    use std::collections::HashMap;
+
+    #[test]
+    fn computes_intrinsic_gas() {
+        let empty = AccessList::default();
+        assert_eq!(intrinsic_gas(TxFork::London, &[], false, &empty, 0), 21_000);
+        assert_eq!(intrinsic_gas(TxFork::Homestead, &[0, 1], false, &empty, 0), 21_072);
+        assert_eq!(intrinsic_gas(TxFork::London, &[0, 1], false, &empty, 0), 21_020);
+        // 33 bytes of init code take two words
+        assert_eq!(intrinsic_gas(TxFork::Shanghai, &[0; 33], true, &empty, 0), 53_136);
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::ZERO,
+            storage_keys: vec![B256::ZERO; 2],
+        }]);
+        assert_eq!(intrinsic_gas(TxFork::Berlin, &[], false, &access_list, 0), 27_200);
+        assert_eq!(intrinsic_gas(TxFork::Prague, &[], false, &empty, 2), 71_000);
+        // the floor of 100 non-zero bytes exceeds the standard cost
+        assert_eq!(intrinsic_gas(TxFork::Prague, &[1; 100], false, &empty, 0), 25_000);
+    }
+
+    #[test]
+    fn generates_valid_transactions() {
+        let mut generator = TxGenerator::new(7, TxFork::Prague, 1337);
+        let signers = generator.signers().collect::<Vec<_>>();
+        let txs = generator.generate(200);
+        assert_eq!(TxGenerator::new(7, TxFork::Prague, 1337).generate(200), txs);
+
+        let mut nonces = HashMap::<Address, u64>::new();
+        let mut types = [0; 5];
+        for tx in &txs {
+            types[tx.tx_type() as usize] += 1;
+            assert_eq!(tx.chain_id(), Some(1337));
+
+            let sender = tx.recover_signer().unwrap();
+            assert!(signers.contains(&sender));
+            let nonce = nonces.entry(sender).or_default();
+            assert_eq!(tx.nonce(), *nonce);
+            *nonce += 1;
+
+            let access_list = tx.access_list().cloned().unwrap_or_default();
+            let authorizations = tx.authorization_list().map_or(0, <[_]>::len);
+            let intrinsic = intrinsic_gas(
+                TxFork::Prague,
+                tx.input(),
+                tx.to().is_create(),
+                &access_list,
+                authorizations,
+            );
+            assert!(tx.gas_limit() >= intrinsic);
+
+            let encoded = tx.encoded_2718();
+            assert_eq!(TxEnvelope::decode_2718(&mut encoded.as_slice()).unwrap(), *tx);
+        }
+        assert!(types.iter().all(|count| *count > 0), "all types are generated: {types:?}");
+
+        let legacy = TxGenerator::new(7, TxFork::Homestead, 1).generate(20);
+        assert!(legacy.iter().all(|tx| tx.is_legacy()));
+    }
+}
@@ -0,0 +1,171 @@
+use alloy_transport::{resolve::Resolve, Pbf, TransportError, TransportErrorKind};
+use futures_util::future::join;
+use reqwest::Client;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use url::Url;
+
+/// The DNS record type of IPv4 addresses.
+const TYPE_A: u16 = 1;
+/// The DNS record type of IPv6 addresses.
+const TYPE_AAAA: u16 = 28;
+
+/// Resolves hostnames with DNS-over-HTTPS, using the JSON API of providers such as Cloudflare
+/// (`https://1.1.1.1/dns-query`) and Google (`https://8.8.8.8/resolve`).
+///
+/// A and AAAA records are queried concurrently, and IPv4 addresses are preferred. Resolution only
+/// fails if neither query finds an address. Answers are cached for their TTL. Use a DoH server URL
+/// with an IP address, so that resolution does not depend on the system resolver.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy_transport_http::{DohResolver, Http, HttpConfig};
+///
+/// let resolver = DohResolver::new("https://1.1.1.1/dns-query".parse()?);
+/// let config = HttpConfig::new().with_resolver(resolver);
+/// let transport = Http::with_config("https://rpc.example.com".parse()?, &config)?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct DohResolver {
+    client: Client,
+    url: Url,
+    cache: Mutex<HashMap<String, (Vec<IpAddr>, Instant)>>,
+}
+
+impl DohResolver {
+    /// Creates a resolver querying the DoH server.
+    pub fn new(url: Url) -> Self {
+        Self::with_client(Client::new(), url)
+    }
+
+    /// Creates a resolver querying the DoH server with the client.
+    pub fn with_client(client: Client, url: Url) -> Self {
+        Self { client, url, cache: Mutex::default() }
+    }
+
+    async fn query(&self, host: &str, ty: u16) -> Result<(Vec<IpAddr>, u32), TransportError> {
+        let mut url = self.url.clone();
+        url.query_pairs_mut().append_pair("name", host).append_pair("type", &ty.to_string());
+        let resp = self
+            .client
+            .get(url)
+            .header(reqwest::header::ACCEPT, "application/dns-json")
+            .send()
+            .await
+            .map_err(TransportErrorKind::custom)?
+            .error_for_status()
+            .map_err(TransportErrorKind::custom)?
+            .json::<DohResponse>()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        if resp.status != 0 {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "DoH query for {host} failed with status {}",
+                resp.status
+            )));
+        }
+
+        let mut ttl = u32::MAX;
+        let mut addrs = Vec::new();
+        // answers may include CNAME records before the addresses
+        for answer in resp.answer.into_iter().filter(|answer| answer.ty == ty) {
+            let addr = answer.data.parse().map_err(TransportErrorKind::custom)?;
+            addrs.push(addr);
+            ttl = ttl.min(answer.ttl);
+        }
+        Ok((addrs, ttl))
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve<'a>(&'a self, host: &'a str) -> Pbf<'a, Vec<IpAddr>, TransportError> {
+        Box::pin(async move {
+            let key = host.to_ascii_lowercase();
+            if let Some((addrs, expiry)) = self.cache.lock().unwrap().get(&key) {
+                if *expiry > Instant::now() {
+                    return Ok(addrs.clone());
+                }
+            }
+
+            // a host may have only one kind of address, so a failed or empty query is only an
+            // error if the other one finds nothing either
+            let (v4, v6) = join(self.query(host, TYPE_A), self.query(host, TYPE_AAAA)).await;
+            let mut addrs = Vec::new();
+            let mut ttl = u32::MAX;
+            let mut error = None;
+            for result in [v4, v6] {
+                match result {
+                    Ok((found, found_ttl)) if !found.is_empty() => {
+                        addrs.extend(found);
+                        ttl = ttl.min(found_ttl);
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        error.get_or_insert(err);
+                    }
+                }
+            }
+            if addrs.is_empty() {
+                return Err(error.unwrap_or_else(|| {
+                    TransportErrorKind::custom_str(&format!("DoH found no addresses for {host}"))
+                }));
+            }
+
+            let expiry = Instant::now() + Duration::from_secs(ttl.into());
+            self.cache.lock().unwrap().insert(key, (addrs.clone(), expiry));
+            Ok(addrs)
+        })
+    }
+}
+
+/// A response of the DoH JSON API.
+#[derive(Debug, Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+/// A record of a [`DohResponse`].
+#[derive(Debug, Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    ty: u16,
+    #[serde(rename = "TTL")]
+    ttl: u32,
+    data: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_doh_responses() {
+        let json = r#"{
+            "Status": 0,
+            "TC": false,
+            "Question": [{"name": "rpc.example.com", "type": 1}],
+            "Answer": [
+                {"name": "rpc.example.com", "type": 5, "TTL": 300, "data": "lb.example.com."},
+                {"name": "lb.example.com", "type": 1, "TTL": 60, "data": "192.0.2.1"}
+            ]
+        }"#;
+        let resp: DohResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.status, 0);
+        let addrs = resp.answer.iter().filter(|answer| answer.ty == TYPE_A).collect::<Vec<_>>();
+        assert_eq!(addrs.len(), 1);
+        assert_eq!(addrs[0].data.parse::<IpAddr>().unwrap(), IpAddr::from([192, 0, 2, 1]));
+
+        let resp: DohResponse = serde_json::from_str(r#"{"Status": 3}"#).unwrap();
+        assert!(resp.answer.is_empty());
+    }
+}
@@ -1,18 +1,19 @@
 use alloy_json_rpc::{RequestPacket, ResponsePacket};
 use alloy_transport::{
-    utils::guess_local_url, TransportConnect, TransportError, TransportErrorKind, TransportFut,
+    utils::guess_local_url, Resolver, ResponseLimits, TransportConnect, TransportError,
+    TransportErrorKind, TransportFut,
 };
 use http_body_util::{BodyExt, Full};
 use hyper::{
-    body::{Bytes, Incoming},
+    body::{Buf, Bytes, Incoming},
     header, Request, Response,
 };
-use hyper_util::client::legacy::Error;
-use std::{future::Future, marker::PhantomData, pin::Pin, task};
+use hyper_util::client::legacy::{connect::dns::Name, Error};
+use std::{future::Future, marker::PhantomData, net::SocketAddr, pin::Pin, task};
 use tower::Service;
 use tracing::{debug, debug_span, trace, Instrument};
 
-use crate::{Http, HttpConnect};
+use crate::{Http, HttpConfig, HttpConnect};
 
 type Hyper = hyper_util::client::legacy::Client<
     hyper_util::client::legacy::connect::HttpConnector,
@@ -28,6 +29,13 @@ impl HyperTransport {
         let client = HyperClient::new();
         Self::with_client(client, url)
     }
+
+    /// Create a new [`HyperTransport`] with the given URL and a hyper client built from the given
+    /// settings.
+    pub fn new_hyper_with_config(url: url::Url, config: &HttpConfig) -> Self {
+        Self::with_client(HyperClient::with_config(config), url)
+            .with_response_limits(config.response_limits())
+    }
 }
 
 /// A [hyper] based client that can be used with tower layers.
@@ -56,6 +64,90 @@ impl HyperClient {
     }
 }
 
+impl HyperClient {
+    /// Create a new [HyperClient] with the given settings.
+    ///
+    /// The client does not use TLS, so HTTP/2 is only used with
+    /// [`HttpConfig::with_http2_prior_knowledge`].
+    pub fn with_config(config: &HttpConfig) -> Self {
+        let mut connector = hyper_util::client::legacy::connect::HttpConnector::new();
+        connector.set_keepalive(config.tcp_keepalive);
+
+        let mut builder =
+            hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new());
+        builder
+            .timer(hyper_util::rt::TokioTimer::new())
+            .pool_timer(hyper_util::rt::TokioTimer::new())
+            .http2_only(config.http2_prior_knowledge);
+        if let Some(max) = config.http2_max_concurrent_streams {
+            builder.http2_initial_max_send_streams(max);
+        }
+        if let Some(interval) = config.http2_keep_alive_interval {
+            builder.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = config.pool_idle_timeout {
+            builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = config.pool_max_idle_per_host {
+            builder.pool_max_idle_per_host(max);
+        }
+
+        Self::with_service(builder.build(connector))
+    }
+}
+
+/// Adapts a [`Resolver`] to the resolver of the [`HttpConnector`] of a [`hyper`] client.
+///
+/// [`HyperClient::with_config`] uses the system resolver, so a client using a custom one is built
+/// from a connector:
+///
+/// ```
+/// use alloy_transport::resolve::StaticResolver;
+/// use alloy_transport_http::{
+///     hyper::body::Bytes,
+///     hyper_util::{self, client::legacy::connect::HttpConnector},
+///     HyperClient, HyperResolver,
+/// };
+/// use http_body_util::Full;
+///
+/// let resolver =
+///     StaticResolver::new().with_host("rpc.example.com", ["10.0.0.1".parse().unwrap()]);
+/// let connector = HttpConnector::new_with_resolver(HyperResolver::new(resolver));
+/// let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+///     .build::<_, Full<Bytes>>(connector);
+/// let client = HyperClient::<Full<Bytes>, _>::with_service(client);
+/// ```
+///
+/// [`HttpConnector`]: hyper_util::client::legacy::connect::HttpConnector
+#[derive(Clone, Debug)]
+pub struct HyperResolver(Resolver);
+
+impl HyperResolver {
+    /// Creates an adapter of the resolver.
+    pub fn new(resolver: impl Into<Resolver>) -> Self {
+        Self(resolver.into())
+    }
+}
+
+impl Service<Name> for HyperResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = TransportError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let resolver = self.0.clone();
+        Box::pin(async move {
+            let addrs = resolver.resolve(name.as_str()).await?;
+            // the port is set by the connector
+            Ok(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)).collect::<Vec<_>>().into_iter())
+        })
+    }
+}
+
 impl Default for HyperClient {
     fn default() -> Self {
         Self::new()
@@ -82,7 +174,12 @@ where
     /// Make a request to the server using the given service.
     fn request_hyper(&self, req: RequestPacket) -> TransportFut<'static> {
         let this = self.clone();
-        let span = debug_span!("HyperClient", url = %this.url);
+        let span =
+            debug_span!("HyperClient", url = %this.url, correlation_id = tracing::field::Empty);
+        let correlation_id = req.correlation_id().map(|id| {
+            span.record("correlation_id", id);
+            header::HeaderValue::from_str(id)
+        });
         Box::pin(
             async move {
                 debug!(count = req.len(), "sending request packet to server");
@@ -90,15 +187,17 @@ where
                 // convert the Box<RawValue> into a hyper request<B>
                 let body = ser.get().as_bytes().to_owned().into();
 
-                let req = hyper::Request::builder()
+                let mut builder = hyper::Request::builder()
                     .method(hyper::Method::POST)
                     .uri(this.url.as_str())
                     .header(
                         header::CONTENT_TYPE,
                         header::HeaderValue::from_static("application/json"),
-                    )
-                    .body(body)
-                    .expect("request parts are invalid");
+                    );
+                if let Some(Ok(correlation_id)) = correlation_id {
+                    builder = builder.header(crate::CORRELATION_ID_HEADER, correlation_id);
+                }
+                let req = builder.body(body).expect("request parts are invalid");
 
                 let mut service = this.client.service.clone();
                 let resp = service.call(req).await.map_err(TransportErrorKind::custom)?;
@@ -110,12 +209,7 @@ where
                 // Unpack data from the response body. We do this regardless of
                 // the status code, as we want to return the error in the body
                 // if there is one.
-                let body = resp
-                    .into_body()
-                    .collect()
-                    .await
-                    .map_err(TransportErrorKind::custom)?
-                    .to_bytes();
+                let body = read_body(resp.into_body(), this.limits).await?;
 
                 debug!(bytes = body.len(), "retrieved response body. Use `trace` for full body");
                 trace!(body = %String::from_utf8_lossy(&body), "response body");
@@ -130,6 +224,7 @@ where
                 // Deserialize a Box<RawValue> from the body. If deserialization fails, return
                 // the body as a string in the error. The conversion to String
                 // is lossy and may not cover all the bytes in the body.
+                this.limits.check(&body).map_err(TransportErrorKind::response_limit)?;
                 serde_json::from_slice(&body).map_err(|err| {
                     TransportError::deser_err(err, String::from_utf8_lossy(body.as_ref()))
                 })
@@ -139,6 +234,39 @@ where
     }
 }
 
+/// Read a response body, failing as soon as it exceeds the maximum size of the limits.
+async fn read_body<B>(body: B, limits: ResponseLimits) -> Result<Bytes, TransportError>
+where
+    B: BodyExt + Send,
+    B::Error: std::error::Error + Send + Sync + 'static,
+{
+    if limits.max_size().is_none() {
+        return Ok(body.collect().await.map_err(TransportErrorKind::custom)?.to_bytes());
+    }
+
+    // the lower bound is the announced `Content-Length`, if any
+    let size_hint = usize::try_from(body.size_hint().lower()).unwrap_or(usize::MAX);
+    limits.check_size(size_hint).map_err(TransportErrorKind::response_limit)?;
+
+    let mut body = std::pin::pin!(body);
+    let mut bytes = Vec::new();
+    while let Some(frame) = body.frame().await {
+        let Ok(mut data) = frame.map_err(TransportErrorKind::custom)?.into_data() else {
+            continue;
+        };
+        limits
+            .check_size(bytes.len() + data.remaining())
+            .map_err(TransportErrorKind::response_limit)?;
+        while data.has_remaining() {
+            let chunk = data.chunk();
+            let len = chunk.len();
+            bytes.extend_from_slice(chunk);
+            data.advance(len);
+        }
+    }
+    Ok(bytes.into())
+}
+
 impl TransportConnect for HttpConnect<HyperTransport> {
     type Transport = HyperTransport;
 
@@ -150,9 +278,10 @@ impl TransportConnect for HttpConnect<HyperTransport> {
         &'a self,
     ) -> alloy_transport::Pbf<'b, Self::Transport, TransportError> {
         Box::pin(async move {
-            let hyper_t = HyperClient::new();
+            let hyper_t = HyperClient::with_config(&self.config);
 
-            Ok(Http::with_client(hyper_t, self.url.clone()))
+            Ok(Http::with_client(hyper_t, self.url.clone())
+                .with_response_limits(self.config.response_limits()))
         })
     }
 }
@@ -196,3 +325,25 @@ where
         self.request_hyper(req)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_transport::ResponseLimitError;
+
+    #[tokio::test]
+    async fn limits_response_body() {
+        let body = || Full::new(Bytes::from_static(b"[1,2,3]"));
+        let limits = ResponseLimits::new().with_max_size(7);
+        assert_eq!(read_body(body(), limits).await.unwrap(), "[1,2,3]");
+
+        let limits = ResponseLimits::new().with_max_size(6);
+        let err = read_body(body(), limits).await.unwrap_err();
+        assert!(matches!(
+            err,
+            TransportError::Transport(TransportErrorKind::ResponseLimit(
+                ResponseLimitError::TooLarge { size: 7, max: 6 }
+            ))
+        ));
+    }
+}
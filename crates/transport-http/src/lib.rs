@@ -15,6 +15,11 @@ mod reqwest_transport;
 #[doc(inline)]
 pub use reqwest_transport::*;
 
+#[cfg(all(not(target_arch = "wasm32"), feature = "reqwest"))]
+mod doh;
+#[cfg(all(not(target_arch = "wasm32"), feature = "reqwest"))]
+pub use doh::DohResolver;
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
 pub use hyper;
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
@@ -29,13 +34,19 @@ pub use layers::{AuthLayer, AuthService};
 mod hyper_transport;
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
 #[doc(inline)]
-pub use hyper_transport::{HyperClient, HyperResponse, HyperResponseFut, HyperTransport};
+pub use hyper_transport::{
+    HyperClient, HyperResolver, HyperResponse, HyperResponseFut, HyperTransport,
+};
 
-use alloy_transport::utils::guess_local_url;
+use alloy_transport::{utils::guess_local_url, Resolver, ResponseLimits};
 use core::str::FromStr;
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Duration};
 use url::Url;
 
+/// The HTTP header the correlation ID of a request packet is sent in, see
+/// [`RequestMeta::correlation_id`](alloy_json_rpc::RequestMeta::correlation_id).
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
 /// Connection details for an HTTP transport.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[doc(hidden)]
@@ -43,19 +54,180 @@ pub struct HttpConnect<T> {
     /// The URL to connect to.
     url: Url,
 
+    /// The settings of the HTTP client.
+    config: HttpConfig,
+
     _pd: PhantomData<T>,
 }
 
 impl<T> HttpConnect<T> {
     /// Create a new [`HttpConnect`] with the given URL.
     pub const fn new(url: Url) -> Self {
-        Self { url, _pd: PhantomData }
+        Self { url, config: HttpConfig::new(), _pd: PhantomData }
+    }
+
+    /// Set the settings of the HTTP client.
+    pub fn with_config(mut self, config: HttpConfig) -> Self {
+        self.config = config;
+        self
     }
 
     /// Get a reference to the URL.
     pub const fn url(&self) -> &Url {
         &self.url
     }
+
+    /// Get a reference to the settings of the HTTP client.
+    pub const fn config(&self) -> &HttpConfig {
+        &self.config
+    }
+}
+
+/// Connection settings for the HTTP transport clients.
+///
+/// By default HTTP/2 is used when the server supports it, which is negotiated with ALPN over TLS.
+/// Plaintext connections use HTTP/1 unless [`with_http2_prior_knowledge`] is set. Unset options
+/// keep the defaults of the client.
+///
+/// [`with_http2_prior_knowledge`]: Self::with_http2_prior_knowledge
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HttpConfig {
+    http1_only: bool,
+    http2_prior_knowledge: bool,
+    http2_max_concurrent_streams: Option<usize>,
+    http2_keep_alive_interval: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+    resolver: Option<Resolver>,
+    response_limits: ResponseLimits,
+}
+
+impl HttpConfig {
+    /// Create a new [`HttpConfig`] with the default settings.
+    pub const fn new() -> Self {
+        Self {
+            http1_only: false,
+            http2_prior_knowledge: false,
+            http2_max_concurrent_streams: None,
+            http2_keep_alive_interval: None,
+            pool_idle_timeout: None,
+            pool_max_idle_per_host: None,
+            tcp_keepalive: None,
+            resolver: None,
+            response_limits: ResponseLimits::new(),
+        }
+    }
+
+    /// Set whether HTTP/2 may be used. If disabled, only HTTP/1 is used.
+    pub const fn with_http2(mut self, enabled: bool) -> Self {
+        self.http1_only = !enabled;
+        if !enabled {
+            self.http2_prior_knowledge = false;
+        }
+        self
+    }
+
+    /// Only use HTTP/2, without negotiating it first. This enables HTTP/2 on plaintext
+    /// connections, but fails against servers that do not support it.
+    pub const fn with_http2_prior_knowledge(mut self) -> Self {
+        self.http1_only = false;
+        self.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Set the maximum number of concurrent streams opened on an HTTP/2 connection, until the
+    /// server advertises its own limit.
+    ///
+    /// Only used by the `hyper` client, as `reqwest` always uses the limit of the server.
+    pub const fn with_http2_max_concurrent_streams(mut self, max: usize) -> Self {
+        self.http2_max_concurrent_streams = Some(max);
+        self
+    }
+
+    /// Set the interval of HTTP/2 ping frames that keep the connection alive.
+    pub const fn with_http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Set how long idle connections are kept in the connection pool.
+    pub const fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum number of idle connections kept in the pool for each host.
+    pub const fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the TCP keepalive duration of the connections.
+    pub const fn with_tcp_keepalive(mut self, keepalive: Duration) -> Self {
+        self.tcp_keepalive = Some(keepalive);
+        self
+    }
+
+    /// Set the resolver of the hostname of the endpoint, instead of the system resolver.
+    ///
+    /// Used by the `reqwest` client. The `hyper` client takes it with
+    /// [`HyperResolver`](crate::HyperResolver) instead, see there.
+    pub fn with_resolver(mut self, resolver: impl Into<Resolver>) -> Self {
+        self.resolver = Some(resolver.into());
+        self
+    }
+
+    /// Set the limits on the responses of the endpoint, see [`ResponseLimits`].
+    pub const fn with_response_limits(mut self, limits: ResponseLimits) -> Self {
+        self.response_limits = limits;
+        self
+    }
+
+    /// Returns whether HTTP/2 may be used.
+    pub const fn http2(&self) -> bool {
+        !self.http1_only
+    }
+
+    /// Returns whether only HTTP/2 is used, without negotiating it first.
+    pub const fn http2_prior_knowledge(&self) -> bool {
+        self.http2_prior_knowledge
+    }
+
+    /// Returns the maximum number of concurrent HTTP/2 streams, if set.
+    pub const fn http2_max_concurrent_streams(&self) -> Option<usize> {
+        self.http2_max_concurrent_streams
+    }
+
+    /// Returns the interval of HTTP/2 ping frames, if set.
+    pub const fn http2_keep_alive_interval(&self) -> Option<Duration> {
+        self.http2_keep_alive_interval
+    }
+
+    /// Returns how long idle connections are kept in the pool, if set.
+    pub const fn pool_idle_timeout(&self) -> Option<Duration> {
+        self.pool_idle_timeout
+    }
+
+    /// Returns the maximum number of idle connections per host, if set.
+    pub const fn pool_max_idle_per_host(&self) -> Option<usize> {
+        self.pool_max_idle_per_host
+    }
+
+    /// Returns the TCP keepalive duration, if set.
+    pub const fn tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive
+    }
+
+    /// Returns the resolver of the hostname of the endpoint, if set.
+    pub const fn resolver(&self) -> Option<&Resolver> {
+        self.resolver.as_ref()
+    }
+
+    /// Returns the limits on the responses of the endpoint.
+    pub const fn response_limits(&self) -> ResponseLimits {
+        self.response_limits
+    }
 }
 
 impl<T> FromStr for HttpConnect<T> {
@@ -81,12 +253,25 @@ impl<T> FromStr for HttpConnect<T> {
 pub struct Http<T> {
     client: T,
     url: Url,
+    limits: ResponseLimits,
 }
 
 impl<T> Http<T> {
     /// Create a new [`Http`] transport with a custom client.
     pub const fn with_client(client: T, url: Url) -> Self {
-        Self { client, url }
+        Self { client, url, limits: ResponseLimits::new() }
+    }
+
+    /// Set the limits on the responses of the endpoint. Responses exceeding them are rejected
+    /// with a [`ResponseLimitError`](alloy_transport::ResponseLimitError) before they are parsed.
+    pub const fn with_response_limits(mut self, limits: ResponseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set the limits on the responses of the endpoint.
+    pub fn set_response_limits(&mut self, limits: ResponseLimits) {
+        self.limits = limits;
     }
 
     /// Set the URL.
@@ -117,4 +302,9 @@ impl<T> Http<T> {
     pub fn url(&self) -> &str {
         self.url.as_ref()
     }
+
+    /// Get the limits on the responses of the endpoint.
+    pub const fn response_limits(&self) -> ResponseLimits {
+        self.limits
+    }
 }
@@ -1,9 +1,16 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::HttpConfig;
 use crate::{Http, HttpConnect};
 use alloy_json_rpc::{RequestPacket, ResponsePacket};
+#[cfg(not(target_arch = "wasm32"))]
+use alloy_transport::Resolver;
 use alloy_transport::{
-    utils::guess_local_url, TransportConnect, TransportError, TransportErrorKind, TransportFut,
+    utils::guess_local_url, ResponseLimits, TransportConnect, TransportError, TransportErrorKind,
+    TransportFut,
 };
 use std::task;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{net::SocketAddr, sync::Arc};
 use tower::Service;
 use tracing::{debug, debug_span, trace, Instrument};
 use url::Url;
@@ -27,29 +34,96 @@ impl TransportConnect for ReqwestConnect {
     fn get_transport<'a: 'b, 'b>(
         &'a self,
     ) -> alloy_transport::Pbf<'b, Self::Transport, TransportError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Box::pin(async move { Http::with_config(self.url.clone(), &self.config) });
+        #[cfg(target_arch = "wasm32")]
         Box::pin(async move { Ok(Http::with_client(Client::new(), self.url.clone())) })
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl HttpConfig {
+    /// Create a [`reqwest::ClientBuilder`] with these settings, which can be customized further.
+    pub fn reqwest_builder(&self) -> reqwest::ClientBuilder {
+        let mut builder = Client::builder();
+        if self.http1_only {
+            builder = builder.http1_only();
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder = builder.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(keepalive) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(keepalive);
+        }
+        if let Some(resolver) = &self.resolver {
+            builder = builder.dns_resolver(Arc::new(ReqwestResolver(resolver.clone())));
+        }
+        builder
+    }
+
+    /// Build a [`reqwest`] client with these settings.
+    pub fn build_reqwest(&self) -> Result<Client, TransportError> {
+        self.reqwest_builder().build().map_err(TransportErrorKind::custom)
+    }
+}
+
+/// Adapts a [`Resolver`] to the resolver of [`reqwest`].
+#[cfg(not(target_arch = "wasm32"))]
+struct ReqwestResolver(Resolver);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl reqwest::dns::Resolve for ReqwestResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let resolver = self.0.clone();
+        Box::pin(async move {
+            let addrs = resolver.resolve(name.as_str()).await?;
+            // the port is set by the client
+            let addrs: reqwest::dns::Addrs =
+                Box::new(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
 impl Http<Client> {
     /// Create a new [`Http`] transport.
     pub fn new(url: Url) -> Self {
-        Self { client: Default::default(), url }
+        Self::with_client(Default::default(), url)
+    }
+
+    /// Create a new [`Http`] transport with a client built from the given settings.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_config(url: Url, config: &HttpConfig) -> Result<Self, TransportError> {
+        config.build_reqwest().map(|client| {
+            Self::with_client(client, url).with_response_limits(config.response_limits())
+        })
     }
 
     /// Make a request.
     fn request_reqwest(&self, req: RequestPacket) -> TransportFut<'static> {
         let this = self.clone();
-        let span: tracing::Span = debug_span!("ReqwestTransport", url = %self.url);
+        let span: tracing::Span = debug_span!("ReqwestTransport", url = %self.url, correlation_id = tracing::field::Empty);
+        if let Some(correlation_id) = req.correlation_id() {
+            span.record("correlation_id", correlation_id);
+        }
         Box::pin(
             async move {
-                let resp = this
-                    .client
-                    .post(this.url)
-                    .json(&req)
-                    .send()
-                    .await
-                    .map_err(TransportErrorKind::custom)?;
+                let mut request = this.client.post(this.url).json(&req);
+                if let Some(Ok(correlation_id)) =
+                    req.correlation_id().map(reqwest::header::HeaderValue::from_str)
+                {
+                    request = request.header(crate::CORRELATION_ID_HEADER, correlation_id);
+                }
+                let resp = request.send().await.map_err(TransportErrorKind::custom)?;
                 let status = resp.status();
 
                 debug!(%status, "received response from server");
@@ -57,7 +131,7 @@ impl Http<Client> {
                 // Unpack data from the response body. We do this regardless of
                 // the status code, as we want to return the error in the body
                 // if there is one.
-                let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+                let body = read_body(resp, this.limits).await?;
 
                 debug!(bytes = body.len(), "retrieved response body. Use `trace` for full body");
                 trace!(body = %String::from_utf8_lossy(&body), "response body");
@@ -72,6 +146,7 @@ impl Http<Client> {
                 // Deserialize a Box<RawValue> from the body. If deserialization fails, return
                 // the body as a string in the error. The conversion to String
                 // is lossy and may not cover all the bytes in the body.
+                this.limits.check(&body).map_err(TransportErrorKind::response_limit)?;
                 serde_json::from_slice(&body)
                     .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&body)))
             }
@@ -80,6 +155,44 @@ impl Http<Client> {
     }
 }
 
+/// Read the body of a response, failing as soon as it exceeds the maximum size of the limits.
+async fn read_body(
+    resp: reqwest::Response,
+    limits: ResponseLimits,
+) -> Result<Vec<u8>, TransportError> {
+    if limits.max_size().is_none() {
+        return resp.bytes().await.map(Into::into).map_err(TransportErrorKind::custom);
+    }
+
+    // reject responses announcing their size before reading them
+    if let Some(len) = resp.content_length() {
+        limits
+            .check_size(usize::try_from(len).unwrap_or(usize::MAX))
+            .map_err(TransportErrorKind::response_limit)?;
+    }
+
+    // the body can't be streamed in the browser
+    #[cfg(target_arch = "wasm32")]
+    {
+        let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+        limits.check_size(body.len()).map_err(TransportErrorKind::response_limit)?;
+        Ok(body.into())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut resp = resp;
+        let mut body = Vec::new();
+        while let Some(chunk) = resp.chunk().await.map_err(TransportErrorKind::custom)? {
+            limits
+                .check_size(body.len() + chunk.len())
+                .map_err(TransportErrorKind::response_limit)?;
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+}
+
 impl Service<RequestPacket> for Http<reqwest::Client> {
     type Response = ResponsePacket;
     type Error = TransportError;
@@ -12,9 +12,10 @@ extern crate tracing;
 use bytes::{Buf, BytesMut};
 use futures::{ready, StreamExt};
 use interprocess::local_socket::{tokio::prelude::*, Name};
-use std::task::Poll::Ready;
+use serde_json::value::RawValue;
+use std::{io::IoSlice, task::Poll::Ready};
 use tokio::{
-    io::{AsyncRead, AsyncWriteExt},
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt},
     select,
 };
 use tokio_util::io::poll_read_buf;
@@ -50,6 +51,7 @@ impl IpcBackend {
         let fut = async move {
             let (read, mut writer) = self.stream.split();
             let mut read = ReadJsonStream::new(read).fuse();
+            let mut pending = Vec::with_capacity(MAX_WRITE_BATCH);
 
             let err = loop {
                 select! {
@@ -57,8 +59,17 @@ impl IpcBackend {
                     item = self.interface.recv_from_frontend() => {
                         match item {
                             Some(msg) => {
-                                let bytes = msg.get();
-                                if let Err(err) = writer.write_all(bytes.as_bytes()).await {
+                                // write the requests that are already queued along with this one
+                                pending.push(msg);
+                                while pending.len() < MAX_WRITE_BATCH {
+                                    match self.interface.try_recv_from_frontend() {
+                                        Some(msg) => pending.push(msg),
+                                        None => break,
+                                    }
+                                }
+                                let res = write_all_vectored(&mut writer, &pending).await;
+                                pending.clear();
+                                if let Err(err) = res {
                                     error!(%err, "Failed to write to IPC socket");
                                     break true;
                                 }
@@ -96,9 +107,96 @@ impl IpcBackend {
     }
 }
 
+/// Maximum number of queued requests written to the socket at once.
+const MAX_WRITE_BATCH: usize = 64;
+
+/// Writes all messages to the writer, using vectored writes so that a batch of messages can be
+/// written with a single syscall.
+async fn write_all_vectored<W>(writer: &mut W, msgs: &[Box<RawValue>]) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut slices = Vec::with_capacity(msgs.len());
+    // the first message that is not fully written, and how much of it is written
+    let (mut index, mut offset) = (0, 0);
+    while index < msgs.len() {
+        slices.clear();
+        slices.push(IoSlice::new(&msgs[index].get().as_bytes()[offset..]));
+        slices.extend(msgs[index + 1..].iter().map(|msg| IoSlice::new(msg.get().as_bytes())));
+
+        let mut written = writer.write_vectored(&slices).await?;
+        if written == 0 {
+            return Err(std::io::ErrorKind::WriteZero.into());
+        }
+        while index < msgs.len() {
+            let remaining = msgs[index].get().len() - offset;
+            if written < remaining {
+                offset += written;
+                break;
+            }
+            written -= remaining;
+            index += 1;
+            offset = 0;
+        }
+    }
+    writer.flush().await
+}
+
 /// Default capacity for the IPC buffer.
 const CAPACITY: usize = 4096;
 
+/// Incremental scanner for the boundaries of concatenated JSON values.
+///
+/// IPC messages are not length-prefixed or delimited, so the end of each message is found by
+/// tracking the nesting depth of objects and arrays. The scan state is kept across reads, so each
+/// byte is scanned once no matter how many reads a message is split across.
+#[derive(Debug, Default)]
+struct JsonFramer {
+    /// Number of bytes of the current value that have been scanned.
+    scanned: usize,
+    /// Nesting depth at the end of the scanned bytes.
+    depth: usize,
+    /// Whether the scanned bytes end inside a string.
+    in_string: bool,
+    /// Whether the scanned bytes end with an escape character inside a string.
+    escaped: bool,
+}
+
+impl JsonFramer {
+    /// Scans the buffer, which starts at the beginning of a value, and returns the length of the
+    /// value if it is complete.
+    ///
+    /// Returns an error if the value is not an object or array.
+    fn next_frame(&mut self, buf: &[u8]) -> std::result::Result<Option<usize>, ()> {
+        for (i, &byte) in buf.iter().enumerate().skip(self.scanned) {
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'{' | b'[' => self.depth += 1,
+                _ if self.depth == 0 => return Err(()),
+                b'}' | b']' => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        *self = Self::default();
+                        return Ok(Some(i + 1));
+                    }
+                }
+                b'"' => self.in_string = true,
+                _ => {}
+            }
+        }
+        self.scanned = buf.len();
+        Ok(None)
+    }
+}
+
 /// A stream of JSON-RPC items, read from an [`AsyncRead`] stream.
 #[derive(Debug)]
 #[pin_project::pin_project]
@@ -106,15 +204,15 @@ pub struct ReadJsonStream<T> {
     /// The underlying reader.
     #[pin]
     reader: T,
-    /// A buffer for reading data from the reader.
+    /// A buffer for reading data from the reader, reused across messages.
     buf: BytesMut,
-    /// Whether the buffer has been drained.
-    drained: bool,
+    /// The scan state of the partial message at the start of the buffer.
+    framer: JsonFramer,
 }
 
 impl<T: AsyncRead> ReadJsonStream<T> {
     fn new(reader: T) -> Self {
-        Self { reader, buf: BytesMut::with_capacity(CAPACITY), drained: true }
+        Self { reader, buf: BytesMut::with_capacity(CAPACITY), framer: JsonFramer::default() }
     }
 }
 
@@ -134,50 +232,50 @@ impl<T: AsyncRead> futures::stream::Stream for ReadJsonStream<T> {
         let mut this = self.project();
 
         loop {
-            // try decoding from the buffer, but only if we have new data
-            if !*this.drained {
-                debug!(buf_len = this.buf.len(), "Deserializing buffered IPC data");
-                let mut de = serde_json::Deserializer::from_slice(this.buf.as_ref()).into_iter();
-
-                let item = de.next();
-
-                // advance the buffer
-                this.buf.advance(de.byte_offset());
+            // skip whitespace between messages
+            if this.framer.scanned == 0 {
+                let start = this.buf.iter().position(|b| !b.is_ascii_whitespace());
+                this.buf.advance(start.unwrap_or(this.buf.len()));
+            }
 
-                match item {
-                    Some(Ok(response)) => {
-                        return Ready(Some(response));
-                    }
-                    Some(Err(err)) => {
-                        if err.is_data() {
+            // decode the next message from the buffer, once it is complete
+            match this.framer.next_frame(this.buf.as_ref()) {
+                Ok(Some(len)) => {
+                    let frame = this.buf.split_to(len);
+                    match serde_json::from_slice(&frame) {
+                        Ok(response) => return Ready(Some(response)),
+                        Err(err) if err.is_data() => {
+                            warn!(%err, "IPC message is not a JSON-RPC item, skipping");
                             trace!(
-                                buffer = %String::from_utf8_lossy(this.buf.as_ref()),
-                                "IPC buffer contains invalid JSON data",
+                                message = %String::from_utf8_lossy(&frame),
+                                "IPC message is not a JSON-RPC item",
                             );
-
-                            // this happens if the deserializer is unable to decode a partial object
-                            *this.drained = true;
-                        } else if err.is_eof() {
-                            trace!("partial object in IPC buffer");
-                            // nothing decoded
-                            *this.drained = true;
-                        } else {
-                            error!(%err, "IPC response contained invalid JSON. Buffer contents will be logged at trace level");
+                            continue;
+                        }
+                        Err(err) => {
+                            error!(%err, "IPC response contained invalid JSON. Message contents will be logged at trace level");
                             trace!(
-                                buffer = %String::from_utf8_lossy(this.buf.as_ref()),
-                                "IPC response contained invalid JSON. NOTE: Buffer contents do not include invalid utf8.",
+                                message = %String::from_utf8_lossy(&frame),
+                                "IPC response contained invalid JSON. NOTE: Message contents do not include invalid utf8.",
                             );
-
                             return Ready(None);
                         }
                     }
-                    None => {
-                        // nothing decoded
-                        *this.drained = true;
-                    }
+                }
+                Ok(None) => trace!(buf_len = this.buf.len(), "partial object in IPC buffer"),
+                Err(()) => {
+                    error!("IPC response contained invalid JSON. Buffer contents will be logged at trace level");
+                    trace!(
+                        buffer = %String::from_utf8_lossy(this.buf.as_ref()),
+                        "IPC response contained invalid JSON. NOTE: Buffer contents do not include invalid utf8.",
+                    );
+                    return Ready(None);
                 }
             }
 
+            // make room for a full read, reclaiming the space of consumed messages if possible
+            this.buf.reserve(CAPACITY);
+
             // read more data into the buffer
             match ready!(poll_read_buf(this.reader.as_mut(), cx, &mut this.buf)) {
                 Ok(0) => {
@@ -189,8 +287,6 @@ impl<T: AsyncRead> futures::stream::Stream for ReadJsonStream<T> {
                 }
                 Ok(data_len) => {
                     debug!(%data_len, "Read data from IPC socket");
-                    // can try decoding again
-                    *this.drained = false;
                 }
                 Err(err) => {
                     error!(%err, "Failed to read from IPC socket, shutting down");
@@ -280,4 +376,45 @@ mod tests {
         let obj = reader.next().await;
         assert!(obj.is_some());
     }
+
+    #[tokio::test]
+    async fn test_multiple_in_one_read() {
+        let mock = tokio_test::io::Builder::new()
+            .read(b"{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":\"}{[\\\"\"}\n {\"jsonrpc\":\"2.0\",\"id\":2,\"result\":[{}]}\r\n[{\"jsonrpc\":\"2.0\",\"id\":3,")
+            .wait(std::time::Duration::from_millis(1))
+            .read(b"\"result\":null}]")
+            .build();
+
+        let mut reader = ReadJsonStream::new(mock);
+        let first = reader.next().await.unwrap();
+        let second = reader.next().await.unwrap();
+        assert!(matches!(first, alloy_json_rpc::PubSubItem::Response(_)));
+        assert!(matches!(second, alloy_json_rpc::PubSubItem::Response(_)));
+        // batch responses are not pubsub items and are skipped
+        assert!(reader.next().await.is_none());
+    }
+
+    #[test]
+    fn test_framer() {
+        let mut framer = JsonFramer::default();
+        let msg = br#"{"a":"\\","b":["}",{"c":"\""}]}"#;
+        assert_eq!(framer.next_frame(&msg[..10]), Ok(None));
+        assert_eq!(framer.next_frame(&msg[..20]), Ok(None));
+        assert_eq!(framer.next_frame(msg), Ok(Some(msg.len())));
+        assert_eq!(framer.scanned, 0);
+        assert_eq!(framer.next_frame(b"null"), Err(()));
+    }
+
+    #[tokio::test]
+    async fn test_vectored_write() {
+        let msgs = ["{\"id\":1}", "{\"id\":2}", "{\"id\":3}"]
+            .map(|msg| RawValue::from_string(msg.to_owned()).unwrap());
+        let mut mock = tokio_test::io::Builder::new()
+            // partial writes across message boundaries
+            .write(b"{\"id\":1}{\"i")
+            .write(b"d\":2}")
+            .write(b"{\"id\":3}")
+            .build();
+        write_all_vectored(&mut mock, &msgs).await.unwrap();
+    }
 }
@@ -10,11 +10,12 @@
 extern crate tracing;
 
 use alloy_pubsub::ConnectionInterface;
+use alloy_transport::ResponseLimits;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod native;
 #[cfg(not(target_arch = "wasm32"))]
-pub use native::WsConnect;
+pub use native::{WsConnect, WsConnector};
 
 #[cfg(not(target_arch = "wasm32"))]
 use rustls as _;
@@ -22,7 +23,7 @@ use rustls as _;
 #[cfg(target_arch = "wasm32")]
 mod wasm;
 #[cfg(target_arch = "wasm32")]
-pub use wasm::WsConnect;
+pub use wasm::{WsConnect, WsConnector};
 
 /// An ongoing connection to a backend.
 ///
@@ -37,6 +38,9 @@ pub struct WsBackend<T> {
 
     /// The interface to the connection.
     pub(crate) interface: ConnectionInterface,
+
+    /// The limits on the messages of the server.
+    pub(crate) limits: ResponseLimits,
 }
 
 impl<T> WsBackend<T> {
@@ -45,6 +49,11 @@ impl<T> WsBackend<T> {
     pub fn handle_text(&mut self, text: &str) -> Result<(), ()> {
         trace!(%text, "received message from websocket");
 
+        if let Err(err) = self.limits.check(text.as_bytes()) {
+            error!(%err, "rejecting message exceeding the response limits");
+            return Err(());
+        }
+
         match serde_json::from_str(text) {
             Ok(item) => {
                 trace!(?item, "deserialized message");
@@ -1,16 +1,21 @@
 use crate::WsBackend;
 use alloy_pubsub::PubSubConnect;
-use alloy_transport::{utils::Spawnable, Authorization, TransportErrorKind, TransportResult};
+use alloy_transport::{
+    utils::Spawnable, Authorization, Resolver, ResponseLimits, TransportErrorKind, TransportResult,
+};
 use futures::{SinkExt, StreamExt};
 use serde_json::value::RawValue;
-use std::time::Duration;
-use tokio::time::sleep;
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+use tokio::{net::TcpStream, time::sleep};
 use tokio_tungstenite::{
-    tungstenite::{self, client::IntoClientRequest, Message},
+    tungstenite::{self, client::IntoClientRequest, protocol::WebSocketConfig, Message},
     MaybeTlsStream, WebSocketStream,
 };
 
-type TungsteniteStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+type TungsteniteStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
 const KEEPALIVE: u64 = 10;
 
@@ -34,6 +39,54 @@ impl WsConnect {
     pub fn with_auth<S: Into<String>>(url: S, auth: Option<Authorization>) -> Self {
         Self { url: url.into(), auth }
     }
+
+    /// Resolves the hostname of the URL with the resolver, instead of the system resolver.
+    pub fn with_resolver(self, resolver: impl Into<Resolver>) -> WsConnector {
+        WsConnector::from(self).with_resolver(resolver)
+    }
+
+    /// Sets the limits on the messages of the server, see [`WsConnector::with_limits`].
+    pub fn with_limits(self, limits: ResponseLimits) -> WsConnector {
+        WsConnector::from(self).with_limits(limits)
+    }
+}
+
+/// A websocket connection with additional options, created by the builder methods of
+/// [`WsConnect`], e.g. [`WsConnect::with_resolver`] or [`WsConnect::with_limits`].
+#[derive(Clone, Debug)]
+pub struct WsConnector {
+    connect: WsConnect,
+    resolver: Option<Resolver>,
+    limits: ResponseLimits,
+}
+
+impl From<WsConnect> for WsConnector {
+    fn from(connect: WsConnect) -> Self {
+        Self { connect, resolver: None, limits: ResponseLimits::new() }
+    }
+}
+
+impl WsConnector {
+    /// Returns the connection details.
+    pub const fn connect_info(&self) -> &WsConnect {
+        &self.connect
+    }
+
+    /// Resolves the hostname of the URL with the resolver, instead of the system resolver.
+    pub fn with_resolver(mut self, resolver: impl Into<Resolver>) -> Self {
+        self.resolver = Some(resolver.into());
+        self
+    }
+
+    /// Sets the limits on the messages of the server.
+    ///
+    /// Larger messages are refused while they are read. Messages exceeding the other limits are
+    /// rejected before they are parsed. Either way the connection is dropped, as the requests
+    /// awaiting the message can't be told apart.
+    pub const fn with_limits(mut self, limits: ResponseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 }
 
 impl IntoClientRequest for WsConnect {
@@ -56,13 +109,35 @@ impl PubSubConnect for WsConnect {
     }
 
     async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
-        let request = self.clone().into_client_request();
+        WsConnector::from(self.clone()).connect().await
+    }
+}
+
+impl PubSubConnect for WsConnector {
+    fn is_local(&self) -> bool {
+        self.connect.is_local()
+    }
+
+    async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
+        let limits = self.limits;
+        let request = self.connect.clone().into_client_request();
         let req = request.map_err(TransportErrorKind::custom)?;
-        let (socket, _) =
-            tokio_tungstenite::connect_async(req).await.map_err(TransportErrorKind::custom)?;
+        let config = limits.max_size().map(|max| WebSocketConfig {
+            max_message_size: Some(max),
+            max_frame_size: Some(max),
+            ..Default::default()
+        });
+        let (socket, _) = match &self.resolver {
+            Some(resolver) => {
+                let stream = connect_resolved(resolver, req.uri()).await?;
+                tokio_tungstenite::client_async_tls_with_config(req, stream, config, None).await
+            }
+            None => tokio_tungstenite::connect_async_with_config(req, config, false).await,
+        }
+        .map_err(TransportErrorKind::custom)?;
 
         let (handle, interface) = alloy_pubsub::ConnectionHandle::new();
-        let backend = WsBackend { socket, interface };
+        let backend = WsBackend { socket, interface, limits };
 
         backend.spawn();
 
@@ -70,6 +145,27 @@ impl PubSubConnect for WsConnect {
     }
 }
 
+/// Connects to the host of the URI, resolving it with the resolver unless it is an IP address,
+/// and trying each address in order.
+async fn connect_resolved(resolver: &Resolver, uri: &http::Uri) -> TransportResult<TcpStream> {
+    let host = uri.host().ok_or_else(|| TransportErrorKind::custom_str("URL without host"))?;
+    let port = uri.port_u16().unwrap_or(if uri.scheme_str() == Some("wss") { 443 } else { 80 });
+    // IPv6 addresses are bracketed in URIs
+    let addrs = match host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+        Ok(addr) => vec![addr],
+        Err(_) => resolver.resolve(host).await?,
+    };
+
+    let mut last_err = None;
+    for addr in addrs {
+        match TcpStream::connect(SocketAddr::new(addr, port)).await {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(TransportErrorKind::custom(last_err.expect("resolved addresses are not empty")))
+}
+
 impl WsBackend<TungsteniteStream> {
     /// Handle a message from the server.
     #[allow(clippy::result_unit_err)]
@@ -1,6 +1,6 @@
 use super::WsBackend;
 use alloy_pubsub::PubSubConnect;
-use alloy_transport::{utils::Spawnable, TransportErrorKind, TransportResult};
+use alloy_transport::{utils::Spawnable, ResponseLimits, TransportErrorKind, TransportResult};
 use futures::{
     sink::SinkExt,
     stream::{Fuse, StreamExt},
@@ -15,17 +15,65 @@ pub struct WsConnect {
     pub url: String,
 }
 
+impl WsConnect {
+    /// Sets the limits on the messages of the server, see [`WsConnector::with_limits`].
+    pub fn with_limits(self, limits: ResponseLimits) -> WsConnector {
+        WsConnector::from(self).with_limits(limits)
+    }
+}
+
 impl PubSubConnect for WsConnect {
     fn is_local(&self) -> bool {
         alloy_transport::utils::guess_local_url(&self.url)
     }
 
     async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
-        let socket =
-            WsMeta::connect(&self.url, None).await.map_err(TransportErrorKind::custom)?.1.fuse();
+        WsConnector::from(self.clone()).connect().await
+    }
+}
+
+/// Connection info for the websocket, with the options that can't be set on a plain
+/// [`WsConnect`], e.g. [`WsConnect::with_limits`].
+#[derive(Clone, Debug)]
+pub struct WsConnector {
+    connect: WsConnect,
+    limits: ResponseLimits,
+}
+
+impl From<WsConnect> for WsConnector {
+    fn from(connect: WsConnect) -> Self {
+        Self { connect, limits: ResponseLimits::new() }
+    }
+}
+
+impl WsConnector {
+    /// Returns the connection info.
+    pub const fn connect_info(&self) -> &WsConnect {
+        &self.connect
+    }
+
+    /// Sets the limits on the messages of the server. Messages exceeding them are rejected
+    /// before they are parsed, and the connection is dropped.
+    pub const fn with_limits(mut self, limits: ResponseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+impl PubSubConnect for WsConnector {
+    fn is_local(&self) -> bool {
+        self.connect.is_local()
+    }
+
+    async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
+        let socket = WsMeta::connect(&self.connect.url, None)
+            .await
+            .map_err(TransportErrorKind::custom)?
+            .1
+            .fuse();
 
         let (handle, interface) = alloy_pubsub::ConnectionHandle::new();
-        let backend = WsBackend { socket, interface };
+        let backend = WsBackend { socket, interface, limits: self.limits };
 
         backend.spawn();
 
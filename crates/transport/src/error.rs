@@ -1,7 +1,8 @@
+use crate::layers::{RateLimitRetryPolicy, RetryPolicy};
 use alloy_json_rpc::{ErrorPayload, Id, RpcError, RpcResult};
 use serde::Deserialize;
 use serde_json::value::RawValue;
-use std::{error::Error as StdError, fmt::Debug};
+use std::{error::Error as StdError, fmt::Debug, time::Duration};
 use thiserror::Error;
 
 /// A transport error is an [`RpcError`] containing a [`TransportErrorKind`].
@@ -36,6 +37,37 @@ pub enum TransportErrorKind {
     #[error("{0}")]
     HttpError(#[from] HttpError),
 
+    /// The client has been shut down.
+    #[error("client has been shut down")]
+    ClientClosed,
+
+    /// The request was rejected without being sent, because the circuit breaker of the endpoint
+    /// is open, see [`CircuitBreakerLayer`](crate::layers::CircuitBreakerLayer).
+    #[error("circuit breaker is open, retry in {retry_after:?}")]
+    CircuitOpen {
+        /// The time until the breaker lets probe requests through.
+        retry_after: Duration,
+    },
+
+    /// The response exceeded the [`ResponseLimits`](crate::ResponseLimits) of the transport.
+    #[error("{0}")]
+    ResponseLimit(#[from] crate::ResponseLimitError),
+
+    /// The request still failed after the maximum number of retries of the
+    /// [`RetryBackoffLayer`](crate::layers::RetryBackoffLayer).
+    #[error("Max retries exceeded {0}")]
+    RetriesExhausted(Box<TransportError>),
+
+    /// Not enough transports of a [`FallbackLayer`](crate::layers::FallbackLayer) returned the
+    /// same response to a request requiring a quorum.
+    #[error("{responses} responses without a quorum of {quorum} agreeing")]
+    QuorumNotReached {
+        /// The number of transports that have to agree.
+        quorum: usize,
+        /// The number of responses that were received.
+        responses: usize,
+    },
+
     /// Custom error.
     #[error("{0}")]
     Custom(#[source] Box<dyn StdError + Send + Sync + 'static>),
@@ -73,6 +105,31 @@ impl TransportErrorKind {
         RpcError::Transport(Self::PubsubUnavailable)
     }
 
+    /// Instantiate a new `TransportError::ClientClosed`.
+    pub const fn client_closed() -> TransportError {
+        RpcError::Transport(Self::ClientClosed)
+    }
+
+    /// Instantiate a new `TransportError::CircuitOpen`.
+    pub const fn circuit_open(retry_after: Duration) -> TransportError {
+        RpcError::Transport(Self::CircuitOpen { retry_after })
+    }
+
+    /// Instantiate a new `TransportError::ResponseLimit`.
+    pub const fn response_limit(err: crate::ResponseLimitError) -> TransportError {
+        RpcError::Transport(Self::ResponseLimit(err))
+    }
+
+    /// Instantiate a new `TransportError::RetriesExhausted`.
+    pub fn retries_exhausted(err: TransportError) -> TransportError {
+        RpcError::Transport(Self::RetriesExhausted(Box::new(err)))
+    }
+
+    /// Instantiate a new `TransportError::QuorumNotReached`.
+    pub const fn quorum_not_reached(quorum: usize, responses: usize) -> TransportError {
+        RpcError::Transport(Self::QuorumNotReached { quorum, responses })
+    }
+
     /// Instantiate a new `TransportError::HttpError`.
     pub const fn http_error(status: u16, body: String) -> TransportError {
         RpcError::Transport(Self::HttpError(HttpError { status, body }))
@@ -94,6 +151,17 @@ impl TransportErrorKind {
             _ => false,
         }
     }
+
+    /// Analyzes the [TransportErrorKind] and decides if the request was rejected by the rate limit
+    /// of the endpoint.
+    pub fn is_rate_limit_err(&self) -> bool {
+        match self {
+            Self::HttpError(http_err) => http_err.is_rate_limit_err(),
+            Self::RetriesExhausted(err) => err.is_rate_limit(),
+            Self::Custom(err) => err.to_string().contains("429 Too Many Requests"),
+            _ => false,
+        }
+    }
 }
 
 /// Type for holding HTTP errors such as 429 rate limit error.
@@ -119,13 +187,40 @@ impl HttpError {
     }
 }
 
+/// Machine-readable retry metadata of a [`TransportError`], see [`RpcErrorExt::retry_hints`].
+///
+/// This is what the [`RetryBackoffLayer`](crate::layers::RetryBackoffLayer) bases its decisions
+/// on, for applications that manage their own retries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetryHints {
+    /// Whether the request may succeed if it is sent again.
+    pub is_retryable: bool,
+    /// The backoff requested by the endpoint, if any.
+    pub suggested_backoff: Option<Duration>,
+    /// Whether the request was rejected by the rate limit of the endpoint.
+    pub is_rate_limit: bool,
+    /// Whether the node has not yet seen the requested block, e.g. because it is behind the other
+    /// nodes of a load balanced endpoint.
+    pub is_node_behind: bool,
+}
+
 /// Extension trait to implement methods for [`RpcError<TransportErrorKind, E>`].
-pub(crate) trait RpcErrorExt {
+pub trait RpcErrorExt {
     /// Analyzes whether to retry the request depending on the error.
     fn is_retryable(&self) -> bool;
 
     /// Fetches the backoff hint from the error message if present
-    fn backoff_hint(&self) -> Option<std::time::Duration>;
+    fn backoff_hint(&self) -> Option<Duration>;
+
+    /// Analyzes whether the request was rejected by the rate limit of the endpoint.
+    fn is_rate_limit(&self) -> bool;
+
+    /// Analyzes whether the node has not yet seen the requested block.
+    fn is_node_behind(&self) -> bool;
+
+    /// Returns the retry metadata of the error, as classified by the
+    /// [`RateLimitRetryPolicy`](crate::layers::RateLimitRetryPolicy).
+    fn retry_hints(&self) -> RetryHints;
 }
 
 impl RpcErrorExt for RpcError<TransportErrorKind> {
@@ -138,22 +233,7 @@ impl RpcErrorExt for RpcError<TransportErrorKind> {
             // the start.
             Self::SerError(_) => false,
             Self::DeserError { text, .. } => {
-                if let Ok(resp) = serde_json::from_str::<ErrorPayload>(text) {
-                    return resp.is_retry_err();
-                }
-
-                // some providers send invalid JSON RPC in the error case (no `id:u64`), but the
-                // text should be a `JsonRpcError`
-                #[derive(Deserialize)]
-                struct Resp {
-                    error: ErrorPayload,
-                }
-
-                if let Ok(resp) = serde_json::from_str::<Resp>(text) {
-                    return resp.error.is_retry_err();
-                }
-
-                false
+                error_payload(text).is_some_and(|payload| payload.is_retry_err())
             }
             Self::ErrorResp(err) => err.is_retry_err(),
             Self::NullResp => true,
@@ -161,29 +241,77 @@ impl RpcErrorExt for RpcError<TransportErrorKind> {
         }
     }
 
-    fn backoff_hint(&self) -> Option<std::time::Duration> {
-        if let Self::ErrorResp(resp) = self {
-            let data = resp.try_data_as::<serde_json::Value>();
-            if let Some(Ok(data)) = data {
-                // if daily rate limit exceeded, infura returns the requested backoff in the error
-                // response
-                let backoff_seconds = &data["rate"]["backoff_seconds"];
-                // infura rate limit error
-                if let Some(seconds) = backoff_seconds.as_u64() {
-                    return Some(std::time::Duration::from_secs(seconds));
-                }
-                if let Some(seconds) = backoff_seconds.as_f64() {
-                    return Some(std::time::Duration::from_secs(seconds as u64 + 1));
+    fn backoff_hint(&self) -> Option<Duration> {
+        match self {
+            Self::ErrorResp(resp) => {
+                let data = resp.try_data_as::<serde_json::Value>();
+                if let Some(Ok(data)) = data {
+                    // if daily rate limit exceeded, infura returns the requested backoff in the
+                    // error response
+                    let backoff_seconds = &data["rate"]["backoff_seconds"];
+                    // infura rate limit error
+                    if let Some(seconds) = backoff_seconds.as_u64() {
+                        return Some(Duration::from_secs(seconds));
+                    }
+                    if let Some(seconds) = backoff_seconds.as_f64() {
+                        return Some(Duration::from_secs(seconds as u64 + 1));
+                    }
                 }
+                None
+            }
+            Self::Transport(TransportErrorKind::CircuitOpen { retry_after }) => Some(*retry_after),
+            Self::Transport(TransportErrorKind::RetriesExhausted(err)) => err.backoff_hint(),
+            _ => None,
+        }
+    }
+
+    fn is_rate_limit(&self) -> bool {
+        match self {
+            Self::Transport(err) => err.is_rate_limit_err(),
+            Self::DeserError { text, .. } => {
+                error_payload(text).is_some_and(|payload| payload.is_rate_limit_err())
+            }
+            Self::ErrorResp(err) => err.is_rate_limit_err(),
+            _ => false,
+        }
+    }
+
+    fn is_node_behind(&self) -> bool {
+        match self {
+            Self::Transport(TransportErrorKind::RetriesExhausted(err)) => err.is_node_behind(),
+            Self::DeserError { text, .. } => {
+                error_payload(text).is_some_and(|payload| payload.is_node_behind_err())
             }
+            Self::ErrorResp(err) => err.is_node_behind_err(),
+            _ => false,
         }
-        None
     }
+
+    fn retry_hints(&self) -> RetryHints {
+        RateLimitRetryPolicy.retry_hints(self)
+    }
+}
+
+/// Parses the error payload out of a response that failed to deserialize.
+fn error_payload(text: &str) -> Option<ErrorPayload> {
+    if let Ok(resp) = serde_json::from_str::<ErrorPayload>(text) {
+        return Some(resp);
+    }
+
+    // some providers send invalid JSON RPC in the error case (no `id:u64`), but the
+    // text should be a `JsonRpcError`
+    #[derive(Deserialize)]
+    struct Resp {
+        error: ErrorPayload,
+    }
+
+    serde_json::from_str::<Resp>(text).ok().map(|resp| resp.error)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde::de::Error;
 
     #[test]
     fn test_retry_error() {
@@ -191,4 +319,41 @@ mod tests {
         let err = serde_json::from_str::<ErrorPayload>(err).unwrap();
         assert!(TransportError::ErrorResp(err).is_retryable());
     }
+
+    #[test]
+    fn test_retry_hints() {
+        let err = r#"{"code":-32005,"message":"daily request count exceeded, request rate limited","data":{"rate":{"backoff_seconds":30}}}"#;
+        let err = TransportError::ErrorResp(serde_json::from_str::<ErrorPayload>(err).unwrap());
+        let hints = RetryHints {
+            is_retryable: true,
+            suggested_backoff: Some(Duration::from_secs(30)),
+            is_rate_limit: true,
+            is_node_behind: false,
+        };
+        assert_eq!(err.retry_hints(), hints);
+
+        // the hints of the last error are kept when the retries are exhausted
+        let err = TransportErrorKind::retries_exhausted(err);
+        assert_eq!(err.retry_hints(), RetryHints { is_retryable: false, ..hints });
+
+        let err = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"header not found"}}"#;
+        let err =
+            TransportError::DeserError { err: serde_json::Error::custom(""), text: err.into() };
+        assert_eq!(
+            err.retry_hints(),
+            RetryHints { is_retryable: true, is_node_behind: true, ..Default::default() }
+        );
+
+        let err = TransportErrorKind::http_error(429, String::new());
+        assert_eq!(
+            err.retry_hints(),
+            RetryHints { is_retryable: true, is_rate_limit: true, ..Default::default() }
+        );
+
+        let err = TransportErrorKind::circuit_open(Duration::from_secs(1));
+        assert_eq!(
+            err.retry_hints(),
+            RetryHints { suggested_backoff: Some(Duration::from_secs(1)), ..Default::default() }
+        );
+    }
 }
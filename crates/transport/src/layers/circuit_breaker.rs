@@ -0,0 +1,541 @@
+use crate::{
+    error::{TransportError, TransportErrorKind},
+    TransportFut,
+};
+use alloy_json_rpc::{RequestPacket, ResponsePacket, RpcError};
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+use tracing::{debug, trace};
+
+/// The state of a circuit breaker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Requests are sent, and their failures counted.
+    Closed,
+    /// Requests are rejected without being sent, until the open duration has passed.
+    Open,
+    /// A limited number of probe requests are sent, which close the breaker if they succeed and
+    /// open it again otherwise.
+    HalfOpen,
+}
+
+/// Hooks called by a [`CircuitBreakerService`], e.g. to export metrics.
+///
+/// All methods do nothing by default.
+pub trait CircuitBreakerMetrics: fmt::Debug + Send + Sync {
+    /// Called when the breaker changes state.
+    fn on_state_change(&self, from: CircuitState, to: CircuitState) {
+        let _ = (from, to);
+    }
+
+    /// Called when a sent request completes, with whether it counted as a failure.
+    fn on_outcome(&self, failure: bool, latency: Duration) {
+        let _ = (failure, latency);
+    }
+
+    /// Called when a request is rejected without being sent.
+    fn on_rejected(&self) {}
+}
+
+/// A Transport Layer that stops sending requests to an endpoint that keeps failing, so that
+/// callers fail fast instead of waiting for it, see [`CircuitBreakerService`].
+///
+/// The breaker opens after [`with_failure_threshold`](Self::with_failure_threshold) consecutive
+/// failures, or when the failure rate over a window exceeds the
+/// [error rate threshold](Self::with_error_rate). While open, requests are rejected with
+/// [`TransportErrorKind::CircuitOpen`]. After [`with_open_duration`](Self::with_open_duration),
+/// it lets [`with_half_open_probes`](Self::with_half_open_probes) requests through, and closes
+/// once they all succeed.
+///
+/// Failures are transport errors, null and malformed responses, and rate limit or overload error
+/// responses. Other error responses, such as reverts, come from a healthy endpoint and are not
+/// failures. This can be changed with [`with_failure_predicate`](Self::with_failure_predicate).
+///
+/// Each service created by the layer has its own breaker, so a layer can be shared by the
+/// transports of several endpoints.
+#[derive(Clone)]
+pub struct CircuitBreakerLayer {
+    failure_threshold: u32,
+    error_rate: Option<ErrorRate>,
+    open_duration: Duration,
+    half_open_probes: u32,
+    is_failure: fn(&Result<ResponsePacket, TransportError>) -> bool,
+    metrics: Option<Arc<dyn CircuitBreakerMetrics>>,
+}
+
+/// The error rate opening a breaker.
+#[derive(Clone, Copy, Debug)]
+struct ErrorRate {
+    threshold: f64,
+    min_requests: usize,
+    window: Duration,
+}
+
+impl fmt::Debug for CircuitBreakerLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreakerLayer")
+            .field("failure_threshold", &self.failure_threshold)
+            .field("error_rate", &self.error_rate)
+            .field("open_duration", &self.open_duration)
+            .field("half_open_probes", &self.half_open_probes)
+            .field("metrics", &self.metrics)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for CircuitBreakerLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreakerLayer {
+    /// Creates a layer opening after 5 consecutive failures for 30 seconds, closing after 1
+    /// successful probe.
+    pub fn new() -> Self {
+        Self {
+            failure_threshold: 5,
+            error_rate: None,
+            open_duration: Duration::from_secs(30),
+            half_open_probes: 1,
+            is_failure: is_endpoint_failure,
+            metrics: None,
+        }
+    }
+
+    /// Sets the number of consecutive failures opening the breaker.
+    pub fn with_failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Also opens the breaker when the ratio of failures to requests completed in the last
+    /// `window` reaches `threshold`, once at least `min_requests` completed in it.
+    pub const fn with_error_rate(
+        mut self,
+        threshold: f64,
+        min_requests: usize,
+        window: Duration,
+    ) -> Self {
+        self.error_rate = Some(ErrorRate { threshold, min_requests, window });
+        self
+    }
+
+    /// Sets how long the breaker stays open before probing the endpoint.
+    pub const fn with_open_duration(mut self, duration: Duration) -> Self {
+        self.open_duration = duration;
+        self
+    }
+
+    /// Sets the number of probe requests sent while half-open, which must all succeed to close
+    /// the breaker.
+    pub fn with_half_open_probes(mut self, probes: u32) -> Self {
+        self.half_open_probes = probes.max(1);
+        self
+    }
+
+    /// Sets which results are failures.
+    pub fn with_failure_predicate(
+        mut self,
+        is_failure: fn(&Result<ResponsePacket, TransportError>) -> bool,
+    ) -> Self {
+        self.is_failure = is_failure;
+        self
+    }
+
+    /// Sets the hooks called by the breaker.
+    pub fn with_metrics(mut self, metrics: impl CircuitBreakerMetrics + 'static) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+}
+
+impl CircuitBreakerLayer {
+    fn notify(&self, change: Option<(CircuitState, CircuitState)>) {
+        if let Some((from, to)) = change {
+            debug!(?from, ?to, "circuit breaker changed state");
+            if let Some(metrics) = &self.metrics {
+                metrics.on_state_change(from, to);
+            }
+        }
+    }
+}
+
+impl<S> Layer<S> for CircuitBreakerLayer {
+    type Service = CircuitBreakerService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CircuitBreakerService {
+            inner,
+            config: Arc::new(self.clone()),
+            breaker: Arc::new(Mutex::new(Breaker::new())),
+        }
+    }
+}
+
+/// Returns whether the result is a failure of the endpoint, see [`CircuitBreakerLayer`].
+fn is_endpoint_failure(result: &Result<ResponsePacket, TransportError>) -> bool {
+    match result {
+        Ok(response) => response.iter_errors().any(|error| error.is_retry_err()),
+        Err(RpcError::ErrorResp(error)) => error.is_retry_err(),
+        Err(
+            RpcError::SerError(_) | RpcError::LocalUsageError(_) | RpcError::UnsupportedFeature(_),
+        ) => false,
+        Err(_) => true,
+    }
+}
+
+/// A Tower Service used by the [`CircuitBreakerLayer`] that rejects requests while the endpoint
+/// keeps failing.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerService<S> {
+    inner: S,
+    config: Arc<CircuitBreakerLayer>,
+    breaker: Arc<Mutex<Breaker>>,
+}
+
+impl<S> CircuitBreakerService<S> {
+    /// Returns the current state of the breaker.
+    ///
+    /// An open breaker whose open duration has passed is reported as half-open.
+    pub fn state(&self) -> CircuitState {
+        match self.breaker.lock().unwrap().state {
+            State::Open { until } if Instant::now() >= until => CircuitState::HalfOpen,
+            state => state.kind(),
+        }
+    }
+
+    /// Rejects requests until the breaker is closed by a successful probe, e.g. for maintenance
+    /// of the endpoint.
+    pub fn trip(&self) {
+        let change = self.breaker.lock().unwrap().open(Instant::now(), &self.config);
+        self.notify(change);
+    }
+
+    /// Closes the breaker, e.g. after the endpoint was fixed.
+    pub fn reset(&self) {
+        let change = self.breaker.lock().unwrap().close();
+        self.notify(change);
+    }
+
+    fn notify(&self, change: Option<(CircuitState, CircuitState)>) {
+        self.config.notify(change);
+    }
+}
+
+impl<S> Service<RequestPacket> for CircuitBreakerService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, inner);
+
+        let (admission, change) = self.breaker.lock().unwrap().admit(Instant::now(), &self.config);
+        self.notify(change);
+        let is_probe = match admission {
+            Ok(is_probe) => is_probe,
+            Err(retry_after) => {
+                trace!(?retry_after, "circuit breaker is open, rejecting request");
+                if let Some(metrics) = &self.config.metrics {
+                    metrics.on_rejected();
+                }
+                return Box::pin(async move { Err(TransportErrorKind::circuit_open(retry_after)) });
+            }
+        };
+
+        let config = self.config.clone();
+        let breaker = self.breaker.clone();
+        Box::pin(async move {
+            let mut probe = ProbeGuard { breaker: &breaker, is_probe };
+            let start = Instant::now();
+            let result = inner.call(request).await;
+
+            let failure = (config.is_failure)(&result);
+            if let Some(metrics) = &config.metrics {
+                metrics.on_outcome(failure, start.elapsed());
+            }
+            let change =
+                breaker.lock().unwrap().record(Instant::now(), probe.take(), failure, &config);
+            config.notify(change);
+            result
+        })
+    }
+}
+
+/// Releases the slot of a probe whose request was dropped before completing.
+struct ProbeGuard<'a> {
+    breaker: &'a Mutex<Breaker>,
+    is_probe: bool,
+}
+
+impl ProbeGuard<'_> {
+    fn take(&mut self) -> bool {
+        std::mem::take(&mut self.is_probe)
+    }
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if self.is_probe {
+            self.breaker.lock().unwrap().release_probe();
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { probes: u32, successes: u32 },
+}
+
+impl State {
+    const fn kind(self) -> CircuitState {
+        match self {
+            Self::Closed => CircuitState::Closed,
+            Self::Open { .. } => CircuitState::Open,
+            Self::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// The state of a breaker, with the outcomes counted while closed.
+#[derive(Debug)]
+struct Breaker {
+    state: State,
+    consecutive_failures: u32,
+    /// The completion time and failure of the requests in the error rate window.
+    outcomes: VecDeque<(Instant, bool)>,
+}
+
+impl Breaker {
+    const fn new() -> Self {
+        Self { state: State::Closed, consecutive_failures: 0, outcomes: VecDeque::new() }
+    }
+
+    /// Returns whether a request may be sent and is a probe, or the time until it may be.
+    #[allow(clippy::type_complexity)]
+    fn admit(
+        &mut self,
+        now: Instant,
+        config: &CircuitBreakerLayer,
+    ) -> (Result<bool, Duration>, Option<(CircuitState, CircuitState)>) {
+        let mut change = None;
+        if let State::Open { until } = self.state {
+            if now < until {
+                return (Err(until - now), None);
+            }
+            self.state = State::HalfOpen { probes: 0, successes: 0 };
+            change = Some((CircuitState::Open, CircuitState::HalfOpen));
+        }
+        match &mut self.state {
+            State::HalfOpen { probes, .. } if *probes < config.half_open_probes => {
+                *probes += 1;
+                (Ok(true), change)
+            }
+            // the probes are in flight
+            State::HalfOpen { .. } => (Err(Duration::ZERO), change),
+            _ => (Ok(false), change),
+        }
+    }
+
+    /// Records the outcome of a request.
+    fn record(
+        &mut self,
+        now: Instant,
+        is_probe: bool,
+        failure: bool,
+        config: &CircuitBreakerLayer,
+    ) -> Option<(CircuitState, CircuitState)> {
+        match &mut self.state {
+            State::HalfOpen { successes, .. } if is_probe => {
+                if failure {
+                    return self.open(now, config);
+                }
+                *successes += 1;
+                if *successes >= config.half_open_probes {
+                    return self.close();
+                }
+                None
+            }
+            State::Closed => {
+                if failure {
+                    self.consecutive_failures += 1;
+                } else {
+                    self.consecutive_failures = 0;
+                }
+                if self.consecutive_failures >= config.failure_threshold
+                    || self.error_rate_exceeded(now, failure, config)
+                {
+                    return self.open(now, config);
+                }
+                None
+            }
+            // requests sent before the breaker opened, or while it was probing
+            _ => None,
+        }
+    }
+
+    fn error_rate_exceeded(
+        &mut self,
+        now: Instant,
+        failure: bool,
+        config: &CircuitBreakerLayer,
+    ) -> bool {
+        let Some(rate) = config.error_rate else { return false };
+        self.outcomes.push_back((now, failure));
+        while self.outcomes.front().is_some_and(|(at, _)| now.duration_since(*at) > rate.window) {
+            self.outcomes.pop_front();
+        }
+        if self.outcomes.len() < rate.min_requests.max(1) {
+            return false;
+        }
+        let failures = self.outcomes.iter().filter(|(_, failure)| *failure).count();
+        failures as f64 / self.outcomes.len() as f64 >= rate.threshold
+    }
+
+    fn release_probe(&mut self) {
+        if let State::HalfOpen { probes, .. } = &mut self.state {
+            *probes = probes.saturating_sub(1);
+        }
+    }
+
+    fn open(
+        &mut self,
+        now: Instant,
+        config: &CircuitBreakerLayer,
+    ) -> Option<(CircuitState, CircuitState)> {
+        let from = self.state.kind();
+        self.state = State::Open { until: now + config.open_duration };
+        self.consecutive_failures = 0;
+        self.outcomes.clear();
+        (from != CircuitState::Open).then_some((from, CircuitState::Open))
+    }
+
+    fn close(&mut self) -> Option<(CircuitState, CircuitState)> {
+        let from = self.state.kind();
+        self.state = State::Closed;
+        self.consecutive_failures = 0;
+        self.outcomes.clear();
+        (from != CircuitState::Closed).then_some((from, CircuitState::Closed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request, Response, ResponsePayload};
+    use serde_json::value::RawValue;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    #[derive(Debug, Default)]
+    struct Changes(Mutex<Vec<(CircuitState, CircuitState)>>);
+
+    impl CircuitBreakerMetrics for Arc<Changes> {
+        fn on_state_change(&self, from: CircuitState, to: CircuitState) {
+            self.0.lock().unwrap().push((from, to));
+        }
+    }
+
+    fn request() -> RequestPacket {
+        Request::new("eth_blockNumber", Id::Number(1), ()).serialize().unwrap().into()
+    }
+
+    #[tokio::test]
+    async fn opens_and_probes() {
+        let failing = Arc::new(AtomicBool::new(true));
+        let calls = Arc::new(AtomicU32::new(0));
+        let inner = tower::service_fn({
+            let (failing, calls) = (failing.clone(), calls.clone());
+            move |_: RequestPacket| {
+                let fail = failing.load(Ordering::Relaxed);
+                calls.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    if fail {
+                        return Err(TransportErrorKind::backend_gone());
+                    }
+                    let result = RawValue::from_string("\"0x1\"".into()).unwrap();
+                    Ok(ResponsePacket::Single(Response {
+                        id: Id::Number(1),
+                        payload: ResponsePayload::Success(result),
+                    }))
+                }
+            }
+        });
+        let changes = Arc::new(Changes::default());
+        let mut service = CircuitBreakerLayer::new()
+            .with_failure_threshold(3)
+            .with_open_duration(Duration::from_millis(50))
+            .with_metrics(changes.clone())
+            .layer(inner);
+
+        for _ in 0..3 {
+            assert!(service.call(request()).await.is_err());
+        }
+        assert_eq!(service.state(), CircuitState::Open);
+        let err = service.call(request()).await.unwrap_err();
+        assert!(matches!(err, RpcError::Transport(TransportErrorKind::CircuitOpen { .. })));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        // a failed probe opens the breaker again
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(service.call(request()).await.is_err());
+        assert_eq!(service.state(), CircuitState::Open);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        failing.store(false, Ordering::Relaxed);
+        assert!(service.call(request()).await.is_ok());
+        assert_eq!(service.state(), CircuitState::Closed);
+        assert_eq!(calls.load(Ordering::Relaxed), 5);
+
+        use CircuitState::*;
+        assert_eq!(
+            *changes.0.lock().unwrap(),
+            [
+                (Closed, Open),
+                (Open, HalfOpen),
+                (HalfOpen, Open),
+                (Open, HalfOpen),
+                (HalfOpen, Closed)
+            ]
+        );
+    }
+
+    #[test]
+    fn opens_on_error_rate() {
+        let config = CircuitBreakerLayer::new().with_failure_threshold(100).with_error_rate(
+            0.5,
+            4,
+            Duration::from_secs(10),
+        );
+        let mut breaker = Breaker::new();
+        let now = Instant::now();
+        for failure in [true, false, true] {
+            assert_eq!(breaker.record(now, false, failure, &config), None);
+        }
+        assert_eq!(
+            breaker.record(now, false, false, &config),
+            Some((CircuitState::Closed, CircuitState::Open))
+        );
+        assert!(breaker.admit(now, &config).0.is_err());
+    }
+}
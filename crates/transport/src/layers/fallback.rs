@@ -0,0 +1,489 @@
+use crate::{
+    error::{RpcErrorExt, TransportError, TransportErrorKind},
+    TransportFut,
+};
+use alloy_json_rpc::{RequestPacket, Response, ResponsePacket};
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// The weight of a new latency in the average latency of an endpoint.
+const LATENCY_WEIGHT: f64 = 0.2;
+
+/// A Transport Layer that sends requests to the fastest healthy one of several transports, and
+/// falls back to the next ones when it fails, see [`FallbackService`].
+///
+/// A request falls back to the next transport on transport errors, invalid responses, and on error
+/// responses for [rate limits](RpcErrorExt::is_rate_limit) or
+/// [blocks the node has not seen yet](RpcErrorExt::is_node_behind). Other error responses, e.g.
+/// reverts, are returned as they are.
+///
+/// The transports are tried by their average latency. A transport that fails a number of times in
+/// a row is unhealthy for a cooldown period, during which it is only tried after the healthy
+/// ones. The next success makes it healthy again.
+///
+/// With [`with_quorum`](Self::with_quorum), the requests for the given methods are sent to all
+/// transports instead, and only succeed when enough of them return the same response.
+#[derive(Clone, Debug)]
+pub struct FallbackLayer {
+    max_failures: u32,
+    cooldown: Duration,
+    quorum: Option<Quorum>,
+}
+
+#[derive(Clone, Debug)]
+struct Quorum {
+    threshold: usize,
+    methods: Arc<HashSet<String>>,
+}
+
+impl Default for FallbackLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FallbackLayer {
+    /// Creates a layer marking transports unhealthy for 30 seconds after 3 failures in a row.
+    pub const fn new() -> Self {
+        Self { max_failures: 3, cooldown: Duration::from_secs(30), quorum: None }
+    }
+
+    /// Sets the number of failures in a row after which a transport is unhealthy, and for how
+    /// long.
+    pub fn with_unhealthy_after(mut self, max_failures: u32, cooldown: Duration) -> Self {
+        self.max_failures = max_failures.max(1);
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Requires `threshold` transports to return the same response to requests for the given
+    /// methods, e.g. `eth_call` or `eth_getBalance`.
+    ///
+    /// These requests are sent to all transports at once, and fail with
+    /// [`TransportErrorKind::QuorumNotReached`] when not enough responses agree. Batches are
+    /// sent to all transports if any of their requests is for one of the methods.
+    pub fn with_quorum<I, M>(mut self, threshold: usize, methods: I) -> Self
+    where
+        I: IntoIterator<Item = M>,
+        M: Into<String>,
+    {
+        let methods = methods.into_iter().map(Into::into).collect();
+        self.quorum = Some(Quorum { threshold: threshold.max(1), methods: Arc::new(methods) });
+        self
+    }
+}
+
+impl<S> Layer<Vec<S>> for FallbackLayer {
+    type Service = FallbackService<S>;
+
+    fn layer(&self, transports: Vec<S>) -> Self::Service {
+        let endpoints = transports.iter().map(|_| EndpointState::default()).collect();
+        FallbackService {
+            transports,
+            endpoints: Arc::new(Mutex::new(endpoints)),
+            max_failures: self.max_failures,
+            cooldown: self.cooldown,
+            quorum: self.quorum.clone(),
+        }
+    }
+}
+
+/// The health of a transport of a [`FallbackService`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// The average latency of the transport, `None` until it answered a request.
+    pub latency: Option<Duration>,
+    /// The number of failures since the last success.
+    pub consecutive_failures: u32,
+    /// Whether the transport is tried before the unhealthy ones.
+    pub healthy: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct EndpointState {
+    latency: Option<Duration>,
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+impl EndpointState {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.unhealthy_until.map_or(true, |until| now >= until)
+    }
+}
+
+/// A Tower Service used by the [`FallbackLayer`] that sends requests to the next transport when
+/// one fails.
+#[derive(Clone, Debug)]
+pub struct FallbackService<S> {
+    transports: Vec<S>,
+    endpoints: Arc<Mutex<Vec<EndpointState>>>,
+    max_failures: u32,
+    cooldown: Duration,
+    quorum: Option<Quorum>,
+}
+
+impl<S> FallbackService<S> {
+    /// Returns the transports requests are sent to.
+    pub fn transports(&self) -> &[S] {
+        &self.transports
+    }
+
+    /// Returns the health of each transport, in the order of [`transports`](Self::transports).
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let now = Instant::now();
+        self.endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|state| EndpointHealth {
+                latency: state.latency,
+                consecutive_failures: state.consecutive_failures,
+                healthy: state.is_healthy(now),
+            })
+            .collect()
+    }
+
+    /// Returns the indices of the transports in the order they are tried: the healthy ones by
+    /// their latency, then the unhealthy ones by the end of their cooldown.
+    fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let endpoints = self.endpoints.lock().unwrap();
+        let mut order = (0..endpoints.len()).collect::<Vec<_>>();
+        // transports without latency are tried first, to measure it
+        order.sort_by_key(|&index| {
+            let state = &endpoints[index];
+            if state.is_healthy(now) {
+                (false, state.latency.unwrap_or_default(), now)
+            } else {
+                (true, Duration::ZERO, state.unhealthy_until.unwrap_or(now))
+            }
+        });
+        order
+    }
+
+    fn requires_quorum(&self, request: &RequestPacket) -> Option<usize> {
+        let quorum = self.quorum.as_ref()?;
+        request
+            .requests()
+            .iter()
+            .any(|req| quorum.methods.contains(req.method()))
+            .then_some(quorum.threshold)
+    }
+}
+
+/// Records the outcome of a request to the transport at `index`.
+fn record(
+    endpoints: &Mutex<Vec<EndpointState>>,
+    index: usize,
+    outcome: Result<Duration, ()>,
+    max_failures: u32,
+    cooldown: Duration,
+) {
+    let mut endpoints = endpoints.lock().unwrap();
+    let state = &mut endpoints[index];
+    match outcome {
+        Ok(latency) => {
+            state.latency = Some(state.latency.map_or(latency, |average| {
+                average.mul_f64(1.0 - LATENCY_WEIGHT) + latency.mul_f64(LATENCY_WEIGHT)
+            }));
+            state.consecutive_failures = 0;
+            state.unhealthy_until = None;
+        }
+        Err(()) => {
+            state.consecutive_failures += 1;
+            if state.consecutive_failures >= max_failures {
+                trace!(index, "transport is unhealthy");
+                state.unhealthy_until = Some(Instant::now() + cooldown);
+            }
+        }
+    }
+}
+
+/// Returns the error to fall back on, if the request should be sent to the next transport.
+fn fallback_error(result: &Result<ResponsePacket, TransportError>) -> Option<TransportError> {
+    match result {
+        Ok(response) => {
+            let err = TransportError::ErrorResp(response.as_error()?.clone());
+            (err.is_rate_limit() || err.is_node_behind()).then_some(err)
+        }
+        // the request itself is invalid
+        Err(TransportError::SerError(_)) => None,
+        Err(err @ TransportError::ErrorResp(_)) => {
+            (err.is_rate_limit() || err.is_node_behind()).then(|| clone_error(err))
+        }
+        Err(_) => Some(TransportErrorKind::custom_str("transport failed")),
+    }
+}
+
+fn clone_error(err: &TransportError) -> TransportError {
+    match err {
+        TransportError::ErrorResp(payload) => TransportError::ErrorResp(payload.clone()),
+        err => TransportErrorKind::custom_str(&err.to_string()),
+    }
+}
+
+/// Returns the responses of a packet in a form that is equal for equal responses, regardless of
+/// the formatting of their JSON and the order of a batch.
+fn normalize(response: &ResponsePacket) -> Vec<(String, serde_json::Value)> {
+    let responses: &[Response] = match response {
+        ResponsePacket::Single(single) => std::slice::from_ref(single),
+        ResponsePacket::Batch(batch) => batch,
+    };
+    let mut normalized = responses
+        .iter()
+        .map(|response| {
+            let value = serde_json::to_value(response).unwrap_or(serde_json::Value::Null);
+            (response.id.to_string(), value)
+        })
+        .collect::<Vec<_>>();
+    normalized.sort_by(|a, b| a.0.cmp(&b.0));
+    normalized
+}
+
+impl<S> Service<RequestPacket> for FallbackService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        for transport in &mut self.transports {
+            match transport.poll_ready(cx) {
+                Poll::Ready(Ok(())) => {}
+                poll => return poll,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        if self.transports.is_empty() {
+            return Box::pin(async { Err(TransportErrorKind::custom_str("no transports")) });
+        }
+
+        let order = self.order();
+        let transports = order.iter().map(|&index| self.transports[index].clone()).collect();
+        let endpoints = self.endpoints.clone();
+        let (max_failures, cooldown) = (self.max_failures, self.cooldown);
+        let record =
+            move |index: usize, outcome| record(&endpoints, index, outcome, max_failures, cooldown);
+
+        match self.requires_quorum(&request) {
+            Some(threshold) => Box::pin(quorum(request, order, transports, threshold, record)),
+            None => Box::pin(fallback(request, order, transports, record)),
+        }
+    }
+}
+
+/// Sends the request to the transports in turn, until one does not fail.
+async fn fallback<S>(
+    request: RequestPacket,
+    order: Vec<usize>,
+    transports: Vec<S>,
+    record: impl Fn(usize, Result<Duration, ()>),
+) -> Result<ResponsePacket, TransportError>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>,
+{
+    let mut last = None;
+    for (index, mut transport) in order.into_iter().zip(transports) {
+        let start = Instant::now();
+        let result = transport.call(request.clone()).await;
+        let Some(err) = fallback_error(&result) else {
+            // valid responses count as successes, even if they are errors
+            if result.is_ok() {
+                record(index, Ok(start.elapsed()));
+            }
+            return result;
+        };
+        trace!(index, %err, "transport failed, falling back to the next one");
+        record(index, Err(()));
+        last = Some(result);
+    }
+    last.expect("at least one transport")
+}
+
+/// Sends the request to all transports, until `threshold` of them return the same response.
+async fn quorum<S>(
+    request: RequestPacket,
+    order: Vec<usize>,
+    transports: Vec<S>,
+    threshold: usize,
+    record: impl Fn(usize, Result<Duration, ()>),
+) -> Result<ResponsePacket, TransportError>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>,
+{
+    if transports.len() < threshold {
+        return Err(TransportErrorKind::quorum_not_reached(threshold, 0));
+    }
+
+    let start = Instant::now();
+    let mut pending = order
+        .into_iter()
+        .zip(transports)
+        .map(|(index, mut transport)| {
+            let future = transport.call(request.clone());
+            async move { (index, future.await) }
+        })
+        .collect::<FuturesUnordered<_>>();
+
+    let mut groups: Vec<(Vec<(String, serde_json::Value)>, usize)> = Vec::new();
+    let mut responses = 0;
+    while let Some((index, result)) = pending.next().await {
+        if fallback_error(&result).is_some() {
+            record(index, Err(()));
+            continue;
+        }
+        let Ok(response) = result else { continue };
+        record(index, Ok(start.elapsed()));
+        responses += 1;
+
+        let normalized = normalize(&response);
+        let count = match groups.iter_mut().find(|(group, _)| *group == normalized) {
+            Some((_, count)) => {
+                *count += 1;
+                *count
+            }
+            None => {
+                groups.push((normalized, 1));
+                1
+            }
+        };
+        if count >= threshold {
+            return Ok(response);
+        }
+    }
+
+    trace!(responses, threshold, "no quorum of agreeing responses");
+    Err(TransportErrorKind::quorum_not_reached(threshold, responses))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request, ResponsePayload};
+    use serde_json::value::RawValue;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// A transport answering with `result`, or failing if it is empty.
+    fn transport(
+        result: &'static str,
+        calls: Arc<AtomicU32>,
+    ) -> impl Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    > + Clone {
+        tower::service_fn(move |_: RequestPacket| -> TransportFut<'static> {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Box::pin(async move {
+                let payload = match result {
+                    "" => return Err(TransportErrorKind::backend_gone()),
+                    "rate limit" => ResponsePayload::Failure(alloy_json_rpc::ErrorPayload {
+                        code: 429,
+                        message: "too many requests".into(),
+                        data: None,
+                    }),
+                    "revert" => ResponsePayload::Failure(alloy_json_rpc::ErrorPayload {
+                        code: 3,
+                        message: "execution reverted".into(),
+                        data: None,
+                    }),
+                    result => {
+                        ResponsePayload::Success(RawValue::from_string(result.into()).unwrap())
+                    }
+                };
+                Ok(ResponsePacket::Single(Response { id: Id::Number(1), payload }))
+            })
+        })
+    }
+
+    fn request(method: &'static str) -> RequestPacket {
+        Request::new(method, Id::Number(1), ()).serialize().unwrap().into()
+    }
+
+    fn result(response: ResponsePacket) -> String {
+        match response {
+            ResponsePacket::Single(Response {
+                payload: ResponsePayload::Success(result), ..
+            }) => result.get().to_string(),
+            _ => panic!("unexpected response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_transport() {
+        let calls: [Arc<AtomicU32>; 3] = Default::default();
+        let mut service =
+            FallbackLayer::new().with_unhealthy_after(2, Duration::from_secs(60)).layer(vec![
+                transport("", calls[0].clone()),
+                transport("rate limit", calls[1].clone()),
+                transport("\"2\"", calls[2].clone()),
+            ]);
+
+        let response = service.call(request("eth_blockNumber")).await.unwrap();
+        assert_eq!(result(response), "\"2\"");
+        let health = service.health();
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert!(health[0].healthy);
+        assert!(health[2].latency.is_some());
+
+        // the failing transports are unhealthy after their second failure, and tried last
+        service.call(request("eth_blockNumber")).await.unwrap();
+        assert!(!service.health()[0].healthy && !service.health()[1].healthy);
+        service.call(request("eth_blockNumber")).await.unwrap();
+        let calls = calls.each_ref().map(|calls| calls.load(Ordering::Relaxed));
+        assert_eq!(calls, [2, 2, 3]);
+
+        // reverts are returned as they are
+        let mut service = FallbackLayer::new()
+            .layer(vec![transport("revert", Arc::default()), transport("1", Arc::default())]);
+        let response = service.call(request("eth_call")).await.unwrap();
+        assert!(response.is_error());
+    }
+
+    #[tokio::test]
+    async fn requires_quorum() {
+        let layer = FallbackLayer::new().with_quorum(2, ["eth_getBalance"]);
+        let mut service = layer.layer(vec![
+            transport("\"0x1\"", Arc::default()),
+            transport("\"0x2\"", Arc::default()),
+            transport("", Arc::default()),
+            transport(" \"0x2\" ", Arc::default()),
+        ]);
+        let response = service.call(request("eth_getBalance")).await.unwrap();
+        assert_eq!(result(response).trim(), "\"0x2\"");
+
+        let mut service = layer.layer(vec![
+            transport("\"0x1\"", Arc::default()),
+            transport("\"0x2\"", Arc::default()),
+        ]);
+        let err = service.call(request("eth_getBalance")).await.unwrap_err();
+        assert!(matches!(
+            err,
+            TransportError::Transport(TransportErrorKind::QuorumNotReached {
+                quorum: 2,
+                responses: 2
+            })
+        ));
+        // other methods fall back as usual
+        let response = service.call(request("eth_blockNumber")).await.unwrap();
+        assert!(!response.is_error());
+    }
+}
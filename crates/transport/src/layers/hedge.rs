@@ -0,0 +1,335 @@
+use crate::{error::TransportError, TransportFut};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use futures_util::future::{select, Either};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// A Transport Layer that sends a request to a backup transport as well when the primary
+/// transport is slow to answer, see [`HedgeService`].
+///
+/// The request is hedged once the primary has not answered for the given percentile of its
+/// recent latencies, clamped to the minimum and maximum delay. The first successful response is
+/// returned, and the other request is dropped. Until enough latencies were observed, the maximum
+/// delay is used.
+///
+/// Only requests that are [idempotent](RequestPacket::is_idempotent) are hedged, so transactions,
+/// subscriptions, filters and other stateful requests are only sent to the primary.
+#[derive(Clone, Debug)]
+pub struct HedgeLayer<B> {
+    backup: B,
+    percentile: f64,
+    min_delay: Duration,
+    max_delay: Duration,
+    window: usize,
+    min_samples: usize,
+}
+
+impl<B> HedgeLayer<B> {
+    /// Creates a layer hedging requests to the backup transport after the 95th percentile of the
+    /// latencies of the last 100 responses, between 10 milliseconds and 1 second.
+    pub const fn new(backup: B) -> Self {
+        Self {
+            backup,
+            percentile: 0.95,
+            min_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+            window: 100,
+            min_samples: 10,
+        }
+    }
+
+    /// Sets the percentile of the latencies of the primary after which requests are hedged,
+    /// between `0.0` and `1.0`.
+    pub fn with_percentile(mut self, percentile: f64) -> Self {
+        self.percentile = percentile.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the bounds of the delay after which requests are hedged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub const fn with_delay_bounds(mut self, min: Duration, max: Duration) -> Self {
+        assert!(min.as_nanos() <= max.as_nanos(), "minimum hedge delay exceeds the maximum");
+        self.min_delay = min;
+        self.max_delay = max;
+        self
+    }
+
+    /// Sets the number of recent latencies of the primary the percentile is computed over, and
+    /// the number of latencies needed before it is used.
+    pub fn with_window(mut self, window: usize, min_samples: usize) -> Self {
+        self.window = window.max(1);
+        self.min_samples = min_samples.clamp(1, self.window);
+        self
+    }
+}
+
+impl<S, B: Clone> Layer<S> for HedgeLayer<B> {
+    type Service = HedgeService<S, B>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HedgeService {
+            inner,
+            backup: self.backup.clone(),
+            config: HedgeConfig {
+                percentile: self.percentile,
+                min_delay: self.min_delay,
+                max_delay: self.max_delay,
+                window: self.window,
+                min_samples: self.min_samples,
+            },
+            latencies: Arc::new(Mutex::new(VecDeque::with_capacity(self.window))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct HedgeConfig {
+    percentile: f64,
+    min_delay: Duration,
+    max_delay: Duration,
+    window: usize,
+    min_samples: usize,
+}
+
+/// A Tower Service used by the [`HedgeLayer`] that sends slow requests to a backup transport.
+#[derive(Clone, Debug)]
+pub struct HedgeService<S, B> {
+    inner: S,
+    backup: B,
+    config: HedgeConfig,
+    /// The latencies of the last responses of the primary.
+    latencies: Arc<Mutex<VecDeque<Duration>>>,
+}
+
+impl<S, B> HedgeService<S, B> {
+    /// Returns the delay after which requests are currently hedged.
+    pub fn hedge_delay(&self) -> Duration {
+        let HedgeConfig { percentile, min_delay, max_delay, min_samples, .. } = self.config;
+        let mut latencies = self.latencies.lock().unwrap().iter().copied().collect::<Vec<_>>();
+        if latencies.len() < min_samples {
+            return max_delay;
+        }
+        latencies.sort_unstable();
+        let index = ((latencies.len() - 1) as f64 * percentile).round() as usize;
+        latencies[index].clamp(min_delay, max_delay)
+    }
+}
+
+fn record_latency(latencies: &Mutex<VecDeque<Duration>>, window: usize, latency: Duration) {
+    let mut latencies = latencies.lock().unwrap();
+    if latencies.len() == window {
+        latencies.pop_front();
+    }
+    latencies.push_back(latency);
+}
+
+impl<S, B> Service<RequestPacket> for HedgeService<S, B>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    S::Future: Send + 'static,
+    B: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    B::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.inner.poll_ready(cx) {
+            Poll::Ready(Ok(())) => self.backup.poll_ready(cx),
+            poll => poll,
+        }
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, inner);
+
+        if !request.is_idempotent() {
+            return Box::pin(inner.call(request));
+        }
+
+        let backup = self.backup.clone();
+        let mut backup = std::mem::replace(&mut self.backup, backup);
+        let delay = self.hedge_delay();
+        let latencies = self.latencies.clone();
+        let window = self.config.window;
+
+        Box::pin(async move {
+            let start = Instant::now();
+            let primary = Box::pin(inner.call(request.clone()));
+            let primary = match select(primary, Box::pin(tokio::time::sleep(delay))).await {
+                Either::Left((result, _)) => {
+                    record_latency(&latencies, window, start.elapsed());
+                    return result;
+                }
+                Either::Right(((), primary)) => primary,
+            };
+
+            // the response would arrive too late to be useful
+            if request.deadline().is_some_and(|deadline| deadline <= Instant::now()) {
+                let result = primary.await;
+                record_latency(&latencies, window, start.elapsed());
+                return result;
+            }
+
+            trace!(delay_millis = delay.as_millis(), "hedging request to backup transport");
+            match select(primary, Box::pin(backup.call(request))).await {
+                Either::Left((result, hedged)) => {
+                    record_latency(&latencies, window, start.elapsed());
+                    match result {
+                        Ok(response) => Ok(response),
+                        Err(err) => {
+                            trace!(%err, "primary transport failed, waiting for backup");
+                            hedged.await
+                        }
+                    }
+                }
+                Either::Right((result, primary)) => match result {
+                    Ok(response) => {
+                        trace!("backup transport answered first");
+                        // the primary answered no faster than this
+                        record_latency(&latencies, window, start.elapsed());
+                        Ok(response)
+                    }
+                    Err(err) => {
+                        trace!(%err, "backup transport failed, waiting for primary");
+                        let result = primary.await;
+                        record_latency(&latencies, window, start.elapsed());
+                        result
+                    }
+                },
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransportErrorKind;
+    use alloy_json_rpc::{Id, Request, Response, ResponsePayload};
+    use serde_json::value::RawValue;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn transport(
+        latency: Duration,
+        result: &'static str,
+        calls: Arc<AtomicU32>,
+    ) -> impl Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    > + Clone {
+        tower::service_fn(move |_: RequestPacket| -> TransportFut<'static> {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Box::pin(async move {
+                tokio::time::sleep(latency).await;
+                if result.is_empty() {
+                    return Err(TransportErrorKind::backend_gone());
+                }
+                Ok(ResponsePacket::Single(Response {
+                    id: Id::Number(1),
+                    payload: ResponsePayload::Success(
+                        RawValue::from_string(result.into()).unwrap(),
+                    ),
+                }))
+            })
+        })
+    }
+
+    fn request(method: &'static str) -> RequestPacket {
+        Request::new(method, Id::Number(1), ()).serialize().unwrap().into()
+    }
+
+    fn result(response: ResponsePacket) -> String {
+        match response {
+            ResponsePacket::Single(Response {
+                payload: ResponsePayload::Success(result), ..
+            }) => result.get().to_string(),
+            _ => panic!("unexpected response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn hedges_slow_requests() {
+        let (primary_calls, backup_calls) =
+            (Arc::new(AtomicU32::new(0)), Arc::new(AtomicU32::new(0)));
+        let backup = transport(Duration::from_millis(5), "\"backup\"", backup_calls.clone());
+        let mut service = HedgeLayer::new(backup)
+            .with_delay_bounds(Duration::from_millis(10), Duration::from_millis(20))
+            .layer(transport(Duration::from_millis(100), "\"primary\"", primary_calls.clone()));
+
+        let response = service.call(request("eth_blockNumber")).await.unwrap();
+        assert_eq!(result(response), "\"backup\"");
+        assert_eq!(backup_calls.load(Ordering::Relaxed), 1);
+
+        // transactions are never sent twice
+        let response = service.call(request("eth_sendRawTransaction")).await.unwrap();
+        assert_eq!(result(response), "\"primary\"");
+        assert_eq!(
+            (primary_calls.load(Ordering::Relaxed), backup_calls.load(Ordering::Relaxed)),
+            (2, 1)
+        );
+
+        // nor are filters, whose IDs are local to a node
+        let response = service.call(request("eth_newFilter")).await.unwrap();
+        assert_eq!(result(response), "\"primary\"");
+        assert_eq!(
+            (primary_calls.load(Ordering::Relaxed), backup_calls.load(Ordering::Relaxed)),
+            (3, 1)
+        );
+
+        // a failed backup falls back to the primary
+        let backup = transport(Duration::ZERO, "", Arc::default());
+        let mut service = HedgeLayer::new(backup)
+            .with_delay_bounds(Duration::ZERO, Duration::ZERO)
+            .layer(transport(Duration::from_millis(10), "\"primary\"", Arc::default()));
+        let response = service.call(request("eth_blockNumber")).await.unwrap();
+        assert_eq!(result(response), "\"primary\"");
+    }
+
+    #[test]
+    fn percentile_delay() {
+        let service = HedgeLayer::new(())
+            .with_percentile(0.9)
+            .with_window(10, 5)
+            .with_delay_bounds(Duration::from_millis(2), Duration::from_millis(50))
+            .layer(());
+        assert_eq!(service.hedge_delay(), Duration::from_millis(50));
+
+        for millis in 1..=20 {
+            record_latency(&service.latencies, 10, Duration::from_millis(millis));
+        }
+        // the window holds 11..=20
+        assert_eq!(service.hedge_delay(), Duration::from_millis(19));
+
+        for _ in 0..10 {
+            record_latency(&service.latencies, 10, Duration::from_millis(1));
+        }
+        assert_eq!(service.hedge_delay(), Duration::from_millis(2));
+    }
+
+    #[test]
+    #[should_panic = "minimum hedge delay exceeds the maximum"]
+    fn inverted_delay_bounds() {
+        let _ =
+            HedgeLayer::new(()).with_delay_bounds(Duration::from_secs(2), Duration::from_secs(1));
+    }
+}
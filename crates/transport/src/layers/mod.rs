@@ -1,6 +1,20 @@
 //! Module for housing transport layers.
 
+mod circuit_breaker;
+pub use circuit_breaker::{
+    CircuitBreakerLayer, CircuitBreakerMetrics, CircuitBreakerService, CircuitState,
+};
+
+mod fallback;
+pub use fallback::{EndpointHealth, FallbackLayer, FallbackService};
+
+mod hedge;
+pub use hedge::{HedgeLayer, HedgeService};
+
 mod retry;
 
 /// RetryBackoffLayer
 pub use retry::{RateLimitRetryPolicy, RetryBackoffLayer, RetryBackoffService, RetryPolicy};
+
+mod sticky;
+pub use sticky::{StickyLayer, StickyService};
@@ -1,15 +1,15 @@
 use crate::{
-    error::{RpcErrorExt, TransportError, TransportErrorKind},
+    error::{RetryHints, RpcErrorExt, TransportError, TransportErrorKind},
     TransportFut,
 };
-use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_json_rpc::{RequestPacket, RequestPriority, ResponsePacket};
 use std::{
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc,
     },
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tower::{Layer, Service};
 use tracing::trace;
@@ -53,6 +53,16 @@ pub trait RetryPolicy: Send + Sync + std::fmt::Debug {
 
     /// Providers may include the `backoff` in the error response directly
     fn backoff_hint(&self, error: &TransportError) -> Option<std::time::Duration>;
+
+    /// Classifies the `error` for applications that manage their own retries.
+    fn retry_hints(&self, error: &TransportError) -> RetryHints {
+        RetryHints {
+            is_retryable: self.should_retry(error),
+            suggested_backoff: self.backoff_hint(error),
+            is_rate_limit: error.is_rate_limit(),
+            is_node_behind: error.is_node_behind(),
+        }
+    }
 }
 
 impl RetryPolicy for RateLimitRetryPolicy {
@@ -150,10 +160,8 @@ where
                 if should_retry {
                     rate_limit_retry_number += 1;
                     if rate_limit_retry_number > this.max_rate_limit_retries {
-                        return Err(TransportErrorKind::custom_str(&format!(
-                            "Max retries exceeded {}",
-                            err
-                        )));
+                        this.requests_enqueued.fetch_sub(1, Ordering::SeqCst);
+                        return Err(TransportErrorKind::retries_exhausted(err));
                     }
                     trace!(%err, "retrying request");
 
@@ -174,13 +182,20 @@ where
                     // (coming from forking mode) assuming here that storage request will be the
                     // driver for Rate limits we choose `17` as the average cost
                     // of any request
+                    //
+                    // high priority requests do not wait for the requests ahead of them
                     const AVG_COST: u64 = 17u64;
-                    let seconds_to_wait_for_compute_budget = compute_unit_offset_in_secs(
-                        AVG_COST,
-                        this.compute_units_per_second,
-                        current_queued_reqs,
-                        ahead_in_queue,
-                    );
+                    let seconds_to_wait_for_compute_budget =
+                        if request.priority() == RequestPriority::High {
+                            0
+                        } else {
+                            compute_unit_offset_in_secs(
+                                AVG_COST,
+                                this.compute_units_per_second,
+                                current_queued_reqs,
+                                ahead_in_queue,
+                            )
+                        };
                     let total_backoff = next_backoff
                         + std::time::Duration::from_secs(seconds_to_wait_for_compute_budget);
 
@@ -192,6 +207,15 @@ where
                         "(all in ms) backing off due to rate limit"
                     );
 
+                    // the response would arrive too late to be useful
+                    if request.deadline().is_some_and(|deadline| {
+                        Instant::now().checked_add(total_backoff).map_or(true, |t| t > deadline)
+                    }) {
+                        trace!("backoff exceeds the request deadline, not retrying");
+                        this.requests_enqueued.fetch_sub(1, Ordering::SeqCst);
+                        return Err(err);
+                    }
+
                     tokio::time::sleep(total_backoff).await;
                 } else {
                     this.requests_enqueued.fetch_sub(1, Ordering::SeqCst);
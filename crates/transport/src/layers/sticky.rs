@@ -0,0 +1,392 @@
+use crate::{
+    error::{TransportError, TransportErrorKind},
+    TransportFut,
+};
+use alloy_json_rpc::{Id, RequestPacket, ResponsePacket, ResponsePayload};
+use serde_json::value::RawValue;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// Methods creating state on the endpoint, with the kind of ID they return.
+const CREATE_METHODS: &[(&str, StateKind)] = &[
+    ("eth_newFilter", StateKind::Filter),
+    ("eth_newBlockFilter", StateKind::Filter),
+    ("eth_newPendingTransactionFilter", StateKind::Filter),
+    ("evm_snapshot", StateKind::Snapshot),
+];
+
+/// Methods using the state of their first parameter, and whether they remove it.
+const USE_METHODS: &[(&str, StateKind, bool)] = &[
+    ("eth_getFilterChanges", StateKind::Filter, false),
+    ("eth_getFilterLogs", StateKind::Filter, false),
+    ("eth_uninstallFilter", StateKind::Filter, true),
+    ("evm_revert", StateKind::Snapshot, true),
+];
+
+/// A Transport Layer that balances requests over several transports, while sending the requests
+/// using state created on an endpoint to that endpoint, see [`StickyService`].
+///
+/// Requests are sent to the transports in turn, except:
+/// - `eth_getFilterChanges`, `eth_getFilterLogs` and `eth_uninstallFilter`, which are sent to the
+///   endpoint that returned the filter ID from `eth_newFilter`, `eth_newBlockFilter` or
+///   `eth_newPendingTransactionFilter`,
+/// - `evm_revert`, which is sent to the endpoint that returned the snapshot ID from `evm_snapshot`,
+/// - with [`with_correlation_affinity`](Self::with_correlation_affinity), requests with a
+///   [correlation ID](RequestPacket::correlation_id), which are sent to the endpoint of the first
+///   request with that ID.
+///
+/// Requests for unknown IDs, e.g. created before the service, are sent to the next transport.
+/// Requests for a known ID are not sent elsewhere when their endpoint fails, since the state
+/// only exists there.
+#[derive(Clone, Copy, Debug)]
+pub struct StickyLayer {
+    capacity: usize,
+    correlation_affinity: bool,
+}
+
+impl Default for StickyLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StickyLayer {
+    /// Creates a layer remembering the endpoints of up to 10000 IDs.
+    pub const fn new() -> Self {
+        Self { capacity: 10_000, correlation_affinity: false }
+    }
+
+    /// Sets the number of IDs whose endpoint is remembered, forgetting the oldest ones first.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Also sends the requests with the same correlation ID to the same endpoint, so that a
+    /// sequence of requests sees the state of a single node.
+    pub const fn with_correlation_affinity(mut self) -> Self {
+        self.correlation_affinity = true;
+        self
+    }
+}
+
+impl<S> Layer<Vec<S>> for StickyLayer {
+    type Service = StickyService<S>;
+
+    fn layer(&self, transports: Vec<S>) -> Self::Service {
+        StickyService {
+            transports,
+            next: Arc::new(AtomicUsize::new(0)),
+            affinity: Arc::new(Mutex::new(Affinity::new(self.capacity))),
+            correlation_affinity: self.correlation_affinity,
+        }
+    }
+}
+
+/// A Tower Service used by the [`StickyLayer`] that balances requests over several transports,
+/// keeping stateful requests on their endpoint.
+#[derive(Clone, Debug)]
+pub struct StickyService<S> {
+    transports: Vec<S>,
+    next: Arc<AtomicUsize>,
+    affinity: Arc<Mutex<Affinity>>,
+    correlation_affinity: bool,
+}
+
+impl<S> StickyService<S> {
+    /// Returns the transports requests are balanced over.
+    pub fn transports(&self) -> &[S] {
+        &self.transports
+    }
+
+    /// Returns the index of the transport that created the filter, if known.
+    pub fn filter_endpoint(&self, id: &str) -> Option<usize> {
+        self.affinity.lock().unwrap().get(&StateKind::Filter.key(id))
+    }
+
+    /// Returns the index of the transport the request is sent to, and the state the request
+    /// creates and removes.
+    fn route(&self, request: &RequestPacket) -> (usize, Vec<(Id, StateKind)>, Vec<Key>) {
+        let mut pinned = None;
+        let mut created = Vec::new();
+        let mut removed = Vec::new();
+        let mut affinity = self.affinity.lock().unwrap();
+
+        for req in request.requests() {
+            let method = req.method();
+            if let Some((_, kind)) = CREATE_METHODS.iter().find(|(m, _)| *m == method) {
+                created.push((req.id().clone(), *kind));
+            }
+            let Some((_, kind, removes)) = USE_METHODS.iter().find(|(m, ..)| *m == method) else {
+                continue;
+            };
+            let Some(key) = req.params().and_then(first_param).map(|id| kind.key(&id)) else {
+                continue;
+            };
+            if pinned.is_none() {
+                pinned = affinity.get(&key);
+            }
+            if *removes {
+                removed.push(key);
+            }
+        }
+
+        let session = request
+            .correlation_id()
+            .filter(|_| self.correlation_affinity)
+            .map(|id| Key::Session(id.to_string()));
+        if let Some(session) = &session {
+            pinned = pinned.or_else(|| affinity.get(session));
+        }
+
+        let index = pinned
+            .unwrap_or_else(|| self.next.fetch_add(1, Ordering::Relaxed) % self.transports.len());
+        // pinned before the response, so that the next requests of the session follow
+        if let Some(session) = session {
+            affinity.insert(session, index);
+        }
+        (index, created, removed)
+    }
+}
+
+impl<S> Service<RequestPacket> for StickyService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Send
+        + 'static
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        for transport in &mut self.transports {
+            match transport.poll_ready(cx) {
+                Poll::Ready(Ok(())) => {}
+                poll => return poll,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        if self.transports.is_empty() {
+            return Box::pin(async { Err(TransportErrorKind::custom_str("no transports")) });
+        }
+
+        let (index, created, removed) = self.route(&request);
+        trace!(index, "routing request");
+        let mut transport = self.transports[index].clone();
+        let affinity = self.affinity.clone();
+
+        Box::pin(async move {
+            let response = transport.call(request).await?;
+            if created.is_empty() && removed.is_empty() {
+                return Ok(response);
+            }
+
+            let mut affinity = affinity.lock().unwrap();
+            for response in responses(&response) {
+                let ResponsePayload::Success(result) = &response.payload else { continue };
+                if let Some((_, kind)) = created.iter().find(|(id, _)| *id == response.id) {
+                    if let Some(id) = state_id(result) {
+                        affinity.insert(kind.key(&id), index);
+                    }
+                }
+            }
+            // state is only removed if the request did not fail
+            if response.is_success() {
+                for key in &removed {
+                    affinity.remove(key);
+                }
+            }
+            drop(affinity);
+            Ok(response)
+        })
+    }
+}
+
+fn responses(response: &ResponsePacket) -> &[alloy_json_rpc::Response] {
+    match response {
+        ResponsePacket::Single(single) => std::slice::from_ref(single),
+        ResponsePacket::Batch(batch) => batch,
+    }
+}
+
+/// Returns the ID in the first parameter of a request.
+fn first_param(params: &RawValue) -> Option<String> {
+    let params: Vec<serde_json::Value> = serde_json::from_str(params.get()).ok()?;
+    params.first().and_then(normalize_id)
+}
+
+/// Returns the ID returned by a request.
+fn state_id(result: &RawValue) -> Option<String> {
+    normalize_id(&serde_json::from_str(result.get()).ok()?)
+}
+
+/// Normalizes a quantity ID, so that it matches regardless of zero padding and case.
+fn normalize_id(value: &serde_json::Value) -> Option<String> {
+    let id = match value {
+        serde_json::Value::String(id) => id.to_ascii_lowercase(),
+        serde_json::Value::Number(id) => return Some(id.to_string()),
+        _ => return None,
+    };
+    let Some(hex) = id.strip_prefix("0x") else { return Some(id) };
+    let hex = hex.trim_start_matches('0');
+    Some(format!("0x{}", if hex.is_empty() { "0" } else { hex }))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StateKind {
+    Filter,
+    Snapshot,
+}
+
+impl StateKind {
+    fn key(self, id: &str) -> Key {
+        let id = normalize_id(&serde_json::Value::String(id.to_string())).unwrap_or_default();
+        match self {
+            Self::Filter => Key::Filter(id),
+            Self::Snapshot => Key::Snapshot(id),
+        }
+    }
+}
+
+/// State created on an endpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Key {
+    Filter(String),
+    Snapshot(String),
+    Session(String),
+}
+
+/// The endpoints of the most recently created state.
+#[derive(Debug)]
+struct Affinity {
+    endpoints: HashMap<Key, usize>,
+    order: VecDeque<Key>,
+    capacity: usize,
+}
+
+impl Affinity {
+    fn new(capacity: usize) -> Self {
+        Self { endpoints: HashMap::new(), order: VecDeque::new(), capacity }
+    }
+
+    fn get(&self, key: &Key) -> Option<usize> {
+        self.endpoints.get(key).copied()
+    }
+
+    fn insert(&mut self, key: Key, index: usize) {
+        if self.endpoints.insert(key.clone(), index).is_some() {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.endpoints.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &Key) {
+        if self.endpoints.remove(key).is_some() {
+            self.order.retain(|k| k != key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Request, Response};
+    use serde_json::value::to_raw_value;
+
+    /// A transport answering filter requests with its index.
+    fn endpoint(
+        index: usize,
+        calls: Arc<Mutex<Vec<(usize, String)>>>,
+    ) -> impl Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    > + Clone {
+        tower::service_fn(move |request: RequestPacket| -> TransportFut<'static> {
+            let RequestPacket::Single(request) = request else { unreachable!() };
+            calls.lock().unwrap().push((index, request.method().to_string()));
+            let result = match request.method() {
+                "eth_newFilter" => to_raw_value(&format!("0x0{index}a")).unwrap(),
+                _ => to_raw_value(&index).unwrap(),
+            };
+            let response =
+                Response { id: request.id().clone(), payload: ResponsePayload::Success(result) };
+            Box::pin(async move { Ok(ResponsePacket::Single(response)) })
+        })
+    }
+
+    fn request(method: &'static str, params: serde_json::Value) -> RequestPacket {
+        Request::new(method, Id::Number(1), params).serialize().unwrap().into()
+    }
+
+    #[tokio::test]
+    async fn routes_filters_to_their_endpoint() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut service = StickyLayer::new()
+            .layer((0..3).map(|index| endpoint(index, calls.clone())).collect::<Vec<_>>());
+
+        service.call(request("eth_blockNumber", serde_json::json!([]))).await.unwrap();
+        service.call(request("eth_newFilter", serde_json::json!([{}]))).await.unwrap();
+        assert_eq!(service.filter_endpoint("0x1A"), Some(1));
+
+        for _ in 0..3 {
+            service
+                .call(request("eth_getFilterChanges", serde_json::json!(["0x1a"])))
+                .await
+                .unwrap();
+        }
+        service.call(request("eth_uninstallFilter", serde_json::json!(["0x01a"]))).await.unwrap();
+        assert_eq!(service.filter_endpoint("0x1a"), None);
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.iter().map(|(index, _)| *index).collect::<Vec<_>>(), [0, 1, 1, 1, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn pins_sessions() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut service = StickyLayer::new()
+            .with_correlation_affinity()
+            .layer((0..2).map(|index| endpoint(index, calls.clone())).collect::<Vec<_>>());
+
+        for _ in 0..3 {
+            let mut req = Request::new("eth_call", Id::Number(1), ());
+            req.meta.set_correlation_id(Some("session".into()));
+            service.call(req.serialize().unwrap().into()).await.unwrap();
+            service.call(request("eth_chainId", serde_json::json!([]))).await.unwrap();
+        }
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.iter().map(|(index, _)| *index).collect::<Vec<_>>(), [0, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn evicts_oldest_entries() {
+        let mut affinity = Affinity::new(2);
+        for (i, id) in ["0x1", "0x2", "0x3"].into_iter().enumerate() {
+            affinity.insert(StateKind::Snapshot.key(id), i);
+        }
+        assert_eq!(affinity.get(&StateKind::Snapshot.key("0x1")), None);
+        assert_eq!(affinity.get(&StateKind::Snapshot.key("0x3")), Some(2));
+        assert_eq!(affinity.get(&StateKind::Filter.key("0x3")), None);
+    }
+}
@@ -18,7 +18,10 @@ pub use common::Authorization;
 mod error;
 #[doc(hidden)]
 pub use error::TransportErrorKind;
-pub use error::{HttpError, TransportError, TransportResult};
+pub use error::{HttpError, RetryHints, RpcErrorExt, TransportError, TransportResult};
+
+mod limits;
+pub use limits::{ResponseLimitError, ResponseLimits};
 
 mod r#trait;
 pub use r#trait::Transport;
@@ -28,6 +31,9 @@ pub use futures_utils_wasm::{impl_future, BoxFuture};
 
 pub mod layers;
 
+pub mod resolve;
+pub use resolve::{Resolve, Resolver};
+
 /// Misc. utilities for building transports.
 pub mod utils;
 
@@ -0,0 +1,205 @@
+/// Limits on the responses accepted from an endpoint.
+///
+/// Untrusted endpoints can answer with arbitrarily large, or deeply nested, responses that are
+/// expensive to buffer and deserialize. Transports enforce these limits before a response is
+/// parsed, and reject it with a [`ResponseLimitError`] otherwise. No limits are set by default.
+///
+/// The size is enforced while the response is read where the transport allows it, so that
+/// oversized responses are never buffered completely. The nesting depth and array lengths are
+/// checked with a single pass over the raw response, before it is deserialized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ResponseLimits {
+    max_size: Option<usize>,
+    max_depth: Option<usize>,
+    max_array_len: Option<usize>,
+}
+
+impl ResponseLimits {
+    /// Creates limits accepting any response.
+    pub const fn new() -> Self {
+        Self { max_size: None, max_depth: None, max_array_len: None }
+    }
+
+    /// Sets the maximum size of a response, in bytes.
+    pub const fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Sets the maximum nesting depth of the arrays and objects of a response.
+    pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the maximum number of elements of any array of a response.
+    pub const fn with_max_array_len(mut self, max_array_len: usize) -> Self {
+        self.max_array_len = Some(max_array_len);
+        self
+    }
+
+    /// Returns the maximum size of a response, in bytes.
+    pub const fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
+
+    /// Returns the maximum nesting depth of a response.
+    pub const fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Returns the maximum number of elements of any array of a response.
+    pub const fn max_array_len(&self) -> Option<usize> {
+        self.max_array_len
+    }
+
+    /// Returns `true` if no limits are set.
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_size.is_none() && self.max_depth.is_none() && self.max_array_len.is_none()
+    }
+
+    /// Checks the size of a response, e.g. its announced `Content-Length` or the number of bytes
+    /// read so far.
+    pub const fn check_size(&self, size: usize) -> Result<(), ResponseLimitError> {
+        match self.max_size {
+            Some(max) if size > max => Err(ResponseLimitError::TooLarge { size, max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks a raw JSON response against all limits.
+    ///
+    /// The response is not validated, malformed JSON is left to the deserializer.
+    pub fn check(&self, response: &[u8]) -> Result<(), ResponseLimitError> {
+        self.check_size(response.len())?;
+        if self.max_depth.is_none() && self.max_array_len.is_none() {
+            return Ok(());
+        }
+
+        // the number of elements of each open array, `None` for objects
+        let mut open: Vec<Option<usize>> = Vec::new();
+        let mut in_string = false;
+        let mut escaped = false;
+        for &byte in response {
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+
+            // the first element of an array
+            if let Some(Some(len @ 0)) = open.last_mut() {
+                if !byte.is_ascii_whitespace() && byte != b']' {
+                    *len = 1;
+                    self.check_array_len(1)?;
+                }
+            }
+
+            match byte {
+                b'"' => in_string = true,
+                b'[' | b'{' => {
+                    open.push((byte == b'[').then_some(0));
+                    if let Some(max) = self.max_depth {
+                        if open.len() > max {
+                            return Err(ResponseLimitError::TooDeep { max });
+                        }
+                    }
+                }
+                b']' | b'}' => {
+                    open.pop();
+                }
+                b',' => {
+                    if let Some(Some(len)) = open.last_mut() {
+                        *len += 1;
+                        self.check_array_len(*len)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    const fn check_array_len(&self, len: usize) -> Result<(), ResponseLimitError> {
+        match self.max_array_len {
+            Some(max) if len > max => Err(ResponseLimitError::ArrayTooLong { max }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A response exceeded the [`ResponseLimits`] of the transport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ResponseLimitError {
+    /// The response is larger than the maximum size.
+    #[error("response of {size} bytes exceeds the maximum size of {max} bytes")]
+    TooLarge {
+        /// The size of the response, or the number of bytes read before it was rejected.
+        size: usize,
+        /// The maximum size.
+        max: usize,
+    },
+    /// The arrays and objects of the response are nested deeper than the maximum depth.
+    #[error("response exceeds the maximum nesting depth of {max}")]
+    TooDeep {
+        /// The maximum depth.
+        max: usize,
+    },
+    /// An array of the response has more elements than the maximum length.
+    #[error("response contains an array longer than the maximum of {max} elements")]
+    ArrayTooLong {
+        /// The maximum number of elements.
+        max: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_size() {
+        let limits = ResponseLimits::new().with_max_size(10);
+        assert_eq!(limits.check(b"[1,2,3,4]"), Ok(()));
+        assert_eq!(
+            limits.check(b"[1,2,3,4,5]"),
+            Err(ResponseLimitError::TooLarge { size: 11, max: 10 })
+        );
+        assert!(ResponseLimits::new().check(&[b'['; 1000]).is_ok());
+    }
+
+    #[test]
+    fn checks_depth() {
+        let limits = ResponseLimits::new().with_max_depth(3);
+        assert_eq!(limits.check(br#"{"a":[{"b":1}],"c":[[]]}"#), Ok(()));
+        // brackets in strings are ignored, as are escaped quotes
+        assert_eq!(limits.check(br#"{"a":"[[[\"[[[","b":[]}"#), Ok(()));
+        assert_eq!(
+            limits.check(br#"{"a":[{"b":[1]}]}"#),
+            Err(ResponseLimitError::TooDeep { max: 3 })
+        );
+    }
+
+    #[test]
+    fn checks_array_len() {
+        let limits = ResponseLimits::new().with_max_array_len(2);
+        assert_eq!(limits.check(br#"[ ] "#), Ok(()));
+        assert_eq!(
+            limits.check(br#"[{"a":1,"b":2,"c":3},[1, 2],"x,y,z"]"#),
+            Err(ResponseLimitError::ArrayTooLong { max: 2 })
+        );
+        assert_eq!(limits.check(br#"[{"a":1,"b":2,"c":3},"x,y,z"]"#), Ok(()));
+        assert_eq!(
+            limits.check(b"[[1,2],[3,4,5]]"),
+            Err(ResponseLimitError::ArrayTooLong { max: 2 })
+        );
+
+        let limits = ResponseLimits::new().with_max_array_len(0);
+        assert_eq!(limits.check(b"{\"a\":[ ]}"), Ok(()));
+        assert_eq!(limits.check(b"[0]"), Err(ResponseLimitError::ArrayTooLong { max: 0 }));
+    }
+}
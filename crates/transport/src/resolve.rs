@@ -0,0 +1,262 @@
+//! Custom resolution of the hostnames of endpoints.
+//!
+//! The HTTP and WS transports resolve hostnames with the system resolver by default. A
+//! [`Resolver`] set on their connection settings replaces it, e.g. to pin endpoints to known
+//! addresses with a [`StaticResolver`], or to restrict the addresses an endpoint may resolve to
+//! with an [`AllowlistResolver`].
+//!
+//! URLs with an IP address instead of a hostname are connected to directly, without a resolver.
+
+use crate::{Pbf, TransportErrorKind, TransportResult};
+pub use ipnet::IpNet;
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    net::IpAddr,
+    sync::Arc,
+};
+
+/// Resolves the hostname of an endpoint to IP addresses.
+///
+/// Implementations return the addresses in order of preference, and may return an error or no
+/// addresses for hosts they do not resolve.
+pub trait Resolve: fmt::Debug + Send + Sync + 'static {
+    /// Resolves the hostname.
+    fn resolve<'a>(&'a self, host: &'a str) -> Pbf<'a, Vec<IpAddr>, crate::TransportError>;
+}
+
+/// A shared [`Resolve`] implementation, set on the connection settings of the transports.
+///
+/// Resolvers compare and hash by identity, so that the settings holding them keep their own
+/// `Eq` and `Hash` implementations.
+#[derive(Clone)]
+pub struct Resolver(Arc<dyn Resolve>);
+
+impl Resolver {
+    /// Creates a resolver from an implementation.
+    pub fn new(resolver: impl Resolve) -> Self {
+        Self(Arc::new(resolver))
+    }
+
+    /// Creates a resolver using the system resolver.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn system() -> Self {
+        Self::new(SystemResolver)
+    }
+
+    /// Resolves the hostname, failing if it resolves to no addresses.
+    pub async fn resolve(&self, host: &str) -> TransportResult<Vec<IpAddr>> {
+        let addrs = self.0.resolve(host).await?;
+        if addrs.is_empty() {
+            return Err(TransportErrorKind::custom_str(&format!("no addresses for host {host}")));
+        }
+        Ok(addrs)
+    }
+
+    fn addr(&self) -> *const () {
+        Arc::as_ptr(&self.0).cast()
+    }
+}
+
+impl fmt::Debug for Resolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for Resolver {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl Eq for Resolver {}
+
+impl Hash for Resolver {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state);
+    }
+}
+
+impl<R: Resolve> From<R> for Resolver {
+    fn from(resolver: R) -> Self {
+        Self::new(resolver)
+    }
+}
+
+/// Resolves hostnames with the system resolver, `getaddrinfo` on most platforms.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemResolver;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Resolve for SystemResolver {
+    fn resolve<'a>(&'a self, host: &'a str) -> Pbf<'a, Vec<IpAddr>, crate::TransportError> {
+        use std::net::ToSocketAddrs;
+
+        let host = host.to_string();
+        Box::pin(async move {
+            let addrs = tokio::task::spawn_blocking(move || (host.as_str(), 0).to_socket_addrs())
+                .await
+                .map_err(TransportErrorKind::custom)?
+                .map_err(TransportErrorKind::custom)?;
+            Ok(addrs.map(|addr| addr.ip()).collect())
+        })
+    }
+}
+
+/// Resolves hostnames to fixed addresses, pinning endpoints regardless of DNS.
+///
+/// Other hostnames are passed to the [fallback](Self::with_fallback), or fail to resolve
+/// without one.
+#[derive(Clone, Debug, Default)]
+pub struct StaticResolver {
+    hosts: HashMap<String, Vec<IpAddr>>,
+    fallback: Option<Resolver>,
+}
+
+impl StaticResolver {
+    /// Creates a resolver without hosts or fallback.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves the hostname to the addresses.
+    pub fn with_host(
+        mut self,
+        host: impl Into<String>,
+        addrs: impl IntoIterator<Item = IpAddr>,
+    ) -> Self {
+        self.hosts.insert(host.into().to_ascii_lowercase(), addrs.into_iter().collect());
+        self
+    }
+
+    /// Resolves other hostnames with the resolver.
+    pub fn with_fallback(mut self, fallback: impl Into<Resolver>) -> Self {
+        self.fallback = Some(fallback.into());
+        self
+    }
+}
+
+impl Resolve for StaticResolver {
+    fn resolve<'a>(&'a self, host: &'a str) -> Pbf<'a, Vec<IpAddr>, crate::TransportError> {
+        Box::pin(async move {
+            if let Some(addrs) = self.hosts.get(&host.to_ascii_lowercase()) {
+                return Ok(addrs.clone());
+            }
+            match &self.fallback {
+                Some(fallback) => fallback.resolve(host).await,
+                None => Err(TransportErrorKind::custom_str(&format!("unknown host {host}"))),
+            }
+        })
+    }
+}
+
+/// Restricts the addresses hostnames resolve to, dropping the others.
+///
+/// Each hostname is checked against its own networks if it has some, and against the default
+/// networks otherwise. Hostnames without networks are resolved unrestricted unless
+/// [`deny_unlisted`](Self::deny_unlisted) is set. A hostname whose addresses are all dropped
+/// fails to resolve, so no connection is made to an address that is not allowed.
+#[derive(Clone, Debug)]
+pub struct AllowlistResolver {
+    inner: Resolver,
+    hosts: HashMap<String, Vec<IpNet>>,
+    default: Option<Vec<IpNet>>,
+}
+
+impl AllowlistResolver {
+    /// Creates a resolver restricting the addresses returned by `inner`.
+    pub fn new(inner: impl Into<Resolver>) -> Self {
+        Self { inner: inner.into(), hosts: HashMap::new(), default: None }
+    }
+
+    /// Only allows the networks for the hostname. Single addresses are networks with a full
+    /// prefix, e.g. `"10.0.0.1/32".parse()`.
+    pub fn with_host(
+        mut self,
+        host: impl Into<String>,
+        nets: impl IntoIterator<Item = IpNet>,
+    ) -> Self {
+        self.hosts.entry(host.into().to_ascii_lowercase()).or_default().extend(nets);
+        self
+    }
+
+    /// Only allows the networks for the hostnames without their own networks.
+    pub fn with_default(mut self, nets: impl IntoIterator<Item = IpNet>) -> Self {
+        self.default.get_or_insert_with(Vec::new).extend(nets);
+        self
+    }
+
+    /// Fails to resolve the hostnames without their own networks, unless default networks are
+    /// set.
+    pub fn deny_unlisted(mut self) -> Self {
+        self.default.get_or_insert_with(Vec::new);
+        self
+    }
+
+    /// Returns whether the address is allowed for the hostname.
+    pub fn is_allowed(&self, host: &str, addr: IpAddr) -> bool {
+        let Some(nets) = self.hosts.get(&host.to_ascii_lowercase()).or(self.default.as_ref())
+        else {
+            return true;
+        };
+        nets.iter().any(|net| net.contains(&addr))
+    }
+}
+
+impl Resolve for AllowlistResolver {
+    fn resolve<'a>(&'a self, host: &'a str) -> Pbf<'a, Vec<IpAddr>, crate::TransportError> {
+        Box::pin(async move {
+            let addrs = self.inner.resolve(host).await?;
+            let allowed = addrs
+                .iter()
+                .copied()
+                .filter(|addr| self.is_allowed(host, *addr))
+                .collect::<Vec<_>>();
+            if allowed.is_empty() {
+                return Err(TransportErrorKind::custom_str(&format!(
+                    "no allowed addresses for host {host}, resolved to {addrs:?}"
+                )));
+            }
+            Ok(allowed)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn resolves_static_hosts_within_allowlist() {
+        let pinned = StaticResolver::new()
+            .with_host(
+                "rpc.example.com",
+                ["10.0.0.1".parse().unwrap(), "192.0.2.1".parse().unwrap()],
+            )
+            .with_host("other.example.com", ["192.0.2.2".parse().unwrap()]);
+        assert_eq!(pinned.resolve("RPC.example.com").await.unwrap().len(), 2);
+        assert!(pinned.resolve("unknown.example.com").await.is_err());
+
+        let resolver = Resolver::new(
+            AllowlistResolver::new(pinned)
+                .with_host("rpc.example.com", ["10.0.0.0/8".parse().unwrap()]),
+        );
+        assert_eq!(
+            resolver.resolve("rpc.example.com").await.unwrap(),
+            ["10.0.0.1".parse::<IpAddr>().unwrap()]
+        );
+        assert_eq!(resolver.resolve("other.example.com").await.unwrap().len(), 1);
+        assert_eq!(resolver, resolver.clone());
+
+        let strict = AllowlistResolver::new(
+            StaticResolver::new().with_host("rpc.example.com", ["192.0.2.1".parse().unwrap()]),
+        )
+        .with_host("rpc.example.com", ["10.0.0.0/8".parse().unwrap()])
+        .deny_unlisted();
+        assert!(strict.resolve("rpc.example.com").await.is_err());
+        assert!(!strict.is_allowed("other.example.com", "10.0.0.1".parse().unwrap()));
+    }
+}
@@ -12,6 +12,13 @@ crates=(
     alloy-rpc-types-engine
 )
 
+# Optional features that must build without `std`, e.g. `kzg-sidecar` for the KZG-free blob types.
+features=(
+    "alloy-eips serde,kzg-sidecar,k256"
+    "alloy-consensus serde,k256"
+    "alloy-consensus zkvm,native-sha256"
+)
+
 cmd=(cargo +stable hack check --no-default-features --target "$target")
 for crate in "${crates[@]}"; do
     cmd+=(-p "$crate")
@@ -19,3 +26,10 @@ done
 
 echo "Running: ${cmd[*]}"
 "${cmd[@]}"
+
+for entry in "${features[@]}"; do
+    read -r crate crate_features <<<"$entry"
+    cmd=(cargo +stable check --no-default-features --target "$target" -p "$crate" --features "$crate_features")
+    echo "Running: ${cmd[*]}"
+    "${cmd[@]}"
+done